@@ -1,6 +1,8 @@
 package api
 
 import (
+	"time"
+
 	"github.com/BlueBeard63/archon/internal/models"
 	"github.com/google/uuid"
 )
@@ -11,7 +13,7 @@ type NodeClient interface {
 	// Site deployment operations
 	DeploySite(endpoint, apiKey string, site *models.Site, domainName string) error
 	DeleteSite(endpoint, apiKey string, siteID uuid.UUID, domain, siteName string, siteType models.SiteType) error
-	GetSiteStatus(endpoint, apiKey string, siteID uuid.UUID, siteName string, siteType models.SiteType) (*models.SiteStatus, error)
+	GetSiteStatus(endpoint, apiKey string, siteID uuid.UUID, siteName string, siteType models.SiteType) (*SiteStatusResponse, error)
 	StopSite(endpoint, apiKey string, siteID uuid.UUID, siteName string, siteType models.SiteType) error
 	RestartSite(endpoint, apiKey string, siteID uuid.UUID) error
 
@@ -21,30 +23,44 @@ type NodeClient interface {
 	GetTraefikInfo(endpoint, apiKey string) (*models.TraefikInfo, error)
 
 	// Container monitoring
-	GetContainerLogs(endpoint, apiKey string, siteID uuid.UUID, lines int) ([]string, error)
+	GetContainerLogs(endpoint, apiKey string, siteID uuid.UUID, lines int, since string) (*ContainerLogs, error)
+	GetBuildLogs(endpoint, apiKey string, siteID uuid.UUID) ([]string, error)
 	GetContainerMetrics(endpoint, apiKey string, siteID uuid.UUID) (*ContainerMetrics, error)
 }
 
 // DeployRequest is the payload sent to nodes to deploy a site
 type DeployRequest struct {
+	ID              uuid.UUID           `json:"id"`
 	Name            string              `json:"name"`
-	Domain          string              `json:"domain"`
+	SiteType        models.SiteType     `json:"site_type"`
 	Docker          Docker              `json:"docker"`
+	ComposeContent  string              `json:"compose_content,omitempty"`
 	EnvironmentVars map[string]string   `json:"environment_vars"`
-	Port            int                 `json:"port"`
+	DomainMappings  []DomainMapping     `json:"domain_mappings"`
 	SSLEnabled      bool                `json:"ssl_enabled"`
+	SSLEmail        string              `json:"ssl_email,omitempty"`
 	ConfigFiles     []models.ConfigFile `json:"config_files"`
-	TraefikLabels   map[string]string   `json:"traefik_labels"` // Generated by Site.GenerateTraefikLabels()
+	TraefikLabels   map[string]string   `json:"traefik_labels,omitempty"`  // Generated by Site.GenerateTraefikLabels()
+	CPULimit        float64             `json:"cpu_limit,omitempty"`       // Omitted when unlimited so older nodes ignore it
+	MemoryLimitMB   int                 `json:"memory_limit_mb,omitempty"` // Omitted when unlimited so older nodes ignore it
+
+	Volumes []models.VolumeMount `json:"volumes,omitempty"` // Persistent mounts for the container
+
+	Strategy     models.DeploymentStrategy `json:"strategy,omitempty"`      // Empty = recreate; nodes without blue/green support always recreate
+	HealthPath   string                    `json:"health_path,omitempty"`   // Probed on a blue/green deploy's new container before traffic is switched to it
+	HealthStatus int                       `json:"health_status,omitempty"` // Status the health path should return (0 = 200)
 }
 
 type Docker struct {
 	Credentials DockerCredentials `json:"credentials"`
 	Image       string            `json:"image"`
+	Build       *models.BuildSpec `json:"build,omitempty"` // Set instead of Image to build on the node
 }
 
 type DockerCredentials struct {
 	Username string `json:"username"`
 	Password string `json:"password"`
+	Registry string `json:"registry,omitempty"` // Registry server address; empty means Docker Hub
 }
 
 // HealthResponse contains node health information
@@ -54,6 +70,12 @@ type HealthResponse struct {
 	Traefik *models.TraefikInfo `json:"traefik"`
 }
 
+// ContainerLogs contains log lines fetched from a site's container
+type ContainerLogs struct {
+	Lines  []string `json:"logs"`
+	Cursor string   `json:"cursor"` // Pass as since to fetch only later lines; empty from nodes that don't support it
+}
+
 // ContainerMetrics contains resource usage for a deployed site
 type ContainerMetrics struct {
 	CPUPercent     float64 `json:"cpu_percent"`
@@ -63,6 +85,14 @@ type ContainerMetrics struct {
 	NetworkTxBytes int64   `json:"network_tx_bytes"`
 }
 
+// SiteStatusResponse is a site's status as reported by its node
+type SiteStatusResponse struct {
+	Status       models.SiteStatus `json:"status"`
+	Message      string            `json:"message,omitempty"`
+	RestartCount int               `json:"restart_count"`        // Times Docker has restarted the container
+	StartedAt    *time.Time        `json:"started_at,omitempty"` // When the container last started, while it is running
+}
+
 // DeployResponse is returned after successfully deploying a site
 type DeployResponse struct {
 	SiteID      uuid.UUID `json:"site_id"`
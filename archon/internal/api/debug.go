@@ -0,0 +1,156 @@
+package api
+
+import (
+	"bytes"
+	"encoding/json"
+	"io"
+	"log"
+	"net/http"
+	"strings"
+	"time"
+
+	"github.com/BlueBeard63/archon/internal/models"
+)
+
+// maxLoggedBodyBytes limits how much of each request/response body is written to the debug log
+const maxLoggedBodyBytes = 2048
+
+// redacted replaces secret values in the debug log
+const redacted = "[REDACTED]"
+
+// secretFields are JSON keys whose values are always redacted
+var secretFields = map[string]bool{
+	"api_key":  true,
+	"password": true,
+	"ssl_key":  true,
+	"token":    true,
+}
+
+// debugTransport logs every request and response passing through the node client
+type debugTransport struct {
+	next   http.RoundTripper
+	logger *log.Logger
+}
+
+// EnableDebugLogging logs node client requests and responses to w, with secrets redacted
+func (c *HTTPNodeClient) EnableDebugLogging(w io.Writer) {
+	next := c.client.Transport
+	if next == nil {
+		next = http.DefaultTransport
+	}
+	c.debugLog = log.New(w, "[node-http] ", log.LstdFlags)
+	c.client.Transport = &debugTransport{next: next, logger: c.debugLog}
+}
+
+// RoundTrip logs the request, forwards it, then logs the response
+func (t *debugTransport) RoundTrip(req *http.Request) (*http.Response, error) {
+	// Read and restore the request body so it can still be sent
+	var reqBody []byte
+	if req.Body != nil {
+		reqBody, _ = io.ReadAll(req.Body)
+		req.Body.Close()
+		req.Body = io.NopCloser(bytes.NewReader(reqBody))
+	}
+
+	t.logger.Printf("--> %s %s headers=%s body=%s", req.Method, req.URL, redactHeaders(req.Header), redactBody(reqBody))
+
+	start := time.Now()
+	resp, err := t.next.RoundTrip(req)
+	elapsed := time.Since(start).Round(time.Millisecond)
+	if err != nil {
+		t.logger.Printf("<-- %s %s error=%v (%s)", req.Method, req.URL, err, elapsed)
+		return nil, err
+	}
+
+	// Read and restore the response body so callers can still decode it
+	respBody, _ := io.ReadAll(resp.Body)
+	resp.Body.Close()
+	resp.Body = io.NopCloser(bytes.NewReader(respBody))
+
+	t.logger.Printf("<-- %s %s status=%d (%s) body=%s", req.Method, req.URL, resp.StatusCode, elapsed, redactBody(respBody))
+	return resp, nil
+}
+
+// logDebugMessage logs a message sent outside the HTTP transport (e.g., over WebSocket)
+func (c *HTTPNodeClient) logDebugMessage(direction, url string, payload interface{}) {
+	if c.debugLog == nil {
+		return
+	}
+	data, _ := json.Marshal(payload)
+	c.debugLog.Printf("%s WS %s body=%s", direction, url, redactBody(data))
+}
+
+// FormatDeployRequest returns the request as indented JSON, with secrets redacted as in the debug log
+func FormatDeployRequest(req DeployRequest) (string, error) {
+	data, err := json.Marshal(req)
+	if err != nil {
+		return "", err
+	}
+	var parsed interface{}
+	if err := json.Unmarshal(data, &parsed); err != nil {
+		return "", err
+	}
+	formatted, err := json.MarshalIndent(redactValue("", parsed), "", "  ")
+	if err != nil {
+		return "", err
+	}
+	return string(formatted), nil
+}
+
+// redactHeaders formats headers with the bearer token hidden
+func redactHeaders(headers http.Header) string {
+	var parts []string
+	for name, values := range headers {
+		value := strings.Join(values, ",")
+		if strings.EqualFold(name, "Authorization") {
+			value = "Bearer " + redacted
+		}
+		parts = append(parts, name+": "+value)
+	}
+	return "{" + strings.Join(parts, "; ") + "}"
+}
+
+// redactBody hides secret fields in a JSON body and truncates it for logging
+// Bodies that are not JSON are only truncated
+func redactBody(body []byte) string {
+	if len(body) == 0 {
+		return "(empty)"
+	}
+
+	var parsed interface{}
+	if err := json.Unmarshal(body, &parsed); err == nil {
+		if redactedBody, err := json.Marshal(redactValue("", parsed)); err == nil {
+			body = redactedBody
+		}
+	}
+
+	if len(body) > maxLoggedBodyBytes {
+		return string(body[:maxLoggedBodyBytes]) + "...(truncated)"
+	}
+	return string(body)
+}
+
+// redactValue walks decoded JSON, replacing secret fields and secret-looking environment variables
+func redactValue(key string, value interface{}) interface{} {
+	switch v := value.(type) {
+	case map[string]interface{}:
+		for k, inner := range v {
+			switch {
+			case secretFields[strings.ToLower(k)]:
+				v[k] = redacted
+			case key == "environment_vars" && models.IsSecretEnvVar(k):
+				v[k] = redacted
+			default:
+				v[k] = redactValue(k, inner)
+			}
+		}
+		return v
+	case []interface{}:
+		for i, inner := range v {
+			v[i] = redactValue(key, inner)
+		}
+		return v
+	default:
+		return v
+	}
+}
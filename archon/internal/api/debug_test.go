@@ -0,0 +1,34 @@
+package api
+
+import (
+	"strings"
+	"testing"
+)
+
+func TestFormatDeployRequest(t *testing.T) {
+	req := DeployRequest{
+		Name: "app",
+		Docker: Docker{
+			Image:       "ghcr.io/acme/app:1.0",
+			Credentials: DockerCredentials{Username: "acme", Password: "hunter2", Registry: "ghcr.io"},
+		},
+		EnvironmentVars: map[string]string{"DB_PASSWORD": "s3cret", "LOG_LEVEL": "debug"},
+		TraefikLabels:   map[string]string{"traefik.enable": "true"},
+	}
+
+	out, err := FormatDeployRequest(req)
+	if err != nil {
+		t.Fatalf("FormatDeployRequest() error = %v", err)
+	}
+
+	for _, secret := range []string{"hunter2", "s3cret"} {
+		if strings.Contains(out, secret) {
+			t.Errorf("output contains secret %q:\n%s", secret, out)
+		}
+	}
+	for _, want := range []string{`"LOG_LEVEL": "debug"`, `"traefik.enable": "true"`, `"registry": "ghcr.io"`, "\n  \"name\": \"app\""} {
+		if !strings.Contains(out, want) {
+			t.Errorf("output missing %q:\n%s", want, out)
+		}
+	}
+}
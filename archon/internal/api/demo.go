@@ -0,0 +1,202 @@
+package api
+
+import (
+	"fmt"
+	"strconv"
+	"sync"
+
+	"github.com/BlueBeard63/archon/internal/models"
+	"github.com/google/uuid"
+)
+
+// DemoNodeClient is an in-memory NodeClient for demo mode. Every node is healthy, and deploying
+// a site records it as running, with generated logs and metrics. Responses depend only on the
+// calls made, never on the clock, so screens can be tested against it.
+type DemoNodeClient struct {
+	mu    sync.Mutex
+	sites map[uuid.UUID]*demoSite
+}
+
+// demoSite is a site deployed to a demo node
+type demoSite struct {
+	endpoint string
+	name     string
+	status   models.SiteStatus
+	logLines int // Log lines written so far, growing with each fetch
+	restarts int // Restarts since the site was deployed
+	polls    int // Metrics fetches so far, to vary the numbers
+}
+
+// demoRequestPaths are the paths in generated access logs
+var demoRequestPaths = []string{"/", "/about", "/api/items", "/static/app.css", "/healthz"}
+
+// NewDemoNodeClient creates a demo client with no sites deployed
+func NewDemoNodeClient() *DemoNodeClient {
+	return &DemoNodeClient{sites: make(map[uuid.UUID]*demoSite)}
+}
+
+// DeploySite records the site as running on the node, failing like a real node would if the
+// deploy request can't be built
+func (c *DemoNodeClient) DeploySite(endpoint, apiKey string, site *models.Site, domainName string) error {
+	if _, err := NewDeployRequest(site, domainName); err != nil {
+		return err
+	}
+
+	c.mu.Lock()
+	defer c.mu.Unlock()
+	c.sites[site.ID] = &demoSite{endpoint: endpoint, name: site.Name, status: models.SiteStatusRunning}
+	return nil
+}
+
+// DeleteSite forgets a deployed site
+func (c *DemoNodeClient) DeleteSite(endpoint, apiKey string, siteID uuid.UUID, domain, siteName string, siteType models.SiteType) error {
+	c.mu.Lock()
+	defer c.mu.Unlock()
+	delete(c.sites, siteID)
+	return nil
+}
+
+// GetSiteStatus returns a deployed site's status, or inactive for a site that isn't deployed.
+// Restarts count the site's restarts; there is no start time, which would depend on the clock.
+func (c *DemoNodeClient) GetSiteStatus(endpoint, apiKey string, siteID uuid.UUID, siteName string, siteType models.SiteType) (*SiteStatusResponse, error) {
+	c.mu.Lock()
+	defer c.mu.Unlock()
+	site, ok := c.sites[siteID]
+	if !ok {
+		return &SiteStatusResponse{Status: models.SiteStatusInactive, Message: "Container not found"}, nil
+	}
+	return &SiteStatusResponse{Status: site.status, RestartCount: site.restarts}, nil
+}
+
+// StopSite marks a deployed site as stopped
+func (c *DemoNodeClient) StopSite(endpoint, apiKey string, siteID uuid.UUID, siteName string, siteType models.SiteType) error {
+	return c.setStatus(siteID, models.SiteStatusStopped)
+}
+
+// RestartSite marks a deployed site as running, counting the restart
+func (c *DemoNodeClient) RestartSite(endpoint, apiKey string, siteID uuid.UUID) error {
+	c.mu.Lock()
+	defer c.mu.Unlock()
+	site, ok := c.sites[siteID]
+	if !ok {
+		return fmt.Errorf("API error (404): site %s is not deployed on this node", siteID)
+	}
+	site.status = models.SiteStatusRunning
+	site.restarts++
+	return nil
+}
+
+// setStatus changes a deployed site's status
+func (c *DemoNodeClient) setStatus(siteID uuid.UUID, status models.SiteStatus) error {
+	c.mu.Lock()
+	defer c.mu.Unlock()
+	site, ok := c.sites[siteID]
+	if !ok {
+		return fmt.Errorf("API error (404): site %s is not deployed on this node", siteID)
+	}
+	site.status = status
+	return nil
+}
+
+// HealthCheck reports the node as healthy, counting the running sites on it
+func (c *DemoNodeClient) HealthCheck(endpoint, apiKey string) (*HealthResponse, error) {
+	c.mu.Lock()
+	defer c.mu.Unlock()
+
+	running, deployed := 0, 0
+	for _, site := range c.sites {
+		if site.endpoint != endpoint {
+			continue
+		}
+		deployed++
+		if site.status == models.SiteStatusRunning {
+			running++
+		}
+	}
+
+	return &HealthResponse{
+		Status:  "healthy",
+		Docker:  &models.DockerInfo{Version: "27.3.1", ContainersRunning: running, ImagesCount: deployed + 2},
+		Traefik: &models.TraefikInfo{Version: "3.1.6", RoutersCount: running, ServicesCount: running},
+	}, nil
+}
+
+// GetDockerInfo returns the node's Docker info from a health check
+func (c *DemoNodeClient) GetDockerInfo(endpoint, apiKey string) (*models.DockerInfo, error) {
+	health, err := c.HealthCheck(endpoint, apiKey)
+	if err != nil {
+		return nil, err
+	}
+	return health.Docker, nil
+}
+
+// GetTraefikInfo returns the node's Traefik info from a health check
+func (c *DemoNodeClient) GetTraefikInfo(endpoint, apiKey string) (*models.TraefikInfo, error) {
+	health, err := c.HealthCheck(endpoint, apiKey)
+	if err != nil {
+		return nil, err
+	}
+	return health.Traefik, nil
+}
+
+// GetContainerLogs returns the last lines of a running site's access log, which gains
+// a few requests on each fetch. The cursor is the number of lines written so far.
+func (c *DemoNodeClient) GetContainerLogs(endpoint, apiKey string, siteID uuid.UUID, lines int, since string) (*ContainerLogs, error) {
+	c.mu.Lock()
+	defer c.mu.Unlock()
+	site, ok := c.sites[siteID]
+	if !ok {
+		return nil, fmt.Errorf("API error (404): site %s is not deployed on this node", siteID)
+	}
+	if site.status == models.SiteStatusRunning {
+		site.logLines += 3
+	}
+
+	start := site.logLines - lines
+	if start < 0 || lines <= 0 {
+		start = 0
+	}
+	if after, err := strconv.Atoi(since); err == nil && after > start && after <= site.logLines {
+		start = after
+	}
+	logs := make([]string, 0, site.logLines-start)
+	for i := start; i < site.logLines; i++ {
+		path := demoRequestPaths[i%len(demoRequestPaths)]
+		logs = append(logs, fmt.Sprintf("%s | GET %s 200 %dms", site.name, path, 4+(i*7)%40))
+	}
+	return &ContainerLogs{Lines: logs, Cursor: strconv.Itoa(site.logLines)}, nil
+}
+
+// GetBuildLogs returns the output of a short image build
+func (c *DemoNodeClient) GetBuildLogs(endpoint, apiKey string, siteID uuid.UUID) ([]string, error) {
+	return []string{
+		"Step 1/4 : FROM node:20-alpine",
+		"Step 2/4 : COPY . /app",
+		"Step 3/4 : RUN npm ci --omit=dev",
+		"Step 4/4 : CMD [\"node\", \"server.js\"]",
+		"Successfully built demo",
+	}, nil
+}
+
+// GetContainerMetrics returns resource usage that varies between fetches
+func (c *DemoNodeClient) GetContainerMetrics(endpoint, apiKey string, siteID uuid.UUID) (*ContainerMetrics, error) {
+	c.mu.Lock()
+	defer c.mu.Unlock()
+	site, ok := c.sites[siteID]
+	if !ok {
+		return nil, fmt.Errorf("API error (404): site %s is not deployed on this node", siteID)
+	}
+	if site.status != models.SiteStatusRunning {
+		return &ContainerMetrics{MemoryLimit: 512 << 20}, nil
+	}
+
+	site.polls++
+	const mb = 1 << 20
+	return &ContainerMetrics{
+		CPUPercent:     2 + float64((site.polls*37)%300)/10,
+		MemoryUsage:    int64(96+(site.polls*13)%64) * mb,
+		MemoryLimit:    512 * mb,
+		NetworkRxBytes: int64(site.polls) * 180 * 1024,
+		NetworkTxBytes: int64(site.polls) * 620 * 1024,
+	}, nil
+}
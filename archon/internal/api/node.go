@@ -2,9 +2,11 @@ package api
 
 import (
 	"bytes"
+	"context"
 	"encoding/json"
 	"fmt"
 	"io"
+	"log"
 	"net/http"
 	"net/url"
 	"strings"
@@ -15,23 +17,59 @@ import (
 	"github.com/gorilla/websocket"
 )
 
+// DefaultRequestTimeout is the request timeout for nodes that don't set their own
+const DefaultRequestTimeout = 30 * time.Second
+
 // HTTPNodeClient implements NodeClient using standard net/http
 type HTTPNodeClient struct {
-	client *http.Client
+	client   *http.Client
+	retry    RetryPolicy
+	debugLog *log.Logger     // Set by EnableDebugLogging
+	ctx      context.Context // Set by WithContext; requests are abandoned once it is done
 }
 
 // NewHTTPNodeClient creates a new HTTP-based node client
 func NewHTTPNodeClient() *HTTPNodeClient {
 	return &HTTPNodeClient{
 		client: &http.Client{
-			Timeout: 30 * time.Second,
+			Timeout: DefaultRequestTimeout,
 			Transport: &http.Transport{
 				MaxIdleConns:       10,
 				IdleConnTimeout:    90 * time.Second,
 				DisableCompression: false,
 			},
 		},
+		retry: DefaultRetryPolicy(),
+	}
+}
+
+// WithTimeout returns a client that gives up on requests after timeout instead of the default.
+// It shares this client's connection pool, retry policy and debug log. A timeout of 0 returns c unchanged.
+func (c *HTTPNodeClient) WithTimeout(timeout time.Duration) *HTTPNodeClient {
+	if timeout <= 0 || timeout == c.client.Timeout {
+		return c
+	}
+	httpClient := *c.client
+	httpClient.Timeout = timeout
+	clone := *c
+	clone.client = &httpClient
+	return &clone
+}
+
+// WithContext returns a client whose requests, retries and WebSocket deploys stop when ctx is done.
+// It shares this client's connection pool, retry policy and debug log.
+func (c *HTTPNodeClient) WithContext(ctx context.Context) *HTTPNodeClient {
+	clone := *c
+	clone.ctx = ctx
+	return &clone
+}
+
+// requestContext returns the context set by WithContext, or a background context
+func (c *HTTPNodeClient) requestContext() context.Context {
+	if c.ctx == nil {
+		return context.Background()
 	}
+	return c.ctx
 }
 
 // DeploymentMessage represents a message sent during WebSocket deployment
@@ -42,44 +80,54 @@ type DeploymentMessage struct {
 	Error   string `json:"error,omitempty"`
 }
 
+// DeployStepBlueGreen is the step of progress messages reporting the phases of a blue/green deploy
+const DeployStepBlueGreen = "blue_green"
+
 // DeploymentProgressCallback is called for each progress update during deployment
 type DeploymentProgressCallback func(msg DeploymentMessage)
 
-// DeploySite sends a deployment request to a node
-func (c *HTTPNodeClient) DeploySite(endpoint, apiKey string, site *models.Site, domainName string) error {
-	// Build deploy request with domain mappings support
-	domainMappings := convertToNodeDomainMappings(site, domainName)
-
-	req := struct {
-		ID              uuid.UUID           `json:"id"`
-		Name            string              `json:"name"`
-		SiteType        models.SiteType     `json:"site_type"`
-		Docker          Docker              `json:"docker"`
-		ComposeContent  string              `json:"compose_content,omitempty"`
-		EnvironmentVars map[string]string   `json:"environment_vars"`
-		DomainMappings  []DomainMapping     `json:"domain_mappings"`
-		SSLEnabled      bool                `json:"ssl_enabled"`
-		SSLEmail        string              `json:"ssl_email,omitempty"`
-		ConfigFiles     []models.ConfigFile `json:"config_files"`
-		TraefikLabels   map[string]string   `json:"traefik_labels,omitempty"`
-	}{
+// NewDeployRequest builds the payload sent to a node to deploy site, routing domainName and the site's other mappings
+// Placeholders in the site's config files are filled in here; an unknown one is an error
+func NewDeployRequest(site *models.Site, domainName string) (DeployRequest, error) {
+	configFiles, err := site.RenderConfigFiles(domainName)
+	if err != nil {
+		return DeployRequest{}, err
+	}
+
+	return DeployRequest{
 		ID:       site.ID,
 		Name:     site.Name,
 		SiteType: site.GetSiteType(),
 		Docker: Docker{
 			Image: site.DockerImage,
+			Build: site.Build,
 			Credentials: DockerCredentials{
 				Username: site.DockerUsername,
 				Password: site.DockerToken,
+				Registry: site.RegistryAddress(),
 			},
 		},
 		ComposeContent:  site.ComposeContent,
 		EnvironmentVars: site.EnvironmentVars,
-		DomainMappings:  domainMappings,
+		DomainMappings:  convertToNodeDomainMappings(site, domainName),
 		SSLEnabled:      site.SSLEnabled,
 		SSLEmail:        site.SSLEmail,
-		ConfigFiles:     site.ConfigFiles,
+		ConfigFiles:     configFiles,
 		TraefikLabels:   site.GenerateTraefikLabels(domainName),
+		CPULimit:        site.CPULimit,
+		MemoryLimitMB:   site.MemoryLimitMB,
+		Volumes:         site.Volumes,
+		Strategy:        site.Strategy,
+		HealthPath:      site.HealthPath,
+		HealthStatus:    site.HealthStatus,
+	}, nil
+}
+
+// DeploySite sends a deployment request to a node
+func (c *HTTPNodeClient) DeploySite(endpoint, apiKey string, site *models.Site, domainName string) error {
+	req, err := NewDeployRequest(site, domainName)
+	if err != nil {
+		return err
 	}
 
 	url := fmt.Sprintf("%s/api/v1/sites/deploy", endpoint)
@@ -97,11 +145,17 @@ func (c *HTTPNodeClient) DeploySite(endpoint, apiKey string, site *models.Site,
 }
 
 // DeploySiteWebSocket sends a deployment request via WebSocket with progress updates
-func (c *HTTPNodeClient) DeploySiteWebSocket(endpoint, apiKey string, site *models.Site, domainName string, progressCallback DeploymentProgressCallback) error {
+// The returned response is nil if the node closed the connection without sending one
+func (c *HTTPNodeClient) DeploySiteWebSocket(endpoint, apiKey string, site *models.Site, domainName string, progressCallback DeploymentProgressCallback) (*DeployResponse, error) {
+	req, err := NewDeployRequest(site, domainName)
+	if err != nil {
+		return nil, err
+	}
+
 	// Convert HTTP/HTTPS endpoint to WebSocket URL
 	wsURL, err := convertToWebSocketURL(endpoint, "/api/v1/sites/deploy/ws")
 	if err != nil {
-		return fmt.Errorf("failed to convert to WebSocket URL: %w", err)
+		return nil, fmt.Errorf("failed to convert to WebSocket URL: %w", err)
 	}
 
 	// Establish WebSocket connection with Authorization header
@@ -115,60 +169,36 @@ func (c *HTTPNodeClient) DeploySiteWebSocket(endpoint, apiKey string, site *mode
 		headers.Set("Authorization", "Bearer "+apiKey)
 	}
 
-	conn, _, err := dialer.Dial(wsURL, headers)
+	ctx := c.requestContext()
+	conn, _, err := dialer.DialContext(ctx, wsURL, headers)
 	if err != nil {
-		return fmt.Errorf("failed to connect to WebSocket: %w", err)
+		return nil, fmt.Errorf("failed to connect to WebSocket: %w", err)
 	}
 	defer conn.Close()
 
-	// Build deploy request with domain mappings support
-	domainMappings := convertToNodeDomainMappings(site, domainName)
-
-	req := struct {
-		ID              uuid.UUID           `json:"id"`
-		Name            string              `json:"name"`
-		SiteType        models.SiteType     `json:"site_type"`
-		Docker          Docker              `json:"docker"`
-		ComposeContent  string              `json:"compose_content,omitempty"`
-		EnvironmentVars map[string]string   `json:"environment_vars"`
-		DomainMappings  []DomainMapping     `json:"domain_mappings"`
-		SSLEnabled      bool                `json:"ssl_enabled"`
-		SSLEmail        string              `json:"ssl_email,omitempty"`
-		ConfigFiles     []models.ConfigFile `json:"config_files"`
-		TraefikLabels   map[string]string   `json:"traefik_labels,omitempty"`
-	}{
-		ID:       site.ID,
-		Name:     site.Name,
-		SiteType: site.GetSiteType(),
-		Docker: Docker{
-			Image: site.DockerImage,
-			Credentials: DockerCredentials{
-				Username: site.DockerUsername,
-				Password: site.DockerToken,
-			},
-		},
-		ComposeContent:  site.ComposeContent,
-		EnvironmentVars: site.EnvironmentVars,
-		DomainMappings:  domainMappings,
-		SSLEnabled:      site.SSLEnabled,
-		SSLEmail:        site.SSLEmail,
-		ConfigFiles:     site.ConfigFiles,
-		TraefikLabels:   site.GenerateTraefikLabels(domainName),
-	}
+	// Closing the connection unblocks the reads below when the deploy is cancelled
+	stop := context.AfterFunc(ctx, func() { conn.Close() })
+	defer stop()
 
 	// Send deployment request as first message
+	c.logDebugMessage("-->", wsURL, req)
 	if err := conn.WriteJSON(req); err != nil {
-		return fmt.Errorf("failed to send deployment request: %w", err)
+		return nil, fmt.Errorf("failed to send deployment request: %w", err)
 	}
 
 	// Listen for progress messages
 	for {
 		var msg DeploymentMessage
 		if err := conn.ReadJSON(&msg); err != nil {
+			if ctx.Err() != nil {
+				return nil, fmt.Errorf("deployment cancelled: %w", ctx.Err())
+			}
 			// Connection closed or error reading
-			return fmt.Errorf("WebSocket read error: %w", err)
+			return nil, fmt.Errorf("WebSocket read error: %w", err)
 		}
 
+		c.logDebugMessage("<--", wsURL, msg)
+
 		// Call progress callback if provided
 		if progressCallback != nil {
 			progressCallback(msg)
@@ -177,14 +207,20 @@ func (c *HTTPNodeClient) DeploySiteWebSocket(endpoint, apiKey string, site *mode
 		// Handle message types
 		switch msg.Type {
 		case "success":
-			// Deployment completed successfully
-			return nil
+			// Deployment completed successfully; the node follows up with the deploy response
+			var resp DeployResponse
+			if err := conn.ReadJSON(&resp); err != nil {
+				// Older nodes may close without one, which doesn't make the deploy a failure
+				return nil, nil
+			}
+			c.logDebugMessage("<--", wsURL, resp)
+			return &resp, nil
 		case "error":
 			// Deployment failed
 			if msg.Error != "" {
-				return fmt.Errorf("deployment failed: %s", msg.Error)
+				return nil, fmt.Errorf("deployment failed: %s", msg.Error)
 			}
-			return fmt.Errorf("deployment failed: %s", msg.Message)
+			return nil, fmt.Errorf("deployment failed: %s", msg.Message)
 		case "progress":
 			// Continue listening for more messages
 			continue
@@ -243,7 +279,7 @@ func (c *HTTPNodeClient) DeleteSite(endpoint, apiKey string, siteID uuid.UUID, d
 }
 
 // GetSiteStatus retrieves the current status of a deployed site
-func (c *HTTPNodeClient) GetSiteStatus(endpoint, apiKey string, siteID uuid.UUID, siteName string, siteType models.SiteType) (*models.SiteStatus, error) {
+func (c *HTTPNodeClient) GetSiteStatus(endpoint, apiKey string, siteID uuid.UUID, siteName string, siteType models.SiteType) (*SiteStatusResponse, error) {
 	url := fmt.Sprintf("%s/api/v1/sites/%s/status", endpoint, siteID.String())
 
 	// Add query params for compose sites
@@ -261,7 +297,7 @@ func (c *HTTPNodeClient) GetSiteStatus(endpoint, apiKey string, siteID uuid.UUID
 		return nil, fmt.Errorf("get status failed with status %d", resp.StatusCode)
 	}
 
-	var status models.SiteStatus
+	var status SiteStatusResponse
 	if err := json.NewDecoder(resp.Body).Decode(&status); err != nil {
 		return nil, fmt.Errorf("failed to decode status response: %w", err)
 	}
@@ -357,10 +393,14 @@ func (c *HTTPNodeClient) GetTraefikInfo(endpoint, apiKey string) (*models.Traefi
 	return health.Traefik, nil
 }
 
-// GetContainerLogs retrieves recent logs from a site's container
-func (c *HTTPNodeClient) GetContainerLogs(endpoint, apiKey string, siteID uuid.UUID, lines int) ([]string, error) {
-	url := fmt.Sprintf("%s/api/v1/sites/%s/logs", endpoint, siteID.String())
-	resp, err := c.doRequest("GET", url, apiKey, nil)
+// GetContainerLogs retrieves recent logs from a site's container, or only the lines logged
+// after since when it is a cursor returned by an earlier call
+func (c *HTTPNodeClient) GetContainerLogs(endpoint, apiKey string, siteID uuid.UUID, lines int, since string) (*ContainerLogs, error) {
+	logsURL := fmt.Sprintf("%s/api/v1/sites/%s/logs", endpoint, siteID.String())
+	if since != "" {
+		logsURL += "?since=" + url.QueryEscape(since)
+	}
+	resp, err := c.doRequest("GET", logsURL, apiKey, nil)
 	if err != nil {
 		return nil, err
 	}
@@ -370,6 +410,27 @@ func (c *HTTPNodeClient) GetContainerLogs(endpoint, apiKey string, siteID uuid.U
 		return nil, fmt.Errorf("get logs failed with status %d", resp.StatusCode)
 	}
 
+	var logs ContainerLogs
+	if err := json.NewDecoder(resp.Body).Decode(&logs); err != nil {
+		return nil, fmt.Errorf("failed to decode logs response: %w", err)
+	}
+
+	return &logs, nil
+}
+
+// GetBuildLogs retrieves the output of the last image build for a site built from source
+func (c *HTTPNodeClient) GetBuildLogs(endpoint, apiKey string, siteID uuid.UUID) ([]string, error) {
+	url := fmt.Sprintf("%s/api/v1/sites/%s/logs?source=build", endpoint, siteID.String())
+	resp, err := c.doRequest("GET", url, apiKey, nil)
+	if err != nil {
+		return nil, err
+	}
+	defer resp.Body.Close()
+
+	if resp.StatusCode != http.StatusOK {
+		return nil, fmt.Errorf("get build logs failed with status %d", resp.StatusCode)
+	}
+
 	var logsResp struct {
 		Logs []string `json:"logs"`
 	}
@@ -382,40 +443,96 @@ func (c *HTTPNodeClient) GetContainerLogs(endpoint, apiKey string, siteID uuid.U
 
 // GetContainerMetrics retrieves resource usage metrics for a site
 func (c *HTTPNodeClient) GetContainerMetrics(endpoint, apiKey string, siteID uuid.UUID) (*ContainerMetrics, error) {
-	// Note: This endpoint is not yet implemented in archon-node
-	// For now, return a placeholder error
-	return nil, fmt.Errorf("metrics endpoint not yet implemented")
+	url := fmt.Sprintf("%s/api/v1/sites/%s/metrics", endpoint, siteID.String())
+	resp, err := c.doRequest("GET", url, apiKey, nil)
+	if err != nil {
+		return nil, err
+	}
+	defer resp.Body.Close()
+
+	if resp.StatusCode != http.StatusOK {
+		return nil, fmt.Errorf("get metrics failed with status %d", resp.StatusCode)
+	}
+
+	var metrics ContainerMetrics
+	if err := json.NewDecoder(resp.Body).Decode(&metrics); err != nil {
+		return nil, fmt.Errorf("failed to decode metrics response: %w", err)
+	}
+
+	return &metrics, nil
 }
 
 // doRequest is a helper function to execute HTTP requests with auth
+// Network failures and 5xx responses are retried according to the client's retry policy. Other
+// methods are only retried if the request never reached the node: a POST such as a deploy may
+// have taken effect before it failed.
 func (c *HTTPNodeClient) doRequest(method, url, apiKey string, body interface{}) (*http.Response, error) {
-	var reqBody io.Reader
+	return c.sendRequest(method, url, apiKey, body, isIdempotent(method))
+}
+
+// sendRequest executes an HTTP request with auth, retrying failures if retry is set and
+// failures to connect regardless
+func (c *HTTPNodeClient) sendRequest(method, url, apiKey string, body interface{}, retry bool) (*http.Response, error) {
+	var jsonData []byte
 
 	// Marshal body to JSON if provided
 	if body != nil {
-		jsonData, err := json.Marshal(body)
+		var err error
+		jsonData, err = json.Marshal(body)
 		if err != nil {
 			return nil, fmt.Errorf("failed to marshal request body: %w", err)
 		}
-		reqBody = bytes.NewReader(jsonData)
 	}
 
-	// Create request
-	req, err := http.NewRequest(method, url, reqBody)
-	if err != nil {
-		return nil, fmt.Errorf("failed to create request: %w", err)
+	attempts := c.retry.MaxAttempts
+	if attempts < 1 {
+		attempts = 1
 	}
 
-	// Set headers
-	if apiKey != "" {
-		req.Header.Set("Authorization", "Bearer "+apiKey)
-	}
-	if body != nil {
-		req.Header.Set("Content-Type", "application/json")
-	}
+	var resp *http.Response
+	var err error
+	for attempt := 1; attempt <= attempts; attempt++ {
+		if attempt > 1 {
+			delay := c.retry.delay(attempt - 1)
+			if c.debugLog != nil {
+				c.debugLog.Printf("retrying %s %s in %s (attempt %d/%d)", method, url, delay.Round(time.Millisecond), attempt, attempts)
+			}
+			select {
+			case <-time.After(delay):
+			case <-c.requestContext().Done():
+				return nil, fmt.Errorf("request failed: %w", c.requestContext().Err())
+			}
+		}
 
-	// Execute request
-	resp, err := c.client.Do(req)
+		// Create request (the body reader is consumed by each attempt)
+		var reqBody io.Reader
+		if jsonData != nil {
+			reqBody = bytes.NewReader(jsonData)
+		}
+		req, reqErr := http.NewRequestWithContext(c.requestContext(), method, url, reqBody)
+		if reqErr != nil {
+			return nil, fmt.Errorf("failed to create request: %w", reqErr)
+		}
+
+		// Set headers
+		if apiKey != "" {
+			req.Header.Set("Authorization", "Bearer "+apiKey)
+		}
+		if body != nil {
+			req.Header.Set("Content-Type", "application/json")
+		}
+
+		// Execute request
+		resp, err = c.client.Do(req)
+		if attempt == attempts || !shouldRetry(resp, err) || (!retry && !notSent(err)) {
+			break
+		}
+		if resp != nil {
+			// Discard the failed response before retrying
+			io.Copy(io.Discard, resp.Body)
+			resp.Body.Close()
+		}
+	}
 	if err != nil {
 		return nil, fmt.Errorf("request failed: %w", err)
 	}
@@ -481,5 +598,17 @@ func convertToNodeDomainMappings(site *models.Site, domainName string) []DomainM
 			HostPort: mapping.HostPort,
 		})
 	}
+
+	// Extra hostnames share the primary mapping's port so nginx/apache nodes proxy and certify them too
+	if len(mappings) > 0 {
+		primary := mappings[0]
+		for _, host := range site.Hostnames(primary.Domain)[1:] {
+			mappings = append(mappings, DomainMapping{
+				Domain:   host,
+				Port:     primary.Port,
+				HostPort: primary.HostPort,
+			})
+		}
+	}
 	return mappings
 }
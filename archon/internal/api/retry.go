@@ -0,0 +1,90 @@
+package api
+
+import (
+	"errors"
+	"math/rand"
+	"net"
+	"net/http"
+	"net/url"
+	"time"
+)
+
+// RetryPolicy controls how node requests are retried when a node is briefly unreachable
+type RetryPolicy struct {
+	MaxAttempts int           // Total attempts including the first (1 disables retries)
+	BaseDelay   time.Duration // Delay before the first retry, doubled for each later retry
+	MaxDelay    time.Duration // Upper bound for a single delay
+	Jitter      float64       // Fraction of each delay that is randomised (0-1)
+}
+
+// DefaultRetryPolicy returns the retry policy used when none is configured
+func DefaultRetryPolicy() RetryPolicy {
+	return RetryPolicy{
+		MaxAttempts: 3,
+		BaseDelay:   500 * time.Millisecond,
+		MaxDelay:    10 * time.Second,
+		Jitter:      0.2,
+	}
+}
+
+// SetRetryPolicy sets the retry policy for all node requests
+func (c *HTTPNodeClient) SetRetryPolicy(policy RetryPolicy) {
+	c.retry = policy
+}
+
+// delay returns how long to wait before the given retry (1 for the first retry)
+func (p RetryPolicy) delay(retry int) time.Duration {
+	// Large shifts overflow, so anything past 2^31 x BaseDelay is treated as MaxDelay
+	d := p.MaxDelay
+	if shift := retry - 1; shift < 32 {
+		if exp := p.BaseDelay << shift; exp > 0 && (p.MaxDelay <= 0 || exp < p.MaxDelay) {
+			d = exp
+		}
+	}
+
+	if p.Jitter > 0 {
+		// Spread the delay by up to ±Jitter so clients don't retry in lockstep
+		spread := float64(d) * p.Jitter
+		d += time.Duration((rand.Float64()*2 - 1) * spread)
+	}
+	if d < 0 {
+		d = 0
+	}
+	return d
+}
+
+// isIdempotent returns true for methods that can be repeated without changing the outcome
+func isIdempotent(method string) bool {
+	switch method {
+	case http.MethodGet, http.MethodHead, http.MethodPut, http.MethodDelete:
+		return true
+	}
+	return false
+}
+
+// shouldRetry returns true for network failures and 5xx server errors
+// 4xx responses are the caller's fault and will fail the same way again
+func shouldRetry(resp *http.Response, err error) bool {
+	if err != nil {
+		return true
+	}
+	return resp.StatusCode >= 500
+}
+
+// notSent returns true if a request failed before it was sent, because the node's name didn't
+// resolve or the connection was refused, so even a non-idempotent request is safe to repeat
+func notSent(err error) bool {
+	var opErr *net.OpError
+	if errors.As(err, &opErr) && opErr.Op == "dial" {
+		return true
+	}
+	var dnsErr *net.DNSError
+	return errors.As(err, &dnsErr)
+}
+
+// IsUnreachable returns true if a node request failed without getting a response
+// (connection refused, DNS failure, timeout), as opposed to the node answering with an error
+func IsUnreachable(err error) bool {
+	var urlErr *url.Error
+	return errors.As(err, &urlErr)
+}
@@ -0,0 +1,217 @@
+package api
+
+import (
+	"context"
+	"errors"
+	"net/http"
+	"net/http/httptest"
+	"sync/atomic"
+	"testing"
+	"time"
+
+	"github.com/BlueBeard63/archon/internal/models"
+	"github.com/google/uuid"
+)
+
+func TestDoRequestRetries(t *testing.T) {
+	tests := []struct {
+		name         string
+		statuses     []int // Response status per attempt; the last one repeats
+		maxAttempts  int
+		wantAttempts int32
+		wantErr      bool
+	}{
+		{name: "success on first attempt", statuses: []int{200}, maxAttempts: 3, wantAttempts: 1},
+		{name: "retries 503 until success", statuses: []int{503, 503, 200}, maxAttempts: 3, wantAttempts: 3},
+		{name: "gives up after max attempts", statuses: []int{500}, maxAttempts: 3, wantAttempts: 3, wantErr: true},
+		{name: "does not retry 404", statuses: []int{404}, maxAttempts: 3, wantAttempts: 1, wantErr: true},
+		{name: "does not retry 401", statuses: []int{401}, maxAttempts: 3, wantAttempts: 1, wantErr: true},
+		{name: "single attempt disables retries", statuses: []int{502, 200}, maxAttempts: 1, wantAttempts: 1, wantErr: true},
+	}
+
+	for _, tt := range tests {
+		t.Run(tt.name, func(t *testing.T) {
+			var attempts int32
+			server := httptest.NewServer(http.HandlerFunc(func(w http.ResponseWriter, r *http.Request) {
+				n := int(atomic.AddInt32(&attempts, 1))
+				if n > len(tt.statuses) {
+					n = len(tt.statuses)
+				}
+				w.WriteHeader(tt.statuses[n-1])
+			}))
+			defer server.Close()
+
+			client := NewHTTPNodeClient()
+			client.SetRetryPolicy(RetryPolicy{MaxAttempts: tt.maxAttempts, BaseDelay: time.Millisecond})
+
+			err := client.DeleteSite(server.URL, "key", uuid.New(), "example.com", "", models.SiteTypeContainer)
+			if (err != nil) != tt.wantErr {
+				t.Errorf("DeleteSite error = %v, wantErr %v", err, tt.wantErr)
+			}
+			if IsUnreachable(err) {
+				t.Errorf("IsUnreachable(%v) = true for a node that responded", err)
+			}
+			if got := atomic.LoadInt32(&attempts); got != tt.wantAttempts {
+				t.Errorf("attempts = %d, want %d", got, tt.wantAttempts)
+			}
+		})
+	}
+}
+
+func TestDoRequestDoesNotRetryPosts(t *testing.T) {
+	var attempts int32
+	server := httptest.NewServer(http.HandlerFunc(func(w http.ResponseWriter, r *http.Request) {
+		atomic.AddInt32(&attempts, 1)
+		w.WriteHeader(http.StatusBadGateway)
+	}))
+	defer server.Close()
+
+	client := NewHTTPNodeClient()
+	client.SetRetryPolicy(RetryPolicy{MaxAttempts: 3, BaseDelay: time.Millisecond})
+
+	// The node may have acted on a POST before failing, so it is sent once
+	site := models.NewSite("web", uuid.New(), uuid.New(), "nginx:latest", 8080)
+	if err := client.DeploySite(server.URL, "key", site, "example.com"); err == nil {
+		t.Error("expected DeploySite to fail")
+	}
+	if err := client.RestartSite(server.URL, "key", uuid.New()); err == nil {
+		t.Error("expected RestartSite to fail")
+	}
+	if got := atomic.LoadInt32(&attempts); got != 2 {
+		t.Errorf("attempts = %d, want 2 (one per request)", got)
+	}
+
+	// Webhook deliveries opt in to retries
+	atomic.StoreInt32(&attempts, 0)
+	if err := client.PostWebhook(server.URL, WebhookEvent{Event: WebhookDeployFailed}); err == nil {
+		t.Error("expected PostWebhook to fail")
+	}
+	if got := atomic.LoadInt32(&attempts); got != 3 {
+		t.Errorf("webhook attempts = %d, want 3", got)
+	}
+}
+
+func TestDoRequestRetriesNetworkErrors(t *testing.T) {
+	// Close the server so every attempt fails to connect
+	server := httptest.NewServer(http.HandlerFunc(func(w http.ResponseWriter, r *http.Request) {}))
+	endpoint := server.URL
+	server.Close()
+
+	client := NewHTTPNodeClient()
+	client.SetRetryPolicy(RetryPolicy{MaxAttempts: 3, BaseDelay: 10 * time.Millisecond})
+
+	start := time.Now()
+	_, err := client.HealthCheck(endpoint, "")
+	if err == nil {
+		t.Fatal("expected error from unreachable node")
+	}
+	if !IsUnreachable(err) {
+		t.Errorf("IsUnreachable(%v) = false for a closed server", err)
+	}
+
+	// Two retries: 10ms then 20ms
+	if elapsed := time.Since(start); elapsed < 30*time.Millisecond {
+		t.Errorf("elapsed = %s, want at least 30ms of backoff", elapsed)
+	}
+}
+
+func TestDoRequestRetriesPostsThatWereNotSent(t *testing.T) {
+	// Close the server so every attempt fails to connect
+	server := httptest.NewServer(http.HandlerFunc(func(w http.ResponseWriter, r *http.Request) {}))
+	endpoint := server.URL
+	server.Close()
+
+	client := NewHTTPNodeClient()
+	client.SetRetryPolicy(RetryPolicy{MaxAttempts: 3, BaseDelay: 10 * time.Millisecond})
+
+	// A refused connection never reached the node, so the deploy is safe to send again
+	start := time.Now()
+	site := models.NewSite("web", uuid.New(), uuid.New(), "nginx:latest", 8080)
+	if err := client.DeploySite(endpoint, "key", site, "example.com"); err == nil {
+		t.Fatal("expected error from unreachable node")
+	}
+
+	// Two retries: 10ms then 20ms
+	if elapsed := time.Since(start); elapsed < 30*time.Millisecond {
+		t.Errorf("elapsed = %s, want at least 30ms of backoff", elapsed)
+	}
+}
+
+func TestRetryPolicyDelay(t *testing.T) {
+	policy := RetryPolicy{BaseDelay: 100 * time.Millisecond, MaxDelay: time.Second}
+
+	tests := []struct {
+		retry int
+		want  time.Duration
+	}{
+		{retry: 1, want: 100 * time.Millisecond},
+		{retry: 2, want: 200 * time.Millisecond},
+		{retry: 3, want: 400 * time.Millisecond},
+		{retry: 5, want: time.Second}, // capped
+		{retry: 80, want: time.Second}, // overflow is capped too
+	}
+
+	for _, tt := range tests {
+		if got := policy.delay(tt.retry); got != tt.want {
+			t.Errorf("delay(%d) = %s, want %s", tt.retry, got, tt.want)
+		}
+	}
+
+	// Jitter keeps the delay within ±Jitter of the base
+	policy.Jitter = 0.5
+	for i := 0; i < 100; i++ {
+		if got := policy.delay(1); got < 50*time.Millisecond || got > 150*time.Millisecond {
+			t.Fatalf("jittered delay(1) = %s, want 50ms-150ms", got)
+		}
+	}
+}
+
+func TestWithTimeout(t *testing.T) {
+	// The handler outlives the short timeout but not the default one
+	server := httptest.NewServer(http.HandlerFunc(func(w http.ResponseWriter, r *http.Request) {
+		time.Sleep(100 * time.Millisecond)
+	}))
+	defer server.Close()
+
+	client := NewHTTPNodeClient()
+	client.SetRetryPolicy(RetryPolicy{MaxAttempts: 1})
+
+	if got := client.WithTimeout(0); got != client {
+		t.Error("WithTimeout(0) should return the client unchanged")
+	}
+
+	short := client.WithTimeout(10 * time.Millisecond)
+	if err := short.RestartSite(server.URL, "key", uuid.New()); err == nil {
+		t.Error("expected the short timeout to fail the request")
+	}
+	if err := client.RestartSite(server.URL, "key", uuid.New()); err != nil {
+		t.Errorf("default timeout request failed: %v", err)
+	}
+	if client.client.Timeout != DefaultRequestTimeout {
+		t.Errorf("original client timeout = %s, want %s", client.client.Timeout, DefaultRequestTimeout)
+	}
+}
+
+func TestWithContextCancelsRetries(t *testing.T) {
+	var attempts int32
+	server := httptest.NewServer(http.HandlerFunc(func(w http.ResponseWriter, r *http.Request) {
+		atomic.AddInt32(&attempts, 1)
+		w.WriteHeader(http.StatusServiceUnavailable)
+	}))
+	defer server.Close()
+
+	client := NewHTTPNodeClient()
+	client.SetRetryPolicy(RetryPolicy{MaxAttempts: 5, BaseDelay: time.Hour})
+
+	// Cancel while the client waits to retry the first failed attempt
+	ctx, cancel := context.WithCancel(context.Background())
+	time.AfterFunc(50*time.Millisecond, cancel)
+
+	err := client.WithContext(ctx).DeleteSite(server.URL, "key", uuid.New(), "example.com", "", models.SiteTypeContainer)
+	if !errors.Is(err, context.Canceled) {
+		t.Errorf("DeleteSite error = %v, want context.Canceled", err)
+	}
+	if got := atomic.LoadInt32(&attempts); got != 1 {
+		t.Errorf("attempts = %d, want 1", got)
+	}
+}
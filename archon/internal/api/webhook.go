@@ -0,0 +1,44 @@
+package api
+
+import (
+	"io"
+	"net/http"
+	"time"
+
+	"github.com/google/uuid"
+)
+
+// WebhookTimeout is how long a webhook endpoint has to accept an event
+const WebhookTimeout = 10 * time.Second
+
+// Webhook event types
+const (
+	WebhookDeploySucceeded = "deploy_succeeded"
+	WebhookDeployFailed    = "deploy_failed"
+	WebhookNodeOffline     = "node_offline"
+	WebhookDnsSynced       = "dns_synced"
+	WebhookDnsSyncFailed   = "dns_sync_failed"
+)
+
+// WebhookEvent is the JSON body posted to the webhook URL for a key event
+type WebhookEvent struct {
+	Event     string    `json:"event"`  // One of the Webhook* event types
+	Entity    string    `json:"entity"` // "site", "node" or "domain"
+	EntityID  uuid.UUID `json:"entity_id"`
+	Name      string    `json:"name"`
+	Outcome   string    `json:"outcome"` // "success" or "failure"
+	Error     string    `json:"error,omitempty"`
+	Timestamp time.Time `json:"timestamp"`
+}
+
+// PostWebhook posts an event to url, sharing the node client's connection pool, retry
+// policy and debug log. Any 2xx or 3xx response counts as delivered. Unlike other POSTs,
+// failed deliveries are retried, as an event delivered twice does no harm.
+func (c *HTTPNodeClient) PostWebhook(url string, event WebhookEvent) error {
+	resp, err := c.WithTimeout(WebhookTimeout).sendRequest(http.MethodPost, url, "", event, true)
+	if err != nil {
+		return err
+	}
+	io.Copy(io.Discard, resp.Body)
+	return resp.Body.Close()
+}
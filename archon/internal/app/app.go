@@ -1,25 +1,82 @@
 package app
 
 import (
+	"bytes"
+	"context"
+	"errors"
 	"fmt"
+	"os"
+	"path/filepath"
 	"strings"
+	"sync"
 	"time"
+	"unicode"
 
 	tea "github.com/charmbracelet/bubbletea"
 	"github.com/google/uuid"
 	zone "github.com/lrstanley/bubblezone"
+	"github.com/pelletier/go-toml/v2"
 
 	"github.com/BlueBeard63/archon/internal/api"
+	"github.com/BlueBeard63/archon/internal/audit"
+	"github.com/BlueBeard63/archon/internal/certs"
 	"github.com/BlueBeard63/archon/internal/config"
+	"github.com/BlueBeard63/archon/internal/desktop"
 	"github.com/BlueBeard63/archon/internal/dns"
+	"github.com/BlueBeard63/archon/internal/dns/cloudflare"
+	"github.com/BlueBeard63/archon/internal/health"
 	"github.com/BlueBeard63/archon/internal/models"
 	"github.com/BlueBeard63/archon/internal/state"
 	"github.com/BlueBeard63/archon/internal/ui"
+	"github.com/BlueBeard63/archon/internal/ui/theme"
 )
 
 // Used to suppress unused variable warnings
 var _ = fmt.Sprint
 
+// siteDetailLogLines is how many container log lines the site detail screen fetches
+const siteDetailLogLines = 200
+
+// logFollowInterval is how often the site detail screen polls logs while following them
+const logFollowInterval = time.Second
+
+// certCheckInterval is how often every domain's TLS certificate expiry is checked in the background
+const certCheckInterval = 12 * time.Hour
+
+// certCheckTimeout bounds each TLS handshake when checking a certificate
+const certCheckTimeout = 10 * time.Second
+
+// siteHealthInterval is how often the health path of every running site is probed in the background
+const siteHealthInterval = 30 * time.Second
+
+// siteHealthTimeout bounds each site health probe
+const siteHealthTimeout = 10 * time.Second
+
+// propagationCheckTimeout bounds checking all of a domain's records against the public resolvers
+const propagationCheckTimeout = 15 * time.Second
+
+// defaultMaxConcurrentOperations is how many tracked operations run at once when the setting is unset
+const defaultMaxConcurrentOperations = 4
+
+// bulkConcurrency limits how many sites a bulk stop or deploy works on at once, to spare the node
+const bulkConcurrency = 3
+
+// spinnerInterval is how often the status bar spinner advances while operations are pending
+const spinnerInterval = 100 * time.Millisecond
+
+// metricsRefreshInterval is how often the site detail screen refreshes metrics for its trend graphs
+const metricsRefreshInterval = 5 * time.Second
+
+// configWatchInterval is how often the config file is checked for edits made outside Archon.
+// An edit is reloaded once the file has stopped changing for a full interval.
+const configWatchInterval = 2 * time.Second
+
+// configReloadConfirmKind is the confirmation kind for reloading an edited config over unsaved changes
+const configReloadConfirmKind = "config reload"
+
+// minHealthCheckIntervalSecs stops a tiny configured interval from flooding nodes with health checks
+const minHealthCheckIntervalSecs = 10
+
 // Model implements tea.Model for Bubbletea's Elm Architecture
 type Model struct {
 	state        *state.AppState
@@ -27,12 +84,26 @@ type Model struct {
 	configLoader config.ConfigLoader
 	configPath   string
 	zone         *zone.Manager
+	theme        theme.Theme   // Colors the UI is drawn with, from the theme settings
+	opSlots      chan struct{} // Semaphore bounding how many tracked operations run at once
+	watch        *configWatch  // The config file as last loaded or saved, to reload edits made outside Archon
+	saves        *configSaves  // Orders config saves made in the background
+	audit        *audit.Log    // Record of deploys, edits, deletes and DNS changes (nil when turned off)
+	demoDns      dns.Provider  // Stands in for every domain's DNS provider in demo mode (nil otherwise)
+
+	deployProgress chan SiteDeployProgressMsg // Blue/green deploy phases reported by nodes, shown as they arrive
+
+	// Set on the copy returned by startOperation
+	opCtx context.Context
+	opID  uuid.UUID
 }
 
 // NewModel creates a new application model with initial state
-func NewModel(configPath string) (*Model, error) {
+// A non-empty passphrase decrypts secrets on load and encrypts them on save
+func NewModel(configPath string, passphrase string) (*Model, error) {
 	// Create config loader
 	loader := config.NewFileConfigLoader()
+	loader.SetPassphrase(passphrase)
 
 	// Load config from path
 	cfg, err := loader.Load(configPath)
@@ -42,22 +113,263 @@ func NewModel(configPath string) (*Model, error) {
 
 	// Initialize AppState from config
 	appState := state.NewAppState()
+	appState.ConfigPath = configPath
+	appState.Desktop = desktop.System()
+	t := applyConfig(appState, cfg)
+
+	// Deploys running when Archon last exited are checked on their nodes in Init
+	if deploying, err := state.LoadDeploying(state.DeployingPath(configPath)); err != nil {
+		appState.AddNotification("Failed to load interrupted deploys: "+err.Error(), "warning")
+	} else {
+		appState.MarkDeploying(deploying)
+	}
+
+	// A fresh install starts in the setup wizard rather than an empty dashboard
+	if appState.NeedsSetup() {
+		appState.CurrentScreen = state.ScreenSetup
+	}
+
+	// Bring back the last session's notifications for the history screen
+	if saved, err := state.LoadNotifications(state.NotificationsPath(configPath)); err != nil {
+		appState.AddNotification("Failed to load previous notifications: "+err.Error(), "warning")
+	} else {
+		appState.RestoreNotifications(saved)
+	}
+
+	// Log node API traffic when debugging is enabled
+	nodeClient := api.NewHTTPNodeClient()
+	nodeClient.SetRetryPolicy(retryPolicy(cfg.Settings))
+	if cfg.Settings.DebugHTTP {
+		logFile, err := openDebugLog(configPath)
+		if err != nil {
+			return nil, fmt.Errorf("failed to open debug log: %w", err)
+		}
+		nodeClient.EnableDebugLogging(logFile)
+	}
+
+	m := &Model{
+		state:        appState,
+		nodeClient:   nodeClient,
+		configLoader: loader,
+		configPath:   configPath,
+		zone:         zone.New(),
+		theme:        t,
+		opSlots:      make(chan struct{}, operationSlots(cfg.Settings)),
+		watch:        &configWatch{},
+		saves:        &configSaves{},
+		audit:        openAuditLog(configPath, cfg.Settings),
+
+		deployProgress: make(chan SiteDeployProgressMsg, 16),
+	}
+	m.markConfigSynced()
+	return m, nil
+}
+
+// configWatch tracks the config file on disk so edits made outside Archon can be reloaded
+type configWatch struct {
+	loaded  time.Time // Modification time when the config was last loaded, saved or reloaded
+	seen    time.Time // Modification time at the previous check, to wait for a write to finish
+	applied []byte    // The in-memory config at that point, to tell whether there are unsaved changes
+	saving  int       // Background saves not yet reported; the file changes meanwhile are Archon's own
+}
+
+// configSaves orders config saves made in the background, so a save that started earlier never
+// overwrites one that started later
+type configSaves struct {
+	mu      sync.Mutex
+	started uint64 // Saves started, numbering them (only used in Update)
+	written uint64 // Number of the latest save written to disk
+}
+
+// configModTime returns the config file's modification time, or zero if it can't be read
+func configModTime(path string) time.Time {
+	info, err := os.Stat(path)
+	if err != nil {
+		return time.Time{}
+	}
+	return info.ModTime()
+}
+
+// markConfigSynced records that the in-memory config matches the file on disk
+func (m Model) markConfigSynced() {
+	fingerprint, _ := configFingerprint(m.currentConfig())
+	m.markConfigSaved(fingerprint)
+}
+
+// markConfigSaved records that the file on disk holds the config with the given fingerprint
+func (m Model) markConfigSaved(fingerprint []byte) {
+	modTime := configModTime(m.configPath)
+	m.watch.loaded, m.watch.seen = modTime, modTime
+	m.watch.applied = fingerprint
+}
+
+// hasUnsavedChanges reports whether the config was changed in Archon since it was last loaded or saved
+func (m Model) hasUnsavedChanges() bool {
+	if m.state.Dirty {
+		return true
+	}
+	current, err := configFingerprint(m.currentConfig())
+	return err != nil || !bytes.Equal(current, m.watch.applied)
+}
+
+// configFingerprint serializes the parts of a config the user edits, leaving out the site
+// and node status that health checks and deploys keep updating between saves
+func configFingerprint(cfg *config.Config) ([]byte, error) {
+	edited := *cfg
+	edited.Sites = append([]models.Site(nil), cfg.Sites...)
+	for i := range edited.Sites {
+		edited.Sites[i].Status = ""
+	}
+	edited.Nodes = append([]models.Node(nil), cfg.Nodes...)
+	for i := range edited.Nodes {
+		node := &edited.Nodes[i]
+		node.Status, node.DockerInfo, node.TraefikInfo = "", nil, nil
+		node.LastHealthCheck, node.LastLatencyMs = nil, nil
+	}
+	return toml.Marshal(edited)
+}
+
+// operationSlots returns how many tracked operations may run at once
+func operationSlots(settings config.Settings) int {
+	if settings.MaxConcurrentOperations > 0 {
+		return settings.MaxConcurrentOperations
+	}
+	return defaultMaxConcurrentOperations
+}
+
+// retryPolicy builds the node request retry policy from settings, using defaults for unset values
+func retryPolicy(settings config.Settings) api.RetryPolicy {
+	policy := api.DefaultRetryPolicy()
+	if settings.RetryMaxAttempts > 0 {
+		policy.MaxAttempts = settings.RetryMaxAttempts
+	}
+	if settings.RetryBaseDelayMs > 0 {
+		policy.BaseDelay = time.Duration(settings.RetryBaseDelayMs) * time.Millisecond
+	}
+	if settings.RetryJitter != nil {
+		policy.Jitter = min(max(*settings.RetryJitter, 0), 1)
+	}
+	return policy
+}
+
+// nodeClientFor returns the client to use for requests to node, applying the node's timeout override
+func (m Model) nodeClientFor(node *models.Node) api.NodeClient {
+	if httpClient, ok := m.nodeClient.(*api.HTTPNodeClient); ok {
+		httpClient = httpClient.WithTimeout(node.RequestTimeout())
+		if m.opCtx != nil {
+			httpClient = httpClient.WithContext(m.opCtx)
+		}
+		return httpClient
+	}
+	return m.nodeClient
+}
+
+// openDebugLog opens archon.log next to the config file for appending
+func openDebugLog(configPath string) (*os.File, error) {
+	configDir := filepath.Dir(configPath)
+	if err := os.MkdirAll(configDir, 0755); err != nil {
+		return nil, err
+	}
+	return os.OpenFile(filepath.Join(configDir, "archon.log"), os.O_CREATE|os.O_WRONLY|os.O_APPEND, 0600)
+}
+
+// applyConfig copies the loaded config's data and settings into the app state and returns the
+// theme they select. A theme file that can't be loaded is reported and the built-in theme used.
+func applyConfig(appState *state.AppState, cfg *config.Config) theme.Theme {
+	t, ok := theme.ByName(cfg.Settings.Theme)
+	var themeErr error
+	if cfg.Settings.ThemeFile != "" {
+		t, themeErr = theme.LoadFile(configRelativePath(appState.ConfigPath, cfg.Settings.ThemeFile), t)
+	}
+
 	appState.Sites = cfg.Sites
 	appState.Domains = cfg.Domains
 	appState.Nodes = cfg.Nodes
-	appState.ConfigPath = configPath
 	appState.AutoSave = cfg.Settings.AutoSave
+	appState.HealthCheckIntervalSecs = cfg.Settings.HealthCheckIntervalSecs
 	appState.CloudflareAPIToken = cfg.Settings.CloudflareAPIToken
 	appState.Route53AccessKey = cfg.Settings.Route53AccessKey
 	appState.Route53SecretKey = cfg.Settings.Route53SecretKey
+	appState.DebugHTTP = cfg.Settings.DebugHTTP
+	appState.LogBufferLines = cfg.Settings.LogBufferLines
+	appState.RetryMaxAttempts = cfg.Settings.RetryMaxAttempts
+	appState.RetryBaseDelayMs = cfg.Settings.RetryBaseDelayMs
+	appState.RetryJitter = cfg.Settings.RetryJitter
+	appState.DNSRateLimitRetries = cfg.Settings.DNSRateLimitRetries
+	appState.MaxConcurrentOperations = cfg.Settings.MaxConcurrentOperations
+	appState.PortRangeStart = cfg.Settings.PortRangeStart
+	appState.PortRangeEnd = cfg.Settings.PortRangeEnd
+	appState.LatencyWarnMs = cfg.Settings.LatencyWarnMs
+	appState.LatencyCriticalMs = cfg.Settings.LatencyCriticalMs
+	appState.ConfigBackups = cfg.Settings.ConfigBackups
+	appState.SecretStorage = cfg.Settings.SecretStorage
+	appState.AuditLog = cfg.Settings.AuditLog
+	appState.AuditLogMaxMB = cfg.Settings.AuditLogMaxMB
+	appState.DesktopNotifications = cfg.Settings.DesktopNotifications
+	appState.WebhookURL = cfg.Settings.WebhookURL
+	appState.Keybindings = cfg.Settings.Keybindings
+
+	switch {
+	case cfg.Settings.SecretStorage == config.SecretStorageKeyring && config.SystemKeyring() == nil:
+		appState.AddNotification("No OS keyring available, secrets are stored in the config file", "warning")
+	case cfg.Settings.SecretStorage != "" && cfg.Settings.SecretStorage != config.SecretStorageKeyring:
+		appState.AddNotification(fmt.Sprintf("Unknown secret_storage %q, secrets are stored in the config file", cfg.Settings.SecretStorage), "warning")
+	}
 
-	return &Model{
-		state:        appState,
-		nodeClient:   api.NewHTTPNodeClient(),
-		configLoader: loader,
-		configPath:   configPath,
-		zone:         zone.New(),
-	}, nil
+	keymap, unknown := config.NewKeymap(cfg.Settings.Keybindings)
+	appState.Keymap = keymap
+	if len(unknown) > 0 {
+		appState.AddNotification("Ignoring unknown keybindings: "+strings.Join(unknown, ", "), "warning")
+	}
+
+	appState.ThemeName = cfg.Settings.Theme
+	appState.ThemeFile = cfg.Settings.ThemeFile
+	if !ok {
+		appState.AddNotification(fmt.Sprintf("Unknown theme %q, using the default theme", cfg.Settings.Theme), "warning")
+	}
+	if themeErr != nil {
+		appState.AddNotification(themeErr.Error()+", using the built-in theme", "warning")
+	}
+	return t
+}
+
+// configRelativePath resolves a relative path setting, such as theme_file, against the config file's directory
+func configRelativePath(configPath, path string) string {
+	if filepath.IsAbs(path) || configPath == "" {
+		return path
+	}
+	return filepath.Join(filepath.Dir(configPath), path)
+}
+
+// openAuditLog returns the audit log set up in settings, or nil if it is turned off
+func openAuditLog(configPath string, settings config.Settings) *audit.Log {
+	path := settings.AuditLog
+	switch path {
+	case config.AuditLogOff:
+		return nil
+	case "":
+		path = config.DefaultAuditLog
+	}
+	return audit.New(configRelativePath(configPath, path), int64(settings.AuditLogMaxMB)*1024*1024)
+}
+
+// tableNavKey translates a key press into the table's own navigation key, applying the keymap
+// so rebound up/down keys move the selection. It returns false for keys the table doesn't handle.
+func (m Model) tableNavKey(msg tea.KeyMsg) (tea.KeyMsg, bool) {
+	key, keys := msg.String(), m.state.Keymap
+	switch {
+	case keys.Matches(key, config.KeyUp):
+		return tea.KeyMsg{Type: tea.KeyUp}, true
+	case keys.Matches(key, config.KeyDown):
+		return tea.KeyMsg{Type: tea.KeyDown}, true
+	case keys.Matches(key, config.KeyTop):
+		return tea.KeyMsg{Type: tea.KeyHome}, true
+	case keys.Matches(key, config.KeyBottom):
+		return tea.KeyMsg{Type: tea.KeyEnd}, true
+	case key == "pgup" || key == "pgdown":
+		return msg, true
+	}
+	return msg, false
 }
 
 // Init is called once when the program starts (TEA pattern)
@@ -65,8 +377,15 @@ func NewModel(configPath string) (*Model, error) {
 func (m Model) Init() tea.Cmd {
 	// Return batch of initialization commands
 	return tea.Batch(
-		tea.EnterAltScreen,        // Enable alternate screen buffer
-		tea.EnableMouseCellMotion, // MOUSE SUPPORT: Enable mouse events
+		tea.EnterAltScreen,         // Enable alternate screen buffer
+		tea.EnableMouseCellMotion,  // MOUSE SUPPORT: Enable mouse events
+		m.scheduleHealthCheck(),    // Start periodic node health checks
+		m.scheduleMetricsRefresh(), // Start periodic site detail metrics refresh
+		checkCertsNow,              // Check domain certificates now, then periodically
+		m.scheduleSiteHealth(),     // Start periodic site health probes
+		m.scheduleConfigWatch(),    // Reload the config when it is edited outside Archon
+		m.resumeDeploys(),          // Find out how deploys cut short by the last exit ended
+		m.listenDeployProgress(),   // Show blue/green deploy phases as nodes report them
 	)
 }
 
@@ -80,34 +399,30 @@ func (m Model) Update(msg tea.Msg) (tea.Model, tea.Cmd) {
 	// ========================================================================
 
 	case tea.KeyMsg:
+		// The confirmation dialog, command palette and sites search take every key while open
+		if m.state.PendingConfirmation != nil || m.state.Palette != nil || m.state.SiteSearchActive {
+			return m.handleKeyPress(msg)
+		}
+
 		// Let table handle navigation on list screens
 		switch m.state.CurrentScreen {
 		case state.ScreenSitesList:
-			if m.state.SitesTable != nil {
-				switch msg.String() {
-				case "up", "k", "down", "j", "pgup", "pgdown", "home", "end":
-					cmd := m.state.SitesTable.Update(msg)
-					m.state.SitesListIndex = m.state.SitesTable.GetCursor()
-					return m, cmd
-				}
+			if nav, ok := m.tableNavKey(msg); ok && m.state.SitesTable != nil {
+				cmd := m.state.SitesTable.Update(nav)
+				m.state.SitesListIndex = m.state.SitesTable.GetCursor()
+				return m, cmd
 			}
 		case state.ScreenDomainsList:
-			if m.state.DomainsTable != nil {
-				switch msg.String() {
-				case "up", "k", "down", "j", "pgup", "pgdown", "home", "end":
-					cmd := m.state.DomainsTable.Update(msg)
-					m.state.DomainsListIndex = m.state.DomainsTable.GetCursor()
-					return m, cmd
-				}
+			if nav, ok := m.tableNavKey(msg); ok && m.state.DomainsTable != nil {
+				cmd := m.state.DomainsTable.Update(nav)
+				m.state.DomainsListIndex = m.state.DomainsTable.GetCursor()
+				return m, cmd
 			}
 		case state.ScreenNodesList:
-			if m.state.NodesTable != nil {
-				switch msg.String() {
-				case "up", "k", "down", "j", "pgup", "pgdown", "home", "end":
-					cmd := m.state.NodesTable.Update(msg)
-					m.state.NodesListIndex = m.state.NodesTable.GetCursor()
-					return m, cmd
-				}
+			if nav, ok := m.tableNavKey(msg); ok && m.state.NodesTable != nil {
+				cmd := m.state.NodesTable.Update(nav)
+				m.state.NodesListIndex = m.state.NodesTable.GetCursor()
+				return m, cmd
 			}
 		}
 
@@ -115,6 +430,11 @@ func (m Model) Update(msg tea.Msg) (tea.Model, tea.Cmd) {
 		return m.handleKeyPress(msg)
 
 	case tea.MouseMsg:
+		// The confirmation dialog and command palette are modal; ignore clicks while open
+		if m.state.PendingConfirmation != nil || m.state.Palette != nil {
+			return m, nil
+		}
+
 		// Handle bubblezone clicks
 		if msg.Action == tea.MouseActionRelease && msg.Button == tea.MouseButtonLeft {
 			// Check if click was on a tab
@@ -139,10 +459,20 @@ func (m Model) Update(msg tea.Msg) (tea.Model, tea.Cmd) {
 				return m, nil
 			}
 			if m.zone.Get("tab:help").InBounds(msg) {
+				m.state.HelpScroll = 0
 				m.state.NavigateTo(state.ScreenHelp)
 				return m, nil
 			}
 
+			// Failed sites on the dashboard open their detail screen
+			if m.state.CurrentScreen == state.ScreenDashboard {
+				for _, site := range m.state.FailedSites() {
+					if m.zone.Get("alert:site-" + site.ID.String()).InBounds(msg) {
+						return m.openSiteDetail(site.ID, state.SiteDetailTabOverview)
+					}
+				}
+			}
+
 			// Check if click was on a button
 			if m.zone.Get("button:create-site").InBounds(msg) {
 				m.state.NavigateTo(state.ScreenSiteCreate)
@@ -158,8 +488,8 @@ func (m Model) Update(msg tea.Msg) (tea.Model, tea.Cmd) {
 			}
 
 			// Check for row action buttons (deploy/stop/restart/edit/delete/view)
-			// Sites
-			for i, site := range m.state.Sites {
+			// Sites (index matches the filtered table rows)
+			for i, site := range m.state.VisibleSites() {
 				setupDnsID := "button:setup-dns-" + site.ID.String()
 				deployID := "button:deploy-site-" + site.ID.String()
 				stopID := "button:stop-site-" + site.ID.String()
@@ -185,7 +515,8 @@ func (m Model) Update(msg tea.Msg) (tea.Model, tea.Cmd) {
 					}
 
 					// Deploy site
-					return m, m.spawnDeploySite(site.ID)
+					siteID := site.ID
+					return m, func() tea.Msg { return DeploySiteMsg{SiteID: siteID} }
 				}
 				if m.zone.Get(stopID).InBounds(msg) {
 					// Sync table cursor
@@ -195,7 +526,8 @@ func (m Model) Update(msg tea.Msg) (tea.Model, tea.Cmd) {
 					}
 
 					// Stop site
-					return m, m.spawnStopSite(site.ID)
+					op := m.startOperation(state.OpStopSite, site.Name, StopSiteMsg{SiteID: site.ID})
+					return m, op.track(op.spawnStopSite(site.ID))
 				}
 				if m.zone.Get(restartID).InBounds(msg) {
 					// Sync table cursor
@@ -205,7 +537,9 @@ func (m Model) Update(msg tea.Msg) (tea.Model, tea.Cmd) {
 					}
 
 					// Restart site
-					return m, m.spawnRestartSite(site.ID)
+					m.state.ResetAutoRestarts(site.ID)
+					op := m.startOperation(state.OpRestartSite, site.Name, RestartSiteMsg{SiteID: site.ID})
+					return m, op.track(op.spawnRestartSite(site.ID))
 				}
 				if m.zone.Get(editID).InBounds(msg) {
 					// Sync table cursor
@@ -226,8 +560,8 @@ func (m Model) Update(msg tea.Msg) (tea.Model, tea.Cmd) {
 						m.state.SitesTable.SetCursor(i)
 					}
 
-					// Delete site
-					return m.handleDeleteSite(site.ID)
+					// Delete site (after confirmation)
+					return m.requestDelete("site", site.ID, site.Name)
 				}
 			}
 
@@ -255,13 +589,13 @@ func (m Model) Update(msg tea.Msg) (tea.Model, tea.Cmd) {
 						m.state.DomainsTable.SetCursor(i)
 					}
 
-					// Delete domain
-					return m.handleDeleteDomain(domain.ID)
+					// Delete domain (after confirmation)
+					return m.requestDelete("domain", domain.ID, domain.Name)
 				}
 			}
 
 			// Nodes
-			for i, node := range m.state.Nodes {
+			for i, node := range m.state.VisibleNodes() {
 				viewID := "button:view-node-" + node.ID.String()
 				editID := "button:edit-node-" + node.ID.String()
 				deleteID := "button:delete-node-" + node.ID.String()
@@ -297,8 +631,8 @@ func (m Model) Update(msg tea.Msg) (tea.Model, tea.Cmd) {
 						m.state.NodesTable.SetCursor(i)
 					}
 
-					// Delete node
-					return m.handleDeleteNode(node.ID)
+					// Delete node (after confirmation)
+					return m.requestDelete("node", node.ID, node.Name)
 				}
 			}
 
@@ -456,28 +790,88 @@ func (m Model) Update(msg tea.Msg) (tea.Model, tea.Cmd) {
 		// }
 		return m, nil
 
+	case RestoreSiteMsg:
+		return m.handleRestoreSite(msg.SiteID)
+
 	case DeploySiteMsg:
 		// Update status to deploying
 		site := m.state.GetSiteByID(msg.SiteID)
 		if site != nil {
 			site.Status = models.SiteStatusDeploying
 			site.UpdatedAt = time.Now()
+			m.recordDeploying()
 		}
 		// Spawn async deployment operation
-		return m, m.spawnDeploySite(msg.SiteID)
+		op := m.startOperation(state.OpDeploySite, m.siteName(msg.SiteID), msg)
+		return m, op.track(op.spawnDeploySite(msg.SiteID, msg.Image))
+
+	case RollbackSiteMsg:
+		// Deploy the earlier image in place of the configured one, which the site keeps
+		site := m.state.GetSiteByID(msg.SiteID)
+		if site == nil {
+			return m, nil
+		}
+		if site.Status == models.SiteStatusDeploying {
+			m.state.AddNotification(fmt.Sprintf("Cannot roll back %s while it is deploying", site.Name), "warning")
+			return m, nil
+		}
+		m.state.AddNotification(fmt.Sprintf("Rolling back %s to %s", site.Name, msg.Image), "info")
+		return m.Update(DeploySiteMsg{SiteID: msg.SiteID, Image: msg.Image})
+
+	case SiteDeployProgressMsg:
+		m.state.AddNotification(fmt.Sprintf("%s: %s", m.siteName(msg.SiteID), msg.Message), "info")
+		return m, m.listenDeployProgress()
+
+	case PreviewDeployMsg:
+		request, err := m.buildDeployPreview(msg.SiteID)
+		if err != nil {
+			m.state.AddNotification("Cannot preview deploy: "+err.Error(), "error")
+			return m, nil
+		}
+		m.state.DeployPreview = &state.DeployPreview{SiteID: msg.SiteID, Request: request}
+		m.state.DeployPreviewViewport.GotoTop()
+		m.state.NavigateTo(state.ScreenDeployPreview)
+		return m, nil
 
 	case SiteDeployedMsg:
 		// Update site status in state
 		site := m.state.GetSiteByID(msg.SiteID)
 		if site != nil {
+			record := models.DeploymentRecord{Timestamp: time.Now(), Image: site.DeployedImage(), Outcome: models.DeploymentSucceeded}
+			if msg.Image != "" {
+				record.Image = msg.Image
+			}
+			if msg.Response != nil {
+				record.ContainerID = msg.Response.ContainerID
+			}
+			if msg.Error != nil {
+				record.Outcome = models.DeploymentFailed
+				record.Error = msg.Error.Error()
+			} else if partial := msg.partialFailure(); partial != nil {
+				record.Error = partial.Error()
+			}
+			site.RecordDeployment(record)
+			recordDeployResult(site, msg)
+			if msg.Image != "" && msg.Error == nil {
+				m.state.AddNotification(fmt.Sprintf("Rolled back %s to %s; deploying it again deploys %s", site.Name, msg.Image, site.DeployedImage()), "success")
+			}
+
 			if msg.Error != nil {
-				site.Status = models.SiteStatusFailed
 				m.state.AddNotification("Deployment failed: "+msg.Error.Error(), "error")
+				m.sendWebhook(api.WebhookDeployFailed, "site", site.ID, site.Name, msg.Error)
+				// Pull the build output so the cause of a failed build is visible
+				if site.IsBuild() {
+					return m, m.spawnFetchNodeLogs(site.ID, 100, true)
+				}
+			} else if partial := msg.partialFailure(); partial != nil {
+				m.state.AddNotification("Site partly deployed: "+partial.Error(), "warning")
+				m.sendWebhook(api.WebhookDeployFailed, "site", site.ID, site.Name, partial)
 			} else {
-				site.Status = models.SiteStatusRunning
+				m.state.ResetAutoRestarts(site.ID)
 				m.state.AddNotification("Site deployed successfully", "success")
+				m.sendWebhook(api.WebhookDeploySucceeded, "site", site.ID, site.Name, nil)
 			}
-			site.UpdatedAt = time.Now()
+			m.recordDeploying()
 			// Trigger auto-save if enabled
 			if m.state.AutoSave {
 				return m, m.saveConfig()
@@ -485,6 +879,46 @@ func (m Model) Update(msg tea.Msg) (tea.Model, tea.Cmd) {
 		}
 		return m, nil
 
+	case ResumeDeployMsg:
+		return m, m.spawnResumeDeploy(msg.SiteID)
+
+	case DeployResumedMsg:
+		// Settle a deploy cut short by the last exit from the node's status of the site
+		site := m.state.GetSiteByID(msg.SiteID)
+		if site == nil || site.Status != models.SiteStatusDeploying {
+			return m, nil
+		}
+		switch {
+		case msg.Error != nil:
+			site.Status = models.SiteStatusFailed
+			site.LastError = "Deploy interrupted when Archon exited, and the node couldn't be checked: " + msg.Error.Error()
+			m.state.AddNotification(fmt.Sprintf("Deploy of %s was interrupted when Archon exited; redeploy it", site.Name), "warning")
+		case msg.Status == models.SiteStatusRunning && msg.startedSince(m.state.DeployStarted(site.ID)):
+			site.Status = models.SiteStatusRunning
+			site.LastError = ""
+			m.state.AddNotification(fmt.Sprintf("%s finished deploying while Archon was closed", site.Name), "success")
+		case time.Since(m.state.DeployStarted(site.ID)) < resumeDeployTimeout:
+			// The node may still be pulling or building the image, with the old container
+			// running or none at all
+			return m, tea.Tick(resumeDeployRecheck, func(time.Time) tea.Msg {
+				return ResumeDeployMsg{SiteID: msg.SiteID}
+			})
+		case msg.Status == models.SiteStatusRunning:
+			site.Status = models.SiteStatusFailed
+			site.LastError = "Deploy interrupted when Archon exited (the node still runs the previous container)"
+			m.state.AddNotification(fmt.Sprintf("Deploy of %s was interrupted when Archon exited; redeploy it", site.Name), "warning")
+		default:
+			site.Status = models.SiteStatusFailed
+			site.LastError = fmt.Sprintf("Deploy interrupted when Archon exited (the node reports %s)", msg.Status)
+			m.state.AddNotification(fmt.Sprintf("Deploy of %s was interrupted when Archon exited; redeploy it", site.Name), "warning")
+		}
+		site.UpdatedAt = time.Now()
+		m.recordDeploying()
+		if m.state.AutoSave {
+			return m, m.saveConfig()
+		}
+		return m, nil
+
 	case DNSSetupResultMsg:
 		// Handle DNS setup result
 		if msg.Error != nil {
@@ -494,13 +928,36 @@ func (m Model) Update(msg tea.Msg) (tea.Model, tea.Cmd) {
 		}
 		return m, nil
 
+	case StopSiteMsg:
+		// Spawn async stop; SiteOperationResultMsg marks the site stopped
+		op := m.startOperation(state.OpStopSite, m.siteName(msg.SiteID), msg)
+		return m, op.track(op.spawnStopSite(msg.SiteID))
+
+	case RestartSiteMsg:
+		// Spawn async restart; SiteOperationResultMsg marks the site running
+		m.state.ResetAutoRestarts(msg.SiteID)
+		op := m.startOperation(state.OpRestartSite, m.siteName(msg.SiteID), msg)
+		return m, op.track(op.spawnRestartSite(msg.SiteID))
+
+	case StopAllOnNodeMsg:
+		return m, m.startBulk(msg.NodeID, state.OpStopSite)
+
+	case DeployAllOnNodeMsg:
+		return m, m.startBulk(msg.NodeID, state.OpDeploySite)
+
 	case SiteOperationResultMsg:
 		// Handle stop/restart operation results
 		site := m.state.GetSiteByID(msg.SiteID)
 		if site != nil {
 			if msg.Error != nil {
+				site.LastError = fmt.Sprintf("%s failed: %s", msg.Operation, msg.Error.Error())
 				m.state.AddNotification(fmt.Sprintf("Failed to %s site: %s", msg.Operation, msg.Error.Error()), "error")
+				// A failed restart leaves the site down, so try again while it has attempts left
+				if msg.Operation == "restart" {
+					return m, m.autoRestart(site.ID)
+				}
 			} else {
+				site.LastError = ""
 				// Update status based on operation
 				switch msg.Operation {
 				case "stop":
@@ -521,6 +978,113 @@ func (m Model) Update(msg tea.Msg) (tea.Model, tea.Cmd) {
 		}
 		return m, nil
 
+	case FetchNodeLogsMsg:
+		// Spawn async log fetch
+		return m, m.spawnFetchNodeLogs(msg.SiteID, msg.Lines, msg.Build)
+
+	case NodeLogsResultMsg:
+		// Drop results from a follow that was since stopped and restarted, so only one poll
+		// chain runs and the log cursor never moves backwards
+		if msg.FollowGen != 0 && msg.FollowGen != m.state.FollowLogsGen {
+			return m, nil
+		}
+
+		// Handle fetched logs
+		if msg.Error != nil {
+			m.state.AddNotification("Failed to fetch logs: "+msg.Error.Error(), "error")
+			// Stop the logs tab waiting on a fetch that failed, but keep any earlier logs
+			if _, ok := m.state.GetLogs(msg.SiteID); !ok && !msg.Build {
+				m.state.AppendLogs(msg.SiteID, nil)
+			}
+			// Stop following rather than report the same failure every second
+			if !msg.Build && m.state.FollowLogs == msg.SiteID {
+				m.state.FollowLogs = uuid.Nil
+			}
+			return m, nil
+		}
+		if msg.Build {
+			m.state.AddNotification(fmt.Sprintf("Build output (%d lines): %s", len(msg.Logs), lastLogLine(msg.Logs)), "info")
+			return m, nil
+		}
+		// Lines fetched after a cursor are all new; nodes without cursors return the whole tail
+		if msg.Since != "" && msg.Cursor != "" {
+			m.state.AppendNewLogs(msg.SiteID, msg.Logs, msg.Cursor)
+		} else {
+			m.state.AppendLogs(msg.SiteID, msg.Logs)
+			m.state.SetLogCursor(msg.SiteID, msg.Cursor)
+		}
+
+		// Poll again once this fetch has landed, so slow nodes never have overlapping requests
+		if msg.FollowGen != 0 && m.state.FollowLogs == msg.SiteID {
+			return m, m.scheduleLogFollow(msg.SiteID, msg.FollowGen)
+		}
+		return m, nil
+
+	case LogFollowTickMsg:
+		if m.state.FollowLogs != msg.SiteID || msg.Gen != m.state.FollowLogsGen {
+			return m, nil
+		}
+		// Stop following once the logs tab is no longer showing this site
+		if m.state.CurrentScreen != state.ScreenSiteDetail || m.state.SelectedSiteID != msg.SiteID ||
+			m.state.SiteDetailTab != state.SiteDetailTabLogs {
+			m.state.FollowLogs = uuid.Nil
+			return m, nil
+		}
+		return m, m.spawnFollowLogs(msg.SiteID, msg.Gen)
+
+	case LogsExportedMsg:
+		if msg.Error != nil {
+			m.state.AddNotification("Failed to export logs: "+msg.Error.Error(), "error")
+			return m, nil
+		}
+		m.state.AddNotification("Logs saved to "+msg.Path, "success")
+		return m, nil
+
+	case FetchNodeMetricsMsg:
+		// Spawn async metrics fetch
+		return m, m.spawnFetchNodeMetrics(msg.SiteID)
+
+	case NodeMetricsResultMsg:
+		// Handle fetched metrics
+		if msg.Error != nil {
+			m.state.SetMetrics(msg.SiteID, nil)
+			// Periodic refreshes fail quietly, e.g. while the site is stopped
+			if !msg.Background {
+				m.state.AddNotification("Failed to fetch metrics: "+msg.Error.Error(), "error")
+			}
+			return m, nil
+		}
+		m.state.SetMetrics(msg.SiteID, msg.Metrics)
+		return m, nil
+
+	case SiteStatusResultMsg:
+		// Apply live status fetched from the node
+		if msg.Error != nil {
+			// Background polls fail quietly, e.g. while the node is briefly unreachable
+			if !msg.Background {
+				m.state.AddNotification("Failed to refresh site status: "+msg.Error.Error(), "error")
+			}
+			return m, nil
+		}
+		if site := m.state.GetSiteByID(msg.SiteID); site != nil && msg.Status != "" {
+			// Nodes report a crashed container as stopped; only a stop from Archon leaves a site
+			// stopped, so a running site whose container stopped by itself has failed
+			status := msg.Status
+			if status == models.SiteStatusStopped && (site.Status == models.SiteStatusRunning || site.Status == models.SiteStatusFailed) &&
+				!m.stopPending(site.ID) {
+				status = models.SiteStatusFailed
+				if site.Status != models.SiteStatusFailed {
+					site.LastError = "Container stopped unexpectedly"
+				}
+			}
+			site.Status = status
+			m.state.SetContainerStatus(msg.SiteID, msg.Container)
+			if site.Status == models.SiteStatusFailed {
+				return m, m.autoRestart(site.ID)
+			}
+		}
+		return m, nil
+
 	// ========================================================================
 	// Domain Operations
 	// ========================================================================
@@ -531,12 +1095,104 @@ func (m Model) Update(msg tea.Msg) (tea.Model, tea.Cmd) {
 
 	case SyncDnsMsg:
 		// Spawn async DNS sync operation
-		return m, m.spawnSyncDns(msg.DomainID)
+		op := m.startOperation(state.OpSyncDns, m.domainName(msg.DomainID), msg)
+		return m, op.track(op.spawnSyncDns(msg.DomainID, msg.Preview))
 
 	case DnsSyncedMsg:
-		// TODO: Update domain's DNS records with synced data
+		domain := m.state.GetDomainByID(msg.DomainID)
+		if domain == nil {
+			return m, nil
+		}
+		if msg.Error != nil {
+			m.state.AddNotification("DNS sync failed: "+msg.Error.Error(), "error")
+			m.sendWebhook(api.WebhookDnsSyncFailed, "domain", domain.ID, domain.Name, msg.Error)
+			return m, nil
+		}
+
+		// Show what would change and wait for approval, unless nothing differs
+		if msg.Preview {
+			diff := dns.DiffRecords(domain.DnsRecords, msg.Records)
+			if !diff.IsEmpty() {
+				m.state.PendingDnsSync = &state.DnsSyncPreview{DomainID: domain.ID, Remote: msg.Records, Diff: diff}
+				if m.state.CurrentScreen != state.ScreenDnsSyncDiff {
+					m.state.NavigateTo(state.ScreenDnsSyncDiff)
+				}
+				return m, nil
+			}
+		}
+
+		return m, m.applyDnsSync(domain, msg.Records)
+
+	case CreateDnsRecordMsg:
+		return m.handleSaveDnsRecord(msg.DomainID, msg.Record, -1)
+
+	case UpdateDnsRecordMsg:
+		return m.handleSaveDnsRecord(msg.DomainID, msg.Record, msg.Index)
+
+	case DeleteDnsRecordMsg:
+		return m.handleDeleteDnsRecord(msg.DomainID, msg.Index)
+
+	case PushDnsRecordsMsg:
+		// Spawn async DNS push operation
+		op := m.startOperation(state.OpPushDns, m.domainName(msg.DomainID), msg)
+		return m, op.track(op.spawnPushDnsRecords(msg.DomainID, msg.DeleteRemote))
+
+	case DnsRecordsPushedMsg:
+		domain := m.state.GetDomainByID(msg.DomainID)
+		if domain == nil {
+			return m, nil
+		}
+		if msg.Error != nil {
+			m.state.AddNotification("DNS push failed: "+msg.Error.Error(), "error")
+			return m, nil
+		}
+
+		// Records may have been edited while the push ran, so only the pushed ones are updated
+		domain.DnsRecords = dns.ApplyPush(domain.DnsRecords, msg.Sent, msg.Records)
+
+		// Report each record separately so failures are easy to spot
+		failed := 0
+		for _, outcome := range msg.Outcomes {
+			if outcome.Error != nil {
+				failed++
+				m.state.AddNotification(outcome.Describe(), "error")
+			} else {
+				m.state.AddNotification(outcome.Describe(), "success")
+			}
+		}
+		switch {
+		case len(msg.Outcomes) == 0:
+			m.state.AddNotification("DNS records for "+domain.Name+" already match the provider", "info")
+		case failed > 0:
+			m.state.AddNotification(fmt.Sprintf("DNS push for %s: %d of %d change(s) failed", domain.Name, failed, len(msg.Outcomes)), "warning")
+		default:
+			m.state.AddNotification(fmt.Sprintf("DNS push for %s: %d change(s) applied", domain.Name, len(msg.Outcomes)), "success")
+		}
+
+		// Trigger auto-save if enabled
+		if m.state.AutoSave {
+			return m, m.saveConfig()
+		}
+		m.state.Dirty = true
 		return m, nil
 
+	case ApplyDnsSyncMsg:
+		pending := m.state.PendingDnsSync
+		m.state.PendingDnsSync = nil
+		if pending == nil {
+			return m, nil
+		}
+		if m.state.CurrentScreen == state.ScreenDnsSyncDiff {
+			m.state.NavigateBack()
+		}
+
+		domain := m.state.GetDomainByID(pending.DomainID)
+		if domain == nil {
+			m.state.AddNotification("Domain no longer exists", "error")
+			return m, nil
+		}
+		return m, m.applyDnsSync(domain, pending.Remote)
+
 	// ========================================================================
 	// Node Operations
 	// ========================================================================
@@ -550,54 +1206,440 @@ func (m Model) Update(msg tea.Msg) (tea.Model, tea.Cmd) {
 		return m, m.spawnNodeHealthCheck(msg.NodeID)
 
 	case NodeHealthCheckResultMsg:
-		// Node status is already updated in spawnNodeHealthCheck
+		// Node status is already updated in spawnNodeHealthCheck on success
 		if msg.Error != nil {
-			m.state.AddNotification("Node health check failed: "+msg.Error.Error(), "error")
-		}
-		return m, nil
+			node := m.state.GetNodeByID(msg.NodeID)
+			var previous models.NodeStatus
+			// A node that answered with an error is up but broken (e.g. Docker is down)
+			status := models.NodeStatusDegraded
+			if api.IsUnreachable(msg.Error) {
+				status = models.NodeStatusOffline
+			}
+			if node != nil {
+				previous = node.Status
+				node.Status = status
+			}
+			// Background checks only notify when a node's status changes, not on every failed check
+			switch {
+			case msg.Saved && node != nil:
+				// Expected for a new node until the node server is installed on it
+				m.state.AddNotification("Node "+node.Name+" is not reachable yet: "+msg.Error.Error(), "warning")
+			case !msg.Background:
+				m.state.AddNotification("Node health check failed: "+msg.Error.Error(), "error")
+			case node == nil || previous == status:
+				// Already reported when the status changed
+			case status == models.NodeStatusOffline:
+				m.state.AddNotification("Node "+node.Name+" is offline: "+msg.Error.Error(), "warning")
+				m.state.NotifyDesktop("Node " + node.Name + " is offline")
+				m.sendWebhook(api.WebhookNodeOffline, "node", node.ID, node.Name, msg.Error)
+			default:
+				m.state.AddNotification("Node "+node.Name+" is degraded: "+msg.Error.Error(), "warning")
+			}
 
-	// ========================================================================
-	// Form Handling
-	// ========================================================================
+			// Keep retrying an unreachable node with backoff, independent of the periodic check
+			if node != nil && status == models.NodeStatusOffline {
+				if msg.Reconnect {
+					return m, m.scheduleNodeReconnect(node.ID, m.state.NextReconnectDelay(node.ID))
+				}
+				if delay, ok := m.state.StartReconnect(node.ID); ok {
+					return m, m.scheduleNodeReconnect(node.ID, delay)
+				}
+				return m, nil
+			}
+			m.state.StopReconnect(msg.NodeID)
+			return m, nil
+		}
 
-	case FormInputMsg:
-		// TODO: Append character to current form field
-		// if m.state.CurrentFieldIndex < len(m.state.FormFields) {
-		//     m.state.FormFields[m.state.CurrentFieldIndex] += string(msg.Char)
-		// }
+		if _, ok := m.state.StopReconnect(msg.NodeID); ok {
+			if node := m.state.GetNodeByID(msg.NodeID); node != nil {
+				m.state.AddNotification(fmt.Sprintf("Node %s is reachable again (%s)", node.Name, node.Status), "success")
+			}
+		} else if msg.Saved {
+			// Confirm a new or moved node is reachable; other checks just update its status
+			if node := m.state.GetNodeByID(msg.NodeID); node != nil {
+				details := string(node.Status)
+				if node.LastLatencyMs != nil {
+					details += fmt.Sprintf(", %dms", *node.LastLatencyMs)
+				}
+				m.state.AddNotification(fmt.Sprintf("Node %s is reachable (%s)", node.Name, details), "success")
+			}
+		}
 		return m, nil
 
-	case FormBackspaceMsg:
-		// TODO: Remove last character from current field
-		return m, nil
+	case NodeReconnectMsg:
+		// Stop if the node was deleted or another health check has already reached it
+		node := m.state.GetNodeByID(msg.NodeID)
+		if node == nil || node.Status != models.NodeStatusOffline {
+			m.state.StopReconnect(msg.NodeID)
+			return m, nil
+		}
+		check := m.spawnNodeHealthCheck(msg.NodeID)
+		return m, func() tea.Msg {
+			result := check().(NodeHealthCheckResultMsg)
+			result.Background = true
+			result.Reconnect = true
+			return result
+		}
 
-	case FormSubmitMsg:
-		// Delegate to form submit handler
-		return m.handleFormSubmit()
+	case TestNodeConnectionMsg:
+		m.state.AddNotification("Testing connection to "+msg.Endpoint+"...", "info")
+		return m, m.spawnTestNodeConnection(msg.Endpoint, msg.APIKey)
 
-	// ========================================================================
-	// Configuration
-	// ========================================================================
+	case NodeConnectionTestedMsg:
+		if msg.Error != nil {
+			m.state.AddNotification("Connection to "+msg.Endpoint+" failed: "+msg.Error.Error(), "error")
+			return m, nil
+		}
+		details := fmt.Sprintf("%dms", msg.LatencyMs)
+		if msg.Result != nil && msg.Result.Docker != nil {
+			details += ", Docker " + msg.Result.Docker.Version
+		}
+		m.state.AddNotification(fmt.Sprintf("Connected to %s (%s)", msg.Endpoint, details), "success")
+		return m, nil
 
-	case SaveConfigMsg:
-		return m, m.saveConfig()
+	case CloudflareZoneLookedUpMsg:
+		// The form may have been left or changed while the lookup ran
+		if m.state.CurrentScreen != state.ScreenDomainCreate || len(m.state.FormFields) != 5 || m.state.FormFields[1] != "cloudflare" ||
+			!strings.EqualFold(strings.TrimSuffix(strings.TrimSpace(m.state.FormFields[0]), "."), msg.Domain) {
+			return m, nil
+		}
+		if msg.Error != nil {
+			// Fall back to entering the Zone ID by hand
+			m.state.CurrentFieldIndex = 2
+			if errors.Is(msg.Error, cloudflare.ErrZoneListDenied) {
+				m.state.AddNotification("The Cloudflare API token can't list zones; enter the Zone ID for "+msg.Domain+" by hand", "warning")
+			} else {
+				m.state.AddNotification("Couldn't look up the Cloudflare zone for "+msg.Domain+" ("+msg.Error.Error()+"); enter its Zone ID by hand", "warning")
+			}
+			return m, nil
+		}
+		m.state.FormFields[2] = msg.ZoneID
+		return m.handleDomainCreateSubmit()
 
-	case ConfigSavedMsg:
+	case CloudflareDomainVerifiedMsg:
+		// The form may have been left while the token was checked
+		if m.state.CurrentScreen != state.ScreenDomainCreate {
+			return m, nil
+		}
 		if msg.Error != nil {
-			m.state.AddNotification("Failed to save config: "+msg.Error.Error(), "error")
+			m.state.AddNotification("Cloudflare check failed for "+msg.Domain+": "+msg.Error.Error(), "error")
+			return m, nil
+		}
+		if m.state.DomainNameTaken(msg.Domain, uuid.Nil) {
+			m.state.AddNotification("Domain already exists: "+msg.Domain, "error")
+			return m, nil
+		}
+		return m.createDomain(msg.Domain, msg.Provider)
+
+	case TickMsg:
+		// Periodic health check of every node, then schedule the next one
+		return m, tea.Batch(append(m.backgroundHealthChecks(), m.scheduleHealthCheck())...)
+
+	case CertTickMsg:
+		// Background check of every domain's certificate, then schedule the next one. The checks
+		// come back together so the round is saved once.
+		checks := make([]tea.Cmd, 0, len(m.state.Domains))
+		for _, domain := range m.state.Domains {
+			checks = append(checks, m.spawnCheckCert(domain.ID))
+		}
+		return m, tea.Batch(m.scheduleCertCheck(), func() tea.Msg {
+			results := make([]CertCheckedMsg, len(checks))
+			var wg sync.WaitGroup
+			for i, check := range checks {
+				wg.Add(1)
+				go func() {
+					defer wg.Done()
+					results[i] = check().(CertCheckedMsg)
+					results[i].Background = true
+				}()
+			}
+			wg.Wait()
+			return CertsCheckedMsg{Results: results}
+		})
+
+	case CertsCheckedMsg:
+		changed := false
+		for _, result := range msg.Results {
+			if m.applyCertCheck(result) {
+				changed = true
+			}
+		}
+		// Trigger auto-save if enabled and an expiry moved, e.g. after a renewal
+		if changed && m.state.AutoSave {
+			return m, m.saveConfig()
 		}
 		return m, nil
 
-	// ========================================================================
-	// System
-	// ========================================================================
+	case CertCheckedMsg:
+		// Trigger auto-save if enabled and the expiry moved
+		if m.applyCertCheck(msg) && m.state.AutoSave {
+			return m, m.saveConfig()
+		}
+		return m, nil
+
+	case SiteHealthTickMsg:
+		// Probe every running site with a health path, then schedule the next round. Sites that
+		// restart automatically also have their status polled, so a crash is noticed off screen.
+		cmds := []tea.Cmd{m.scheduleSiteHealth()}
+		for _, site := range m.state.Sites {
+			if site.Status == models.SiteStatusRunning {
+				cmds = append(cmds, m.spawnCheckSiteHealth(site.ID))
+			}
+			if site.AutoRestart && (site.Status == models.SiteStatusRunning || site.Status == models.SiteStatusFailed) {
+				cmds = append(cmds, m.spawnPollSiteStatus(site.ID))
+			}
+		}
+		return m, tea.Batch(cmds...)
+
+	case SiteHealthCheckedMsg:
+		site := m.state.GetSiteByID(msg.SiteID)
+		if site == nil {
+			return m, nil
+		}
+		previous := m.state.GetSiteHealth(msg.SiteID)
+		if msg.Error != nil {
+			m.state.SetSiteHealth(msg.SiteID, models.SiteHealthUnhealthy)
+			if previous != models.SiteHealthUnhealthy {
+				m.state.AddNotification("Site "+site.Name+" is unhealthy: "+msg.Error.Error(), "warning")
+			}
+			return m, nil
+		}
+		m.state.SetSiteHealth(msg.SiteID, models.SiteHealthHealthy)
+		if previous == models.SiteHealthUnhealthy {
+			m.state.AddNotification("Site "+site.Name+" is healthy again", "success")
+		}
+		return m, nil
+
+	case PropagationCheckedMsg:
+		domain := m.state.GetDomainByID(msg.DomainID)
+		if domain == nil {
+			return m, nil
+		}
+		m.state.SetPropagation(msg.DomainID, msg.Results)
+
+		propagated := 0
+		for _, result := range msg.Results {
+			if result.Propagated() {
+				propagated++
+			}
+		}
+		level := "success"
+		if propagated < len(msg.Results) {
+			level = "warning"
+		}
+		m.state.AddNotification(fmt.Sprintf("%d of %d DNS records for %s resolve on every resolver", propagated, len(msg.Results), domain.Name), level)
+		return m, nil
+
+	case MetricsTickMsg:
+		// Refresh metrics while a site detail screen is open, and drop the history once it closes
+		if m.state.CurrentScreen != state.ScreenSiteDetail {
+			m.state.ClearMetricsHistory()
+			return m, m.scheduleMetricsRefresh()
+		}
+		fetch := m.spawnFetchNodeMetrics(m.state.SelectedSiteID)
+		return m, tea.Batch(func() tea.Msg {
+			result := fetch().(NodeMetricsResultMsg)
+			result.Background = true
+			return result
+		}, m.scheduleMetricsRefresh())
+
+	// ========================================================================
+	// Form Handling
+	// ========================================================================
+
+	case FormInputMsg:
+		// TODO: Append character to current form field
+		// if m.state.CurrentFieldIndex < len(m.state.FormFields) {
+		//     m.state.FormFields[m.state.CurrentFieldIndex] += string(msg.Char)
+		// }
+		return m, nil
+
+	case FormBackspaceMsg:
+		// TODO: Remove last character from current field
+		return m, nil
+
+	case FormSubmitMsg:
+		// Delegate to form submit handler
+		return m.handleFormSubmit()
+
+	// ========================================================================
+	// Configuration
+	// ========================================================================
+
+	case SaveConfigMsg:
+		return m, m.saveConfigCmd(true)
+
+	case ReloadConfigMsg:
+		return m, m.spawnReloadConfig()
+
+	case ConfigReloadedMsg:
+		if msg.Error != nil {
+			m.state.AddNotification("Failed to reload config: "+msg.Error.Error(), "error")
+			return m, nil
+		}
+		m.theme = applyConfig(m.state, msg.Config)
+		m.state.SitesListIndex = 0
+		m.state.DomainsListIndex = 0
+		m.state.NodesListIndex = 0
+		m.markConfigSynced()
+		m.state.AddNotification("Config reloaded from "+m.configPath, "success")
+		return m, nil
+
+	case ConfigWatchTickMsg:
+		// Reload an outside edit once the file has settled, asking first if it would discard unsaved changes
+		next := m.scheduleConfigWatch()
+		modTime := configModTime(m.configPath)
+		changed := !modTime.IsZero() && !modTime.Equal(m.watch.loaded)
+		settled := modTime.Equal(m.watch.seen)
+		m.watch.seen = modTime
+		if !changed || !settled || m.state.PendingConfirmation != nil || m.watch.saving > 0 {
+			return m, next
+		}
+
+		// Only ask once per edit, whatever the answer
+		m.watch.loaded = modTime
+		if m.hasUnsavedChanges() {
+			m.state.PendingConfirmation = &state.Confirmation{
+				Kind:    configReloadConfirmKind,
+				Name:    filepath.Base(m.configPath),
+				Title:   "Reload config?",
+				Message: filepath.Base(m.configPath) + " was changed outside Archon.\nReloading it discards your unsaved changes.",
+			}
+			return m, next
+		}
+		m.state.AddNotification("Config changed on disk, reloading", "info")
+		return m, tea.Batch(next, m.spawnReloadConfig())
+
+	// ========================================================================
+	// Live Data
+	// ========================================================================
+
+	case RefreshMsg:
+		return m, m.refreshLiveData()
+
+	case ConfigSavedMsg:
+		if m.watch.saving > 0 {
+			m.watch.saving--
+		}
+		if msg.Error != nil {
+			m.state.AddNotification("Failed to save config: "+msg.Error.Error(), "error")
+			return m, nil
+		}
+		if msg.Fingerprint != nil {
+			m.markConfigSaved(msg.Fingerprint)
+			// Changes made while the save ran are still unsaved
+			if current, err := configFingerprint(m.currentConfig()); err == nil && bytes.Equal(current, msg.Fingerprint) {
+				m.state.Dirty = false
+			}
+		}
+		if msg.Announce {
+			m.state.AddNotification("Config saved to "+m.configPath, "success")
+		}
+		return m, nil
+
+	// ========================================================================
+	// System
+	// ========================================================================
 
 	case NotificationMsg:
 		m.state.AddNotification(msg.Message, msg.Level)
 		return m, nil
 
+	case tea.FocusMsg:
+		m.state.TerminalFocused = true
+		return m, nil
+
+	case tea.BlurMsg:
+		m.state.TerminalFocused = false
+		return m, nil
+
+	case AsyncResultMsg:
+		failed := false
+		if op, ok := m.state.CompleteAsyncOperation(msg.OperationID); ok {
+			// Keep failed and cancelled operations so they can be retried
+			var opErr error
+			if op.Status == "failed" {
+				m.state.RecordFailedOperation(op, op.Error)
+				opErr = errors.New(op.Error)
+			} else if err := resultError(msg.Result); err != nil {
+				m.state.RecordFailedOperation(op, err.Error())
+				opErr = err
+			}
+			failed = opErr != nil
+			m.auditOperation(op, opErr)
+		}
+		m.state.ClampOperationsIndex()
+		var cmd tea.Cmd
+		if msg.Result != nil {
+			_, cmd = m.Update(msg.Result)
+		}
+		// After the result, so a finished bulk operation's summary is the latest notification
+		cmd = tea.Batch(cmd, m.advanceBulk(msg.OperationID, failed))
+
+		// A quit waiting on operations goes ahead once the last one finishes, saving the
+		// results now that they're in
+		if m.state.QuitWaiting {
+			if pending := len(m.state.PendingOperations); pending > 0 {
+				m.state.PendingConfirmation = quitWaitConfirmation(pending)
+			} else {
+				quit := QuitMsg{Save: m.state.QuitSave || m.state.AutoSave}
+				return m, tea.Batch(cmd, func() tea.Msg { return quit })
+			}
+		}
+		return m, cmd
+
+	case RetryOperationMsg:
+		op, ok := m.state.TakeFailedOperation(msg.OperationID)
+		if !ok || op.Retry == nil {
+			m.state.AddNotification("Operation can't be retried", "warning")
+			return m, nil
+		}
+		m.state.ClampOperationsIndex()
+		m.state.AddNotification("Retrying: "+op.Label(), "info")
+		return m, func() tea.Msg { return op.Retry }
+
+	case CancelOperationMsg:
+		op := m.state.GetAsyncOperation(msg.OperationID)
+		if op == nil || !m.state.CancelAsyncOperation(msg.OperationID) {
+			m.state.AddNotification("Operation has already finished", "warning")
+			return m, nil
+		}
+		m.state.AddNotification("Cancelled: "+op.Label(), "info")
+		return m, nil
+
+	case SpinnerTickMsg:
+		if len(m.state.PendingOperations) == 0 {
+			m.state.SpinnerRunning = false
+			return m, nil
+		}
+		m.state.SpinnerFrame++
+		return m, scheduleSpinner()
+
 	case QuitMsg:
+		// Wait for deploys and other operations rather than abandoning them mid-request
+		if pending := len(m.state.PendingOperations); pending > 0 && !msg.Force {
+			m.state.QuitWaiting = true
+			m.state.QuitSave = m.state.QuitSave || msg.Save
+			m.state.PendingConfirmation = quitWaitConfirmation(pending)
+			return m, nil
+		}
+		if msg.Force {
+			for _, op := range m.state.PendingOperations {
+				m.state.CancelAsyncOperation(op.ID)
+			}
+		}
+		if msg.Save {
+			if err := m.saveConfigSync(); err != nil {
+				m.state.QuitWaiting, m.state.QuitSave = false, false
+				m.state.AddNotification("Failed to save config, not quitting: "+err.Error(), "error")
+				return m, nil
+			}
+		}
 		m.state.ShouldQuit = true
+		// Keep the notifications for the next session; there is nowhere left to report a failure
+		if m.state.ConfigPath != "" {
+			_ = state.SaveNotifications(state.NotificationsPath(m.state.ConfigPath), m.state.Notifications)
+		}
 		return m, tea.Quit
 	}
 
@@ -613,7 +1655,7 @@ func (m Model) View() string {
 	}
 
 	// Delegate to UI package for rendering with zone manager
-	return m.zone.Scan(ui.RenderWithZones(m.state, m.zone))
+	return m.zone.Scan(ui.RenderWithZones(m.state, m.zone, m.theme))
 }
 
 // ============================================================================
@@ -621,164 +1663,391 @@ func (m Model) View() string {
 // ============================================================================
 // These functions return tea.Cmd that run async operations and return messages
 
-func (m Model) spawnDeploySite(siteID uuid.UUID) tea.Cmd {
+// validateSiteForDeploy checks the parts of a site's configuration that the node can't recover from
+func validateSiteForDeploy(site *models.Site) error {
+	// Container sites need exactly one of a prebuilt image or a build context
+	if err := site.ValidateImageSource(); err != nil {
+		return err
+	}
+
+	// Middlewares are only set in the config file, so catch typos before they reach Traefik
+	for i := range site.Middlewares {
+		if err := site.Middlewares[i].Validate(); err != nil {
+			return fmt.Errorf("middleware %d: %w", i+1, err)
+		}
+	}
+	return nil
+}
+
+// buildDeployPreview builds the request spawnDeploySite would send for a site, without contacting the node or DNS provider
+func (m Model) buildDeployPreview(siteID uuid.UUID) (string, error) {
+	site := m.state.GetSiteByID(siteID)
+	if site == nil {
+		return "", fmt.Errorf("site not found")
+	}
+	domain := m.state.GetDomainByID(site.DomainID)
+	if domain == nil {
+		return "", fmt.Errorf("domain not found")
+	}
+	mappings := site.GetDomainMappings()
+	if len(mappings) == 0 {
+		return "", fmt.Errorf("site has no domain mappings")
+	}
+	if err := validateSiteForDeploy(site); err != nil {
+		return "", err
+	}
+
+	// Deploys route the first mapping's full domain, as in spawnDeploySite
+	domainName := models.GetFullDomain(domain.Name, mappings[0].Subdomain)
+	req, err := api.NewDeployRequest(site, domainName)
+	if err != nil {
+		return "", err
+	}
+	return api.FormatDeployRequest(req)
+}
+
+// spawnDeploySite runs deploySite in the background
+func (m Model) spawnDeploySite(siteID uuid.UUID, image string) tea.Cmd {
 	return func() tea.Msg {
-		// Get site from state by ID
-		site := m.state.GetSiteByID(siteID)
-		if site == nil {
-			return SiteDeployedMsg{
-				SiteID: siteID,
-				Error:  fmt.Errorf("site not found"),
-			}
+		result := m.deploySite(siteID, image)
+		result.Image = image
+		return result
+	}
+}
+
+// deploySite deploys a site to each of its nodes, with image in place of its configured image if set
+func (m Model) deploySite(siteID uuid.UUID, image string) SiteDeployedMsg {
+	// Get site from state by ID
+	site := m.state.GetSiteByID(siteID)
+	if site == nil {
+		return SiteDeployedMsg{
+			SiteID: siteID,
+			Error:  fmt.Errorf("site not found"),
 		}
+	}
+	if image != "" {
+		// Deploy a copy, so saves while the deploy runs keep the configured image
+		rollback := *site
+		rollback.DockerImage = image
+		site = &rollback
+	}
 
-		// Get node from state by site.NodeID
-		node := m.state.GetNodeByID(site.NodeID)
-		if node == nil {
-			return SiteDeployedMsg{
-				SiteID: siteID,
-				Error:  fmt.Errorf("node not found"),
-			}
+	// Get the site's nodes: the primary node, then any replicas
+	nodes, err := m.siteNodes(site)
+	if err != nil {
+		return SiteDeployedMsg{
+			SiteID: siteID,
+			Error:  err,
 		}
+	}
 
-		// Get domain from state by site.DomainID
-		domain := m.state.GetDomainByID(site.DomainID)
-		if domain == nil {
-			return SiteDeployedMsg{
-				SiteID: siteID,
-				Error:  fmt.Errorf("domain not found"),
-			}
+	// Get domain from state by site.DomainID
+	domain := m.state.GetDomainByID(site.DomainID)
+	if domain == nil {
+		return SiteDeployedMsg{
+			SiteID: siteID,
+			Error:  fmt.Errorf("domain not found"),
 		}
+	}
 
-		// Get all domain mappings
-		mappings := site.GetDomainMappings()
-		if len(mappings) == 0 {
+	// Get all domain mappings
+	mappings := site.GetDomainMappings()
+	if len(mappings) == 0 {
+		return SiteDeployedMsg{
+			SiteID: siteID,
+			Error:  fmt.Errorf("site has no domain mappings"),
+		}
+	}
+
+	if err := validateSiteForDeploy(site); err != nil {
+		return SiteDeployedMsg{
+			SiteID: siteID,
+			Error:  err,
+		}
+	}
+	for _, node := range nodes {
+		if err := site.ValidateDeploymentStrategy(node.ProxyType); err != nil {
 			return SiteDeployedMsg{
 				SiteID: siteID,
-				Error:  fmt.Errorf("site has no domain mappings"),
+				Error:  err,
 			}
 		}
+	}
 
-		// Check DNS records exist for all domain mappings (if provider is not manual)
-		targetIP := node.IPAddress.String()
-		var fullDomains []string
+	// Check DNS records exist for all domain mappings (if provider is not manual)
+	// Replicas serve the same hostnames, so a record may point at any of the site's nodes
+	if domain.DnsProvider.Type != models.DnsProviderManual {
+		for _, node := range nodes {
+			if node.DNSTargetIP() == nil {
+				return SiteDeployedMsg{
+					SiteID: siteID,
+					Error:  fmt.Errorf("node %s has no public or management IP address set for DNS records", node.Name),
+				}
+			}
+		}
+	}
+	targetIP := nodes[0].DNSTargetIP().String()
+	var fullDomains []string
 
-		// Check DNS records exist before deploying (if provider is not manual)
-		if domain.DnsProvider.Type != models.DnsProviderManual {
-			// Use domain's provider config combined with global settings
-			providerConfig := domain.DnsProvider
+	// Check DNS records exist before deploying (if provider is not manual)
+	if domain.DnsProvider.Type != models.DnsProviderManual {
+		// Use domain's provider config combined with global settings
+		providerConfig := domain.DnsProvider
 
-			// Validate that required configuration is present
-			if providerConfig.Type == models.DnsProviderCloudflare {
-				// Use domain's Zone ID + global API token
-				if providerConfig.ZoneID == "" {
-					return SiteDeployedMsg{
-						SiteID: siteID,
-						Error:  fmt.Errorf("domain %s is missing Cloudflare Zone ID configuration", domain.Name),
-					}
+		// Validate that required configuration is present
+		if providerConfig.Type == models.DnsProviderCloudflare {
+			// Use domain's Zone ID + global API token
+			if providerConfig.ZoneID == "" {
+				return SiteDeployedMsg{
+					SiteID: siteID,
+					Error:  fmt.Errorf("domain %s is missing Cloudflare Zone ID configuration", domain.Name),
 				}
-				if m.state.CloudflareAPIToken == "" {
-					return SiteDeployedMsg{
-						SiteID: siteID,
-						Error:  fmt.Errorf("Cloudflare API Token not configured in settings"),
-					}
+			}
+			if m.state.CloudflareAPIToken == "" {
+				return SiteDeployedMsg{
+					SiteID: siteID,
+					Error:  fmt.Errorf("Cloudflare API Token not configured in settings"),
 				}
-				// Combine domain Zone ID with global API token
-				providerConfig.APIToken = m.state.CloudflareAPIToken
 			}
+			// Combine domain Zone ID with global API token
+			providerConfig.APIToken = m.state.CloudflareAPIToken
+		}
 
-			if providerConfig.Type == models.DnsProviderRoute53 {
-				if providerConfig.AccessKey == "" || providerConfig.SecretKey == "" || providerConfig.HostedZoneID == "" {
-					return SiteDeployedMsg{
-						SiteID: siteID,
-						Error:  fmt.Errorf("domain %s is missing Route53 Access Key, Secret Key, or Hosted Zone ID configuration", domain.Name),
-					}
+		if providerConfig.Type == models.DnsProviderRoute53 {
+			if providerConfig.AccessKey == "" || providerConfig.SecretKey == "" || providerConfig.HostedZoneID == "" {
+				return SiteDeployedMsg{
+					SiteID: siteID,
+					Error:  fmt.Errorf("domain %s is missing Route53 Access Key, Secret Key, or Hosted Zone ID configuration", domain.Name),
 				}
 			}
+		}
+
+		dnsProvider, err := m.createDnsProvider(&providerConfig)
+		if err != nil {
+			return SiteDeployedMsg{
+				SiteID: siteID,
+				Error:  fmt.Errorf("failed to create DNS provider: %w", err),
+			}
+		}
 
-			dnsProvider, err := dns.CreateProvider(&providerConfig)
+		if dnsProvider != nil {
+			// List existing DNS records to check if one already exists
+			existingRecords, err := dnsProvider.ListRecords(domain.Name)
 			if err != nil {
 				return SiteDeployedMsg{
 					SiteID: siteID,
-					Error:  fmt.Errorf("failed to create DNS provider: %w", err),
+					Error:  fmt.Errorf("failed to list DNS records: %w", err),
 				}
 			}
 
-			if dnsProvider != nil {
-				// List existing DNS records to check if one already exists
-				existingRecords, err := dnsProvider.ListRecords(domain.Name)
-				if err != nil {
-					return SiteDeployedMsg{
-						SiteID: siteID,
-						Error:  fmt.Errorf("failed to list DNS records: %w", err),
-					}
-				}
-
-				// Check DNS records for all domain mappings
-				for _, mapping := range mappings {
-					fullDomain := models.GetFullDomain(domain.Name, mapping.Subdomain)
-					fullDomains = append(fullDomains, fullDomain)
+			// Check DNS records for all domain mappings
+			for _, mapping := range mappings {
+				fullDomain := models.GetFullDomain(domain.Name, mapping.Subdomain)
+				fullDomains = append(fullDomains, fullDomain)
 
-					// Check if a record with the same name exists
-					var existingRecord *models.DnsRecord
-					for i := range existingRecords {
-						if existingRecords[i].Name == fullDomain && existingRecords[i].RecordType == models.DnsRecordTypeA {
-							existingRecord = &existingRecords[i]
+				// Check if a record with the same name exists, preferring one pointing at a node
+				var existingRecord *models.DnsRecord
+				for i := range existingRecords {
+					if existingRecords[i].Name == fullDomain && isNodeRecordType(existingRecords[i].RecordType, nodes) {
+						existingRecord = &existingRecords[i]
+						if pointsToAnyNode(existingRecord, nodes) {
 							break
 						}
 					}
+				}
 
-					if existingRecord != nil {
-						// Record exists - check if it points to the correct IP
-						if existingRecord.Value != targetIP {
-							// Record points to wrong IP - cannot deploy
-							return SiteDeployedMsg{
-								SiteID: siteID,
-								Error:  fmt.Errorf("DNS record for %s points to %s but should point to %s. Please setup DNS first (press 'r' or click 🌐)", fullDomain, existingRecord.Value, targetIP),
-							}
-						}
-						// Record is correct - continue with deployment
-					} else {
-						// Record doesn't exist - cannot deploy
+				if existingRecord != nil {
+					// Record exists - check if it points to one of the site's nodes
+					if !pointsToAnyNode(existingRecord, nodes) {
+						// Record points to wrong IP - cannot deploy
 						return SiteDeployedMsg{
 							SiteID: siteID,
-							Error:  fmt.Errorf("DNS record for %s does not exist. Please setup DNS first (press 'r' or click 🌐)", fullDomain),
+							Error:  fmt.Errorf("DNS record for %s points to %s but should point to %s. Please setup DNS first (press 'r' or click 🌐)", fullDomain, existingRecord.Value, targetIP),
 						}
 					}
+					// Record is correct - continue with deployment
+				} else {
+					// Record doesn't exist - cannot deploy
+					return SiteDeployedMsg{
+						SiteID: siteID,
+						Error:  fmt.Errorf("DNS record for %s does not exist. Please setup DNS first (press 'r' or click 🌐)", fullDomain),
+					}
 				}
 			}
-		} else {
-			// For manual DNS provider, still collect domain names
-			for _, mapping := range mappings {
-				fullDomain := models.GetFullDomain(domain.Name, mapping.Subdomain)
-				fullDomains = append(fullDomains, fullDomain)
-			}
 		}
+	} else {
+		// For manual DNS provider, still collect domain names
+		for _, mapping := range mappings {
+			fullDomain := models.GetFullDomain(domain.Name, mapping.Subdomain)
+			fullDomains = append(fullDomains, fullDomain)
+		}
+	}
+
+	// Use type assertion to access DeploySiteWebSocket method; other clients (demo mode)
+	// deploy with a plain request
+	httpClient, ok := m.nodeClient.(*api.HTTPNodeClient)
 
-		// Use type assertion to access DeploySiteWebSocket method
-		httpClient, ok := m.nodeClient.(*api.HTTPNodeClient)
+	// Deploy to each node in turn, using WebSocket with progress callback
+	// Use the first domain for deployment (the deployment handles all domains); every replica
+	// gets the same hostnames and Traefik labels
+	progress := m.deployProgress
+	replicas := make([]ReplicaDeployResult, 0, len(nodes))
+	for _, node := range nodes {
 		if !ok {
-			return SiteDeployedMsg{
-				SiteID: siteID,
-				Error:  fmt.Errorf("node client does not support WebSocket"),
-			}
+			err := m.nodeClientFor(node).DeploySite(node.APIEndpoint, node.APIKey, site, fullDomains[0])
+			replicas = append(replicas, ReplicaDeployResult{NodeID: node.ID, NodeName: node.Name, Error: err})
+			continue
 		}
-
-		// Deploy using WebSocket with progress callback
-		// Use the first domain for deployment (the deployment handles all domains)
-		err := httpClient.DeploySiteWebSocket(
+		prefix := ""
+		if len(nodes) > 1 {
+			prefix = node.Name + ": "
+		}
+		resp, err := httpClient.DeploySiteWebSocket(
 			node.APIEndpoint,
 			node.APIKey,
 			site,
 			fullDomains[0],
-			nil, // No progress callback for now - just use WebSocket for timeout prevention
+			func(msg api.DeploymentMessage) {
+				// Blue/green phases are shown as they happen; other steps only matter if the deploy fails
+				if msg.Type != "progress" || msg.Step != api.DeployStepBlueGreen || progress == nil {
+					return
+				}
+				select {
+				case progress <- SiteDeployProgressMsg{SiteID: siteID, Message: prefix + msg.Message, Step: msg.Step}:
+				default:
+					// Nothing reads the phases of command-line deploys, so once the buffer is full they are dropped
+				}
+			},
 		)
+		replicas = append(replicas, ReplicaDeployResult{NodeID: node.ID, NodeName: node.Name, Response: resp, Error: err})
+	}
 
-		return SiteDeployedMsg{
-			SiteID: siteID,
-			Error:  err,
+	return newSiteDeployedMsg(siteID, replicas)
+}
+
+// siteNodes returns the nodes the site runs on, the primary node first
+func (m Model) siteNodes(site *models.Site) ([]*models.Node, error) {
+	nodeIDs := site.GetNodeIDs()
+	if len(nodeIDs) == 0 {
+		return nil, fmt.Errorf("node not found")
+	}
+	nodes := make([]*models.Node, 0, len(nodeIDs))
+	for _, nodeID := range nodeIDs {
+		node := m.state.GetNodeByID(nodeID)
+		if node == nil {
+			if len(nodeIDs) > 1 {
+				return nil, fmt.Errorf("node %s not found", nodeID)
+			}
+			return nil, fmt.Errorf("node not found")
 		}
+		nodes = append(nodes, node)
+	}
+	return nodes, nil
+}
+
+// onEachNode calls fn for each node. With more than one node, every node is tried and the
+// error names the nodes that failed.
+func onEachNode(nodes []*models.Node, fn func(node *models.Node) error) error {
+	if len(nodes) == 1 {
+		return fn(nodes[0])
+	}
+	var failures []string
+	for _, node := range nodes {
+		if err := fn(node); err != nil {
+			failures = append(failures, fmt.Sprintf("%s: %v", node.Name, err))
+		}
+	}
+	if len(failures) > 0 {
+		return fmt.Errorf("failed on %s", strings.Join(failures, "; "))
 	}
+	return nil
+}
+
+// isNodeRecordType returns true if records of the type can point at one of the nodes
+func isNodeRecordType(recordType models.DnsRecordType, nodes []*models.Node) bool {
+	for _, node := range nodes {
+		if models.RecordTypeForIP(node.DNSTargetIP()) == recordType {
+			return true
+		}
+	}
+	return false
+}
+
+// pointsToAnyNode returns true if the record points at one of the nodes
+func pointsToAnyNode(record *models.DnsRecord, nodes []*models.Node) bool {
+	for _, node := range nodes {
+		if record.PointsTo(node.DNSTargetIP()) {
+			return true
+		}
+	}
+	return false
+}
+
+// newSiteDeployedMsg combines the outcome of deploying a site to each of its nodes. The deploy
+// fails only if no node was deployed to; otherwise partialFailure reports the nodes that failed.
+func newSiteDeployedMsg(siteID uuid.UUID, replicas []ReplicaDeployResult) SiteDeployedMsg {
+	msg := SiteDeployedMsg{SiteID: siteID, Replicas: replicas}
+	var failures []string
+	for _, replica := range replicas {
+		if replica.Error != nil {
+			failures = append(failures, fmt.Sprintf("%s: %v", replica.NodeName, replica.Error))
+		} else if msg.Response == nil {
+			msg.Response = replica.Response
+		}
+	}
+
+	switch {
+	case len(replicas) == 1:
+		msg.Error = replicas[0].Error
+	case len(failures) == len(replicas):
+		msg.Error = fmt.Errorf("failed on every node (%s)", strings.Join(failures, "; "))
+	}
+	return msg
+}
+
+// partialFailure returns an error naming the nodes a deploy failed on when it succeeded on others,
+// or nil if the deploy succeeded everywhere or failed outright
+func (msg SiteDeployedMsg) partialFailure() error {
+	if msg.Error != nil {
+		return nil
+	}
+	var failures []string
+	for _, replica := range msg.Replicas {
+		if replica.Error != nil {
+			failures = append(failures, fmt.Sprintf("%s: %v", replica.NodeName, replica.Error))
+		}
+	}
+	if len(failures) == 0 {
+		return nil
+	}
+	return fmt.Errorf("deployed to %d of %d nodes, failed on %s", len(msg.Replicas)-len(failures), len(msg.Replicas), strings.Join(failures, "; "))
+}
+
+// recordDeployResult updates the site's status and last error from a deploy's outcome, and the
+// status of each of its nodes when it has replicas
+func recordDeployResult(site *models.Site, msg SiteDeployedMsg) {
+	if site.HasReplicas() {
+		for _, replica := range msg.Replicas {
+			if replica.Error != nil {
+				site.SetReplicaStatus(replica.NodeID, models.SiteStatusFailed, replica.Error.Error())
+			} else {
+				site.SetReplicaStatus(replica.NodeID, models.SiteStatusRunning, "")
+			}
+		}
+	}
+
+	// A site running on some of its nodes is still serving, so it counts as running
+	if msg.Error != nil {
+		site.Status = models.SiteStatusFailed
+		site.LastError = msg.Error.Error()
+	} else if partial := msg.partialFailure(); partial != nil {
+		site.Status = models.SiteStatusRunning
+		site.LastError = partial.Error()
+	} else {
+		site.Status = models.SiteStatusRunning
+		site.LastError = ""
+	}
+	site.UpdatedAt = time.Now()
 }
 
 func (m Model) spawnSetupDNS(siteID uuid.UUID) tea.Cmd {
@@ -859,7 +2128,7 @@ func (m Model) spawnSetupDNS(siteID uuid.UUID) tea.Cmd {
 			}
 		}
 
-		dnsProvider, err := dns.CreateProvider(&providerConfig)
+		dnsProvider, err := m.createDnsProvider(&providerConfig)
 		if err != nil {
 			return DNSSetupResultMsg{
 				SiteID: siteID,
@@ -883,7 +2152,16 @@ func (m Model) spawnSetupDNS(siteID uuid.UUID) tea.Cmd {
 			}
 		}
 
-		targetIP := node.IPAddress.String()
+		// Point records at the node's public IP, falling back to its management IP
+		dnsIP := node.DNSTargetIP()
+		if dnsIP == nil {
+			return DNSSetupResultMsg{
+				SiteID: siteID,
+				Error:  fmt.Errorf("node %s has no public or management IP address set for DNS records", node.Name),
+			}
+		}
+		targetIP := dnsIP.String()
+		recordType := models.RecordTypeForIP(dnsIP) // AAAA for IPv6 nodes
 		var messages []string
 		var failedDomains []string
 
@@ -894,7 +2172,7 @@ func (m Model) spawnSetupDNS(siteID uuid.UUID) tea.Cmd {
 			// Check if a record with the same name exists
 			var existingRecord *models.DnsRecord
 			for i := range existingRecords {
-				if existingRecords[i].Name == fullDomain && existingRecords[i].RecordType == models.DnsRecordTypeA {
+				if existingRecords[i].Name == fullDomain && existingRecords[i].RecordType == recordType {
 					existingRecord = &existingRecords[i]
 					break
 				}
@@ -902,7 +2180,7 @@ func (m Model) spawnSetupDNS(siteID uuid.UUID) tea.Cmd {
 
 			if existingRecord != nil {
 				// Record exists - check if it points to the correct IP
-				if existingRecord.Value == targetIP {
+				if existingRecord.PointsTo(dnsIP) {
 					// Record is already correct
 					messages = append(messages, fmt.Sprintf("✓ DNS record for %s already exists and points to %s", fullDomain, targetIP))
 				} else {
@@ -920,39 +2198,478 @@ func (m Model) spawnSetupDNS(siteID uuid.UUID) tea.Cmd {
 			} else {
 				// Record doesn't exist - create it
 				record := models.NewDnsRecord(
-					models.DnsRecordTypeA,
+					recordType,
 					fullDomain,
 					targetIP,
 					300, // 5 minute TTL
 				)
 
-				_, err = dnsProvider.CreateRecord(domain.Name, record, []string{site.Name})
-				if err != nil {
-					failedDomains = append(failedDomains, fullDomain)
-					messages = append(messages, fmt.Sprintf("✗ Failed to create DNS record for %s: %v", fullDomain, err))
-				} else {
-					messages = append(messages, fmt.Sprintf("✓ DNS record for %s created successfully pointing to %s", fullDomain, targetIP))
-				}
-			}
+				_, err = dnsProvider.CreateRecord(domain.Name, record, []string{site.Name})
+				if err != nil {
+					failedDomains = append(failedDomains, fullDomain)
+					messages = append(messages, fmt.Sprintf("✗ Failed to create DNS record for %s: %v", fullDomain, err))
+				} else {
+					messages = append(messages, fmt.Sprintf("✓ DNS record for %s created successfully pointing to %s", fullDomain, targetIP))
+				}
+			}
+		}
+
+		// Return results
+		if len(failedDomains) > 0 {
+			return DNSSetupResultMsg{
+				SiteID: siteID,
+				Message: fmt.Sprintf("Processed %d domain(s) - %d failed. Details:\n%s\n\nAllow 5-10 minutes for DNS propagation.",
+					len(mappings), len(failedDomains), strings.Join(messages, "\n")),
+				Error: fmt.Errorf("failed to setup DNS for %d domain(s): %v", len(failedDomains), failedDomains),
+			}
+		} else {
+			return DNSSetupResultMsg{
+				SiteID: siteID,
+				Message: fmt.Sprintf("Successfully processed %d domain(s). Details:\n%s\n\nAllow 5-10 minutes for DNS propagation.",
+					len(mappings), strings.Join(messages, "\n")),
+				Error: nil,
+			}
+		}
+	}
+}
+
+// spawnFetchNodeLogs fetches container logs, or the last build output when build is set
+func (m Model) spawnFetchNodeLogs(siteID uuid.UUID, lines int, build bool) tea.Cmd {
+	return func() tea.Msg {
+		site := m.state.GetSiteByID(siteID)
+		if site == nil {
+			return NodeLogsResultMsg{SiteID: siteID, Build: build, Error: fmt.Errorf("site not found")}
+		}
+
+		node := m.state.GetNodeByID(site.NodeID)
+		if node == nil {
+			return NodeLogsResultMsg{SiteID: siteID, Build: build, Error: fmt.Errorf("node not found")}
+		}
+
+		if build {
+			logs, err := m.nodeClientFor(node).GetBuildLogs(node.APIEndpoint, node.APIKey, siteID)
+			return NodeLogsResultMsg{
+				SiteID: siteID,
+				Logs:   logs,
+				Build:  build,
+				Error:  err,
+			}
+		}
+
+		logs, err := m.nodeClientFor(node).GetContainerLogs(node.APIEndpoint, node.APIKey, siteID, lines, "")
+		if err != nil {
+			return NodeLogsResultMsg{SiteID: siteID, Error: err}
+		}
+		return NodeLogsResultMsg{
+			SiteID: siteID,
+			Logs:   logs.Lines,
+			Cursor: logs.Cursor,
+		}
+	}
+}
+
+// spawnFollowLogs fetches the container log lines logged after the site's log cursor,
+// for the follow started as generation gen
+func (m Model) spawnFollowLogs(siteID uuid.UUID, gen int) tea.Cmd {
+	since := m.state.LogCursor(siteID)
+	return func() tea.Msg {
+		site := m.state.GetSiteByID(siteID)
+		if site == nil {
+			return NodeLogsResultMsg{SiteID: siteID, FollowGen: gen, Error: fmt.Errorf("site not found")}
+		}
+
+		node := m.state.GetNodeByID(site.NodeID)
+		if node == nil {
+			return NodeLogsResultMsg{SiteID: siteID, FollowGen: gen, Error: fmt.Errorf("node not found")}
+		}
+
+		logs, err := m.nodeClientFor(node).GetContainerLogs(node.APIEndpoint, node.APIKey, siteID, siteDetailLogLines, since)
+		if err != nil {
+			return NodeLogsResultMsg{SiteID: siteID, FollowGen: gen, Error: err}
+		}
+		return NodeLogsResultMsg{
+			SiteID:    siteID,
+			Logs:      logs.Lines,
+			Since:     since,
+			Cursor:    logs.Cursor,
+			FollowGen: gen,
+		}
+	}
+}
+
+// spawnFetchNodeMetrics fetches resource usage metrics for a site's container
+func (m Model) spawnFetchNodeMetrics(siteID uuid.UUID) tea.Cmd {
+	return func() tea.Msg {
+		site := m.state.GetSiteByID(siteID)
+		if site == nil {
+			return NodeMetricsResultMsg{SiteID: siteID, Error: fmt.Errorf("site not found")}
+		}
+
+		node := m.state.GetNodeByID(site.NodeID)
+		if node == nil {
+			return NodeMetricsResultMsg{SiteID: siteID, Error: fmt.Errorf("node not found")}
+		}
+
+		metrics, err := m.nodeClientFor(node).GetContainerMetrics(node.APIEndpoint, node.APIKey, siteID)
+		return NodeMetricsResultMsg{
+			SiteID:  siteID,
+			Metrics: metrics,
+			Error:   err,
+		}
+	}
+}
+
+// siteDetailCmds fetches everything shown on the site detail screen: status, logs and metrics
+func (m Model) siteDetailCmds(siteID uuid.UUID) []tea.Cmd {
+	return []tea.Cmd{
+		m.spawnFetchSiteStatus(siteID),
+		m.spawnFetchNodeLogs(siteID, siteDetailLogLines, false),
+		m.spawnFetchNodeMetrics(siteID),
+	}
+}
+
+// autoRestart restarts a failed site that opted in to it, reporting the attempt as a notification.
+// It does nothing while a restart of the site is running or Archon is waiting to quit, and gives
+// up after MaxAutoRestarts attempts until the site is deployed or restarted by hand.
+func (m Model) autoRestart(siteID uuid.UUID) tea.Cmd {
+	site := m.state.GetSiteByID(siteID)
+	if site == nil || !site.AutoRestart || m.state.QuitWaiting || m.restartPending(siteID) {
+		return nil
+	}
+
+	attempt := m.state.CountAutoRestart(siteID)
+	switch {
+	case attempt > state.MaxAutoRestarts+1:
+		return nil
+	case attempt > state.MaxAutoRestarts:
+		m.state.AddNotification(fmt.Sprintf("Site %s is still failing after %d automatic restarts; restart or redeploy it by hand", site.Name, state.MaxAutoRestarts), "warning")
+		return nil
+	}
+
+	m.state.AddNotification(fmt.Sprintf("Site %s failed; restarting it automatically (attempt %d of %d)", site.Name, attempt, state.MaxAutoRestarts), "warning")
+	op := m.startOperation(state.OpRestartSite, site.Name, RestartSiteMsg{SiteID: siteID})
+	return op.track(op.spawnRestartSite(siteID))
+}
+
+// restartPending reports whether a restart of the site is in flight
+func (m Model) restartPending(siteID uuid.UUID) bool {
+	for _, op := range m.state.PendingOperations {
+		if retry, ok := op.Retry.(RestartSiteMsg); ok && retry.SiteID == siteID {
+			return true
+		}
+	}
+	return false
+}
+
+// stopPending reports whether a stop of the site is in flight
+func (m Model) stopPending(siteID uuid.UUID) bool {
+	for _, op := range m.state.PendingOperations {
+		if retry, ok := op.Retry.(StopSiteMsg); ok && retry.SiteID == siteID {
+			return true
+		}
+	}
+	return false
+}
+
+// recordAudit adds an action to the audit log, as a failure if err is set. Errors writing the log
+// are reported as a warning rather than blocking the action.
+func (m Model) recordAudit(action, entity string, id uuid.UUID, name string, err error) {
+	entry := audit.Entry{Action: action, Entity: entity, Name: name}
+	if id != uuid.Nil {
+		entry.EntityID = id.String()
+	}
+	if err != nil {
+		entry.Error = err.Error()
+	}
+	if logErr := m.audit.Record(entry); logErr != nil {
+		m.state.AddNotification("Failed to write audit log: "+logErr.Error(), "warning")
+	}
+}
+
+// auditOperation records a finished deploy, stop, restart or DNS push in the audit log.
+// DNS syncs only read the provider's records, so they are recorded when applied instead.
+func (m Model) auditOperation(op state.AsyncOperation, err error) {
+	switch retry := op.Retry.(type) {
+	case DeploySiteMsg:
+		m.recordAudit(op.OpType, "site", retry.SiteID, op.Target, err)
+	case StopSiteMsg:
+		m.recordAudit(op.OpType, "site", retry.SiteID, op.Target, err)
+	case RestartSiteMsg:
+		m.recordAudit(op.OpType, "site", retry.SiteID, op.Target, err)
+	case PushDnsRecordsMsg:
+		m.recordAudit(op.OpType, "domain", retry.DomainID, op.Target, err)
+	}
+}
+
+// sendWebhook posts a key event to the webhook URL, if one is set. It is sent in the background
+// and never waited for; failed deliveries only show up in the debug log.
+func (m Model) sendWebhook(event, entity string, id uuid.UUID, name string, err error) {
+	client, ok := m.nodeClient.(*api.HTTPNodeClient)
+	if m.state.WebhookURL == "" || !ok {
+		return
+	}
+	payload := api.WebhookEvent{
+		Event:     event,
+		Entity:    entity,
+		EntityID:  id,
+		Name:      name,
+		Outcome:   audit.OutcomeSuccess,
+		Timestamp: time.Now(),
+	}
+	if err != nil {
+		payload.Outcome = audit.OutcomeFailure
+		payload.Error = err.Error()
+	}
+	url := m.state.WebhookURL
+	go func() { _ = client.PostWebhook(url, payload) }()
+}
+
+// listenDeployProgress waits for the next blue/green deploy phase reported by a node
+func (m Model) listenDeployProgress() tea.Cmd {
+	progress := m.deployProgress
+	if progress == nil {
+		return nil
+	}
+	return func() tea.Msg { return <-progress }
+}
+
+// startOperation records an async operation as pending and returns a copy of the model whose
+// node requests are abandoned if the operation is cancelled. Run the operation's command
+// from the copy and wrap it with track. retry is the message that starts the operation again.
+func (m Model) startOperation(opType, target string, retry tea.Msg) Model {
+	ctx, cancel := context.WithCancel(context.Background())
+	m.opCtx = ctx
+	m.opID = m.state.AddAsyncOperation(opType, target, retry, cancel)
+	return m
+}
+
+// track wraps the command of the operation started by startOperation so its result comes back
+// in an AsyncResultMsg, and starts the status bar spinner if it isn't already running.
+// The command waits for a free operation slot first, so bulk actions don't flood nodes.
+func (m Model) track(cmd tea.Cmd) tea.Cmd {
+	id, ctx, slots := m.opID, m.opCtx, m.opSlots
+	tracked := func() tea.Msg {
+		if slots != nil {
+			select {
+			case slots <- struct{}{}:
+				defer func() { <-slots }()
+			case <-ctx.Done():
+				// Cancelled while queued; the command fails fast on the cancelled context
+			}
+		}
+		return AsyncResultMsg{OperationID: id, Result: cmd()}
+	}
+	if m.state.SpinnerRunning {
+		return tracked
+	}
+	m.state.SpinnerRunning = true
+	return tea.Batch(tracked, scheduleSpinner())
+}
+
+// startBulk queues opType for every site on a node and starts the first few
+func (m Model) startBulk(nodeID uuid.UUID, opType string) tea.Cmd {
+	if m.state.Bulk != nil {
+		m.state.AddNotification("Wait for \""+m.state.Bulk.Progress()+"\" to finish", "warning")
+		return nil
+	}
+	node := m.state.GetNodeByID(nodeID)
+	if node == nil {
+		m.state.AddNotification("Node not found", "error")
+		return nil
+	}
+	sites := m.state.SitesOnNode(nodeID)
+	if len(sites) == 0 {
+		m.state.AddNotification("No sites on "+node.Name, "info")
+		return nil
+	}
+
+	m.state.Bulk = state.NewBulkOperation(node, opType, sites)
+	return m.launchBulk()
+}
+
+// launchBulk starts queued bulk operation sites until bulkConcurrency are in flight
+func (m Model) launchBulk() tea.Cmd {
+	bulk := m.state.Bulk
+	var cmds []tea.Cmd
+	for len(bulk.Running) < bulkConcurrency {
+		siteID, ok := bulk.Next()
+		if !ok {
+			break
+		}
+		site := m.state.GetSiteByID(siteID)
+		if site == nil {
+			bulk.Failed++ // Deleted while queued
+			continue
+		}
+
+		var op Model
+		if bulk.OpType == state.OpDeploySite {
+			site.Status = models.SiteStatusDeploying
+			site.UpdatedAt = time.Now()
+			m.recordDeploying()
+			op = m.startOperation(state.OpDeploySite, site.Name, DeploySiteMsg{SiteID: siteID})
+			cmds = append(cmds, op.track(op.spawnDeploySite(siteID, "")))
+		} else {
+			op = m.startOperation(state.OpStopSite, site.Name, StopSiteMsg{SiteID: siteID})
+			cmds = append(cmds, op.track(op.spawnStopSite(siteID)))
+		}
+		bulk.Running[op.opID] = true
+	}
+	if bulk.Done() {
+		m.finishBulk()
+	}
+	return tea.Batch(cmds...)
+}
+
+// advanceBulk records a finished bulk operation site and starts the next one
+func (m Model) advanceBulk(opID uuid.UUID, failed bool) tea.Cmd {
+	bulk := m.state.Bulk
+	if bulk == nil || !bulk.Finish(opID, failed) {
+		return nil
+	}
+	if bulk.Done() {
+		m.finishBulk()
+		return nil
+	}
+	// Don't start queued sites while a quit is waiting for the running ones
+	if m.state.QuitWaiting {
+		return nil
+	}
+	return m.launchBulk()
+}
+
+// finishBulk reports the outcome of the finished bulk operation and clears it
+func (m Model) finishBulk() {
+	bulk := m.state.Bulk
+	m.state.Bulk = nil
+	verb := "Stopped"
+	if bulk.OpType == state.OpDeploySite {
+		verb = "Deployed"
+	}
+	message := fmt.Sprintf("%s %d/%d sites on %s", verb, bulk.Succeeded, bulk.Total, bulk.NodeName)
+	if bulk.Failed > 0 {
+		m.state.AddNotification(fmt.Sprintf("%s (%d failed, open Operations with Ctrl+O to retry)", message, bulk.Failed), "warning")
+		return
+	}
+	m.state.AddNotification(message, "success")
+}
+
+// resultError returns the error reported by a tracked operation's result message
+func resultError(msg tea.Msg) error {
+	switch result := msg.(type) {
+	case SiteDeployedMsg:
+		if result.Error != nil {
+			return result.Error
+		}
+		return result.partialFailure()
+	case SiteOperationResultMsg:
+		return result.Error
+	case DnsSyncedMsg:
+		return result.Error
+	case DnsRecordsPushedMsg:
+		return result.Error
+	}
+	return nil
+}
+
+// siteName returns the site's name for operation labels, or "" if it no longer exists
+func (m Model) siteName(id uuid.UUID) string {
+	if site := m.state.GetSiteByID(id); site != nil {
+		return site.Name
+	}
+	return ""
+}
+
+// domainName returns the domain's name for operation labels, or "" if it no longer exists
+func (m Model) domainName(id uuid.UUID) string {
+	if domain := m.state.GetDomainByID(id); domain != nil {
+		return domain.Name
+	}
+	return ""
+}
+
+// scheduleSpinner sends a SpinnerTickMsg after the spinner interval
+func scheduleSpinner() tea.Cmd {
+	return tea.Tick(spinnerInterval, func(time.Time) tea.Msg {
+		return SpinnerTickMsg{}
+	})
+}
+
+// scheduleLogFollow sends a LogFollowTickMsg for the site's follow generation gen after the follow interval
+func (m Model) scheduleLogFollow(siteID uuid.UUID, gen int) tea.Cmd {
+	return tea.Tick(logFollowInterval, func(time.Time) tea.Msg {
+		return LogFollowTickMsg{SiteID: siteID, Gen: gen}
+	})
+}
+
+// spawnExportLogs writes log lines to a timestamped file in the logs directory next to the config file
+func (m Model) spawnExportLogs(siteName string, logs []string) tea.Cmd {
+	dir := filepath.Join(filepath.Dir(m.configPath), "logs")
+	return func() tea.Msg {
+		if err := os.MkdirAll(dir, 0755); err != nil {
+			return LogsExportedMsg{Error: err}
+		}
+
+		var content strings.Builder
+		for _, line := range logs {
+			content.WriteString(strings.TrimRight(line, "\r\n") + "\n")
+		}
+
+		base := fmt.Sprintf("%s-%s", logFileStem(siteName), time.Now().Format("20060102-150405"))
+		f, path, err := createLogFile(dir, base)
+		if err != nil {
+			return LogsExportedMsg{Error: err}
+		}
+		_, err = f.WriteString(content.String())
+		if closeErr := f.Close(); err == nil {
+			err = closeErr
+		}
+		if err != nil {
+			return LogsExportedMsg{Error: err}
 		}
+		return LogsExportedMsg{Path: path}
+	}
+}
 
-		// Return results
-		if len(failedDomains) > 0 {
-			return DNSSetupResultMsg{
-				SiteID: siteID,
-				Message: fmt.Sprintf("Processed %d domain(s) - %d failed. Details:\n%s\n\nAllow 5-10 minutes for DNS propagation.",
-					len(mappings), len(failedDomains), strings.Join(messages, "\n")),
-				Error: fmt.Errorf("failed to setup DNS for %d domain(s): %v", len(failedDomains), failedDomains),
-			}
-		} else {
-			return DNSSetupResultMsg{
-				SiteID: siteID,
-				Message: fmt.Sprintf("Successfully processed %d domain(s). Details:\n%s\n\nAllow 5-10 minutes for DNS propagation.",
-					len(mappings), strings.Join(messages, "\n")),
-				Error: nil,
-			}
+// createLogFile creates base.log in dir, or base-2.log, base-3.log and so on when an export
+// from the same second already exists, so repeated exports never overwrite each other
+func createLogFile(dir, base string) (*os.File, string, error) {
+	for n := 1; ; n++ {
+		name := base + ".log"
+		if n > 1 {
+			name = fmt.Sprintf("%s-%d.log", base, n)
+		}
+		path := filepath.Join(dir, name)
+		f, err := os.OpenFile(path, os.O_WRONLY|os.O_CREATE|os.O_EXCL, 0600)
+		if errors.Is(err, os.ErrExist) {
+			continue
+		}
+		return f, path, err
+	}
+}
+
+// logFileStem makes a site name safe to use in a file name
+func logFileStem(siteName string) string {
+	stem := strings.Map(func(r rune) rune {
+		if unicode.IsLetter(r) || unicode.IsDigit(r) || r == '-' || r == '_' || r == '.' {
+			return r
+		}
+		return '-'
+	}, siteName)
+	if strings.Trim(stem, "-.") == "" {
+		return "site"
+	}
+	return stem
+}
+
+// lastLogLine returns the last non-blank log line, which for a failed build is usually the error
+func lastLogLine(logs []string) string {
+	for i := len(logs) - 1; i >= 0; i-- {
+		if line := strings.TrimSpace(logs[i]); line != "" {
+			return line
 		}
 	}
+	return "(empty)"
 }
 
 func (m Model) spawnStopSite(siteID uuid.UUID) tea.Cmd {
@@ -967,24 +2684,26 @@ func (m Model) spawnStopSite(siteID uuid.UUID) tea.Cmd {
 			}
 		}
 
-		// Get node from state by site.NodeID
-		node := m.state.GetNodeByID(site.NodeID)
-		if node == nil {
+		// Get the site's nodes: the primary node, then any replicas
+		nodes, err := m.siteNodes(site)
+		if err != nil {
 			return SiteOperationResultMsg{
 				SiteID:    siteID,
 				Operation: "stop",
-				Error:     fmt.Errorf("node not found"),
+				Error:     err,
 			}
 		}
 
-		// Call nodeClient.StopSite()
-		err := m.nodeClient.StopSite(
-			node.APIEndpoint,
-			node.APIKey,
-			siteID,
-			site.Name,
-			site.GetSiteType(),
-		)
+		// Call nodeClient.StopSite() on every node the site runs on
+		err = onEachNode(nodes, func(node *models.Node) error {
+			return m.nodeClientFor(node).StopSite(
+				node.APIEndpoint,
+				node.APIKey,
+				siteID,
+				site.Name,
+				site.GetSiteType(),
+			)
+		})
 
 		return SiteOperationResultMsg{
 			SiteID:    siteID,
@@ -1006,22 +2725,24 @@ func (m Model) spawnRestartSite(siteID uuid.UUID) tea.Cmd {
 			}
 		}
 
-		// Get node from state by site.NodeID
-		node := m.state.GetNodeByID(site.NodeID)
-		if node == nil {
+		// Get the site's nodes: the primary node, then any replicas
+		nodes, err := m.siteNodes(site)
+		if err != nil {
 			return SiteOperationResultMsg{
 				SiteID:    siteID,
 				Operation: "restart",
-				Error:     fmt.Errorf("node not found"),
+				Error:     err,
 			}
 		}
 
-		// Call nodeClient.RestartSite()
-		err := m.nodeClient.RestartSite(
-			node.APIEndpoint,
-			node.APIKey,
-			siteID,
-		)
+		// Call nodeClient.RestartSite() on every node the site runs on
+		err = onEachNode(nodes, func(node *models.Node) error {
+			return m.nodeClientFor(node).RestartSite(
+				node.APIEndpoint,
+				node.APIKey,
+				siteID,
+			)
+		})
 
 		return SiteOperationResultMsg{
 			SiteID:    siteID,
@@ -1031,16 +2752,131 @@ func (m Model) spawnRestartSite(siteID uuid.UUID) tea.Cmd {
 	}
 }
 
-func (m Model) spawnSyncDns(domainID uuid.UUID) tea.Cmd {
+func (m Model) spawnSyncDns(domainID uuid.UUID, preview bool) tea.Cmd {
+	return func() tea.Msg {
+		// Get domain from state by ID
+		domain := m.state.GetDomainByID(domainID)
+		if domain == nil {
+			return DnsSyncedMsg{DomainID: domainID, Error: fmt.Errorf("domain not found")}
+		}
+
+		// Manual DNS has nothing to sync from
+		if domain.DnsProvider.Type == models.DnsProviderManual {
+			return DnsSyncedMsg{DomainID: domainID, Error: fmt.Errorf("domain %s uses manual DNS", domain.Name)}
+		}
+
+		dnsProvider, err := m.dnsProviderForDomain(domain)
+		if err != nil {
+			return DnsSyncedMsg{DomainID: domainID, Error: err}
+		}
+
+		// Fetch the provider's current records
+		records, err := dnsProvider.ListRecords(domain.Name)
+		if err != nil {
+			return DnsSyncedMsg{DomainID: domainID, Error: fmt.Errorf("failed to list DNS records: %w", err)}
+		}
+
+		return DnsSyncedMsg{DomainID: domainID, Records: records, Preview: preview}
+	}
+}
+
+// spawnPushDnsRecords pushes a domain's local DNS records to its provider
+func (m Model) spawnPushDnsRecords(domainID uuid.UUID, deleteRemote bool) tea.Cmd {
 	return func() tea.Msg {
-		// TODO: Implement DNS sync logic
-		// 1. Get domain from state by ID
-		// 2. Create DNS provider from domain.DnsProvider
-		// 3. Call provider.ListRecords()
-		// 4. Return DnsSyncedMsg with records
+		domain := m.state.GetDomainByID(domainID)
+		if domain == nil {
+			return DnsRecordsPushedMsg{DomainID: domainID, Error: fmt.Errorf("domain not found")}
+		}
+
+		// Manual DNS has no provider to push to
+		if domain.DnsProvider.Type == models.DnsProviderManual {
+			return DnsRecordsPushedMsg{DomainID: domainID, Error: fmt.Errorf("domain %s uses manual DNS", domain.Name)}
+		}
+
+		dnsProvider, err := m.dnsProviderForDomain(domain)
+		if err != nil {
+			return DnsRecordsPushedMsg{DomainID: domainID, Error: err}
+		}
+
+		// Fetch the provider's current records to work out what needs to change
+		remote, err := dnsProvider.ListRecords(domain.Name)
+		if err != nil {
+			return DnsRecordsPushedMsg{DomainID: domainID, Error: fmt.Errorf("failed to list DNS records: %w", err)}
+		}
+
+		sent := append([]models.DnsRecord(nil), domain.DnsRecords...)
+		records, outcomes := dns.PushRecords(dnsProvider, domain.Name, sent, remote, deleteRemote)
+		return DnsRecordsPushedMsg{DomainID: domainID, Sent: sent, Records: records, Outcomes: outcomes}
+	}
+}
+
+// applyDnsSync merges fetched provider records into a domain's local records and reports the result
+// Records are merged rather than replaced so local edits survive the sync
+func (m Model) applyDnsSync(domain *models.Domain, records []models.DnsRecord) tea.Cmd {
+	result := dns.MergeRecords(domain.DnsRecords, records)
+	domain.DnsRecords = result.Records
+	m.recordAudit("apply_dns_sync", "domain", domain.ID, domain.Name, nil)
+	m.sendWebhook(api.WebhookDnsSynced, "domain", domain.ID, domain.Name, nil)
+
+	m.state.AddNotification(fmt.Sprintf("DNS synced for %s: %d added, %d updated, %d local-only",
+		domain.Name, result.Added, result.Updated, len(result.LocalOnly)), "success")
+
+	// Surface conflicts instead of silently overwriting local edits
+	if len(result.Conflicts) > 0 {
+		var names []string
+		for _, rec := range result.Conflicts {
+			names = append(names, fmt.Sprintf("%s %s (local %s, remote %s)", rec.RecordType, rec.Name, rec.Value, rec.ConflictValue))
+		}
+		m.state.AddNotification(fmt.Sprintf("%d DNS record(s) changed both locally and at the provider: %s",
+			len(result.Conflicts), strings.Join(names, "; ")), "warning")
+	}
+
+	// Trigger auto-save if enabled
+	if m.state.AutoSave {
+		return m.saveConfig()
+	}
+	m.state.Dirty = true
+	return nil
+}
+
+// createDnsProvider creates the DNS provider for a domain's provider config, or returns the
+// in-memory provider in demo mode. Manual DNS has no provider either way.
+func (m Model) createDnsProvider(providerConfig *models.DnsProvider) (dns.Provider, error) {
+	if m.demoDns != nil && providerConfig.Type != models.DnsProviderManual {
+		return m.demoDns, nil
+	}
+	return dns.CreateProvider(providerConfig, m.state.DNSRateLimitRetries)
+}
+
+// dnsProviderForDomain creates a DNS provider for a domain, combining the
+// domain's zone configuration with the global credentials from settings
+func (m Model) dnsProviderForDomain(domain *models.Domain) (dns.Provider, error) {
+	providerConfig := domain.DnsProvider
+
+	if providerConfig.Type == models.DnsProviderCloudflare {
+		if providerConfig.ZoneID == "" {
+			return nil, fmt.Errorf("domain %s is missing Cloudflare Zone ID configuration", domain.Name)
+		}
+		if m.state.CloudflareAPIToken == "" {
+			return nil, fmt.Errorf("Cloudflare API Token not configured in settings")
+		}
+		providerConfig.APIToken = m.state.CloudflareAPIToken
+	}
 
-		return DnsSyncedMsg{DomainID: domainID, Error: nil}
+	if providerConfig.Type == models.DnsProviderRoute53 {
+		if providerConfig.AccessKey == "" || providerConfig.SecretKey == "" || providerConfig.HostedZoneID == "" {
+			return nil, fmt.Errorf("domain %s is missing Route53 Access Key, Secret Key, or Hosted Zone ID configuration", domain.Name)
+		}
+	}
+
+	dnsProvider, err := m.createDnsProvider(&providerConfig)
+	if err != nil {
+		return nil, fmt.Errorf("failed to create DNS provider: %w", err)
 	}
+	if dnsProvider == nil {
+		return nil, fmt.Errorf("DNS provider is nil")
+	}
+	return dnsProvider, nil
 }
 
 func (m Model) spawnNodeHealthCheck(nodeID uuid.UUID) tea.Cmd {
@@ -1054,8 +2890,14 @@ func (m Model) spawnNodeHealthCheck(nodeID uuid.UUID) tea.Cmd {
 			}
 		}
 
-		// Call nodeClient.HealthCheck()
-		health, err := m.nodeClient.HealthCheck(node.APIEndpoint, node.APIKey)
+		// Call nodeClient.HealthCheck(), timing the round trip
+		start := time.Now()
+		health, err := m.nodeClientFor(node).HealthCheck(node.APIEndpoint, node.APIKey)
+		latency := time.Since(start).Milliseconds()
+
+		// A failed check is still a check; the status is set from the result in Update
+		now := time.Now()
+		node.LastHealthCheck = &now
 		if err != nil {
 			return NodeHealthCheckResultMsg{
 				NodeID: nodeID,
@@ -1064,11 +2906,8 @@ func (m Model) spawnNodeHealthCheck(nodeID uuid.UUID) tea.Cmd {
 		}
 
 		// Update node status and info
-		node.Status = health.Status
-		node.DockerInfo = health.Docker
-		node.TraefikInfo = health.Traefik
-		now := time.Now()
-		node.LastHealthCheck = &now
+		node.ApplyHealth(health.Status, health.Docker, health.Traefik)
+		node.LastLatencyMs = &latency
 
 		return NodeHealthCheckResultMsg{
 			NodeID: nodeID,
@@ -1077,24 +2916,429 @@ func (m Model) spawnNodeHealthCheck(nodeID uuid.UUID) tea.Cmd {
 	}
 }
 
+// spawnTestNodeConnection health checks an endpoint that hasn't been saved as a node yet
+func (m Model) spawnTestNodeConnection(endpoint, apiKey string) tea.Cmd {
+	return func() tea.Msg {
+		normalized, err := models.NormalizeEndpoint(endpoint)
+		if err != nil {
+			return NodeConnectionTestedMsg{
+				Endpoint: endpoint,
+				Error:    fmt.Errorf("invalid API endpoint: %w", err),
+			}
+		}
+
+		start := time.Now()
+		health, err := m.nodeClient.HealthCheck(normalized, apiKey)
+		return NodeConnectionTestedMsg{
+			Endpoint:  normalized,
+			Result:    health,
+			LatencyMs: time.Since(start).Milliseconds(),
+			Error:     err,
+		}
+	}
+}
+
+// spawnLookupCloudflareZone finds the Cloudflare zone of a domain being created, using the API token from settings
+func (m Model) spawnLookupCloudflareZone(domain string) tea.Cmd {
+	token, demo := m.state.CloudflareAPIToken, m.demoDns != nil
+	return func() tea.Msg {
+		if demo {
+			return CloudflareZoneLookedUpMsg{Domain: domain, ZoneID: "demo"}
+		}
+		zoneID, err := cloudflare.NewCloudflareProvider(token, "").LookupZoneID(domain)
+		return CloudflareZoneLookedUpMsg{Domain: domain, ZoneID: zoneID, Error: err}
+	}
+}
+
+// spawnVerifyCloudflareDomain checks the API token from settings can edit DNS in a new domain's zone
+func (m Model) spawnVerifyCloudflareDomain(domain string, provider models.DnsProvider) tea.Cmd {
+	token, demo := m.state.CloudflareAPIToken, m.demoDns != nil
+	return func() tea.Msg {
+		if demo {
+			return CloudflareDomainVerifiedMsg{Domain: domain, Provider: provider}
+		}
+		err := cloudflare.NewCloudflareProvider(token, provider.ZoneID).VerifyAccess()
+		return CloudflareDomainVerifiedMsg{Domain: domain, Provider: provider, Error: err}
+	}
+}
+
+// healthCheckInterval returns the configured time between background health checks
+func (m Model) healthCheckInterval() time.Duration {
+	secs := m.state.HealthCheckIntervalSecs
+	if secs <= 0 {
+		secs = config.DefaultSettings().HealthCheckIntervalSecs
+	}
+	if secs < minHealthCheckIntervalSecs {
+		secs = minHealthCheckIntervalSecs
+	}
+	return time.Duration(secs) * time.Second
+}
+
+// scheduleHealthCheck sends a TickMsg after the health check interval
+// The interval is read on every tick, so a reloaded config takes effect on the next cycle
+func (m Model) scheduleHealthCheck() tea.Cmd {
+	return tea.Tick(m.healthCheckInterval(), func(time.Time) tea.Msg {
+		return TickMsg{}
+	})
+}
+
+// scheduleNodeReconnect sends a NodeReconnectMsg for an offline node after delay
+func (m Model) scheduleNodeReconnect(nodeID uuid.UUID, delay time.Duration) tea.Cmd {
+	return tea.Tick(delay, func(time.Time) tea.Msg {
+		return NodeReconnectMsg{NodeID: nodeID}
+	})
+}
+
+// scheduleConfigWatch sends a ConfigWatchTickMsg after the config watch interval
+func (m Model) scheduleConfigWatch() tea.Cmd {
+	return tea.Tick(configWatchInterval, func(time.Time) tea.Msg {
+		return ConfigWatchTickMsg{}
+	})
+}
+
+// applyCertCheck records a domain's certificate expiry and warns if it is close, returning
+// true if the expiry changed
+func (m Model) applyCertCheck(msg CertCheckedMsg) bool {
+	domain := m.state.GetDomainByID(msg.DomainID)
+	if domain == nil {
+		return false
+	}
+	if msg.Error != nil {
+		// Background checks fail quietly, e.g. for domains not served over HTTPS yet
+		if !msg.Background {
+			m.state.AddNotification("Certificate check for "+domain.Name+" failed: "+msg.Error.Error(), "error")
+		}
+		return false
+	}
+
+	expiresAt := msg.ExpiresAt
+	changed := domain.CertExpiresAt == nil || !domain.CertExpiresAt.Equal(expiresAt)
+	domain.CertExpiresAt = &expiresAt
+	date := expiresAt.Format("2006-01-02")
+	days, _ := domain.CertDaysLeft(time.Now())
+	switch {
+	case days < 0:
+		m.state.AddNotification(fmt.Sprintf("Certificate for %s expired on %s", msg.Host, date), "warning")
+	case days < models.CertExpiryWarningDays:
+		m.state.AddNotification(fmt.Sprintf("Certificate for %s expires in %d day(s) (%s)", msg.Host, days, date), "warning")
+	case !msg.Background:
+		m.state.AddNotification(fmt.Sprintf("Certificate for %s is valid until %s", domain.Name, date), "success")
+	}
+	return changed
+}
+
+// checkCertsNow starts the background certificate checks straight away
+func checkCertsNow() tea.Msg {
+	return CertTickMsg{}
+}
+
+// scheduleCertCheck sends a CertTickMsg after the certificate check interval
+func (m Model) scheduleCertCheck() tea.Cmd {
+	return tea.Tick(certCheckInterval, func(time.Time) tea.Msg {
+		return CertTickMsg{}
+	})
+}
+
+// spawnCheckCert probes the certificate served for each of a domain's hosts and reports
+// the one that expires first. Hosts that can't be reached are skipped unless all fail.
+func (m Model) spawnCheckCert(domainID uuid.UUID) tea.Cmd {
+	hosts := m.state.DomainHosts(domainID)
+	return func() tea.Msg {
+		result := CertCheckedMsg{DomainID: domainID}
+		if len(hosts) == 0 {
+			result.Error = fmt.Errorf("domain not found")
+			return result
+		}
+
+		var failures []string
+		for _, host := range hosts {
+			ctx, cancel := context.WithTimeout(context.Background(), certCheckTimeout)
+			expiresAt, err := certs.Expiry(ctx, host)
+			cancel()
+			if err != nil {
+				failures = append(failures, host+": "+err.Error())
+				continue
+			}
+			if result.Host == "" || expiresAt.Before(result.ExpiresAt) {
+				result.Host = host
+				result.ExpiresAt = expiresAt
+			}
+		}
+
+		if result.Host == "" {
+			result.Error = fmt.Errorf("%s", strings.Join(failures, "; "))
+		}
+		return result
+	}
+}
+
+// scheduleSiteHealth sends a SiteHealthTickMsg after the site health interval
+func (m Model) scheduleSiteHealth() tea.Cmd {
+	return tea.Tick(siteHealthInterval, func(time.Time) tea.Msg {
+		return SiteHealthTickMsg{}
+	})
+}
+
+// spawnCheckSiteHealth probes a site's health path on its first domain, or does nothing if the
+// site has no health path
+func (m Model) spawnCheckSiteHealth(siteID uuid.UUID) tea.Cmd {
+	site := m.state.GetSiteByID(siteID)
+	url := m.state.SiteHealthURL(siteID)
+	if site == nil || url == "" {
+		return nil
+	}
+	expected := site.ExpectedHealthStatus()
+	return func() tea.Msg {
+		ctx, cancel := context.WithTimeout(context.Background(), siteHealthTimeout)
+		defer cancel()
+		return SiteHealthCheckedMsg{SiteID: siteID, Error: health.Check(ctx, url, expected)}
+	}
+}
+
+// spawnCheckPropagation queries the public resolvers for each of a domain's records and
+// reports which of them return the record's value
+func (m Model) spawnCheckPropagation(domainID uuid.UUID) tea.Cmd {
+	domain := m.state.GetDomainByID(domainID)
+	if domain == nil {
+		return nil
+	}
+	name := domain.Name
+	records := append([]models.DnsRecord(nil), domain.DnsRecords...)
+	return func() tea.Msg {
+		ctx, cancel := context.WithTimeout(context.Background(), propagationCheckTimeout)
+		defer cancel()
+
+		results := make([]dns.PropagationResult, len(records))
+		for i, record := range records {
+			results[i] = dns.CheckPropagation(ctx, dns.NetResolver{}, dns.DefaultResolvers, name, record)
+		}
+		return PropagationCheckedMsg{DomainID: domainID, Results: results}
+	}
+}
+
+// spawnSavedNodeHealthCheck health checks a node that was just created or edited, so the
+// result is reported whether or not the node is reachable
+func (m Model) spawnSavedNodeHealthCheck(nodeID uuid.UUID) tea.Cmd {
+	check := m.spawnNodeHealthCheck(nodeID)
+	return func() tea.Msg {
+		result := check().(NodeHealthCheckResultMsg)
+		result.Saved = true
+		return result
+	}
+}
+
+// scheduleMetricsRefresh sends a MetricsTickMsg after the metrics refresh interval
+func (m Model) scheduleMetricsRefresh() tea.Cmd {
+	return tea.Tick(metricsRefreshInterval, func(time.Time) tea.Msg {
+		return MetricsTickMsg{}
+	})
+}
+
+// backgroundHealthChecks health checks every node, marking the results as background
+func (m Model) backgroundHealthChecks() []tea.Cmd {
+	cmds := make([]tea.Cmd, 0, len(m.state.Nodes))
+	for _, node := range m.state.Nodes {
+		check := m.spawnNodeHealthCheck(node.ID)
+		cmds = append(cmds, func() tea.Msg {
+			result := check().(NodeHealthCheckResultMsg)
+			result.Background = true
+			return result
+		})
+	}
+	return cmds
+}
+
+// spawnFetchSiteStatus asks a site's node for the live container status
+func (m Model) spawnFetchSiteStatus(siteID uuid.UUID) tea.Cmd {
+	return func() tea.Msg {
+		site := m.state.GetSiteByID(siteID)
+		if site == nil {
+			return SiteStatusResultMsg{SiteID: siteID, Error: fmt.Errorf("site not found")}
+		}
+
+		node := m.state.GetNodeByID(site.NodeID)
+		if node == nil {
+			return SiteStatusResultMsg{SiteID: siteID, Error: fmt.Errorf("node not found")}
+		}
+
+		status, err := m.nodeClientFor(node).GetSiteStatus(node.APIEndpoint, node.APIKey, siteID, site.Name, site.GetSiteType())
+		if err != nil {
+			return SiteStatusResultMsg{SiteID: siteID, Error: fmt.Errorf("%s: %w", site.Name, err)}
+		}
+
+		return SiteStatusResultMsg{SiteID: siteID, Status: status.Status, Container: status}
+	}
+}
+
+// spawnPollSiteStatus fetches a site's live status like spawnFetchSiteStatus, marking the result
+// as background so a failure isn't reported
+func (m Model) spawnPollSiteStatus(siteID uuid.UUID) tea.Cmd {
+	fetch := m.spawnFetchSiteStatus(siteID)
+	return func() tea.Msg {
+		result := fetch().(SiteStatusResultMsg)
+		result.Background = true
+		return result
+	}
+}
+
+// resumeDeployRecheck is how long to wait before checking again on a deploy from the last
+// session that its node is still running
+const resumeDeployRecheck = 10 * time.Second
+
+// resumeDeployTimeout is how long after it started a deploy from the last session is waited
+// for, before it is taken to have been cut short
+const resumeDeployTimeout = 15 * time.Minute
+
+// resumeDeploys checks every site left deploying by the last session on its node
+func (m Model) resumeDeploys() tea.Cmd {
+	var cmds []tea.Cmd
+	for _, id := range m.state.DeployingSiteIDs() {
+		cmds = append(cmds, m.spawnResumeDeploy(id))
+	}
+	return tea.Batch(cmds...)
+}
+
+// spawnResumeDeploy fetches the node's status of a site left deploying by the last session
+func (m Model) spawnResumeDeploy(siteID uuid.UUID) tea.Cmd {
+	return func() tea.Msg {
+		site := m.state.GetSiteByID(siteID)
+		if site == nil {
+			return DeployResumedMsg{SiteID: siteID, Error: fmt.Errorf("site not found")}
+		}
+		node := m.state.GetNodeByID(site.NodeID)
+		if node == nil {
+			return DeployResumedMsg{SiteID: siteID, Error: fmt.Errorf("node not found")}
+		}
+
+		status, err := m.nodeClientFor(node).GetSiteStatus(node.APIEndpoint, node.APIKey, siteID, site.Name, site.GetSiteType())
+		if err != nil {
+			return DeployResumedMsg{SiteID: siteID, Error: err}
+		}
+		return DeployResumedMsg{SiteID: siteID, Status: status.Status, StartedAt: status.StartedAt}
+	}
+}
+
+// startedSince reports whether the running container was started by a deploy that began at
+// deployStart rather than left over from before it. Without either time (e.g. for a compose
+// site) the running site is taken to be the deployed one.
+func (msg DeployResumedMsg) startedSince(deployStart time.Time) bool {
+	return deployStart.IsZero() || msg.StartedAt == nil || !msg.StartedAt.Before(deployStart)
+}
+
+// recordDeploying keeps the list of sites being deployed next to the config up to date, so
+// deploys cut short by Archon exiting are checked at the next start
+func (m Model) recordDeploying() {
+	if m.state.ConfigPath == "" {
+		return
+	}
+	if err := state.SaveDeploying(state.DeployingPath(m.state.ConfigPath), m.state.DeployingSites()); err != nil {
+		m.state.AddNotification("Failed to record deploys in progress: "+err.Error(), "warning")
+	}
+}
+
+// refreshLiveData re-fetches the remote data shown on the current screen:
+// node health on node screens, site status on site screens and DNS records on domain screens
+// The dashboard summarises nodes and sites, so it refreshes both
+func (m Model) refreshLiveData() tea.Cmd {
+	var cmds []tea.Cmd
+	var what string
+
+	refreshNodes := func() {
+		for _, node := range m.state.Nodes {
+			cmds = append(cmds, m.spawnNodeHealthCheck(node.ID))
+		}
+	}
+	refreshSites := func(sites []models.Site) {
+		for _, site := range sites {
+			cmds = append(cmds, m.spawnFetchSiteStatus(site.ID))
+		}
+	}
+	refreshDomain := func(domain models.Domain) {
+		if domain.DnsProvider.Type != models.DnsProviderManual {
+			cmds = append(cmds, m.spawnSyncDns(domain.ID, false))
+		}
+	}
+
+	switch m.state.CurrentScreen {
+	case state.ScreenDashboard:
+		refreshNodes()
+		refreshSites(m.state.Sites)
+		what = "nodes and sites"
+	case state.ScreenNodesList, state.ScreenNodeConfig:
+		refreshNodes()
+		what = "node health"
+	case state.ScreenSitesList:
+		refreshSites(m.state.VisibleSites())
+		what = "site status"
+	case state.ScreenDomainsList:
+		for _, domain := range m.state.Domains {
+			refreshDomain(domain)
+		}
+		what = "DNS records"
+	case state.ScreenDomainDnsRecords:
+		if domain := m.state.GetDomainByID(m.state.SelectedDomainID); domain != nil {
+			refreshDomain(*domain)
+		}
+		what = "DNS records"
+	case state.ScreenSiteDetail:
+		if m.state.GetSiteByID(m.state.SelectedSiteID) != nil {
+			cmds = append(cmds, m.siteDetailCmds(m.state.SelectedSiteID)...)
+		}
+		what = "site details"
+	}
+
+	if len(cmds) == 0 {
+		m.state.AddNotification("Nothing to refresh on this screen", "info")
+		return nil
+	}
+
+	m.state.AddNotification(fmt.Sprintf("Refreshing %s (%d requests)...", what, len(cmds)), "info")
+	return tea.Batch(cmds...)
+}
+
+// spawnReloadConfig re-reads the config file from disk
+func (m Model) spawnReloadConfig() tea.Cmd {
+	return func() tea.Msg {
+		cfg, err := m.configLoader.Load(m.configPath)
+		return ConfigReloadedMsg{Config: cfg, Error: err}
+	}
+}
+
+// saveConfig saves the config in the background, reporting the result as a ConfigSavedMsg
 func (m Model) saveConfig() tea.Cmd {
+	return m.saveConfigCmd(false)
+}
+
+// saveConfigCmd returns a command saving the config as it is now. Update goes on changing the
+// state, so the config is copied here, and the slow part of the save (encrypting secrets,
+// keyring access, writing the files) runs in the command. announce reports success too.
+func (m Model) saveConfigCmd(announce bool) tea.Cmd {
+	m.watch.saving++
+	snapshot, err := toml.Marshal(m.currentConfig())
+	if err != nil {
+		return func() tea.Msg { return ConfigSavedMsg{Error: err} }
+	}
+	fingerprint, _ := configFingerprint(m.currentConfig())
+	m.saves.started++
+	seq := m.saves.started
+
+	loader, path, saves := m.configLoader, m.configPath, m.saves
 	return func() tea.Msg {
-		// TODO: Implement config save
-		// 1. Build config.Config from m.state
-		// 2. Call m.configLoader.Save()
-		// 3. Return ConfigSavedMsg
-
-		// cfg := &config.Config{
-		//     Version:  "1.0.0",
-		//     Sites:    m.state.Sites,
-		//     Domains:  m.state.Domains,
-		//     Nodes:    m.state.Nodes,
-		//     Settings: ...,
-		// }
-		//
-		// err := m.configLoader.Save(m.configPath, cfg)
-		// return ConfigSavedMsg{Error: err}
+		var cfg config.Config
+		if err := toml.Unmarshal(snapshot, &cfg); err != nil {
+			return ConfigSavedMsg{Error: err}
+		}
 
-		return ConfigSavedMsg{Error: nil}
+		saves.mu.Lock()
+		defer saves.mu.Unlock()
+		if seq <= saves.written {
+			// A save started later has already written these changes and any since
+			return ConfigSavedMsg{Announce: announce}
+		}
+		if err := loader.Save(path, &cfg); err != nil {
+			return ConfigSavedMsg{Error: err}
+		}
+		saves.written = seq
+		return ConfigSavedMsg{Fingerprint: fingerprint, Announce: announce}
 	}
 }
@@ -0,0 +1,582 @@
+package app
+
+import (
+	"encoding/json"
+	"errors"
+	"flag"
+	"fmt"
+	"io"
+	"os"
+	"path/filepath"
+	"strconv"
+	"strings"
+
+	"github.com/google/uuid"
+
+	"github.com/BlueBeard63/archon/internal/api"
+	"github.com/BlueBeard63/archon/internal/compose"
+	"github.com/BlueBeard63/archon/internal/config"
+	"github.com/BlueBeard63/archon/internal/dns"
+	"github.com/BlueBeard63/archon/internal/models"
+	"github.com/BlueBeard63/archon/internal/state"
+)
+
+// ErrUsage is returned by RunCommand when the command line is malformed
+var ErrUsage = errors.New("usage error")
+
+// CommandUsage describes the headless subcommands
+const CommandUsage = `Usage:
+  archon [--config <path>]                                Start the terminal UI
+  archon --demo                                           Start the terminal UI with simulated nodes and DNS
+  archon [--config <path>] deploy [--json] <site>         Deploy a site to its node
+  archon [--config <path>] sync-dns [--json] <domain>     Pull a domain's DNS records from its provider
+  archon [--config <path>] node health [--json] <node>    Check a node's health
+  archon [--config <path>] import compose [--node <node>] [--domain <domain>] [--json] <file>
+                                                          Create a site for each service in a docker-compose.yml
+  archon [--config <path>] export compose [--output <file>] <site>
+                                                          Write a site as a docker-compose.yml for plain Docker
+  archon [--config <path>] config backups [--json]        List the backups kept of the config
+  archon [--config <path>] config restore <n>             Roll the config back to backup n (1 is the most recent)
+
+Flags:
+  --config <path>    Config file to use instead of the default (sites and nodes are stored next to it)
+  --demo             Try Archon with sample nodes, domains and sites; nothing is sent to a node or DNS provider
+  --json             Print the result as JSON on stdout instead of text
+  --node <node>      Node imported sites run on (optional when only one node is configured)
+  --domain <domain>  Domain for services without a Traefik Host rule, using the service name as subdomain
+  --output <file>    File to write the exported compose file to (default <site>.compose.yml)`
+
+// commandOptions holds the flags shared by the headless subcommands
+type commandOptions struct {
+	json   bool
+	node   string // import compose only
+	domain string // import compose only
+	output string // export compose only
+}
+
+// DnsSyncResult is the JSON output of sync-dns
+type DnsSyncResult struct {
+	Domain    string             `json:"domain"`
+	Added     int                `json:"added"`
+	Updated   int                `json:"updated"`
+	LocalOnly int                `json:"local_only"`
+	Conflicts []models.DnsRecord `json:"conflicts"`
+}
+
+// ComposeExportResult is the JSON output of export compose
+type ComposeExportResult struct {
+	Site string `json:"site"`
+	Path string `json:"path"`
+}
+
+// ComposeImportResult is the JSON output of import compose
+type ComposeImportResult struct {
+	Sites    []string `json:"sites"`
+	Warnings []string `json:"warnings"`
+}
+
+// RunCommand runs a headless subcommand without starting the terminal UI.
+// It reuses the same async operations as the interactive app, waiting for each to finish,
+// and writes results to out. A non-nil error means the command failed.
+func (m *Model) RunCommand(args []string, out io.Writer) error {
+	if len(args) == 0 {
+		return usageError("missing command")
+	}
+
+	switch args[0] {
+	case "deploy":
+		opts, name, err := parseCommandArgs("deploy", args[1:])
+		if err != nil {
+			return err
+		}
+		return m.runDeploy(name, opts, out)
+
+	case "sync-dns":
+		opts, name, err := parseCommandArgs("sync-dns", args[1:])
+		if err != nil {
+			return err
+		}
+		return m.runSyncDns(name, opts, out)
+
+	case "node":
+		if len(args) < 2 || args[1] != "health" {
+			return usageError("expected: node health <node>")
+		}
+		opts, name, err := parseCommandArgs("node health", args[2:])
+		if err != nil {
+			return err
+		}
+		return m.runNodeHealth(name, opts, out)
+
+	case "import":
+		if len(args) < 2 || args[1] != "compose" {
+			return usageError("expected: import compose <file>")
+		}
+		opts, file, err := parseCommandArgs("import compose", args[2:])
+		if err != nil {
+			return err
+		}
+		return m.runImportCompose(file, opts, out)
+
+	case "export":
+		if len(args) < 2 || args[1] != "compose" {
+			return usageError("expected: export compose <site>")
+		}
+		opts, name, err := parseCommandArgs("export compose", args[2:])
+		if err != nil {
+			return err
+		}
+		return m.runExportCompose(name, opts, out)
+
+	case "config":
+		return RunConfigCommand(m.configPath, args[1:], out)
+	}
+
+	return usageError("unknown command: " + args[0])
+}
+
+// parseCommandArgs parses a subcommand's flags and its single name argument
+func parseCommandArgs(command string, args []string) (commandOptions, string, error) {
+	opts, rest, err := parseCommandFlags(command, args)
+	if err != nil {
+		return opts, "", err
+	}
+	if len(rest) != 1 {
+		return opts, "", usageError(command + " takes exactly one name")
+	}
+	return opts, rest[0], nil
+}
+
+// parseCommandFlags parses a subcommand's flags, returning the remaining arguments
+func parseCommandFlags(command string, args []string) (commandOptions, []string, error) {
+	var opts commandOptions
+	flags := flag.NewFlagSet(command, flag.ContinueOnError)
+	flags.SetOutput(io.Discard)
+	flags.BoolVar(&opts.json, "json", false, "print the result as JSON")
+	if command == "import compose" {
+		flags.StringVar(&opts.node, "node", "", "node imported sites run on")
+		flags.StringVar(&opts.domain, "domain", "", "domain for services without a Traefik Host rule")
+	}
+	if command == "export compose" {
+		flags.StringVar(&opts.output, "output", "", "file to write the compose file to")
+	}
+
+	if err := flags.Parse(args); err != nil {
+		return opts, nil, usageError(err.Error())
+	}
+	return opts, flags.Args(), nil
+}
+
+// usageError wraps ErrUsage with a description of what was wrong
+func usageError(message string) error {
+	return fmt.Errorf("%w: %s", ErrUsage, message)
+}
+
+// writeJSON prints v as indented JSON
+func writeJSON(out io.Writer, v interface{}) error {
+	encoder := json.NewEncoder(out)
+	encoder.SetIndent("", "  ")
+	return encoder.Encode(v)
+}
+
+// runDeploy deploys the named site and records the outcome in its status
+func (m *Model) runDeploy(name string, opts commandOptions, out io.Writer) error {
+	site := m.findSite(name)
+	if site == nil {
+		return fmt.Errorf("site not found: %s", name)
+	}
+
+	if !opts.json {
+		fmt.Fprintf(out, "Deploying site: %s\n", site.Name)
+	}
+	result := m.spawnDeploySite(site.ID, "")().(SiteDeployedMsg)
+	m.recordAudit(state.OpDeploySite, "site", site.ID, site.Name, result.Error)
+
+	recordDeployResult(site, result)
+	if m.state.AutoSave {
+		if err := m.saveConfigSync(); err != nil {
+			fmt.Fprintf(os.Stderr, "Warning: failed to save config: %v\n", err)
+		}
+	}
+
+	if result.Error != nil {
+		return fmt.Errorf("deployment failed: %w", result.Error)
+	}
+	if partial := result.partialFailure(); partial != nil {
+		return fmt.Errorf("deployment incomplete: %w", partial)
+	}
+
+	if opts.json {
+		// Older nodes don't send a response, so report what is known
+		resp := result.Response
+		if resp == nil {
+			resp = &api.DeployResponse{SiteID: site.ID, Status: string(site.Status)}
+		}
+		return writeJSON(out, resp)
+	}
+	fmt.Fprintln(out, "Site deployed successfully")
+	if result.Response != nil && result.Response.ContainerID != "" {
+		fmt.Fprintf(out, "Container: %s\n", result.Response.ContainerID)
+	}
+	return nil
+}
+
+// runExportCompose writes the named site as a docker-compose.yml, with the Traefik labels a deploy would use
+func (m *Model) runExportCompose(name string, opts commandOptions, out io.Writer) error {
+	site := m.findSite(name)
+	if site == nil {
+		return fmt.Errorf("site not found: %s", name)
+	}
+
+	// Route the first mapping's full domain, as deploys do
+	domainName := ""
+	if domain := m.state.GetDomainByID(site.DomainID); domain != nil {
+		if mappings := site.GetDomainMappings(); len(mappings) > 0 {
+			domainName = models.GetFullDomain(domain.Name, mappings[0].Subdomain)
+		}
+	}
+	if domainName == "" && !site.IsCompose() {
+		return fmt.Errorf("site %s has no domain to route", site.Name)
+	}
+
+	content, err := compose.ExportSite(site, domainName)
+	if err != nil {
+		return fmt.Errorf("failed to export site: %w", err)
+	}
+
+	path := opts.output
+	if path == "" {
+		path = compose.ServiceName(site.Name) + ".compose.yml"
+	}
+	// Environment variables may hold secrets
+	if err := os.WriteFile(path, content, 0600); err != nil {
+		return fmt.Errorf("failed to write compose file: %w", err)
+	}
+
+	if opts.json {
+		return writeJSON(out, ComposeExportResult{Site: site.Name, Path: path})
+	}
+	fmt.Fprintf(out, "Exported %s to %s\n", site.Name, path)
+	return nil
+}
+
+// runSyncDns pulls the named domain's records from its provider and merges them into the config
+func (m *Model) runSyncDns(name string, opts commandOptions, out io.Writer) error {
+	domain := m.findDomain(name)
+	if domain == nil {
+		return fmt.Errorf("domain not found: %s", name)
+	}
+
+	result := m.spawnSyncDns(domain.ID, false)().(DnsSyncedMsg)
+	if result.Error != nil {
+		return fmt.Errorf("DNS sync failed: %w", result.Error)
+	}
+
+	merged := dns.MergeRecords(domain.DnsRecords, result.Records)
+	domain.DnsRecords = merged.Records
+	m.recordAudit("apply_dns_sync", "domain", domain.ID, domain.Name, nil)
+
+	// Headless syncs always save, since the merged records are the point of the command
+	if err := m.saveConfigSync(); err != nil {
+		return fmt.Errorf("failed to save config: %w", err)
+	}
+
+	if opts.json {
+		return writeJSON(out, DnsSyncResult{
+			Domain:    domain.Name,
+			Added:     merged.Added,
+			Updated:   merged.Updated,
+			LocalOnly: len(merged.LocalOnly),
+			Conflicts: merged.Conflicts,
+		})
+	}
+
+	for _, rec := range merged.Conflicts {
+		fmt.Fprintf(out, "Conflict: %s %s changed locally (%s) and at the provider (%s), keeping local value\n",
+			rec.RecordType, rec.Name, rec.Value, rec.ConflictValue)
+	}
+	fmt.Fprintf(out, "DNS synced for %s: %d added, %d updated, %d local-only\n",
+		domain.Name, merged.Added, merged.Updated, len(merged.LocalOnly))
+	return nil
+}
+
+// runNodeHealth checks the named node and fails unless it reports online
+func (m *Model) runNodeHealth(name string, opts commandOptions, out io.Writer) error {
+	node := m.findNode(name)
+	if node == nil {
+		return fmt.Errorf("node not found: %s", name)
+	}
+
+	result := m.spawnNodeHealthCheck(node.ID)().(NodeHealthCheckResultMsg)
+	if result.Error != nil {
+		return fmt.Errorf("node health check failed: %w", result.Error)
+	}
+
+	if opts.json {
+		health := api.HealthResponse{Status: node.Status, Docker: node.DockerInfo, Traefik: node.TraefikInfo}
+		if err := writeJSON(out, health); err != nil {
+			return err
+		}
+	} else {
+		fmt.Fprintf(out, "Node %s: %s", node.Name, node.Status)
+		if node.LastLatencyMs != nil {
+			fmt.Fprintf(out, " (%dms)", *node.LastLatencyMs)
+		}
+		fmt.Fprintln(out)
+		if node.DockerInfo != nil {
+			fmt.Fprintf(out, "Docker %s: %d containers running, %d images\n",
+				node.DockerInfo.Version, node.DockerInfo.ContainersRunning, node.DockerInfo.ImagesCount)
+		}
+		if node.TraefikInfo != nil {
+			fmt.Fprintf(out, "Traefik %s: %d routers, %d services\n",
+				node.TraefikInfo.Version, node.TraefikInfo.RoutersCount, node.TraefikInfo.ServicesCount)
+		}
+	}
+
+	if node.Status != models.NodeStatusOnline {
+		return fmt.Errorf("node %s is %s", node.Name, node.Status)
+	}
+	return nil
+}
+
+// runImportCompose creates a site on the chosen node for each service in a compose file.
+// Anything a site can't represent is reported as a warning instead of failing the import.
+func (m *Model) runImportCompose(path string, opts commandOptions, out io.Writer) error {
+	node, err := m.importNode(opts.node)
+	if err != nil {
+		return err
+	}
+	var fallback *models.Domain
+	if opts.domain != "" {
+		if fallback = m.findDomain(opts.domain); fallback == nil {
+			return fmt.Errorf("domain not found: %s", opts.domain)
+		}
+	}
+
+	data, err := os.ReadFile(path)
+	if err != nil {
+		return fmt.Errorf("failed to read compose file: %w", err)
+	}
+	services, warnings, err := compose.ImportServices(string(data))
+	if err != nil {
+		return err
+	}
+
+	// Build contexts are relative to the compose file, not to where archon was run
+	composeDir, err := filepath.Abs(filepath.Dir(path))
+	if err != nil {
+		return fmt.Errorf("failed to resolve compose file directory: %w", err)
+	}
+
+	result := ComposeImportResult{Sites: []string{}}
+	for _, service := range services {
+		service.ResolveBuildContext(composeDir)
+		if m.state.SiteNameTaken(service.Name, uuid.Nil) {
+			warnings = append(warnings, fmt.Sprintf("%s: skipped, a site with this name already exists", service.Name))
+			continue
+		}
+		site, siteWarnings := m.siteFromService(service, node, fallback)
+		warnings = append(warnings, siteWarnings...)
+		m.state.Sites = append(m.state.Sites, *site)
+		result.Sites = append(result.Sites, site.Name)
+		m.recordAudit("create_site", "site", site.ID, site.Name, nil)
+	}
+	result.Warnings = append([]string{}, warnings...)
+
+	if len(result.Sites) > 0 {
+		// Like sync-dns, the new sites are the point of the command, so always save
+		if err := m.saveConfigSync(); err != nil {
+			return fmt.Errorf("failed to save config: %w", err)
+		}
+	}
+
+	if opts.json {
+		return writeJSON(out, result)
+	}
+	for _, warning := range result.Warnings {
+		fmt.Fprintf(out, "Warning: %s\n", warning)
+	}
+	fmt.Fprintf(out, "Imported %d sites onto node %s", len(result.Sites), node.Name)
+	if len(result.Sites) > 0 {
+		fmt.Fprintf(out, ": %s", strings.Join(result.Sites, ", "))
+	}
+	fmt.Fprintln(out)
+	return nil
+}
+
+// importNode finds the node named on the command line, or the only configured node if none was named
+func (m *Model) importNode(name string) (*models.Node, error) {
+	if name == "" {
+		if len(m.state.Nodes) != 1 {
+			return nil, usageError("--node is required when more than one node is configured")
+		}
+		return &m.state.Nodes[0], nil
+	}
+	if node := m.findNode(name); node != nil {
+		return node, nil
+	}
+	return nil, fmt.Errorf("node not found: %s", name)
+}
+
+// findSite returns the configured site with the given name, or nil
+func (m *Model) findSite(name string) *models.Site {
+	for i := range m.state.Sites {
+		if strings.EqualFold(m.state.Sites[i].Name, name) {
+			return &m.state.Sites[i]
+		}
+	}
+	return nil
+}
+
+// findNode returns the configured node with the given name, or nil
+func (m *Model) findNode(name string) *models.Node {
+	for i := range m.state.Nodes {
+		if strings.EqualFold(m.state.Nodes[i].Name, name) {
+			return &m.state.Nodes[i]
+		}
+	}
+	return nil
+}
+
+// findDomain returns the configured domain with the given name, or nil
+func (m *Model) findDomain(name string) *models.Domain {
+	for i := range m.state.Domains {
+		if models.SameDomainName(m.state.Domains[i].Name, name) {
+			return &m.state.Domains[i]
+		}
+	}
+	return nil
+}
+
+// siteFromService builds a site from an imported compose service. Traefik hostnames are
+// matched to configured domains; without any, the fallback domain is used with the service
+// name as subdomain.
+func (m *Model) siteFromService(service compose.ImportedService, node *models.Node, fallback *models.Domain) (*models.Site, []string) {
+	var warnings []string
+	containerPort, hostPort := service.Port()
+	if containerPort == 0 {
+		warnings = append(warnings, fmt.Sprintf("%s: no ports found, set one before deploying", service.Name))
+	} else if hostPort != 0 {
+		if other := m.state.PortInUse(node.ID, hostPort, uuid.Nil); other != nil {
+			warnings = append(warnings, fmt.Sprintf("%s: host port %d is already used by %s on %s", service.Name, hostPort, other.Name, node.Name))
+		}
+	}
+
+	var mappings []models.DomainMapping
+	for _, host := range service.Hosts {
+		host = strings.TrimSuffix(host, ".")
+		domain := m.domainForHost(host)
+		if domain == nil {
+			warnings = append(warnings, fmt.Sprintf("%s: no configured domain serves %s", service.Name, host))
+			continue
+		}
+		subdomain := strings.TrimSuffix(strings.TrimSuffix(host, domainHostName(domain)), ".")
+		mappings = append(mappings, models.DomainMapping{DomainID: domain.ID, Subdomain: subdomain, Port: containerPort, HostPort: hostPort})
+	}
+	if len(mappings) == 0 && fallback != nil {
+		mappings = append(mappings, models.DomainMapping{DomainID: fallback.ID, Subdomain: service.Name, Port: containerPort, HostPort: hostPort})
+	}
+	if len(mappings) == 0 {
+		warnings = append(warnings, fmt.Sprintf("%s: no domain, set one before deploying", service.Name))
+	}
+
+	domainID := uuid.Nil
+	if len(mappings) > 0 {
+		domainID = mappings[0].DomainID
+	}
+	site := models.NewSite(service.Name, domainID, node.ID, service.Image, containerPort)
+	site.DomainMappings = mappings
+	site.Build = service.Build
+	site.EnvironmentVars = service.Environment
+	site.Volumes = service.Volumes
+	return site, warnings
+}
+
+// domainForHost returns the configured domain a hostname belongs to, preferring the longest match
+func (m *Model) domainForHost(host string) *models.Domain {
+	host = strings.TrimSuffix(strings.ToLower(host), ".")
+	var best *models.Domain
+	for i := range m.state.Domains {
+		name := domainHostName(&m.state.Domains[i])
+		if host != name && !strings.HasSuffix(host, "."+name) {
+			continue
+		}
+		if best == nil || len(name) > len(domainHostName(best)) {
+			best = &m.state.Domains[i]
+		}
+	}
+	return best
+}
+
+// domainHostName returns a domain's name as it appears in hostnames: lower case, without a root dot
+func domainHostName(domain *models.Domain) string {
+	return strings.TrimSuffix(strings.ToLower(domain.Name), ".")
+}
+
+// RunConfigCommand runs a config subcommand (the arguments after "config"). It works on the
+// files directly, without loading the config, so a config that no longer loads can be restored.
+func RunConfigCommand(configPath string, args []string, out io.Writer) error {
+	if len(args) == 0 {
+		return usageError("expected: config backups or config restore <n>")
+	}
+	switch args[0] {
+	case "backups":
+		opts, rest, err := parseCommandFlags("config backups", args[1:])
+		if err != nil {
+			return err
+		}
+		if len(rest) > 0 {
+			return usageError("config backups takes no arguments")
+		}
+		return runConfigBackups(configPath, opts, out)
+	case "restore":
+		opts, number, err := parseCommandArgs("config restore", args[1:])
+		if err != nil {
+			return err
+		}
+		n, err := strconv.Atoi(number)
+		if err != nil || n < 1 {
+			return usageError("backup number must be 1 or more: " + number)
+		}
+		return runConfigRestore(configPath, n, opts, out)
+	}
+	return usageError("unknown config command: " + args[0])
+}
+
+// runConfigBackups lists the backups kept of the config file, most recent first
+func runConfigBackups(configPath string, opts commandOptions, out io.Writer) error {
+	backups, err := config.ListBackups(configPath)
+	if err != nil {
+		return fmt.Errorf("failed to list backups: %w", err)
+	}
+
+	if opts.json {
+		if backups == nil {
+			backups = []config.Backup{}
+		}
+		return writeJSON(out, backups)
+	}
+	if len(backups) == 0 {
+		fmt.Fprintf(out, "No backups of %s yet\n", configPath)
+		return nil
+	}
+	fmt.Fprintf(out, "Backups of %s:\n", configPath)
+	for _, backup := range backups {
+		fmt.Fprintf(out, "  %d  %s  %d sites, %d domains, %d nodes\n",
+			backup.Number, backup.ModTime.Format("2006-01-02 15:04:05"), backup.Sites, backup.Domains, backup.Nodes)
+	}
+	return nil
+}
+
+// runConfigRestore rolls the config back to a backup
+func runConfigRestore(configPath string, n int, opts commandOptions, out io.Writer) error {
+	if err := config.RestoreBackup(configPath, n); err != nil {
+		return fmt.Errorf("failed to restore backup: %w", err)
+	}
+
+	if opts.json {
+		return writeJSON(out, map[string]int{"restored": n})
+	}
+	fmt.Fprintf(out, "Restored %s from backup %d; the replaced config was backed up first\n", configPath, n)
+	return nil
+}
@@ -0,0 +1,65 @@
+package app
+
+import (
+	"errors"
+	"os"
+	"path/filepath"
+	"testing"
+
+	"github.com/BlueBeard63/archon/internal/compose"
+)
+
+func TestParseCommandArgs(t *testing.T) {
+	tests := []struct {
+		name      string
+		args      []string
+		wantName  string
+		wantJSON  bool
+		wantUsage bool
+	}{
+		{name: "name only", args: []string{"blog"}, wantName: "blog"},
+		{name: "json flag before name", args: []string{"--json", "blog"}, wantName: "blog", wantJSON: true},
+		{name: "single dash json flag", args: []string{"-json", "blog"}, wantName: "blog", wantJSON: true},
+		{name: "missing name", args: nil, wantUsage: true},
+		{name: "extra argument", args: []string{"blog", "shop"}, wantUsage: true},
+		{name: "unknown flag", args: []string{"--yaml", "blog"}, wantUsage: true},
+	}
+
+	for _, tt := range tests {
+		t.Run(tt.name, func(t *testing.T) {
+			opts, name, err := parseCommandArgs("deploy", tt.args)
+			if tt.wantUsage {
+				if !errors.Is(err, ErrUsage) {
+					t.Fatalf("parseCommandArgs() error = %v, want ErrUsage", err)
+				}
+				return
+			}
+			if err != nil {
+				t.Fatalf("parseCommandArgs() error = %v", err)
+			}
+			if name != tt.wantName || opts.json != tt.wantJSON {
+				t.Errorf("parseCommandArgs() = (%q, json=%v), want (%q, json=%v)", name, opts.json, tt.wantName, tt.wantJSON)
+			}
+		})
+	}
+}
+
+func TestSiteFromServiceMatchesRootDotDomain(t *testing.T) {
+	m, err := NewDemoModel()
+	if err != nil {
+		t.Fatalf("NewDemoModel() error = %v", err)
+	}
+	t.Cleanup(func() { os.RemoveAll(filepath.Dir(m.configPath)) })
+
+	domain := &m.state.Domains[0]
+	domain.Name = "Example.com."
+	service := compose.ImportedService{Name: "shop", Image: "shop:1", Hosts: []string{"shop.example.com."}}
+
+	site, _ := m.siteFromService(service, &m.state.Nodes[0], nil)
+	if len(site.DomainMappings) != 1 {
+		t.Fatalf("DomainMappings = %+v, want one mapping", site.DomainMappings)
+	}
+	if mapping := site.DomainMappings[0]; mapping.DomainID != domain.ID || mapping.Subdomain != "shop" {
+		t.Errorf("mapping = %+v, want subdomain shop of %s", mapping, domain.Name)
+	}
+}
@@ -0,0 +1,111 @@
+package app
+
+import (
+	"fmt"
+	"net"
+	"os"
+	"path/filepath"
+
+	"github.com/BlueBeard63/archon/internal/api"
+	"github.com/BlueBeard63/archon/internal/config"
+	"github.com/BlueBeard63/archon/internal/dns"
+	"github.com/BlueBeard63/archon/internal/models"
+)
+
+// NewDemoModel creates a model for trying Archon without a node server or DNS provider account.
+// A sample config is written to a new temporary directory, so the user's own config is never
+// touched; nodes are answered by an in-memory client and DNS by an in-memory provider.
+func NewDemoModel() (*Model, error) {
+	dir, err := os.MkdirTemp("", "archon-demo-")
+	if err != nil {
+		return nil, fmt.Errorf("failed to create demo directory: %w", err)
+	}
+	configPath := filepath.Join(dir, "config.toml")
+	cfg := demoConfig()
+	if err := config.NewFileConfigLoader().Save(configPath, cfg); err != nil {
+		return nil, fmt.Errorf("failed to write demo config: %w", err)
+	}
+
+	m, err := NewModel(configPath, "")
+	if err != nil {
+		return nil, err
+	}
+	client, provider := api.NewDemoNodeClient(), dns.NewDemoProvider()
+	m.nodeClient = client
+	m.demoDns = provider
+
+	// Sites marked running are deployed, with the DNS records a deploy needs
+	for i := range m.state.Sites {
+		site := &m.state.Sites[i]
+		if site.Status != models.SiteStatusRunning {
+			continue
+		}
+		node := m.state.GetNodeByID(site.NodeID)
+		domain := m.state.GetDomainByID(site.DomainID)
+		if node == nil || domain == nil {
+			continue
+		}
+		var domainName string
+		for _, mapping := range site.GetDomainMappings() {
+			fullDomain := models.GetFullDomain(domain.Name, mapping.Subdomain)
+			if domainName == "" {
+				domainName = fullDomain
+			}
+			if domain.DnsProvider.Type == models.DnsProviderManual {
+				continue
+			}
+			record := models.NewDnsRecord(models.RecordTypeForIP(node.DNSTargetIP()), fullDomain, node.DNSTargetIP().String(), 300)
+			if _, err := provider.CreateRecord(domain.Name, record, nil); err != nil {
+				return nil, err
+			}
+		}
+		if err := client.DeploySite(node.APIEndpoint, node.APIKey, site, domainName); err != nil {
+			return nil, fmt.Errorf("failed to deploy demo site %s: %w", site.Name, err)
+		}
+	}
+
+	m.state.AddNotification("Demo mode: nodes and DNS are simulated, and changes are saved to "+configPath, "info")
+	return m, nil
+}
+
+// demoConfig returns the sample nodes, domains and sites shown in demo mode
+func demoConfig() *config.Config {
+	settings := config.DefaultSettings()
+	settings.CloudflareAPIToken = "demo"
+
+	euNode := models.NewNode("edge-eu-1", "http://10.0.1.10:8080", "demo", net.ParseIP("203.0.113.10"), models.ProxyTypeTraefik)
+	euNode.Tags = []string{"eu-west", "production"}
+	usNode := models.NewNode("edge-us-1", "http://10.0.2.10:8080", "demo", net.ParseIP("198.51.100.20"), models.ProxyTypeTraefik)
+	usNode.Tags = []string{"us-east", "production"}
+	stagingNode := models.NewNode("staging", "http://10.0.3.10:8080", "demo", net.ParseIP("192.0.2.30"), models.ProxyTypeNginx)
+	stagingNode.Tags = []string{"staging"}
+
+	mainDomain := models.NewDomain("example.com", models.DnsProvider{Type: models.DnsProviderCloudflare, ZoneID: "demo"})
+	docsDomain := models.NewDomain("example.org", models.DnsProvider{Type: models.DnsProviderManual})
+
+	blog := models.NewSite("blog", mainDomain.ID, euNode.ID, "ghost:5-alpine", 2368)
+	blog.DomainMappings[0].Subdomain = "blog"
+	blog.EnvironmentVars = map[string]string{"url": "https://blog.example.com", "database__client": "sqlite3"}
+	blog.Status = models.SiteStatusRunning
+
+	shop := models.NewSite("shop", mainDomain.ID, usNode.ID, "nginx:1.27-alpine", 80)
+	shop.DomainMappings[0].Subdomain = "shop"
+	shop.DomainMappings = append(shop.DomainMappings, models.DomainMapping{DomainID: mainDomain.ID, Subdomain: "www", Port: 80})
+	shop.Tags = []string{"storefront"}
+	shop.Status = models.SiteStatusRunning
+
+	backend := models.NewSite("api", mainDomain.ID, euNode.ID, "ghcr.io/example/api:1.4.2", 3000)
+	backend.DomainMappings[0].Subdomain = "api"
+	backend.EnvironmentVars = map[string]string{"NODE_ENV": "production", "API_TOKEN": "demo-secret"}
+
+	docs := models.NewSite("docs", docsDomain.ID, stagingNode.ID, "squidfunk/mkdocs-material:9", 8000)
+	docs.Status = models.SiteStatusRunning
+
+	return &config.Config{
+		Version:  "1.0.0",
+		Sites:    []models.Site{*blog, *shop, *backend, *docs},
+		Domains:  []models.Domain{*mainDomain, *docsDomain},
+		Nodes:    []models.Node{*euNode, *usNode, *stagingNode},
+		Settings: settings,
+	}
+}
@@ -0,0 +1,160 @@
+package app
+
+import (
+	"os"
+	"path/filepath"
+	"testing"
+
+	"github.com/BlueBeard63/archon/internal/config"
+	"github.com/BlueBeard63/archon/internal/models"
+	"github.com/BlueBeard63/archon/internal/state"
+	"github.com/BlueBeard63/archon/internal/ui/theme"
+)
+
+func TestNewDemoModel(t *testing.T) {
+	m, err := NewDemoModel()
+	if err != nil {
+		t.Fatalf("NewDemoModel() error = %v", err)
+	}
+	t.Cleanup(func() { os.RemoveAll(filepath.Dir(m.configPath)) })
+
+	if len(m.state.Nodes) == 0 || len(m.state.Domains) == 0 || len(m.state.Sites) == 0 {
+		t.Fatalf("demo has %d nodes, %d domains and %d sites, want some of each",
+			len(m.state.Nodes), len(m.state.Domains), len(m.state.Sites))
+	}
+
+	for _, node := range m.state.Nodes {
+		msg := m.spawnNodeHealthCheck(node.ID)().(NodeHealthCheckResultMsg)
+		if msg.Error != nil {
+			t.Errorf("health check of %s: error = %v", node.Name, msg.Error)
+		}
+		if got := m.state.GetNodeByID(node.ID).Status; got != models.NodeStatusOnline {
+			t.Errorf("%s status = %s, want %s", node.Name, got, models.NodeStatusOnline)
+		}
+	}
+
+	// Running sites are deployed at startup; the rest aren't on any node yet
+	for _, site := range m.state.Sites {
+		msg := m.spawnFetchSiteStatus(site.ID)().(SiteStatusResultMsg)
+		if msg.Error != nil {
+			t.Errorf("status of %s: error = %v", site.Name, msg.Error)
+			continue
+		}
+		if msg.Status != site.Status {
+			t.Errorf("%s status = %s, want %s", site.Name, msg.Status, site.Status)
+		}
+	}
+}
+
+func TestUnexpectedStopRestartsSite(t *testing.T) {
+	m, err := NewDemoModel()
+	if err != nil {
+		t.Fatalf("NewDemoModel() error = %v", err)
+	}
+	t.Cleanup(func() { os.RemoveAll(filepath.Dir(m.configPath)) })
+
+	site := &m.state.Sites[0]
+	site.AutoRestart = true
+
+	// A site stopped from Archon stays stopped
+	site.Status = models.SiteStatusStopped
+	if _, cmd := m.Update(SiteStatusResultMsg{SiteID: site.ID, Status: models.SiteStatusStopped}); cmd != nil || site.Status != models.SiteStatusStopped {
+		t.Errorf("stopped site: status = %s, restart = %v, want stopped and no restart", site.Status, cmd != nil)
+	}
+
+	// A running site whose container stopped by itself has failed and is restarted
+	site.Status = models.SiteStatusRunning
+	if _, cmd := m.Update(SiteStatusResultMsg{SiteID: site.ID, Status: models.SiteStatusStopped, Background: true}); cmd == nil || site.Status != models.SiteStatusFailed {
+		t.Errorf("crashed site: status = %s, restart = %v, want failed and a restart", site.Status, cmd != nil)
+	}
+}
+
+func TestRollbackKeepsConfiguredImage(t *testing.T) {
+	m, err := NewDemoModel()
+	if err != nil {
+		t.Fatalf("NewDemoModel() error = %v", err)
+	}
+	t.Cleanup(func() { os.RemoveAll(filepath.Dir(m.configPath)) })
+
+	site := &m.state.Sites[0]
+	configured := site.DockerImage
+
+	site.Status = models.SiteStatusDeploying
+	if _, cmd := m.Update(RollbackSiteMsg{SiteID: site.ID, Image: "old:1"}); cmd != nil {
+		t.Error("rollback of a deploying site started a deploy")
+	}
+
+	site.Status = models.SiteStatusRunning
+	if _, cmd := m.Update(RollbackSiteMsg{SiteID: site.ID, Image: "old:1"}); cmd == nil {
+		t.Fatal("rollback didn't start a deploy")
+	}
+	if site.DockerImage != configured || site.Status != models.SiteStatusDeploying {
+		t.Errorf("during rollback: image = %q, status = %s, want %q and deploying", site.DockerImage, site.Status, configured)
+	}
+}
+
+func TestRestartedLogFollowDropsStalePolls(t *testing.T) {
+	m, err := NewDemoModel()
+	if err != nil {
+		t.Fatalf("NewDemoModel() error = %v", err)
+	}
+	t.Cleanup(func() { os.RemoveAll(filepath.Dir(m.configPath)) })
+
+	site := &m.state.Sites[0]
+	m.state.FollowLogs = site.ID
+	m.state.FollowLogsGen = 2
+
+	// A fetch and a tick from the follow before it was toggled off and on again
+	if _, cmd := m.Update(NodeLogsResultMsg{SiteID: site.ID, Logs: []string{"old"}, Since: "1", Cursor: "2", FollowGen: 1}); cmd != nil {
+		t.Error("stale follow fetch scheduled another poll")
+	}
+	if logs, _ := m.state.GetLogs(site.ID); len(logs) != 0 {
+		t.Errorf("stale follow fetch added logs %q", logs)
+	}
+	if _, cmd := m.Update(LogFollowTickMsg{SiteID: site.ID, Gen: 1}); cmd != nil {
+		t.Error("stale follow tick fetched logs")
+	}
+
+	// The current follow keeps polling from its cursor
+	if _, cmd := m.Update(NodeLogsResultMsg{SiteID: site.ID, Logs: []string{"new"}, Since: "1", Cursor: "2", FollowGen: 2}); cmd == nil {
+		t.Error("current follow fetch didn't schedule another poll")
+	}
+	if cursor := m.state.LogCursor(site.ID); cursor != "2" {
+		t.Errorf("LogCursor = %q, want %q", cursor, "2")
+	}
+}
+
+func TestBadThemeFileFallsBackToBuiltInTheme(t *testing.T) {
+	s := state.NewAppState()
+	s.ConfigPath = filepath.Join(t.TempDir(), "config.toml")
+	cfg := &config.Config{Settings: config.Settings{Theme: "light", ThemeFile: "missing.toml"}}
+
+	got := applyConfig(s, cfg)
+
+	if want := theme.Light(); got != want {
+		t.Errorf("theme = %+v, want the built-in light theme", got)
+	}
+	if latest, ok := s.LatestNotification(); !ok || latest.Level != "warning" {
+		t.Errorf("latest notification = %+v, want a warning about the theme file", latest)
+	}
+}
+
+func TestRepeatedLogExportsDontOverwrite(t *testing.T) {
+	m, err := NewDemoModel()
+	if err != nil {
+		t.Fatalf("NewDemoModel() error = %v", err)
+	}
+	t.Cleanup(func() { os.RemoveAll(filepath.Dir(m.configPath)) })
+
+	first := m.spawnExportLogs("web", []string{"first"})().(LogsExportedMsg)
+	second := m.spawnExportLogs("web", []string{"second"})().(LogsExportedMsg)
+	if first.Error != nil || second.Error != nil {
+		t.Fatalf("export errors = %v, %v", first.Error, second.Error)
+	}
+	if first.Path == second.Path {
+		t.Fatalf("both exports wrote %s", first.Path)
+	}
+	if content, err := os.ReadFile(first.Path); err != nil || string(content) != "first\n" {
+		t.Errorf("first export = %q, %v; want %q", content, err, "first\n")
+	}
+}
@@ -2,9 +2,11 @@ package app
 
 import (
 	"fmt"
+	"math"
 	"net"
 	"os"
 	"path/filepath"
+	"strconv"
 	"strings"
 	"time"
 
@@ -13,6 +15,7 @@ import (
 
 	"github.com/BlueBeard63/archon/internal/compose"
 	"github.com/BlueBeard63/archon/internal/config"
+	"github.com/BlueBeard63/archon/internal/desktop"
 	"github.com/BlueBeard63/archon/internal/models"
 	"github.com/BlueBeard63/archon/internal/state"
 )
@@ -109,8 +112,11 @@ func (m Model) handleKeyPress(msg tea.KeyMsg) (tea.Model, tea.Cmd) {
 	// Check if we're on a form screen (prioritize form input)
 	isFormScreen := m.state.CurrentScreen == state.ScreenSiteCreate ||
 		m.state.CurrentScreen == state.ScreenSiteEdit ||
+		m.state.CurrentScreen == state.ScreenSiteEnvVars ||
+		m.state.CurrentScreen == state.ScreenSiteConfigFiles ||
 		m.state.CurrentScreen == state.ScreenDomainCreate ||
 		m.state.CurrentScreen == state.ScreenDomainEdit ||
+		m.state.CurrentScreen == state.ScreenDnsRecordForm ||
 		m.state.CurrentScreen == state.ScreenNodeCreate ||
 		m.state.CurrentScreen == state.ScreenNodeEdit ||
 		m.state.CurrentScreen == state.ScreenNodeConfigSave ||
@@ -119,35 +125,121 @@ func (m Model) handleKeyPress(msg tea.KeyMsg) (tea.Model, tea.Cmd) {
 	// Critical global key bindings (work on all screens)
 	switch msg.String() {
 	case "ctrl+c":
-		// Quit application (always available)
-		return m, func() tea.Msg { return QuitMsg{} }
+		// Quit application (always available), straight away if already waiting for operations
+		force := m.state.QuitWaiting
+		return m, func() tea.Msg { return QuitMsg{Force: force} }
+	}
+
+	// A confirmation dialog captures all other keys until it is answered
+	if m.state.PendingConfirmation != nil {
+		return m.handleConfirmationKeys(msg)
+	}
 
+	// The command palette captures all other keys while it is open
+	if m.state.Palette != nil {
+		return m.handlePaletteKeys(msg)
+	}
+
+	// Typing a sites list search captures all other keys until it is confirmed or cleared
+	if m.state.SiteSearchActive && m.state.CurrentScreen == state.ScreenSitesList {
+		return m.handleSiteSearchKeys(msg)
+	}
+
+	// Typing a .env file path captures all other keys until it is imported or cancelled
+	if m.state.EnvImportActive && m.state.CurrentScreen == state.ScreenSiteEnvVars {
+		return m.handleEnvImportKeys(msg)
+	}
+
+	// The config file editor captures all other keys until the file is saved or cancelled
+	if m.state.ConfigFileForm != nil && m.state.CurrentScreen == state.ScreenSiteConfigFiles {
+		return m.handleConfigFileFormKeys(msg)
+	}
+
+	switch msg.String() {
 	case "esc":
+		// The setup wizard has nothing to go back to, so Esc skips it
+		if m.state.CurrentScreen == state.ScreenSetup {
+			m.state.FinishSetup()
+			return m, nil
+		}
 		// Go back to previous screen (always available)
 		m.state.NavigateBack()
 		return m, nil
 	}
 
+	// Reveal or hide masked secrets, also while typing in a form
+	if m.state.Keymap.Matches(msg.String(), config.KeyReveal) {
+		m.state.RevealSecrets = !m.state.RevealSecrets
+		return m, nil
+	}
+
 	// Non-form global key bindings (skip on form screens to allow text input)
 	if !isFormScreen {
-		switch msg.String() {
-		case "q":
-			// Quit application
+		key, keys := msg.String(), m.state.Keymap
+		switch {
+		case keys.Matches(key, config.KeyQuit):
+			// Quit application, offering to save unsaved changes first
+			if m.state.Dirty && !m.state.AutoSave {
+				m.state.PendingConfirmation = &state.Confirmation{
+					Kind:    quitConfirmKind,
+					Title:   "Quit with unsaved changes?",
+					Message: "Changes made since the last save will be lost.",
+					Keys:    "Press s to save and quit • d to discard • Esc to cancel",
+				}
+				return m, nil
+			}
 			return m, func() tea.Msg { return QuitMsg{} }
 
-		case "0":
+		case keys.Matches(key, config.KeyForward):
+			// Re-enter the screen just backed out of, refreshing a site's details as on opening it
+			if m.state.NavigateForward() && m.state.CurrentScreen == state.ScreenSiteDetail {
+				return m, tea.Batch(m.siteDetailCmds(m.state.SelectedSiteID)...)
+			}
+			return m, nil
+
+		case keys.Matches(key, config.KeyDashboard):
 			// Go to dashboard
 			m.state.NavigateTo(state.ScreenDashboard)
 			return m, nil
 
-		case "?":
+		case keys.Matches(key, config.KeyHelp):
 			// Show help screen
+			m.state.HelpScroll = 0
 			m.state.NavigateTo(state.ScreenHelp)
 			return m, nil
 
-		case "ctrl+s":
+		case keys.Matches(key, config.KeySave):
 			// Manual save
 			return m, func() tea.Msg { return SaveConfigMsg{} }
+
+		case keys.Matches(key, config.KeyRefresh):
+			// Refresh live data for the current screen
+			return m, func() tea.Msg { return RefreshMsg{} }
+
+		case keys.Matches(key, config.KeyReloadConfig):
+			// Reload config from disk (separate from refreshing live data)
+			return m, func() tea.Msg { return ReloadConfigMsg{} }
+
+		case keys.Matches(key, config.KeyPalette):
+			// Open the command palette
+			m.state.Palette = &state.Palette{}
+			return m, nil
+
+		case keys.Matches(key, config.KeyOperations):
+			// Show pending and failed operations
+			if m.state.CurrentScreen != state.ScreenOperations {
+				m.state.OperationsIndex = 0
+				m.state.NavigateTo(state.ScreenOperations)
+			}
+			return m, nil
+
+		case keys.Matches(key, config.KeyNotifications):
+			// Show the notification history, newest first
+			if m.state.CurrentScreen != state.ScreenNotifications {
+				m.state.NotificationsScroll = 0
+				m.state.NavigateTo(state.ScreenNotifications)
+			}
+			return m, nil
 		}
 	}
 
@@ -163,12 +255,30 @@ func (m Model) handleKeyPress(msg tea.KeyMsg) (tea.Model, tea.Cmd) {
 		return m.handleSiteEditKeys(msg)
 	case state.ScreenSiteEnvVars:
 		return m.handleSiteEnvVarsKeys(msg)
+	case state.ScreenSiteConfigFiles:
+		return m.handleSiteConfigFilesKeys(msg)
+	case state.ScreenSiteDetail:
+		return m.handleSiteDetailKeys(msg)
+	case state.ScreenSitesDeleted:
+		return m.handleSitesDeletedKeys(msg)
 	case state.ScreenDomainsList:
 		return m.handleDomainsListKeys(msg)
 	case state.ScreenDomainCreate:
 		return m.handleDomainCreateKeys(msg)
 	case state.ScreenDomainEdit:
 		return m.handleDomainEditKeys(msg)
+	case state.ScreenDomainDnsRecords:
+		return m.handleDomainDnsRecordsKeys(msg)
+	case state.ScreenDnsRecordForm:
+		return m.handleDnsRecordFormKeys(msg)
+	case state.ScreenDnsSyncDiff:
+		return m.handleDnsSyncDiffKeys(msg)
+	case state.ScreenDeployPreview:
+		return m.handleDeployPreviewKeys(msg)
+	case state.ScreenOperations:
+		return m.handleOperationsKeys(msg)
+	case state.ScreenNotifications:
+		return m.handleNotificationsKeys(msg)
 	case state.ScreenNodesList:
 		return m.handleNodesListKeys(msg)
 	case state.ScreenNodeCreate:
@@ -183,6 +293,98 @@ func (m Model) handleKeyPress(msg tea.KeyMsg) (tea.Model, tea.Cmd) {
 		return m.handleNodeConfigSaveKeys(msg)
 	case state.ScreenHelp:
 		return m.handleHelpKeys(msg)
+	case state.ScreenSetup:
+		return m.handleSetupKeys(msg)
+	}
+
+	return m, nil
+}
+
+// handlePaletteKeys handles keys while the command palette is open: typing filters,
+// up/down select, Enter jumps to the selected entry and Esc closes it
+func (m Model) handlePaletteKeys(msg tea.KeyMsg) (tea.Model, tea.Cmd) {
+	palette := m.state.Palette
+	matches := m.state.PaletteMatches()
+
+	switch msg.Type {
+	case tea.KeyEsc:
+		m.state.Palette = nil
+		return m, nil
+
+	case tea.KeyUp, tea.KeyShiftTab:
+		if palette.Index > 0 {
+			palette.Index--
+		}
+		return m, nil
+
+	case tea.KeyDown, tea.KeyTab:
+		if palette.Index < len(matches)-1 {
+			palette.Index++
+		}
+		return m, nil
+
+	case tea.KeyEnter:
+		if palette.Index < 0 || palette.Index >= len(matches) {
+			return m, nil
+		}
+		m.state.Palette = nil
+		return m.openPaletteEntry(matches[palette.Index])
+
+	case tea.KeyBackspace:
+		if query := []rune(palette.Query); len(query) > 0 {
+			palette.Query = string(query[:len(query)-1])
+			palette.Index = 0
+		}
+		return m, nil
+
+	case tea.KeySpace:
+		palette.Query += " "
+		palette.Index = 0
+		return m, nil
+
+	case tea.KeyRunes:
+		palette.Query += string(msg.Runes)
+		palette.Index = 0
+		return m, nil
+	}
+
+	// The palette key closes it again
+	if m.state.Keymap.Matches(msg.String(), config.KeyPalette) {
+		m.state.Palette = nil
+	}
+	return m, nil
+}
+
+// openPaletteEntry jumps to a palette entry: a site's detail screen, or the
+// domain or node selected in its list
+func (m Model) openPaletteEntry(entry state.PaletteEntry) (tea.Model, tea.Cmd) {
+	switch entry.Kind {
+	case "site":
+		if m.state.GetSiteByID(entry.ID) != nil {
+			return m.openSiteDetail(entry.ID, state.SiteDetailTabOverview)
+		}
+
+	case "domain":
+		for i := range m.state.Domains {
+			if m.state.Domains[i].ID == entry.ID {
+				m.state.DomainsListIndex = i
+				m.state.SelectedDomainID = entry.ID
+				m.state.NavigateTo(state.ScreenDomainsList)
+				break
+			}
+		}
+
+	case "node":
+		// The nodes list indexes the unfiltered nodes once the tag filter is cleared
+		m.state.NodeTagFilter = ""
+		for i := range m.state.Nodes {
+			if m.state.Nodes[i].ID == entry.ID {
+				m.state.NodesListIndex = i
+				m.state.SelectedNodeID = entry.ID
+				m.state.NavigateTo(state.ScreenNodesList)
+				break
+			}
+		}
 	}
 
 	return m, nil
@@ -190,73 +392,173 @@ func (m Model) handleKeyPress(msg tea.KeyMsg) (tea.Model, tea.Cmd) {
 
 // handleDashboardKeys handles keys on the dashboard screen
 func (m Model) handleDashboardKeys(msg tea.KeyMsg) (tea.Model, tea.Cmd) {
-	switch msg.String() {
-	case "1", "s":
+	key, keys := msg.String(), m.state.Keymap
+	switch {
+	case key == "1" || key == "s":
 		m.state.NavigateTo(state.ScreenSitesList)
 		return m, nil
-	case "2", "d":
+	case key == "2" || key == "d":
 		m.state.NavigateTo(state.ScreenDomainsList)
 		return m, nil
-	case "3", "n":
+	case key == "3" || key == "n":
 		m.state.NavigateTo(state.ScreenNodesList)
 		return m, nil
-	case "4", "c":
+	case key == "4" || key == "c":
 		m.state.NavigateTo(state.ScreenSettings)
 		return m, nil
+	case keys.Matches(key, config.KeyReload):
+		// Refresh node health and site status
+		return m, func() tea.Msg { return RefreshMsg{} }
+
+	case keys.Matches(key, config.KeyFilterTag):
+		// Narrow the nodes summary to a tag, shared with the nodes list
+		if tags := m.state.AllNodeTags(); len(tags) > 0 {
+			m.cycleNodeTagFilter(tags)
+		}
+		return m, nil
+
+	case keys.Matches(key, config.KeyUp), keys.Matches(key, config.KeyDown), key == "enter":
+		// Select and open sites in the failed sites panel
+		failed := m.state.FailedSites()
+		if len(failed) == 0 {
+			return m, nil
+		}
+		index := m.state.DashboardAlertIndex
+		if index >= len(failed) {
+			index = len(failed) - 1
+		}
+		switch {
+		case keys.Matches(key, config.KeyUp) && index > 0:
+			index--
+		case keys.Matches(key, config.KeyDown) && index < len(failed)-1:
+			index++
+		case key == "enter":
+			return m.openSiteDetail(failed[index].ID, state.SiteDetailTabOverview)
+		}
+		m.state.DashboardAlertIndex = index
+		return m, nil
 	}
 
 	return m, nil
 }
 
+// handleSetupKeys handles keys on the setup wizard: Enter opens the form for the current step,
+// which returns here when saved or cancelled
+func (m Model) handleSetupKeys(msg tea.KeyMsg) (tea.Model, tea.Cmd) {
+	switch msg.String() {
+	case "enter":
+		switch m.state.SetupStep() {
+		case state.SetupStepNode:
+			m.state.NavigateTo(state.ScreenNodeCreate)
+		case state.SetupStepDomain:
+			m.state.NavigateTo(state.ScreenDomainCreate)
+		case state.SetupStepSite:
+			m.state.NavigateTo(state.ScreenSiteCreate)
+		default:
+			m.state.FinishSetup()
+		}
+	case "c":
+		if m.state.SetupStep() == state.SetupStepDomain {
+			m.state.NavigateTo(state.ScreenSettings)
+		}
+	}
+	return m, nil
+}
+
 // handleSitesListKeys handles keys on the sites list screen
 func (m Model) handleSitesListKeys(msg tea.KeyMsg) (tea.Model, tea.Cmd) {
-	switch msg.String() {
-	case "n", "c":
+	// Selection index refers to the filtered list
+	sites := m.state.VisibleSites()
+
+	key, keys := msg.String(), m.state.Keymap
+	switch {
+	case keys.Matches(key, config.KeyCreate):
 		m.state.NavigateTo(state.ScreenSiteCreate)
 		return m, nil
 
-	case " ", "enter":
+	case keys.Matches(key, config.KeyDeploy):
 		// Deploy selected site
-		if len(m.state.Sites) > 0 && m.state.SitesListIndex >= 0 && m.state.SitesListIndex < len(m.state.Sites) {
-			site := m.state.Sites[m.state.SitesListIndex]
+		if m.state.SitesListIndex >= 0 && m.state.SitesListIndex < len(sites) {
+			site := sites[m.state.SitesListIndex]
 			m.state.AddNotification("Deploying site: "+site.Name, "info")
-			return m, m.spawnDeploySite(site.ID)
+			siteID := site.ID
+			return m, func() tea.Msg { return DeploySiteMsg{SiteID: siteID} }
 		}
 		return m, nil
 
-	case "e":
+	case keys.Matches(key, config.KeyEdit):
 		// Edit selected site
-		if len(m.state.Sites) > 0 && m.state.SitesListIndex >= 0 && m.state.SitesListIndex < len(m.state.Sites) {
-			site := m.state.Sites[m.state.SitesListIndex]
+		if m.state.SitesListIndex >= 0 && m.state.SitesListIndex < len(sites) {
+			site := sites[m.state.SitesListIndex]
 			m.state.SelectedSiteID = site.ID
 			m.state.CurrentFieldIndex = 0 // Reset to first field
 			m.state.NavigateTo(state.ScreenSiteEdit)
 		}
 		return m, nil
 
-	case "d":
+	case keys.Matches(key, config.KeyCopy):
+		// Copy the selected site's ID
+		if m.state.SitesListIndex >= 0 && m.state.SitesListIndex < len(sites) {
+			return m.copyToClipboard("site ID", sites[m.state.SitesListIndex].ID.String())
+		}
+		return m, nil
+
+	case keys.Matches(key, config.KeyRestore):
+		// Show recently deleted sites that can be restored
+		if len(m.state.RecentlyDeletedSites) == 0 {
+			m.state.AddNotification("No recently deleted sites to restore", "info")
+			return m, nil
+		}
+		m.state.DeletedSitesIndex = len(m.state.RecentlyDeletedSites) - 1 // Newest first
+		m.state.NavigateTo(state.ScreenSitesDeleted)
+		return m, nil
+
+	case keys.Matches(key, config.KeyPreviewDeploy):
+		// Preview the deploy request for the selected site
+		if m.state.SitesListIndex >= 0 && m.state.SitesListIndex < len(sites) {
+			siteID := sites[m.state.SitesListIndex].ID
+			return m, func() tea.Msg { return PreviewDeployMsg{SiteID: siteID} }
+		}
+		return m, nil
+
+	case keys.Matches(key, config.KeyDetails) || keys.Matches(key, config.KeyLogs):
+		// Open site details (l jumps straight to the logs tab)
+		if m.state.SitesListIndex >= 0 && m.state.SitesListIndex < len(sites) {
+			site := sites[m.state.SitesListIndex]
+			tab := state.SiteDetailTabOverview
+			if keys.Matches(key, config.KeyLogs) {
+				tab = state.SiteDetailTabLogs
+			}
+			return m.openSiteDetail(site.ID, tab)
+		}
+		return m, nil
+
+	case keys.Matches(key, config.KeyDelete):
 		// Delete selected site
-		if len(m.state.Sites) > 0 && m.state.SitesListIndex >= 0 && m.state.SitesListIndex < len(m.state.Sites) {
-			site := m.state.Sites[m.state.SitesListIndex]
-			return m.handleDeleteSite(site.ID)
+		if m.state.SitesListIndex >= 0 && m.state.SitesListIndex < len(sites) {
+			site := sites[m.state.SitesListIndex]
+			return m.requestDelete("site", site.ID, site.Name)
 		}
 		return m, nil
 
-	case "s":
+	case keys.Matches(key, config.KeyStartStop):
 		// Toggle start/stop for selected site
-		if len(m.state.Sites) > 0 && m.state.SitesListIndex >= 0 && m.state.SitesListIndex < len(m.state.Sites) {
-			site := m.state.Sites[m.state.SitesListIndex]
+		if m.state.SitesListIndex >= 0 && m.state.SitesListIndex < len(sites) {
+			site := sites[m.state.SitesListIndex]
 
 			// If running or deploying, stop it
 			if site.Status == models.SiteStatusRunning || site.Status == models.SiteStatusDeploying {
 				m.state.AddNotification("Stopping site: "+site.Name, "info")
-				return m, m.spawnStopSite(site.ID)
+				op := m.startOperation(state.OpStopSite, site.Name, StopSiteMsg{SiteID: site.ID})
+				return m, op.track(op.spawnStopSite(site.ID))
 			}
 
 			// If stopped or failed, restart it
 			if site.Status == models.SiteStatusStopped || site.Status == models.SiteStatusFailed {
 				m.state.AddNotification("Restarting site: "+site.Name, "info")
-				return m, m.spawnRestartSite(site.ID)
+				m.state.ResetAutoRestarts(site.ID)
+				op := m.startOperation(state.OpRestartSite, site.Name, RestartSiteMsg{SiteID: site.ID})
+				return m, op.track(op.spawnRestartSite(site.ID))
 			}
 
 			// If inactive, do nothing (user should press space/enter to deploy)
@@ -266,19 +568,409 @@ func (m Model) handleSitesListKeys(msg tea.KeyMsg) (tea.Model, tea.Cmd) {
 		}
 		return m, nil
 
-	case "r":
+	case keys.Matches(key, config.KeySetupDNS):
 		// Setup DNS records for selected site
-		if len(m.state.Sites) > 0 && m.state.SitesListIndex >= 0 && m.state.SitesListIndex < len(m.state.Sites) {
-			site := m.state.Sites[m.state.SitesListIndex]
+		if m.state.SitesListIndex >= 0 && m.state.SitesListIndex < len(sites) {
+			site := sites[m.state.SitesListIndex]
 			m.state.AddNotification("Setting up DNS records for: "+site.Name, "info")
 			return m, m.spawnSetupDNS(site.ID)
 		}
 		return m, nil
+
+	case keys.Matches(key, config.KeySearch):
+		// Start typing a search by name, domain or status
+		m.state.SiteSearchActive = true
+		return m, nil
+
+	case keys.Matches(key, config.KeySort):
+		// Cycle sort column: insertion order -> name -> status -> node -> port
+		m.state.CycleSiteSort()
+		return m, nil
+
+	case keys.Matches(key, config.KeySortReverse):
+		if m.state.SiteSortColumn != state.SiteSortNone {
+			m.state.ReverseSiteSort()
+		}
+		return m, nil
+
+	case keys.Matches(key, config.KeyFilterTag):
+		// Cycle tag filter: all -> each tag in order -> all
+		tags := m.state.AllTags()
+		if len(tags) == 0 {
+			m.state.AddNotification("No sites have tags yet. Add tags when creating or editing a site", "info")
+			return m, nil
+		}
+		next := ""
+		if m.state.SiteTagFilter == "" {
+			next = tags[0]
+		} else {
+			for i, tag := range tags {
+				if tag == m.state.SiteTagFilter && i+1 < len(tags) {
+					next = tags[i+1]
+					break
+				}
+			}
+		}
+		m.state.SiteTagFilter = next
+
+		// Reset selection to the top of the filtered list
+		m.state.SitesListIndex = 0
+		if m.state.SitesTable != nil {
+			m.state.SitesTable.SetCursor(0)
+		}
+
+		if next == "" {
+			m.state.AddNotification("Showing all sites", "info")
+		} else {
+			m.state.AddNotification(fmt.Sprintf("Filtering sites by tag: %s (%d sites)", next, len(m.state.SitesWithTag(next))), "info")
+		}
+		return m, nil
+	}
+
+	return m, nil
+}
+
+// handleSiteSearchKeys handles keys while a sites list search is being typed:
+// Enter keeps the search, Esc clears it
+func (m Model) handleSiteSearchKeys(msg tea.KeyMsg) (tea.Model, tea.Cmd) {
+	switch msg.Type {
+	case tea.KeyEnter:
+		m.state.SiteSearchActive = false
+		return m, nil
+
+	case tea.KeyEsc:
+		m.state.SiteSearchActive = false
+		m.state.SiteSearch = ""
+
+	case tea.KeyBackspace:
+		query := []rune(m.state.SiteSearch)
+		if len(query) == 0 {
+			return m, nil
+		}
+		m.state.SiteSearch = string(query[:len(query)-1])
+
+	case tea.KeySpace:
+		m.state.SiteSearch += " "
+
+	case tea.KeyRunes:
+		m.state.SiteSearch += string(msg.Runes)
+
+	default:
+		return m, nil
+	}
+
+	// The filtered list changed, so keep the selection inside it
+	m.state.SitesListIndex = 0
+	if m.state.SitesTable != nil {
+		m.state.SitesTable.SetCursor(0)
+	}
+	return m, nil
+}
+
+// openSiteDetail navigates to the site detail screen on the given tab and fetches its live data
+func (m Model) openSiteDetail(siteID uuid.UUID, tab int) (tea.Model, tea.Cmd) {
+	m.state.SelectedSiteID = siteID
+	m.state.NavigateTo(state.ScreenSiteDetail)
+	m.state.SiteDetailTab = tab
+	return m, tea.Batch(m.siteDetailCmds(siteID)...)
+}
+
+// handleSiteDetailKeys handles keys on the site detail screen
+func (m Model) handleSiteDetailKeys(msg tea.KeyMsg) (tea.Model, tea.Cmd) {
+	tabCount := len(state.SiteDetailTabNames)
+
+	key, keys := msg.String(), m.state.Keymap
+	switch {
+	case key == "tab" || key == "right" || key == "l":
+		// Next tab
+		m.state.SiteDetailTab = (m.state.SiteDetailTab + 1) % tabCount
+		return m, nil
+
+	case key == "shift+tab" || key == "left" || key == "h":
+		// Previous tab
+		m.state.SiteDetailTab = (m.state.SiteDetailTab + tabCount - 1) % tabCount
+		return m, nil
+
+	case keys.Matches(key, config.KeyReload):
+		// Refresh status, logs and metrics
+		m.state.AddNotification("Refreshing site details...", "info")
+		return m, tea.Batch(m.siteDetailCmds(m.state.SelectedSiteID)...)
+
+	case keys.Matches(key, config.KeyEdit):
+		// Edit this site
+		if m.state.GetSiteByID(m.state.SelectedSiteID) != nil {
+			m.state.CurrentFieldIndex = 0
+			m.state.NavigateTo(state.ScreenSiteEdit)
+		}
+		return m, nil
+
+	case keys.Matches(key, config.KeyPreviewDeploy):
+		// Preview the deploy request for this site
+		siteID := m.state.SelectedSiteID
+		return m, func() tea.Msg { return PreviewDeployMsg{SiteID: siteID} }
+
+	case keys.Matches(key, config.KeyFollowLogs):
+		// Start or stop tailing the container logs
+		siteID := m.state.SelectedSiteID
+		if m.state.FollowLogs == siteID {
+			m.state.FollowLogs = uuid.Nil
+			return m, nil
+		}
+		m.state.FollowLogs = siteID
+		m.state.FollowLogsGen++
+		m.state.SiteDetailTab = state.SiteDetailTabLogs
+		return m, m.spawnFollowLogs(siteID, m.state.FollowLogsGen)
+
+	case keys.Matches(key, config.KeyCopy):
+		// Copy this site's ID
+		if m.state.GetSiteByID(m.state.SelectedSiteID) != nil {
+			return m.copyToClipboard("site ID", m.state.SelectedSiteID.String())
+		}
+		return m, nil
+
+	case keys.Matches(key, config.KeyRollback):
+		// Confirm redeploying the last known-good image
+		site := m.state.GetSiteByID(m.state.SelectedSiteID)
+		if site == nil {
+			return m, nil
+		}
+		target, err := site.RollbackTarget()
+		if err != nil {
+			m.state.AddNotification("Cannot roll back "+site.Name+": "+err.Error(), "warning")
+			return m, nil
+		}
+		deployed := target.Timestamp.Local().Format("2006-01-02 15:04")
+		m.state.PendingConfirmation = &state.Confirmation{
+			Kind:     rollbackConfirmKind,
+			TargetID: site.ID,
+			Name:     target.Image,
+			Title:    "Roll back " + site.Name + "?",
+			Message:  fmt.Sprintf("Redeploy %s, last deployed successfully on %s?\nThe site keeps %s as its configured image.", target.Image, deployed, site.DockerImage),
+		}
+		return m, nil
+
+	case keys.Matches(key, config.KeyExportLogs):
+		// Save the buffered logs to a file
+		site := m.state.GetSiteByID(m.state.SelectedSiteID)
+		if site == nil {
+			return m, nil
+		}
+		logs, _ := m.state.GetLogs(site.ID)
+		if len(logs) == 0 {
+			m.state.AddNotification("No logs to export yet", "info")
+			return m, nil
+		}
+		return m, m.spawnExportLogs(site.Name, logs)
+	}
+
+	return m, nil
+}
+
+// handleSitesDeletedKeys handles keys on the recently deleted sites screen
+// The list is shown newest first, while the buffer is stored oldest first
+func (m Model) handleSitesDeletedKeys(msg tea.KeyMsg) (tea.Model, tea.Cmd) {
+	deleted := m.state.RecentlyDeletedSites
+
+	key, keys := msg.String(), m.state.Keymap
+	switch {
+	case keys.Matches(key, config.KeyUp):
+		if m.state.DeletedSitesIndex < len(deleted)-1 {
+			m.state.DeletedSitesIndex++
+		}
+		return m, nil
+
+	case keys.Matches(key, config.KeyDown):
+		if m.state.DeletedSitesIndex > 0 {
+			m.state.DeletedSitesIndex--
+		}
+		return m, nil
+
+	case key == "enter" || keys.Matches(key, config.KeyRestore) || keys.Matches(key, config.KeyReload):
+		if m.state.DeletedSitesIndex >= 0 && m.state.DeletedSitesIndex < len(deleted) {
+			siteID := deleted[m.state.DeletedSitesIndex].ID
+			return m, func() tea.Msg { return RestoreSiteMsg{SiteID: siteID} }
+		}
+		return m, nil
 	}
 
 	return m, nil
 }
 
+// handleOperationsKeys handles keys on the operations screen: up/down select, the cancel key
+// aborts the selected pending operation and the retry key restarts the selected failed one
+func (m Model) handleOperationsKeys(msg tea.KeyMsg) (tea.Model, tea.Cmd) {
+	ops := m.state.Operations()
+
+	key, keys := msg.String(), m.state.Keymap
+	switch {
+	case keys.Matches(key, config.KeyUp):
+		if m.state.OperationsIndex > 0 {
+			m.state.OperationsIndex--
+		}
+		return m, nil
+
+	case keys.Matches(key, config.KeyDown):
+		if m.state.OperationsIndex < len(ops)-1 {
+			m.state.OperationsIndex++
+		}
+		return m, nil
+
+	case keys.Matches(key, config.KeyCancelOperation):
+		if m.state.OperationsIndex >= 0 && m.state.OperationsIndex < len(ops) {
+			id := ops[m.state.OperationsIndex].ID
+			return m, func() tea.Msg { return CancelOperationMsg{OperationID: id} }
+		}
+		return m, nil
+
+	case keys.Matches(key, config.KeyRetryOperation):
+		if m.state.OperationsIndex >= 0 && m.state.OperationsIndex < len(ops) {
+			id := ops[m.state.OperationsIndex].ID
+			return m, func() tea.Msg { return RetryOperationMsg{OperationID: id} }
+		}
+		return m, nil
+	}
+
+	return m, nil
+}
+
+// handleNotificationsKeys scrolls the notification history
+func (m Model) handleNotificationsKeys(msg tea.KeyMsg) (tea.Model, tea.Cmd) {
+	last := max(len(m.state.Notifications)-1, 0)
+
+	key, keys := msg.String(), m.state.Keymap
+	switch {
+	case keys.Matches(key, config.KeyUp):
+		if m.state.NotificationsScroll > 0 {
+			m.state.NotificationsScroll--
+		}
+	case keys.Matches(key, config.KeyDown):
+		if m.state.NotificationsScroll < last {
+			m.state.NotificationsScroll++
+		}
+	case keys.Matches(key, config.KeyTop):
+		m.state.NotificationsScroll = 0
+	case keys.Matches(key, config.KeyBottom):
+		m.state.NotificationsScroll = last
+	}
+	return m, nil
+}
+
+// handleSaveDnsRecord validates a DNS record and adds it to the domain (index -1) or replaces
+// the record at index. The change is local until the domain's DNS is pushed.
+func (m Model) handleSaveDnsRecord(domainID uuid.UUID, record *models.DnsRecord, index int) (tea.Model, tea.Cmd) {
+	domain := m.state.GetDomainByID(domainID)
+	if domain == nil || record == nil {
+		m.state.AddNotification("Domain no longer exists", "error")
+		return m, nil
+	}
+	// Catch mistakes such as a hostname in an A record before the provider rejects them
+	if err := record.Validate(); err != nil {
+		m.state.AddNotification("Invalid DNS record: "+err.Error(), "error")
+		return m, nil
+	}
+
+	saved := *record
+	saved.Normalize()
+	if index < 0 {
+		saved.ID = nil
+		saved.SyncState = models.DnsSyncStateLocalOnly
+		domain.DnsRecords = append(domain.DnsRecords, saved)
+		m.state.AddNotification(fmt.Sprintf("Added %s record %s, push DNS to apply it", saved.RecordType, saved.Name), "success")
+	} else {
+		if index >= len(domain.DnsRecords) {
+			m.state.AddNotification("DNS record not found", "error")
+			return m, nil
+		}
+		existing := domain.DnsRecords[index]
+		saved.ID = existing.ID
+		saved.RemoteValue = existing.RemoteValue
+		saved.ConflictValue = existing.ConflictValue
+		saved.SyncState = existing.SyncState
+		if saved.SyncState != models.DnsSyncStateLocalOnly && saved.SyncState != models.DnsSyncStateConflict {
+			saved.SyncState = models.DnsSyncStateModified
+		}
+		domain.DnsRecords[index] = saved
+		m.state.AddNotification(fmt.Sprintf("Updated %s record %s, push DNS to apply it", saved.RecordType, saved.Name), "success")
+	}
+
+	m.recordAudit("update_dns_records", "domain", domain.ID, domain.Name, nil)
+	return m, m.configEdited()
+}
+
+// handleDeleteDnsRecord removes the record at index from a domain. A record that exists at the
+// provider stays there until DNS is pushed with provider-only records deleted.
+func (m Model) handleDeleteDnsRecord(domainID uuid.UUID, index int) (tea.Model, tea.Cmd) {
+	domain := m.state.GetDomainByID(domainID)
+	if domain == nil || index < 0 || index >= len(domain.DnsRecords) {
+		m.state.AddNotification("DNS record not found", "error")
+		return m, nil
+	}
+
+	record := domain.DnsRecords[index]
+	domain.DnsRecords = append(domain.DnsRecords[:index], domain.DnsRecords[index+1:]...)
+	if m.state.DnsRecordsIndex >= len(domain.DnsRecords) {
+		m.state.DnsRecordsIndex = max(len(domain.DnsRecords)-1, 0)
+	}
+
+	if record.ID != nil && !domain.IsManualDNS() {
+		m.state.AddNotification(fmt.Sprintf("Deleted %s record %s locally, press P to delete it at the provider", record.RecordType, record.Name), "success")
+	} else {
+		m.state.AddNotification(fmt.Sprintf("Deleted %s record %s", record.RecordType, record.Name), "success")
+	}
+	m.recordAudit("update_dns_records", "domain", domain.ID, domain.Name, nil)
+	return m, m.configEdited()
+}
+
+// handleRestoreSite re-adds a recently deleted site and redeploys it
+func (m Model) handleRestoreSite(siteID uuid.UUID) (tea.Model, tea.Cmd) {
+	var site *models.Site
+	for i := range m.state.RecentlyDeletedSites {
+		if m.state.RecentlyDeletedSites[i].ID == siteID {
+			site = &m.state.RecentlyDeletedSites[i]
+			break
+		}
+	}
+	if site == nil {
+		m.state.AddNotification("Site is no longer restorable", "error")
+		return m, nil
+	}
+
+	// The site's nodes and domains must still exist to deploy it
+	for _, nodeID := range site.GetNodeIDs() {
+		if m.state.GetNodeByID(nodeID) == nil {
+			m.state.AddNotification("Cannot restore "+site.Name+": its node has been deleted", "error")
+			return m, nil
+		}
+	}
+	for _, mapping := range site.GetDomainMappings() {
+		if m.state.GetDomainByID(mapping.DomainID) == nil {
+			m.state.AddNotification("Cannot restore "+site.Name+": one of its domains has been deleted", "error")
+			return m, nil
+		}
+	}
+	if m.state.SiteNameTaken(site.Name, uuid.Nil) {
+		m.state.AddNotification("Cannot restore "+site.Name+": a site with that name already exists", "error")
+		return m, nil
+	}
+
+	restored, _ := m.state.TakeDeletedSite(siteID)
+	m.state.Sites = append(m.state.Sites, restored)
+	m.state.AddNotification("Restored site: "+restored.Name+", redeploying", "success")
+	m.recordAudit("restore_site", "site", restored.ID, restored.Name, nil)
+
+	// Leave the restore screen once nothing is left to restore
+	if len(m.state.RecentlyDeletedSites) == 0 && m.state.CurrentScreen == state.ScreenSitesDeleted {
+		m.state.NavigateBack()
+	} else if m.state.DeletedSitesIndex >= len(m.state.RecentlyDeletedSites) {
+		m.state.DeletedSitesIndex = len(m.state.RecentlyDeletedSites) - 1
+	}
+
+	// Auto-save config if enabled
+	save := m.configEdited()
+
+	return m, tea.Batch(save, func() tea.Msg { return DeploySiteMsg{SiteID: restored.ID} })
+}
+
 // handleSiteCreateKeys handles keys on the site creation form
 func (m Model) handleSiteCreateKeys(msg tea.KeyMsg) (tea.Model, tea.Cmd) {
 	// Check if we're on a dropdown field (SiteType=-1, Node=1)
@@ -292,12 +984,16 @@ func (m Model) handleSiteCreateKeys(msg tea.KeyMsg) (tea.Model, tea.Cmd) {
 		next := current + 1
 
 		// For compose mode: skip fields 3 (docker username), 4 (docker token), 6 (config file)
-		// For container mode: all fields are visible
+		// For container mode building from source: skip field 4 (field 3 holds the Dockerfile)
 		for next < len(m.state.FormFields) {
 			if isCompose && (next == 3 || next == 4 || next == 6) {
 				next++
 				continue
 			}
+			if m.state.IsBuildSource() && next == 4 {
+				next++
+				continue
+			}
 			break
 		}
 
@@ -319,6 +1015,10 @@ func (m Model) handleSiteCreateKeys(msg tea.KeyMsg) (tea.Model, tea.Cmd) {
 				prev--
 				continue
 			}
+			if m.state.IsBuildSource() && prev == 4 {
+				prev--
+				continue
+			}
 			break
 		}
 
@@ -361,7 +1061,9 @@ func (m Model) handleSiteCreateKeys(msg tea.KeyMsg) (tea.Model, tea.Cmd) {
 					m.state.SiteTypeSelection = "compose"
 				}
 			} else if len(m.state.Nodes) > 0 {
-				m.state.FormFields[1] = m.state.Nodes[m.state.DropdownIndex].Name
+				node := m.state.Nodes[m.state.DropdownIndex]
+				m.state.FormFields[1] = node.Name
+				m.suggestSitePort(node.ID)
 			}
 			m.state.DropdownOpen = false
 
@@ -383,7 +1085,14 @@ func (m Model) handleSiteCreateKeys(msg tea.KeyMsg) (tea.Model, tea.Cmd) {
 			m.state.DropdownOpen = false
 			return m, nil
 
-		case tea.KeyBackspace, tea.KeyRunes, tea.KeySpace:
+		case tea.KeySpace:
+			// Add or remove the highlighted node, so the site runs on several nodes as replicas
+			if !isSiteTypeField && len(m.state.Nodes) > 0 {
+				m.state.ToggleFormNode(m.state.Nodes[m.state.DropdownIndex].Name)
+			}
+			return m, nil
+
+		case tea.KeyBackspace, tea.KeyRunes:
 			// Close dropdown and allow manual input (not for site type - it's a fixed dropdown)
 			if !isSiteTypeField {
 				m.state.DropdownOpen = false
@@ -400,8 +1109,40 @@ func (m Model) handleSiteCreateKeys(msg tea.KeyMsg) (tea.Model, tea.Cmd) {
 		return m.handleDomainMappingInput(msg)
 	}
 
+	// Toggle blue/green deploys (container sites only)
+	if m.state.Keymap.Matches(msg.String(), config.KeyBlueGreen) {
+		if m.state.SiteTypeSelection != "compose" {
+			m.state.SiteBlueGreen = !m.state.SiteBlueGreen
+		}
+		return m, nil
+	}
+
 	// Normal field input handling
 	switch msg.Type {
+	case tea.KeyCtrlB:
+		// Toggle between a prebuilt image and building from source (container sites only)
+		if m.state.SiteTypeSelection != "compose" {
+			m.state.ToggleImageSource()
+		}
+		return m, nil
+
+	case tea.KeyCtrlF:
+		// Edit the config files mounted into the container (container sites only)
+		if m.state.SiteTypeSelection != "compose" {
+			m.state.NavigateTo(state.ScreenSiteConfigFiles)
+		}
+		return m, nil
+
+	case tea.KeyCtrlT:
+		// Toggle SSL for the site
+		m.state.SiteSSLEnabled = !m.state.SiteSSLEnabled
+		return m, nil
+
+	case tea.KeyCtrlG:
+		// Toggle restarting the site automatically when it fails
+		m.state.SiteAutoRestart = !m.state.SiteAutoRestart
+		return m, nil
+
 	case tea.KeyUp:
 		// Open dropdown on up arrow if on dropdown field and not open
 		if isDropdownField && !m.state.DropdownOpen {
@@ -488,6 +1229,37 @@ func (m Model) handleSiteCreateKeys(msg tea.KeyMsg) (tea.Model, tea.Cmd) {
 	return m, nil
 }
 
+// suggestSitePort defaults the first domain mapping's host port to the lowest port free on the node.
+// A port the user entered is kept unless another site on the node already publishes it.
+func (m Model) suggestSitePort(nodeID uuid.UUID) {
+	if len(m.state.DomainMappingPairs) == 0 {
+		return
+	}
+	pair := &m.state.DomainMappingPairs[0]
+
+	containerPort := 0
+	if value := strings.TrimSpace(pair.Port); value != "" && value != "8080" {
+		container, host, err := models.ParsePortMapping(value)
+		if err == nil && m.state.PortInUse(nodeID, host, uuid.Nil) == nil {
+			return
+		}
+		if err == nil && container != host {
+			containerPort = container // Keep the container port, only move the host port
+		}
+	}
+
+	port := m.state.NextFreePort(nodeID)
+	if port == 0 {
+		start, end := m.state.PortRange()
+		m.state.AddNotification(fmt.Sprintf("No free port between %d and %d on this node", start, end), "warning")
+		return
+	}
+	if containerPort == 0 {
+		containerPort = port
+	}
+	pair.Port = models.FormatPortMapping(containerPort, port)
+}
+
 // handleSiteEditKeys handles keys on the site edit form
 func (m Model) handleSiteEditKeys(msg tea.KeyMsg) (tea.Model, tea.Cmd) {
 	// Check if we're on a dropdown field (Node=1 in new layout)
@@ -529,7 +1301,14 @@ func (m Model) handleSiteEditKeys(msg tea.KeyMsg) (tea.Model, tea.Cmd) {
 			m.state.DropdownOpen = false
 			return m, nil
 
-		case tea.KeyBackspace, tea.KeyRunes, tea.KeySpace:
+		case tea.KeySpace:
+			// Add or remove the highlighted node, so the site runs on several nodes as replicas
+			if len(m.state.Nodes) > 0 {
+				m.state.ToggleFormNode(m.state.Nodes[m.state.DropdownIndex].Name)
+			}
+			return m, nil
+
+		case tea.KeyBackspace, tea.KeyRunes:
 			// Close dropdown and allow manual input
 			m.state.DropdownOpen = false
 			// Fall through to normal input handling
@@ -541,8 +1320,40 @@ func (m Model) handleSiteEditKeys(msg tea.KeyMsg) (tea.Model, tea.Cmd) {
 		return m.handleDomainMappingInput(msg)
 	}
 
+	// Toggle blue/green deploys (container sites only)
+	if m.state.Keymap.Matches(msg.String(), config.KeyBlueGreen) {
+		if site := m.state.GetSiteByID(m.state.SelectedSiteID); site != nil && site.GetSiteType() != models.SiteTypeCompose {
+			m.state.SiteBlueGreen = !m.state.SiteBlueGreen
+		}
+		return m, nil
+	}
+
 	// Normal field input handling
 	switch msg.Type {
+	case tea.KeyCtrlB:
+		// Toggle between a prebuilt image and building from source (container sites only)
+		if m.state.SiteTypeSelection != "compose" {
+			m.state.ToggleImageSource()
+		}
+		return m, nil
+
+	case tea.KeyCtrlF:
+		// Edit the config files mounted into the container (container sites only)
+		if site := m.state.GetSiteByID(m.state.SelectedSiteID); site != nil && site.GetSiteType() != models.SiteTypeCompose {
+			m.state.NavigateTo(state.ScreenSiteConfigFiles)
+		}
+		return m, nil
+
+	case tea.KeyCtrlT:
+		// Toggle SSL for the site
+		m.state.SiteSSLEnabled = !m.state.SiteSSLEnabled
+		return m, nil
+
+	case tea.KeyCtrlG:
+		// Toggle restarting the site automatically when it fails
+		m.state.SiteAutoRestart = !m.state.SiteAutoRestart
+		return m, nil
+
 	case tea.KeyUp:
 		// Open dropdown on up arrow if on dropdown field and not open
 		if isDropdownField && !m.state.DropdownOpen {
@@ -598,8 +1409,11 @@ func (m Model) handleSiteEditKeys(msg tea.KeyMsg) (tea.Model, tea.Cmd) {
 		if m.state.DropdownOpen {
 			m.state.DropdownOpen = false
 		}
-		// Move to next field or domain mapping section
+		// Move to next field or domain mapping section (field 4 is hidden when building from source)
 		m.state.CurrentFieldIndex++
+		if m.state.IsBuildSource() && m.state.CurrentFieldIndex == 4 {
+			m.state.CurrentFieldIndex++
+		}
 		if m.state.CurrentFieldIndex >= len(m.state.FormFields) {
 			// Move to domain mapping section
 			m.state.CurrentFieldIndex = 200
@@ -616,40 +1430,227 @@ func (m Model) handleSiteEditKeys(msg tea.KeyMsg) (tea.Model, tea.Cmd) {
 		}
 		// Move to previous field
 		m.state.CurrentFieldIndex--
+		if m.state.IsBuildSource() && m.state.CurrentFieldIndex == 4 {
+			m.state.CurrentFieldIndex--
+		}
 		if m.state.CurrentFieldIndex < 0 {
 			m.state.CurrentFieldIndex = len(m.state.FormFields) - 1
 		}
 		return m, nil
 
-	case tea.KeyEnter:
-		// If on dropdown field and not open, open it
-		if isDropdownField && !m.state.DropdownOpen {
-			m.state.DropdownOpen = true
-			m.state.DropdownIndex = 0
+	case tea.KeyEnter:
+		// If on dropdown field and not open, open it
+		if isDropdownField && !m.state.DropdownOpen {
+			m.state.DropdownOpen = true
+			m.state.DropdownIndex = 0
+			return m, nil
+		}
+		// Otherwise submit form
+		return m.handleSiteEditSubmit()
+	}
+
+	return m, nil
+}
+
+// handleSiteEnvVarsKeys handles keys on the dedicated ENV vars screen
+func (m Model) handleSiteEnvVarsKeys(msg tea.KeyMsg) (tea.Model, tea.Cmd) {
+	switch msg.Type {
+	case tea.KeyEsc:
+		// Go back to edit screen
+		m.state.NavigateBack()
+		return m, nil
+	case tea.KeyEnter:
+		// Save and go back (ENV vars already in state, will be saved with site)
+		if _, err := m.state.EnvironmentVars(); err != nil {
+			m.state.AddNotification(err.Error(), "error")
+			return m, nil
+		}
+		m.state.NavigateBack()
+		return m, nil
+	case tea.KeyCtrlF:
+		// Ask for a .env file to import, keeping the last path typed
+		m.state.EnvImportActive = true
+		return m, nil
+	default:
+		// Delegate to existing ENV input handler
+		return m.handleEnvVarInput(msg)
+	}
+}
+
+// handleSiteConfigFilesKeys handles keys on the site form's config files list
+func (m Model) handleSiteConfigFilesKeys(msg tea.KeyMsg) (tea.Model, tea.Cmd) {
+	files := m.state.ConfigFileDrafts
+	switch msg.String() {
+	case "up", "k":
+		if m.state.ConfigFilesIndex > 0 {
+			m.state.ConfigFilesIndex--
+		}
+	case "down", "j":
+		if m.state.ConfigFilesIndex < len(files)-1 {
+			m.state.ConfigFilesIndex++
+		}
+	case "a", "n":
+		m.state.ConfigFileForm = state.NewConfigFileForm(-1, models.ConfigFile{})
+	case "e", "enter":
+		if m.state.ConfigFilesIndex < len(files) {
+			m.state.ConfigFileForm = state.NewConfigFileForm(m.state.ConfigFilesIndex, files[m.state.ConfigFilesIndex])
+		}
+	case "d":
+		// Only the draft is removed; the site keeps the file unless the form is saved
+		if m.state.ConfigFilesIndex < len(files) {
+			name := files[m.state.ConfigFilesIndex].Name
+			m.state.ConfigFileDrafts = append(files[:m.state.ConfigFilesIndex], files[m.state.ConfigFilesIndex+1:]...)
+			if m.state.ConfigFilesIndex >= len(m.state.ConfigFileDrafts) && m.state.ConfigFilesIndex > 0 {
+				m.state.ConfigFilesIndex--
+			}
+			m.state.AddNotification("Config file removed: "+name, "info")
+		}
+	}
+	return m, nil
+}
+
+// handleConfigFileFormKeys handles keys while a config file is being added or edited
+func (m Model) handleConfigFileFormKeys(msg tea.KeyMsg) (tea.Model, tea.Cmd) {
+	form := m.state.ConfigFileForm
+	editor := form.Focused()
+	multiline := form.Field == 2
+
+	switch msg.Type {
+	case tea.KeyEsc:
+		m.state.ConfigFileForm = nil
+	case tea.KeyCtrlS:
+		return m.handleConfigFileFormSubmit()
+	case tea.KeyTab:
+		form.Field = (form.Field + 1) % 3
+	case tea.KeyShiftTab:
+		form.Field = (form.Field + 2) % 3
+	case tea.KeyEnter:
+		if multiline {
+			editor.Newline()
+		} else {
+			form.Field++
+		}
+	case tea.KeyUp:
+		if multiline {
+			editor.MoveUp()
+		}
+	case tea.KeyDown:
+		if multiline {
+			editor.MoveDown()
+		}
+	case tea.KeyLeft:
+		editor.MoveLeft()
+	case tea.KeyRight:
+		editor.MoveRight()
+	case tea.KeyHome, tea.KeyCtrlA:
+		editor.Home()
+	case tea.KeyEnd, tea.KeyCtrlE:
+		editor.End()
+	case tea.KeyBackspace:
+		editor.Backspace()
+	case tea.KeyDelete:
+		editor.Delete()
+	case tea.KeySpace:
+		editor.Insert(" ")
+	case tea.KeyRunes:
+		text := string(msg.Runes)
+		if !multiline {
+			// Pasted newlines don't belong in a name or path
+			text = strings.ReplaceAll(text, "\n", "")
+		}
+		editor.Insert(text)
+	}
+	return m, nil
+}
+
+// handleConfigFileFormSubmit validates the edited config file and stores it in the site form's drafts
+func (m Model) handleConfigFileFormSubmit() (tea.Model, tea.Cmd) {
+	form := m.state.ConfigFileForm
+	file := form.File()
+	if err := file.Validate(); err != nil {
+		m.state.AddNotification(err.Error(), "error")
+		return m, nil
+	}
+	for i, other := range m.state.ConfigFileDrafts {
+		if i != form.Index && other.ContainerPath == file.ContainerPath {
+			m.state.AddNotification("Another config file is already mounted at "+file.ContainerPath, "error")
 			return m, nil
 		}
-		// Otherwise submit form
-		return m.handleSiteEditSubmit()
 	}
 
+	if form.Index < 0 {
+		m.state.ConfigFileDrafts = append(m.state.ConfigFileDrafts, file)
+		m.state.ConfigFilesIndex = len(m.state.ConfigFileDrafts) - 1
+	} else {
+		m.state.ConfigFileDrafts[form.Index] = file
+	}
+	m.state.ConfigFileForm = nil
+	m.state.AddNotification(file.Name+" will be saved with the site", "info")
 	return m, nil
 }
 
-// handleSiteEnvVarsKeys handles keys on the dedicated ENV vars screen
-func (m Model) handleSiteEnvVarsKeys(msg tea.KeyMsg) (tea.Model, tea.Cmd) {
+// handleEnvImportKeys handles typing the path of a .env file to import on the ENV vars screen
+func (m Model) handleEnvImportKeys(msg tea.KeyMsg) (tea.Model, tea.Cmd) {
 	switch msg.Type {
+	case tea.KeyEnter:
+		return m.importEnvFile()
+
 	case tea.KeyEsc:
-		// Go back to edit screen
-		m.state.NavigateBack()
+		m.state.EnvImportActive = false
+
+	case tea.KeyBackspace:
+		path := []rune(m.state.EnvImportPath)
+		if len(path) > 0 {
+			m.state.EnvImportPath = string(path[:len(path)-1])
+		}
+
+	case tea.KeySpace:
+		m.state.EnvImportPath += " "
+
+	case tea.KeyRunes:
+		m.state.EnvImportPath += string(msg.Runes)
+	}
+
+	return m, nil
+}
+
+// importEnvFile merges the variables of the .env file at EnvImportPath into the ENV pairs
+func (m Model) importEnvFile() (tea.Model, tea.Cmd) {
+	path := strings.TrimSpace(m.state.EnvImportPath)
+	if path == "" {
+		m.state.AddNotification("File path is required", "error")
 		return m, nil
-	case tea.KeyEnter:
-		// Save and go back (ENV vars already in state, will be saved with site)
-		m.state.NavigateBack()
+	}
+
+	// Expand ~ to home directory
+	if strings.HasPrefix(path, "~") {
+		homeDir, err := os.UserHomeDir()
+		if err != nil {
+			m.state.AddNotification("Failed to get home directory: "+err.Error(), "error")
+			return m, nil
+		}
+		path = strings.Replace(path, "~", homeDir, 1)
+	}
+
+	content, err := os.ReadFile(path)
+	if err != nil {
+		m.state.AddNotification("Failed to read .env file: "+err.Error(), "error")
+		return m, nil
+	}
+
+	vars, malformed := models.ParseEnvFile(string(content))
+	added, updated := m.state.MergeEnvVars(vars)
+	m.state.EnvImportActive = false
+	m.state.EnvVarFocusedField = 0
+	m.state.CursorPosition = len(m.state.EnvVarPairs[m.state.EnvVarFocusedPair].Key)
+
+	summary := fmt.Sprintf("Imported %d variables from %s (%d new, %d updated)", len(vars), filepath.Base(path), added, updated)
+	if len(malformed) > 0 {
+		m.state.AddNotification(fmt.Sprintf("%s; skipped %d malformed lines: %s", summary, len(malformed), strings.Join(malformed, "; ")), "warning")
 		return m, nil
-	default:
-		// Delegate to existing ENV input handler
-		return m.handleEnvVarInput(msg)
 	}
+	m.state.AddNotification(summary, "success")
+	return m, nil
 }
 
 // handleDomainMappingInput handles keyboard input for domain mapping fields
@@ -927,6 +1928,31 @@ func (m Model) handleEnvVarInput(msg tea.KeyMsg) (tea.Model, tea.Cmd) {
 		}
 		return m, nil
 
+	case tea.KeyCtrlN:
+		// Insert an empty pair below the focused one and focus its key
+		m.state.EnvVarPairs = append(m.state.EnvVarPairs[:pairIdx+1], append([]state.EnvVarPair{{}}, m.state.EnvVarPairs[pairIdx+1:]...)...)
+		m.state.EnvVarFocusedPair = pairIdx + 1
+		m.state.EnvVarFocusedField = 0
+		m.state.CursorPosition = 0
+		return m, nil
+
+	case tea.KeyCtrlD:
+		// Remove the focused pair; the last one is cleared instead so there is always a row to type in
+		if len(m.state.EnvVarPairs) == 1 {
+			m.state.EnvVarPairs[0] = state.EnvVarPair{}
+		} else {
+			m.state.EnvVarPairs = append(m.state.EnvVarPairs[:pairIdx], m.state.EnvVarPairs[pairIdx+1:]...)
+			if m.state.EnvVarFocusedPair >= len(m.state.EnvVarPairs) {
+				m.state.EnvVarFocusedPair = len(m.state.EnvVarPairs) - 1
+			}
+		}
+		if m.state.EnvVarFocusedField == 0 {
+			m.state.CursorPosition = len(m.state.EnvVarPairs[m.state.EnvVarFocusedPair].Key)
+		} else {
+			m.state.CursorPosition = len(m.state.EnvVarPairs[m.state.EnvVarFocusedPair].Value)
+		}
+		return m, nil
+
 	case tea.KeyEnter:
 		// Submit form based on current screen
 		if m.state.CurrentScreen == state.ScreenSiteEdit {
@@ -1036,12 +2062,24 @@ func (m Model) handleEnvVarInput(msg tea.KeyMsg) (tea.Model, tea.Cmd) {
 
 // handleDomainsListKeys handles keys on the domains list screen
 func (m Model) handleDomainsListKeys(msg tea.KeyMsg) (tea.Model, tea.Cmd) {
-	switch msg.String() {
-	case "n", "c":
+	key, keys := msg.String(), m.state.Keymap
+	switch {
+	case keys.Matches(key, config.KeyCreate):
 		m.state.NavigateTo(state.ScreenDomainCreate)
 		return m, nil
 
-	case "e":
+	case keys.Matches(key, config.KeyReload):
+		// Refresh DNS records for all domains
+		return m, func() tea.Msg { return RefreshMsg{} }
+
+	case keys.Matches(key, config.KeyCopy):
+		// Copy the selected domain's name
+		if m.state.DomainsListIndex >= 0 && m.state.DomainsListIndex < len(m.state.Domains) {
+			return m.copyToClipboard("domain name", m.state.Domains[m.state.DomainsListIndex].Name)
+		}
+		return m, nil
+
+	case keys.Matches(key, config.KeyEdit):
 		// Edit selected domain
 		if len(m.state.Domains) > 0 && m.state.DomainsListIndex >= 0 && m.state.DomainsListIndex < len(m.state.Domains) {
 			domain := m.state.Domains[m.state.DomainsListIndex]
@@ -1050,20 +2088,281 @@ func (m Model) handleDomainsListKeys(msg tea.KeyMsg) (tea.Model, tea.Cmd) {
 		}
 		return m, nil
 
-	case "d":
+	case keys.Matches(key, config.KeyDelete):
 		// Delete selected domain
 		if len(m.state.Domains) > 0 && m.state.DomainsListIndex >= 0 && m.state.DomainsListIndex < len(m.state.Domains) {
 			domain := m.state.Domains[m.state.DomainsListIndex]
-			return m.handleDeleteDomain(domain.ID)
+			return m.requestDelete("domain", domain.ID, domain.Name)
 		}
 		return m, nil
 
-	case "enter":
+	case key == "enter":
 		// View DNS records for selected domain
 		if len(m.state.Domains) > 0 && m.state.DomainsListIndex >= 0 && m.state.DomainsListIndex < len(m.state.Domains) {
+			m.state.SelectedDomainID = m.state.Domains[m.state.DomainsListIndex].ID
+			m.state.DnsRecordsIndex = 0
 			m.state.NavigateTo(state.ScreenDomainDnsRecords)
 		}
 		return m, nil
+
+	case keys.Matches(key, config.KeySyncDNS):
+		// Sync DNS records for selected domain from its provider
+		if len(m.state.Domains) > 0 && m.state.DomainsListIndex >= 0 && m.state.DomainsListIndex < len(m.state.Domains) {
+			domain := m.state.Domains[m.state.DomainsListIndex]
+			m.state.AddNotification("Syncing DNS records for: "+domain.Name, "info")
+			return m, func() tea.Msg { return SyncDnsMsg{DomainID: domain.ID, Preview: true} }
+		}
+		return m, nil
+
+	case keys.Matches(key, config.KeyPushDNS):
+		// Push local DNS records to the provider, keeping provider-only records
+		if len(m.state.Domains) > 0 && m.state.DomainsListIndex >= 0 && m.state.DomainsListIndex < len(m.state.Domains) {
+			domain := m.state.Domains[m.state.DomainsListIndex]
+			m.state.AddNotification("Pushing DNS records for: "+domain.Name, "info")
+			return m, func() tea.Msg { return PushDnsRecordsMsg{DomainID: domain.ID} }
+		}
+		return m, nil
+
+	case keys.Matches(key, config.KeyPushDNSPrune):
+		// Push local DNS records and delete provider records missing locally (asks first)
+		if len(m.state.Domains) > 0 && m.state.DomainsListIndex >= 0 && m.state.DomainsListIndex < len(m.state.Domains) {
+			domain := m.state.Domains[m.state.DomainsListIndex]
+			return m.requestDelete(dnsPruneConfirmKind, domain.ID, "not in Archon for "+domain.Name)
+		}
+		return m, nil
+
+	case keys.Matches(key, config.KeyCheckCert):
+		// Check when the TLS certificates for the selected domain's hosts expire
+		if len(m.state.Domains) > 0 && m.state.DomainsListIndex >= 0 && m.state.DomainsListIndex < len(m.state.Domains) {
+			domain := m.state.Domains[m.state.DomainsListIndex]
+			m.state.AddNotification("Checking certificates for: "+domain.Name, "info")
+			return m, m.spawnCheckCert(domain.ID)
+		}
+		return m, nil
+	}
+
+	return m, nil
+}
+
+// handleDomainDnsRecordsKeys handles keys on a domain's DNS records screen
+func (m Model) handleDomainDnsRecordsKeys(msg tea.KeyMsg) (tea.Model, tea.Cmd) {
+	domain := m.state.GetDomainByID(m.state.SelectedDomainID)
+	if domain == nil {
+		return m, nil
+	}
+	records := domain.DnsRecords
+	selected := m.state.DnsRecordsIndex >= 0 && m.state.DnsRecordsIndex < len(records)
+
+	key, keys := msg.String(), m.state.Keymap
+	switch {
+	case keys.Matches(key, config.KeyUp):
+		if m.state.DnsRecordsIndex > 0 {
+			m.state.DnsRecordsIndex--
+		}
+		return m, nil
+
+	case keys.Matches(key, config.KeyDown):
+		if m.state.DnsRecordsIndex < len(records)-1 {
+			m.state.DnsRecordsIndex++
+		}
+		return m, nil
+
+	case keys.Matches(key, config.KeyAddRecord), keys.Matches(key, config.KeyCreate):
+		// Open the record form for a new record
+		m.openDnsRecordForm(-1, models.DnsRecord{RecordType: models.DnsRecordTypeA, TTL: 1})
+		return m, nil
+
+	case keys.Matches(key, config.KeyEdit):
+		// Open the record form for the selected record
+		if selected {
+			m.openDnsRecordForm(m.state.DnsRecordsIndex, records[m.state.DnsRecordsIndex])
+		}
+		return m, nil
+
+	case keys.Matches(key, config.KeyDelete):
+		// Delete the selected record (asks first)
+		if selected {
+			record := records[m.state.DnsRecordsIndex]
+			return m.requestDelete(dnsRecordConfirmKind, domain.ID, fmt.Sprintf("%s %s", record.RecordType, record.Name))
+		}
+		return m, nil
+
+	case keys.Matches(key, config.KeySyncDNS):
+		// Fetch the provider's records, previewing changes before applying them
+		m.state.AddNotification("Syncing DNS records for: "+domain.Name, "info")
+		return m, func() tea.Msg { return SyncDnsMsg{DomainID: domain.ID, Preview: true} }
+
+	case keys.Matches(key, config.KeyPushDNS):
+		// Push local records to the provider, keeping provider-only records
+		m.state.AddNotification("Pushing DNS records for: "+domain.Name, "info")
+		return m, func() tea.Msg { return PushDnsRecordsMsg{DomainID: domain.ID} }
+
+	case keys.Matches(key, config.KeyPushDNSPrune):
+		// Push local records and delete provider records missing locally (asks first)
+		return m.requestDelete(dnsPruneConfirmKind, domain.ID, "not in Archon for "+domain.Name)
+
+	case keys.Matches(key, config.KeyCheckPropagation):
+		// Check whether public resolvers return each record's value
+		if len(records) == 0 {
+			m.state.AddNotification("No DNS records to check for: "+domain.Name, "info")
+			return m, nil
+		}
+		m.state.AddNotification("Checking DNS propagation for: "+domain.Name, "info")
+		return m, m.spawnCheckPropagation(domain.ID)
+	}
+
+	return m, nil
+}
+
+// openDnsRecordForm shows the record form filled in from record; index is the record being
+// edited, or -1 to add a record
+func (m Model) openDnsRecordForm(index int, record models.DnsRecord) {
+	m.state.NavigateTo(state.ScreenDnsRecordForm)
+	m.state.DnsRecordFormIndex = index
+
+	proxied := "no"
+	if record.Proxied {
+		proxied = "yes"
+	}
+	m.state.FormFields = []string{string(record.RecordType), record.Name, record.Value, strconv.Itoa(record.TTL), proxied}
+	m.setFieldAndResetCursor(0)
+}
+
+// handleDnsRecordFormKeys handles keys on the DNS record form
+func (m Model) handleDnsRecordFormKeys(msg tea.KeyMsg) (tea.Model, tea.Cmd) {
+	if m.handleTextInput(msg, m.state.CurrentFieldIndex) {
+		return m, nil
+	}
+
+	switch msg.Type {
+	case tea.KeyTab:
+		m.setFieldAndResetCursor((m.state.CurrentFieldIndex + 1) % len(m.state.FormFields))
+		return m, nil
+
+	case tea.KeyShiftTab:
+		prevField := m.state.CurrentFieldIndex - 1
+		if prevField < 0 {
+			prevField = len(m.state.FormFields) - 1
+		}
+		m.setFieldAndResetCursor(prevField)
+		return m, nil
+
+	case tea.KeyEnter:
+		return m.handleDnsRecordFormSubmit()
+	}
+
+	return m, nil
+}
+
+// handleDnsRecordFormSubmit checks the record form and saves the record, keeping the form open
+// when a field is invalid
+func (m Model) handleDnsRecordFormSubmit() (tea.Model, tea.Cmd) {
+	// Fields: 0=type, 1=name, 2=value, 3=TTL, 4=proxied
+	fields := m.state.FormFields
+	if len(fields) != 5 {
+		return m, nil
+	}
+
+	recordType, err := models.ParseDnsRecordType(strings.TrimSpace(fields[0]))
+	if err != nil {
+		m.state.AddNotification("Invalid DNS record: "+err.Error(), "error")
+		return m, nil
+	}
+
+	ttl := 1
+	if value := strings.TrimSpace(fields[3]); value != "" {
+		ttl, err = strconv.Atoi(value)
+		if err != nil {
+			m.state.AddNotification("Invalid DNS record: TTL must be a number of seconds", "error")
+			return m, nil
+		}
+	}
+
+	var proxied bool
+	switch strings.ToLower(strings.TrimSpace(fields[4])) {
+	case "yes", "y", "true":
+		proxied = true
+	case "no", "n", "false", "":
+	default:
+		m.state.AddNotification("Invalid DNS record: proxied must be yes or no", "error")
+		return m, nil
+	}
+
+	record := models.NewDnsRecord(recordType, strings.TrimSpace(fields[1]), strings.TrimSpace(fields[2]), ttl)
+	record.Proxied = proxied
+	if err := record.Validate(); err != nil {
+		m.state.AddNotification("Invalid DNS record: "+err.Error(), "error")
+		return m, nil
+	}
+
+	domainID, index := m.state.SelectedDomainID, m.state.DnsRecordFormIndex
+	m.state.NavigateBack()
+	if index < 0 {
+		return m, func() tea.Msg { return CreateDnsRecordMsg{DomainID: domainID, Record: record} }
+	}
+	return m, func() tea.Msg { return UpdateDnsRecordMsg{DomainID: domainID, Index: index, Record: record} }
+}
+
+// handleDnsSyncDiffKeys handles keys on the DNS sync diff screen
+func (m Model) handleDnsSyncDiffKeys(msg tea.KeyMsg) (tea.Model, tea.Cmd) {
+	switch msg.String() {
+	case "enter", "y", "a":
+		// Apply the fetched records
+		return m, func() tea.Msg { return ApplyDnsSyncMsg{} }
+
+	case "n", "d":
+		// Discard the fetched records, leaving local records untouched
+		m.state.PendingDnsSync = nil
+		m.state.AddNotification("DNS sync discarded", "info")
+		m.state.NavigateBack()
+		return m, nil
+	}
+
+	return m, nil
+}
+
+// handleDeployPreviewKeys handles keys on the deploy preview screen (scrollable viewport)
+func (m Model) handleDeployPreviewKeys(msg tea.KeyMsg) (tea.Model, tea.Cmd) {
+	var cmd tea.Cmd
+
+	key, keys := msg.String(), m.state.Keymap
+	switch {
+	case keys.Matches(key, config.KeyDown):
+		m.state.DeployPreviewViewport.LineDown(1)
+		return m, nil
+
+	case keys.Matches(key, config.KeyUp):
+		m.state.DeployPreviewViewport.LineUp(1)
+		return m, nil
+
+	case key == "pgdown" || key == "f" || key == "pgup" || key == "b":
+		m.state.DeployPreviewViewport, cmd = m.state.DeployPreviewViewport.Update(msg)
+		return m, cmd
+
+	case keys.Matches(key, config.KeyTop):
+		m.state.DeployPreviewViewport.GotoTop()
+		return m, nil
+
+	case keys.Matches(key, config.KeyBottom):
+		m.state.DeployPreviewViewport.GotoBottom()
+		return m, nil
+
+	case key == "enter":
+		// Deploy the previewed site
+		preview := m.state.DeployPreview
+		if preview == nil {
+			return m, nil
+		}
+		site := m.state.GetSiteByID(preview.SiteID)
+		if site == nil {
+			m.state.AddNotification("Site not found", "error")
+			return m, nil
+		}
+		m.state.DeployPreview = nil
+		m.state.NavigateBack()
+		m.state.AddNotification("Deploying site: "+site.Name, "info")
+		siteID := site.ID
+		return m, func() tea.Msg { return DeploySiteMsg{SiteID: siteID} }
 	}
 
 	return m, nil
@@ -1181,10 +2480,8 @@ func (m Model) handleDomainCreateKeys(msg tea.KeyMsg) (tea.Model, tea.Cmd) {
 		return m, nil
 
 	case tea.KeyShiftTab:
-		// Close dropdown if open
-		if m.state.DropdownOpen {
-			m.state.DropdownOpen = false
-		}
+		// Close the dropdown, which is only ever open on the provider field
+		m.state.DropdownOpen = false
 		// Move to previous field
 		m.state.CurrentFieldIndex--
 		if m.state.CurrentFieldIndex < 0 {
@@ -1325,10 +2622,8 @@ func (m Model) handleDomainEditKeys(msg tea.KeyMsg) (tea.Model, tea.Cmd) {
 		return m, nil
 
 	case tea.KeyShiftTab:
-		// Close dropdown if open
-		if m.state.DropdownOpen {
-			m.state.DropdownOpen = false
-		}
+		// Close the dropdown, which is only ever open on the provider field
+		m.state.DropdownOpen = false
 		// Move to previous field
 		m.state.CurrentFieldIndex--
 		if m.state.CurrentFieldIndex < 0 {
@@ -1359,41 +2654,124 @@ func (m Model) handleDomainEditKeys(msg tea.KeyMsg) (tea.Model, tea.Cmd) {
 
 // handleNodesListKeys handles keys on the nodes list screen
 func (m Model) handleNodesListKeys(msg tea.KeyMsg) (tea.Model, tea.Cmd) {
-	switch msg.String() {
-	case "n", "c":
+	key, keys := msg.String(), m.state.Keymap
+	switch {
+	case keys.Matches(key, config.KeyCreate):
 		m.state.NavigateTo(state.ScreenNodeCreate)
 		return m, nil
 
-	case "v", "enter":
+	case keys.Matches(key, config.KeyReload):
+		// Refresh health for all nodes
+		return m, func() tea.Msg { return RefreshMsg{} }
+
+	case keys.Matches(key, config.KeyCopy):
+		// Copy the selected node's API endpoint
+		if node := m.state.SelectedVisibleNode(); node != nil {
+			return m.copyToClipboard("API endpoint", node.APIEndpoint)
+		}
+		return m, nil
+
+	case keys.Matches(key, config.KeyViewConfig):
 		// View config for selected node
-		if len(m.state.Nodes) > 0 && m.state.NodesListIndex >= 0 && m.state.NodesListIndex < len(m.state.Nodes) {
-			m.state.SelectedNodeID = m.state.Nodes[m.state.NodesListIndex].ID
+		if node := m.state.SelectedVisibleNode(); node != nil {
+			m.state.SelectedNodeID = node.ID
 			m.state.NavigateTo(state.ScreenNodeConfig)
 		}
 		return m, nil
 
-	case "e":
+	case keys.Matches(key, config.KeyStopAll):
+		// Stop every site on the selected node
+		if node := m.state.SelectedVisibleNode(); node != nil {
+			nodeID := node.ID
+			return m, func() tea.Msg { return StopAllOnNodeMsg{NodeID: nodeID} }
+		}
+		return m, nil
+
+	case keys.Matches(key, config.KeyDeployAll):
+		// Deploy every site on the selected node
+		if node := m.state.SelectedVisibleNode(); node != nil {
+			nodeID := node.ID
+			return m, func() tea.Msg { return DeployAllOnNodeMsg{NodeID: nodeID} }
+		}
+		return m, nil
+
+	case keys.Matches(key, config.KeyEdit):
 		// Edit selected node
-		if len(m.state.Nodes) > 0 && m.state.NodesListIndex >= 0 && m.state.NodesListIndex < len(m.state.Nodes) {
-			node := m.state.Nodes[m.state.NodesListIndex]
+		if node := m.state.SelectedVisibleNode(); node != nil {
 			m.state.AddNotification("Edit node: "+node.Name+" (not yet implemented)", "info")
 		}
 		return m, nil
 
-	case "d":
+	case keys.Matches(key, config.KeyDelete):
 		// Delete selected node
-		if len(m.state.Nodes) > 0 && m.state.NodesListIndex >= 0 && m.state.NodesListIndex < len(m.state.Nodes) {
-			node := m.state.Nodes[m.state.NodesListIndex]
-			return m.handleDeleteNode(node.ID)
+		if node := m.state.SelectedVisibleNode(); node != nil {
+			return m.requestDelete("node", node.ID, node.Name)
+		}
+		return m, nil
+
+	case keys.Matches(key, config.KeyFilterTag):
+		// Cycle tag filter: all -> each tag in order -> all
+		tags := m.state.AllNodeTags()
+		if len(tags) == 0 {
+			m.state.AddNotification("No nodes have tags yet. Add tags when creating or editing a node", "info")
+			return m, nil
 		}
+		m.cycleNodeTagFilter(tags)
 		return m, nil
 	}
 
 	return m, nil
 }
 
+// cycleNodeTagFilter moves the node tag filter to the next tag, or back to all nodes after the last one
+func (m Model) cycleNodeTagFilter(tags []string) {
+	next := ""
+	if m.state.NodeTagFilter == "" {
+		next = tags[0]
+	} else {
+		for i, tag := range tags {
+			if tag == m.state.NodeTagFilter && i+1 < len(tags) {
+				next = tags[i+1]
+				break
+			}
+		}
+	}
+	m.state.NodeTagFilter = next
+
+	// Reset selection to the top of the filtered list
+	m.state.NodesListIndex = 0
+	if m.state.NodesTable != nil {
+		m.state.NodesTable.SetCursor(0)
+	}
+
+	if next == "" {
+		m.state.AddNotification("Showing all nodes", "info")
+	} else {
+		m.state.AddNotification(fmt.Sprintf("Filtering nodes by tag: %s (%d nodes)", next, len(m.state.NodesWithTag(next))), "info")
+	}
+}
+
+// testNodeFormConnection tests the endpoint and API key currently entered in a node form
+func (m Model) testNodeFormConnection() tea.Cmd {
+	endpoint := m.state.FormFields[1]
+	apiKey := ""
+	if m.state.CurrentScreen == state.ScreenNodeCreate {
+		apiKey = m.state.FormFields[5] // Generated key on the create form
+	} else if node := m.state.GetNodeByID(m.state.SelectedNodeID); node != nil {
+		apiKey = node.APIKey
+	}
+	return func() tea.Msg {
+		return TestNodeConnectionMsg{Endpoint: endpoint, APIKey: apiKey}
+	}
+}
+
 // handleNodeCreateKeys handles keys on the node creation form
 func (m Model) handleNodeCreateKeys(msg tea.KeyMsg) (tea.Model, tea.Cmd) {
+	// Ctrl+T tests the entered endpoint before the node is created
+	if msg.Type == tea.KeyCtrlT {
+		return m, m.testNodeFormConnection()
+	}
+
 	// Check if we're on proxy field (index 2)
 	isProxyField := m.state.CurrentFieldIndex == 2
 
@@ -1417,8 +2795,8 @@ func (m Model) handleNodeCreateKeys(msg tea.KeyMsg) (tea.Model, tea.Cmd) {
 			m.state.FormFields[2] = proxies[m.state.DropdownIndex]
 			m.state.DropdownOpen = false
 			if msg.Type == tea.KeyTab {
-				// Move to next field (cycle through 0, 1, 2)
-				m.state.CurrentFieldIndex = (m.state.CurrentFieldIndex + 1) % 3
+				// Move to next field (cycle through 0, 1, 2, 3, 4)
+				m.state.CurrentFieldIndex = (m.state.CurrentFieldIndex + 1) % 5
 			}
 			return m, nil
 		case tea.KeyEsc:
@@ -1446,37 +2824,37 @@ func (m Model) handleNodeCreateKeys(msg tea.KeyMsg) (tea.Model, tea.Cmd) {
 			return m, nil
 		case tea.KeyTab:
 			// Move to next field without opening dropdown
-			m.state.CurrentFieldIndex = (m.state.CurrentFieldIndex + 1) % 3
+			m.state.CurrentFieldIndex = (m.state.CurrentFieldIndex + 1) % 5
 			return m, nil
 		case tea.KeyShiftTab:
 			// Move to previous field
 			m.state.CurrentFieldIndex--
 			if m.state.CurrentFieldIndex < 0 {
-				m.state.CurrentFieldIndex = 2
+				m.state.CurrentFieldIndex = 4
 			}
 			return m, nil
 		}
 	}
 
-	// Handle regular text input for Name and API Endpoint fields (0, 1)
+	// Handle regular text input for Name, API Endpoint, Public IP and Tags fields (0, 1, 3, 4)
 	switch msg.Type {
 	case tea.KeySpace:
-		// Add space to current field (only editable text fields - first 2)
-		if m.state.CurrentFieldIndex < 2 {
+		// Add space to current field (only editable text fields - 0, 1, 3, 4)
+		if m.state.CurrentFieldIndex != 2 && m.state.CurrentFieldIndex < 5 {
 			m.state.FormFields[m.state.CurrentFieldIndex] += " "
 		}
 		return m, nil
 
 	case tea.KeyRunes:
-		// Add character to current field (only editable text fields - first 2)
-		if m.state.CurrentFieldIndex < 2 {
+		// Add character to current field (only editable text fields - 0, 1, 3, 4)
+		if m.state.CurrentFieldIndex != 2 && m.state.CurrentFieldIndex < 5 {
 			m.state.FormFields[m.state.CurrentFieldIndex] += string(msg.Runes)
 		}
 		return m, nil
 
 	case tea.KeyBackspace:
-		// Remove last character from current field (only editable text fields - first 2)
-		if m.state.CurrentFieldIndex < 2 {
+		// Remove last character from current field (only editable text fields - 0, 1, 3, 4)
+		if m.state.CurrentFieldIndex != 2 && m.state.CurrentFieldIndex < 5 {
 			value := m.state.FormFields[m.state.CurrentFieldIndex]
 			if len(value) > 0 {
 				m.state.FormFields[m.state.CurrentFieldIndex] = value[:len(value)-1]
@@ -1485,15 +2863,15 @@ func (m Model) handleNodeCreateKeys(msg tea.KeyMsg) (tea.Model, tea.Cmd) {
 		return m, nil
 
 	case tea.KeyTab:
-		// Move to next field (cycle through editable fields: 0, 1, 2)
-		m.state.CurrentFieldIndex = (m.state.CurrentFieldIndex + 1) % 3
+		// Move to next field (cycle through editable fields: 0, 1, 2, 3, 4)
+		m.state.CurrentFieldIndex = (m.state.CurrentFieldIndex + 1) % 5
 		return m, nil
 
 	case tea.KeyShiftTab:
 		// Move to previous field
 		m.state.CurrentFieldIndex--
 		if m.state.CurrentFieldIndex < 0 {
-			m.state.CurrentFieldIndex = 2
+			m.state.CurrentFieldIndex = 4
 		}
 		return m, nil
 
@@ -1509,6 +2887,11 @@ func (m Model) handleNodeCreateKeys(msg tea.KeyMsg) (tea.Model, tea.Cmd) {
 
 // handleNodeEditKeys handles keys on the node edit form
 func (m Model) handleNodeEditKeys(msg tea.KeyMsg) (tea.Model, tea.Cmd) {
+	// Ctrl+T tests the entered endpoint before the changes are saved
+	if msg.Type == tea.KeyCtrlT {
+		return m, m.testNodeFormConnection()
+	}
+
 	// Check if we're on proxy field (index 2)
 	isProxyField := m.state.CurrentFieldIndex == 2
 
@@ -1532,8 +2915,8 @@ func (m Model) handleNodeEditKeys(msg tea.KeyMsg) (tea.Model, tea.Cmd) {
 			m.state.FormFields[2] = proxies[m.state.DropdownIndex]
 			m.state.DropdownOpen = false
 			if msg.Type == tea.KeyTab {
-				// Move to next field (cycle through 0, 1, 2)
-				m.state.CurrentFieldIndex = (m.state.CurrentFieldIndex + 1) % 3
+				// Move to next field (cycle through 0, 1, 2, 3, 4)
+				m.state.CurrentFieldIndex = (m.state.CurrentFieldIndex + 1) % 5
 			}
 			return m, nil
 		case tea.KeyEsc:
@@ -1561,37 +2944,37 @@ func (m Model) handleNodeEditKeys(msg tea.KeyMsg) (tea.Model, tea.Cmd) {
 			return m, nil
 		case tea.KeyTab:
 			// Move to next field without opening dropdown
-			m.state.CurrentFieldIndex = (m.state.CurrentFieldIndex + 1) % 3
+			m.state.CurrentFieldIndex = (m.state.CurrentFieldIndex + 1) % 5
 			return m, nil
 		case tea.KeyShiftTab:
 			// Move to previous field
 			m.state.CurrentFieldIndex--
 			if m.state.CurrentFieldIndex < 0 {
-				m.state.CurrentFieldIndex = 2
+				m.state.CurrentFieldIndex = 4
 			}
 			return m, nil
 		}
 	}
 
-	// Handle regular text input for Name and API Endpoint fields (0, 1)
+	// Handle regular text input for Name, API Endpoint, Public IP and Tags fields (0, 1, 3, 4)
 	switch msg.Type {
 	case tea.KeySpace:
-		// Add space to current field (only editable text fields - first 2)
-		if m.state.CurrentFieldIndex < 2 {
+		// Add space to current field (only editable text fields - 0, 1, 3, 4)
+		if m.state.CurrentFieldIndex != 2 && m.state.CurrentFieldIndex < 5 {
 			m.state.FormFields[m.state.CurrentFieldIndex] += " "
 		}
 		return m, nil
 
 	case tea.KeyRunes:
-		// Add character to current field (only editable text fields - first 2)
-		if m.state.CurrentFieldIndex < 2 {
+		// Add character to current field (only editable text fields - 0, 1, 3, 4)
+		if m.state.CurrentFieldIndex != 2 && m.state.CurrentFieldIndex < 5 {
 			m.state.FormFields[m.state.CurrentFieldIndex] += string(msg.Runes)
 		}
 		return m, nil
 
 	case tea.KeyBackspace:
-		// Remove last character from current field (only editable text fields - first 2)
-		if m.state.CurrentFieldIndex < 2 {
+		// Remove last character from current field (only editable text fields - 0, 1, 3, 4)
+		if m.state.CurrentFieldIndex != 2 && m.state.CurrentFieldIndex < 5 {
 			value := m.state.FormFields[m.state.CurrentFieldIndex]
 			if len(value) > 0 {
 				m.state.FormFields[m.state.CurrentFieldIndex] = value[:len(value)-1]
@@ -1600,15 +2983,15 @@ func (m Model) handleNodeEditKeys(msg tea.KeyMsg) (tea.Model, tea.Cmd) {
 		return m, nil
 
 	case tea.KeyTab:
-		// Move to next field (cycle through editable fields: 0, 1, 2)
-		m.state.CurrentFieldIndex = (m.state.CurrentFieldIndex + 1) % 3
+		// Move to next field (cycle through editable fields: 0, 1, 2, 3, 4)
+		m.state.CurrentFieldIndex = (m.state.CurrentFieldIndex + 1) % 5
 		return m, nil
 
 	case tea.KeyShiftTab:
 		// Move to previous field
 		m.state.CurrentFieldIndex--
 		if m.state.CurrentFieldIndex < 0 {
-			m.state.CurrentFieldIndex = 2
+			m.state.CurrentFieldIndex = 4
 		}
 		return m, nil
 
@@ -1626,38 +3009,39 @@ func (m Model) handleNodeEditKeys(msg tea.KeyMsg) (tea.Model, tea.Cmd) {
 func (m Model) handleNodeConfigKeys(msg tea.KeyMsg) (tea.Model, tea.Cmd) {
 	var cmd tea.Cmd
 
-	switch msg.String() {
-	case "down", "j":
+	key, keys := msg.String(), m.state.Keymap
+	switch {
+	case keys.Matches(key, config.KeyDown):
 		// Scroll down one line
 		m.state.NodeConfigViewport.LineDown(1)
 		return m, nil
 
-	case "up", "k":
+	case keys.Matches(key, config.KeyUp):
 		// Scroll up one line
 		m.state.NodeConfigViewport.LineUp(1)
 		return m, nil
 
-	case "pgdown", "f":
+	case key == "pgdown" || key == "f":
 		// Scroll down one page
 		m.state.NodeConfigViewport, cmd = m.state.NodeConfigViewport.Update(msg)
 		return m, cmd
 
-	case "pgup", "b":
+	case key == "pgup" || key == "b":
 		// Scroll up one page
 		m.state.NodeConfigViewport, cmd = m.state.NodeConfigViewport.Update(msg)
 		return m, cmd
 
-	case "home", "g":
+	case keys.Matches(key, config.KeyTop):
 		// Jump to top
 		m.state.NodeConfigViewport.GotoTop()
 		return m, nil
 
-	case "end", "G":
+	case keys.Matches(key, config.KeyBottom):
 		// Jump to bottom
 		m.state.NodeConfigViewport.GotoBottom()
 		return m, nil
 
-	case "s":
+	case key == "s":
 		// Save config to file
 		return m.handleSaveNodeConfig()
 	}
@@ -1782,7 +3166,24 @@ func (m Model) handleSettingsKeys(msg tea.KeyMsg) (tea.Model, tea.Cmd) {
 
 // handleHelpKeys handles keys on the help screen
 func (m Model) handleHelpKeys(msg tea.KeyMsg) (tea.Model, tea.Cmd) {
-	// Help screen is mostly static, just allow navigation back
+	// Scroll the key bindings; the help screen keeps the scroll within them when rendering
+	page := max(m.state.WindowHeight-13, 5)
+
+	key, keys := msg.String(), m.state.Keymap
+	switch {
+	case keys.Matches(key, config.KeyUp):
+		m.state.HelpScroll = max(m.state.HelpScroll-1, 0)
+	case keys.Matches(key, config.KeyDown):
+		m.state.HelpScroll++
+	case key == "pgup":
+		m.state.HelpScroll = max(m.state.HelpScroll-page, 0)
+	case key == "pgdown":
+		m.state.HelpScroll += page
+	case keys.Matches(key, config.KeyTop):
+		m.state.HelpScroll = 0
+	case keys.Matches(key, config.KeyBottom):
+		m.state.HelpScroll = math.MaxInt32
+	}
 	return m, nil
 }
 
@@ -1792,18 +3193,12 @@ func (m Model) handleHelpKeys(msg tea.KeyMsg) (tea.Model, tea.Cmd) {
 
 // handleMouseClick routes mouse click events to screen-specific handlers
 func (m Model) handleMouseClick(msg tea.MouseMsg) (tea.Model, tea.Cmd) {
-	// Only handle mouse button release (not press/drag)
-	if msg.Action != tea.MouseActionRelease {
+	// Only handle left button release (not press/drag/wheel)
+	if msg.Action != tea.MouseActionRelease || msg.Button != tea.MouseButtonLeft {
 		return m, nil
 	}
 
-	// TODO: Implement mouse handling per screen
-	// Each screen will need to calculate what was clicked based on msg.X and msg.Y
-	// Example:
-	// - Table rows: Calculate which row based on Y coordinate
-	// - Buttons: Check if X,Y falls within button bounds
-	// - Form fields: Check if Y coordinate matches a field's row
-
+	// Form fields and buttons are matched by zone in Update; this handles the rest
 	switch m.state.CurrentScreen {
 	case state.ScreenSitesList:
 		return m.handleSitesListClick(msg)
@@ -1822,38 +3217,42 @@ func (m Model) handleMouseClick(msg tea.MouseMsg) (tea.Model, tea.Cmd) {
 	return m, nil
 }
 
-// handleSitesListClick handles mouse clicks on the sites list
+// handleSitesListClick selects the sites table row that was clicked
 func (m Model) handleSitesListClick(msg tea.MouseMsg) (tea.Model, tea.Cmd) {
-	// TODO: Implement click-to-select for sites table
-	// Calculate which table row was clicked based on msg.Y
-	// Account for header, menu, and table header rows
-
-	// Example structure:
-	// const (
-	//     headerHeight = 1
-	//     menuHeight = 1
-	//     tableHeaderHeight = 2
-	// )
-	// tableStartY := headerHeight + menuHeight + tableHeaderHeight
-	// if msg.Y >= tableStartY {
-	//     rowIndex := msg.Y - tableStartY
-	//     if rowIndex >= 0 && rowIndex < len(m.state.Sites) {
-	//         m.state.SitesListIndex = rowIndex
-	//     }
-	// }
-
+	if m.state.SitesTable == nil {
+		return m, nil
+	}
+	_, y := m.zone.Get("table:sites").Pos(msg)
+	if row := m.state.SitesTable.HandleMouseClick(y); row >= 0 {
+		m.state.SitesListIndex = row
+		m.state.SitesTable.SetCursor(row)
+	}
 	return m, nil
 }
 
-// handleDomainsListClick handles mouse clicks on the domains list
+// handleDomainsListClick selects the domains table row that was clicked
 func (m Model) handleDomainsListClick(msg tea.MouseMsg) (tea.Model, tea.Cmd) {
-	// TODO: Implement click-to-select for domains table
+	if m.state.DomainsTable == nil {
+		return m, nil
+	}
+	_, y := m.zone.Get("table:domains").Pos(msg)
+	if row := m.state.DomainsTable.HandleMouseClick(y); row >= 0 {
+		m.state.DomainsListIndex = row
+		m.state.DomainsTable.SetCursor(row)
+	}
 	return m, nil
 }
 
-// handleNodesListClick handles mouse clicks on the nodes list
+// handleNodesListClick selects the nodes table row that was clicked
 func (m Model) handleNodesListClick(msg tea.MouseMsg) (tea.Model, tea.Cmd) {
-	// TODO: Implement click-to-select for nodes table
+	if m.state.NodesTable == nil {
+		return m, nil
+	}
+	_, y := m.zone.Get("table:nodes").Pos(msg)
+	if row := m.state.NodesTable.HandleMouseClick(y); row >= 0 {
+		m.state.NodesListIndex = row
+		m.state.NodesTable.SetCursor(row)
+	}
 	return m, nil
 }
 
@@ -1907,6 +3306,10 @@ func (m Model) handleSiteCreateSubmit() (tea.Model, tea.Cmd) {
 		m.state.AddNotification("Required field Name must be filled", "error")
 		return m, nil
 	}
+	if m.state.SiteNameTaken(m.state.FormFields[0], uuid.Nil) {
+		m.state.AddNotification("Site already exists: "+m.state.FormFields[0], "error")
+		return m, nil
+	}
 	if m.state.FormFields[1] == "" {
 		m.state.AddNotification("Required field Node must be filled", "error")
 		return m, nil
@@ -1914,6 +3317,8 @@ func (m Model) handleSiteCreateSubmit() (tea.Model, tea.Cmd) {
 	if m.state.FormFields[2] == "" {
 		if isCompose {
 			m.state.AddNotification("Required field Compose File Path must be filled", "error")
+		} else if m.state.IsBuildSource() {
+			m.state.AddNotification("Required field Build Context must be filled", "error")
 		} else {
 			m.state.AddNotification("Required field Docker Image must be filled", "error")
 		}
@@ -1950,18 +3355,14 @@ func (m Model) handleSiteCreateSubmit() (tea.Model, tea.Cmd) {
 		}
 	}
 
-	// Find node by name (index 1)
-	var nodeID uuid.UUID
-	nodeFound := false
-	for _, node := range m.state.Nodes {
-		if node.Name == m.state.FormFields[1] {
-			nodeID = node.ID
-			nodeFound = true
-			break
-		}
+	// Find nodes by name (index 1): the primary node, then any replicas
+	nodeIDs, err := m.state.NodeIDsByName(m.state.FormFields[1])
+	if err != nil {
+		m.state.AddNotification(err.Error(), "error")
+		return m, nil
 	}
-	if !nodeFound {
-		m.state.AddNotification("Node not found: "+m.state.FormFields[1], "error")
+	if len(nodeIDs) == 0 {
+		m.state.AddNotification("Required field Node must be filled", "error")
 		return m, nil
 	}
 
@@ -2016,45 +3417,135 @@ func (m Model) handleSiteCreateSubmit() (tea.Model, tea.Cmd) {
 		return m, nil
 	}
 
+	// Refuse host ports another site on any of the nodes already publishes
+	for _, nodeID := range nodeIDs {
+		for _, mapping := range domainMappings {
+			hostPort := mapping.GetEffectiveHostPort()
+			if other := m.state.PortInUse(nodeID, hostPort, uuid.Nil); other != nil {
+				m.state.AddNotification(fmt.Sprintf("Port %d is already used by site %s on this node", hostPort, other.Name), "error")
+				return m, nil
+			}
+		}
+	}
+
 	// Create new site
 	var site *models.Site
 	if isCompose {
 		// For compose: create site with empty docker image (compose handles containers)
-		site = models.NewSite(m.state.FormFields[0], firstDomainID, nodeID, "", firstPort)
+		site = models.NewSite(m.state.FormFields[0], firstDomainID, nodeIDs[0], "", firstPort)
 		site.SiteType = models.SiteTypeCompose
 		site.ComposeContent = composeContent
+	} else if m.state.IsBuildSource() {
+		// For container built from source: field 2 is the build context, field 3 the Dockerfile
+		site = models.NewSite(m.state.FormFields[0], firstDomainID, nodeIDs[0], "", firstPort)
+		site.SiteType = models.SiteTypeContainer
+		site.Build = &models.BuildSpec{
+			Context:    strings.TrimSpace(m.state.FormFields[2]),
+			Dockerfile: strings.TrimSpace(m.state.FormFields[3]),
+		}
 	} else {
 		// For container: use docker image from field 2
-		site = models.NewSite(m.state.FormFields[0], firstDomainID, nodeID, m.state.FormFields[2], firstPort)
+		site = models.NewSite(m.state.FormFields[0], firstDomainID, nodeIDs[0], m.state.FormFields[2], firstPort)
 		site.SiteType = models.SiteTypeContainer
 	}
 
+	if err := site.ValidateImageSource(); err != nil {
+		m.state.AddNotification(err.Error(), "error")
+		return m, nil
+	}
+
 	// Replace default domain mapping with all mappings from the form
 	site.DomainMappings = domainMappings
+	site.SetNodeIDs(nodeIDs)
+	site.SSLEnabled = m.state.SiteSSLEnabled
+	site.AutoRestart = m.state.SiteAutoRestart
+	if m.state.SiteBlueGreen && !site.IsCompose() {
+		site.Strategy = models.StrategyBlueGreen
+	}
+	for _, nodeID := range nodeIDs {
+		if node := m.state.GetNodeByID(nodeID); node != nil {
+			if err := site.ValidateDeploymentStrategy(node.ProxyType); err != nil {
+				m.state.AddNotification(err.Error(), "error")
+				return m, nil
+			}
+		}
+	}
 
 	// Set SSL email (field 5) if provided
 	if m.state.FormFields[5] != "" {
 		site.SSLEmail = strings.TrimSpace(m.state.FormFields[5])
 	}
 
+	// Set tags (field 7)
+	site.Tags = models.ParseTags(m.state.FormFields[7])
+
+	// Set cert resolver (field 8), keeping the default when left blank
+	if resolver := strings.TrimSpace(m.state.FormFields[8]); resolver != "" {
+		site.CertResolver = resolver
+	}
+
+	// Set extra hostnames (field 9)
+	site.ExtraHosts = models.ParseHostnames(m.state.FormFields[9])
+
+	// Set health check (fields 14, 15); a blank path means no health check
+	healthPath, err := models.ParseHealthPath(m.state.FormFields[14])
+	if err != nil {
+		m.state.AddNotification(err.Error(), "error")
+		return m, nil
+	}
+	healthStatus, err := models.ParseHealthStatus(m.state.FormFields[15])
+	if err != nil {
+		m.state.AddNotification(err.Error(), "error")
+		return m, nil
+	}
+	site.HealthPath = healthPath
+	site.HealthStatus = healthStatus
+
 	// For container deployments: parse environment variables and config files
 	if !isCompose {
-		// Set Docker credentials (fields 3, 4)
-		if m.state.FormFields[3] != "" {
+		// Set resource limits (fields 10, 11); blank means unlimited
+		cpuLimit, err := models.ParseCPULimit(m.state.FormFields[10])
+		if err != nil {
+			m.state.AddNotification(err.Error(), "error")
+			return m, nil
+		}
+		memoryLimit, err := models.ParseMemoryLimit(m.state.FormFields[11])
+		if err != nil {
+			m.state.AddNotification(err.Error(), "error")
+			return m, nil
+		}
+		site.CPULimit = cpuLimit
+		site.MemoryLimitMB = memoryLimit
+
+		// Set volumes (field 12)
+		volumes, err := models.ParseVolumeMounts(m.state.FormFields[12])
+		if err != nil {
+			m.state.AddNotification(err.Error(), "error")
+			return m, nil
+		}
+		site.Volumes = volumes
+
+		// Set Docker credentials (fields 3, 4) when pulling a prebuilt image
+		if !site.IsBuild() && m.state.FormFields[3] != "" {
 			site.DockerUsername = strings.TrimSpace(m.state.FormFields[3])
 		}
-		if m.state.FormFields[4] != "" {
+		if !site.IsBuild() && m.state.FormFields[4] != "" {
 			site.DockerToken = strings.TrimSpace(m.state.FormFields[4])
 		}
+		if !site.IsBuild() {
+			site.DockerRegistry = strings.TrimSpace(m.state.FormFields[13])
+		}
 
 		// Parse environment variables from EnvVarPairs
-		for _, pair := range m.state.EnvVarPairs {
-			key := strings.TrimSpace(pair.Key)
-			value := strings.TrimSpace(pair.Value)
-			if key != "" {
-				site.EnvironmentVars[key] = value
-			}
+		envVars, err := m.state.EnvironmentVars()
+		if err != nil {
+			m.state.AddNotification(err.Error(), "error")
+			return m, nil
 		}
+		site.EnvironmentVars = envVars
+
+		// Config files from the config files screen
+		site.ConfigFiles = append(site.ConfigFiles, m.state.ConfigFileDrafts...)
 
 		// Load config file (field 6) if provided
 		if m.state.FormFields[6] != "" {
@@ -2081,17 +3572,14 @@ func (m Model) handleSiteCreateSubmit() (tea.Model, tea.Cmd) {
 		siteTypeLabel = "Compose"
 	}
 	m.state.AddNotification(fmt.Sprintf("%s site created: %s", siteTypeLabel, site.Name), "success")
+	m.recordAudit("create_site", "site", site.ID, site.Name, nil)
 
 	// Auto-save config if enabled
-	if m.state.AutoSave {
-		go func() {
-			_ = m.saveConfigSync()
-		}()
-	}
+	save := m.configEdited()
 
 	m.state.NavigateBack()
 
-	return m, nil
+	return m, save
 }
 
 // handleSiteEditSubmit processes site edit form submission
@@ -2121,27 +3609,31 @@ func (m Model) handleSiteEditSubmit() (tea.Model, tea.Cmd) {
 		m.state.AddNotification("Required field Name must be filled", "error")
 		return m, nil
 	}
+	if m.state.SiteNameTaken(m.state.FormFields[0], m.state.Sites[siteIndex].ID) {
+		m.state.AddNotification("Site already exists: "+m.state.FormFields[0], "error")
+		return m, nil
+	}
 	if m.state.FormFields[1] == "" {
 		m.state.AddNotification("Required field Node must be filled", "error")
 		return m, nil
 	}
 	if !isCompose && m.state.FormFields[2] == "" {
-		m.state.AddNotification("Required field Docker Image must be filled", "error")
+		if m.state.IsBuildSource() {
+			m.state.AddNotification("Required field Build Context must be filled", "error")
+		} else {
+			m.state.AddNotification("Required field Docker Image must be filled", "error")
+		}
 		return m, nil
 	}
 
-	// Find node by name (index 1)
-	var nodeID uuid.UUID
-	nodeFound := false
-	for _, node := range m.state.Nodes {
-		if node.Name == m.state.FormFields[1] {
-			nodeID = node.ID
-			nodeFound = true
-			break
-		}
+	// Find nodes by name (index 1): the primary node, then any replicas
+	nodeIDs, err := m.state.NodeIDsByName(m.state.FormFields[1])
+	if err != nil {
+		m.state.AddNotification(err.Error(), "error")
+		return m, nil
 	}
-	if !nodeFound {
-		m.state.AddNotification("Node not found: "+m.state.FormFields[1], "error")
+	if len(nodeIDs) == 0 {
+		m.state.AddNotification("Required field Node must be filled", "error")
 		return m, nil
 	}
 
@@ -2196,34 +3688,113 @@ func (m Model) handleSiteEditSubmit() (tea.Model, tea.Cmd) {
 		return m, nil
 	}
 
+	// Refuse host ports another site on any of the nodes already publishes
+	for _, nodeID := range nodeIDs {
+		for _, mapping := range domainMappings {
+			hostPort := mapping.GetEffectiveHostPort()
+			if other := m.state.PortInUse(nodeID, hostPort, m.state.Sites[siteIndex].ID); other != nil {
+				m.state.AddNotification(fmt.Sprintf("Port %d is already used by site %s on this node", hostPort, other.Name), "error")
+				return m, nil
+			}
+		}
+	}
+
+	// Parse resource limits (fields 10, 11), volumes (field 12) and the health check (fields 14, 15) before changing anything
+	cpuLimit, err := models.ParseCPULimit(m.state.FormFields[10])
+	if err != nil {
+		m.state.AddNotification(err.Error(), "error")
+		return m, nil
+	}
+	memoryLimit, err := models.ParseMemoryLimit(m.state.FormFields[11])
+	if err != nil {
+		m.state.AddNotification(err.Error(), "error")
+		return m, nil
+	}
+	volumes, err := models.ParseVolumeMounts(m.state.FormFields[12])
+	if err != nil {
+		m.state.AddNotification(err.Error(), "error")
+		return m, nil
+	}
+	healthPath, err := models.ParseHealthPath(m.state.FormFields[14])
+	if err != nil {
+		m.state.AddNotification(err.Error(), "error")
+		return m, nil
+	}
+	healthStatus, err := models.ParseHealthStatus(m.state.FormFields[15])
+	if err != nil {
+		m.state.AddNotification(err.Error(), "error")
+		return m, nil
+	}
+	envVars, err := m.state.EnvironmentVars()
+	if err != nil {
+		m.state.AddNotification(err.Error(), "error")
+		return m, nil
+	}
+	var strategy models.DeploymentStrategy
+	if m.state.SiteBlueGreen && !m.state.Sites[siteIndex].IsCompose() {
+		strategy = models.StrategyBlueGreen
+		edited := m.state.Sites[siteIndex]
+		edited.Strategy = strategy
+		for _, nodeID := range nodeIDs {
+			if node := m.state.GetNodeByID(nodeID); node != nil {
+				if err := edited.ValidateDeploymentStrategy(node.ProxyType); err != nil {
+					m.state.AddNotification(err.Error(), "error")
+					return m, nil
+				}
+			}
+		}
+	}
+
 	// Update common site fields
 	oldName := m.state.Sites[siteIndex].Name
 	m.state.Sites[siteIndex].Name = m.state.FormFields[0]
 	m.state.Sites[siteIndex].DomainID = firstDomainID
-	m.state.Sites[siteIndex].NodeID = nodeID
+	m.state.Sites[siteIndex].SetNodeIDs(nodeIDs)
 	m.state.Sites[siteIndex].Port = firstPort
-	m.state.Sites[siteIndex].SSLEmail = strings.TrimSpace(m.state.FormFields[5]) // SSL Email at index 5
+	m.state.Sites[siteIndex].SSLEnabled = m.state.SiteSSLEnabled
+	m.state.Sites[siteIndex].AutoRestart = m.state.SiteAutoRestart
+	m.state.Sites[siteIndex].Strategy = strategy
+	m.state.Sites[siteIndex].SSLEmail = strings.TrimSpace(m.state.FormFields[5])       // SSL Email at index 5
+	m.state.Sites[siteIndex].Tags = models.ParseTags(m.state.FormFields[7])            // Tags at index 7
+	m.state.Sites[siteIndex].CertResolver = strings.TrimSpace(m.state.FormFields[8])   // Cert resolver at index 8 (blank uses the default)
+	m.state.Sites[siteIndex].ExtraHosts = models.ParseHostnames(m.state.FormFields[9]) // Extra hostnames at index 9
+	m.state.Sites[siteIndex].HealthPath = healthPath                                   // Health check path at index 14
+	m.state.Sites[siteIndex].HealthStatus = healthStatus                               // Expected health status at index 15
 
 	// Update domain mappings with all mappings from form
 	m.state.Sites[siteIndex].DomainMappings = domainMappings
 
 	// For container deployments: update docker-specific fields
 	if !isCompose {
-		m.state.Sites[siteIndex].DockerImage = m.state.FormFields[2]                     // Docker Image at index 2
-		m.state.Sites[siteIndex].DockerUsername = strings.TrimSpace(m.state.FormFields[3]) // Docker Username at index 3
-		m.state.Sites[siteIndex].DockerToken = strings.TrimSpace(m.state.FormFields[4])    // Docker Token at index 4
-
-		// Update environment variables from EnvVarPairs
-		m.state.Sites[siteIndex].EnvironmentVars = make(map[string]string)
-		for _, pair := range m.state.EnvVarPairs {
-			key := strings.TrimSpace(pair.Key)
-			value := strings.TrimSpace(pair.Value)
-			if key != "" {
-				m.state.Sites[siteIndex].EnvironmentVars[key] = value
+		m.state.Sites[siteIndex].CPULimit = cpuLimit // Resource limits at indexes 10 and 11
+		m.state.Sites[siteIndex].MemoryLimitMB = memoryLimit
+		m.state.Sites[siteIndex].Volumes = volumes // Volumes at index 12
+
+		if m.state.IsBuildSource() {
+			// Build context at index 2, Dockerfile at index 3
+			m.state.Sites[siteIndex].DockerImage = ""
+			m.state.Sites[siteIndex].DockerUsername = ""
+			m.state.Sites[siteIndex].DockerToken = ""
+			m.state.Sites[siteIndex].DockerRegistry = ""
+			m.state.Sites[siteIndex].Build = &models.BuildSpec{
+				Context:    strings.TrimSpace(m.state.FormFields[2]),
+				Dockerfile: strings.TrimSpace(m.state.FormFields[3]),
 			}
+		} else {
+			m.state.Sites[siteIndex].DockerImage = m.state.FormFields[2]                        // Docker Image at index 2
+			m.state.Sites[siteIndex].DockerUsername = strings.TrimSpace(m.state.FormFields[3])  // Docker Username at index 3
+			m.state.Sites[siteIndex].DockerToken = strings.TrimSpace(m.state.FormFields[4])     // Docker Token at index 4
+			m.state.Sites[siteIndex].DockerRegistry = strings.TrimSpace(m.state.FormFields[13]) // Docker Registry at index 13 (blank infers it from the image)
+			m.state.Sites[siteIndex].Build = nil
 		}
 
-		// Load config file (field 6) if provided
+		// Update environment variables from EnvVarPairs
+		m.state.Sites[siteIndex].EnvironmentVars = envVars
+
+		// Config files from the config files screen
+		m.state.Sites[siteIndex].ConfigFiles = append([]models.ConfigFile{}, m.state.ConfigFileDrafts...)
+
+		// Load config file (field 6) if provided, replacing a file of the same name
 		if m.state.FormFields[6] != "" {
 			configPath := strings.TrimSpace(m.state.FormFields[6])
 			content, err := os.ReadFile(configPath)
@@ -2232,13 +3803,20 @@ func (m Model) handleSiteEditSubmit() (tea.Model, tea.Cmd) {
 			} else {
 				// Extract filename from path
 				filename := filepath.Base(configPath)
-				// Replace existing config files
-				m.state.Sites[siteIndex].ConfigFiles = []models.ConfigFile{
-					{
-						Name:          filename,
-						Content:       string(content),
-						ContainerPath: "/config/" + filename,
-					},
+				loaded := models.ConfigFile{
+					Name:          filename,
+					Content:       string(content),
+					ContainerPath: "/config/" + filename,
+				}
+				replaced := false
+				for i, file := range m.state.Sites[siteIndex].ConfigFiles {
+					if file.Name == filename {
+						m.state.Sites[siteIndex].ConfigFiles[i] = loaded
+						replaced = true
+					}
+				}
+				if !replaced {
+					m.state.Sites[siteIndex].ConfigFiles = append(m.state.Sites[siteIndex].ConfigFiles, loaded)
 				}
 			}
 		}
@@ -2259,62 +3837,75 @@ func (m Model) handleSiteEditSubmit() (tea.Model, tea.Cmd) {
 	}
 	changes = append(changes, "updated site configuration")
 
+	// Redeploy a running site so the node picks up the changes
+	site := m.state.Sites[siteIndex]
+	redeploy := site.Status == models.SiteStatusRunning
+	if redeploy {
+		changes = append(changes, "redeploying")
+	}
+
 	message := fmt.Sprintf("%s site updated: %s", siteTypeLabel, strings.Join(changes, ", "))
 	m.state.AddNotification(message, "success")
+	m.recordAudit("update_site", "site", site.ID, site.Name, nil)
 
 	// Auto-save config if enabled
-	if m.state.AutoSave {
-		go func() {
-			_ = m.saveConfigSync()
-		}()
-	}
+	save := m.configEdited()
 
 	m.state.NavigateBack()
 
-	return m, nil
+	if redeploy {
+		return m, tea.Batch(save, func() tea.Msg { return DeploySiteMsg{SiteID: site.ID} })
+	}
+	return m, save
 }
 
 // handleDomainCreateSubmit processes domain creation form submission
 func (m Model) handleDomainCreateSubmit() (tea.Model, tea.Cmd) {
 	// Fields: 0=domain name, 1=provider, 2=zone/hosted zone ID, 3=API token/access key, 4=secret key (route53 only)
-	domainName := m.state.FormFields[0]
+	domainName := strings.TrimSuffix(strings.TrimSpace(m.state.FormFields[0]), ".")
 	providerType := m.state.FormFields[1]
 
-	// Validate domain name is not empty
-	if domainName == "" {
-		m.state.AddNotification("Domain name is required", "error")
+	// Validate the domain name is a fully qualified name, e.g. example.com
+	if err := models.ValidateDomainName(domainName); err != nil {
+		m.state.AddNotification("Invalid domain: "+err.Error(), "error")
 		return m, nil
 	}
 
-	// Check for duplicates
-	for _, domain := range m.state.Domains {
-		if domain.Name == domainName {
-			m.state.AddNotification("Domain already exists: "+domainName, "error")
-			return m, nil
-		}
+	// Check for duplicates (case-insensitive, ignoring a trailing dot)
+	if m.state.DomainNameTaken(domainName, uuid.Nil) {
+		m.state.AddNotification("Domain already exists: "+domainName, "error")
+		return m, nil
 	}
 
 	// Create DNS provider based on type with configuration
 	var provider models.DnsProvider
 	switch providerType {
 	case "cloudflare":
-		zoneID := m.state.FormFields[2]
+		zoneID := strings.TrimSpace(m.state.FormFields[2])
 
-		// Validate required fields for Cloudflare (Zone ID only, API token is global)
-		if zoneID == "" {
-			m.state.AddNotification("Cloudflare Zone ID is required", "error")
+		// The API token is set in settings; without a Zone ID here, it is used to look the zone up
+		if m.state.CloudflareAPIToken == "" {
+			m.state.AddNotification("Cloudflare API Token is required: set it in Settings first", "error")
 			return m, nil
 		}
+		if zoneID == "" {
+			m.state.AddNotification("Looking up the Cloudflare zone for "+domainName+"...", "info")
+			return m, m.spawnLookupCloudflareZone(domainName)
+		}
 
 		provider = models.DnsProvider{
 			Type:   models.DnsProviderCloudflare,
 			ZoneID: zoneID,
 			// APIToken is stored globally in settings, not per-domain
 		}
+
+		// A token that can't edit the zone's DNS would only fail at the first sync, so check it before saving
+		m.state.AddNotification("Checking the Cloudflare API token can edit DNS for "+domainName+"...", "info")
+		return m, m.spawnVerifyCloudflareDomain(domainName, provider)
 	case "route53":
-		hostedZoneID := m.state.FormFields[2]
-		accessKey := m.state.FormFields[3]
-		secretKey := m.state.FormFields[4]
+		hostedZoneID := strings.TrimSpace(m.state.FormFields[2])
+		accessKey := strings.TrimSpace(m.state.FormFields[3])
+		secretKey := strings.TrimSpace(m.state.FormFields[4])
 
 		// Validate required fields for Route53
 		if hostedZoneID == "" || accessKey == "" || secretKey == "" {
@@ -2332,32 +3923,34 @@ func (m Model) handleDomainCreateSubmit() (tea.Model, tea.Cmd) {
 		provider = models.DnsProvider{Type: models.DnsProviderManual}
 	}
 
+	return m.createDomain(domainName, provider)
+}
+
+// createDomain saves a domain from the create form once its fields are checked
+func (m Model) createDomain(domainName string, provider models.DnsProvider) (tea.Model, tea.Cmd) {
 	domain := models.NewDomain(domainName, provider)
 	m.state.Domains = append(m.state.Domains, *domain)
 
 	m.state.AddNotification("Domain created: "+domainName+" ("+domain.ProviderName()+")", "success")
+	m.recordAudit("create_domain", "domain", domain.ID, domain.Name, nil)
 
 	// Auto-save config if enabled
-	if m.state.AutoSave {
-		go func() {
-			_ = m.saveConfigSync()
-		}()
-	}
+	save := m.configEdited()
 
 	m.state.NavigateBack()
 
-	return m, nil
+	return m, save
 }
 
 // handleDomainEditSubmit processes domain edit form submission
 func (m Model) handleDomainEditSubmit() (tea.Model, tea.Cmd) {
 	// Fields: 0=domain name, 1=provider, 2=zone/hosted zone ID, 3=API token/access key, 4=secret key (route53 only)
-	newDomainName := m.state.FormFields[0]
+	newDomainName := strings.TrimSuffix(strings.TrimSpace(m.state.FormFields[0]), ".")
 	providerType := m.state.FormFields[1]
 
-	// Validate domain name is not empty
-	if newDomainName == "" {
-		m.state.AddNotification("Domain name is required", "error")
+	// Validate the domain name is a fully qualified name, e.g. example.com
+	if err := models.ValidateDomainName(newDomainName); err != nil {
+		m.state.AddNotification("Invalid domain: "+err.Error(), "error")
 		return m, nil
 	}
 
@@ -2380,11 +3973,9 @@ func (m Model) handleDomainEditSubmit() (tea.Model, tea.Cmd) {
 	oldProviderType := string(m.state.Domains[domainIndex].DnsProvider.Type)
 
 	// Check for duplicates (excluding current domain)
-	for i, domain := range m.state.Domains {
-		if i != domainIndex && domain.Name == newDomainName {
-			m.state.AddNotification("Domain already exists: "+newDomainName, "error")
-			return m, nil
-		}
+	if m.state.DomainNameTaken(newDomainName, m.state.Domains[domainIndex].ID) {
+		m.state.AddNotification("Domain already exists: "+newDomainName, "error")
+		return m, nil
 	}
 
 	// Update domain name
@@ -2458,17 +4049,14 @@ func (m Model) handleDomainEditSubmit() (tea.Model, tea.Cmd) {
 		message = "Domain updated (no changes)"
 	}
 	m.state.AddNotification(message, "success")
+	m.recordAudit("update_domain", "domain", m.state.Domains[domainIndex].ID, m.state.Domains[domainIndex].Name, nil)
 
 	// Auto-save config if enabled
-	if m.state.AutoSave {
-		go func() {
-			_ = m.saveConfigSync()
-		}()
-	}
+	save := m.configEdited()
 
 	m.state.NavigateBack()
 
-	return m, nil
+	return m, save
 }
 
 // handleSettingsSave processes settings form submission
@@ -2479,17 +4067,14 @@ func (m Model) handleSettingsSave() (tea.Model, tea.Cmd) {
 	m.state.Route53SecretKey = m.state.FormFields[2]
 
 	m.state.AddNotification("Settings saved successfully", "success")
+	m.recordAudit("update_settings", "settings", uuid.Nil, "", nil)
 
 	// Auto-save config if enabled
-	if m.state.AutoSave {
-		go func() {
-			_ = m.saveConfigSync()
-		}()
-	}
+	save := m.configEdited()
 
 	m.state.NavigateBack()
 
-	return m, nil
+	return m, save
 }
 
 // handleNodeCreateSubmit processes node creation form submission
@@ -2504,23 +4089,16 @@ func (m Model) handleNodeCreateSubmit() (tea.Model, tea.Cmd) {
 		return m, nil
 	}
 
-	// Try to extract IP from API endpoint, or use placeholder
-	endpoint := m.state.FormFields[1]
-	var ip net.IP
-
-	// Try to extract hostname from URL
-	if strings.Contains(endpoint, "://") {
-		// Parse as URL
-		parts := strings.Split(endpoint, "://")
-		if len(parts) > 1 {
-			host := strings.Split(parts[1], ":")[0] // Remove port if present
-			ip = net.ParseIP(host)
-		}
-	} else {
-		// Try direct IP parse
-		ip = net.ParseIP(endpoint)
+	// Validate and normalize the API endpoint
+	endpoint, err := models.NormalizeEndpoint(m.state.FormFields[1])
+	if err != nil {
+		m.state.AddNotification("Invalid API Endpoint: "+err.Error(), "error")
+		return m, nil
 	}
 
+	// Try to extract IP (v4 or v6) from API endpoint, or use placeholder
+	ip := models.EndpointIP(endpoint)
+
 	// If still no valid IP, use placeholder
 	if ip == nil {
 		ip = net.ParseIP("0.0.0.0")
@@ -2551,12 +4129,23 @@ func (m Model) handleNodeCreateSubmit() (tea.Model, tea.Cmd) {
 	// Create new node with proxy type and generated API key
 	node := models.NewNode(
 		m.state.FormFields[0], // name
-		m.state.FormFields[1], // endpoint
-		m.state.FormFields[3], // generated api key (now field 3)
+		endpoint,              // normalized endpoint
+		m.state.FormFields[5], // generated api key (now field 5)
 		ip,
 		proxyType, // proxy type
 	)
 
+	// Set public IP (field 3) if provided
+	if publicIPStr := strings.TrimSpace(m.state.FormFields[3]); publicIPStr != "" {
+		publicIP := net.ParseIP(publicIPStr)
+		if publicIP == nil {
+			m.state.AddNotification("Invalid public IP address: "+publicIPStr, "error")
+			return m, nil
+		}
+		node.PublicIP = publicIP
+	}
+	node.Tags = models.ParseTags(m.state.FormFields[4])
+
 	m.state.Nodes = append(m.state.Nodes, *node)
 
 	// Get proxy label for notification
@@ -2571,19 +4160,22 @@ func (m Model) handleNodeCreateSubmit() (tea.Model, tea.Cmd) {
 	}
 
 	m.state.AddNotification(fmt.Sprintf("Node created: %s (%s, API Key: %s)", node.Name, proxyLabel, node.APIKey), "success")
+	m.recordAudit("create_node", "node", node.ID, node.Name, nil)
 
 	// Auto-save config if enabled
-	if m.state.AutoSave {
-		go func() {
-			_ = m.saveConfigSync()
-		}()
+	save := m.configEdited()
+
+	// From the setup wizard, going back from the config screen should return to the wizard
+	if n := len(m.state.PreviousScreens); n > 0 && m.state.PreviousScreens[n-1] == state.ScreenSetup {
+		m.state.NavigateBack()
 	}
 
 	// Set selected node and navigate to config screen
 	m.state.SelectedNodeID = node.ID
 	m.state.NavigateTo(state.ScreenNodeConfig)
 
-	return m, nil
+	// Check straight away whether the node is reachable (it won't be until the node server is installed)
+	return m, tea.Batch(save, m.spawnSavedNodeHealthCheck(node.ID))
 }
 
 // handleNodeEditSubmit processes node edit form submission
@@ -2613,10 +4205,27 @@ func (m Model) handleNodeEditSubmit() (tea.Model, tea.Cmd) {
 		return m, nil
 	}
 
+	// Validate and normalize the API endpoint
+	endpoint, err := models.NormalizeEndpoint(m.state.FormFields[1])
+	if err != nil {
+		m.state.AddNotification("Invalid API Endpoint: "+err.Error(), "error")
+		return m, nil
+	}
+
 	oldName := m.state.Nodes[nodeIndex].Name
 	oldEndpoint := m.state.Nodes[nodeIndex].APIEndpoint
 	oldProxyType := string(m.state.Nodes[nodeIndex].ProxyType)
 
+	// Parse public IP (field 3), blank clears it
+	var publicIP net.IP
+	if publicIPStr := strings.TrimSpace(m.state.FormFields[3]); publicIPStr != "" {
+		publicIP = net.ParseIP(publicIPStr)
+		if publicIP == nil {
+			m.state.AddNotification("Invalid public IP address: "+publicIPStr, "error")
+			return m, nil
+		}
+	}
+
 	// Check for duplicate name (excluding current node)
 	for i, node := range m.state.Nodes {
 		if i != nodeIndex && node.Name == m.state.FormFields[0] {
@@ -2627,7 +4236,7 @@ func (m Model) handleNodeEditSubmit() (tea.Model, tea.Cmd) {
 
 	// Update node fields
 	m.state.Nodes[nodeIndex].Name = m.state.FormFields[0]
-	m.state.Nodes[nodeIndex].APIEndpoint = m.state.FormFields[1]
+	m.state.Nodes[nodeIndex].APIEndpoint = endpoint
 
 	// Parse and update proxy type
 	proxyTypeStr := m.state.FormFields[2]
@@ -2644,29 +4253,23 @@ func (m Model) handleNodeEditSubmit() (tea.Model, tea.Cmd) {
 	}
 	m.state.Nodes[nodeIndex].ProxyType = proxyType
 
-	// Try to extract IP from API endpoint
-	endpoint := m.state.FormFields[1]
-	var ip net.IP
-	if strings.Contains(endpoint, "://") {
-		parts := strings.Split(endpoint, "://")
-		if len(parts) > 1 {
-			host := strings.Split(parts[1], ":")[0]
-			ip = net.ParseIP(host)
-		}
-	} else {
-		ip = net.ParseIP(endpoint)
-	}
-	if ip != nil {
+	// Try to extract IP (v4 or v6) from API endpoint
+	if ip := models.EndpointIP(endpoint); ip != nil {
 		m.state.Nodes[nodeIndex].IPAddress = ip
 	}
+	oldPublicIP := m.state.Nodes[nodeIndex].PublicIP
+	m.state.Nodes[nodeIndex].PublicIP = publicIP
+	oldTags := models.FormatTags(m.state.Nodes[nodeIndex].Tags)
+	m.state.Nodes[nodeIndex].Tags = models.ParseTags(m.state.FormFields[4])
+	newTags := models.FormatTags(m.state.Nodes[nodeIndex].Tags)
 
 	// Build notification message
 	var changes []string
 	if oldName != m.state.FormFields[0] {
 		changes = append(changes, fmt.Sprintf("name: %s → %s", oldName, m.state.FormFields[0]))
 	}
-	if oldEndpoint != m.state.FormFields[1] {
-		changes = append(changes, fmt.Sprintf("endpoint: %s → %s", oldEndpoint, m.state.FormFields[1]))
+	if oldEndpoint != endpoint {
+		changes = append(changes, fmt.Sprintf("endpoint: %s → %s", oldEndpoint, endpoint))
 	}
 	if oldProxyType != proxyTypeStr {
 		proxyLabels := map[string]string{
@@ -2684,6 +4287,12 @@ func (m Model) handleNodeEditSubmit() (tea.Model, tea.Cmd) {
 		}
 		changes = append(changes, fmt.Sprintf("proxy: %s → %s", oldLabel, newLabel))
 	}
+	if !oldPublicIP.Equal(publicIP) {
+		changes = append(changes, fmt.Sprintf("public IP: %s → %s", formatOptionalIP(oldPublicIP), formatOptionalIP(publicIP)))
+	}
+	if oldTags != newTags {
+		changes = append(changes, fmt.Sprintf("tags: %s → %s", formatOptionalTags(oldTags), formatOptionalTags(newTags)))
+	}
 
 	var message string
 	if len(changes) > 0 {
@@ -2692,17 +4301,34 @@ func (m Model) handleNodeEditSubmit() (tea.Model, tea.Cmd) {
 		message = "Node updated (no changes)"
 	}
 	m.state.AddNotification(message, "success")
+	m.recordAudit("update_node", "node", m.state.Nodes[nodeIndex].ID, m.state.Nodes[nodeIndex].Name, nil)
 
 	// Auto-save config if enabled
-	if m.state.AutoSave {
-		go func() {
-			_ = m.saveConfigSync()
-		}()
-	}
+	save := m.configEdited()
 
 	m.state.NavigateBack()
 
-	return m, nil
+	// Check the node is reachable at its new endpoint
+	if oldEndpoint != endpoint {
+		return m, tea.Batch(save, m.spawnSavedNodeHealthCheck(m.state.Nodes[nodeIndex].ID))
+	}
+	return m, save
+}
+
+// formatOptionalIP formats an optional IP for display, showing "(none)" when unset
+func formatOptionalIP(ip net.IP) string {
+	if len(ip) == 0 {
+		return "(none)"
+	}
+	return ip.String()
+}
+
+// formatOptionalTags formats a tag list for change notifications, showing "(none)" when empty
+func formatOptionalTags(tags string) string {
+	if tags == "" {
+		return "(none)"
+	}
+	return tags
 }
 
 // ============================================================================
@@ -2726,6 +4352,7 @@ func (m Model) handleTabClick(zoneID string) (tea.Model, tea.Cmd) {
 	case "settings":
 		m.state.NavigateTo(state.ScreenSettings)
 	case "help":
+		m.state.HelpScroll = 0
 		m.state.NavigateTo(state.ScreenHelp)
 	}
 
@@ -2752,29 +4379,201 @@ func (m Model) handleFieldClick(zoneID string) (tea.Model, tea.Cmd) {
 
 // saveConfigSync synchronously saves the current state to config file
 func (m Model) saveConfigSync() error {
-	cfg := &config.Config{
+	m.saves.mu.Lock()
+	defer m.saves.mu.Unlock()
+	if err := m.configLoader.Save(m.configPath, m.currentConfig()); err != nil {
+		return err
+	}
+	// Background saves still running hold older changes
+	m.saves.written = m.saves.started
+	m.markConfigSynced()
+	m.state.Dirty = false
+	return nil
+}
+
+// configEdited records a change to the sites, domains, nodes or settings: with auto-save on,
+// it returns the command saving it, and otherwise it is left as an unsaved change
+func (m Model) configEdited() tea.Cmd {
+	if m.state.AutoSave {
+		return m.saveConfig()
+	}
+	m.state.Dirty = true
+	return nil
+}
+
+// currentConfig builds the config to save from the current state
+func (m Model) currentConfig() *config.Config {
+	return &config.Config{
 		Version:  "1.0.0",
 		Sites:    m.state.Sites,
 		Domains:  m.state.Domains,
 		Nodes:    m.state.Nodes,
 		Settings: config.Settings{
 			AutoSave:                m.state.AutoSave,
-			HealthCheckIntervalSecs: 60,
+			HealthCheckIntervalSecs: m.state.HealthCheckIntervalSecs,
 			DefaultDnsTTL:           3600,
-			Theme:                   "default",
+			Theme:                   m.state.ThemeName,
+			ThemeFile:               m.state.ThemeFile,
 			CloudflareAPIToken:      m.state.CloudflareAPIToken,
 			Route53AccessKey:        m.state.Route53AccessKey,
 			Route53SecretKey:        m.state.Route53SecretKey,
+			DebugHTTP:               m.state.DebugHTTP,
+			LogBufferLines:          m.state.LogBufferLines,
+			RetryMaxAttempts:        m.state.RetryMaxAttempts,
+			RetryBaseDelayMs:        m.state.RetryBaseDelayMs,
+			RetryJitter:             m.state.RetryJitter,
+			DNSRateLimitRetries:     m.state.DNSRateLimitRetries,
+			MaxConcurrentOperations: m.state.MaxConcurrentOperations,
+			PortRangeStart:          m.state.PortRangeStart,
+			PortRangeEnd:            m.state.PortRangeEnd,
+			LatencyWarnMs:           m.state.LatencyWarnMs,
+			LatencyCriticalMs:       m.state.LatencyCriticalMs,
+			ConfigBackups:           m.state.ConfigBackups,
+			SecretStorage:           m.state.SecretStorage,
+			AuditLog:                m.state.AuditLog,
+			AuditLogMaxMB:           m.state.AuditLogMaxMB,
+			DesktopNotifications:    m.state.DesktopNotifications,
+			WebhookURL:              m.state.WebhookURL,
+			Keybindings:             m.state.Keybindings,
 		},
 	}
-
-	return m.configLoader.Save(m.configPath, cfg)
 }
 
 // ============================================================================
 // Delete Handlers
 // ============================================================================
 
+// dnsPruneConfirmKind is the confirmation kind for pushing DNS records with provider-only records deleted
+const dnsPruneConfirmKind = "DNS records"
+
+// dnsRecordConfirmKind is the confirmation kind for deleting the selected DNS record of the selected domain
+const dnsRecordConfirmKind = "DNS record"
+
+// copyToClipboard copies value to the system clipboard. Where there is no clipboard (e.g.
+// over SSH) the value is shown in the notification instead, so it can be copied by hand.
+func (m Model) copyToClipboard(label, value string) (tea.Model, tea.Cmd) {
+	if err := desktop.CopyText(value); err != nil {
+		m.state.AddNotification(fmt.Sprintf("Couldn't copy %s (%v): %s", label, err, value), "warning")
+		return m, nil
+	}
+	m.state.AddNotification(fmt.Sprintf("Copied %s to the clipboard: %s", label, value), "success")
+	return m, nil
+}
+
+// requestDelete asks the user to confirm a delete before it is carried out
+func (m Model) requestDelete(kind string, id uuid.UUID, name string) (tea.Model, tea.Cmd) {
+	m.state.PendingConfirmation = &state.Confirmation{Kind: kind, TargetID: id, Name: name}
+	return m, nil
+}
+
+// rollbackConfirmKind is the confirmation kind for redeploying a site's earlier image, named by the confirmation
+const rollbackConfirmKind = "rollback"
+
+// quitConfirmKind is the confirmation kind for quitting with unsaved changes
+const quitConfirmKind = "quit"
+
+// quitWaitKind is the confirmation kind shown while a quit waits for pending operations
+const quitWaitKind = "quit_wait"
+
+// quitWaitConfirmation is the dialog shown while a quit waits for pending operations
+func quitWaitConfirmation(pending int) *state.Confirmation {
+	operations := "operations"
+	if pending == 1 {
+		operations = "operation"
+	}
+	return &state.Confirmation{
+		Kind:    quitWaitKind,
+		Title:   fmt.Sprintf("Waiting for %d %s to finish…", pending, operations),
+		Message: "Archon quits once they're done, so deploys aren't left half-finished.",
+		Keys:    "Press f or Ctrl+C to quit now and abandon them • Esc to keep working",
+	}
+}
+
+// handleConfirmationKeys answers the pending confirmation dialog: y confirms, n or Esc cancels
+func (m Model) handleConfirmationKeys(msg tea.KeyMsg) (tea.Model, tea.Cmd) {
+	pending := m.state.PendingConfirmation
+	if pending.Kind == quitConfirmKind {
+		return m.handleQuitConfirmationKeys(msg)
+	}
+	if pending.Kind == quitWaitKind {
+		return m.handleQuitWaitKeys(msg)
+	}
+
+	switch msg.String() {
+	case "y", "Y":
+		m.state.PendingConfirmation = nil
+		switch pending.Kind {
+		case "site":
+			return m.handleDeleteSite(pending.TargetID)
+		case "domain":
+			return m.handleDeleteDomain(pending.TargetID)
+		case "node":
+			return m.handleDeleteNode(pending.TargetID)
+		case dnsRecordConfirmKind:
+			return m.handleDeleteDnsRecord(pending.TargetID, m.state.DnsRecordsIndex)
+		case dnsPruneConfirmKind:
+			m.state.AddNotification("Pushing DNS records and deleting provider-only records", "info")
+			return m, func() tea.Msg { return PushDnsRecordsMsg{DomainID: pending.TargetID, DeleteRemote: true} }
+		case configReloadConfirmKind:
+			return m, m.spawnReloadConfig()
+		case rollbackConfirmKind:
+			return m, func() tea.Msg { return RollbackSiteMsg{SiteID: pending.TargetID, Image: pending.Name} }
+		}
+		return m, nil
+
+	case "n", "N", "esc":
+		m.state.PendingConfirmation = nil
+		if pending.Kind == configReloadConfirmKind {
+			m.state.AddNotification("Kept unsaved changes; saving will overwrite the edited config", "warning")
+			return m, nil
+		}
+		if pending.Kind == rollbackConfirmKind {
+			m.state.AddNotification("Rollback cancelled", "info")
+			return m, nil
+		}
+		m.state.AddNotification("Delete cancelled", "info")
+		return m, nil
+	}
+
+	// Ignore everything else while the dialog is open
+	return m, nil
+}
+
+// handleQuitConfirmationKeys answers the unsaved changes prompt on quit: s saves and quits,
+// d quits without saving, n or Esc stays
+func (m Model) handleQuitConfirmationKeys(msg tea.KeyMsg) (tea.Model, tea.Cmd) {
+	switch msg.String() {
+	case "s", "S", "y", "Y":
+		// Saved when quitting, after any pending operations finish
+		m.state.PendingConfirmation = nil
+		return m, func() tea.Msg { return QuitMsg{Save: true} }
+	case "d", "D":
+		m.state.PendingConfirmation = nil
+		return m, func() tea.Msg { return QuitMsg{} }
+	case "n", "N", "esc":
+		m.state.PendingConfirmation = nil
+		return m, nil
+	}
+	return m, nil
+}
+
+// handleQuitWaitKeys answers the dialog shown while a quit waits for pending operations:
+// f quits now, cancelling them, and n or Esc stays
+func (m Model) handleQuitWaitKeys(msg tea.KeyMsg) (tea.Model, tea.Cmd) {
+	switch msg.String() {
+	case "f", "F":
+		m.state.PendingConfirmation = nil
+		quit := QuitMsg{Save: m.state.QuitSave, Force: true}
+		return m, func() tea.Msg { return quit }
+	case "n", "N", "esc":
+		m.state.PendingConfirmation = nil
+		m.state.QuitWaiting, m.state.QuitSave = false, false
+		m.state.AddNotification("Quit cancelled; operations keep running", "info")
+		return m, nil
+	}
+	return m, nil
+}
+
 // handleDeleteSite removes a site from the state and filesystem
 func (m Model) handleDeleteSite(siteID uuid.UUID) (tea.Model, tea.Cmd) {
 	// Find and remove site
@@ -2790,18 +4589,14 @@ func (m Model) handleDeleteSite(siteID uuid.UUID) (tea.Model, tea.Cmd) {
 				}
 			}
 
-			// Remove from slice
+			// Remove from slice, keeping a copy so it can be restored this session
 			m.state.Sites = append(m.state.Sites[:i], m.state.Sites[i+1:]...)
-			m.state.AddNotification("Deleted site: "+site.Name, "success")
+			m.state.RememberDeletedSite(site)
+			m.state.AddNotification("Deleted site: "+site.Name+" (press u on the sites list to restore)", "success")
+			m.recordAudit("delete_site", "site", site.ID, site.Name, nil)
 
 			// Auto-save config if enabled
-			if m.state.AutoSave {
-				go func() {
-					_ = m.saveConfigSync()
-				}()
-			}
-
-			return m, nil
+			return m, m.configEdited()
 		}
 	}
 
@@ -2811,12 +4606,12 @@ func (m Model) handleDeleteSite(siteID uuid.UUID) (tea.Model, tea.Cmd) {
 
 // handleDeleteDomain removes a domain from the state
 func (m Model) handleDeleteDomain(domainID uuid.UUID) (tea.Model, tea.Cmd) {
-	// Check if domain is used by any sites
-	for _, site := range m.state.Sites {
-		if site.DomainID == domainID {
-			m.state.AddNotification("Cannot delete domain: used by site "+site.Name, "error")
-			return m, nil
-		}
+	// Block deletion while any sites still reference the domain
+	if dependents := m.state.SitesForDomain(domainID); len(dependents) > 0 {
+		err := fmt.Errorf("used by %d site(s): %s", len(dependents), joinSiteNames(dependents))
+		m.state.AddNotification("Cannot delete domain: "+err.Error()+". Delete or reassign them first", "error")
+		m.recordAudit("delete_domain", "domain", domainID, m.domainName(domainID), err)
+		return m, nil
 	}
 
 	// Find and remove domain
@@ -2825,15 +4620,10 @@ func (m Model) handleDeleteDomain(domainID uuid.UUID) (tea.Model, tea.Cmd) {
 			// Remove from slice
 			m.state.Domains = append(m.state.Domains[:i], m.state.Domains[i+1:]...)
 			m.state.AddNotification("Deleted domain: "+domain.Name, "success")
+			m.recordAudit("delete_domain", "domain", domain.ID, domain.Name, nil)
 
 			// Auto-save config if enabled
-			if m.state.AutoSave {
-				go func() {
-					_ = m.saveConfigSync()
-				}()
-			}
-
-			return m, nil
+			return m, m.configEdited()
 		}
 	}
 
@@ -2843,12 +4633,16 @@ func (m Model) handleDeleteDomain(domainID uuid.UUID) (tea.Model, tea.Cmd) {
 
 // handleDeleteNode removes a node from the state and filesystem
 func (m Model) handleDeleteNode(nodeID uuid.UUID) (tea.Model, tea.Cmd) {
-	// Check if node is used by any sites
-	for _, site := range m.state.Sites {
-		if site.NodeID == nodeID {
-			m.state.AddNotification("Cannot delete node: used by site "+site.Name, "error")
-			return m, nil
-		}
+	// Block deletion while any sites are still bound to the node
+	if dependents := m.state.SitesForNode(nodeID); len(dependents) > 0 {
+		err := fmt.Errorf("used by %d site(s): %s", len(dependents), joinSiteNames(dependents))
+		m.state.AddNotification("Cannot delete node: "+err.Error()+". Delete or move them first", "error")
+		var name string
+		if node := m.state.GetNodeByID(nodeID); node != nil {
+			name = node.Name
+		}
+		m.recordAudit("delete_node", "node", nodeID, name, err)
+		return m, nil
 	}
 
 	// Find and remove node
@@ -2863,15 +4657,10 @@ func (m Model) handleDeleteNode(nodeID uuid.UUID) (tea.Model, tea.Cmd) {
 			// Remove from slice
 			m.state.Nodes = append(m.state.Nodes[:i], m.state.Nodes[i+1:]...)
 			m.state.AddNotification("Deleted node: "+node.Name, "success")
+			m.recordAudit("delete_node", "node", node.ID, node.Name, nil)
 
 			// Auto-save config if enabled
-			if m.state.AutoSave {
-				go func() {
-					_ = m.saveConfigSync()
-				}()
-			}
-
-			return m, nil
+			return m, m.configEdited()
 		}
 	}
 
@@ -2879,6 +4668,15 @@ func (m Model) handleDeleteNode(nodeID uuid.UUID) (tea.Model, tea.Cmd) {
 	return m, nil
 }
 
+// joinSiteNames formats site names as a comma-separated list
+func joinSiteNames(sites []models.Site) string {
+	names := make([]string, 0, len(sites))
+	for _, site := range sites {
+		names = append(names, site.Name)
+	}
+	return strings.Join(names, ", ")
+}
+
 // tryDetectComposePorts attempts to detect ports from the compose file and pre-populate domain mapping
 func (m *Model) tryDetectComposePorts() {
 	path := m.state.FormFields[2]
@@ -1,8 +1,12 @@
 package app
 
 import (
+	"time"
+
 	"github.com/google/uuid"
 	"github.com/BlueBeard63/archon/internal/api"
+	"github.com/BlueBeard63/archon/internal/config"
+	"github.com/BlueBeard63/archon/internal/dns"
 	"github.com/BlueBeard63/archon/internal/models"
 	"github.com/BlueBeard63/archon/internal/state"
 )
@@ -36,22 +40,51 @@ type DeleteSiteMsg struct {
 	SiteID uuid.UUID
 }
 
+// RestoreSiteMsg re-adds a recently deleted site and redeploys it
+type RestoreSiteMsg struct {
+	SiteID uuid.UUID
+}
+
+// RollbackSiteMsg redeploys a site with an earlier image, keeping its configured image
+type RollbackSiteMsg struct {
+	SiteID uuid.UUID
+	Image  string
+}
+
 // DeploySiteMsg triggers site deployment to its node
 type DeploySiteMsg struct {
 	SiteID uuid.UUID
+	Image  string // Deployed in place of the site's configured image when set, for a rollback
+}
+
+// PreviewDeployMsg shows the request a deploy would send for a site without sending it
+type PreviewDeployMsg struct {
+	SiteID uuid.UUID
 }
 
 // SiteDeployedMsg is returned after deployment completes
+// Error is set only if the deploy failed on every node; see partialFailure for the rest
 type SiteDeployedMsg struct {
-	SiteID uuid.UUID
-	Error  error
+	SiteID   uuid.UUID
+	Response *api.DeployResponse   // Sent by the first node deployed to successfully (nil from older nodes)
+	Replicas []ReplicaDeployResult // Outcome on each of the site's nodes, once deploying has started
+	Image    string                // Deployed in place of the configured image, for a rollback
+	Error    error
+}
+
+// ReplicaDeployResult is the outcome of deploying a site to one of its nodes
+type ReplicaDeployResult struct {
+	NodeID   uuid.UUID
+	NodeName string
+	Response *api.DeployResponse
+	Error    error
 }
 
 // SiteDeployProgressMsg is sent during deployment with progress updates
 type SiteDeployProgressMsg struct {
 	SiteID  uuid.UUID
 	Message string
-	Step    string // "init", "ssl", "docker", "proxy", "complete"
+	Step    string // "init", "ssl", "docker", "blue_green", "proxy", "complete"
 }
 
 // SetupDNSMsg triggers DNS record setup for a site
@@ -76,6 +109,39 @@ type RestartSiteMsg struct {
 	SiteID uuid.UUID
 }
 
+// StopAllOnNodeMsg stops every site on a node
+type StopAllOnNodeMsg struct {
+	NodeID uuid.UUID
+}
+
+// DeployAllOnNodeMsg deploys every site on a node
+type DeployAllOnNodeMsg struct {
+	NodeID uuid.UUID
+}
+
+// SiteStatusResultMsg is returned after fetching a site's live status from its node
+type SiteStatusResultMsg struct {
+	SiteID     uuid.UUID
+	Status     models.SiteStatus
+	Container  *api.SiteStatusResponse // The node's full response, with restarts and uptime
+	Error      error
+	Background bool // From the periodic auto-restart poll rather than a user action
+}
+
+// ResumeDeployMsg checks a deploy that was running when Archon last exited on its node
+type ResumeDeployMsg struct {
+	SiteID uuid.UUID
+}
+
+// DeployResumedMsg is returned with the node's status of a site whose deploy was running
+// when Archon last exited
+type DeployResumedMsg struct {
+	SiteID    uuid.UUID
+	Status    models.SiteStatus
+	StartedAt *time.Time // When the site's container last started, while it is running
+	Error     error
+}
+
 // SiteOperationResultMsg is returned after stop/restart operations
 type SiteOperationResultMsg struct {
 	SiteID    uuid.UUID
@@ -100,12 +166,32 @@ type DeleteDomainMsg struct {
 // SyncDnsMsg triggers DNS record synchronization with provider
 type SyncDnsMsg struct {
 	DomainID uuid.UUID
+	Preview  bool // Show the record diff for approval before applying
 }
 
 // DnsSyncedMsg is returned after DNS sync completes
 type DnsSyncedMsg struct {
 	DomainID uuid.UUID
 	Records  []models.DnsRecord
+	Preview  bool
+	Error    error
+}
+
+// ApplyDnsSyncMsg applies the pending DNS sync preview after the user approves it
+type ApplyDnsSyncMsg struct{}
+
+// PushDnsRecordsMsg pushes a domain's local DNS records to its provider
+type PushDnsRecordsMsg struct {
+	DomainID     uuid.UUID
+	DeleteRemote bool // Also delete provider records that don't exist locally
+}
+
+// DnsRecordsPushedMsg is returned after pushing DNS records completes
+type DnsRecordsPushedMsg struct {
+	DomainID uuid.UUID
+	Sent     []models.DnsRecord // Local records as they were when the push started
+	Records  []models.DnsRecord // Sent records with provider IDs and sync states updated
+	Outcomes []dns.PushOutcome
 	Error    error
 }
 
@@ -118,13 +204,14 @@ type CreateDnsRecordMsg struct {
 // UpdateDnsRecordMsg updates an existing DNS record
 type UpdateDnsRecordMsg struct {
 	DomainID uuid.UUID
+	Index    int // Position of the record in the domain's records; local-only records have no ID yet
 	Record   *models.DnsRecord
 }
 
 // DeleteDnsRecordMsg removes a DNS record
 type DeleteDnsRecordMsg struct {
 	DomainID uuid.UUID
-	RecordID string
+	Index    int // Position of the record in the domain's records
 }
 
 // DnsRecordOperationResultMsg is returned after DNS record operations
@@ -155,22 +242,75 @@ type NodeHealthCheckMsg struct {
 
 // NodeHealthCheckResultMsg is returned after health check completes
 type NodeHealthCheckResultMsg struct {
+	NodeID     uuid.UUID
+	Result     *api.HealthResponse
+	Error      error
+	Background bool // From the periodic health check rather than a user action
+	Saved      bool // Checked because the node was just created or its endpoint edited
+	Reconnect  bool // From the reconnect loop of an offline node
+}
+
+// NodeReconnectMsg health checks an offline node again once its backoff delay has passed
+type NodeReconnectMsg struct {
 	NodeID uuid.UUID
-	Result *api.HealthResponse
+}
+
+// TestNodeConnectionMsg health checks an endpoint and API key before they are saved as a node
+type TestNodeConnectionMsg struct {
+	Endpoint string
+	APIKey   string
+}
+
+// NodeConnectionTestedMsg is returned after a node connection test completes
+type NodeConnectionTestedMsg struct {
+	Endpoint  string
+	Result    *api.HealthResponse
+	LatencyMs int64
+	Error     error
+}
+
+// CloudflareZoneLookedUpMsg is returned after looking up the Cloudflare zone of a domain being created
+type CloudflareZoneLookedUpMsg struct {
+	Domain string
+	ZoneID string
 	Error  error
 }
 
+// CloudflareDomainVerifiedMsg is returned after checking the API token can edit DNS for a domain being created
+type CloudflareDomainVerifiedMsg struct {
+	Domain   string
+	Provider models.DnsProvider
+	Error    error
+}
+
 // FetchNodeLogsMsg retrieves logs from a site on a node
 type FetchNodeLogsMsg struct {
 	SiteID uuid.UUID
 	Lines  int
+	Build  bool // Fetch output of the last image build instead of container logs
 }
 
 // NodeLogsResultMsg is returned with log lines
 type NodeLogsResultMsg struct {
+	SiteID    uuid.UUID
+	Logs      []string
+	Build     bool
+	Since     string // Log cursor the lines were fetched after ("" = latest tail)
+	Cursor    string // Log cursor after the last line, to fetch from next time
+	FollowGen int    // state.FollowLogsGen of the follow that fetched the logs (0 = not following)
+	Error     error
+}
+
+// LogFollowTickMsg polls a followed site's logs again
+type LogFollowTickMsg struct {
 	SiteID uuid.UUID
-	Logs   []string
-	Error  error
+	Gen    int // state.FollowLogsGen when the tick was scheduled
+}
+
+// LogsExportedMsg is returned after writing a site's buffered logs to a file
+type LogsExportedMsg struct {
+	Path  string
+	Error error
 }
 
 // FetchNodeMetricsMsg retrieves resource metrics for a site
@@ -180,9 +320,10 @@ type FetchNodeMetricsMsg struct {
 
 // NodeMetricsResultMsg is returned with metrics data
 type NodeMetricsResultMsg struct {
-	SiteID  uuid.UUID
-	Metrics *api.ContainerMetrics
-	Error   error
+	SiteID     uuid.UUID
+	Metrics    *api.ContainerMetrics
+	Error      error
+	Background bool // From the periodic refresh on the site detail screen rather than a user action
 }
 
 // ============================================================================
@@ -233,11 +374,31 @@ type SaveConfigMsg struct{}
 
 // ConfigSavedMsg is returned after config save completes
 type ConfigSavedMsg struct {
-	Error error
+	Error       error
+	Fingerprint []byte // configFingerprint of the config saved (nil if a later save wrote it instead)
+	Announce    bool   // Saved on request, so success is reported too
+}
+
+// RefreshMsg re-fetches live data from nodes and DNS providers for the current screen
+type RefreshMsg struct{}
+
+// ReloadConfigMsg re-reads the config file from disk, discarding unsaved changes
+type ReloadConfigMsg struct{}
+
+// ConfigReloadedMsg is returned after the config file has been re-read
+type ConfigReloadedMsg struct {
+	Config *config.Config
+	Error  error
 }
 
-// QuitMsg signals the application should exit
-type QuitMsg struct{}
+// ConfigWatchTickMsg is sent periodically to check the config file for edits made outside Archon
+type ConfigWatchTickMsg struct{}
+
+// QuitMsg signals the application should exit, once pending operations have finished
+type QuitMsg struct {
+	Save  bool // Save the config first, after the operations finish
+	Force bool // Quit without waiting, abandoning pending operations
+}
 
 // NotificationMsg displays a message to the user
 type NotificationMsg struct {
@@ -259,5 +420,54 @@ type AsyncResultMsg struct {
 	Error       error
 }
 
-// TickMsg is sent periodically for background tasks
+// CancelOperationMsg aborts a pending async operation
+type CancelOperationMsg struct {
+	OperationID uuid.UUID
+}
+
+// RetryOperationMsg starts a failed or cancelled async operation again
+type RetryOperationMsg struct {
+	OperationID uuid.UUID
+}
+
+// SpinnerTickMsg advances the status bar spinner while async operations are pending
+type SpinnerTickMsg struct{}
+
+// TickMsg is sent periodically for background tasks (currently node health checks)
 type TickMsg struct{}
+
+// CertTickMsg is sent periodically to check every domain's TLS certificate expiry
+type CertTickMsg struct{}
+
+// CertCheckedMsg is returned after probing the TLS certificates served for a domain
+type CertCheckedMsg struct {
+	DomainID   uuid.UUID
+	Host       string    // Host serving the certificate that expires first
+	ExpiresAt  time.Time // Earliest expiry across the domain's hosts
+	Error      error     // Set when no host could be checked
+	Background bool      // From the periodic check rather than a user action
+}
+
+// CertsCheckedMsg is returned after a background round of certificate checks, with one result per domain
+type CertsCheckedMsg struct {
+	Results []CertCheckedMsg
+}
+
+// SiteHealthTickMsg is sent periodically to probe the health path of every running site and
+// poll the status of sites that restart automatically
+type SiteHealthTickMsg struct{}
+
+// SiteHealthCheckedMsg is returned after probing a site's health path
+type SiteHealthCheckedMsg struct {
+	SiteID uuid.UUID
+	Error  error // Set when the site didn't answer with the expected status
+}
+
+// PropagationCheckedMsg is returned after checking a domain's DNS records against public resolvers
+type PropagationCheckedMsg struct {
+	DomainID uuid.UUID
+	Results  []dns.PropagationResult
+}
+
+// MetricsTickMsg is sent periodically to refresh the open site detail screen's metrics
+type MetricsTickMsg struct{}
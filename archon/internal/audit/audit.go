@@ -0,0 +1,140 @@
+package audit
+
+import (
+	"encoding/json"
+	"fmt"
+	"os"
+	"os/user"
+	"path/filepath"
+	"sync"
+	"time"
+)
+
+// DefaultMaxBytes is the size at which the audit log is rotated when the setting is unset
+const DefaultMaxBytes = 10 * 1024 * 1024
+
+// rotatedFiles is how many rotated logs are kept, as <path>.1 (newest) to <path>.N
+const rotatedFiles = 5
+
+// Outcomes of an audited action
+const (
+	OutcomeSuccess = "success"
+	OutcomeFailure = "failure"
+)
+
+// Entry is one line of the audit log
+type Entry struct {
+	Time     time.Time `json:"time"`
+	User     string    `json:"user"`
+	Action   string    `json:"action"` // e.g. "deploy_site", "delete_node", "update_settings"
+	Entity   string    `json:"entity"` // "site", "domain", "node" or "settings"
+	EntityID string    `json:"entity_id,omitempty"`
+	Name     string    `json:"name,omitempty"`
+	Outcome  string    `json:"outcome"`
+	Error    string    `json:"error,omitempty"`
+}
+
+// Log appends entries to a JSON Lines file, rotating it once it grows past a size limit.
+// A nil *Log records nothing, so auditing can be turned off without checks at every call site.
+type Log struct {
+	mu       sync.Mutex
+	path     string
+	maxBytes int64
+	user     string
+}
+
+// New returns a log writing to path, which is created on the first entry along with its directory.
+// maxBytes <= 0 uses DefaultMaxBytes.
+func New(path string, maxBytes int64) *Log {
+	if maxBytes <= 0 {
+		maxBytes = DefaultMaxBytes
+	}
+	return &Log{path: path, maxBytes: maxBytes, user: currentUser()}
+}
+
+// Path returns the file the log writes to
+func (l *Log) Path() string {
+	return l.path
+}
+
+// currentUser identifies who is running Archon as user@host
+func currentUser() string {
+	name := os.Getenv("USER")
+	if u, err := user.Current(); err == nil {
+		name = u.Username
+	}
+	if host, err := os.Hostname(); err == nil {
+		return name + "@" + host
+	}
+	return name
+}
+
+// Record appends an entry, filling in the time, user and outcome. The file is opened for
+// each entry so it can be moved by rotation or external tools without confusing Archon.
+func (l *Log) Record(entry Entry) error {
+	if l == nil {
+		return nil
+	}
+	if entry.Time.IsZero() {
+		entry.Time = time.Now()
+	}
+	entry.User = l.user
+	if entry.Outcome == "" {
+		entry.Outcome = OutcomeSuccess
+		if entry.Error != "" {
+			entry.Outcome = OutcomeFailure
+		}
+	}
+
+	line, err := json.Marshal(entry)
+	if err != nil {
+		return err
+	}
+	line = append(line, '\n')
+
+	l.mu.Lock()
+	defer l.mu.Unlock()
+
+	if err := os.MkdirAll(filepath.Dir(l.path), 0755); err != nil {
+		return err
+	}
+	if err := l.rotate(int64(len(line))); err != nil {
+		return fmt.Errorf("failed to rotate audit log: %w", err)
+	}
+
+	file, err := os.OpenFile(l.path, os.O_CREATE|os.O_WRONLY|os.O_APPEND, 0600)
+	if err != nil {
+		return err
+	}
+	if _, err := file.Write(line); err != nil {
+		file.Close()
+		return err
+	}
+	return file.Close()
+}
+
+// rotate moves the log to <path>.1, shifting older logs up, if adding size bytes would take it past the limit
+func (l *Log) rotate(size int64) error {
+	info, err := os.Stat(l.path)
+	if os.IsNotExist(err) {
+		return nil
+	}
+	if err != nil {
+		return err
+	}
+	if info.Size() == 0 || info.Size()+size <= l.maxBytes {
+		return nil
+	}
+
+	for n := rotatedFiles - 1; n >= 1; n-- {
+		if err := os.Rename(rotatedPath(l.path, n), rotatedPath(l.path, n+1)); err != nil && !os.IsNotExist(err) {
+			return err
+		}
+	}
+	return os.Rename(l.path, rotatedPath(l.path, 1))
+}
+
+// rotatedPath returns the path of the nth most recent rotated log
+func rotatedPath(path string, n int) string {
+	return fmt.Sprintf("%s.%d", path, n)
+}
@@ -0,0 +1,90 @@
+package audit
+
+import (
+	"bufio"
+	"encoding/json"
+	"os"
+	"path/filepath"
+	"testing"
+)
+
+func readEntries(t *testing.T, path string) []Entry {
+	t.Helper()
+	file, err := os.Open(path)
+	if err != nil {
+		t.Fatal(err)
+	}
+	defer file.Close()
+
+	var entries []Entry
+	scanner := bufio.NewScanner(file)
+	for scanner.Scan() {
+		var entry Entry
+		if err := json.Unmarshal(scanner.Bytes(), &entry); err != nil {
+			t.Fatalf("invalid audit line %q: %v", scanner.Text(), err)
+		}
+		entries = append(entries, entry)
+	}
+	return entries
+}
+
+func TestRecord(t *testing.T) {
+	path := filepath.Join(t.TempDir(), "logs", "audit.jsonl")
+	log := New(path, 0)
+
+	if err := log.Record(Entry{Action: "deploy_site", Entity: "site", EntityID: "42", Name: "blog"}); err != nil {
+		t.Fatalf("Record() error = %v", err)
+	}
+	if err := log.Record(Entry{Action: "delete_node", Entity: "node", Name: "edge", Error: "node not found"}); err != nil {
+		t.Fatalf("Record() error = %v", err)
+	}
+
+	entries := readEntries(t, path)
+	if len(entries) != 2 {
+		t.Fatalf("got %d entries, want 2", len(entries))
+	}
+	if entries[0].Outcome != OutcomeSuccess || entries[0].Time.IsZero() || entries[0].User == "" {
+		t.Errorf("first entry = %+v, want a timestamped success with the user", entries[0])
+	}
+	if entries[1].Outcome != OutcomeFailure {
+		t.Errorf("entry with an error has outcome %q, want failure", entries[1].Outcome)
+	}
+
+	// A nil log records nothing
+	var disabled *Log
+	if err := disabled.Record(Entry{Action: "deploy_site"}); err != nil {
+		t.Errorf("nil Log Record() error = %v", err)
+	}
+}
+
+func TestRotation(t *testing.T) {
+	path := filepath.Join(t.TempDir(), "audit.jsonl")
+	log := New(path, 1000)
+
+	for i := 0; i < 10; i++ {
+		if err := log.Record(Entry{Action: "update_site", Entity: "site", Name: "blog"}); err != nil {
+			t.Fatalf("Record() error = %v", err)
+		}
+	}
+
+	info, err := os.Stat(path)
+	if err != nil {
+		t.Fatal(err)
+	}
+	if info.Size() > 1000 {
+		t.Errorf("audit log is %d bytes, want at most the 1000 byte limit", info.Size())
+	}
+	if _, err := os.Stat(rotatedPath(path, 1)); err != nil {
+		t.Errorf("no rotated log: %v", err)
+	}
+
+	total := len(readEntries(t, path))
+	for n := 1; n <= rotatedFiles; n++ {
+		if _, err := os.Stat(rotatedPath(path, n)); err == nil {
+			total += len(readEntries(t, rotatedPath(path, n)))
+		}
+	}
+	if total != 10 {
+		t.Errorf("found %d entries across the logs, want all 10", total)
+	}
+}
@@ -0,0 +1,39 @@
+package certs
+
+import (
+	"context"
+	"crypto/tls"
+	"fmt"
+	"net"
+	"time"
+)
+
+// Expiry connects to host on port 443 and returns when the certificate it serves expires
+func Expiry(ctx context.Context, host string) (time.Time, error) {
+	return expiry(ctx, net.JoinHostPort(host, "443"), host)
+}
+
+// expiry performs a TLS handshake with addr, sending serverName for SNI, and returns the
+// leaf certificate's NotAfter
+func expiry(ctx context.Context, addr, serverName string) (time.Time, error) {
+	dialer := &tls.Dialer{
+		Config: &tls.Config{
+			ServerName: serverName,
+			// Only the expiry is read, and an expired or otherwise invalid certificate
+			// still needs to be reported rather than failing the handshake
+			InsecureSkipVerify: true, // #nosec G402
+		},
+	}
+
+	conn, err := dialer.DialContext(ctx, "tcp", addr)
+	if err != nil {
+		return time.Time{}, err
+	}
+	defer conn.Close()
+
+	certs := conn.(*tls.Conn).ConnectionState().PeerCertificates
+	if len(certs) == 0 {
+		return time.Time{}, fmt.Errorf("%s presented no certificate", serverName)
+	}
+	return certs[0].NotAfter, nil
+}
@@ -0,0 +1,39 @@
+package certs
+
+import (
+	"context"
+	"net/http"
+	"net/http/httptest"
+	"testing"
+	"time"
+)
+
+func TestExpiry(t *testing.T) {
+	server := httptest.NewTLSServer(http.HandlerFunc(func(w http.ResponseWriter, r *http.Request) {}))
+	defer server.Close()
+
+	ctx, cancel := context.WithTimeout(context.Background(), 5*time.Second)
+	defer cancel()
+
+	// The test server's certificate is self-signed, which must not stop the expiry being read
+	got, err := expiry(ctx, server.Listener.Addr().String(), "example.com")
+	if err != nil {
+		t.Fatalf("expiry() error = %v", err)
+	}
+	if want := server.Certificate().NotAfter; !got.Equal(want) {
+		t.Errorf("expiry() = %v, want %v", got, want)
+	}
+}
+
+func TestExpiryUnreachable(t *testing.T) {
+	server := httptest.NewServer(http.HandlerFunc(func(w http.ResponseWriter, r *http.Request) {}))
+	addr := server.Listener.Addr().String()
+	server.Close()
+
+	ctx, cancel := context.WithTimeout(context.Background(), 5*time.Second)
+	defer cancel()
+
+	if _, err := expiry(ctx, addr, "example.com"); err == nil {
+		t.Error("expiry() on a closed port succeeded, want an error")
+	}
+}
@@ -0,0 +1,119 @@
+package compose
+
+import (
+	"fmt"
+	"strconv"
+	"strings"
+
+	"gopkg.in/yaml.v3"
+
+	"github.com/BlueBeard63/archon/internal/models"
+)
+
+// exportFile is the docker-compose.yml written by ExportSite
+type exportFile struct {
+	Services map[string]exportService `yaml:"services"`
+	Volumes  map[string]struct{}      `yaml:"volumes,omitempty"`
+}
+
+// exportService is a single service in an exported compose file
+type exportService struct {
+	Image       string            `yaml:"image,omitempty"`
+	Build       *exportBuild      `yaml:"build,omitempty"`
+	Restart     string            `yaml:"restart"`
+	Ports       []string          `yaml:"ports,omitempty"`
+	Environment map[string]string `yaml:"environment,omitempty"`
+	Volumes     []string          `yaml:"volumes,omitempty"`
+	Labels      map[string]string `yaml:"labels,omitempty"`
+	Deploy      *exportDeploy     `yaml:"deploy,omitempty"`
+}
+
+type exportBuild struct {
+	Context    string `yaml:"context"`
+	Dockerfile string `yaml:"dockerfile,omitempty"`
+}
+
+type exportDeploy struct {
+	Resources struct {
+		Limits map[string]string `yaml:"limits"`
+	} `yaml:"resources"`
+}
+
+// ExportSite renders a site as a docker-compose.yml for running it with plain Docker.
+// domainName is the hostname routed by the generated Traefik labels, as used for deploys.
+// Compose sites are returned as written.
+func ExportSite(site *models.Site, domainName string) ([]byte, error) {
+	if site.IsCompose() {
+		if strings.TrimSpace(site.ComposeContent) == "" {
+			return nil, fmt.Errorf("site has no compose content")
+		}
+		return []byte(site.ComposeContent), nil
+	}
+
+	service := exportService{
+		Image:       site.DockerImage,
+		Restart:     "unless-stopped",
+		Environment: site.EnvironmentVars,
+		Labels:      site.GenerateTraefikLabels(domainName),
+	}
+	if site.IsBuild() {
+		service.Image = ""
+		service.Build = &exportBuild{Context: site.Build.Context, Dockerfile: site.Build.Dockerfile}
+	}
+	if service.Image == "" && service.Build == nil {
+		return nil, fmt.Errorf("site has no Docker image")
+	}
+
+	seen := make(map[string]bool)
+	for _, mapping := range site.GetDomainMappings() {
+		port := fmt.Sprintf("%d:%d", mapping.GetEffectiveHostPort(), mapping.Port)
+		if !seen[port] {
+			seen[port] = true
+			service.Ports = append(service.Ports, port)
+		}
+	}
+
+	file := exportFile{Services: map[string]exportService{}}
+	for _, volume := range site.Volumes {
+		service.Volumes = append(service.Volumes, volume.String())
+		// Named volumes have to be declared at the top level
+		if !strings.HasPrefix(volume.HostPath, "/") && !strings.HasPrefix(volume.HostPath, ".") {
+			if file.Volumes == nil {
+				file.Volumes = make(map[string]struct{})
+			}
+			file.Volumes[volume.HostPath] = struct{}{}
+		}
+	}
+
+	if site.CPULimit > 0 || site.MemoryLimitMB > 0 {
+		deploy := &exportDeploy{}
+		deploy.Resources.Limits = make(map[string]string)
+		if site.CPULimit > 0 {
+			deploy.Resources.Limits["cpus"] = strconv.FormatFloat(site.CPULimit, 'f', -1, 64)
+		}
+		if site.MemoryLimitMB > 0 {
+			deploy.Resources.Limits["memory"] = fmt.Sprintf("%dM", site.MemoryLimitMB)
+		}
+		service.Deploy = deploy
+	}
+
+	file.Services[ServiceName(site.Name)] = service
+	return yaml.Marshal(file)
+}
+
+// ServiceName makes a site name usable as a compose service name
+func ServiceName(siteName string) string {
+	name := strings.Map(func(r rune) rune {
+		switch {
+		case r >= 'a' && r <= 'z', r >= '0' && r <= '9', r == '-', r == '_', r == '.':
+			return r
+		case r >= 'A' && r <= 'Z':
+			return r + ('a' - 'A')
+		}
+		return '-'
+	}, siteName)
+	if name = strings.Trim(name, "-._"); name == "" {
+		return "site"
+	}
+	return name
+}
@@ -0,0 +1,93 @@
+package compose
+
+import (
+	"reflect"
+	"testing"
+
+	"github.com/google/uuid"
+	"gopkg.in/yaml.v3"
+
+	"github.com/BlueBeard63/archon/internal/models"
+)
+
+func TestExportSite(t *testing.T) {
+	site := models.NewSite("My Blog", uuid.New(), uuid.New(), "ghost:5", 2368)
+	site.DomainMappings[0].HostPort = 8100
+	site.EnvironmentVars["NODE_ENV"] = "production"
+	site.Volumes = []models.VolumeMount{
+		{HostPath: "ghost-content", ContainerPath: "/var/lib/ghost/content"},
+		{HostPath: "/srv/config", ContainerPath: "/config", ReadOnly: true},
+	}
+	site.MemoryLimitMB = 512
+
+	content, err := ExportSite(site, "blog.example.com")
+	if err != nil {
+		t.Fatalf("ExportSite() error = %v", err)
+	}
+
+	// The export must be a compose file the importer reads back to the same site
+	services, warnings, err := ImportServices(string(content))
+	if err != nil {
+		t.Fatalf("ImportServices(export) error = %v\n%s", err, content)
+	}
+	if len(services) != 1 || services[0].Name != "my-blog" {
+		t.Fatalf("exported services = %+v, want my-blog", services)
+	}
+	service := services[0]
+	if service.Image != "ghost:5" {
+		t.Errorf("image = %q, want ghost:5", service.Image)
+	}
+	if c, h := service.Port(); c != 2368 || h != 8100 {
+		t.Errorf("port = (%d, %d), want (2368, 8100)", c, h)
+	}
+	if !reflect.DeepEqual(service.Environment, site.EnvironmentVars) {
+		t.Errorf("environment = %v, want %v", service.Environment, site.EnvironmentVars)
+	}
+	if !reflect.DeepEqual(service.Volumes, site.Volumes) {
+		t.Errorf("volumes = %+v, want %+v", service.Volumes, site.Volumes)
+	}
+	if want := []string{"blog.example.com"}; !reflect.DeepEqual(service.Hosts, want) {
+		t.Errorf("Traefik hosts = %v, want %v", service.Hosts, want)
+	}
+
+	// Resource limits and named volumes have no site equivalent on import, so check the YAML
+	var file struct {
+		Services map[string]struct {
+			Deploy struct {
+				Resources struct {
+					Limits map[string]string `yaml:"limits"`
+				} `yaml:"resources"`
+			} `yaml:"deploy"`
+		} `yaml:"services"`
+		Volumes map[string]interface{} `yaml:"volumes"`
+	}
+	if err := yaml.Unmarshal(content, &file); err != nil {
+		t.Fatal(err)
+	}
+	if got := file.Services["my-blog"].Deploy.Resources.Limits["memory"]; got != "512M" {
+		t.Errorf("memory limit = %q, want 512M", got)
+	}
+	if _, ok := file.Volumes["ghost-content"]; !ok || len(file.Volumes) != 1 {
+		t.Errorf("top-level volumes = %v, want only ghost-content", file.Volumes)
+	}
+	for _, warning := range warnings {
+		if warning == "my-blog: ignoring unsupported fields: deploy, restart" {
+			return
+		}
+	}
+	t.Errorf("warnings = %v, want deploy and restart reported as unsupported", warnings)
+}
+
+func TestServiceName(t *testing.T) {
+	tests := map[string]string{
+		"blog":        "blog",
+		"My Blog!":    "my-blog",
+		"api_v2.prod": "api_v2.prod",
+		"***":         "site",
+	}
+	for input, want := range tests {
+		if got := ServiceName(input); got != want {
+			t.Errorf("ServiceName(%q) = %q, want %q", input, got, want)
+		}
+	}
+}
@@ -0,0 +1,290 @@
+package compose
+
+import (
+	"fmt"
+	"path/filepath"
+	"regexp"
+	"sort"
+	"strings"
+
+	"gopkg.in/yaml.v3"
+
+	"github.com/BlueBeard63/archon/internal/models"
+)
+
+// ImportedService is a compose service translated into the fields of a site
+type ImportedService struct {
+	Name        string
+	Image       string
+	Build       *models.BuildSpec // Set instead of Image when the service is built from source
+	Ports       []DetectedPort
+	Environment map[string]string
+	Volumes     []models.VolumeMount
+	Hosts       []string // Hostnames from Traefik router rules (traefik.http.routers.*.rule)
+	ServicePort int      // Container port from a Traefik service label, 0 if not set
+}
+
+// Port returns the container and host port the site should publish, preferring the
+// port Traefik was configured to route to. Both are 0 if the service exposes no ports.
+func (s *ImportedService) Port() (int, int) {
+	if s.ServicePort > 0 {
+		for _, p := range s.Ports {
+			if p.ContainerPort == s.ServicePort {
+				return p.ContainerPort, p.HostPort
+			}
+		}
+		return s.ServicePort, 0
+	}
+	if len(s.Ports) > 0 {
+		return s.Ports[0].ContainerPort, s.Ports[0].HostPort
+	}
+	return 0, 0
+}
+
+// ResolveBuildContext makes a relative local build context relative to dir, the directory of the
+// compose file, as compose does. Git repository contexts are left as they are.
+func (s *ImportedService) ResolveBuildContext(dir string) {
+	if s.Build == nil || s.Build.IsRemote() || filepath.IsAbs(s.Build.Context) {
+		return
+	}
+	s.Build.Context = filepath.Join(dir, s.Build.Context)
+}
+
+// supportedServiceKeys are the service fields ImportServices translates; others produce a warning
+var supportedServiceKeys = map[string]bool{
+	"image":       true,
+	"build":       true,
+	"ports":       true,
+	"environment": true,
+	"volumes":     true,
+	"labels":      true,
+	"expose":      true,
+}
+
+// hostRulePattern matches the hostnames in a Traefik Host(`a`, `b`) rule
+var hostRulePattern = regexp.MustCompile("Host\\(([^)]*)\\)")
+
+// ImportServices reads the services of a compose file in name order. Fields a site can't
+// represent are skipped with a warning rather than failing the import, as are services
+// with neither an image nor a build context.
+func ImportServices(content string) ([]ImportedService, []string, error) {
+	var file struct {
+		Services map[string]map[string]interface{} `yaml:"services"`
+		Networks interface{}                       `yaml:"networks"`
+		Secrets  interface{}                       `yaml:"secrets"`
+		Configs  interface{}                       `yaml:"configs"`
+	}
+	if err := yaml.Unmarshal([]byte(content), &file); err != nil {
+		return nil, nil, fmt.Errorf("failed to parse compose YAML: %w", err)
+	}
+	if len(file.Services) == 0 {
+		return nil, nil, fmt.Errorf("compose file has no services")
+	}
+
+	var warnings []string
+	warn := func(format string, args ...interface{}) {
+		warnings = append(warnings, fmt.Sprintf(format, args...))
+	}
+	for name, value := range map[string]interface{}{"networks": file.Networks, "secrets": file.Secrets, "configs": file.Configs} {
+		if value != nil {
+			warn("top-level %s are not supported and were ignored", name)
+		}
+	}
+	sort.Strings(warnings)
+
+	names := make([]string, 0, len(file.Services))
+	for name := range file.Services {
+		names = append(names, name)
+	}
+	sort.Strings(names)
+
+	var services []ImportedService
+	for _, name := range names {
+		def := file.Services[name]
+		service := ImportedService{Name: name, Environment: make(map[string]string)}
+		serviceWarn := func(format string, args ...interface{}) {
+			warn("%s: "+format, append([]interface{}{name}, args...)...)
+		}
+
+		var ignored []string
+		for key := range def {
+			if !supportedServiceKeys[key] {
+				ignored = append(ignored, key)
+			}
+		}
+		if len(ignored) > 0 {
+			sort.Strings(ignored)
+			serviceWarn("ignoring unsupported fields: %s", strings.Join(ignored, ", "))
+		}
+
+		if image, ok := def["image"].(string); ok {
+			service.Image = image
+		}
+		if build, ok := def["build"]; ok {
+			spec, err := parseBuild(build)
+			if err != nil {
+				serviceWarn("%v", err)
+			} else if service.Image == "" {
+				service.Build = spec
+			} else {
+				serviceWarn("has both image and build, using the image")
+			}
+		}
+		if service.Image == "" && service.Build == nil {
+			serviceWarn("skipped, it has no image or build context")
+			continue
+		}
+
+		// Exposed ports are only reachable through the proxy, which is all a site needs
+		for _, key := range []string{"ports", "expose"} {
+			ports, _ := def[key].([]interface{})
+			for _, portDef := range ports {
+				detected, err := parsePortDefinition(name, portDef)
+				if err != nil {
+					serviceWarn("ignoring port %v: %v", portDef, err)
+					continue
+				}
+				service.Ports = append(service.Ports, *detected)
+			}
+		}
+
+		for _, entry := range listOrMap(def["environment"]) {
+			if !entry.hasValue {
+				serviceWarn("environment variable %s takes its value from the host, set it before deploying", entry.key)
+			}
+			service.Environment[entry.key] = entry.value
+		}
+
+		if volumes, ok := def["volumes"].([]interface{}); ok {
+			for _, volumeDef := range volumes {
+				mount, err := parseVolume(volumeDef)
+				if err != nil {
+					serviceWarn("ignoring volume: %v", err)
+					continue
+				}
+				service.Volumes = append(service.Volumes, mount)
+			}
+		}
+
+		for _, label := range listOrMap(def["labels"]) {
+			if !strings.HasPrefix(label.key, "traefik.") {
+				serviceWarn("ignoring label %s, sites only support Traefik labels", label.key)
+				continue
+			}
+			if !service.applyTraefikLabel(label.key, label.value) {
+				serviceWarn("Traefik label %s is not supported and was ignored", label.key)
+			}
+		}
+
+		services = append(services, service)
+	}
+
+	return services, warnings, nil
+}
+
+// applyTraefikLabel maps a Traefik label onto the service, returning false if it has no site equivalent.
+// The router rule and service port are regenerated from the site when it is deployed.
+func (s *ImportedService) applyTraefikLabel(key, value string) bool {
+	parts := strings.Split(key, ".")
+	switch {
+	case key == "traefik.enable":
+		return true
+	case len(parts) == 5 && parts[1] == "http" && parts[2] == "routers" && parts[4] == "rule":
+		for _, match := range hostRulePattern.FindAllStringSubmatch(value, -1) {
+			for _, host := range strings.Split(match[1], ",") {
+				host = strings.ToLower(strings.Trim(strings.TrimSpace(host), "`\"'"))
+				if host != "" {
+					s.Hosts = append(s.Hosts, host)
+				}
+			}
+		}
+		return len(s.Hosts) > 0
+	case len(parts) >= 5 && parts[1] == "http" && parts[2] == "routers" &&
+		(parts[4] == "entrypoints" || parts[4] == "tls" || parts[4] == "service"):
+		return true // Archon sets these itself, TLS from the site's SSL settings
+	case len(parts) == 7 && parts[1] == "http" && parts[2] == "services" &&
+		parts[4] == "loadbalancer" && parts[5] == "server" && parts[6] == "port":
+		port, err := parsePortNumber(value)
+		if err != nil {
+			return false
+		}
+		s.ServicePort = port
+		return true
+	}
+	return false
+}
+
+// keyValue is an entry of a compose list-or-map field such as environment or labels
+type keyValue struct {
+	key      string
+	value    string
+	hasValue bool
+}
+
+// listOrMap reads a compose field written either as a map or as a list of "KEY=value"
+// strings, in key order for maps
+func listOrMap(value interface{}) []keyValue {
+	var entries []keyValue
+	switch v := value.(type) {
+	case map[string]interface{}:
+		for key, val := range v {
+			entry := keyValue{key: key, hasValue: val != nil}
+			if val != nil {
+				entry.value = fmt.Sprint(val)
+			}
+			entries = append(entries, entry)
+		}
+		sort.Slice(entries, func(i, j int) bool { return entries[i].key < entries[j].key })
+	case []interface{}:
+		for _, item := range v {
+			text := fmt.Sprint(item)
+			key, val, found := strings.Cut(text, "=")
+			entries = append(entries, keyValue{key: strings.TrimSpace(key), value: val, hasValue: found})
+		}
+	}
+	return entries
+}
+
+// parseBuild reads a service's build context, given as a path or a map with context and dockerfile
+func parseBuild(build interface{}) (*models.BuildSpec, error) {
+	switch v := build.(type) {
+	case string:
+		return &models.BuildSpec{Context: v}, nil
+	case map[string]interface{}:
+		spec := &models.BuildSpec{}
+		spec.Context, _ = v["context"].(string)
+		spec.Dockerfile, _ = v["dockerfile"].(string)
+		if spec.Context == "" {
+			spec.Context = "."
+		}
+		return spec, nil
+	}
+	return nil, fmt.Errorf("unsupported build definition: %v", build)
+}
+
+// parseVolume reads a service volume in short ("src:dst[:ro]") or long (type/source/target) form
+func parseVolume(volumeDef interface{}) (models.VolumeMount, error) {
+	switch v := volumeDef.(type) {
+	case string:
+		mounts, err := models.ParseVolumeMounts(v)
+		if err != nil {
+			return models.VolumeMount{}, err
+		}
+		return mounts[0], nil
+	case map[string]interface{}:
+		mount := models.VolumeMount{}
+		mount.HostPath, _ = v["source"].(string)
+		mount.ContainerPath, _ = v["target"].(string)
+		if readOnly, ok := v["read_only"].(bool); ok {
+			mount.ReadOnly = readOnly
+		}
+		if volumeType, ok := v["type"].(string); ok && volumeType != "bind" && volumeType != "volume" {
+			return mount, fmt.Errorf("%s mounts are not supported", volumeType)
+		}
+		if err := mount.Validate(); err != nil {
+			return mount, err
+		}
+		return mount, nil
+	}
+	return models.VolumeMount{}, fmt.Errorf("unsupported volume definition: %v", volumeDef)
+}
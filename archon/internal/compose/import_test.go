@@ -0,0 +1,147 @@
+package compose
+
+import (
+	"path/filepath"
+	"reflect"
+	"strings"
+	"testing"
+
+	"github.com/BlueBeard63/archon/internal/models"
+)
+
+func TestImportServices(t *testing.T) {
+	content := `
+services:
+  web:
+    image: nginx:1.27
+    ports:
+      - "8080:80"
+    environment:
+      - MODE=production
+      - SECRET
+    volumes:
+      - ./html:/usr/share/nginx/html:ro
+      - type: tmpfs
+        target: /cache
+    labels:
+      traefik.enable: "true"
+      traefik.http.routers.web.rule: Host(` + "`www.example.com`, `example.com`" + `)
+      traefik.http.services.web.loadbalancer.server.port: "80"
+      traefik.http.routers.web.middlewares: auth
+      com.example.team: ops
+    restart: unless-stopped
+  api:
+    build:
+      context: ./api
+      dockerfile: Dockerfile.prod
+    expose:
+      - 3000
+    environment:
+      PORT: 3000
+  worker:
+    command: run
+networks:
+  default: {}
+`
+
+	services, warnings, err := ImportServices(content)
+	if err != nil {
+		t.Fatalf("ImportServices() error = %v", err)
+	}
+	if len(services) != 2 || services[0].Name != "api" || services[1].Name != "web" {
+		t.Fatalf("ImportServices() services = %+v, want api and web", services)
+	}
+
+	api := services[0]
+	if want := (&models.BuildSpec{Context: "./api", Dockerfile: "Dockerfile.prod"}); !reflect.DeepEqual(api.Build, want) {
+		t.Errorf("api.Build = %+v, want %+v", api.Build, want)
+	}
+	if c, h := api.Port(); c != 3000 || h != 0 {
+		t.Errorf("api.Port() = (%d, %d), want (3000, 0)", c, h)
+	}
+	if api.Environment["PORT"] != "3000" {
+		t.Errorf("api.Environment = %v, want PORT=3000", api.Environment)
+	}
+
+	web := services[1]
+	if web.Image != "nginx:1.27" {
+		t.Errorf("web.Image = %q, want nginx:1.27", web.Image)
+	}
+	if c, h := web.Port(); c != 80 || h != 8080 {
+		t.Errorf("web.Port() = (%d, %d), want (80, 8080)", c, h)
+	}
+	if want := map[string]string{"MODE": "production", "SECRET": ""}; !reflect.DeepEqual(web.Environment, want) {
+		t.Errorf("web.Environment = %v, want %v", web.Environment, want)
+	}
+	if want := []models.VolumeMount{{HostPath: "./html", ContainerPath: "/usr/share/nginx/html", ReadOnly: true}}; !reflect.DeepEqual(web.Volumes, want) {
+		t.Errorf("web.Volumes = %+v, want %+v", web.Volumes, want)
+	}
+	if want := []string{"www.example.com", "example.com"}; !reflect.DeepEqual(web.Hosts, want) {
+		t.Errorf("web.Hosts = %v, want %v", web.Hosts, want)
+	}
+
+	// Each unsupported part of the file is reported once
+	for _, want := range []string{
+		"top-level networks",
+		"web: ignoring unsupported fields: restart",
+		"web: environment variable SECRET",
+		"web: ignoring volume: tmpfs mounts are not supported",
+		"web: Traefik label traefik.http.routers.web.middlewares",
+		"web: ignoring label com.example.team",
+		"worker: ignoring unsupported fields: command",
+		"worker: skipped",
+	} {
+		found := 0
+		for _, warning := range warnings {
+			if strings.Contains(warning, want) {
+				found++
+			}
+		}
+		if found != 1 {
+			t.Errorf("warnings contain %q %d times, want once: %v", want, found, warnings)
+		}
+	}
+	if len(warnings) != 8 {
+		t.Errorf("len(warnings) = %d, want 8: %v", len(warnings), warnings)
+	}
+}
+
+func TestImportServicesErrors(t *testing.T) {
+	tests := []struct {
+		name    string
+		content string
+	}{
+		{name: "invalid YAML", content: "services: ["},
+		{name: "no services", content: "version: '3'\n"},
+	}
+
+	for _, tt := range tests {
+		t.Run(tt.name, func(t *testing.T) {
+			if _, _, err := ImportServices(tt.content); err == nil {
+				t.Error("ImportServices() error = nil, want an error")
+			}
+		})
+	}
+}
+
+func TestResolveBuildContext(t *testing.T) {
+	dir := filepath.Join("/srv", "app")
+	tests := []struct {
+		context string
+		want    string
+	}{
+		{context: ".", want: dir},
+		{context: "./api", want: filepath.Join(dir, "api")},
+		{context: "../shared", want: filepath.Join("/srv", "shared")},
+		{context: "/opt/api", want: "/opt/api"},
+		{context: "https://github.com/example/api.git", want: "https://github.com/example/api.git"},
+	}
+
+	for _, tt := range tests {
+		service := ImportedService{Build: &models.BuildSpec{Context: tt.context}}
+		service.ResolveBuildContext(dir)
+		if service.Build.Context != tt.want {
+			t.Errorf("ResolveBuildContext(%q) = %q, want %q", tt.context, service.Build.Context, tt.want)
+		}
+	}
+}
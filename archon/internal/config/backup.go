@@ -0,0 +1,253 @@
+package config
+
+import (
+	"bytes"
+	"fmt"
+	"os"
+	"path/filepath"
+	"time"
+
+	"github.com/pelletier/go-toml/v2"
+)
+
+// DefaultConfigBackups is how many previous versions of the config are kept when the setting is unset
+const DefaultConfigBackups = 5
+
+// Backup is a previous version of the config, saved as <config>.bak.N
+type Backup struct {
+	Number  int       `json:"number"` // 1 is the most recent
+	Path    string    `json:"path"`
+	ModTime time.Time `json:"modified"`
+	Sites   int       `json:"sites"`
+	Domains int       `json:"domains"`
+	Nodes   int       `json:"nodes"`
+}
+
+// BackupCount returns how many backups to keep, 0 when backups are disabled
+func (s Settings) BackupCount() int {
+	switch {
+	case s.ConfigBackups < 0:
+		return 0
+	case s.ConfigBackups == 0:
+		return DefaultConfigBackups
+	}
+	return s.ConfigBackups
+}
+
+// BackupPath returns the path of the nth most recent backup of the config at path
+func BackupPath(path string, n int) string {
+	return fmt.Sprintf("%s.bak.%d", path, n)
+}
+
+// writeFileAtomic writes data to a temporary file next to path and renames it into place,
+// so a crash mid-write leaves either the old file or the new one, never a truncated mix
+func writeFileAtomic(path string, data []byte, perm os.FileMode) error {
+	tmp, err := os.CreateTemp(filepath.Dir(path), "."+filepath.Base(path)+".tmp-*")
+	if err != nil {
+		return err
+	}
+	defer os.Remove(tmp.Name()) // No-op once renamed
+
+	if _, err := tmp.Write(data); err != nil {
+		tmp.Close()
+		return err
+	}
+	if err := tmp.Sync(); err != nil {
+		tmp.Close()
+		return err
+	}
+	if err := tmp.Close(); err != nil {
+		return err
+	}
+	if err := os.Chmod(tmp.Name(), perm); err != nil {
+		return err
+	}
+	return os.Rename(tmp.Name(), path)
+}
+
+// snapshot reads the config currently on disk, including the sites and nodes stored
+// in their own directories, as a single TOML document. Secrets stay as saved (encrypted
+// or not). It returns nil if no config has been saved yet.
+func (f *FileConfigLoader) snapshot(path string) ([]byte, error) {
+	data, err := os.ReadFile(path)
+	if os.IsNotExist(err) {
+		return nil, nil
+	}
+	if err != nil {
+		return nil, err
+	}
+
+	var config Config
+	if err := toml.Unmarshal(data, &config); err != nil {
+		return nil, err
+	}
+	// As in Load, stored sites and nodes take precedence over any left in the config file
+	sites, err := f.LoadAllSites()
+	if err != nil {
+		return nil, err
+	}
+	if len(sites) > 0 {
+		config.Sites = sites
+	}
+	nodes, err := f.LoadAllNodes()
+	if err != nil {
+		return nil, err
+	}
+	if len(nodes) > 0 {
+		config.Nodes = nodes
+	}
+	return toml.Marshal(config)
+}
+
+// backup saves the config on disk as <path>.bak.1, shifting older backups up and
+// dropping any beyond keep. Nothing is rotated if the config hasn't changed since the last backup.
+func (f *FileConfigLoader) backup(path string, keep int) error {
+	data, err := f.snapshot(path)
+	if err != nil || data == nil {
+		return err
+	}
+	if previous, err := os.ReadFile(BackupPath(path, 1)); err == nil && f.sameConfig(previous, data) {
+		return nil
+	}
+
+	if err := os.Remove(BackupPath(path, keep)); err != nil && !os.IsNotExist(err) {
+		return err
+	}
+	for n := keep - 1; n >= 1; n-- {
+		if err := os.Rename(BackupPath(path, n), BackupPath(path, n+1)); err != nil && !os.IsNotExist(err) {
+			return err
+		}
+	}
+	// Backups hold every site's and node's secrets, so keep them private
+	return writeFileAtomic(BackupPath(path, 1), data, 0600)
+}
+
+// sameConfig reports whether two snapshots hold the same config. Encrypted secrets get a fresh
+// nonce on every save, so they are compared decrypted when the loader has the key.
+func (f *FileConfigLoader) sameConfig(a, b []byte) bool {
+	if bytes.Equal(a, b) {
+		return true
+	}
+	if f.cipher == nil {
+		return false
+	}
+
+	decrypted := make([][]byte, 2)
+	for i, data := range [][]byte{a, b} {
+		var config Config
+		if err := toml.Unmarshal(data, &config); err != nil {
+			return false
+		}
+		// Secrets from another key (e.g. before a passphrase change) don't decrypt, so differ
+		if err := config.decryptSecrets(f.cipher); err != nil {
+			return false
+		}
+		plain, err := toml.Marshal(config)
+		if err != nil {
+			return false
+		}
+		decrypted[i] = plain
+	}
+	return bytes.Equal(decrypted[0], decrypted[1])
+}
+
+// backupBeforeDelete backs up the last loaded or saved config before the first site or
+// node directory is removed, since those are deleted ahead of the save that follows
+func (f *FileConfigLoader) backupBeforeDelete() error {
+	if f.configPath == "" || f.deleted {
+		return nil
+	}
+	data, err := os.ReadFile(f.configPath)
+	if err != nil {
+		if os.IsNotExist(err) {
+			return nil
+		}
+		return err
+	}
+	var config Config
+	if err := toml.Unmarshal(data, &config); err != nil {
+		return err
+	}
+	if keep := config.Settings.BackupCount(); keep > 0 {
+		if err := f.backup(f.configPath, keep); err != nil {
+			return fmt.Errorf("failed to back up config: %w", err)
+		}
+	}
+	f.deleted = true
+	return nil
+}
+
+// ListBackups returns the backups of the config at path, most recent first
+func ListBackups(path string) ([]Backup, error) {
+	var backups []Backup
+	for n := 1; ; n++ {
+		backupPath := BackupPath(path, n)
+		info, err := os.Stat(backupPath)
+		if os.IsNotExist(err) {
+			return backups, nil
+		}
+		if err != nil {
+			return nil, err
+		}
+
+		config, err := readBackup(backupPath)
+		if err != nil {
+			return nil, err
+		}
+		backups = append(backups, Backup{
+			Number:  n,
+			Path:    backupPath,
+			ModTime: info.ModTime(),
+			Sites:   len(config.Sites),
+			Domains: len(config.Domains),
+			Nodes:   len(config.Nodes),
+		})
+	}
+}
+
+// readBackup parses a backup written by backup
+func readBackup(path string) (*Config, error) {
+	data, err := os.ReadFile(path)
+	if err != nil {
+		return nil, err
+	}
+	var config Config
+	if err := toml.Unmarshal(data, &config); err != nil {
+		return nil, fmt.Errorf("invalid backup %s: %w", path, err)
+	}
+	return &config, nil
+}
+
+// RestoreBackup replaces the config at path, and the sites and nodes stored next to it,
+// with backup n. The current config is backed up first, so a restore can itself be undone,
+// or copied to <path>.broken if it can't be read.
+func RestoreBackup(path string, n int) error {
+	config, err := readBackup(BackupPath(path, n))
+	if err != nil {
+		if os.IsNotExist(err) {
+			return fmt.Errorf("backup %d not found", n)
+		}
+		return err
+	}
+
+	f := &FileConfigLoader{dataDir: filepath.Dir(path)}
+	if err := f.backup(path, max(config.Settings.BackupCount(), 1)); err != nil {
+		// A config that doesn't parse can't be snapshotted, so keep the file as it is
+		data, readErr := os.ReadFile(path)
+		if readErr != nil {
+			return fmt.Errorf("failed to back up current config: %w", err)
+		}
+		if err := writeFileAtomic(path+".broken", data, 0600); err != nil {
+			return fmt.Errorf("failed to back up current config: %w", err)
+		}
+	}
+
+	// Sites and nodes missing from the backup must not be loaded back from their directories
+	for _, dir := range []string{"sites", "nodes"} {
+		if err := os.RemoveAll(filepath.Join(f.dataDir, dir)); err != nil {
+			return err
+		}
+	}
+	// Secrets in the backup are already in their saved form
+	return f.write(path, config)
+}
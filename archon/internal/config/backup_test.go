@@ -0,0 +1,142 @@
+package config
+
+import (
+	"os"
+	"path/filepath"
+	"testing"
+
+	"github.com/google/uuid"
+
+	"github.com/BlueBeard63/archon/internal/models"
+)
+
+func TestBackupRotation(t *testing.T) {
+	path := filepath.Join(t.TempDir(), "config.toml")
+	loader := NewFileConfigLoader()
+	cfg := DefaultConfig()
+	cfg.Settings.ConfigBackups = 2
+	ids := make(map[string]uuid.UUID)
+
+	save := func(sites ...string) {
+		t.Helper()
+		cfg.Sites = nil
+		for _, name := range sites {
+			if _, ok := ids[name]; !ok {
+				ids[name] = uuid.New()
+			}
+			cfg.Sites = append(cfg.Sites, models.Site{ID: ids[name], Name: name})
+		}
+		if err := loader.Save(path, cfg); err != nil {
+			t.Fatalf("Save() error = %v", err)
+		}
+	}
+	backupSites := func() []int {
+		t.Helper()
+		backups, err := ListBackups(path)
+		if err != nil {
+			t.Fatalf("ListBackups() error = %v", err)
+		}
+		var sites []int
+		for _, backup := range backups {
+			sites = append(sites, backup.Sites)
+		}
+		return sites
+	}
+
+	save("blog")
+	if got := backupSites(); len(got) != 0 {
+		t.Fatalf("first save made backups %v, want none", got)
+	}
+
+	save("blog", "shop")
+	save("blog", "shop", "wiki")
+	if got := backupSites(); len(got) != 2 || got[0] != 2 || got[1] != 1 {
+		t.Fatalf("backup site counts = %v, want [2 1]", got)
+	}
+
+	// Saving the same config repeatedly keeps one copy of it rather than pushing out older backups
+	save("blog", "shop", "wiki")
+	save("blog", "shop", "wiki")
+	save("blog")
+	if got := backupSites(); len(got) != 2 || got[0] != 3 || got[1] != 2 {
+		t.Fatalf("backup site counts = %v, want [3 2]", got)
+	}
+
+	save("blog", "shop")
+	if got := backupSites(); len(got) != 2 || got[0] != 1 || got[1] != 3 {
+		t.Fatalf("backup site counts = %v, want only the 2 most recent, [1 3]", got)
+	}
+	if _, err := os.Stat(BackupPath(path, 3)); !os.IsNotExist(err) {
+		t.Errorf("backup 3 exists, want it dropped")
+	}
+}
+
+func TestBackupSkipsUnchangedEncryptedConfig(t *testing.T) {
+	path := filepath.Join(t.TempDir(), "config.toml")
+	loader := NewFileConfigLoader()
+	loader.SetPassphrase("correct horse")
+	cfg := DefaultConfig()
+	cfg.Sites = []models.Site{{ID: uuid.New(), Name: "blog", DockerToken: "secret"}}
+
+	// Each save encrypts the token with a new nonce, but the config is the same
+	for i := 0; i < 3; i++ {
+		if err := loader.Save(path, cfg); err != nil {
+			t.Fatalf("Save() error = %v", err)
+		}
+	}
+	backups, err := ListBackups(path)
+	if err != nil {
+		t.Fatalf("ListBackups() error = %v", err)
+	}
+	if len(backups) != 1 {
+		t.Errorf("got %d backups after saving the same config 3 times, want 1", len(backups))
+	}
+}
+
+func TestRestoreBackup(t *testing.T) {
+	path := filepath.Join(t.TempDir(), "config.toml")
+	loader := NewFileConfigLoader()
+	cfg := DefaultConfig()
+	cfg.Sites = []models.Site{{ID: uuid.New(), Name: "blog"}, {ID: uuid.New(), Name: "shop"}}
+	cfg.Nodes = []models.Node{{ID: uuid.New(), Name: "edge"}}
+	if err := loader.Save(path, cfg); err != nil {
+		t.Fatal(err)
+	}
+
+	// Delete a site and the node, then roll back
+	cfg.Sites = cfg.Sites[:1]
+	cfg.Nodes = nil
+	if err := loader.DeleteSite("shop", "unknown"); err != nil {
+		t.Fatal(err)
+	}
+	if err := loader.DeleteNode("edge"); err != nil {
+		t.Fatal(err)
+	}
+	if err := loader.Save(path, cfg); err != nil {
+		t.Fatal(err)
+	}
+
+	if err := RestoreBackup(path, 1); err != nil {
+		t.Fatalf("RestoreBackup() error = %v", err)
+	}
+	restored, err := NewFileConfigLoader().Load(path)
+	if err != nil {
+		t.Fatalf("Load() error = %v", err)
+	}
+	if len(restored.Sites) != 2 || len(restored.Nodes) != 1 {
+		t.Errorf("restored %d sites and %d nodes, want 2 and 1", len(restored.Sites), len(restored.Nodes))
+	}
+
+	// The config the restore replaced becomes the most recent backup
+	backups, err := ListBackups(path)
+	if err != nil {
+		t.Fatal(err)
+	}
+	if len(backups) == 0 || backups[0].Sites != 1 || backups[0].Nodes != 0 {
+		t.Errorf("backups = %+v, want the replaced config first", backups)
+	}
+
+	if err := RestoreBackup(path, 9); err == nil {
+		t.Error("RestoreBackup() of a missing backup should fail")
+	}
+}
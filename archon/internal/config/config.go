@@ -17,11 +17,65 @@ type Settings struct {
 	HealthCheckIntervalSecs int    `toml:"health_check_interval_secs"`
 	DefaultDnsTTL           int    `toml:"default_dns_ttl"`
 	Theme                   string `toml:"theme"`
+	ThemeFile               string `toml:"theme_file,omitempty"`           // TOML file of custom colors, applied over the theme
 	CloudflareAPIToken      string `toml:"cloudflare_api_token,omitempty"` // Global default
 	Route53AccessKey        string `toml:"route53_access_key,omitempty"`   // Global default
 	Route53SecretKey        string `toml:"route53_secret_key,omitempty"`   // Global default
+	DebugHTTP               bool   `toml:"debug_http,omitempty"`           // Log node API requests/responses (secrets redacted) to archon.log
+	LogBufferLines          int    `toml:"log_buffer_lines,omitempty"`     // Max container log lines kept per site (default 1000)
+
+	// Node request retries (zero values use the defaults, except retry_jitter = 0, which turns jitter off)
+	RetryMaxAttempts int      `toml:"retry_max_attempts,omitempty"`  // Total attempts per request (1 disables retries, default 3)
+	RetryBaseDelayMs int      `toml:"retry_base_delay_ms,omitempty"` // Delay before the first retry, doubled each time (default 500)
+	RetryJitter      *float64 `toml:"retry_jitter,omitempty"`        // Fraction of each delay randomised, 0-1 (default 0.2, 0 disables jitter)
+
+	// DNS provider rate limiting
+	DNSRateLimitRetries int `toml:"dns_rate_limit_retries,omitempty"` // Retries after an HTTP 429 from the DNS provider (default 3, -1 disables retries)
+
+	// How many deploys, stops, restarts and DNS syncs/pushes run at once; the rest wait for a free slot
+	MaxConcurrentOperations int `toml:"max_concurrent_operations,omitempty"` // Default 4
+
+	// Host ports suggested for new sites: the lowest one unused on the chosen node
+	PortRangeStart int `toml:"port_range_start,omitempty"` // Default 8000
+	PortRangeEnd   int `toml:"port_range_end,omitempty"`   // Default 9000
+
+	// Node health check latency is shown yellow from the warn threshold and red from the critical one
+	LatencyWarnMs     int `toml:"latency_warn_ms,omitempty"`     // Default 150
+	LatencyCriticalMs int `toml:"latency_critical_ms,omitempty"` // Default 500
+
+	// Previous versions kept as config.toml.bak.1..N before each save (default 5, -1 disables)
+	ConfigBackups int `toml:"config_backups,omitempty"`
+
+	// Also show errors, such as failed deploys, as desktop notifications while Archon's terminal isn't focused
+	DesktopNotifications bool `toml:"desktop_notifications,omitempty"`
+
+	// URL a JSON event is posted to when a deploy succeeds or fails, a node goes offline or a DNS sync completes
+	WebhookURL string `toml:"webhook_url,omitempty"`
+
+	// Action name -> key or comma-separated keys; unset actions keep their default keys
+	Keybindings map[string]string `toml:"keybindings,omitempty"`
+
+	// Audit log of deploys, edits, deletes and DNS changes as JSON Lines (read at startup)
+	AuditLog      string `toml:"audit_log,omitempty"`        // Path, relative to this file (default audit.jsonl, "off" disables)
+	AuditLogMaxMB int    `toml:"audit_log_max_mb,omitempty"` // Size at which the log is rotated (default 10)
+
+	// "keyring" keeps node API keys and DNS provider credentials in the OS keyring, with only a
+	// reference in the config; left empty (or without a keyring) they are stored inline
+	SecretStorage string `toml:"secret_storage,omitempty"`
+
+	// Secret encryption (managed by FileConfigLoader, not edited by hand)
+	EncryptionKDF       string `toml:"encryption_kdf,omitempty"`        // Key derivation function used for the passphrase
+	EncryptionKDFParams string `toml:"encryption_kdf_params,omitempty"` // KDF parameters (e.g., "m=65536,t=3,p=4" for argon2id)
+	EncryptionSalt      string `toml:"encryption_salt,omitempty"`       // Base64 KDF salt
+	EncryptionCheck     string `toml:"encryption_check,omitempty"`      // Encrypted known value to verify the passphrase
 }
 
+// DefaultAuditLog is the audit log file, next to the config, when the audit_log setting is unset
+const DefaultAuditLog = "audit.jsonl"
+
+// AuditLogOff is the audit_log setting that turns the audit log off
+const AuditLogOff = "off"
+
 // DefaultSettings returns default configuration settings
 func DefaultSettings() Settings {
 	return Settings{
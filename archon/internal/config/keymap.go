@@ -0,0 +1,193 @@
+package config
+
+import (
+	"sort"
+	"strings"
+)
+
+// KeyAction names an action that can be rebound in the [settings.keybindings] section
+type KeyAction string
+
+const (
+	// Global
+	KeyQuit          KeyAction = "quit"
+	KeyHelp          KeyAction = "help"
+	KeyDashboard     KeyAction = "dashboard"
+	KeySave          KeyAction = "save"
+	KeyRefresh       KeyAction = "refresh"
+	KeyReloadConfig  KeyAction = "reload_config"
+	KeyPalette       KeyAction = "palette"
+	KeyOperations    KeyAction = "operations"
+	KeyNotifications KeyAction = "notifications"
+	KeyReveal        KeyAction = "reveal_secrets" // Show masked secrets in forms until leaving the screen
+	KeyForward       KeyAction = "forward"        // Re-enter the screen just left with Esc
+
+	// Navigation
+	KeyUp     KeyAction = "up"
+	KeyDown   KeyAction = "down"
+	KeyTop    KeyAction = "top"
+	KeyBottom KeyAction = "bottom"
+
+	// Lists
+	KeyCreate KeyAction = "create"
+	KeyEdit   KeyAction = "edit"
+	KeyDelete KeyAction = "delete"
+	KeyReload KeyAction = "reload" // Refresh the current screen's data
+
+	// Sites
+	KeyDeploy        KeyAction = "deploy"
+	KeyPreviewDeploy KeyAction = "preview_deploy"
+	KeyDetails       KeyAction = "details"
+	KeyLogs          KeyAction = "logs"
+	KeyFollowLogs    KeyAction = "follow_logs"
+	KeyExportLogs    KeyAction = "export_logs"
+	KeyStartStop     KeyAction = "start_stop"
+	KeySetupDNS      KeyAction = "setup_dns"
+	KeyFilterTag     KeyAction = "filter_tag"
+	KeySearch        KeyAction = "search"
+	KeySort          KeyAction = "sort"
+	KeySortReverse   KeyAction = "sort_reverse"
+	KeyRestore       KeyAction = "restore"
+	KeyRollback      KeyAction = "rollback" // Redeploy the site's last known-good image
+	KeyCopy          KeyAction = "copy" // Copy the selected item's endpoint, ID or name to the clipboard
+
+	// Site forms
+	KeyBlueGreen KeyAction = "blue_green" // Toggle blue/green deploys for a container site
+
+	// Domains
+	KeySyncDNS          KeyAction = "sync_dns"
+	KeyPushDNS          KeyAction = "push_dns"
+	KeyPushDNSPrune     KeyAction = "push_dns_prune"
+	KeyCheckCert        KeyAction = "check_cert"
+	KeyCheckPropagation KeyAction = "check_propagation" // Query public resolvers for the DNS records screen's records
+	KeyAddRecord        KeyAction = "add_record"        // Add a record on the DNS records screen
+
+	// Nodes
+	KeyViewConfig KeyAction = "view_config"
+	KeyStopAll    KeyAction = "stop_all"
+	KeyDeployAll  KeyAction = "deploy_all"
+
+	// Operations
+	KeyCancelOperation KeyAction = "cancel_operation"
+	KeyRetryOperation  KeyAction = "retry_operation"
+)
+
+// DefaultKeybindings returns the built-in keys for every action
+func DefaultKeybindings() map[KeyAction][]string {
+	return map[KeyAction][]string{
+		KeyQuit:          {"q"},
+		KeyHelp:          {"?"},
+		KeyDashboard:     {"0"},
+		KeySave:          {"ctrl+s"},
+		KeyRefresh:       {"f5"},
+		KeyReloadConfig:  {"ctrl+l"},
+		KeyPalette:       {"ctrl+p"},
+		KeyOperations:    {"ctrl+o"},
+		KeyNotifications: {"ctrl+n"},
+		KeyReveal:        {"ctrl+r"},
+		KeyForward:       {"]", "alt+right"},
+
+		KeyUp:     {"up", "k"},
+		KeyDown:   {"down", "j"},
+		KeyTop:    {"home", "g"},
+		KeyBottom: {"end", "G"},
+
+		KeyCreate: {"n", "c"},
+		KeyEdit:   {"e"},
+		KeyDelete: {"d"},
+		KeyReload: {"r"},
+
+		KeyDeploy:        {" ", "enter"},
+		KeyPreviewDeploy: {"p"},
+		KeyDetails:       {"i"},
+		KeyLogs:          {"l"},
+		KeyFollowLogs:    {"f"},
+		KeyExportLogs:    {"x"},
+		KeyStartStop:     {"s"},
+		KeySetupDNS:      {"r"},
+		KeyFilterTag:     {"t"},
+		KeySearch:        {"/"},
+		KeySort:          {"o"},
+		KeySortReverse:   {"O"},
+		KeyRestore:       {"u"},
+		KeyRollback:      {"b"},
+		KeyCopy:          {"y"},
+
+		KeyBlueGreen: {"ctrl+x"},
+
+		KeySyncDNS:          {"s"},
+		KeyPushDNS:          {"p"},
+		KeyPushDNSPrune:     {"P"},
+		KeyCheckCert:        {"t"},
+		KeyCheckPropagation: {"v"},
+		KeyAddRecord:        {"a"},
+
+		KeyViewConfig: {"v", "enter"},
+		KeyStopAll:    {"S"},
+		KeyDeployAll:  {"D"},
+
+		KeyCancelOperation: {"c"},
+		KeyRetryOperation:  {"r"},
+	}
+}
+
+// Keymap resolves key presses to actions, using configured keys where set and defaults elsewhere
+type Keymap struct {
+	bindings map[KeyAction][]string
+}
+
+// NewKeymap builds a keymap from the [settings.keybindings] section, which maps action names
+// to a key or a comma-separated list of keys (e.g. down = "n,down"). A configured action
+// replaces its default keys. Unknown action names are ignored and returned sorted.
+func NewKeymap(overrides map[string]string) (*Keymap, []string) {
+	bindings := DefaultKeybindings()
+	var unknown []string
+
+	for name, value := range overrides {
+		action := KeyAction(strings.ToLower(strings.TrimSpace(name)))
+		if _, ok := bindings[action]; !ok {
+			unknown = append(unknown, name)
+			continue
+		}
+		if keys := parseKeys(value); len(keys) > 0 {
+			bindings[action] = keys
+		}
+	}
+
+	sort.Strings(unknown)
+	return &Keymap{bindings: bindings}, unknown
+}
+
+// parseKeys splits a comma-separated key list, keeping "," and " " usable as keys
+func parseKeys(value string) []string {
+	if value == "," || value == " " {
+		return []string{value}
+	}
+	var keys []string
+	for _, key := range strings.Split(value, ",") {
+		if key = strings.TrimSpace(key); key != "" {
+			if key == "space" {
+				key = " "
+			}
+			keys = append(keys, key)
+		}
+	}
+	return keys
+}
+
+// Matches returns true if key (as reported by tea.KeyMsg.String) is bound to action
+// A nil keymap uses the default bindings
+func (k *Keymap) Matches(key string, action KeyAction) bool {
+	var keys []string
+	if k == nil {
+		keys = DefaultKeybindings()[action]
+	} else {
+		keys = k.bindings[action]
+	}
+	for _, bound := range keys {
+		if bound == key {
+			return true
+		}
+	}
+	return false
+}
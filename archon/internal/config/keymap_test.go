@@ -0,0 +1,62 @@
+package config
+
+import (
+	"reflect"
+	"testing"
+)
+
+func TestNewKeymap(t *testing.T) {
+	keymap, unknown := NewKeymap(map[string]string{
+		"down":   "n, down",
+		"up":     "e",
+		"Create": "a",
+		"deploy": "space",
+		"jump":   "x",
+		"quit":   "",
+	})
+
+	if want := []string{"jump"}; !reflect.DeepEqual(unknown, want) {
+		t.Errorf("unknown = %v, want %v", unknown, want)
+	}
+
+	tests := []struct {
+		name   string
+		key    string
+		action KeyAction
+		want   bool
+	}{
+		{name: "configured key is bound", key: "n", action: KeyDown, want: true},
+		{name: "second configured key is bound", key: "down", action: KeyDown, want: true},
+		{name: "configured action loses its defaults", key: "j", action: KeyDown, want: false},
+		{name: "action names are case-insensitive", key: "a", action: KeyCreate, want: true},
+		{name: "space is spelled out", key: " ", action: KeyDeploy, want: true},
+		{name: "unmapped action keeps defaults", key: "d", action: KeyDelete, want: true},
+		{name: "empty binding keeps defaults", key: "q", action: KeyQuit, want: true},
+	}
+
+	for _, tt := range tests {
+		t.Run(tt.name, func(t *testing.T) {
+			if got := keymap.Matches(tt.key, tt.action); got != tt.want {
+				t.Errorf("Matches(%q, %s) = %v, want %v", tt.key, tt.action, got, tt.want)
+			}
+		})
+	}
+}
+
+func TestNilKeymapUsesDefaults(t *testing.T) {
+	var keymap *Keymap
+	if !keymap.Matches("k", KeyUp) || keymap.Matches("x", KeyUp) {
+		t.Error("nil keymap should match only the default keys")
+	}
+}
+
+func TestBlueGreenKeyIsFreeOutsideForms(t *testing.T) {
+	// Site forms skip the global keys, but the toggle shouldn't reuse one users learn elsewhere
+	for _, key := range DefaultKeybindings()[KeyBlueGreen] {
+		for _, global := range []KeyAction{KeyOperations, KeyNotifications, KeyPalette, KeyReveal, KeySave} {
+			if (*Keymap)(nil).Matches(key, global) {
+				t.Errorf("blue/green key %q is also bound to %s", key, global)
+			}
+		}
+	}
+}
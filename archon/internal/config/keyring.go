@@ -0,0 +1,222 @@
+package config
+
+import (
+	"errors"
+	"fmt"
+	"os/exec"
+	"runtime"
+	"strings"
+	"sync"
+
+	"github.com/BlueBeard63/archon/internal/models"
+)
+
+// KeyringPrefix marks a config value as a reference to a secret kept in the OS keyring
+const KeyringPrefix = "keyring:"
+
+// SecretStorageKeyring is the secret_storage setting that keeps credentials in the OS keyring
+const SecretStorageKeyring = "keyring"
+
+// keyringService is the service name Archon's secrets are filed under in the OS keyring
+const keyringService = "archon"
+
+// ErrKeyringUnavailable is returned when loading a config that references the OS keyring
+// on a system where no keyring can be used
+var ErrKeyringUnavailable = errors.New("config references secrets in the OS keyring, but no keyring is available")
+
+// Keyring stores secrets in the operating system's credential store, by account name
+type Keyring interface {
+	Get(account string) (string, error)
+	Set(account, secret string) error
+}
+
+// SystemKeyring returns the OS keyring, or nil if there is none: the login keychain on macOS
+// (through security) and the Secret Service on Linux (through secret-tool from libsecret)
+func SystemKeyring() Keyring {
+	switch runtime.GOOS {
+	case "darwin":
+		if _, err := exec.LookPath("security"); err == nil {
+			return macKeychain{}
+		}
+	case "linux", "freebsd", "openbsd":
+		if _, err := exec.LookPath("secret-tool"); err == nil {
+			return secretService{}
+		}
+	}
+	return nil
+}
+
+// osKeyring returns the keyring credentials are stored in, looking up the OS keyring on first use
+func (f *FileConfigLoader) osKeyring() Keyring {
+	if f.keyring == nil {
+		if kr := SystemKeyring(); kr != nil {
+			f.keyring = &cachedKeyring{Keyring: kr, known: make(map[string]string)}
+		}
+	}
+	return f.keyring
+}
+
+// cachedKeyring remembers the secrets it has read or stored, so saving an unchanged
+// config doesn't write every credential to the OS keyring again
+type cachedKeyring struct {
+	Keyring
+	mu    sync.Mutex
+	known map[string]string
+}
+
+func (k *cachedKeyring) Get(account string) (string, error) {
+	k.mu.Lock()
+	defer k.mu.Unlock()
+	if secret, ok := k.known[account]; ok {
+		return secret, nil
+	}
+	secret, err := k.Keyring.Get(account)
+	if err == nil {
+		k.known[account] = secret
+	}
+	return secret, err
+}
+
+func (k *cachedKeyring) Set(account, secret string) error {
+	k.mu.Lock()
+	defer k.mu.Unlock()
+	if known, ok := k.known[account]; ok && known == secret {
+		return nil
+	}
+	if err := k.Keyring.Set(account, secret); err != nil {
+		return err
+	}
+	k.known[account] = secret
+	return nil
+}
+
+// IsKeyringRef returns true if the value refers to a secret in the OS keyring
+func IsKeyringRef(value string) bool {
+	return strings.HasPrefix(value, KeyringPrefix)
+}
+
+// macKeychain stores secrets as generic passwords in the macOS login keychain
+type macKeychain struct{}
+
+func (macKeychain) Get(account string) (string, error) {
+	out, err := exec.Command("security", "find-generic-password", "-s", keyringService, "-a", account, "-w").Output()
+	if err != nil {
+		return "", fmt.Errorf("keychain lookup of %s failed: %w", account, err)
+	}
+	return strings.TrimSuffix(string(out), "\n"), nil
+}
+
+func (k macKeychain) Set(account, secret string) error {
+	// Pass the command on stdin so the secret doesn't show up in the process list
+	cmd := exec.Command("security", "-i")
+	cmd.Stdin = strings.NewReader(fmt.Sprintf("add-generic-password -U -s %s -a %s -w %s\n",
+		securityQuote(keyringService), securityQuote(account), securityQuote(secret)))
+	if out, err := cmd.CombinedOutput(); err != nil {
+		return fmt.Errorf("keychain store of %s failed: %w %s", account, err, strings.TrimSpace(string(out)))
+	}
+
+	// security -i doesn't fail when a command does, so read the secret back
+	if stored, err := k.Get(account); err != nil || stored != secret {
+		return fmt.Errorf("keychain store of %s failed", account)
+	}
+	return nil
+}
+
+// securityQuote quotes an argument for the command line read by security -i
+func securityQuote(value string) string {
+	return `"` + strings.NewReplacer(`\`, `\\`, `"`, `\"`).Replace(value) + `"`
+}
+
+// secretService stores secrets in the freedesktop Secret Service (GNOME Keyring, KWallet)
+type secretService struct{}
+
+func (secretService) Get(account string) (string, error) {
+	out, err := exec.Command("secret-tool", "lookup", "service", keyringService, "account", account).Output()
+	if err != nil {
+		return "", fmt.Errorf("keyring lookup of %s failed: %w", account, err)
+	}
+	return string(out), nil
+}
+
+func (secretService) Set(account, secret string) error {
+	// secret-tool reads the secret from stdin
+	cmd := exec.Command("secret-tool", "store", "--label=Archon "+account, "service", keyringService, "account", account)
+	cmd.Stdin = strings.NewReader(secret)
+	if out, err := cmd.CombinedOutput(); err != nil {
+		return fmt.Errorf("keyring store of %s failed: %w %s", account, err, strings.TrimSpace(string(out)))
+	}
+	return nil
+}
+
+// keyringField is a credential that can be moved to the keyring, with the account it is stored under
+type keyringField struct {
+	account string
+	value   *string
+}
+
+// keyringFields returns the node API keys and DNS provider credentials, which are stored in
+// the keyring when secret_storage = "keyring". Accounts use IDs so renames keep working.
+func (c *Config) keyringFields() []keyringField {
+	fields := []keyringField{
+		{"settings/cloudflare_api_token", &c.Settings.CloudflareAPIToken},
+		{"settings/route53_secret_key", &c.Settings.Route53SecretKey},
+	}
+	for i := range c.Domains {
+		id := c.Domains[i].ID.String()
+		fields = append(fields,
+			keyringField{"domain/" + id + "/api_token", &c.Domains[i].DnsProvider.APIToken},
+			keyringField{"domain/" + id + "/secret_key", &c.Domains[i].DnsProvider.SecretKey},
+		)
+	}
+	for i := range c.Nodes {
+		fields = append(fields, keyringField{"node/" + c.Nodes[i].ID.String() + "/api_key", &c.Nodes[i].APIKey})
+	}
+	return fields
+}
+
+// HasKeyringRefs returns true if any credential in the config is kept in the OS keyring
+func (c *Config) HasKeyringRefs() bool {
+	for _, field := range c.keyringFields() {
+		if IsKeyringRef(*field.value) {
+			return true
+		}
+	}
+	return false
+}
+
+// storeSecretsInKeyring returns a copy of the config with credentials moved to the keyring and
+// replaced by references. A credential the keyring won't take stays inline, as it would without one.
+// The original config (shared with the running app) is left untouched.
+func (c *Config) storeSecretsInKeyring(kr Keyring) *Config {
+	stored := *c
+	stored.Domains = append([]models.Domain(nil), c.Domains...)
+	stored.Nodes = append([]models.Node(nil), c.Nodes...)
+
+	for _, field := range stored.keyringFields() {
+		if *field.value == "" || IsKeyringRef(*field.value) {
+			continue
+		}
+		if err := kr.Set(field.account, *field.value); err == nil {
+			*field.value = KeyringPrefix + field.account
+		}
+	}
+	return &stored
+}
+
+// resolveKeyringRefs replaces keyring references in the config with the secrets they point to
+func (c *Config) resolveKeyringRefs(kr Keyring) error {
+	for _, field := range c.keyringFields() {
+		if !IsKeyringRef(*field.value) {
+			continue
+		}
+		if kr == nil {
+			return ErrKeyringUnavailable
+		}
+		secret, err := kr.Get(strings.TrimPrefix(*field.value, KeyringPrefix))
+		if err != nil {
+			return err
+		}
+		*field.value = secret
+	}
+	return nil
+}
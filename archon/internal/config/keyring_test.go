@@ -0,0 +1,78 @@
+package config
+
+import (
+	"errors"
+	"os"
+	"path/filepath"
+	"strings"
+	"testing"
+
+	"github.com/google/uuid"
+
+	"github.com/BlueBeard63/archon/internal/models"
+)
+
+// memoryKeyring is a Keyring held in memory
+type memoryKeyring map[string]string
+
+func (k memoryKeyring) Get(account string) (string, error) {
+	secret, ok := k[account]
+	if !ok {
+		return "", errors.New("not found")
+	}
+	return secret, nil
+}
+
+func (k memoryKeyring) Set(account, secret string) error {
+	k[account] = secret
+	return nil
+}
+
+func TestKeyringSecretStorage(t *testing.T) {
+	path := filepath.Join(t.TempDir(), "config.toml")
+	kr := memoryKeyring{}
+	node := models.Node{ID: uuid.New(), Name: "edge", APIKey: "node-key"}
+	domain := models.Domain{ID: uuid.New(), Name: "example.com", DnsProvider: models.DnsProvider{APIToken: "cf-token"}}
+
+	cfg := DefaultConfig()
+	cfg.Settings.SecretStorage = SecretStorageKeyring
+	cfg.Nodes = []models.Node{node}
+	cfg.Domains = []models.Domain{domain}
+
+	saver := &FileConfigLoader{keyring: kr}
+	if err := saver.Save(path, cfg); err != nil {
+		t.Fatalf("Save() error = %v", err)
+	}
+	if cfg.Nodes[0].APIKey != "node-key" {
+		t.Errorf("Save() changed the in-memory API key to %q", cfg.Nodes[0].APIKey)
+	}
+
+	// Only references are written to disk
+	var written []string
+	filepath.Walk(filepath.Dir(path), func(file string, info os.FileInfo, err error) error {
+		if err == nil && !info.IsDir() {
+			data, _ := os.ReadFile(file)
+			written = append(written, string(data))
+		}
+		return nil
+	})
+	all := strings.Join(written, "\n")
+	if strings.Contains(all, "node-key") || strings.Contains(all, "cf-token") {
+		t.Errorf("secrets were written to disk:\n%s", all)
+	}
+	if kr["node/"+node.ID.String()+"/api_key"] != "node-key" || kr["domain/"+domain.ID.String()+"/api_token"] != "cf-token" {
+		t.Errorf("keyring = %v, want the node API key and DNS token", kr)
+	}
+
+	loaded, err := (&FileConfigLoader{keyring: kr}).Load(path)
+	if err != nil {
+		t.Fatalf("Load() error = %v", err)
+	}
+	if loaded.Nodes[0].APIKey != "node-key" || loaded.Domains[0].DnsProvider.APIToken != "cf-token" {
+		t.Errorf("Load() did not resolve keyring references: %+v %+v", loaded.Nodes[0], loaded.Domains[0].DnsProvider)
+	}
+
+	if _, err := NewFileConfigLoader().Load(path); SystemKeyring() == nil && !errors.Is(err, ErrKeyringUnavailable) {
+		t.Errorf("Load() without a keyring error = %v, want ErrKeyringUnavailable", err)
+	}
+}
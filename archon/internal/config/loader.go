@@ -1,6 +1,8 @@
 package config
 
 import (
+	"encoding/base64"
+	"fmt"
 	"os"
 	"path/filepath"
 	"runtime"
@@ -10,16 +12,34 @@ import (
 )
 
 // FileConfigLoader implements ConfigLoader using file-based TOML storage
-type FileConfigLoader struct{}
+type FileConfigLoader struct {
+	passphrase string        // Secrets are encrypted at rest when set
+	kdf        kdf           // KDF the salt is used with; Argon2id for new salts
+	salt       []byte        // KDF salt from the loaded config, or generated on the first encrypted save
+	check      string        // Encrypted check value stored alongside the salt
+	cipher     *SecretCipher // Derived from passphrase and salt
+	dataDir    string        // Holds the sites/ and nodes/ trees; the directory of the last loaded or saved config
+	configPath string        // The last loaded or saved config, backed up before a site or node is deleted
+	deleted    bool          // A site or node was deleted since the last save, after backing up the config
+	keyring    Keyring       // OS keyring for secret_storage = "keyring", looked up on first use
+}
 
 // NewFileConfigLoader creates a new file-based config loader
 func NewFileConfigLoader() *FileConfigLoader {
 	return &FileConfigLoader{}
 }
 
+// SetPassphrase sets the passphrase used to decrypt secrets on load and encrypt them on save
+func (f *FileConfigLoader) SetPassphrase(passphrase string) {
+	f.passphrase = passphrase
+	f.cipher = nil
+}
+
 // Load reads configuration from a TOML file and aggregates directory-based storage
 func (f *FileConfigLoader) Load(path string) (*Config, error) {
 	var config Config
+	f.dataDir = filepath.Dir(path)
+	f.configPath = path
 
 	// Check if legacy config file exists
 	data, err := os.ReadFile(path)
@@ -49,6 +69,24 @@ func (f *FileConfigLoader) Load(path string) (*Config, error) {
 		config.Nodes = nodes
 	}
 
+	// Decrypt secrets transparently; plaintext values from older configs pass through unchanged
+	if config.Settings.EncryptionSalt != "" || config.HasEncryptedSecrets() {
+		sc, err := f.unlock(&config.Settings)
+		if err != nil {
+			return nil, err
+		}
+		if err := config.decryptSecrets(sc); err != nil {
+			return nil, err
+		}
+	}
+
+	// Fetch secrets kept in the OS keyring
+	if config.HasKeyringRefs() {
+		if err := config.resolveKeyringRefs(f.osKeyring()); err != nil {
+			return nil, err
+		}
+	}
+
 	// If config is completely empty, initialize with defaults
 	if config.Version == "" {
 		config.Version = "1.0.0"
@@ -67,7 +105,47 @@ func (f *FileConfigLoader) Save(path string, config *Config) error {
 	if err := os.MkdirAll(filepath.Dir(path), 0755); err != nil {
 		return err
 	}
+	f.dataDir = filepath.Dir(path)
+	f.configPath = path
+
+	// Keep the previous versions so a bad save can be rolled back. After a delete the
+	// config was already backed up, and what's on disk is only partly deleted.
+	if keep := config.Settings.BackupCount(); keep > 0 && !f.deleted {
+		if err := f.backup(path, keep); err != nil {
+			return fmt.Errorf("failed to back up config: %w", err)
+		}
+	}
+	f.deleted = false
+
+	// Move credentials to the OS keyring when asked to, keeping them inline if there is none
+	if config.Settings.SecretStorage == SecretStorageKeyring {
+		if kr := f.osKeyring(); kr != nil {
+			config = config.storeSecretsInKeyring(kr)
+		}
+	}
+
+	// Encrypt secrets when a passphrase is set
+	if f.passphrase != "" {
+		sc, err := f.encryptionCipher()
+		if err != nil {
+			return err
+		}
+		config, err = config.encryptSecrets(sc)
+		if err != nil {
+			return err
+		}
+		config.Settings.EncryptionKDF = f.kdf.name
+		config.Settings.EncryptionKDFParams = f.kdf.params()
+		config.Settings.EncryptionSalt = base64.StdEncoding.EncodeToString(f.salt)
+		config.Settings.EncryptionCheck = f.check
+	}
+
+	return f.write(path, config)
+}
 
+// write stores config as given: domains and settings in the config file at path,
+// and each site and node in its own directory
+func (f *FileConfigLoader) write(path string, config *Config) error {
 	// Create a copy of config without sites and nodes (stored separately)
 	legacyConfig := Config{
 		Version:  config.Version,
@@ -83,7 +161,7 @@ func (f *FileConfigLoader) Save(path string, config *Config) error {
 		return err
 	}
 
-	if err := os.WriteFile(path, data, 0644); err != nil {
+	if err := writeFileAtomic(path, data, 0644); err != nil {
 		return err
 	}
 
@@ -177,10 +255,45 @@ func GetArchonConfigDir() (string, error) {
 	return filepath.Join(configDir, "archon"), nil
 }
 
+// storageDir returns the directory holding the sites/ and nodes/ trees
+// Sites and nodes live next to the config file, so separate configs keep separate data
+func (f *FileConfigLoader) storageDir() (string, error) {
+	if f.dataDir != "" {
+		return f.dataDir, nil
+	}
+	return GetArchonConfigDir()
+}
+
+// CheckWritable verifies that the config file and its directory can be written,
+// creating the directory if needed, so changes aren't lost when they are saved
+func CheckWritable(path string) error {
+	dir := filepath.Dir(path)
+	if err := os.MkdirAll(dir, 0755); err != nil {
+		return fmt.Errorf("cannot create config directory %s: %w", dir, err)
+	}
+
+	probe, err := os.CreateTemp(dir, ".archon-write-check-*")
+	if err != nil {
+		return fmt.Errorf("config directory %s is not writable: %w", dir, err)
+	}
+	probe.Close()
+	os.Remove(probe.Name())
+
+	// An existing config file must be writable too
+	file, err := os.OpenFile(path, os.O_WRONLY, 0)
+	if err != nil {
+		if os.IsNotExist(err) {
+			return nil
+		}
+		return fmt.Errorf("config file %s is not writable: %w", path, err)
+	}
+	return file.Close()
+}
+
 // SaveSite saves a single site to its directory structure
 // Path: ~/.config/archon/sites/[domain]/[subdomain]/[siteName]/config.toml
 func (f *FileConfigLoader) SaveSite(site *models.Site, domainName string) error {
-	baseDir, err := GetArchonConfigDir()
+	baseDir, err := f.storageDir()
 	if err != nil {
 		return err
 	}
@@ -202,12 +315,12 @@ func (f *FileConfigLoader) SaveSite(site *models.Site, domainName string) error
 	}
 
 	// Write to file
-	return os.WriteFile(sitePath, data, 0644)
+	return writeFileAtomic(sitePath, data, 0644)
 }
 
 // LoadAllSites loads all sites from the directory structure
 func (f *FileConfigLoader) LoadAllSites() ([]models.Site, error) {
-	baseDir, err := GetArchonConfigDir()
+	baseDir, err := f.storageDir()
 	if err != nil {
 		return nil, err
 	}
@@ -255,7 +368,7 @@ func (f *FileConfigLoader) LoadAllSites() ([]models.Site, error) {
 // SaveNode saves a single node to its directory structure
 // Path: ~/.config/archon/nodes/[nodeName]/config.toml
 func (f *FileConfigLoader) SaveNode(node *models.Node) error {
-	baseDir, err := GetArchonConfigDir()
+	baseDir, err := f.storageDir()
 	if err != nil {
 		return err
 	}
@@ -274,12 +387,12 @@ func (f *FileConfigLoader) SaveNode(node *models.Node) error {
 	}
 
 	// Write to file
-	return os.WriteFile(nodePath, data, 0644)
+	return writeFileAtomic(nodePath, data, 0644)
 }
 
 // LoadAllNodes loads all nodes from the directory structure
 func (f *FileConfigLoader) LoadAllNodes() ([]models.Node, error) {
-	baseDir, err := GetArchonConfigDir()
+	baseDir, err := f.storageDir()
 	if err != nil {
 		return nil, err
 	}
@@ -326,22 +439,48 @@ func (f *FileConfigLoader) LoadAllNodes() ([]models.Node, error) {
 
 // DeleteSite removes a site's directory
 func (f *FileConfigLoader) DeleteSite(siteName, domainName string) error {
-	baseDir, err := GetArchonConfigDir()
+	baseDir, err := f.storageDir()
 	if err != nil {
 		return err
 	}
 
+	if err := f.backupBeforeDelete(); err != nil {
+		return err
+	}
+
 	sitePath := filepath.Join(baseDir, "sites", domainName, siteName)
 	return os.RemoveAll(sitePath)
 }
 
 // DeleteNode removes a node's directory
 func (f *FileConfigLoader) DeleteNode(nodeName string) error {
-	baseDir, err := GetArchonConfigDir()
+	baseDir, err := f.storageDir()
 	if err != nil {
 		return err
 	}
 
+	if err := f.backupBeforeDelete(); err != nil {
+		return err
+	}
+
 	nodePath := filepath.Join(baseDir, "nodes", nodeName)
 	return os.RemoveAll(nodePath)
 }
+
+// NeedsPassphrase reports whether the config at path has encrypted secrets,
+// and whether it does not exist yet (first run)
+func NeedsPassphrase(path string) (needed bool, firstRun bool, err error) {
+	data, err := os.ReadFile(path)
+	if err != nil {
+		if os.IsNotExist(err) {
+			return false, true, nil
+		}
+		return false, false, err
+	}
+
+	var config Config
+	if err := toml.Unmarshal(data, &config); err != nil {
+		return false, false, err
+	}
+	return config.Settings.EncryptionSalt != "", false, nil
+}
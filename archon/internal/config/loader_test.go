@@ -0,0 +1,73 @@
+package config
+
+import (
+	"os"
+	"path/filepath"
+	"testing"
+
+	"github.com/google/uuid"
+
+	"github.com/BlueBeard63/archon/internal/models"
+)
+
+func TestCheckWritable(t *testing.T) {
+	dir := t.TempDir()
+
+	t.Run("missing directory is created", func(t *testing.T) {
+		path := filepath.Join(dir, "staging", "config.toml")
+		if err := CheckWritable(path); err != nil {
+			t.Fatalf("CheckWritable() error = %v", err)
+		}
+		if _, err := os.Stat(filepath.Dir(path)); err != nil {
+			t.Errorf("config directory was not created: %v", err)
+		}
+	})
+
+	t.Run("probe file is cleaned up", func(t *testing.T) {
+		path := filepath.Join(dir, "clean", "config.toml")
+		if err := CheckWritable(path); err != nil {
+			t.Fatalf("CheckWritable() error = %v", err)
+		}
+		entries, _ := os.ReadDir(filepath.Dir(path))
+		if len(entries) != 0 {
+			t.Errorf("directory should be empty, found %d entries", len(entries))
+		}
+	})
+
+	t.Run("read-only directory is rejected", func(t *testing.T) {
+		if os.Geteuid() == 0 {
+			t.Skip("permissions are not enforced for root")
+		}
+		readOnly := filepath.Join(dir, "readonly")
+		if err := os.Mkdir(readOnly, 0555); err != nil {
+			t.Fatal(err)
+		}
+		if err := CheckWritable(filepath.Join(readOnly, "config.toml")); err == nil {
+			t.Error("CheckWritable() should fail for a read-only directory")
+		}
+	})
+}
+
+func TestSitesStoredNextToConfig(t *testing.T) {
+	path := filepath.Join(t.TempDir(), "prod", "config.toml")
+	site := models.Site{ID: uuid.New(), Name: "blog"}
+
+	loader := NewFileConfigLoader()
+	cfg := DefaultConfig()
+	cfg.Sites = []models.Site{site}
+	if err := loader.Save(path, cfg); err != nil {
+		t.Fatalf("Save() error = %v", err)
+	}
+
+	if _, err := os.Stat(filepath.Join(filepath.Dir(path), "sites", "unknown", "blog", "config.toml")); err != nil {
+		t.Fatalf("site was not saved next to the config: %v", err)
+	}
+
+	loaded, err := NewFileConfigLoader().Load(path)
+	if err != nil {
+		t.Fatalf("Load() error = %v", err)
+	}
+	if len(loaded.Sites) != 1 || loaded.Sites[0].ID != site.ID {
+		t.Errorf("Load() sites = %v, want the saved site", loaded.Sites)
+	}
+}
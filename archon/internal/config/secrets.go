@@ -0,0 +1,299 @@
+package config
+
+import (
+	"crypto/aes"
+	"crypto/cipher"
+	"crypto/pbkdf2"
+	"crypto/rand"
+	"crypto/sha256"
+	"encoding/base64"
+	"errors"
+	"fmt"
+	"strings"
+
+	"golang.org/x/crypto/argon2"
+
+	"github.com/BlueBeard63/archon/internal/models"
+)
+
+// EncryptedPrefix marks a config value as an encrypted secret
+// A v1 value is the prefix followed by base64 (standard, padded) of a 12-byte random nonce
+// and the AES-256-GCM ciphertext and tag, sealed with the key derived below
+const EncryptedPrefix = "enc:v1:"
+
+// Key derivation for secret encryption
+// New keys are derived with Argon2id from the passphrase and a random 16-byte salt stored
+// base64-encoded in settings.encryption_salt. The KDF name and its parameters are stored in
+// settings.encryption_kdf and settings.encryption_kdf_params, so configs written with
+// PBKDF2-SHA256 (600,000 iterations) by earlier versions keep loading.
+const (
+	kdfArgon2id      = "argon2id"
+	kdfPBKDF2        = "pbkdf2-sha256"
+	pbkdf2Iterations = 600000
+	kdfKeyLength     = 32 // AES-256
+	saltLength       = 16
+)
+
+// kdf is the key derivation function, and its parameters, a config's secrets are encrypted with
+type kdf struct {
+	name    string
+	time    uint32 // Argon2id passes over memory
+	memory  uint32 // Argon2id memory in KiB
+	threads uint8  // Argon2id parallelism
+}
+
+// defaultKDF follows the RFC 9106 recommendation for memory-constrained environments
+var defaultKDF = kdf{name: kdfArgon2id, time: 3, memory: 64 * 1024, threads: 4}
+
+// parseKDF reads the KDF settings of a config; an unset name means PBKDF2, the only KDF of early configs
+func parseKDF(name, params string) (kdf, error) {
+	switch name {
+	case "", kdfPBKDF2:
+		return kdf{name: kdfPBKDF2}, nil
+	case kdfArgon2id:
+		k := kdf{name: kdfArgon2id}
+		if n, err := fmt.Sscanf(params, "m=%d,t=%d,p=%d", &k.memory, &k.time, &k.threads); err != nil || n != 3 {
+			return kdf{}, fmt.Errorf("malformed %s parameters %q", kdfArgon2id, params)
+		}
+		if k.time < 1 || k.threads < 1 || k.memory < 8*uint32(k.threads) {
+			return kdf{}, fmt.Errorf("invalid %s parameters %q", kdfArgon2id, params)
+		}
+		return k, nil
+	default:
+		return kdf{}, fmt.Errorf("unsupported key derivation function %q", name)
+	}
+}
+
+// params returns the parameters stored in settings.encryption_kdf_params (empty for PBKDF2)
+func (k kdf) params() string {
+	if k.name != kdfArgon2id {
+		return ""
+	}
+	return fmt.Sprintf("m=%d,t=%d,p=%d", k.memory, k.time, k.threads)
+}
+
+// deriveKey derives the AES key from the passphrase and salt
+func (k kdf) deriveKey(passphrase string, salt []byte) ([]byte, error) {
+	if k.name == kdfArgon2id {
+		return argon2.IDKey([]byte(passphrase), salt, k.time, k.memory, k.threads, kdfKeyLength), nil
+	}
+	return pbkdf2.Key(sha256.New, passphrase, salt, pbkdf2Iterations, kdfKeyLength)
+}
+
+// encryptionCheckValue is encrypted with the derived key so a wrong passphrase is detected on load
+const encryptionCheckValue = "archon"
+
+var (
+	// ErrPassphraseRequired is returned when loading a config with encrypted secrets and no passphrase
+	ErrPassphraseRequired = errors.New("config contains encrypted secrets; a passphrase is required")
+	// ErrWrongPassphrase is returned when the passphrase does not decrypt the config
+	ErrWrongPassphrase = errors.New("incorrect passphrase")
+)
+
+// SecretCipher encrypts and decrypts individual config values with a passphrase-derived key
+type SecretCipher struct {
+	aead cipher.AEAD
+}
+
+// NewSecretCipher derives an AES-GCM key from the passphrase and salt with Argon2id
+func NewSecretCipher(passphrase string, salt []byte) (*SecretCipher, error) {
+	return newSecretCipher(passphrase, salt, defaultKDF)
+}
+
+// newSecretCipher derives an AES-GCM key from the passphrase and salt with the given KDF
+func newSecretCipher(passphrase string, salt []byte, k kdf) (*SecretCipher, error) {
+	key, err := k.deriveKey(passphrase, salt)
+	if err != nil {
+		return nil, fmt.Errorf("failed to derive key: %w", err)
+	}
+
+	block, err := aes.NewCipher(key)
+	if err != nil {
+		return nil, err
+	}
+	aead, err := cipher.NewGCM(block)
+	if err != nil {
+		return nil, err
+	}
+
+	return &SecretCipher{aead: aead}, nil
+}
+
+// NewSalt generates a random salt for key derivation
+func NewSalt() ([]byte, error) {
+	salt := make([]byte, saltLength)
+	if _, err := rand.Read(salt); err != nil {
+		return nil, err
+	}
+	return salt, nil
+}
+
+// IsEncrypted returns true if the value carries the encrypted secret marker
+func IsEncrypted(value string) bool {
+	return strings.HasPrefix(value, EncryptedPrefix)
+}
+
+// Encrypt returns the value as an encrypted blob with the marker prefix
+// Empty and already-encrypted values are returned unchanged
+func (c *SecretCipher) Encrypt(plaintext string) (string, error) {
+	if plaintext == "" || IsEncrypted(plaintext) {
+		return plaintext, nil
+	}
+
+	nonce := make([]byte, c.aead.NonceSize())
+	if _, err := rand.Read(nonce); err != nil {
+		return "", err
+	}
+
+	sealed := c.aead.Seal(nonce, nonce, []byte(plaintext), nil)
+	return EncryptedPrefix + base64.StdEncoding.EncodeToString(sealed), nil
+}
+
+// Decrypt returns the plaintext of an encrypted blob
+// Values without the marker prefix are plaintext from older configs and are returned unchanged
+func (c *SecretCipher) Decrypt(value string) (string, error) {
+	if !IsEncrypted(value) {
+		return value, nil
+	}
+
+	sealed, err := base64.StdEncoding.DecodeString(strings.TrimPrefix(value, EncryptedPrefix))
+	if err != nil {
+		return "", fmt.Errorf("malformed encrypted value: %w", err)
+	}
+
+	nonceSize := c.aead.NonceSize()
+	if len(sealed) < nonceSize {
+		return "", fmt.Errorf("malformed encrypted value: too short")
+	}
+
+	plaintext, err := c.aead.Open(nil, sealed[:nonceSize], sealed[nonceSize:], nil)
+	if err != nil {
+		return "", ErrWrongPassphrase
+	}
+	return string(plaintext), nil
+}
+
+// secretFields returns pointers to every credential stored in the config
+func (c *Config) secretFields() []*string {
+	fields := []*string{
+		&c.Settings.CloudflareAPIToken,
+		&c.Settings.Route53AccessKey,
+		&c.Settings.Route53SecretKey,
+	}
+	for i := range c.Domains {
+		fields = append(fields,
+			&c.Domains[i].DnsProvider.APIToken,
+			&c.Domains[i].DnsProvider.AccessKey,
+			&c.Domains[i].DnsProvider.SecretKey,
+		)
+	}
+	for i := range c.Sites {
+		fields = append(fields, &c.Sites[i].DockerToken)
+	}
+	for i := range c.Nodes {
+		fields = append(fields, &c.Nodes[i].APIKey)
+	}
+	return fields
+}
+
+// HasEncryptedSecrets returns true if any credential in the config is encrypted
+func (c *Config) HasEncryptedSecrets() bool {
+	for _, field := range c.secretFields() {
+		if IsEncrypted(*field) {
+			return true
+		}
+	}
+	return false
+}
+
+// encryptSecrets returns a copy of the config with every credential encrypted
+// The original config (shared with the running app) is left untouched
+func (c *Config) encryptSecrets(sc *SecretCipher) (*Config, error) {
+	encrypted := *c
+	encrypted.Sites = append([]models.Site(nil), c.Sites...)
+	encrypted.Domains = append([]models.Domain(nil), c.Domains...)
+	encrypted.Nodes = append([]models.Node(nil), c.Nodes...)
+
+	for _, field := range encrypted.secretFields() {
+		if IsKeyringRef(*field) {
+			continue // The secret itself is in the keyring
+		}
+		value, err := sc.Encrypt(*field)
+		if err != nil {
+			return nil, fmt.Errorf("failed to encrypt secret: %w", err)
+		}
+		*field = value
+	}
+	return &encrypted, nil
+}
+
+// decryptSecrets decrypts every encrypted credential in the config in place
+func (c *Config) decryptSecrets(sc *SecretCipher) error {
+	for _, field := range c.secretFields() {
+		value, err := sc.Decrypt(*field)
+		if err != nil {
+			return err
+		}
+		*field = value
+	}
+	return nil
+}
+
+// unlock derives the cipher for a loaded config and checks the passphrase against it
+func (f *FileConfigLoader) unlock(settings *Settings) (*SecretCipher, error) {
+	if f.passphrase == "" {
+		return nil, ErrPassphraseRequired
+	}
+	if settings.EncryptionSalt == "" {
+		return nil, fmt.Errorf("config has encrypted secrets but no encryption salt")
+	}
+	k, err := parseKDF(settings.EncryptionKDF, settings.EncryptionKDFParams)
+	if err != nil {
+		return nil, err
+	}
+
+	salt, err := base64.StdEncoding.DecodeString(settings.EncryptionSalt)
+	if err != nil {
+		return nil, fmt.Errorf("malformed encryption salt: %w", err)
+	}
+
+	sc, err := newSecretCipher(f.passphrase, salt, k)
+	if err != nil {
+		return nil, err
+	}
+	if check, err := sc.Decrypt(settings.EncryptionCheck); err != nil || check != encryptionCheckValue {
+		return nil, ErrWrongPassphrase
+	}
+
+	f.kdf = k
+	f.salt = salt
+	f.cipher = sc
+	f.check = settings.EncryptionCheck
+	return sc, nil
+}
+
+// encryptionCipher returns the cipher used for saving, creating a new salt on the first encrypted save
+func (f *FileConfigLoader) encryptionCipher() (*SecretCipher, error) {
+	if f.cipher != nil {
+		return f.cipher, nil
+	}
+
+	salt, err := NewSalt()
+	if err != nil {
+		return nil, err
+	}
+	sc, err := NewSecretCipher(f.passphrase, salt)
+	if err != nil {
+		return nil, err
+	}
+	check, err := sc.Encrypt(encryptionCheckValue)
+	if err != nil {
+		return nil, err
+	}
+
+	f.kdf = defaultKDF
+	f.salt = salt
+	f.cipher = sc
+	f.check = check
+	return sc, nil
+}
@@ -0,0 +1,257 @@
+package config
+
+import (
+	"encoding/base64"
+	"errors"
+	"os"
+	"path/filepath"
+	"testing"
+
+	"github.com/google/uuid"
+	"github.com/pelletier/go-toml/v2"
+
+	"github.com/BlueBeard63/archon/internal/models"
+)
+
+func testCipher(t *testing.T, passphrase string, salt []byte) *SecretCipher {
+	t.Helper()
+	sc, err := NewSecretCipher(passphrase, salt)
+	if err != nil {
+		t.Fatalf("NewSecretCipher: %v", err)
+	}
+	return sc
+}
+
+func TestSecretCipherRoundTrip(t *testing.T) {
+	salt := []byte("0123456789abcdef")
+	sc := testCipher(t, "correct horse", salt)
+
+	tests := []struct {
+		name      string
+		value     string
+		encrypted bool
+	}{
+		{name: "token is encrypted", value: "cf-api-token", encrypted: true},
+		{name: "empty value stays empty", value: "", encrypted: false},
+		{name: "unicode value", value: "pässwörd🔑", encrypted: true},
+	}
+
+	for _, tt := range tests {
+		t.Run(tt.name, func(t *testing.T) {
+			blob, err := sc.Encrypt(tt.value)
+			if err != nil {
+				t.Fatalf("Encrypt: %v", err)
+			}
+			if IsEncrypted(blob) != tt.encrypted {
+				t.Fatalf("IsEncrypted(%q) = %v, want %v", blob, IsEncrypted(blob), tt.encrypted)
+			}
+
+			// Encrypting an already-encrypted value must not double-wrap it
+			again, err := sc.Encrypt(blob)
+			if err != nil || again != blob {
+				t.Errorf("re-encrypting changed the value: %q, %v", again, err)
+			}
+
+			got, err := sc.Decrypt(blob)
+			if err != nil {
+				t.Fatalf("Decrypt: %v", err)
+			}
+			if got != tt.value {
+				t.Errorf("Decrypt = %q, want %q", got, tt.value)
+			}
+		})
+	}
+}
+
+func TestSecretCipherDecrypt(t *testing.T) {
+	salt := []byte("0123456789abcdef")
+	sc := testCipher(t, "correct horse", salt)
+	blob, err := sc.Encrypt("secret")
+	if err != nil {
+		t.Fatalf("Encrypt: %v", err)
+	}
+
+	tests := []struct {
+		name          string
+		cipher        *SecretCipher
+		value         string
+		want          string
+		wantErr       bool
+		wantWrongPass bool
+	}{
+		{name: "plaintext passes through", cipher: sc, value: "legacy-token", want: "legacy-token"},
+		{name: "wrong passphrase", cipher: testCipher(t, "wrong", salt), value: blob, wantErr: true, wantWrongPass: true},
+		{name: "different salt", cipher: testCipher(t, "correct horse", []byte("fedcba9876543210")), value: blob, wantErr: true, wantWrongPass: true},
+		{name: "malformed blob", cipher: sc, value: EncryptedPrefix + "!!!", wantErr: true},
+	}
+
+	for _, tt := range tests {
+		t.Run(tt.name, func(t *testing.T) {
+			got, err := tt.cipher.Decrypt(tt.value)
+			if tt.wantErr {
+				if err == nil {
+					t.Fatalf("expected error, got %q", got)
+				}
+				if tt.wantWrongPass && !errors.Is(err, ErrWrongPassphrase) {
+					t.Errorf("error = %v, want %v", err, ErrWrongPassphrase)
+				}
+				return
+			}
+			if err != nil {
+				t.Fatalf("unexpected error: %v", err)
+			}
+			if got != tt.want {
+				t.Errorf("Decrypt = %q, want %q", got, tt.want)
+			}
+		})
+	}
+}
+
+func TestConfigEncryptSecrets(t *testing.T) {
+	sc := testCipher(t, "correct horse", []byte("0123456789abcdef"))
+
+	cfg := &Config{
+		Settings: Settings{CloudflareAPIToken: "cf-token"},
+		Domains: []models.Domain{
+			{Name: "example.com", DnsProvider: models.DnsProvider{APIToken: "domain-token"}},
+		},
+		Sites: []models.Site{{Name: "web", DockerToken: "registry-token"}},
+		Nodes: []models.Node{{Name: "node1", APIKey: "node-key"}},
+	}
+
+	encrypted, err := cfg.encryptSecrets(sc)
+	if err != nil {
+		t.Fatalf("encryptSecrets: %v", err)
+	}
+
+	// The running config keeps plaintext values
+	if cfg.HasEncryptedSecrets() {
+		t.Fatal("encryptSecrets modified the original config")
+	}
+	for _, field := range encrypted.secretFields() {
+		if *field != "" && !IsEncrypted(*field) {
+			t.Errorf("secret %q was not encrypted", *field)
+		}
+	}
+	if encrypted.Nodes[0].Name != "node1" {
+		t.Errorf("non-secret field changed: %q", encrypted.Nodes[0].Name)
+	}
+
+	if err := encrypted.decryptSecrets(sc); err != nil {
+		t.Fatalf("decryptSecrets: %v", err)
+	}
+	if encrypted.Settings.CloudflareAPIToken != "cf-token" ||
+		encrypted.Domains[0].DnsProvider.APIToken != "domain-token" ||
+		encrypted.Sites[0].DockerToken != "registry-token" ||
+		encrypted.Nodes[0].APIKey != "node-key" {
+		t.Errorf("decrypted config does not match original: %+v", encrypted)
+	}
+}
+
+func TestParseKDF(t *testing.T) {
+	tests := []struct {
+		name    string
+		kdfName string
+		params  string
+		want    kdf
+		wantErr bool
+	}{
+		{name: "unset is pbkdf2", want: kdf{name: kdfPBKDF2}},
+		{name: "pbkdf2", kdfName: kdfPBKDF2, want: kdf{name: kdfPBKDF2}},
+		{name: "argon2id", kdfName: kdfArgon2id, params: "m=65536,t=3,p=4", want: defaultKDF},
+		{name: "argon2id without parameters", kdfName: kdfArgon2id, wantErr: true},
+		{name: "argon2id without threads", kdfName: kdfArgon2id, params: "m=65536,t=3,p=0", wantErr: true},
+		{name: "unknown", kdfName: "scrypt", wantErr: true},
+	}
+
+	for _, tt := range tests {
+		t.Run(tt.name, func(t *testing.T) {
+			got, err := parseKDF(tt.kdfName, tt.params)
+			if (err != nil) != tt.wantErr {
+				t.Fatalf("parseKDF() error = %v, wantErr %v", err, tt.wantErr)
+			}
+			if got != tt.want {
+				t.Errorf("parseKDF() = %+v, want %+v", got, tt.want)
+			}
+		})
+	}
+}
+
+func TestEncryptedConfigUsesArgon2id(t *testing.T) {
+	path := filepath.Join(t.TempDir(), "config.toml")
+	cfg := DefaultConfig()
+	cfg.Nodes = []models.Node{{ID: uuid.New(), Name: "node1", APIKey: "node-key"}}
+
+	loader := NewFileConfigLoader()
+	loader.SetPassphrase("correct horse")
+	if err := loader.Save(path, cfg); err != nil {
+		t.Fatalf("Save() error = %v", err)
+	}
+
+	data, err := os.ReadFile(path)
+	if err != nil {
+		t.Fatal(err)
+	}
+	var saved Config
+	if err := toml.Unmarshal(data, &saved); err != nil {
+		t.Fatal(err)
+	}
+	if saved.Settings.EncryptionKDF != kdfArgon2id || saved.Settings.EncryptionKDFParams != defaultKDF.params() {
+		t.Errorf("saved KDF = %q %q, want %q %q", saved.Settings.EncryptionKDF, saved.Settings.EncryptionKDFParams, kdfArgon2id, defaultKDF.params())
+	}
+
+	reader := NewFileConfigLoader()
+	reader.SetPassphrase("correct horse")
+	loaded, err := reader.Load(path)
+	if err != nil {
+		t.Fatalf("Load() error = %v", err)
+	}
+	if loaded.Nodes[0].APIKey != "node-key" {
+		t.Errorf("node API key = %q, want %q", loaded.Nodes[0].APIKey, "node-key")
+	}
+}
+
+func TestPBKDF2ConfigStillLoads(t *testing.T) {
+	path := filepath.Join(t.TempDir(), "config.toml")
+	salt := []byte("0123456789abcdef")
+	sc, err := newSecretCipher("correct horse", salt, kdf{name: kdfPBKDF2})
+	if err != nil {
+		t.Fatal(err)
+	}
+	check, err := sc.Encrypt(encryptionCheckValue)
+	if err != nil {
+		t.Fatal(err)
+	}
+
+	// A config saved by a version that only knew PBKDF2
+	cfg := DefaultConfig()
+	cfg.Nodes = []models.Node{{ID: uuid.New(), Name: "node1", APIKey: "node-key"}}
+	legacy, err := cfg.encryptSecrets(sc)
+	if err != nil {
+		t.Fatal(err)
+	}
+	legacy.Settings.EncryptionKDF = kdfPBKDF2
+	legacy.Settings.EncryptionSalt = base64.StdEncoding.EncodeToString(salt)
+	legacy.Settings.EncryptionCheck = check
+	if err := (&FileConfigLoader{dataDir: filepath.Dir(path)}).write(path, legacy); err != nil {
+		t.Fatal(err)
+	}
+
+	loader := NewFileConfigLoader()
+	loader.SetPassphrase("correct horse")
+	loaded, err := loader.Load(path)
+	if err != nil {
+		t.Fatalf("Load() error = %v", err)
+	}
+	if loaded.Nodes[0].APIKey != "node-key" {
+		t.Errorf("node API key = %q, want %q", loaded.Nodes[0].APIKey, "node-key")
+	}
+
+	// Saving keeps the KDF the salt was used with
+	if err := loader.Save(path, loaded); err != nil {
+		t.Fatalf("Save() error = %v", err)
+	}
+	if _, err := loader.Load(path); err != nil {
+		t.Fatalf("Load() after save error = %v", err)
+	}
+}
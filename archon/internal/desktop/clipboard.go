@@ -0,0 +1,16 @@
+package desktop
+
+import (
+	"errors"
+
+	"github.com/atotto/clipboard"
+)
+
+// CopyText puts text on the system clipboard. It fails where there is no clipboard: on Linux
+// and the BSDs without a display or without xclip, xsel or wl-copy installed (e.g. over SSH).
+func CopyText(text string) error {
+	if clipboard.Unsupported {
+		return errors.New("no clipboard available (install xclip, xsel or wl-clipboard)")
+	}
+	return clipboard.WriteAll(text)
+}
@@ -0,0 +1,54 @@
+package desktop
+
+import (
+	"fmt"
+	"os"
+	"os/exec"
+	"runtime"
+	"strings"
+)
+
+// Notifier raises OS desktop notifications
+type Notifier interface {
+	Notify(title, message string) error
+}
+
+// System returns the desktop's notifier, or nil on systems that can't show notifications
+// (no display, or the tool isn't installed): notify-send from libnotify on Linux and the
+// BSDs, and osascript on macOS
+func System() Notifier {
+	switch runtime.GOOS {
+	case "darwin":
+		if _, err := exec.LookPath("osascript"); err == nil {
+			return appleScript{}
+		}
+	case "linux", "freebsd", "openbsd":
+		if os.Getenv("DISPLAY") == "" && os.Getenv("WAYLAND_DISPLAY") == "" {
+			return nil
+		}
+		if _, err := exec.LookPath("notify-send"); err == nil {
+			return notifySend{}
+		}
+	}
+	return nil
+}
+
+// notifySend shows notifications through the freedesktop notification daemon
+type notifySend struct{}
+
+func (notifySend) Notify(title, message string) error {
+	return exec.Command("notify-send", "--app-name=Archon", "--urgency=critical", "--", title, message).Run()
+}
+
+// appleScript shows notifications through macOS Notification Center
+type appleScript struct{}
+
+func (appleScript) Notify(title, message string) error {
+	script := fmt.Sprintf("display notification %s with title %s", appleScriptQuote(message), appleScriptQuote(title))
+	return exec.Command("osascript", "-e", script).Run()
+}
+
+// appleScriptQuote quotes a value as an AppleScript string literal
+func appleScriptQuote(value string) string {
+	return `"` + strings.NewReplacer(`\`, `\\`, `"`, `\"`).Replace(value) + `"`
+}
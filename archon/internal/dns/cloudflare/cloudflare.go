@@ -4,7 +4,10 @@ import (
 	"bytes"
 	"encoding/json"
 	"fmt"
+	"io"
 	"net/http"
+	"strconv"
+	"strings"
 	"time"
 
 	"github.com/BlueBeard63/archon/internal/models"
@@ -12,11 +15,26 @@ import (
 
 const cloudflareAPIBase = "https://api.cloudflare.com/client/v4"
 
+// listPageSize is the number of records requested per page (Cloudflare's maximum is 100)
+const listPageSize = 100
+
+// DefaultRateLimitRetries is how many times a rate-limited (HTTP 429) request is retried
+const DefaultRateLimitRetries = models.DefaultRateLimitRetries
+
+// Delays used when a 429 response has no usable Retry-After header, and the cap on any wait
+const (
+	defaultRetryAfter = 1 * time.Second
+	maxRetryAfter     = 60 * time.Second
+)
+
 // Provider implements dns.Provider for Cloudflare
 type Provider struct {
-	apiToken string
-	zoneID   string
-	client   *http.Client
+	apiToken         string
+	zoneID           string
+	baseURL          string // Cloudflare API base, overridden in tests
+	client           *http.Client
+	rateLimitRetries int
+	sleep            func(time.Duration) // Overridden in tests to avoid real waits
 }
 
 // NewCloudflareProvider creates a new Cloudflare DNS provider
@@ -24,61 +42,144 @@ func NewCloudflareProvider(apiToken, zoneID string) *Provider {
 	return &Provider{
 		apiToken: apiToken,
 		zoneID:   zoneID,
+		baseURL:  cloudflareAPIBase,
 		client: &http.Client{
 			Timeout: 15 * time.Second,
 		},
+		rateLimitRetries: DefaultRateLimitRetries,
+		sleep:            time.Sleep,
+	}
+}
+
+// SetRateLimitRetries sets how many times a rate-limited request is retried (0 disables retries)
+func (p *Provider) SetRateLimitRetries(retries int) {
+	if retries < 0 {
+		retries = 0
+	}
+	p.rateLimitRetries = retries
+}
+
+// doRequest sends an authenticated request to the Cloudflare API
+// Rate-limited responses are retried after the Retry-After delay, up to rateLimitRetries times
+func (p *Provider) doRequest(method, url string, body []byte) (*http.Response, error) {
+	for attempt := 0; ; attempt++ {
+		// The body reader is consumed by each attempt, so the request is rebuilt every time
+		var bodyReader io.Reader
+		if body != nil {
+			bodyReader = bytes.NewReader(body)
+		}
+		req, err := http.NewRequest(method, url, bodyReader)
+		if err != nil {
+			return nil, fmt.Errorf("failed to create request: %w", err)
+		}
+
+		req.Header.Set("Authorization", "Bearer "+p.apiToken)
+		req.Header.Set("Content-Type", "application/json")
+
+		resp, err := p.client.Do(req)
+		if err != nil {
+			return nil, fmt.Errorf("failed to make request: %w", err)
+		}
+		if resp.StatusCode != http.StatusTooManyRequests {
+			return resp, nil
+		}
+
+		delay := retryAfter(resp.Header.Get("Retry-After"), attempt, time.Now())
+		resp.Body.Close()
+
+		if attempt >= p.rateLimitRetries {
+			return nil, fmt.Errorf("cloudflare API rate limit exceeded (HTTP 429) after %d retries", attempt)
+		}
+		p.sleep(delay)
 	}
 }
 
+// retryAfter returns how long to wait before retrying a rate-limited request
+// The header may be a number of seconds or an HTTP date; without one the wait doubles per attempt
+func retryAfter(header string, attempt int, now time.Time) time.Duration {
+	delay := defaultRetryAfter << uint(min(attempt, 6))
+
+	header = strings.TrimSpace(header)
+	if secs, err := strconv.Atoi(header); err == nil && secs >= 0 {
+		delay = time.Duration(secs) * time.Second
+	} else if when, err := http.ParseTime(header); err == nil {
+		delay = when.Sub(now)
+		if delay < 0 {
+			delay = 0
+		}
+	}
+
+	if delay > maxRetryAfter {
+		delay = maxRetryAfter
+	}
+	return delay
+}
+
 // ListRecords retrieves all DNS records for a zone
+// Cloudflare returns at most listPageSize records per page, so every page is fetched
 func (p *Provider) ListRecords(domain string) ([]models.DnsRecord, error) {
-	url := fmt.Sprintf("%s/zones/%s/dns_records", cloudflareAPIBase, p.zoneID)
+	var records []models.DnsRecord
 
-	// Create HTTP request
-	req, err := http.NewRequest("GET", url, nil)
-	if err != nil {
-		return nil, fmt.Errorf("failed to create request: %w", err)
+	for page := 1; ; page++ {
+		cfRecords, totalPages, err := p.listRecordsPage(page)
+		if err != nil {
+			return nil, err
+		}
+
+		// Convert to models.DnsRecord
+		for _, cfRecord := range cfRecords {
+			records = append(records, fromCloudflareRecord(cfRecord))
+		}
+
+		if page >= totalPages || len(cfRecords) == 0 {
+			break
+		}
+	}
+
+	if records == nil {
+		records = []models.DnsRecord{}
 	}
+	return records, nil
+}
 
-	req.Header.Set("Authorization", "Bearer "+p.apiToken)
-	req.Header.Set("Content-Type", "application/json")
+// listRecordsPage retrieves one page of DNS records and the total number of pages
+func (p *Provider) listRecordsPage(page int) ([]cloudflareRecord, int, error) {
+	url := fmt.Sprintf("%s/zones/%s/dns_records?page=%d&per_page=%d", p.baseURL, p.zoneID, page, listPageSize)
 
 	// Make request
-	resp, err := p.client.Do(req)
+	resp, err := p.doRequest("GET", url, nil)
 	if err != nil {
-		return nil, fmt.Errorf("failed to make request: %w", err)
+		return nil, 0, err
 	}
 	defer resp.Body.Close()
 
 	// Parse response
 	var cfResp struct {
-		Success bool               `json:"success"`
-		Errors  []cloudflareError  `json:"errors"`
-		Result  []cloudflareRecord `json:"result"`
+		Success    bool               `json:"success"`
+		Errors     []cloudflareError  `json:"errors"`
+		Result     []cloudflareRecord `json:"result"`
+		ResultInfo struct {
+			Page       int `json:"page"`
+			TotalPages int `json:"total_pages"`
+		} `json:"result_info"`
 	}
 	if err := json.NewDecoder(resp.Body).Decode(&cfResp); err != nil {
-		return nil, fmt.Errorf("failed to parse response: %w", err)
+		return nil, 0, fmt.Errorf("failed to parse response: %w", err)
 	}
 
 	if !cfResp.Success {
 		if len(cfResp.Errors) > 0 {
-			return nil, fmt.Errorf("cloudflare API error: %s", cfResp.Errors[0].Message)
+			return nil, 0, fmt.Errorf("cloudflare API error: %s", cfResp.Errors[0].Message)
 		}
-		return nil, fmt.Errorf("cloudflare API request failed")
+		return nil, 0, fmt.Errorf("cloudflare API request failed")
 	}
 
-	// Convert to models.DnsRecord
-	records := make([]models.DnsRecord, 0, len(cfResp.Result))
-	for _, cfRecord := range cfResp.Result {
-		records = append(records, fromCloudflareRecord(cfRecord))
-	}
-
-	return records, nil
+	return cfResp.Result, cfResp.ResultInfo.TotalPages, nil
 }
 
 // CreateRecord creates a new DNS record in Cloudflare
 func (p *Provider) CreateRecord(domain string, record *models.DnsRecord, tags []string) (*models.DnsRecord, error) {
-	url := fmt.Sprintf("%s/zones/%s/dns_records", cloudflareAPIBase, p.zoneID)
+	url := fmt.Sprintf("%s/zones/%s/dns_records", p.baseURL, p.zoneID)
 
 	// Convert to Cloudflare format
 	cfRecord := toCloudflareRecord(record, tags)
@@ -88,19 +189,10 @@ func (p *Provider) CreateRecord(domain string, record *models.DnsRecord, tags []
 		return nil, fmt.Errorf("failed to marshal request: %w", err)
 	}
 
-	// Create HTTP request with body
-	req, err := http.NewRequest("POST", url, bytes.NewReader(body))
-	if err != nil {
-		return nil, fmt.Errorf("failed to create request: %w", err)
-	}
-
-	req.Header.Set("Authorization", "Bearer "+p.apiToken)
-	req.Header.Set("Content-Type", "application/json")
-
 	// Make request
-	resp, err := p.client.Do(req)
+	resp, err := p.doRequest("POST", url, body)
 	if err != nil {
-		return nil, fmt.Errorf("failed to make request: %w", err)
+		return nil, err
 	}
 	defer resp.Body.Close()
 
@@ -134,7 +226,7 @@ func (p *Provider) UpdateRecord(domain string, record *models.DnsRecord, tags []
 		return nil, fmt.Errorf("record ID is required for updates")
 	}
 
-	url := fmt.Sprintf("%s/zones/%s/dns_records/%s", cloudflareAPIBase, p.zoneID, *record.ID)
+	url := fmt.Sprintf("%s/zones/%s/dns_records/%s", p.baseURL, p.zoneID, *record.ID)
 
 	// Convert to Cloudflare format
 	cfRecord := toCloudflareRecord(record, tags)
@@ -145,19 +237,10 @@ func (p *Provider) UpdateRecord(domain string, record *models.DnsRecord, tags []
 		return nil, fmt.Errorf("failed to marshal request: %w", err)
 	}
 
-	// Create HTTP request
-	req, err := http.NewRequest("PUT", url, bytes.NewReader(body))
-	if err != nil {
-		return nil, fmt.Errorf("failed to create request: %w", err)
-	}
-
-	req.Header.Set("Authorization", "Bearer "+p.apiToken)
-	req.Header.Set("Content-Type", "application/json")
-
 	// Make request
-	resp, err := p.client.Do(req)
+	resp, err := p.doRequest("PUT", url, body)
 	if err != nil {
-		return nil, fmt.Errorf("failed to make request: %w", err)
+		return nil, err
 	}
 	defer resp.Body.Close()
 
@@ -191,21 +274,12 @@ func (p *Provider) DeleteRecord(domain string, recordID string) error {
 		return fmt.Errorf("record ID is required for deletion")
 	}
 
-	url := fmt.Sprintf("%s/zones/%s/dns_records/%s", cloudflareAPIBase, p.zoneID, recordID)
-
-	// Create HTTP request
-	req, err := http.NewRequest("DELETE", url, nil)
-	if err != nil {
-		return fmt.Errorf("failed to create request: %w", err)
-	}
-
-	req.Header.Set("Authorization", "Bearer "+p.apiToken)
-	req.Header.Set("Content-Type", "application/json")
+	url := fmt.Sprintf("%s/zones/%s/dns_records/%s", p.baseURL, p.zoneID, recordID)
 
 	// Make request
-	resp, err := p.client.Do(req)
+	resp, err := p.doRequest("DELETE", url, nil)
 	if err != nil {
-		return fmt.Errorf("failed to make request: %w", err)
+		return err
 	}
 	defer resp.Body.Close()
 
@@ -239,13 +313,21 @@ type cloudflareError struct {
 
 // cloudflareRecord represents a DNS record in Cloudflare's format
 type cloudflareRecord struct {
-	ID      string `json:"id,omitempty"`
-	Type    string `json:"type"`
-	Name    string `json:"name"`
-	Content string `json:"content"`
-	TTL     int    `json:"ttl"`
-	Proxied bool   `json:"proxied"`
-	Comment string `json:"comment,omitempty"`
+	ID      string             `json:"id,omitempty"`
+	Type    string             `json:"type"`
+	Name    string             `json:"name"`
+	Content string             `json:"content,omitempty"`
+	Data    *cloudflareCAAData `json:"data,omitempty"` // Set instead of content for CAA records
+	TTL     int                `json:"ttl"`
+	Proxied bool               `json:"proxied"`
+	Comment string             `json:"comment,omitempty"`
+}
+
+// cloudflareCAAData is the structured content Cloudflare uses for CAA records
+type cloudflareCAAData struct {
+	Flags uint8  `json:"flags"`
+	Tag   string `json:"tag"`
+	Value string `json:"value"`
 }
 
 // toCloudflareRecord converts models.DnsRecord to Cloudflare format
@@ -258,6 +340,14 @@ func toCloudflareRecord(record *models.DnsRecord, tags []string) cloudflareRecor
 		Proxied: record.Proxied,
 	}
 
+	// Cloudflare takes CAA records as flags, tag and value rather than content
+	if record.RecordType == models.DnsRecordTypeCAA {
+		if caa, err := models.ParseCAA(record.Value); err == nil {
+			cfRec.Content = ""
+			cfRec.Data = &cloudflareCAAData{Flags: caa.Flags, Tag: caa.Tag, Value: caa.Value}
+		}
+	}
+
 	// Use first tag as comment if tags are provided
 	if len(tags) > 0 {
 		cfRec.Comment = tags[0]
@@ -279,7 +369,7 @@ func toCloudflareRecord(record *models.DnsRecord, tags []string) cloudflareRecor
 // fromCloudflareRecord converts Cloudflare format to models.DnsRecord
 func fromCloudflareRecord(cf cloudflareRecord) models.DnsRecord {
 	id := cf.ID
-	return models.DnsRecord{
+	record := models.DnsRecord{
 		ID:         &id,
 		RecordType: models.DnsRecordType(cf.Type),
 		Name:       cf.Name,
@@ -287,4 +377,9 @@ func fromCloudflareRecord(cf cloudflareRecord) models.DnsRecord {
 		TTL:        cf.TTL,
 		Proxied:    cf.Proxied,
 	}
+	if cf.Type == string(models.DnsRecordTypeCAA) && cf.Data != nil {
+		record.Value = models.CAA{Flags: cf.Data.Flags, Tag: cf.Data.Tag, Value: cf.Data.Value}.String()
+	}
+	record.Normalize()
+	return record
 }
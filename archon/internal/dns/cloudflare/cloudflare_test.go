@@ -0,0 +1,407 @@
+package cloudflare
+
+import (
+	"encoding/json"
+	"fmt"
+	"net/http"
+	"net/http/httptest"
+	"strconv"
+	"testing"
+	"time"
+
+	"github.com/BlueBeard63/archon/internal/models"
+)
+
+// newTestProvider returns a provider that talks to the given test server
+func newTestProvider(server *httptest.Server) *Provider {
+	p := NewCloudflareProvider("test-token", "zone-1")
+	p.baseURL = server.URL
+	p.sleep = func(time.Duration) {}
+	return p
+}
+
+func TestListRecordsPagination(t *testing.T) {
+	tests := []struct {
+		name        string
+		totalPages  int
+		perPage     int
+		wantRecords int
+		wantPages   int
+	}{
+		{name: "single page", totalPages: 1, perPage: 3, wantRecords: 3, wantPages: 1},
+		{name: "multiple pages are concatenated", totalPages: 3, perPage: 2, wantRecords: 6, wantPages: 3},
+		{name: "empty zone", totalPages: 0, perPage: 0, wantRecords: 0, wantPages: 1},
+	}
+
+	for _, tt := range tests {
+		t.Run(tt.name, func(t *testing.T) {
+			requests := 0
+			server := httptest.NewServer(http.HandlerFunc(func(w http.ResponseWriter, r *http.Request) {
+				requests++
+				if r.URL.Path != "/zones/zone-1/dns_records" {
+					t.Errorf("unexpected path %q", r.URL.Path)
+				}
+				if got := r.URL.Query().Get("per_page"); got != strconv.Itoa(listPageSize) {
+					t.Errorf("per_page = %q, want %d", got, listPageSize)
+				}
+				page, _ := strconv.Atoi(r.URL.Query().Get("page"))
+				if page != requests {
+					t.Errorf("page = %d, want %d", page, requests)
+				}
+
+				var result []cloudflareRecord
+				for i := 0; i < tt.perPage; i++ {
+					result = append(result, cloudflareRecord{
+						ID:      fmt.Sprintf("rec-%d-%d", page, i),
+						Type:    "A",
+						Name:    fmt.Sprintf("host%d-%d.example.com", page, i),
+						Content: "1.2.3.4",
+						TTL:     300,
+					})
+				}
+
+				_ = json.NewEncoder(w).Encode(map[string]interface{}{
+					"success": true,
+					"errors":  []cloudflareError{},
+					"result":  result,
+					"result_info": map[string]int{
+						"page":        page,
+						"per_page":    listPageSize,
+						"total_pages": tt.totalPages,
+					},
+				})
+			}))
+			defer server.Close()
+
+			records, err := newTestProvider(server).ListRecords("example.com")
+			if err != nil {
+				t.Fatalf("unexpected error: %v", err)
+			}
+			if len(records) != tt.wantRecords {
+				t.Errorf("got %d records, want %d", len(records), tt.wantRecords)
+			}
+			if requests != tt.wantPages {
+				t.Errorf("made %d requests, want %d", requests, tt.wantPages)
+			}
+
+			// Records must keep page order
+			if tt.wantRecords > 0 && *records[0].ID != "rec-1-0" {
+				t.Errorf("first record ID = %q, want rec-1-0", *records[0].ID)
+			}
+		})
+	}
+}
+
+func TestListRecordsAPIError(t *testing.T) {
+	server := httptest.NewServer(http.HandlerFunc(func(w http.ResponseWriter, r *http.Request) {
+		_ = json.NewEncoder(w).Encode(map[string]interface{}{
+			"success": false,
+			"errors":  []cloudflareError{{Code: 9109, Message: "Invalid access token"}},
+		})
+	}))
+	defer server.Close()
+
+	if _, err := newTestProvider(server).ListRecords("example.com"); err == nil {
+		t.Fatal("expected error for failed API response")
+	}
+}
+
+func TestRateLimitRetries(t *testing.T) {
+	tests := []struct {
+		name         string
+		rateLimited  int // number of 429 responses before success
+		retries      int
+		wantErr      bool
+		wantRequests int
+	}{
+		{name: "no rate limiting", rateLimited: 0, retries: 3, wantRequests: 1},
+		{name: "succeeds after retries", rateLimited: 2, retries: 3, wantRequests: 3},
+		{name: "gives up after max retries", rateLimited: 5, retries: 2, wantErr: true, wantRequests: 3},
+		{name: "retries disabled", rateLimited: 1, retries: 0, wantErr: true, wantRequests: 1},
+	}
+
+	// Each provider method must go through the rate limit handling
+	methods := map[string]func(p *Provider) error{
+		"list": func(p *Provider) error {
+			_, err := p.ListRecords("example.com")
+			return err
+		},
+		"create": func(p *Provider) error {
+			record := models.NewDnsRecord(models.DnsRecordTypeA, "example.com", "1.2.3.4", 300)
+			_, err := p.CreateRecord("example.com", record, nil)
+			return err
+		},
+		"update": func(p *Provider) error {
+			id := "rec-1"
+			record := models.DnsRecord{ID: &id, RecordType: models.DnsRecordTypeA, Name: "example.com", Value: "1.2.3.4"}
+			_, err := p.UpdateRecord("example.com", &record, nil)
+			return err
+		},
+		"delete": func(p *Provider) error {
+			return p.DeleteRecord("example.com", "rec-1")
+		},
+	}
+
+	for _, tt := range tests {
+		for method, call := range methods {
+			t.Run(tt.name+"/"+method, func(t *testing.T) {
+				requests := 0
+				var slept []time.Duration
+				server := httptest.NewServer(http.HandlerFunc(func(w http.ResponseWriter, r *http.Request) {
+					requests++
+					if requests <= tt.rateLimited {
+						w.Header().Set("Retry-After", "2")
+						w.WriteHeader(http.StatusTooManyRequests)
+						_, _ = w.Write([]byte(`{"success":false,"errors":[{"code":971,"message":"Please wait and consider throttling your request speed"}]}`))
+						return
+					}
+
+					// Listing returns an array of records, the other methods a single record
+					var result interface{} = cloudflareRecord{ID: "rec-1", Type: "A", Name: "example.com", Content: "1.2.3.4", TTL: 300}
+					if r.Method == http.MethodGet {
+						result = []interface{}{result}
+					}
+					_ = json.NewEncoder(w).Encode(map[string]interface{}{
+						"success":     true,
+						"result":      result,
+						"result_info": map[string]int{"page": 1, "total_pages": 1},
+					})
+				}))
+				defer server.Close()
+
+				p := newTestProvider(server)
+				p.SetRateLimitRetries(tt.retries)
+				p.sleep = func(d time.Duration) { slept = append(slept, d) }
+
+				err := call(p)
+				if tt.wantErr && err == nil {
+					t.Fatal("expected rate limit error")
+				}
+				if !tt.wantErr && err != nil {
+					t.Fatalf("unexpected error: %v", err)
+				}
+				if requests != tt.wantRequests {
+					t.Errorf("made %d requests, want %d", requests, tt.wantRequests)
+				}
+				for _, d := range slept {
+					if d != 2*time.Second {
+						t.Errorf("slept %s, want Retry-After of 2s", d)
+					}
+				}
+			})
+		}
+	}
+}
+
+func TestRetryAfter(t *testing.T) {
+	now := time.Date(2024, 1, 1, 12, 0, 0, 0, time.UTC)
+
+	tests := []struct {
+		name    string
+		header  string
+		attempt int
+		want    time.Duration
+	}{
+		{name: "seconds", header: "5", want: 5 * time.Second},
+		{name: "zero seconds", header: "0", want: 0},
+		{name: "HTTP date", header: now.Add(10 * time.Second).Format(http.TimeFormat), want: 10 * time.Second},
+		{name: "date in the past", header: now.Add(-time.Minute).Format(http.TimeFormat), want: 0},
+		{name: "missing header backs off", header: "", attempt: 2, want: 4 * time.Second},
+		{name: "unparseable header backs off", header: "soon", attempt: 0, want: defaultRetryAfter},
+		{name: "long wait is capped", header: "3600", want: maxRetryAfter},
+	}
+
+	for _, tt := range tests {
+		t.Run(tt.name, func(t *testing.T) {
+			if got := retryAfter(tt.header, tt.attempt, now); got != tt.want {
+				t.Errorf("retryAfter(%q, %d) = %s, want %s", tt.header, tt.attempt, got, tt.want)
+			}
+		})
+	}
+}
+
+func TestRecordRoundTrip(t *testing.T) {
+	tests := []struct {
+		name     string
+		record   models.DnsRecord
+		wantJSON string
+	}{
+		{
+			name:     "CAA is sent as structured data",
+			record:   models.DnsRecord{RecordType: models.DnsRecordTypeCAA, Name: "example.com", Value: `0 issue "letsencrypt.org"`, TTL: 300},
+			wantJSON: `{"type":"CAA","name":"example.com","data":{"flags":0,"tag":"issue","value":"letsencrypt.org"},"ttl":300,"proxied":false}`,
+		},
+		{
+			name:     "NS is sent as content",
+			record:   models.DnsRecord{RecordType: models.DnsRecordTypeNS, Name: "sub.example.com", Value: "ns1.example.net", TTL: 3600},
+			wantJSON: `{"type":"NS","name":"sub.example.com","content":"ns1.example.net","ttl":3600,"proxied":false}`,
+		},
+		{
+			name:     "PTR is sent as content",
+			record:   models.DnsRecord{RecordType: models.DnsRecordTypePTR, Name: "10.113.0.203.in-addr.arpa", Value: "host.example.com", TTL: 300},
+			wantJSON: `{"type":"PTR","name":"10.113.0.203.in-addr.arpa","content":"host.example.com","ttl":300,"proxied":false}`,
+		},
+	}
+
+	for _, tt := range tests {
+		t.Run(tt.name, func(t *testing.T) {
+			cfRec := toCloudflareRecord(&tt.record, nil)
+			data, err := json.Marshal(cfRec)
+			if err != nil {
+				t.Fatal(err)
+			}
+			if string(data) != tt.wantJSON {
+				t.Errorf("request body = %s, want %s", data, tt.wantJSON)
+			}
+
+			// Cloudflare also returns content for CAA records; data takes precedence
+			cfRec.ID = "rec-1"
+			if cfRec.Data != nil {
+				cfRec.Content = `0 issue "letsencrypt.org"`
+			}
+			got := fromCloudflareRecord(cfRec)
+			if got.RecordType != tt.record.RecordType || got.Value != tt.record.Value || got.TTL != tt.record.TTL {
+				t.Errorf("round trip = %+v, want %+v", got, tt.record)
+			}
+		})
+	}
+}
+
+func TestLookupZoneID(t *testing.T) {
+	tests := []struct {
+		name    string
+		domain  string
+		zones   map[string]string // Zone name to ID, as visible to the token
+		denied  bool
+		want    string
+		wantErr error
+	}{
+		{name: "zone apex", domain: "example.com", zones: map[string]string{"example.com": "zone-1"}, want: "zone-1"},
+		{name: "subdomain uses parent zone", domain: "app.example.com.", zones: map[string]string{"example.com": "zone-1"}, want: "zone-1"},
+		{name: "no visible zone", domain: "example.org", zones: map[string]string{"example.com": "zone-1"}},
+		{name: "token can't list zones", domain: "example.com", denied: true, wantErr: ErrZoneListDenied},
+	}
+
+	for _, tt := range tests {
+		t.Run(tt.name, func(t *testing.T) {
+			server := httptest.NewServer(http.HandlerFunc(func(w http.ResponseWriter, r *http.Request) {
+				if tt.denied {
+					w.WriteHeader(http.StatusForbidden)
+					_ = json.NewEncoder(w).Encode(map[string]interface{}{
+						"success": false,
+						"errors":  []cloudflareError{{Code: 9109, Message: "Unauthorized to access requested resource"}},
+					})
+					return
+				}
+				name := r.URL.Query().Get("name")
+				result := []map[string]string{}
+				if id, ok := tt.zones[name]; ok {
+					result = append(result, map[string]string{"id": id, "name": name})
+				}
+				_ = json.NewEncoder(w).Encode(map[string]interface{}{"success": true, "result": result})
+			}))
+			defer server.Close()
+
+			got, err := newTestProvider(server).LookupZoneID(tt.domain)
+			switch {
+			case tt.wantErr != nil:
+				if err != tt.wantErr {
+					t.Fatalf("LookupZoneID() error = %v, want %v", err, tt.wantErr)
+				}
+			case tt.want == "":
+				if err == nil {
+					t.Fatalf("LookupZoneID() = %q, want an error", got)
+				}
+			case err != nil:
+				t.Fatalf("LookupZoneID() error = %v", err)
+			}
+			if got != tt.want {
+				t.Errorf("LookupZoneID() = %q, want %q", got, tt.want)
+			}
+		})
+	}
+}
+
+func TestVerifyAccess(t *testing.T) {
+	tests := []struct {
+		name        string
+		tokenStatus string // Empty when the token isn't recognised
+		canRead     bool
+		canEdit     bool
+		editStatus  int // Status of the edit check when the token can edit; 404 "record not found" if 0
+		wantErr     bool
+		wantNoEdit  bool // The error should be ErrCannotEditDNS
+	}{
+		{name: "token can edit DNS", tokenStatus: "active", canRead: true, canEdit: true},
+		{name: "edit check hits a server error", tokenStatus: "active", canRead: true, canEdit: true, editStatus: http.StatusInternalServerError, wantErr: true},
+		{name: "edit check is unauthorized", tokenStatus: "active", canRead: true, canEdit: true, editStatus: http.StatusUnauthorized, wantErr: true},
+		{name: "read-only token", tokenStatus: "active", canRead: true, wantErr: true, wantNoEdit: true},
+		{name: "token for another zone", tokenStatus: "active", wantErr: true},
+		{name: "disabled token", tokenStatus: "disabled", canRead: true, canEdit: true, wantErr: true},
+		{name: "unknown token", wantErr: true},
+	}
+
+	for _, tt := range tests {
+		t.Run(tt.name, func(t *testing.T) {
+			forbidden := func(w http.ResponseWriter) {
+				w.WriteHeader(http.StatusForbidden)
+				_ = json.NewEncoder(w).Encode(map[string]interface{}{
+					"success": false,
+					"errors":  []cloudflareError{{Code: 10000, Message: "Authentication error"}},
+				})
+			}
+			server := httptest.NewServer(http.HandlerFunc(func(w http.ResponseWriter, r *http.Request) {
+				switch {
+				case r.URL.Path == "/user/tokens/verify":
+					if tt.tokenStatus == "" {
+						w.WriteHeader(http.StatusUnauthorized)
+						_ = json.NewEncoder(w).Encode(map[string]interface{}{
+							"success": false,
+							"errors":  []cloudflareError{{Code: 1000, Message: "Invalid API Token"}},
+						})
+						return
+					}
+					_ = json.NewEncoder(w).Encode(map[string]interface{}{
+						"success": true,
+						"result":  map[string]string{"status": tt.tokenStatus},
+					})
+				case r.Method == http.MethodGet && r.URL.Path == "/zones/zone-1/dns_records":
+					if !tt.canRead {
+						forbidden(w)
+						return
+					}
+					_ = json.NewEncoder(w).Encode(map[string]interface{}{"success": true, "result": []cloudflareRecord{}})
+				case r.Method == http.MethodPatch:
+					if !tt.canEdit {
+						forbidden(w)
+						return
+					}
+					if tt.editStatus != 0 {
+						w.WriteHeader(tt.editStatus)
+						_ = json.NewEncoder(w).Encode(map[string]interface{}{
+							"success": false,
+							"errors":  []cloudflareError{{Code: 10000, Message: http.StatusText(tt.editStatus)}},
+						})
+						return
+					}
+					w.WriteHeader(http.StatusNotFound)
+					_ = json.NewEncoder(w).Encode(map[string]interface{}{
+						"success": false,
+						"errors":  []cloudflareError{{Code: 81044, Message: "Record does not exist."}},
+					})
+				default:
+					t.Errorf("unexpected request %s %s", r.Method, r.URL.Path)
+				}
+			}))
+			defer server.Close()
+
+			err := newTestProvider(server).VerifyAccess()
+			if (err != nil) != tt.wantErr {
+				t.Fatalf("VerifyAccess() error = %v, wantErr %v", err, tt.wantErr)
+			}
+			if tt.wantNoEdit && err != ErrCannotEditDNS {
+				t.Errorf("VerifyAccess() error = %v, want %v", err, ErrCannotEditDNS)
+			}
+		})
+	}
+}
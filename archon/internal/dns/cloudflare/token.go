@@ -0,0 +1,102 @@
+package cloudflare
+
+import (
+	"encoding/json"
+	"errors"
+	"fmt"
+	"net/http"
+)
+
+// ErrCannotEditDNS is returned by VerifyAccess when the API token may read the zone's DNS records but not change them
+var ErrCannotEditDNS = errors.New("token cannot edit DNS for this zone")
+
+// permissionCheckRecordID is a record ID that never exists. Updating it is refused with 403 when the
+// token lacks DNS edit permission, and fails with "not found" when it has it, so nothing changes.
+const permissionCheckRecordID = "00000000000000000000000000000000"
+
+// recordNotFoundCode is Cloudflare's error code for a DNS record that doesn't exist
+const recordNotFoundCode = 81044
+
+// VerifyAccess checks the provider's API token is active and can read and edit DNS records in its
+// zone, so a bad token is caught when the domain is added rather than at its first sync
+func (p *Provider) VerifyAccess() error {
+	if err := p.verifyToken(); err != nil {
+		return err
+	}
+
+	// Reading the zone's records needs DNS read permission for this zone in particular
+	url := fmt.Sprintf("%s/zones/%s/dns_records?per_page=1", p.baseURL, p.zoneID)
+	resp, err := p.doRequest("GET", url, nil)
+	if err != nil {
+		return err
+	}
+	var cfResp cloudflareResponse
+	err = json.NewDecoder(resp.Body).Decode(&cfResp)
+	resp.Body.Close()
+	if err != nil {
+		return fmt.Errorf("failed to parse response: %w", err)
+	}
+	if !cfResp.Success {
+		if len(cfResp.Errors) > 0 {
+			return fmt.Errorf("token cannot read DNS for this zone: %s", cfResp.Errors[0].Message)
+		}
+		return fmt.Errorf("token cannot read DNS for this zone")
+	}
+
+	url = fmt.Sprintf("%s/zones/%s/dns_records/%s", p.baseURL, p.zoneID, permissionCheckRecordID)
+	resp, err = p.doRequest("PATCH", url, []byte("{}"))
+	if err != nil {
+		return err
+	}
+	defer resp.Body.Close()
+	if resp.StatusCode == http.StatusForbidden {
+		return ErrCannotEditDNS
+	}
+
+	// Only "not found" shows the edit was allowed; anything else (401, 5xx) proves nothing
+	cfResp = cloudflareResponse{}
+	_ = json.NewDecoder(resp.Body).Decode(&cfResp)
+	if resp.StatusCode == http.StatusNotFound {
+		return nil
+	}
+	for _, e := range cfResp.Errors {
+		if e.Code == recordNotFoundCode {
+			return nil
+		}
+	}
+	if len(cfResp.Errors) > 0 {
+		return fmt.Errorf("failed to check DNS edit permission: HTTP %d: %s", resp.StatusCode, cfResp.Errors[0].Message)
+	}
+	return fmt.Errorf("failed to check DNS edit permission: HTTP %d", resp.StatusCode)
+}
+
+// verifyToken checks the API token is valid and active with Cloudflare's token verification endpoint
+func (p *Provider) verifyToken() error {
+	resp, err := p.doRequest("GET", p.baseURL+"/user/tokens/verify", nil)
+	if err != nil {
+		return err
+	}
+	defer resp.Body.Close()
+
+	var cfResp struct {
+		Success bool              `json:"success"`
+		Errors  []cloudflareError `json:"errors"`
+		Result  struct {
+			Status string `json:"status"`
+		} `json:"result"`
+	}
+	if err := json.NewDecoder(resp.Body).Decode(&cfResp); err != nil {
+		return fmt.Errorf("failed to parse response: %w", err)
+	}
+
+	if !cfResp.Success {
+		if len(cfResp.Errors) > 0 {
+			return fmt.Errorf("API token is invalid: %s", cfResp.Errors[0].Message)
+		}
+		return fmt.Errorf("API token is invalid")
+	}
+	if cfResp.Result.Status != "active" {
+		return fmt.Errorf("API token is %s, not active", cfResp.Result.Status)
+	}
+	return nil
+}
@@ -0,0 +1,70 @@
+package cloudflare
+
+import (
+	"encoding/json"
+	"errors"
+	"fmt"
+	"net/http"
+	"net/url"
+	"strings"
+)
+
+// ErrZoneListDenied is returned by LookupZoneID when the API token isn't allowed to list zones
+var ErrZoneListDenied = errors.New("the Cloudflare API token isn't allowed to list zones (it needs Zone:Read)")
+
+// LookupZoneID finds the ID of the zone serving domain, so it doesn't have to be copied from the
+// Cloudflare dashboard. A subdomain such as app.example.com is looked up in its parent zones in turn.
+// The provider's own zone ID isn't used.
+func (p *Provider) LookupZoneID(domain string) (string, error) {
+	name := strings.ToLower(strings.TrimSuffix(strings.TrimSpace(domain), "."))
+	for strings.Contains(name, ".") {
+		zoneID, err := p.findZone(name)
+		if err != nil || zoneID != "" {
+			return zoneID, err
+		}
+		name = name[strings.Index(name, ".")+1:]
+	}
+	return "", fmt.Errorf("no Cloudflare zone for %s is visible to the API token", domain)
+}
+
+// findZone returns the ID of the zone named name, or "" if the token can't see one
+func (p *Provider) findZone(name string) (string, error) {
+	zonesURL := fmt.Sprintf("%s/zones?name=%s", p.baseURL, url.QueryEscape(name))
+
+	resp, err := p.doRequest("GET", zonesURL, nil)
+	if err != nil {
+		return "", err
+	}
+	defer resp.Body.Close()
+
+	// Tokens without Zone:Read are refused outright
+	if resp.StatusCode == http.StatusForbidden {
+		return "", ErrZoneListDenied
+	}
+
+	var cfResp struct {
+		Success bool              `json:"success"`
+		Errors  []cloudflareError `json:"errors"`
+		Result  []struct {
+			ID   string `json:"id"`
+			Name string `json:"name"`
+		} `json:"result"`
+	}
+	if err := json.NewDecoder(resp.Body).Decode(&cfResp); err != nil {
+		return "", fmt.Errorf("failed to parse response: %w", err)
+	}
+
+	if !cfResp.Success {
+		if len(cfResp.Errors) > 0 {
+			return "", fmt.Errorf("cloudflare API error: %s", cfResp.Errors[0].Message)
+		}
+		return "", fmt.Errorf("cloudflare API request failed")
+	}
+
+	for _, zone := range cfResp.Result {
+		if strings.EqualFold(zone.Name, name) {
+			return zone.ID, nil
+		}
+	}
+	return "", nil
+}
@@ -0,0 +1,73 @@
+package dns
+
+import (
+	"fmt"
+	"sync"
+
+	"github.com/BlueBeard63/archon/internal/models"
+)
+
+// DemoProvider is an in-memory Provider for demo mode. Records are kept per domain until
+// Archon exits, with IDs assigned like a real provider's.
+type DemoProvider struct {
+	mu      sync.Mutex
+	records map[string][]models.DnsRecord
+	nextID  int
+}
+
+// NewDemoProvider creates a demo provider with no records
+func NewDemoProvider() *DemoProvider {
+	return &DemoProvider{records: make(map[string][]models.DnsRecord)}
+}
+
+// ListRecords returns a copy of the domain's records
+func (p *DemoProvider) ListRecords(domain string) ([]models.DnsRecord, error) {
+	p.mu.Lock()
+	defer p.mu.Unlock()
+	return append([]models.DnsRecord(nil), p.records[domain]...), nil
+}
+
+// CreateRecord stores a record under a new ID
+func (p *DemoProvider) CreateRecord(domain string, record *models.DnsRecord, tags []string) (*models.DnsRecord, error) {
+	p.mu.Lock()
+	defer p.mu.Unlock()
+	p.nextID++
+	id := fmt.Sprintf("demo-%d", p.nextID)
+	created := *record
+	created.ID = &id
+	created.SyncState, created.RemoteValue, created.ConflictValue = "", "", ""
+	p.records[domain] = append(p.records[domain], created)
+	return &created, nil
+}
+
+// UpdateRecord replaces the record with the same ID
+func (p *DemoProvider) UpdateRecord(domain string, record *models.DnsRecord, tags []string) (*models.DnsRecord, error) {
+	if record.ID == nil {
+		return nil, fmt.Errorf("record has no ID")
+	}
+	p.mu.Lock()
+	defer p.mu.Unlock()
+	for i, existing := range p.records[domain] {
+		if *existing.ID == *record.ID {
+			updated := *record
+			updated.SyncState, updated.RemoteValue, updated.ConflictValue = "", "", ""
+			p.records[domain][i] = updated
+			return &updated, nil
+		}
+	}
+	return nil, fmt.Errorf("record %s not found", *record.ID)
+}
+
+// DeleteRecord removes the record with the given ID
+func (p *DemoProvider) DeleteRecord(domain string, recordID string) error {
+	p.mu.Lock()
+	defer p.mu.Unlock()
+	records := p.records[domain]
+	for i, existing := range records {
+		if *existing.ID == recordID {
+			p.records[domain] = append(records[:i:i], records[i+1:]...)
+			return nil
+		}
+	}
+	return fmt.Errorf("record %s not found", recordID)
+}
@@ -0,0 +1,93 @@
+package dns
+
+import (
+	"github.com/BlueBeard63/archon/internal/models"
+)
+
+// RecordChange pairs a local record with the provider's differing version of it
+type RecordChange struct {
+	Local  models.DnsRecord
+	Remote models.DnsRecord
+}
+
+// RecordDiff describes how the provider's records differ from the local ones
+type RecordDiff struct {
+	Added    []models.DnsRecord // Records only at the provider
+	Removed  []models.DnsRecord // Records only in the local set
+	Modified []RecordChange     // Records in both with a different value, TTL or proxy setting
+}
+
+// IsEmpty returns true if the local and provider records match
+func (d RecordDiff) IsEmpty() bool {
+	return d.Count() == 0
+}
+
+// Count returns the total number of differing records
+func (d RecordDiff) Count() int {
+	return len(d.Added) + len(d.Removed) + len(d.Modified)
+}
+
+// DiffRecords compares local records against the provider's, keyed by (type, name).
+// Several records can share a key (e.g. multiple TXT or MX records), so within a key
+// records with identical values are paired first and the rest are paired in order.
+// It does not modify either slice.
+func DiffRecords(local, remote []models.DnsRecord) RecordDiff {
+	var diff RecordDiff
+
+	// Group remote record indexes by key, keeping provider order
+	byKey := make(map[string][]int)
+	for i := range remote {
+		key := recordKey(&remote[i])
+		byKey[key] = append(byKey[key], i)
+	}
+	matched := make([]bool, len(remote))
+
+	// First pass: pair records whose values already match
+	pairs := make([]int, len(local))
+	for i := range local {
+		pairs[i] = -1
+		for _, idx := range byKey[recordKey(&local[i])] {
+			if !matched[idx] && remote[idx].Value == local[i].Value {
+				pairs[i] = idx
+				matched[idx] = true
+				break
+			}
+		}
+	}
+
+	// Second pass: pair the remaining records with the next unmatched remote record of the same key
+	for i := range local {
+		if pairs[i] != -1 {
+			continue
+		}
+		for _, idx := range byKey[recordKey(&local[i])] {
+			if !matched[idx] {
+				pairs[i] = idx
+				matched[idx] = true
+				break
+			}
+		}
+	}
+
+	for i, idx := range pairs {
+		switch {
+		case idx == -1:
+			diff.Removed = append(diff.Removed, local[i])
+		case recordsDiffer(&local[i], &remote[idx]):
+			diff.Modified = append(diff.Modified, RecordChange{Local: local[i], Remote: remote[idx]})
+		}
+	}
+
+	for i := range remote {
+		if !matched[i] {
+			diff.Added = append(diff.Added, remote[i])
+		}
+	}
+
+	return diff
+}
+
+// recordsDiffer returns true if the provider-visible fields of two records differ
+func recordsDiffer(a, b *models.DnsRecord) bool {
+	return a.Value != b.Value || a.TTL != b.TTL || a.Proxied != b.Proxied
+}
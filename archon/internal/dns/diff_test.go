@@ -0,0 +1,141 @@
+package dns
+
+import (
+	"testing"
+
+	"github.com/BlueBeard63/archon/internal/models"
+)
+
+func TestDiffRecords(t *testing.T) {
+	tests := []struct {
+		name         string
+		local        []models.DnsRecord
+		remote       []models.DnsRecord
+		wantAdded    []string // values of added records, in order
+		wantRemoved  []string // values of removed records, in order
+		wantModified []string // "local->remote" values of modified records, in order
+	}{
+		{
+			name: "identical sets have no diff",
+			local: []models.DnsRecord{
+				{RecordType: models.DnsRecordTypeA, Name: "example.com", Value: "1.1.1.1", TTL: 300},
+			},
+			remote: []models.DnsRecord{
+				{ID: strPtr("1"), RecordType: models.DnsRecordTypeA, Name: "example.com", Value: "1.1.1.1", TTL: 300},
+			},
+		},
+		{
+			name:  "empty local adds everything",
+			local: nil,
+			remote: []models.DnsRecord{
+				{RecordType: models.DnsRecordTypeA, Name: "example.com", Value: "1.1.1.1"},
+				{RecordType: models.DnsRecordTypeCNAME, Name: "www.example.com", Value: "example.com"},
+			},
+			wantAdded: []string{"1.1.1.1", "example.com"},
+		},
+		{
+			name: "empty remote removes everything",
+			local: []models.DnsRecord{
+				{RecordType: models.DnsRecordTypeA, Name: "example.com", Value: "1.1.1.1"},
+			},
+			remote:      nil,
+			wantRemoved: []string{"1.1.1.1"},
+		},
+		{
+			name: "changed value is modified",
+			local: []models.DnsRecord{
+				{RecordType: models.DnsRecordTypeA, Name: "example.com", Value: "1.1.1.1"},
+			},
+			remote: []models.DnsRecord{
+				{RecordType: models.DnsRecordTypeA, Name: "example.com", Value: "2.2.2.2"},
+			},
+			wantModified: []string{"1.1.1.1->2.2.2.2"},
+		},
+		{
+			name: "changed ttl or proxy is modified",
+			local: []models.DnsRecord{
+				{RecordType: models.DnsRecordTypeA, Name: "example.com", Value: "1.1.1.1", TTL: 300},
+				{RecordType: models.DnsRecordTypeA, Name: "www.example.com", Value: "1.1.1.1", TTL: 300},
+			},
+			remote: []models.DnsRecord{
+				{RecordType: models.DnsRecordTypeA, Name: "example.com", Value: "1.1.1.1", TTL: 3600},
+				{RecordType: models.DnsRecordTypeA, Name: "www.example.com", Value: "1.1.1.1", TTL: 300, Proxied: true},
+			},
+			wantModified: []string{"1.1.1.1->1.1.1.1", "1.1.1.1->1.1.1.1"},
+		},
+		{
+			name: "same name with different type is not a match",
+			local: []models.DnsRecord{
+				{RecordType: models.DnsRecordTypeA, Name: "example.com", Value: "1.1.1.1"},
+			},
+			remote: []models.DnsRecord{
+				{RecordType: models.DnsRecordTypeAAAA, Name: "example.com", Value: "2001:db8::1"},
+			},
+			wantAdded:   []string{"2001:db8::1"},
+			wantRemoved: []string{"1.1.1.1"},
+		},
+		{
+			name: "records sharing a key pair by value before order",
+			local: []models.DnsRecord{
+				{RecordType: models.DnsRecordTypeTXT, Name: "example.com", Value: "v=spf1 -all"},
+				{RecordType: models.DnsRecordTypeTXT, Name: "example.com", Value: "verify=abc"},
+			},
+			remote: []models.DnsRecord{
+				{RecordType: models.DnsRecordTypeTXT, Name: "example.com", Value: "verify=xyz"},
+				{RecordType: models.DnsRecordTypeTXT, Name: "example.com", Value: "v=spf1 -all"},
+			},
+			wantModified: []string{"verify=abc->verify=xyz"},
+		},
+		{
+			name: "extra records sharing a key are added",
+			local: []models.DnsRecord{
+				{RecordType: models.DnsRecordTypeTXT, Name: "example.com", Value: "one"},
+			},
+			remote: []models.DnsRecord{
+				{RecordType: models.DnsRecordTypeTXT, Name: "example.com", Value: "one"},
+				{RecordType: models.DnsRecordTypeTXT, Name: "example.com", Value: "two"},
+			},
+			wantAdded: []string{"two"},
+		},
+	}
+
+	for _, tt := range tests {
+		t.Run(tt.name, func(t *testing.T) {
+			diff := DiffRecords(tt.local, tt.remote)
+
+			var added, removed, modified []string
+			for _, rec := range diff.Added {
+				added = append(added, rec.Value)
+			}
+			for _, rec := range diff.Removed {
+				removed = append(removed, rec.Value)
+			}
+			for _, change := range diff.Modified {
+				modified = append(modified, change.Local.Value+"->"+change.Remote.Value)
+			}
+
+			assertValues(t, "Added", added, tt.wantAdded)
+			assertValues(t, "Removed", removed, tt.wantRemoved)
+			assertValues(t, "Modified", modified, tt.wantModified)
+
+			wantEmpty := len(tt.wantAdded)+len(tt.wantRemoved)+len(tt.wantModified) == 0
+			if diff.IsEmpty() != wantEmpty {
+				t.Errorf("IsEmpty() = %v, want %v", diff.IsEmpty(), wantEmpty)
+			}
+		})
+	}
+}
+
+func assertValues(t *testing.T, label string, got, want []string) {
+	t.Helper()
+	if len(got) != len(want) {
+		t.Errorf("%s = %v, want %v", label, got, want)
+		return
+	}
+	for i := range got {
+		if got[i] != want[i] {
+			t.Errorf("%s = %v, want %v", label, got, want)
+			return
+		}
+	}
+}
@@ -0,0 +1,205 @@
+package dns
+
+import (
+	"context"
+	"fmt"
+	"net"
+	"strings"
+	"sync"
+
+	"github.com/BlueBeard63/archon/internal/models"
+)
+
+// DefaultResolvers are the public resolvers queried by CheckPropagation: Cloudflare, Google and Quad9
+var DefaultResolvers = []string{"1.1.1.1:53", "8.8.8.8:53", "9.9.9.9:53"}
+
+// Resolver looks up the values a DNS server returns for a name and record type
+type Resolver interface {
+	Lookup(ctx context.Context, server string, recordType models.DnsRecordType, name string) ([]string, error)
+}
+
+// ResolverResult is what one resolver returned for a record
+type ResolverResult struct {
+	Server string
+	Values []string // Values returned, in the form records hold them (hostnames without a trailing dot)
+	Match  bool     // The expected value was among the returned values
+	Error  error
+}
+
+// PropagationResult is how far a record has propagated across the queried resolvers
+type PropagationResult struct {
+	Record    models.DnsRecord
+	Resolvers []ResolverResult
+}
+
+// Matched returns how many resolvers returned the record's value
+func (p PropagationResult) Matched() int {
+	matched := 0
+	for _, r := range p.Resolvers {
+		if r.Match {
+			matched++
+		}
+	}
+	return matched
+}
+
+// Propagated returns true if every resolver returned the record's value
+func (p PropagationResult) Propagated() bool {
+	return len(p.Resolvers) > 0 && p.Matched() == len(p.Resolvers)
+}
+
+// CheckPropagation asks each server for the record and reports whether it returned the
+// record's value. Servers are queried concurrently and results keep the order of servers.
+// Proxied records resolve to the provider's edge addresses, so any answer counts as a match.
+func CheckPropagation(ctx context.Context, resolver Resolver, servers []string, domain string, record models.DnsRecord) PropagationResult {
+	result := PropagationResult{Record: record, Resolvers: make([]ResolverResult, len(servers))}
+	name := QualifiedName(record.Name, domain)
+
+	var wg sync.WaitGroup
+	for i, server := range servers {
+		wg.Add(1)
+		go func(i int, server string) {
+			defer wg.Done()
+			values, err := resolver.Lookup(ctx, server, record.RecordType, name)
+			res := ResolverResult{Server: server, Values: values, Error: err}
+			if err == nil {
+				res.Match = (record.Proxied && len(values) > 0) || containsValue(record, values)
+			}
+			result.Resolvers[i] = res
+		}(i, server)
+	}
+	wg.Wait()
+
+	return result
+}
+
+// QualifiedName returns the full name a record is published under: "@" and the empty name
+// are the domain itself, and relative names are placed under the domain
+func QualifiedName(name, domain string) string {
+	name = strings.TrimSuffix(name, ".")
+	domain = strings.TrimSuffix(domain, ".")
+	switch {
+	case name == "" || name == "@":
+		return domain
+	case strings.EqualFold(name, domain) || strings.HasSuffix(strings.ToLower(name), "."+strings.ToLower(domain)):
+		return name
+	}
+	return name + "." + domain
+}
+
+// containsValue returns true if one of values is the record's value
+func containsValue(record models.DnsRecord, values []string) bool {
+	expected := record.Value
+	if record.RecordType == models.DnsRecordTypeMX {
+		// Resolvers return the mail host only; the priority is not compared
+		if fields := strings.Fields(expected); len(fields) == 2 {
+			expected = fields[1]
+		}
+	}
+
+	for _, value := range values {
+		switch record.RecordType {
+		case models.DnsRecordTypeA, models.DnsRecordTypeAAAA:
+			if ip := net.ParseIP(value); ip != nil && record.PointsTo(ip) {
+				return true
+			}
+		case models.DnsRecordTypeTXT:
+			if value == expected {
+				return true
+			}
+		case models.DnsRecordTypeCAA:
+			// Records may leave the value unquoted or the tag uppercase, so compare parsed values
+			got, gotErr := models.ParseCAA(value)
+			want, wantErr := models.ParseCAA(expected)
+			if gotErr == nil && wantErr == nil && got == want {
+				return true
+			}
+		default:
+			if strings.EqualFold(strings.TrimSuffix(value, "."), strings.TrimSuffix(expected, ".")) {
+				return true
+			}
+		}
+	}
+	return false
+}
+
+// NetResolver queries servers directly over UDP with the Go resolver, bypassing the system's
+// resolver configuration and cache. CNAME, CAA and PTR records, which the Go resolver can't
+// return as published, are queried with a minimal DNS client instead.
+type NetResolver struct{}
+
+// Lookup implements Resolver
+func (NetResolver) Lookup(ctx context.Context, server string, recordType models.DnsRecordType, name string) ([]string, error) {
+	r := &net.Resolver{
+		PreferGo: true,
+		Dial: func(ctx context.Context, network, _ string) (net.Conn, error) {
+			var d net.Dialer
+			return d.DialContext(ctx, network, server)
+		},
+	}
+
+	switch recordType {
+	case models.DnsRecordTypeA, models.DnsRecordTypeAAAA:
+		network := "ip4"
+		if recordType == models.DnsRecordTypeAAAA {
+			network = "ip6"
+		}
+		ips, err := r.LookupIP(ctx, network, name)
+		if err != nil {
+			return nil, err
+		}
+		values := make([]string, len(ips))
+		for i, ip := range ips {
+			values[i] = ip.String()
+		}
+		return values, nil
+
+	case models.DnsRecordTypeCNAME:
+		// LookupCNAME follows the whole chain, but a record is compared with its own target
+		return rawLookup(ctx, server, name, typeCNAME)
+
+	case models.DnsRecordTypeCAA:
+		return rawLookup(ctx, server, name, typeCAA)
+
+	case models.DnsRecordTypePTR:
+		return rawLookup(ctx, server, name, typePTR)
+
+	case models.DnsRecordTypeMX:
+		mxs, err := r.LookupMX(ctx, name)
+		if err != nil {
+			return nil, err
+		}
+		values := make([]string, len(mxs))
+		for i, mx := range mxs {
+			values[i] = strings.TrimSuffix(mx.Host, ".")
+		}
+		return values, nil
+
+	case models.DnsRecordTypeTXT:
+		return r.LookupTXT(ctx, name)
+
+	case models.DnsRecordTypeNS:
+		nss, err := r.LookupNS(ctx, name)
+		if err != nil {
+			return nil, err
+		}
+		values := make([]string, len(nss))
+		for i, ns := range nss {
+			values[i] = strings.TrimSuffix(ns.Host, ".")
+		}
+		return values, nil
+
+	case models.DnsRecordTypeSRV:
+		_, srvs, err := r.LookupSRV(ctx, "", "", name)
+		if err != nil {
+			return nil, err
+		}
+		values := make([]string, len(srvs))
+		for i, srv := range srvs {
+			values[i] = fmt.Sprintf("%d %d %d %s", srv.Priority, srv.Weight, srv.Port, strings.TrimSuffix(srv.Target, "."))
+		}
+		return values, nil
+	}
+
+	return nil, fmt.Errorf("checking %s records is not supported", recordType)
+}
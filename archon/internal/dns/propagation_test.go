@@ -0,0 +1,247 @@
+package dns
+
+import (
+	"context"
+	"encoding/binary"
+	"errors"
+	"net"
+	"reflect"
+	"testing"
+	"time"
+
+	"github.com/BlueBeard63/archon/internal/models"
+)
+
+// fakeResolver answers from a fixed table of server -> name -> values; unknown names fail
+type fakeResolver map[string]map[string][]string
+
+func (f fakeResolver) Lookup(ctx context.Context, server string, recordType models.DnsRecordType, name string) ([]string, error) {
+	values, ok := f[server][name]
+	if !ok {
+		return nil, errors.New("no such host")
+	}
+	return values, nil
+}
+
+func TestCheckPropagation(t *testing.T) {
+	servers := []string{"one:53", "two:53", "three:53"}
+	resolver := fakeResolver{
+		"one:53": {
+			"www.example.com":  {"203.0.113.10"},
+			"example.com":      {"mail.example.com"},
+			"blog.example.com": {"example.com"},
+			"v6.example.com":   {"2001:db8::1"},
+			"caa.example.com":  {`0 issue "letsencrypt.org"`},
+		},
+		"two:53": {
+			"www.example.com":  {"203.0.113.99"},
+			"example.com":      {"mail.example.com"},
+			"blog.example.com": {"EXAMPLE.com"},
+			"v6.example.com":   {"2001:db8:0:0:0:0:0:1"},
+			"caa.example.com":  {`0 issue "pki.goog"`},
+		},
+		"three:53": {
+			"example.com":      {"other.example.net"},
+			"blog.example.com": {"example.com"},
+			"v6.example.com":   {"2001:db8::1"},
+		},
+	}
+
+	tests := []struct {
+		name      string
+		record    models.DnsRecord
+		wantMatch []bool
+		wantErr   []bool
+	}{
+		{
+			name:      "A partially propagated",
+			record:    models.DnsRecord{RecordType: models.DnsRecordTypeA, Name: "www", Value: "203.0.113.10"},
+			wantMatch: []bool{true, false, false},
+			wantErr:   []bool{false, false, true},
+		},
+		{
+			name:      "proxied A matches any answer",
+			record:    models.DnsRecord{RecordType: models.DnsRecordTypeA, Name: "www", Value: "203.0.113.10", Proxied: true},
+			wantMatch: []bool{true, true, false},
+			wantErr:   []bool{false, false, true},
+		},
+		{
+			name:      "MX ignores priority",
+			record:    models.DnsRecord{RecordType: models.DnsRecordTypeMX, Name: "@", Value: "10 mail.example.com"},
+			wantMatch: []bool{true, true, false},
+			wantErr:   []bool{false, false, false},
+		},
+		{
+			name:      "CNAME compares case-insensitively",
+			record:    models.DnsRecord{RecordType: models.DnsRecordTypeCNAME, Name: "blog.example.com", Value: "example.com."},
+			wantMatch: []bool{true, true, true},
+			wantErr:   []bool{false, false, false},
+		},
+		{
+			name:      "AAAA compares parsed addresses",
+			record:    models.DnsRecord{RecordType: models.DnsRecordTypeAAAA, Name: "v6", Value: "2001:db8::1"},
+			wantMatch: []bool{true, true, true},
+			wantErr:   []bool{false, false, false},
+		},
+		{
+			name:      "CAA compares parsed values",
+			record:    models.DnsRecord{RecordType: models.DnsRecordTypeCAA, Name: "caa", Value: "0 ISSUE letsencrypt.org"},
+			wantMatch: []bool{true, false, false},
+			wantErr:   []bool{false, false, true},
+		},
+	}
+
+	for _, tt := range tests {
+		t.Run(tt.name, func(t *testing.T) {
+			got := CheckPropagation(context.Background(), resolver, servers, "example.com", tt.record)
+			if len(got.Resolvers) != len(servers) {
+				t.Fatalf("got %d resolver results, want %d", len(got.Resolvers), len(servers))
+			}
+			for i, res := range got.Resolvers {
+				if res.Server != servers[i] {
+					t.Errorf("result %d server = %s, want %s", i, res.Server, servers[i])
+				}
+				if res.Match != tt.wantMatch[i] {
+					t.Errorf("%s match = %v, want %v", res.Server, res.Match, tt.wantMatch[i])
+				}
+				if (res.Error != nil) != tt.wantErr[i] {
+					t.Errorf("%s error = %v, wantErr %v", res.Server, res.Error, tt.wantErr[i])
+				}
+			}
+
+			wantPropagated := true
+			for _, match := range tt.wantMatch {
+				wantPropagated = wantPropagated && match
+			}
+			if got.Propagated() != wantPropagated {
+				t.Errorf("Propagated() = %v, want %v", got.Propagated(), wantPropagated)
+			}
+		})
+	}
+}
+
+func TestQualifiedName(t *testing.T) {
+	tests := []struct {
+		name string
+		want string
+	}{
+		{name: "@", want: "example.com"},
+		{name: "", want: "example.com"},
+		{name: "www", want: "www.example.com"},
+		{name: "www.example.com", want: "www.example.com"},
+		{name: "www.example.com.", want: "www.example.com"},
+		{name: "example.com", want: "example.com"},
+		{name: "notexample.com", want: "notexample.com.example.com"},
+	}
+
+	for _, tt := range tests {
+		t.Run(tt.name, func(t *testing.T) {
+			if got := QualifiedName(tt.name, "example.com"); got != tt.want {
+				t.Errorf("QualifiedName(%q) = %s, want %s", tt.name, got, tt.want)
+			}
+		})
+	}
+}
+
+// startDNSServer answers UDP queries on a local port with the response built by answer
+func startDNSServer(t *testing.T, answer func(query []byte) []byte) string {
+	t.Helper()
+	conn, err := net.ListenPacket("udp", "127.0.0.1:0")
+	if err != nil {
+		t.Fatalf("failed to listen: %v", err)
+	}
+	t.Cleanup(func() { conn.Close() })
+
+	go func() {
+		buf := make([]byte, maxUDPMessage)
+		for {
+			n, addr, err := conn.ReadFrom(buf)
+			if err != nil {
+				return
+			}
+			_, _ = conn.WriteTo(answer(append([]byte(nil), buf[:n]...)), addr)
+		}
+	}()
+	return conn.LocalAddr().String()
+}
+
+// answerRecord encodes an answer owned by owner, a name in wire form
+func answerRecord(owner []byte, rrType uint16, data []byte) []byte {
+	rr := append([]byte(nil), owner...)
+	rr = binary.BigEndian.AppendUint16(rr, rrType)
+	rr = binary.BigEndian.AppendUint16(rr, classINET)
+	rr = binary.BigEndian.AppendUint32(rr, 300)
+	rr = binary.BigEndian.AppendUint16(rr, uint16(len(data)))
+	return append(rr, data...)
+}
+
+func wireName(t *testing.T, name string) []byte {
+	t.Helper()
+	encoded, err := appendName(nil, name)
+	if err != nil {
+		t.Fatalf("appendName(%q): %v", name, err)
+	}
+	return encoded
+}
+
+func TestNetResolverRawLookups(t *testing.T) {
+	questionName := []byte{0xc0, 12} // Pointer to the name in the question
+	edge, origin, host := wireName(t, "edge.example.net"), wireName(t, "origin.example.org"), wireName(t, "host.example.com")
+	server := startDNSServer(t, func(query []byte) []byte {
+		resp := append([]byte(nil), query...)
+		resp[2] |= 0x80 // Response
+		resp[3] = 0x80  // Recursion available
+
+		name, _, _ := readName(query, 12)
+		if name != "www.example.com" {
+			resp[3] |= rcodeNameError
+			return resp
+		}
+
+		var answers [][]byte
+		switch binary.BigEndian.Uint16(query[len(query)-4:]) {
+		case typeCNAME:
+			// The first hop, then a later one that mustn't be reported
+			answers = append(answers,
+				answerRecord(questionName, typeCNAME, edge),
+				answerRecord(edge, typeCNAME, origin),
+			)
+		case typeCAA:
+			answers = append(answers, answerRecord(questionName, typeCAA, append([]byte{0, 5}, "issueletsencrypt.org"...)))
+		case typePTR:
+			answers = append(answers, answerRecord(questionName, typePTR, host))
+		}
+		binary.BigEndian.PutUint16(resp[6:], uint16(len(answers)))
+		for _, answer := range answers {
+			resp = append(resp, answer...)
+		}
+		return resp
+	})
+
+	tests := []struct {
+		recordType models.DnsRecordType
+		name       string
+		want       []string
+		wantErr    bool
+	}{
+		{recordType: models.DnsRecordTypeCNAME, name: "www.example.com", want: []string{"edge.example.net"}},
+		{recordType: models.DnsRecordTypeCAA, name: "www.example.com", want: []string{`0 issue "letsencrypt.org"`}},
+		{recordType: models.DnsRecordTypePTR, name: "www.example.com", want: []string{"host.example.com"}},
+		{recordType: models.DnsRecordTypeCNAME, name: "missing.example.com", wantErr: true},
+	}
+
+	for _, tt := range tests {
+		t.Run(string(tt.recordType)+" "+tt.name, func(t *testing.T) {
+			ctx, cancel := context.WithTimeout(context.Background(), 5*time.Second)
+			defer cancel()
+
+			got, err := NetResolver{}.Lookup(ctx, server, tt.recordType, tt.name)
+			if (err != nil) != tt.wantErr {
+				t.Fatalf("Lookup() error = %v, wantErr %v", err, tt.wantErr)
+			}
+			if !reflect.DeepEqual(got, tt.want) {
+				t.Errorf("Lookup() = %v, want %v", got, tt.want)
+			}
+		})
+	}
+}
@@ -25,13 +25,16 @@ type Provider interface {
 
 // CreateProvider is a factory function that creates the appropriate DNS provider
 // based on the configuration. Returns nil for manual DNS.
-func CreateProvider(provider *models.DnsProvider) (Provider, error) {
+// globalRateLimitRetries is the settings' rate limit retries, used when the domain sets none.
+func CreateProvider(provider *models.DnsProvider, globalRateLimitRetries int) (Provider, error) {
 	switch provider.Type {
 	case models.DnsProviderCloudflare:
 		if provider.APIToken == "" || provider.ZoneID == "" {
 			return nil, fmt.Errorf("Cloudflare provider requires APIToken and ZoneID")
 		}
-		return cloudflare.NewCloudflareProvider(provider.APIToken, provider.ZoneID), nil
+		cf := cloudflare.NewCloudflareProvider(provider.APIToken, provider.ZoneID)
+		cf.SetRateLimitRetries(provider.RateLimitRetryCount(globalRateLimitRetries))
+		return cf, nil
 
 	case models.DnsProviderRoute53:
 		// TODO: Implement Route53 provider in future
@@ -0,0 +1,172 @@
+package dns
+
+import (
+	"fmt"
+
+	"github.com/BlueBeard63/archon/internal/models"
+)
+
+// Push operations reported in PushOutcome
+const (
+	PushCreate = "create"
+	PushUpdate = "update"
+	PushDelete = "delete"
+)
+
+// PushOutcome is the result of pushing one record to the provider
+type PushOutcome struct {
+	Operation string // PushCreate, PushUpdate or PushDelete
+	Record    models.DnsRecord
+	Error     error
+}
+
+// PushRecords makes the provider match the local records and returns the updated local records.
+// Records without a provider ID (or whose ID no longer exists at the provider) are created,
+// records that differ from the provider's copy are updated, and when deleteRemote is set
+// provider records with no local counterpart are deleted. Local values win over the provider's.
+// A failed operation leaves that record's local copy unchanged and does not stop the others.
+func PushRecords(p Provider, domain string, local, remote []models.DnsRecord, deleteRemote bool) ([]models.DnsRecord, []PushOutcome) {
+	remoteByID := make(map[string]models.DnsRecord)
+	for _, rec := range remote {
+		if rec.ID != nil {
+			remoteByID[*rec.ID] = rec
+		}
+	}
+
+	records := make([]models.DnsRecord, len(local))
+	copy(records, local)
+
+	var outcomes []PushOutcome
+	kept := make(map[string]bool)
+
+	for i := range records {
+		rec := &records[i]
+
+		var existing models.DnsRecord
+		found := false
+		if rec.ID != nil && *rec.ID != "" {
+			existing, found = remoteByID[*rec.ID]
+		}
+
+		switch {
+		case !found:
+			// New locally, or deleted at the provider since the last sync
+			toCreate := *rec
+			toCreate.ID = nil
+			// Invalid records are reported here rather than sent for the provider to reject
+			if err := toCreate.Validate(); err != nil {
+				outcomes = append(outcomes, PushOutcome{Operation: PushCreate, Record: *rec, Error: err})
+				continue
+			}
+			created, err := p.CreateRecord(domain, &toCreate, nil)
+			outcomes = append(outcomes, PushOutcome{Operation: PushCreate, Record: *rec, Error: err})
+			if err != nil {
+				continue
+			}
+			if created != nil {
+				rec.ID = created.ID
+			}
+
+		case rec.IsModified() || recordsDiffer(rec, &existing):
+			kept[*rec.ID] = true
+			if err := rec.Validate(); err != nil {
+				outcomes = append(outcomes, PushOutcome{Operation: PushUpdate, Record: *rec, Error: err})
+				continue
+			}
+			_, err := p.UpdateRecord(domain, rec, nil)
+			outcomes = append(outcomes, PushOutcome{Operation: PushUpdate, Record: *rec, Error: err})
+			if err != nil {
+				continue
+			}
+
+		default:
+			// Already matches the provider
+			kept[*rec.ID] = true
+			continue
+		}
+
+		if rec.ID != nil {
+			kept[*rec.ID] = true
+		}
+		rec.SyncState = models.DnsSyncStateSynced
+		rec.RemoteValue = rec.Value
+		rec.ConflictValue = ""
+	}
+
+	if deleteRemote {
+		for _, rec := range remote {
+			if rec.ID == nil || kept[*rec.ID] {
+				continue
+			}
+			err := p.DeleteRecord(domain, *rec.ID)
+			outcomes = append(outcomes, PushOutcome{Operation: PushDelete, Record: rec, Error: err})
+		}
+	}
+
+	return records, outcomes
+}
+
+// Describe returns a short human-readable summary of the outcome
+func (o PushOutcome) Describe() string {
+	verb := map[string]string{PushCreate: "Created", PushUpdate: "Updated", PushDelete: "Deleted"}[o.Operation]
+	if o.Error != nil {
+		return fmt.Sprintf("Failed to %s %s %s: %v", o.Operation, o.Record.RecordType, o.Record.Name, o.Error)
+	}
+	return fmt.Sprintf("%s %s %s", verb, o.Record.RecordType, o.Record.Name)
+}
+
+// ApplyPush applies the result of a push to the current local records. sent is the local records
+// as they were passed to PushRecords and pushed the records it returned, so edits made while the
+// push ran are not lost: a record changed since it was sent keeps its edits and only picks up the
+// provider ID of a record created for it, and a record deleted since stays deleted.
+func ApplyPush(current, sent, pushed []models.DnsRecord) []models.DnsRecord {
+	records := make([]models.DnsRecord, len(current))
+	copy(records, current)
+	matched := make([]bool, len(records))
+
+	for i := range sent {
+		if i >= len(pushed) {
+			break
+		}
+		j := findSentRecord(records, matched, &sent[i])
+		if j < 0 {
+			continue
+		}
+		matched[j] = true
+
+		if sameRecord(&records[j], &sent[i]) {
+			records[j] = pushed[i]
+		} else if records[j].ID == nil {
+			records[j].ID = pushed[i].ID
+		}
+	}
+
+	return records
+}
+
+// findSentRecord returns the index of the current record that sent was pushed from, or -1.
+// Records with a provider ID are matched by it; new records by type and name.
+func findSentRecord(records []models.DnsRecord, matched []bool, sent *models.DnsRecord) int {
+	for j := range records {
+		if matched[j] {
+			continue
+		}
+		rec := &records[j]
+		if sent.ID != nil {
+			if rec.ID != nil && *rec.ID == *sent.ID {
+				return j
+			}
+			continue
+		}
+		if rec.ID == nil && rec.RecordType == sent.RecordType && rec.Name == sent.Name {
+			return j
+		}
+	}
+	return -1
+}
+
+// sameRecord returns true if the record has not been edited since it was sent
+func sameRecord(a, b *models.DnsRecord) bool {
+	return a.RecordType == b.RecordType && a.Name == b.Name && !recordsDiffer(a, b) &&
+		a.SyncState == b.SyncState
+}
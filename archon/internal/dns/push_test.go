@@ -0,0 +1,223 @@
+package dns
+
+import (
+	"errors"
+	"fmt"
+	"testing"
+
+	"github.com/BlueBeard63/archon/internal/models"
+)
+
+// fakeProvider records calls and fails for records whose value is "fail"
+type fakeProvider struct {
+	created []string
+	updated []string
+	deleted []string
+	nextID  int
+}
+
+func (f *fakeProvider) ListRecords(domain string) ([]models.DnsRecord, error) {
+	return nil, nil
+}
+
+func (f *fakeProvider) CreateRecord(domain string, record *models.DnsRecord, tags []string) (*models.DnsRecord, error) {
+	if record.Value == "fail" {
+		return nil, errors.New("provider error")
+	}
+	f.nextID++
+	created := *record
+	created.ID = strPtr(fmt.Sprintf("new-%d", f.nextID))
+	f.created = append(f.created, record.Name)
+	return &created, nil
+}
+
+func (f *fakeProvider) UpdateRecord(domain string, record *models.DnsRecord, tags []string) (*models.DnsRecord, error) {
+	if record.Value == "fail" {
+		return nil, errors.New("provider error")
+	}
+	f.updated = append(f.updated, *record.ID)
+	return record, nil
+}
+
+func (f *fakeProvider) DeleteRecord(domain string, recordID string) error {
+	f.deleted = append(f.deleted, recordID)
+	return nil
+}
+
+func TestPushRecords(t *testing.T) {
+	tests := []struct {
+		name         string
+		local        []models.DnsRecord
+		remote       []models.DnsRecord
+		deleteRemote bool
+		wantCreated  int
+		wantUpdated  int
+		wantDeleted  int
+		wantFailed   int
+	}{
+		{
+			name: "record without id is created",
+			local: []models.DnsRecord{
+				{RecordType: models.DnsRecordTypeA, Name: "www.example.com", Value: "1.1.1.1"},
+			},
+			wantCreated: 1,
+		},
+		{
+			name: "unchanged record is left alone",
+			local: []models.DnsRecord{
+				{ID: strPtr("1"), RecordType: models.DnsRecordTypeA, Name: "example.com", Value: "1.1.1.1", TTL: 300},
+			},
+			remote: []models.DnsRecord{
+				{ID: strPtr("1"), RecordType: models.DnsRecordTypeA, Name: "example.com", Value: "1.1.1.1", TTL: 300},
+			},
+		},
+		{
+			name: "locally edited record is updated",
+			local: []models.DnsRecord{
+				{ID: strPtr("1"), RecordType: models.DnsRecordTypeA, Name: "example.com", Value: "2.2.2.2", SyncState: models.DnsSyncStateModified},
+			},
+			remote: []models.DnsRecord{
+				{ID: strPtr("1"), RecordType: models.DnsRecordTypeA, Name: "example.com", Value: "1.1.1.1"},
+			},
+			wantUpdated: 1,
+		},
+		{
+			name: "record deleted at the provider is recreated",
+			local: []models.DnsRecord{
+				{ID: strPtr("gone"), RecordType: models.DnsRecordTypeA, Name: "example.com", Value: "1.1.1.1"},
+			},
+			wantCreated: 1,
+		},
+		{
+			name: "remote-only records are kept by default",
+			remote: []models.DnsRecord{
+				{ID: strPtr("9"), RecordType: models.DnsRecordTypeTXT, Name: "example.com", Value: "stale"},
+			},
+		},
+		{
+			name: "remote-only records are deleted when requested",
+			local: []models.DnsRecord{
+				{ID: strPtr("1"), RecordType: models.DnsRecordTypeA, Name: "example.com", Value: "1.1.1.1"},
+			},
+			remote: []models.DnsRecord{
+				{ID: strPtr("1"), RecordType: models.DnsRecordTypeA, Name: "example.com", Value: "1.1.1.1"},
+				{ID: strPtr("9"), RecordType: models.DnsRecordTypeTXT, Name: "example.com", Value: "stale"},
+			},
+			deleteRemote: true,
+			wantDeleted:  1,
+		},
+		{
+			name: "failure is reported and does not stop other records",
+			local: []models.DnsRecord{
+				{RecordType: models.DnsRecordTypeA, Name: "bad.example.com", Value: "fail"},
+				{RecordType: models.DnsRecordTypeA, Name: "good.example.com", Value: "1.1.1.1"},
+			},
+			wantCreated: 1,
+			wantFailed:  1,
+		},
+		{
+			name: "invalid record is reported without reaching the provider",
+			local: []models.DnsRecord{
+				{RecordType: models.DnsRecordTypeA, Name: "www.example.com", Value: "web.example.com"},
+				{ID: strPtr("1"), RecordType: models.DnsRecordTypeMX, Name: "example.com", Value: "mail.example.com", Proxied: true, SyncState: models.DnsSyncStateModified},
+			},
+			remote: []models.DnsRecord{
+				{ID: strPtr("1"), RecordType: models.DnsRecordTypeMX, Name: "example.com", Value: "mail.example.com"},
+			},
+			deleteRemote: true,
+			wantFailed:   2,
+		},
+	}
+
+	for _, tt := range tests {
+		t.Run(tt.name, func(t *testing.T) {
+			provider := &fakeProvider{}
+			records, outcomes := PushRecords(provider, "example.com", tt.local, tt.remote, tt.deleteRemote)
+
+			if len(provider.created) != tt.wantCreated {
+				t.Errorf("created %v, want %d", provider.created, tt.wantCreated)
+			}
+			if len(provider.updated) != tt.wantUpdated {
+				t.Errorf("updated %v, want %d", provider.updated, tt.wantUpdated)
+			}
+			if len(provider.deleted) != tt.wantDeleted {
+				t.Errorf("deleted %v, want %d", provider.deleted, tt.wantDeleted)
+			}
+
+			failed := 0
+			for _, outcome := range outcomes {
+				if outcome.Error != nil {
+					failed++
+				}
+			}
+			if failed != tt.wantFailed {
+				t.Errorf("%d failed outcomes, want %d", failed, tt.wantFailed)
+			}
+			if len(outcomes) != tt.wantCreated+tt.wantUpdated+tt.wantDeleted+tt.wantFailed {
+				t.Errorf("got %d outcomes, want one per operation", len(outcomes))
+			}
+
+			if len(records) != len(tt.local) {
+				t.Fatalf("got %d local records, want %d", len(records), len(tt.local))
+			}
+			for _, rec := range records {
+				if rec.Value == "fail" || (rec.ID == nil && rec.Validate() != nil) {
+					if rec.ID != nil {
+						t.Errorf("failed record %s was given an ID", rec.Name)
+					}
+					continue
+				}
+				if rec.ID == nil {
+					t.Errorf("record %s has no ID after push", rec.Name)
+				}
+			}
+		})
+	}
+}
+
+func TestPushRecordsDoesNotModifyInput(t *testing.T) {
+	local := []models.DnsRecord{
+		{RecordType: models.DnsRecordTypeA, Name: "www.example.com", Value: "1.1.1.1"},
+	}
+
+	records, _ := PushRecords(&fakeProvider{}, "example.com", local, nil, false)
+
+	if local[0].ID != nil {
+		t.Errorf("input record was modified: ID = %s", *local[0].ID)
+	}
+	if records[0].SyncState != models.DnsSyncStateSynced {
+		t.Errorf("pushed record state = %q, want %q", records[0].SyncState, models.DnsSyncStateSynced)
+	}
+}
+
+func TestApplyPushKeepsEditsMadeDuringPush(t *testing.T) {
+	sent := []models.DnsRecord{
+		{RecordType: models.DnsRecordTypeA, Name: "www.example.com", Value: "1.1.1.1"},
+		{ID: strPtr("1"), RecordType: models.DnsRecordTypeA, Name: "example.com", Value: "2.2.2.2", SyncState: models.DnsSyncStateModified},
+		{ID: strPtr("2"), RecordType: models.DnsRecordTypeTXT, Name: "example.com", Value: "v=spf1", SyncState: models.DnsSyncStateModified},
+	}
+	records, _ := PushRecords(&fakeProvider{}, "example.com", sent, nil, false)
+
+	// While the push ran: the new record was edited, record 1 is unchanged,
+	// record 2 was deleted and another record was added
+	current := []models.DnsRecord{
+		{RecordType: models.DnsRecordTypeA, Name: "www.example.com", Value: "3.3.3.3"},
+		sent[1],
+		{RecordType: models.DnsRecordTypeCNAME, Name: "blog.example.com", Value: "example.com"},
+	}
+
+	got := ApplyPush(current, sent, records)
+
+	if len(got) != 3 {
+		t.Fatalf("got %d records, want 3", len(got))
+	}
+	if got[0].Value != "3.3.3.3" || got[0].ID == nil || got[0].SyncState == models.DnsSyncStateSynced {
+		t.Errorf("edited record = %+v, want edit kept with provider ID and not synced", got[0])
+	}
+	if got[1].SyncState != models.DnsSyncStateSynced || got[1].RemoteValue != "2.2.2.2" {
+		t.Errorf("unchanged record = %+v, want synced", got[1])
+	}
+	if got[2].Name != "blog.example.com" || got[2].ID != nil {
+		t.Errorf("record added during push = %+v, want left alone", got[2])
+	}
+}
@@ -0,0 +1,246 @@
+package dns
+
+import (
+	"context"
+	"encoding/binary"
+	"errors"
+	"fmt"
+	"io"
+	"math/rand"
+	"net"
+	"strings"
+
+	"github.com/BlueBeard63/archon/internal/models"
+)
+
+// DNS message constants for the record types the net package can't look up itself
+const (
+	typeCNAME uint16 = 5
+	typePTR   uint16 = 12
+	typeCAA   uint16 = 257
+	classINET uint16 = 1
+
+	rcodeNameError = 3   // NXDOMAIN
+	maxUDPMessage  = 512 // Queries carry no EDNS option, so larger answers are truncated
+	maxNameHops    = 64  // Compression pointers followed before a name is treated as a loop
+)
+
+var errMalformedMessage = errors.New("malformed DNS response")
+
+// rawLookup sends one recursive query for name and the record type to server and returns the
+// answers of that type. Unlike net.Resolver it doesn't follow CNAMEs, so a CNAME query returns
+// the name's own target rather than the end of the chain. Truncated answers are retried over TCP.
+func rawLookup(ctx context.Context, server, name string, qtype uint16) ([]string, error) {
+	id := uint16(rand.Uint32())
+	query, err := appendName(newHeader(id), name)
+	if err != nil {
+		return nil, err
+	}
+	query = binary.BigEndian.AppendUint16(query, qtype)
+	query = binary.BigEndian.AppendUint16(query, classINET)
+
+	msg, err := exchange(ctx, "udp", server, query)
+	if err == nil && len(msg) > 2 && msg[2]&0x02 != 0 {
+		msg, err = exchange(ctx, "tcp", server, query)
+	}
+	if err != nil {
+		return nil, &net.DNSError{Err: err.Error(), Name: name, Server: server}
+	}
+
+	values, err := parseAnswers(msg, id, name, qtype)
+	if err != nil {
+		var dnsErr *net.DNSError
+		if errors.As(err, &dnsErr) {
+			dnsErr.Name, dnsErr.Server = name, server
+			return nil, dnsErr
+		}
+		return nil, &net.DNSError{Err: err.Error(), Name: name, Server: server}
+	}
+	return values, nil
+}
+
+// newHeader returns a query header asking for recursion with one question
+func newHeader(id uint16) []byte {
+	header := make([]byte, 12, maxUDPMessage)
+	binary.BigEndian.PutUint16(header[0:], id)
+	header[2] = 0x01 // Recursion desired
+	binary.BigEndian.PutUint16(header[4:], 1)
+	return header
+}
+
+// appendName appends name in wire form, as length-prefixed labels ending with the root
+func appendName(msg []byte, name string) ([]byte, error) {
+	name = strings.TrimSuffix(name, ".")
+	if name != "" {
+		for _, label := range strings.Split(name, ".") {
+			if label == "" || len(label) > 63 {
+				return nil, fmt.Errorf("invalid DNS name %q", name)
+			}
+			msg = append(msg, byte(len(label)))
+			msg = append(msg, label...)
+		}
+	}
+	return append(msg, 0), nil
+}
+
+// exchange sends the query to server and reads the response, which TCP prefixes with its length
+func exchange(ctx context.Context, network, server string, query []byte) ([]byte, error) {
+	var d net.Dialer
+	conn, err := d.DialContext(ctx, network, server)
+	if err != nil {
+		return nil, err
+	}
+	defer conn.Close()
+	if deadline, ok := ctx.Deadline(); ok {
+		if err := conn.SetDeadline(deadline); err != nil {
+			return nil, err
+		}
+	}
+
+	if network == "tcp" {
+		framed := binary.BigEndian.AppendUint16(nil, uint16(len(query)))
+		if _, err := conn.Write(append(framed, query...)); err != nil {
+			return nil, err
+		}
+		var length [2]byte
+		if _, err := io.ReadFull(conn, length[:]); err != nil {
+			return nil, err
+		}
+		msg := make([]byte, binary.BigEndian.Uint16(length[:]))
+		if _, err := io.ReadFull(conn, msg); err != nil {
+			return nil, err
+		}
+		return msg, nil
+	}
+
+	if _, err := conn.Write(query); err != nil {
+		return nil, err
+	}
+	msg := make([]byte, maxUDPMessage)
+	n, err := conn.Read(msg)
+	if err != nil {
+		return nil, err
+	}
+	return msg[:n], nil
+}
+
+// parseAnswers returns the values of the response's answers of type qtype, in the form records
+// hold them. CNAME answers must also be owned by name, as a response may include later hops.
+func parseAnswers(msg []byte, id uint16, name string, qtype uint16) ([]string, error) {
+	if len(msg) < 12 || binary.BigEndian.Uint16(msg[0:]) != id || msg[2]&0x80 == 0 {
+		return nil, errMalformedMessage
+	}
+	switch rcode := msg[3] & 0x0f; rcode {
+	case 0:
+	case rcodeNameError:
+		return nil, &net.DNSError{Err: "no such host", IsNotFound: true}
+	default:
+		return nil, fmt.Errorf("server returned error code %d", rcode)
+	}
+
+	questions := int(binary.BigEndian.Uint16(msg[4:]))
+	answers := int(binary.BigEndian.Uint16(msg[6:]))
+	off := 12
+	for i := 0; i < questions; i++ {
+		_, next, err := readName(msg, off)
+		if err != nil {
+			return nil, err
+		}
+		off = next + 4 // Type and class
+	}
+
+	var values []string
+	for i := 0; i < answers; i++ {
+		owner, next, err := readName(msg, off)
+		if err != nil {
+			return nil, err
+		}
+		if next+10 > len(msg) {
+			return nil, errMalformedMessage
+		}
+		rrType := binary.BigEndian.Uint16(msg[next:])
+		start := next + 10
+		end := start + int(binary.BigEndian.Uint16(msg[next+8:]))
+		if end > len(msg) {
+			return nil, errMalformedMessage
+		}
+		off = end
+
+		if rrType != qtype || (qtype == typeCNAME && !strings.EqualFold(owner, strings.TrimSuffix(name, "."))) {
+			continue
+		}
+		value, err := decodeRData(msg, start, end, rrType)
+		if err != nil {
+			return nil, err
+		}
+		values = append(values, value)
+	}
+
+	if len(values) == 0 {
+		return nil, &net.DNSError{Err: "no such host", IsNotFound: true}
+	}
+	return values, nil
+}
+
+// decodeRData returns the value of a CNAME, PTR or CAA record from its data at msg[start:end]
+func decodeRData(msg []byte, start, end int, rrType uint16) (string, error) {
+	switch rrType {
+	case typeCNAME, typePTR:
+		target, _, err := readName(msg[:end], start)
+		return target, err
+
+	case typeCAA:
+		if end-start < 2 || start+2+int(msg[start+1]) > end {
+			return "", errMalformedMessage
+		}
+		tagEnd := start + 2 + int(msg[start+1])
+		caa := models.CAA{
+			Flags: msg[start],
+			Tag:   strings.ToLower(string(msg[start+2 : tagEnd])),
+			Value: string(msg[tagEnd:end]),
+		}
+		return caa.String(), nil
+	}
+	return "", fmt.Errorf("unsupported record type %d", rrType)
+}
+
+// readName reads the possibly compressed name at off, returning it without the trailing dot
+// and the offset just past it in the message
+func readName(msg []byte, off int) (string, int, error) {
+	var labels []string
+	end := -1
+	for hops := 0; ; {
+		if off >= len(msg) {
+			return "", 0, errMalformedMessage
+		}
+		length := int(msg[off])
+		switch {
+		case length == 0:
+			if end < 0 {
+				end = off + 1
+			}
+			return strings.Join(labels, "."), end, nil
+
+		case length&0xc0 == 0xc0:
+			// A pointer to a name (or its tail) earlier in the message
+			if off+1 >= len(msg) || hops >= maxNameHops {
+				return "", 0, errMalformedMessage
+			}
+			if end < 0 {
+				end = off + 2
+			}
+			off = int(binary.BigEndian.Uint16(msg[off:]) & 0x3fff)
+			hops++
+
+		case length&0xc0 != 0:
+			return "", 0, errMalformedMessage
+
+		default:
+			if off+1+length > len(msg) {
+				return "", 0, errMalformedMessage
+			}
+			labels = append(labels, string(msg[off+1:off+1+length]))
+			off += 1 + length
+		}
+	}
+}
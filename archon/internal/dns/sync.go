@@ -0,0 +1,114 @@
+package dns
+
+import (
+	"github.com/BlueBeard63/archon/internal/models"
+)
+
+// MergeResult holds the outcome of merging provider records into local records
+type MergeResult struct {
+	Records   []models.DnsRecord // Merged records (local order preserved, new remote records appended)
+	Added     int                // Records that only existed at the provider
+	Updated   int                // Local records whose values were refreshed from the provider
+	LocalOnly []models.DnsRecord // Records that exist locally but not at the provider
+	Conflicts []models.DnsRecord // Records edited locally that also changed at the provider
+}
+
+// recordKey identifies a record by name and type for matching records without IDs
+func recordKey(r *models.DnsRecord) string {
+	return string(r.RecordType) + "|" + r.Name
+}
+
+// MergeRecords merges the provider's records into the local list instead of replacing it.
+// Records are matched by provider ID first, then by name+type. Local edits are kept;
+// if the provider value also changed since the last sync the record is marked as a conflict.
+func MergeRecords(local, remote []models.DnsRecord) MergeResult {
+	var result MergeResult
+
+	// Index remote records by ID and by name+type
+	byID := make(map[string]int)
+	byKey := make(map[string]int)
+	for i := range remote {
+		if remote[i].ID != nil {
+			byID[*remote[i].ID] = i
+		}
+		if _, exists := byKey[recordKey(&remote[i])]; !exists {
+			byKey[recordKey(&remote[i])] = i
+		}
+	}
+	matched := make([]bool, len(remote))
+
+	for _, rec := range local {
+		// Find the matching remote record
+		idx := -1
+		if rec.ID != nil {
+			if i, ok := byID[*rec.ID]; ok && !matched[i] {
+				idx = i
+			}
+		}
+		if idx == -1 {
+			if i, ok := byKey[recordKey(&rec)]; ok && !matched[i] {
+				idx = i
+			}
+		}
+
+		// Not at the provider - keep it but flag it
+		if idx == -1 {
+			if rec.SyncState != models.DnsSyncStateModified {
+				rec.SyncState = models.DnsSyncStateLocalOnly
+			}
+			result.LocalOnly = append(result.LocalOnly, rec)
+			result.Records = append(result.Records, rec)
+			continue
+		}
+
+		matched[idx] = true
+		remoteRec := remote[idx]
+
+		if rec.IsModified() {
+			// Local edits win unless the provider changed underneath them
+			rec.ID = remoteRec.ID
+			remoteChanged := rec.RemoteValue != "" && remoteRec.Value != rec.RemoteValue
+			switch {
+			case rec.Value == remoteRec.Value:
+				// Provider already has the edited value
+				rec.TTL = remoteRec.TTL
+				rec.Proxied = remoteRec.Proxied
+				rec.SyncState = models.DnsSyncStateSynced
+				rec.RemoteValue = remoteRec.Value
+				rec.ConflictValue = ""
+			case remoteChanged || rec.SyncState == models.DnsSyncStateConflict:
+				// RemoteValue keeps the value of the last sync, so later syncs still see the
+				// provider's change and the conflict holds until the record is pushed
+				rec.SyncState = models.DnsSyncStateConflict
+				rec.ConflictValue = remoteRec.Value
+				result.Conflicts = append(result.Conflicts, rec)
+			default:
+				rec.SyncState = models.DnsSyncStateModified
+				rec.RemoteValue = remoteRec.Value
+			}
+			result.Records = append(result.Records, rec)
+			continue
+		}
+
+		// Unmodified local record - take the provider's values
+		if rec.Value != remoteRec.Value || rec.TTL != remoteRec.TTL || rec.Proxied != remoteRec.Proxied {
+			result.Updated++
+		}
+		remoteRec.SyncState = models.DnsSyncStateSynced
+		remoteRec.RemoteValue = remoteRec.Value
+		result.Records = append(result.Records, remoteRec)
+	}
+
+	// Append records that only exist at the provider
+	for i, rec := range remote {
+		if matched[i] {
+			continue
+		}
+		rec.SyncState = models.DnsSyncStateSynced
+		rec.RemoteValue = rec.Value
+		result.Records = append(result.Records, rec)
+		result.Added++
+	}
+
+	return result
+}
@@ -0,0 +1,172 @@
+package dns
+
+import (
+	"testing"
+
+	"github.com/BlueBeard63/archon/internal/models"
+)
+
+func strPtr(s string) *string {
+	return &s
+}
+
+func TestMergeRecords(t *testing.T) {
+	tests := []struct {
+		name          string
+		local         []models.DnsRecord
+		remote        []models.DnsRecord
+		wantRecords   int
+		wantAdded     int
+		wantUpdated   int
+		wantLocalOnly int
+		wantConflicts int
+		wantState     models.DnsSyncState // state of the first merged record
+		wantValue     string              // value of the first merged record
+	}{
+		{
+			name:  "empty local takes remote",
+			local: nil,
+			remote: []models.DnsRecord{
+				{ID: strPtr("1"), RecordType: models.DnsRecordTypeA, Name: "example.com", Value: "1.2.3.4"},
+			},
+			wantRecords: 1,
+			wantAdded:   1,
+			wantState:   models.DnsSyncStateSynced,
+			wantValue:   "1.2.3.4",
+		},
+		{
+			name: "match by id updates value",
+			local: []models.DnsRecord{
+				{ID: strPtr("1"), RecordType: models.DnsRecordTypeA, Name: "example.com", Value: "1.1.1.1"},
+			},
+			remote: []models.DnsRecord{
+				{ID: strPtr("1"), RecordType: models.DnsRecordTypeA, Name: "example.com", Value: "2.2.2.2"},
+			},
+			wantRecords: 1,
+			wantUpdated: 1,
+			wantState:   models.DnsSyncStateSynced,
+			wantValue:   "2.2.2.2",
+		},
+		{
+			name: "match by name and type when local has no id",
+			local: []models.DnsRecord{
+				{RecordType: models.DnsRecordTypeA, Name: "www.example.com", Value: "1.1.1.1"},
+			},
+			remote: []models.DnsRecord{
+				{ID: strPtr("7"), RecordType: models.DnsRecordTypeA, Name: "www.example.com", Value: "1.1.1.1"},
+			},
+			wantRecords: 1,
+			wantState:   models.DnsSyncStateSynced,
+			wantValue:   "1.1.1.1",
+		},
+		{
+			name: "local record missing remotely is kept and flagged",
+			local: []models.DnsRecord{
+				{ID: strPtr("1"), RecordType: models.DnsRecordTypeA, Name: "old.example.com", Value: "1.1.1.1"},
+			},
+			remote:        nil,
+			wantRecords:   1,
+			wantLocalOnly: 1,
+			wantState:     models.DnsSyncStateLocalOnly,
+			wantValue:     "1.1.1.1",
+		},
+		{
+			name: "local edit survives unchanged remote",
+			local: []models.DnsRecord{
+				{ID: strPtr("1"), RecordType: models.DnsRecordTypeA, Name: "example.com", Value: "9.9.9.9", RemoteValue: "1.1.1.1", SyncState: models.DnsSyncStateModified},
+			},
+			remote: []models.DnsRecord{
+				{ID: strPtr("1"), RecordType: models.DnsRecordTypeA, Name: "example.com", Value: "1.1.1.1"},
+			},
+			wantRecords: 1,
+			wantState:   models.DnsSyncStateModified,
+			wantValue:   "9.9.9.9",
+		},
+		{
+			name: "local edit and remote change conflict",
+			local: []models.DnsRecord{
+				{ID: strPtr("1"), RecordType: models.DnsRecordTypeA, Name: "example.com", Value: "9.9.9.9", RemoteValue: "1.1.1.1", SyncState: models.DnsSyncStateModified},
+			},
+			remote: []models.DnsRecord{
+				{ID: strPtr("1"), RecordType: models.DnsRecordTypeA, Name: "example.com", Value: "5.5.5.5"},
+			},
+			wantRecords:   1,
+			wantConflicts: 1,
+			wantState:     models.DnsSyncStateConflict,
+			wantValue:     "9.9.9.9",
+		},
+		{
+			name: "local order preserved with new remote records appended",
+			local: []models.DnsRecord{
+				{ID: strPtr("2"), RecordType: models.DnsRecordTypeCNAME, Name: "www.example.com", Value: "example.com"},
+			},
+			remote: []models.DnsRecord{
+				{ID: strPtr("1"), RecordType: models.DnsRecordTypeA, Name: "example.com", Value: "1.1.1.1"},
+				{ID: strPtr("2"), RecordType: models.DnsRecordTypeCNAME, Name: "www.example.com", Value: "example.com"},
+			},
+			wantRecords: 2,
+			wantAdded:   1,
+			wantState:   models.DnsSyncStateSynced,
+			wantValue:   "example.com",
+		},
+	}
+
+	for _, tt := range tests {
+		t.Run(tt.name, func(t *testing.T) {
+			result := MergeRecords(tt.local, tt.remote)
+
+			if len(result.Records) != tt.wantRecords {
+				t.Fatalf("got %d records, want %d", len(result.Records), tt.wantRecords)
+			}
+			if result.Added != tt.wantAdded {
+				t.Errorf("Added = %d, want %d", result.Added, tt.wantAdded)
+			}
+			if result.Updated != tt.wantUpdated {
+				t.Errorf("Updated = %d, want %d", result.Updated, tt.wantUpdated)
+			}
+			if len(result.LocalOnly) != tt.wantLocalOnly {
+				t.Errorf("LocalOnly = %d, want %d", len(result.LocalOnly), tt.wantLocalOnly)
+			}
+			if len(result.Conflicts) != tt.wantConflicts {
+				t.Errorf("Conflicts = %d, want %d", len(result.Conflicts), tt.wantConflicts)
+			}
+			if result.Records[0].SyncState != tt.wantState {
+				t.Errorf("first record state = %q, want %q", result.Records[0].SyncState, tt.wantState)
+			}
+			if result.Records[0].Value != tt.wantValue {
+				t.Errorf("first record value = %q, want %q", result.Records[0].Value, tt.wantValue)
+			}
+		})
+	}
+}
+
+func TestMergeRecordsKeepsConflict(t *testing.T) {
+	local := []models.DnsRecord{
+		{ID: strPtr("1"), RecordType: models.DnsRecordTypeA, Name: "example.com", Value: "9.9.9.9", RemoteValue: "1.1.1.1", SyncState: models.DnsSyncStateModified},
+	}
+	remote := []models.DnsRecord{
+		{ID: strPtr("1"), RecordType: models.DnsRecordTypeA, Name: "example.com", Value: "5.5.5.5"},
+	}
+
+	// Syncing again with the provider unchanged must not drop the conflict
+	first := MergeRecords(local, remote)
+	second := MergeRecords(first.Records, remote)
+
+	for name, result := range map[string]MergeResult{"first sync": first, "second sync": second} {
+		rec := result.Records[0]
+		if rec.SyncState != models.DnsSyncStateConflict || len(result.Conflicts) != 1 {
+			t.Errorf("%s: state = %q with %d conflicts, want a conflict", name, rec.SyncState, len(result.Conflicts))
+		}
+		if rec.Value != "9.9.9.9" || rec.RemoteValue != "1.1.1.1" || rec.ConflictValue != "5.5.5.5" {
+			t.Errorf("%s: value, remote value, conflict value = %q, %q, %q, want 9.9.9.9, 1.1.1.1, 5.5.5.5",
+				name, rec.Value, rec.RemoteValue, rec.ConflictValue)
+		}
+	}
+
+	// The provider taking the local value resolves it
+	remote[0].Value = "9.9.9.9"
+	third := MergeRecords(second.Records, remote)
+	if rec := third.Records[0]; rec.SyncState != models.DnsSyncStateSynced || rec.ConflictValue != "" {
+		t.Errorf("after the provider matched: state = %q, conflict value = %q, want synced with none", rec.SyncState, rec.ConflictValue)
+	}
+}
@@ -0,0 +1,32 @@
+package health
+
+import (
+	"context"
+	"fmt"
+	"net/http"
+)
+
+// client doesn't follow redirects, so the status compared is the one the health path returns
+var client = &http.Client{
+	CheckRedirect: func(*http.Request, []*http.Request) error {
+		return http.ErrUseLastResponse
+	},
+}
+
+// Check sends a GET to url and returns an error unless it answers with the expected status
+func Check(ctx context.Context, url string, expected int) error {
+	req, err := http.NewRequestWithContext(ctx, http.MethodGet, url, nil)
+	if err != nil {
+		return err
+	}
+	resp, err := client.Do(req)
+	if err != nil {
+		return err
+	}
+	defer resp.Body.Close()
+
+	if resp.StatusCode != expected {
+		return fmt.Errorf("%s returned %d, want %d", url, resp.StatusCode, expected)
+	}
+	return nil
+}
@@ -0,0 +1,64 @@
+package models
+
+import (
+	"fmt"
+	"regexp"
+	"strconv"
+	"strings"
+)
+
+// configPlaceholderPattern matches ${name} placeholders, and $${name} which escapes one
+var configPlaceholderPattern = regexp.MustCompile(`\$?\$\{([^}]*)\}`)
+
+// RenderConfigFiles returns the site's config files with placeholders in their content
+// replaced by site values: ${name} is the site name, ${domain} is domainName (the primary
+// hostname), ${port} is the primary mapping's container port and ${env.NAME} is the site's
+// NAME environment variable. $${...} is written as a literal ${...}. An unknown placeholder
+// or unset environment variable is an error rather than being sent to the node as is.
+func (s *Site) RenderConfigFiles(domainName string) ([]ConfigFile, error) {
+	if len(s.ConfigFiles) == 0 {
+		return s.ConfigFiles, nil
+	}
+
+	port := s.Port
+	if mappings := s.GetDomainMappings(); len(mappings) > 0 {
+		port = mappings[0].Port
+	}
+	values := map[string]string{
+		"name":   s.Name,
+		"domain": domainName,
+		"port":   strconv.Itoa(port),
+	}
+
+	files := make([]ConfigFile, len(s.ConfigFiles))
+	for i, file := range s.ConfigFiles {
+		var err error
+		file.Content = configPlaceholderPattern.ReplaceAllStringFunc(file.Content, func(match string) string {
+			if strings.HasPrefix(match, "$$") {
+				return match[1:]
+			}
+			key := match[2 : len(match)-1]
+			if value, ok := values[key]; ok {
+				return value
+			}
+			if name, ok := strings.CutPrefix(key, "env."); ok {
+				if value, ok := s.EnvironmentVars[name]; ok {
+					return value
+				}
+				if err == nil {
+					err = fmt.Errorf("config file %s: %s is not set in the site's environment variables", file.Name, match)
+				}
+				return match
+			}
+			if err == nil {
+				err = fmt.Errorf("config file %s: unknown placeholder %s (use ${name}, ${domain}, ${port} or ${env.NAME}, or $${...} for a literal)", file.Name, match)
+			}
+			return match
+		})
+		if err != nil {
+			return nil, err
+		}
+		files[i] = file
+	}
+	return files, nil
+}
@@ -0,0 +1,58 @@
+package models
+
+import (
+	"strings"
+	"testing"
+
+	"github.com/google/uuid"
+)
+
+func TestRenderConfigFiles(t *testing.T) {
+	tests := []struct {
+		name    string
+		content string
+		want    string
+		wantErr string
+	}{
+		{name: "no placeholders", content: "worker_processes 1;", want: "worker_processes 1;"},
+		{
+			name:    "site values",
+			content: "server_name ${domain};\nlisten ${port};\n# ${name}",
+			want:    "server_name blog.example.com;\nlisten 3000;\n# blog",
+		},
+		{name: "environment variable", content: "url=${env.DATABASE_URL}", want: "url=postgres://db/blog"},
+		{name: "escaped placeholder", content: "echo $${HOME} ${port}", want: "echo ${HOME} 3000"},
+		{name: "shell variables are left alone", content: "echo $HOME", want: "echo $HOME"},
+		{name: "unknown placeholder", content: "${hostname}", wantErr: "unknown placeholder ${hostname}"},
+		{name: "unset environment variable", content: "${env.MISSING}", wantErr: "${env.MISSING} is not set"},
+	}
+
+	for _, tt := range tests {
+		t.Run(tt.name, func(t *testing.T) {
+			site := &Site{
+				Name:            "blog",
+				Port:            80,
+				DomainMappings:  []DomainMapping{{DomainID: uuid.New(), Port: 3000}},
+				EnvironmentVars: map[string]string{"DATABASE_URL": "postgres://db/blog"},
+				ConfigFiles:     []ConfigFile{{Name: "app.conf", ContainerPath: "/etc/app.conf", Content: tt.content}},
+			}
+
+			files, err := site.RenderConfigFiles("blog.example.com")
+			if tt.wantErr != "" {
+				if err == nil || !strings.Contains(err.Error(), tt.wantErr) {
+					t.Fatalf("RenderConfigFiles() error = %v, want error containing %q", err, tt.wantErr)
+				}
+				return
+			}
+			if err != nil {
+				t.Fatalf("RenderConfigFiles() error = %v", err)
+			}
+			if files[0].Content != tt.want {
+				t.Errorf("content = %q, want %q", files[0].Content, tt.want)
+			}
+			if site.ConfigFiles[0].Content != tt.content {
+				t.Errorf("site's config file was modified: %q", site.ConfigFiles[0].Content)
+			}
+		})
+	}
+}
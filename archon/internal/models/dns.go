@@ -1,5 +1,13 @@
 package models
 
+import (
+	"fmt"
+	"net"
+	"regexp"
+	"strconv"
+	"strings"
+)
+
 type DnsRecordType string
 
 const (
@@ -9,15 +17,200 @@ const (
 	DnsRecordTypeMX    DnsRecordType = "MX"
 	DnsRecordTypeTXT   DnsRecordType = "TXT"
 	DnsRecordTypeSRV   DnsRecordType = "SRV"
+	DnsRecordTypeCAA   DnsRecordType = "CAA" // Which certificate authorities may issue for the domain
+	DnsRecordTypeNS    DnsRecordType = "NS"
+	DnsRecordTypePTR   DnsRecordType = "PTR"
+)
+
+// DnsRecordTypes lists the supported record types
+var DnsRecordTypes = []DnsRecordType{
+	DnsRecordTypeA, DnsRecordTypeAAAA, DnsRecordTypeCNAME, DnsRecordTypeMX, DnsRecordTypeTXT,
+	DnsRecordTypeSRV, DnsRecordTypeCAA, DnsRecordTypeNS, DnsRecordTypePTR,
+}
+
+// ParseDnsRecordType returns the supported record type named by s, in any case
+func ParseDnsRecordType(s string) (DnsRecordType, error) {
+	for _, recordType := range DnsRecordTypes {
+		if strings.EqualFold(s, string(recordType)) {
+			return recordType, nil
+		}
+	}
+	return "", fmt.Errorf("unsupported record type %q", s)
+}
+
+// CAA property tags a certificate authority acts on
+const (
+	CAATagIssue     = "issue"     // CA allowed to issue certificates
+	CAATagIssueWild = "issuewild" // CA allowed to issue wildcard certificates
+	CAATagIodef     = "iodef"     // Where CAs report policy violations (mailto: or https: URL)
+)
+
+// CAA is the structured value of a CAA record. A record's Value holds it in zone file
+// form: flags, tag and quoted value, e.g. `0 issue "letsencrypt.org"`.
+type CAA struct {
+	Flags uint8 // 128 marks the tag as critical
+	Tag   string
+	Value string
+}
+
+// ParseCAA reads a CAA record value. The value may be left unquoted.
+func ParseCAA(value string) (CAA, error) {
+	fields := strings.SplitN(strings.TrimSpace(value), " ", 3)
+	if len(fields) != 3 {
+		return CAA{}, fmt.Errorf("CAA record value must be flags, tag and value, e.g. 0 issue \"letsencrypt.org\", got %q", value)
+	}
+	flags, err := strconv.ParseUint(fields[0], 10, 8)
+	if err != nil {
+		return CAA{}, fmt.Errorf("CAA record flags must be 0-255, got %q", fields[0])
+	}
+	caa := CAA{Flags: uint8(flags), Tag: strings.ToLower(fields[1]), Value: strings.TrimSpace(fields[2])}
+	switch caa.Tag {
+	case CAATagIssue, CAATagIssueWild, CAATagIodef:
+	default:
+		return CAA{}, fmt.Errorf("CAA record tag must be issue, issuewild or iodef, got %q", fields[1])
+	}
+	if unquoted, err := strconv.Unquote(caa.Value); err == nil {
+		caa.Value = unquoted
+	}
+	return caa, nil
+}
+
+// String returns the CAA value in zone file form
+func (c CAA) String() string {
+	return fmt.Sprintf("%d %s %s", c.Flags, c.Tag, strconv.Quote(c.Value))
+}
+
+// DnsSyncState tracks how a local record relates to the provider's copy
+type DnsSyncState string
+
+const (
+	DnsSyncStateSynced    DnsSyncState = "synced"     // Matches the provider
+	DnsSyncStateModified  DnsSyncState = "modified"   // Edited locally, not yet pushed
+	DnsSyncStateLocalOnly DnsSyncState = "local_only" // Exists locally but not at the provider
+	DnsSyncStateConflict  DnsSyncState = "conflict"   // Edited locally and changed at the provider
 )
 
 type DnsRecord struct {
-	ID         *string       `json:"id,omitempty" toml:"id,omitempty"`
-	RecordType DnsRecordType `json:"record_type" toml:"record_type"`
-	Name       string        `json:"name" toml:"name"`
-	Value      string        `json:"value" toml:"value"`
-	TTL        int           `json:"ttl" toml:"ttl"`
-	Proxied    bool          `json:"proxied" toml:"proxied"` // Cloudflare-specific
+	ID            *string       `json:"id,omitempty" toml:"id,omitempty"`
+	RecordType    DnsRecordType `json:"record_type" toml:"record_type"`
+	Name          string        `json:"name" toml:"name"`
+	Value         string        `json:"value" toml:"value"`
+	TTL           int           `json:"ttl" toml:"ttl"`
+	Proxied       bool          `json:"proxied" toml:"proxied"`                                   // Cloudflare-specific
+	SyncState     DnsSyncState  `json:"sync_state,omitempty" toml:"sync_state,omitempty"`         // Empty is treated as synced
+	RemoteValue   string        `json:"remote_value,omitempty" toml:"remote_value,omitempty"`     // Provider value as of the last sync, kept while in conflict
+	ConflictValue string        `json:"conflict_value,omitempty" toml:"conflict_value,omitempty"` // Provider's changed value while in conflict
+}
+
+// IsModified returns true if the record has local edits that have not been pushed
+func (r *DnsRecord) IsModified() bool {
+	return r.SyncState == DnsSyncStateModified || r.SyncState == DnsSyncStateConflict
+}
+
+// RecordTypeForIP returns the address record type for an IP: A for IPv4, AAAA for IPv6
+func RecordTypeForIP(ip net.IP) DnsRecordType {
+	if ip.To4() != nil {
+		return DnsRecordTypeA
+	}
+	return DnsRecordTypeAAAA
+}
+
+// PointsTo returns true if an address record's value is the given IP
+// IPv6 values are compared parsed, since providers may not return the canonical text form
+func (r *DnsRecord) PointsTo(ip net.IP) bool {
+	value := net.ParseIP(r.Value)
+	return value != nil && value.Equal(ip)
+}
+
+// TTL limits for DNS records; 0 and 1 leave the TTL to the provider ("automatic")
+const (
+	MinDnsTTL = 60
+	MaxDnsTTL = 86400
+)
+
+// hostnamePattern matches a DNS hostname: dot-separated labels of letters, digits and hyphens,
+// optionally fully qualified with a trailing dot. Underscores are allowed for service names.
+var hostnamePattern = regexp.MustCompile(`^([A-Za-z0-9_]([A-Za-z0-9_-]{0,61}[A-Za-z0-9])?\.)*[A-Za-z0-9]([A-Za-z0-9-]{0,61}[A-Za-z0-9])?\.?$`)
+
+// IsHostname returns true if value is a valid DNS hostname
+func IsHostname(value string) bool {
+	return len(value) <= 253 && hostnamePattern.MatchString(value)
+}
+
+// Validate checks the record's value against its type, its TTL and its proxied flag,
+// so mistakes are caught before the provider rejects them
+func (r *DnsRecord) Validate() error {
+	if strings.TrimSpace(r.Name) == "" {
+		return fmt.Errorf("record name is required")
+	}
+	if r.Value == "" {
+		return fmt.Errorf("%s record value is required", r.RecordType)
+	}
+
+	switch r.RecordType {
+	case DnsRecordTypeA:
+		if ip := net.ParseIP(r.Value); ip == nil || ip.To4() == nil || strings.Contains(r.Value, ":") {
+			return fmt.Errorf("A record value must be an IPv4 address, got %q", r.Value)
+		}
+	case DnsRecordTypeAAAA:
+		if ip := net.ParseIP(r.Value); ip == nil || !strings.Contains(r.Value, ":") {
+			return fmt.Errorf("AAAA record value must be an IPv6 address, got %q", r.Value)
+		}
+	case DnsRecordTypeCNAME:
+		if !IsHostname(r.Value) || net.ParseIP(r.Value) != nil {
+			return fmt.Errorf("CNAME record value must be a hostname, got %q", r.Value)
+		}
+	case DnsRecordTypeMX:
+		// The value may start with the priority, as Route 53 stores it ("10 mail.example.com")
+		host := r.Value
+		if priority, rest, found := strings.Cut(r.Value, " "); found {
+			if _, err := strconv.ParseUint(priority, 10, 16); err != nil {
+				return fmt.Errorf("MX record priority must be 0-65535, got %q", priority)
+			}
+			host = strings.TrimSpace(rest)
+		}
+		if !IsHostname(host) || net.ParseIP(host) != nil {
+			return fmt.Errorf("MX record value must be a mail server hostname, got %q", host)
+		}
+	case DnsRecordTypeNS, DnsRecordTypePTR:
+		if !IsHostname(r.Value) || net.ParseIP(r.Value) != nil {
+			return fmt.Errorf("%s record value must be a hostname, got %q", r.RecordType, r.Value)
+		}
+	case DnsRecordTypeCAA:
+		if _, err := ParseCAA(r.Value); err != nil {
+			return err
+		}
+	case DnsRecordTypeTXT, DnsRecordTypeSRV:
+		// Free-form, or structured in ways the providers check themselves
+	default:
+		return fmt.Errorf("unsupported record type %q", r.RecordType)
+	}
+
+	if r.TTL > 1 && (r.TTL < MinDnsTTL || r.TTL > MaxDnsTTL) {
+		return fmt.Errorf("TTL must be between %d and %d seconds (or 1 for automatic), got %d", MinDnsTTL, MaxDnsTTL, r.TTL)
+	}
+	if r.TTL < 0 {
+		return fmt.Errorf("TTL can't be negative")
+	}
+
+	if r.Proxied {
+		switch r.RecordType {
+		case DnsRecordTypeA, DnsRecordTypeAAAA, DnsRecordTypeCNAME:
+		default:
+			return fmt.Errorf("only A, AAAA and CNAME records can be proxied, not %s", r.RecordType)
+		}
+	}
+	return nil
+}
+
+// Normalize rewrites the value in the form providers return it, so a pushed record
+// isn't reported as changed on the next sync. CAA values are quoted, with a lowercase tag.
+func (r *DnsRecord) Normalize() {
+	if r.RecordType == DnsRecordTypeCAA {
+		if caa, err := ParseCAA(r.Value); err == nil {
+			r.Value = caa.String()
+		}
+	}
 }
 
 // NewDnsRecord creates a new DNS record with default values
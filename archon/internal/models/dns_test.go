@@ -0,0 +1,87 @@
+package models
+
+import (
+	"strings"
+	"testing"
+)
+
+func TestDnsRecordValidate(t *testing.T) {
+	tests := []struct {
+		name    string
+		record  DnsRecord
+		wantErr string // Empty when the record is valid
+	}{
+		{name: "A with IPv4", record: DnsRecord{RecordType: DnsRecordTypeA, Name: "www", Value: "203.0.113.10", TTL: 300}},
+		{name: "A with hostname", record: DnsRecord{RecordType: DnsRecordTypeA, Name: "www", Value: "web.example.com"}, wantErr: "IPv4"},
+		{name: "A with IPv6", record: DnsRecord{RecordType: DnsRecordTypeA, Name: "www", Value: "2001:db8::1"}, wantErr: "IPv4"},
+		{name: "AAAA with IPv6", record: DnsRecord{RecordType: DnsRecordTypeAAAA, Name: "www", Value: "2001:db8::1"}},
+		{name: "AAAA with IPv4", record: DnsRecord{RecordType: DnsRecordTypeAAAA, Name: "www", Value: "203.0.113.10"}, wantErr: "IPv6"},
+		{name: "CNAME with hostname", record: DnsRecord{RecordType: DnsRecordTypeCNAME, Name: "www", Value: "example.com.", Proxied: true}},
+		{name: "CNAME with IP", record: DnsRecord{RecordType: DnsRecordTypeCNAME, Name: "www", Value: "203.0.113.10"}, wantErr: "hostname"},
+		{name: "CNAME with spaces", record: DnsRecord{RecordType: DnsRecordTypeCNAME, Name: "www", Value: "not a host"}, wantErr: "hostname"},
+		{name: "MX with hostname", record: DnsRecord{RecordType: DnsRecordTypeMX, Name: "@", Value: "mail.example.com"}},
+		{name: "MX with priority", record: DnsRecord{RecordType: DnsRecordTypeMX, Name: "@", Value: "10 mail.example.com"}},
+		{name: "MX with bad priority", record: DnsRecord{RecordType: DnsRecordTypeMX, Name: "@", Value: "high mail.example.com"}, wantErr: "priority"},
+		{name: "TXT is free-form", record: DnsRecord{RecordType: DnsRecordTypeTXT, Name: "@", Value: "v=spf1 include:_spf.example.com ~all"}},
+		{name: "missing name", record: DnsRecord{RecordType: DnsRecordTypeA, Value: "203.0.113.10"}, wantErr: "name"},
+		{name: "missing value", record: DnsRecord{RecordType: DnsRecordTypeTXT, Name: "@"}, wantErr: "value"},
+		{name: "automatic TTL", record: DnsRecord{RecordType: DnsRecordTypeA, Name: "www", Value: "203.0.113.10", TTL: 1}},
+		{name: "TTL too short", record: DnsRecord{RecordType: DnsRecordTypeA, Name: "www", Value: "203.0.113.10", TTL: 30}, wantErr: "TTL"},
+		{name: "TTL too long", record: DnsRecord{RecordType: DnsRecordTypeA, Name: "www", Value: "203.0.113.10", TTL: 604800}, wantErr: "TTL"},
+		{name: "proxied TXT", record: DnsRecord{RecordType: DnsRecordTypeTXT, Name: "@", Value: "hello", Proxied: true}, wantErr: "proxied"},
+		{name: "CAA", record: DnsRecord{RecordType: DnsRecordTypeCAA, Name: "@", Value: `0 issue "letsencrypt.org"`}},
+		{name: "CAA without tag", record: DnsRecord{RecordType: DnsRecordTypeCAA, Name: "@", Value: "letsencrypt.org"}, wantErr: "CAA"},
+		{name: "NS with hostname", record: DnsRecord{RecordType: DnsRecordTypeNS, Name: "sub", Value: "ns1.example.net"}},
+		{name: "PTR with IP", record: DnsRecord{RecordType: DnsRecordTypePTR, Name: "10.113.0.203.in-addr.arpa", Value: "203.0.113.10"}, wantErr: "hostname"},
+		{name: "proxied NS", record: DnsRecord{RecordType: DnsRecordTypeNS, Name: "sub", Value: "ns1.example.net", Proxied: true}, wantErr: "proxied"},
+		{name: "unknown type", record: DnsRecord{RecordType: "LOC", Name: "@", Value: "52 22 23 N"}, wantErr: "unsupported"},
+	}
+
+	for _, tt := range tests {
+		t.Run(tt.name, func(t *testing.T) {
+			err := tt.record.Validate()
+			if tt.wantErr == "" {
+				if err != nil {
+					t.Errorf("Validate() error = %v, want nil", err)
+				}
+				return
+			}
+			if err == nil || !strings.Contains(err.Error(), tt.wantErr) {
+				t.Errorf("Validate() error = %v, want one mentioning %q", err, tt.wantErr)
+			}
+		})
+	}
+}
+
+func TestParseCAA(t *testing.T) {
+	tests := []struct {
+		value   string
+		want    CAA
+		wantErr bool
+	}{
+		{value: `0 issue "letsencrypt.org"`, want: CAA{Flags: 0, Tag: "issue", Value: "letsencrypt.org"}},
+		{value: `128 ISSUEWILD ";"`, want: CAA{Flags: 128, Tag: "issuewild", Value: ";"}},
+		{value: `0 iodef mailto:security@example.com`, want: CAA{Flags: 0, Tag: "iodef", Value: "mailto:security@example.com"}},
+		{value: `0 issue`, wantErr: true},
+		{value: `256 issue "letsencrypt.org"`, wantErr: true},
+		{value: `0 policy "letsencrypt.org"`, wantErr: true},
+	}
+
+	for _, tt := range tests {
+		t.Run(tt.value, func(t *testing.T) {
+			got, err := ParseCAA(tt.value)
+			if (err != nil) != tt.wantErr {
+				t.Fatalf("ParseCAA() error = %v, wantErr %v", err, tt.wantErr)
+			}
+			if err == nil && got != tt.want {
+				t.Errorf("ParseCAA() = %+v, want %+v", got, tt.want)
+			}
+		})
+	}
+
+	record := DnsRecord{RecordType: DnsRecordTypeCAA, Name: "example.com", Value: "0 ISSUE letsencrypt.org"}
+	record.Normalize()
+	if record.Value != `0 issue "letsencrypt.org"` {
+		t.Errorf("Normalize() value = %s, want the quoted form", record.Value)
+	}
+}
@@ -1,6 +1,9 @@
 package models
 
 import (
+	"fmt"
+	"regexp"
+	"strings"
 	"time"
 
 	"github.com/google/uuid"
@@ -21,6 +24,29 @@ type DnsProvider struct {
 	AccessKey    string          `json:"access_key,omitempty" toml:"access_key,omitempty"`           // Route53
 	SecretKey    string          `json:"secret_key,omitempty" toml:"secret_key,omitempty"`           // Route53
 	HostedZoneID string          `json:"hosted_zone_id,omitempty" toml:"hosted_zone_id,omitempty"`   // Route53
+
+	RateLimitRetries int `json:"rate_limit_retries,omitempty" toml:"rate_limit_retries,omitempty"` // Retries after HTTP 429 (0 = global setting, -1 = none)
+}
+
+// DefaultRateLimitRetries is how many times a rate-limited DNS request is retried when neither
+// the domain nor the settings set it
+const DefaultRateLimitRetries = 3
+
+// RateLimitRetryCount returns how many times a rate-limited request is retried: the provider's
+// own setting, else global, else DefaultRateLimitRetries. In both settings 0 is unset and a
+// negative value disables retries.
+func (p DnsProvider) RateLimitRetryCount(global int) int {
+	retries := p.RateLimitRetries
+	if retries == 0 {
+		retries = global
+	}
+	switch {
+	case retries < 0:
+		return 0
+	case retries == 0:
+		return DefaultRateLimitRetries
+	}
+	return retries
 }
 
 type Domain struct {
@@ -30,6 +56,25 @@ type Domain struct {
 	DnsRecords     []DnsRecord `json:"dns_records" toml:"dns_records"`
 	TraefikEnabled bool        `json:"traefik_enabled" toml:"traefik_enabled"`
 	CreatedAt      time.Time   `json:"created_at" toml:"created_at"`
+
+	CertExpiresAt *time.Time `json:"cert_expires_at,omitempty" toml:"cert_expires_at,omitempty"` // Earliest TLS certificate expiry across the domain's hosts (nil = not checked yet)
+}
+
+// CertExpiryWarningDays is how close to expiry a domain's certificate must be before it is flagged
+const CertExpiryWarningDays = 14
+
+// CertDaysLeft returns the whole days until the domain's certificate expires (negative once it has)
+// and false if the certificate hasn't been checked
+func (d *Domain) CertDaysLeft(now time.Time) (int, bool) {
+	if d.CertExpiresAt == nil {
+		return 0, false
+	}
+	left := d.CertExpiresAt.Sub(now)
+	days := int(left / (24 * time.Hour))
+	if left < 0 && left%(24*time.Hour) != 0 {
+		days-- // Round towards the past, so an hour after expiry is day -1
+	}
+	return days, true
 }
 
 // IsManualDNS returns true if this domain uses manual DNS configuration
@@ -51,6 +96,41 @@ func (d *Domain) ProviderName() string {
 	}
 }
 
+// SameDomainName reports whether two domain names refer to the same domain,
+// ignoring case and a trailing root dot ("Example.com." matches "example.com")
+func SameDomainName(a, b string) bool {
+	return strings.EqualFold(strings.TrimSuffix(strings.TrimSpace(a), "."), strings.TrimSuffix(strings.TrimSpace(b), "."))
+}
+
+// domainLabelPattern matches one label of a domain name: letters, digits and inner hyphens
+var domainLabelPattern = regexp.MustCompile(`^[A-Za-z0-9]([A-Za-z0-9-]{0,61}[A-Za-z0-9])?$`)
+
+// ValidateDomainName checks that name is a fully qualified domain name such as example.com.
+// A trailing root dot is allowed; IP addresses and single labels such as localhost are not.
+func ValidateDomainName(name string) error {
+	name = strings.TrimSuffix(strings.TrimSpace(name), ".")
+	if name == "" {
+		return fmt.Errorf("domain name is required")
+	}
+	if len(name) > 253 {
+		return fmt.Errorf("domain name must be at most 253 characters")
+	}
+
+	labels := strings.Split(name, ".")
+	if len(labels) < 2 {
+		return fmt.Errorf("domain name must be fully qualified, e.g. example.com, got %q", name)
+	}
+	for _, label := range labels {
+		if !domainLabelPattern.MatchString(label) {
+			return fmt.Errorf("domain name %q has an invalid label %q: use letters, digits and hyphens, up to 63 characters", name, label)
+		}
+	}
+	if strings.Trim(labels[len(labels)-1], "0123456789") == "" {
+		return fmt.Errorf("domain name %q must end in a top-level domain, not a number", name)
+	}
+	return nil
+}
+
 // NewDomain creates a new Domain with default values
 func NewDomain(name string, provider DnsProvider) *Domain {
 	return &Domain{
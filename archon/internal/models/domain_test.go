@@ -0,0 +1,99 @@
+package models
+
+import (
+	"strings"
+	"testing"
+	"time"
+)
+
+func TestCertDaysLeft(t *testing.T) {
+	now := time.Date(2026, 3, 1, 12, 0, 0, 0, time.UTC)
+	at := func(d time.Duration) *time.Time {
+		expires := now.Add(d)
+		return &expires
+	}
+
+	tests := []struct {
+		name      string
+		expiresAt *time.Time
+		wantDays  int
+		wantOK    bool
+	}{
+		{name: "not checked", expiresAt: nil, wantDays: 0, wantOK: false},
+		{name: "partial days round down", expiresAt: at(14*24*time.Hour - time.Hour), wantDays: 13, wantOK: true},
+		{name: "expires later today", expiresAt: at(time.Hour), wantDays: 0, wantOK: true},
+		{name: "expired an hour ago", expiresAt: at(-time.Hour), wantDays: -1, wantOK: true},
+		{name: "expired exactly two days ago", expiresAt: at(-48 * time.Hour), wantDays: -2, wantOK: true},
+	}
+
+	for _, tt := range tests {
+		t.Run(tt.name, func(t *testing.T) {
+			d := &Domain{CertExpiresAt: tt.expiresAt}
+			days, ok := d.CertDaysLeft(now)
+			if days != tt.wantDays || ok != tt.wantOK {
+				t.Errorf("CertDaysLeft() = %d, %v; want %d, %v", days, ok, tt.wantDays, tt.wantOK)
+			}
+		})
+	}
+}
+
+func TestRateLimitRetryCount(t *testing.T) {
+	tests := []struct {
+		name   string
+		domain int
+		global int
+		want   int
+	}{
+		{name: "both unset", domain: 0, global: 0, want: DefaultRateLimitRetries},
+		{name: "global setting", domain: 0, global: 5, want: 5},
+		{name: "global disabled", domain: 0, global: -1, want: 0},
+		{name: "domain overrides global", domain: 1, global: 5, want: 1},
+		{name: "domain disabled", domain: -1, global: 5, want: 0},
+	}
+
+	for _, tt := range tests {
+		t.Run(tt.name, func(t *testing.T) {
+			p := DnsProvider{RateLimitRetries: tt.domain}
+			if got := p.RateLimitRetryCount(tt.global); got != tt.want {
+				t.Errorf("RateLimitRetryCount(%d) = %d, want %d", tt.global, got, tt.want)
+			}
+		})
+	}
+}
+
+func TestValidateDomainName(t *testing.T) {
+	tests := []struct {
+		name    string
+		wantErr string // Empty when the name is valid
+	}{
+		{name: "example.com"},
+		{name: "sub.example.co.uk"},
+		{name: "Example.COM."},
+		{name: "xn--bcher-kva.example"},
+		{name: "my-site.example.com"},
+		{name: "", wantErr: "required"},
+		{name: "localhost", wantErr: "fully qualified"},
+		{name: "203.0.113.10", wantErr: "top-level"},
+		{name: "-bad.example.com", wantErr: "invalid label"},
+		{name: "bad-.example.com", wantErr: "invalid label"},
+		{name: "under_score.example.com", wantErr: "invalid label"},
+		{name: "double..dot.com", wantErr: "invalid label"},
+		{name: "with space.com", wantErr: "invalid label"},
+		{name: strings.Repeat("a", 64) + ".com", wantErr: "invalid label"},
+	}
+
+	for _, tt := range tests {
+		t.Run(tt.name, func(t *testing.T) {
+			err := ValidateDomainName(tt.name)
+			if tt.wantErr == "" {
+				if err != nil {
+					t.Errorf("ValidateDomainName() error = %v, want nil", err)
+				}
+				return
+			}
+			if err == nil || !strings.Contains(err.Error(), tt.wantErr) {
+				t.Errorf("ValidateDomainName() error = %v, want one mentioning %q", err, tt.wantErr)
+			}
+		})
+	}
+}
@@ -0,0 +1,91 @@
+package models
+
+import (
+	"fmt"
+	"strings"
+)
+
+// EnvVar is a single environment variable read from a .env file
+type EnvVar struct {
+	Key   string
+	Value string
+}
+
+// ParseEnvFile parses the KEY=VALUE lines of a .env file, in file order. Blank lines and
+// # comments are ignored and an "export " prefix is allowed. Values may be double quoted
+// (backslash escapes such as \n are unescaped) or single quoted (taken literally), and
+// unquoted values end at a " #" comment. Lines that can't be parsed are skipped and
+// reported as "line N: reason".
+func ParseEnvFile(content string) ([]EnvVar, []string) {
+	var vars []EnvVar
+	var malformed []string
+
+	for i, line := range strings.Split(strings.ReplaceAll(content, "\r\n", "\n"), "\n") {
+		line = strings.TrimSpace(line)
+		if line == "" || strings.HasPrefix(line, "#") {
+			continue
+		}
+		line = strings.TrimPrefix(line, "export ")
+
+		key, rawValue, ok := strings.Cut(line, "=")
+		if !ok {
+			malformed = append(malformed, fmt.Sprintf("line %d: expected KEY=VALUE", i+1))
+			continue
+		}
+		key = strings.TrimSpace(key)
+		if err := ValidateEnvVarKey(key); err != nil {
+			malformed = append(malformed, fmt.Sprintf("line %d: %v", i+1, err))
+			continue
+		}
+
+		value, err := parseEnvValue(strings.TrimSpace(rawValue))
+		if err != nil {
+			malformed = append(malformed, fmt.Sprintf("line %d: %v", i+1, err))
+			continue
+		}
+		vars = append(vars, EnvVar{Key: key, Value: value})
+	}
+
+	return vars, malformed
+}
+
+// parseEnvValue unquotes a .env value and strips a trailing comment
+func parseEnvValue(raw string) (string, error) {
+	if raw == "" {
+		return "", nil
+	}
+
+	quote := raw[0]
+	if quote != '"' && quote != '\'' {
+		if idx := strings.Index(raw, " #"); idx >= 0 {
+			raw = raw[:idx]
+		}
+		return strings.TrimSpace(raw), nil
+	}
+
+	var value strings.Builder
+	for i := 1; i < len(raw); i++ {
+		c := raw[i]
+		switch {
+		case c == quote:
+			// Only a comment may follow the closing quote
+			if rest := strings.TrimSpace(raw[i+1:]); rest != "" && !strings.HasPrefix(rest, "#") {
+				return "", fmt.Errorf("unexpected %q after closing quote", rest)
+			}
+			return value.String(), nil
+		case c == '\\' && quote == '"' && i+1 < len(raw):
+			i++
+			switch raw[i] {
+			case 'n':
+				value.WriteByte('\n')
+			case 't':
+				value.WriteByte('\t')
+			default:
+				value.WriteByte(raw[i])
+			}
+		default:
+			value.WriteByte(c)
+		}
+	}
+	return "", fmt.Errorf("missing closing %c", quote)
+}
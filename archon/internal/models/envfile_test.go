@@ -0,0 +1,51 @@
+package models
+
+import (
+	"reflect"
+	"strings"
+	"testing"
+)
+
+func TestParseEnvFile(t *testing.T) {
+	content := `# Database settings
+DB_HOST=db.internal
+export DB_PORT = 5432
+
+DB_PASSWORD="p@ss # not a comment"
+GREETING="hello\nworld \"quoted\""
+RAW='literal \n value'
+EMPTY=
+TRAILING=value # comment
+QUOTED_COMMENT="x" # comment
+not a variable
+1 BAD=value
+UNTERMINATED="oops
+AFTER_QUOTE="a" b
+`
+
+	vars, malformed := ParseEnvFile(content)
+
+	wantVars := []EnvVar{
+		{Key: "DB_HOST", Value: "db.internal"},
+		{Key: "DB_PORT", Value: "5432"},
+		{Key: "DB_PASSWORD", Value: "p@ss # not a comment"},
+		{Key: "GREETING", Value: "hello\nworld \"quoted\""},
+		{Key: "RAW", Value: `literal \n value`},
+		{Key: "EMPTY", Value: ""},
+		{Key: "TRAILING", Value: "value"},
+		{Key: "QUOTED_COMMENT", Value: "x"},
+	}
+	if !reflect.DeepEqual(vars, wantVars) {
+		t.Errorf("ParseEnvFile() vars = %+v, want %+v", vars, wantVars)
+	}
+
+	wantLines := []string{"line 11:", "line 12:", "line 13:", "line 14:"}
+	if len(malformed) != len(wantLines) {
+		t.Fatalf("ParseEnvFile() malformed = %v, want %d entries", malformed, len(wantLines))
+	}
+	for i, prefix := range wantLines {
+		if !strings.HasPrefix(malformed[i], prefix) {
+			t.Errorf("malformed[%d] = %q, want prefix %q", i, malformed[i], prefix)
+		}
+	}
+}
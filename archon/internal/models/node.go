@@ -1,7 +1,11 @@
 package models
 
 import (
+	"fmt"
 	"net"
+	"net/url"
+	"strconv"
+	"strings"
 	"time"
 
 	"github.com/google/uuid"
@@ -30,11 +34,15 @@ type Node struct {
 	APIEndpoint     string       `json:"api_endpoint" toml:"api_endpoint"`
 	APIKey          string       `json:"api_key" toml:"api_key"`
 	IPAddress       net.IP       `json:"ip_address" toml:"ip_address"`
+	PublicIP        net.IP       `json:"public_ip,omitempty" toml:"public_ip,omitempty"` // Address DNS records should point at (optional, defaults to IPAddress)
 	ProxyType       ProxyType    `json:"proxy_type" toml:"proxy_type"`
 	Status          NodeStatus   `json:"status" toml:"status"`
 	DockerInfo      *DockerInfo  `json:"docker_info,omitempty" toml:"docker_info,omitempty"`
 	TraefikInfo     *TraefikInfo `json:"traefik_info,omitempty" toml:"traefik_info,omitempty"`
 	LastHealthCheck *time.Time   `json:"last_health_check,omitempty" toml:"last_health_check,omitempty"`
+	LastLatencyMs   *int64       `json:"last_latency_ms,omitempty" toml:"last_latency_ms,omitempty"` // Round-trip time of the last health check
+	TimeoutSeconds  int          `json:"timeout_seconds,omitempty" toml:"timeout_seconds,omitempty"` // Request timeout for this node (0 = client default)
+	Tags            []string     `json:"tags,omitempty" toml:"tags,omitempty"`                       // Free-form labels for grouping nodes (e.g., "eu-west", "staging")
 }
 
 type DockerInfo struct {
@@ -67,6 +75,124 @@ func NewNode(name, apiEndpoint, apiKey string, ipAddress net.IP, proxyType Proxy
 	}
 }
 
+// RequestTimeout returns the node's request timeout override, or 0 to use the client default
+func (n *Node) RequestTimeout() time.Duration {
+	if n.TimeoutSeconds <= 0 {
+		return 0
+	}
+	return time.Duration(n.TimeoutSeconds) * time.Second
+}
+
+// ApplyHealth records a health check response from the node. The node is online unless it
+// reports itself degraded or is missing Docker or proxy info, in which case it answers but
+// can't run or route sites, so it is degraded.
+func (n *Node) ApplyHealth(reported NodeStatus, docker *DockerInfo, traefik *TraefikInfo) {
+	n.Status = NodeStatusOnline
+	if reported == NodeStatusDegraded || docker == nil || traefik == nil {
+		n.Status = NodeStatusDegraded
+	}
+	n.DockerInfo = docker
+	n.TraefikInfo = traefik
+}
+
+// DNSTargetIP returns the address auto-DNS records should point at.
+// Prefers PublicIP (for nodes behind a load balancer or NAT) and falls back to IPAddress.
+// Returns nil if neither is set to a usable address.
+func (n *Node) DNSTargetIP() net.IP {
+	if len(n.PublicIP) > 0 && !n.PublicIP.IsUnspecified() {
+		return n.PublicIP
+	}
+	if len(n.IPAddress) > 0 && !n.IPAddress.IsUnspecified() {
+		return n.IPAddress
+	}
+	return nil
+}
+
+// NormalizeEndpoint validates a node API endpoint and returns it in canonical form.
+// The endpoint must be an http(s) URL with a host; surrounding whitespace and trailing
+// slashes are removed so paths can be appended without producing "//api/v1".
+func NormalizeEndpoint(endpoint string) (string, error) {
+	endpoint = strings.TrimSpace(endpoint)
+	if endpoint == "" {
+		return "", fmt.Errorf("endpoint is empty")
+	}
+
+	// url.Parse accepts "host:8080" as scheme "host", so check for the separator explicitly
+	if !strings.Contains(endpoint, "://") {
+		return "", fmt.Errorf("endpoint %q is missing a scheme (e.g., http://%s)", endpoint, endpoint)
+	}
+
+	u, err := url.Parse(endpoint)
+	if err != nil {
+		return "", fmt.Errorf("endpoint %q is not a valid URL: %w", endpoint, err)
+	}
+	if strings.Count(u.Host, ":") > 1 && !strings.HasPrefix(u.Host, "[") {
+		return "", fmt.Errorf("endpoint %q has an IPv6 address that must be bracketed (e.g., http://[::1]:8080)", endpoint)
+	}
+	if u.Scheme != "http" && u.Scheme != "https" {
+		return "", fmt.Errorf("endpoint %q must use http or https", endpoint)
+	}
+	if u.Hostname() == "" {
+		return "", fmt.Errorf("endpoint %q has no host", endpoint)
+	}
+
+	u.Path = strings.TrimRight(u.Path, "/")
+	u.RawPath = ""
+	return u.String(), nil
+}
+
+// EndpointForIP builds an http endpoint for an IP and port, bracketing IPv6 addresses
+func EndpointForIP(ip net.IP, port int) string {
+	return "http://" + net.JoinHostPort(ip.String(), strconv.Itoa(port))
+}
+
+// EndpointIP returns the IP address in an endpoint URL, or nil if the host is a name
+func EndpointIP(endpoint string) net.IP {
+	u, err := url.Parse(strings.TrimSpace(endpoint))
+	if err != nil || u.Host == "" {
+		// No scheme: treat the whole value as a host, with an optional port
+		host := strings.TrimSpace(endpoint)
+		if h, _, err := net.SplitHostPort(host); err == nil {
+			host = h
+		}
+		return net.ParseIP(strings.Trim(host, "[]"))
+	}
+	return net.ParseIP(u.Hostname())
+}
+
+// DNSRecordType returns the auto-DNS record type for the node's target IP (A or AAAA)
+func (n *Node) DNSRecordType() DnsRecordType {
+	return RecordTypeForIP(n.DNSTargetIP())
+}
+
+// HasTag returns true if the node is labelled with the given tag
+func (n *Node) HasTag(tag string) bool {
+	for _, t := range n.Tags {
+		if t == tag {
+			return true
+		}
+	}
+	return false
+}
+
+// ValidateEndpoint checks the node's API endpoint and replaces it with the normalized form
+func (n *Node) ValidateEndpoint() error {
+	normalized, err := NormalizeEndpoint(n.APIEndpoint)
+	if err != nil {
+		return err
+	}
+	n.APIEndpoint = normalized
+	return nil
+}
+
+// remoteHealthCheckHint returns an extra instruction line for checking the node from another machine
+func (n *Node) remoteHealthCheckHint() string {
+	if len(n.IPAddress) == 0 || n.IPAddress.IsUnspecified() {
+		return ""
+	}
+	return "\n#      # From your workstation: curl " + EndpointForIP(n.IPAddress, 8080) + "/health"
+}
+
 // generateSSLConfig generates SSL configuration based on proxy type
 func (n *Node) generateSSLConfig() string {
 	if n.ProxyType == ProxyTypeTraefik {
@@ -220,7 +346,7 @@ network = "archon-net"
 #      sudo systemctl status archon-node
 #
 # 7. Verify it's running:
-#      curl http://localhost:8080/health
+#      curl http://localhost:8080/health` + n.remoteHealthCheckHint() + `
 #
 # 8. Open firewall (if needed):
 #      sudo ufw allow 8080/tcp
@@ -0,0 +1,215 @@
+package models
+
+import (
+	"net"
+	"testing"
+)
+
+func TestNodeValidateEndpoint(t *testing.T) {
+	tests := []struct {
+		name     string
+		endpoint string
+		want     string
+		wantErr  bool
+	}{
+		{
+			name:     "trailing slash is stripped",
+			endpoint: "http://host:8080/",
+			want:     "http://host:8080",
+		},
+		{
+			name:     "repeated trailing slashes and whitespace are stripped",
+			endpoint: "  http://10.0.0.5:8080//  ",
+			want:     "http://10.0.0.5:8080",
+		},
+		{
+			name:     "missing scheme",
+			endpoint: "host:8080",
+			wantErr:  true,
+		},
+		{
+			name:     "valid http endpoint is unchanged",
+			endpoint: "http://192.168.1.10:8080",
+			want:     "http://192.168.1.10:8080",
+		},
+		{
+			name:     "valid https endpoint is unchanged",
+			endpoint: "https://node1.example.com",
+			want:     "https://node1.example.com",
+		},
+		{
+			name:     "path prefix keeps path but loses trailing slash",
+			endpoint: "https://example.com/archon/",
+			want:     "https://example.com/archon",
+		},
+		{
+			name:     "bracketed IPv6 endpoint keeps brackets",
+			endpoint: "http://[2001:db8::1]:8080/",
+			want:     "http://[2001:db8::1]:8080",
+		},
+		{
+			name:     "unbracketed IPv6 endpoint",
+			endpoint: "http://2001:db8::1:8080",
+			wantErr:  true,
+		},
+		{
+			name:     "unsupported scheme",
+			endpoint: "ftp://host:21",
+			wantErr:  true,
+		},
+		{
+			name:     "no host",
+			endpoint: "http://",
+			wantErr:  true,
+		},
+		{
+			name:     "empty",
+			endpoint: "",
+			wantErr:  true,
+		},
+	}
+
+	for _, tt := range tests {
+		t.Run(tt.name, func(t *testing.T) {
+			node := &Node{APIEndpoint: tt.endpoint}
+			err := node.ValidateEndpoint()
+
+			if tt.wantErr {
+				if err == nil {
+					t.Fatalf("expected error for %q, got endpoint %q", tt.endpoint, node.APIEndpoint)
+				}
+				if node.APIEndpoint != tt.endpoint {
+					t.Errorf("endpoint changed on error: got %q, want %q", node.APIEndpoint, tt.endpoint)
+				}
+				return
+			}
+
+			if err != nil {
+				t.Fatalf("unexpected error: %v", err)
+			}
+			if node.APIEndpoint != tt.want {
+				t.Errorf("APIEndpoint = %q, want %q", node.APIEndpoint, tt.want)
+			}
+		})
+	}
+}
+
+func TestEndpointForIP(t *testing.T) {
+	tests := []struct {
+		name string
+		ip   string
+		want string
+	}{
+		{name: "IPv4", ip: "192.168.1.10", want: "http://192.168.1.10:8080"},
+		{name: "IPv6 is bracketed", ip: "2001:db8::1", want: "http://[2001:db8::1]:8080"},
+		{name: "IPv6 loopback is bracketed", ip: "::1", want: "http://[::1]:8080"},
+	}
+
+	for _, tt := range tests {
+		t.Run(tt.name, func(t *testing.T) {
+			got := EndpointForIP(net.ParseIP(tt.ip), 8080)
+			if got != tt.want {
+				t.Errorf("EndpointForIP(%s) = %q, want %q", tt.ip, got, tt.want)
+			}
+
+			// The generated endpoint must round-trip through validation unchanged
+			node := &Node{APIEndpoint: got}
+			if err := node.ValidateEndpoint(); err != nil || node.APIEndpoint != got {
+				t.Errorf("ValidateEndpoint(%q) = %q, %v", got, node.APIEndpoint, err)
+			}
+		})
+	}
+}
+
+func TestEndpointIP(t *testing.T) {
+	tests := []struct {
+		name     string
+		endpoint string
+		want     string // empty means no IP
+	}{
+		{name: "IPv4 URL", endpoint: "http://10.0.0.5:8080", want: "10.0.0.5"},
+		{name: "IPv6 URL", endpoint: "http://[2001:db8::1]:8080", want: "2001:db8::1"},
+		{name: "hostname URL", endpoint: "https://node1.example.com", want: ""},
+		{name: "bare IPv4", endpoint: "10.0.0.5", want: "10.0.0.5"},
+		{name: "bare IPv6", endpoint: "2001:db8::1", want: "2001:db8::1"},
+	}
+
+	for _, tt := range tests {
+		t.Run(tt.name, func(t *testing.T) {
+			got := EndpointIP(tt.endpoint)
+			if tt.want == "" {
+				if got != nil {
+					t.Errorf("EndpointIP(%q) = %s, want nil", tt.endpoint, got)
+				}
+				return
+			}
+			if !got.Equal(net.ParseIP(tt.want)) {
+				t.Errorf("EndpointIP(%q) = %s, want %s", tt.endpoint, got, tt.want)
+			}
+		})
+	}
+}
+
+func TestNodeDNSRecordType(t *testing.T) {
+	tests := []struct {
+		name      string
+		ipAddress string
+		publicIP  string
+		want      DnsRecordType
+	}{
+		{name: "IPv4 node", ipAddress: "203.0.113.10", want: DnsRecordTypeA},
+		{name: "IPv6-only node", ipAddress: "2001:db8::10", want: DnsRecordTypeAAAA},
+		{name: "dual-stack node with IPv6 public address", ipAddress: "10.0.0.5", publicIP: "2001:db8::10", want: DnsRecordTypeAAAA},
+		{name: "IPv4-mapped IPv6 address is IPv4", ipAddress: "::ffff:203.0.113.10", want: DnsRecordTypeA},
+	}
+
+	for _, tt := range tests {
+		t.Run(tt.name, func(t *testing.T) {
+			node := &Node{IPAddress: net.ParseIP(tt.ipAddress)}
+			if tt.publicIP != "" {
+				node.PublicIP = net.ParseIP(tt.publicIP)
+			}
+
+			if got := node.DNSRecordType(); got != tt.want {
+				t.Errorf("DNSRecordType() = %s, want %s", got, tt.want)
+			}
+
+			// The auto-DNS record must match the target address
+			record := NewDnsRecord(node.DNSRecordType(), "example.com", node.DNSTargetIP().String(), 300)
+			if !record.PointsTo(node.DNSTargetIP()) {
+				t.Errorf("record value %q does not point to %s", record.Value, node.DNSTargetIP())
+			}
+		})
+	}
+}
+
+func TestNodeApplyHealth(t *testing.T) {
+	docker := &DockerInfo{Version: "27.0.1"}
+	traefik := &TraefikInfo{Version: "nginx/1.27"}
+
+	tests := []struct {
+		name     string
+		reported NodeStatus
+		docker   *DockerInfo
+		traefik  *TraefikInfo
+		want     NodeStatus
+	}{
+		{name: "healthy node", reported: "healthy", docker: docker, traefik: traefik, want: NodeStatusOnline},
+		{name: "missing Docker info", reported: "healthy", traefik: traefik, want: NodeStatusDegraded},
+		{name: "missing proxy info", reported: "healthy", docker: docker, want: NodeStatusDegraded},
+		{name: "node reports itself degraded", reported: NodeStatusDegraded, docker: docker, traefik: traefik, want: NodeStatusDegraded},
+	}
+
+	for _, tt := range tests {
+		t.Run(tt.name, func(t *testing.T) {
+			node := &Node{Status: NodeStatusOffline}
+			node.ApplyHealth(tt.reported, tt.docker, tt.traefik)
+			if node.Status != tt.want {
+				t.Errorf("Status = %s, want %s", node.Status, tt.want)
+			}
+			if node.DockerInfo != tt.docker || node.TraefikInfo != tt.traefik {
+				t.Errorf("Docker and Traefik info were not recorded")
+			}
+		})
+	}
+}
@@ -2,6 +2,7 @@ package models
 
 import (
 	"fmt"
+	"path"
 	"strconv"
 	"strings"
 	"time"
@@ -26,25 +27,73 @@ const (
 	SiteTypeCompose   SiteType = "compose"
 )
 
+// DeploymentStrategy is how a deploy replaces the container already running for a site
+type DeploymentStrategy string
+
+const (
+	StrategyRecreate  DeploymentStrategy = "recreate"   // Stop the old container, then start the new one
+	StrategyBlueGreen DeploymentStrategy = "blue_green" // Start the new container beside the old one and switch traffic once it is healthy
+)
+
 type Site struct {
-	ID              uuid.UUID         `json:"id" toml:"id"`
-	Name            string            `json:"name" toml:"name"`
-	SiteType        SiteType          `json:"site_type" toml:"site_type"`                     // container or compose (defaults to container)
-	DomainID        uuid.UUID         `json:"domain_id" toml:"domain_id"`                     // Legacy: single domain (kept for backward compatibility)
-	NodeID          uuid.UUID         `json:"node_id" toml:"node_id"`
-	DockerImage     string            `json:"docker_image" toml:"docker_image"`
-	DockerUsername  string            `json:"docker_username,omitempty" toml:"docker_username,omitempty"`
-	DockerToken     string            `json:"docker_token,omitempty" toml:"docker_token,omitempty"`
-	ComposeContent  string            `json:"compose_content,omitempty" toml:"compose_content,omitempty"` // Docker Compose YAML content (for compose sites)
-	EnvironmentVars map[string]string `json:"environment_vars" toml:"environment_vars"`
-	Port            int               `json:"port" toml:"port"`                                           // Legacy: single port (kept for backward compatibility)
-	DomainMappings  []DomainMapping   `json:"domain_mappings,omitempty" toml:"domain_mappings,omitempty"` // New: multiple domain-port mappings
-	SSLEnabled      bool              `json:"ssl_enabled" toml:"ssl_enabled"`
-	SSLEmail        string            `json:"ssl_email,omitempty" toml:"ssl_email,omitempty"` // Email for Let's Encrypt certificate registration
-	ConfigFiles     []ConfigFile      `json:"config_files" toml:"config_files"`
-	Status          SiteStatus        `json:"status" toml:"status"`
-	CreatedAt       time.Time         `json:"created_at" toml:"created_at"`
-	UpdatedAt       time.Time         `json:"updated_at" toml:"updated_at"`
+	ID              uuid.UUID           `json:"id" toml:"id"`
+	Name            string              `json:"name" toml:"name"`
+	SiteType        SiteType            `json:"site_type" toml:"site_type"`                     // container or compose (defaults to container)
+	DomainID        uuid.UUID           `json:"domain_id" toml:"domain_id"`                     // Legacy: single domain (kept for backward compatibility)
+	NodeID          uuid.UUID           `json:"node_id" toml:"node_id"`                         // Legacy: single node (kept for backward compatibility; the first of NodeIDs when set)
+	NodeIDs         []uuid.UUID         `json:"node_ids,omitempty" toml:"node_ids,omitempty"`   // New: nodes running a replica of the site, behind the same hostnames
+	DockerImage     string              `json:"docker_image" toml:"docker_image"`
+	Build           *BuildSpec          `json:"build,omitempty" toml:"build,omitempty"` // Build the image on the node instead of pulling DockerImage
+	DockerUsername  string              `json:"docker_username,omitempty" toml:"docker_username,omitempty"`
+	DockerToken     string              `json:"docker_token,omitempty" toml:"docker_token,omitempty"`
+	DockerRegistry  string              `json:"docker_registry,omitempty" toml:"docker_registry,omitempty"` // Registry to log in to (e.g., "ghcr.io"); inferred from the image when empty
+	ComposeContent  string              `json:"compose_content,omitempty" toml:"compose_content,omitempty"` // Docker Compose YAML content (for compose sites)
+	EnvironmentVars map[string]string   `json:"environment_vars" toml:"environment_vars"`
+	Port            int                 `json:"port" toml:"port"`                                           // Legacy: single port (kept for backward compatibility)
+	DomainMappings  []DomainMapping     `json:"domain_mappings,omitempty" toml:"domain_mappings,omitempty"` // New: multiple domain-port mappings
+	ExtraHosts      []string            `json:"extra_hosts,omitempty" toml:"extra_hosts,omitempty"`         // Additional hostnames served on the primary mapping's port (e.g., "www.example.com")
+	SSLEnabled      bool                `json:"ssl_enabled" toml:"ssl_enabled"`
+	SSLEmail        string              `json:"ssl_email,omitempty" toml:"ssl_email,omitempty"` // Email for Let's Encrypt certificate registration
+	CertResolver    string              `json:"cert_resolver,omitempty" toml:"cert_resolver,omitempty"` // Traefik certificate resolver name (defaults to "letsencrypt")
+	ConfigFiles     []ConfigFile        `json:"config_files" toml:"config_files"`
+	Volumes         []VolumeMount       `json:"volumes,omitempty" toml:"volumes,omitempty"` // Persistent host paths or named volumes mounted into the container
+	Tags            []string            `json:"tags,omitempty" toml:"tags,omitempty"` // Free-form labels for grouping sites (e.g., "customer-a", "internal")
+	Middlewares     []TraefikMiddleware `json:"middlewares,omitempty" toml:"middlewares,omitempty"` // Traefik middlewares applied to the site's router, in order
+	CPULimit        float64             `json:"cpu_limit,omitempty" toml:"cpu_limit,omitempty"`             // Max CPU cores the container may use (e.g., 0.5); 0 = unlimited
+	MemoryLimitMB   int                 `json:"memory_limit_mb,omitempty" toml:"memory_limit_mb,omitempty"` // Max container memory in MB; 0 = unlimited
+	HealthPath      string              `json:"health_path,omitempty" toml:"health_path,omitempty"`         // Path probed to check the site is healthy (e.g., "/healthz"); empty = no health check
+	HealthStatus    int                 `json:"health_status,omitempty" toml:"health_status,omitempty"`     // Status code the health path should return; 0 = 200
+	AutoRestart     bool                `json:"auto_restart,omitempty" toml:"auto_restart,omitempty"`       // Restart the site when it is seen failed, up to a few attempts
+	Strategy        DeploymentStrategy  `json:"strategy,omitempty" toml:"strategy,omitempty"`               // How deploys replace the running container; empty = recreate
+	Deployments     []DeploymentRecord  `json:"deployments,omitempty" toml:"deployments,omitempty"`         // Past deploys, oldest first (see RecordDeployment)
+	Status          SiteStatus          `json:"status" toml:"status"`
+	LastError       string              `json:"last_error,omitempty" toml:"last_error,omitempty"` // Error from the last failed deploy or operation (cleared when one succeeds)
+	Replicas        []ReplicaStatus     `json:"replicas,omitempty" toml:"replicas,omitempty"`     // Per-node outcome of the last deploy, for sites on more than one node
+	CreatedAt       time.Time           `json:"created_at" toml:"created_at"`
+	UpdatedAt       time.Time           `json:"updated_at" toml:"updated_at"`
+}
+
+// ReplicaStatus records how the last deploy of a site went on one of its nodes
+type ReplicaStatus struct {
+	NodeID    uuid.UUID  `json:"node_id" toml:"node_id"`
+	Status    SiteStatus `json:"status" toml:"status"`
+	LastError string     `json:"last_error,omitempty" toml:"last_error,omitempty"`
+}
+
+// BuildSpec describes how the node should build a site image from source
+type BuildSpec struct {
+	Context    string `json:"context" toml:"context"`                           // Build context path on the node, or a git repository URL
+	Dockerfile string `json:"dockerfile,omitempty" toml:"dockerfile,omitempty"` // Relative to the context (defaults to "Dockerfile")
+}
+
+// IsRemote returns true if the build context is a git repository URL
+func (b *BuildSpec) IsRemote() bool {
+	for _, prefix := range []string{"git://", "git@", "github.com/", "http://", "https://"} {
+		if strings.HasPrefix(b.Context, prefix) {
+			return true
+		}
+	}
+	return false
 }
 
 type ConfigFile struct {
@@ -53,6 +102,123 @@ type ConfigFile struct {
 	ContainerPath string `json:"container_path" toml:"container_path"`
 }
 
+// Validate checks that the file has a name and an absolute container path
+func (c *ConfigFile) Validate() error {
+	if c.Name == "" {
+		return fmt.Errorf("config file name is required")
+	}
+	if !path.IsAbs(c.ContainerPath) {
+		return fmt.Errorf("config file container path must be absolute: %q", c.ContainerPath)
+	}
+	return nil
+}
+
+// VolumeMount binds a host path or named Docker volume into the site's container
+type VolumeMount struct {
+	HostPath      string `json:"host_path" toml:"host_path"`                     // Absolute path on the node, or a Docker volume name
+	ContainerPath string `json:"container_path" toml:"container_path"`           // Absolute path inside the container
+	ReadOnly      bool   `json:"read_only,omitempty" toml:"read_only,omitempty"` // Mount read-only
+}
+
+// Validate checks that the mount has a source and an absolute container path
+func (v *VolumeMount) Validate() error {
+	if v.HostPath == "" {
+		return fmt.Errorf("volume host path is required")
+	}
+	if !path.IsAbs(v.ContainerPath) {
+		return fmt.Errorf("volume container path must be absolute: %q", v.ContainerPath)
+	}
+	return nil
+}
+
+// String formats the mount in Docker notation ("host:container[:ro]")
+func (v VolumeMount) String() string {
+	s := v.HostPath + ":" + v.ContainerPath
+	if v.ReadOnly {
+		s += ":ro"
+	}
+	return s
+}
+
+// DeploymentOutcome is how a deploy of a site ended
+type DeploymentOutcome string
+
+const (
+	DeploymentSucceeded DeploymentOutcome = "succeeded"
+	DeploymentFailed    DeploymentOutcome = "failed"
+)
+
+// MaxDeployments is how many past deploys are kept per site
+const MaxDeployments = 20
+
+// DeploymentRecord is one deploy of a site, for seeing what was running when
+type DeploymentRecord struct {
+	Timestamp   time.Time         `json:"timestamp" toml:"timestamp"`
+	Image       string            `json:"image" toml:"image"`                                   // Image deployed, or the build context for sites built on the node
+	ContainerID string            `json:"container_id,omitempty" toml:"container_id,omitempty"` // Container the node started (empty for failed deploys and older nodes)
+	Outcome     DeploymentOutcome `json:"outcome" toml:"outcome"`
+	Error       string            `json:"error,omitempty" toml:"error,omitempty"` // Why a failed deploy failed, or the nodes a partly successful one failed on
+}
+
+// RecordDeployment adds a deploy to the site's history, keeping the last MaxDeployments
+func (s *Site) RecordDeployment(record DeploymentRecord) {
+	s.Deployments = append(s.Deployments, record)
+	if len(s.Deployments) > MaxDeployments {
+		s.Deployments = append([]DeploymentRecord(nil), s.Deployments[len(s.Deployments)-MaxDeployments:]...)
+	}
+}
+
+// DeployedImage describes what a deploy of the site runs, for its deployment history
+func (s *Site) DeployedImage() string {
+	if s.IsCompose() {
+		return "compose"
+	}
+	return s.ImageSource()
+}
+
+// GetDeploymentStrategy returns the site's deployment strategy, defaulting to recreate
+func (s *Site) GetDeploymentStrategy() DeploymentStrategy {
+	if s.Strategy == "" {
+		return StrategyRecreate
+	}
+	return s.Strategy
+}
+
+// ValidateDeploymentStrategy checks that the site can be deployed with its strategy on a node
+// using proxyType. Blue/green deploys run two containers at once, so they need a single
+// container and Traefik, which routes to containers over the Docker network without host ports.
+func (s *Site) ValidateDeploymentStrategy(proxyType ProxyType) error {
+	if s.GetDeploymentStrategy() != StrategyBlueGreen {
+		return nil
+	}
+	if s.IsCompose() {
+		return fmt.Errorf("blue/green deploys aren't supported for compose sites")
+	}
+	if proxyType != ProxyTypeTraefik {
+		return fmt.Errorf("blue/green deploys need a node using Traefik, not %s", proxyType)
+	}
+	return nil
+}
+
+// RollbackTarget returns the most recent successful deploy of another image than the site is
+// configured with, for redeploying the last known-good image. Only sites deploying a prebuilt
+// image can be rolled back.
+func (s *Site) RollbackTarget() (DeploymentRecord, error) {
+	if s.IsCompose() || s.IsBuild() {
+		return DeploymentRecord{}, fmt.Errorf("only sites deploying a prebuilt image can be rolled back")
+	}
+	for i := len(s.Deployments) - 1; i >= 0; i-- {
+		record := s.Deployments[i]
+		// Deploys from before the site switched to a prebuilt image recorded a build or compose
+		if record.Outcome != DeploymentSucceeded || record.Image == s.DockerImage ||
+			record.Image == "compose" || strings.HasPrefix(record.Image, "build: ") {
+			continue
+		}
+		return record, nil
+	}
+	return DeploymentRecord{}, fmt.Errorf("no earlier successful deploy of another image")
+}
+
 // DomainMapping represents a domain-to-port mapping for multi-domain sites
 type DomainMapping struct {
 	DomainID  uuid.UUID `json:"domain_id" toml:"domain_id"`
@@ -61,21 +227,67 @@ type DomainMapping struct {
 	HostPort  int       `json:"host_port,omitempty" toml:"host_port,omitempty"` // Host port (optional, defaults to Port if not specified)
 }
 
+// DefaultCertResolver is the Traefik certificate resolver used when a site doesn't set one
+const DefaultCertResolver = "letsencrypt"
+
 // GenerateTraefikLabels generates Docker labels for Traefik reverse proxy configuration
-// TODO: Implement this method to generate appropriate Traefik labels for automatic routing
+// The router and service are named after the site ID; middlewares are applied in the order listed
 // Reference: https://doc.traefik.io/traefik/routing/providers/docker/
 func (s *Site) GenerateTraefikLabels(domainName string) map[string]string {
-	labels := make(map[string]string)
-
-	// TODO: Add Traefik labels here
-	// Example labels to implement:
-	// - traefik.enable=true
-	// - traefik.http.routers.<site-id>.rule=Host(`<domain>`)
-	// - traefik.http.routers.<site-id>.entrypoints=web,websecure
-	// - traefik.http.services.<site-id>.loadbalancer.server.port=<port>
-	// If SSL enabled:
-	//   - traefik.http.routers.<site-id>.tls=true
-	//   - traefik.http.routers.<site-id>.tls.certresolver=letsencrypt
+	router := s.ID.String()
+	labels := map[string]string{
+		"traefik.enable": "true",
+	}
+	hosts := s.Hostnames(domainName)
+	rules := make([]string, 0, len(hosts))
+	for _, host := range hosts {
+		rules = append(rules, fmt.Sprintf("Host(`%s`)", host))
+	}
+	labels[fmt.Sprintf("traefik.http.routers.%s.rule", router)] = strings.Join(rules, " || ")
+	labels[fmt.Sprintf("traefik.http.routers.%s.entrypoints", router)] = "web,websecure"
+	labels[fmt.Sprintf("traefik.http.routers.%s.service", router)] = router
+
+	// Route to the primary mapping's container port
+	port := s.Port
+	if mappings := s.GetDomainMappings(); len(mappings) > 0 {
+		port = mappings[0].Port
+	}
+	if port > 0 {
+		labels[fmt.Sprintf("traefik.http.services.%s.loadbalancer.server.port", router)] = strconv.Itoa(port)
+	}
+
+	if s.SSLEnabled {
+		labels[fmt.Sprintf("traefik.http.routers.%s.tls", router)] = "true"
+		labels[fmt.Sprintf("traefik.http.routers.%s.tls.certresolver", router)] = s.GetCertResolver()
+
+		// Request one certificate covering every hostname
+		labels[fmt.Sprintf("traefik.http.routers.%s.tls.domains[0].main", router)] = hosts[0]
+		if len(hosts) > 1 {
+			labels[fmt.Sprintf("traefik.http.routers.%s.tls.domains[0].sans", router)] = strings.Join(hosts[1:], ",")
+		}
+	}
+
+	// Traefik stops routing to the container while its health path fails
+	if s.HealthPath != "" {
+		labels[fmt.Sprintf("traefik.http.services.%s.loadbalancer.healthcheck.path", router)] = s.HealthPath
+		labels[fmt.Sprintf("traefik.http.services.%s.loadbalancer.healthcheck.interval", router)] = "10s"
+		if s.HealthStatus != 0 {
+			labels[fmt.Sprintf("traefik.http.services.%s.loadbalancer.healthcheck.status", router)] = strconv.Itoa(s.HealthStatus)
+		}
+	}
+
+	// Middlewares are defined per site so their options can't clash with other sites
+	var middlewares []string
+	for i := range s.Middlewares {
+		name := fmt.Sprintf("%s-%s-%d", router, s.Middlewares[i].Type.labelName(), i)
+		for key, value := range s.Middlewares[i].Labels(name) {
+			labels[key] = value
+		}
+		middlewares = append(middlewares, name)
+	}
+	if len(middlewares) > 0 {
+		labels[fmt.Sprintf("traefik.http.routers.%s.middlewares", router)] = strings.Join(middlewares, ",")
+	}
 
 	return labels
 }
@@ -95,6 +307,7 @@ func NewSite(name string, domainID, nodeID uuid.UUID, dockerImage string, port i
 		EnvironmentVars: make(map[string]string),
 		Port:            port, // Set legacy field for backward compatibility
 		SSLEnabled:      true, // Default to SSL enabled
+		CertResolver:    DefaultCertResolver,
 		ConfigFiles:     []ConfigFile{},
 		Status:          SiteStatusInactive,
 		CreatedAt:       now,
@@ -132,6 +345,79 @@ func (s *Site) GetDomainMappings() []DomainMapping {
 	return []DomainMapping{}
 }
 
+// GetNodeIDs returns the nodes the site runs on, the primary node first
+// Falls back to the legacy NodeID field if NodeIDs is empty
+func (s *Site) GetNodeIDs() []uuid.UUID {
+	if len(s.NodeIDs) > 0 {
+		return s.NodeIDs
+	}
+	if s.NodeID != uuid.Nil {
+		return []uuid.UUID{s.NodeID}
+	}
+	return []uuid.UUID{}
+}
+
+// SetNodeIDs sets the nodes the site runs on, keeping NodeID as the first of them.
+// A single node is stored in NodeID alone, so single-node configs don't change.
+func (s *Site) SetNodeIDs(nodeIDs []uuid.UUID) {
+	s.NodeIDs = nil
+	s.NodeID = uuid.Nil
+	if len(nodeIDs) > 0 {
+		s.NodeID = nodeIDs[0]
+	}
+	if len(nodeIDs) > 1 {
+		s.NodeIDs = nodeIDs
+	}
+
+	// Forget replicas on nodes the site no longer runs on; a single node has none
+	if !s.HasReplicas() {
+		s.Replicas = nil
+		return
+	}
+	var replicas []ReplicaStatus
+	for _, replica := range s.Replicas {
+		if s.OnNode(replica.NodeID) {
+			replicas = append(replicas, replica)
+		}
+	}
+	s.Replicas = replicas
+}
+
+// OnNode returns true if the site runs on the node, as its primary node or a replica
+func (s *Site) OnNode(nodeID uuid.UUID) bool {
+	for _, id := range s.GetNodeIDs() {
+		if id == nodeID {
+			return true
+		}
+	}
+	return false
+}
+
+// HasReplicas returns true if the site runs on more than one node
+func (s *Site) HasReplicas() bool {
+	return len(s.GetNodeIDs()) > 1
+}
+
+// GetReplicaStatus returns the outcome of the last deploy on a node, or nil if there is none
+func (s *Site) GetReplicaStatus(nodeID uuid.UUID) *ReplicaStatus {
+	for i := range s.Replicas {
+		if s.Replicas[i].NodeID == nodeID {
+			return &s.Replicas[i]
+		}
+	}
+	return nil
+}
+
+// SetReplicaStatus records the outcome of a deploy on one of the site's nodes
+func (s *Site) SetReplicaStatus(nodeID uuid.UUID, status SiteStatus, lastError string) {
+	if replica := s.GetReplicaStatus(nodeID); replica != nil {
+		replica.Status = status
+		replica.LastError = lastError
+		return
+	}
+	s.Replicas = append(s.Replicas, ReplicaStatus{NodeID: nodeID, Status: status, LastError: lastError})
+}
+
 // AddDomainMapping adds a new domain-port mapping to the site
 func (s *Site) AddDomainMapping(domainID uuid.UUID, port int) {
 	s.DomainMappings = append(s.DomainMappings, DomainMapping{
@@ -170,6 +456,301 @@ func (s *Site) GetSiteType() SiteType {
 	return s.SiteType
 }
 
+// Hostnames returns the primary hostname followed by the site's extra hostnames, without duplicates
+func (s *Site) Hostnames(primary string) []string {
+	hosts := []string{primary}
+	seen := map[string]bool{primary: true}
+	for _, host := range s.ExtraHosts {
+		if host == "" || seen[host] {
+			continue
+		}
+		seen[host] = true
+		hosts = append(hosts, host)
+	}
+	return hosts
+}
+
+// GetCertResolver returns the Traefik certificate resolver, defaulting for sites saved before it was configurable
+func (s *Site) GetCertResolver() string {
+	if s.CertResolver == "" {
+		return DefaultCertResolver
+	}
+	return s.CertResolver
+}
+
+// IsBuild returns true if the site's image is built on the node rather than pulled
+func (s *Site) IsBuild() bool {
+	return s.Build != nil && s.Build.Context != ""
+}
+
+// ValidateImageSource checks that a container site has exactly one of a prebuilt image or a build spec
+func (s *Site) ValidateImageSource() error {
+	if s.IsCompose() {
+		return nil
+	}
+
+	hasImage := s.DockerImage != ""
+	if hasImage && s.IsBuild() {
+		return fmt.Errorf("site cannot have both a docker image and a build context")
+	}
+	if !hasImage && !s.IsBuild() {
+		return fmt.Errorf("site needs either a docker image or a build context")
+	}
+	return nil
+}
+
+// ImageSource returns a short description of where the site's image comes from
+func (s *Site) ImageSource() string {
+	if s.IsBuild() {
+		dockerfile := s.Build.Dockerfile
+		if dockerfile == "" {
+			dockerfile = "Dockerfile"
+		}
+		return fmt.Sprintf("build: %s (%s)", s.Build.Context, dockerfile)
+	}
+	return s.DockerImage
+}
+
+// RegistryAddress returns the registry the site's image is pulled from
+// Uses DockerRegistry when set, otherwise the registry host in the image name (empty for Docker Hub)
+func (s *Site) RegistryAddress() string {
+	if registry := strings.TrimSpace(s.DockerRegistry); registry != "" {
+		return registry
+	}
+	host, _, found := strings.Cut(s.DockerImage, "/")
+	if !found {
+		return ""
+	}
+	// Like Docker, only treat the first component as a registry if it looks like a host
+	if strings.ContainsAny(host, ".:") || host == "localhost" {
+		return host
+	}
+	return ""
+}
+
+// HasTag returns true if the site is labelled with the given tag
+func (s *Site) HasTag(tag string) bool {
+	for _, t := range s.Tags {
+		if t == tag {
+			return true
+		}
+	}
+	return false
+}
+
+// ParseTags parses a comma-separated tag list, trimming whitespace and dropping empty or duplicate entries
+func ParseTags(input string) []string {
+	var tags []string
+	seen := make(map[string]bool)
+	for _, part := range strings.Split(input, ",") {
+		tag := strings.TrimSpace(part)
+		if tag == "" || seen[tag] {
+			continue
+		}
+		seen[tag] = true
+		tags = append(tags, tag)
+	}
+	return tags
+}
+
+// ParseHostnames parses a comma-separated hostname list, lowercasing entries and dropping empty or duplicate ones
+func ParseHostnames(input string) []string {
+	return ParseTags(strings.ToLower(input))
+}
+
+// secretEnvMarkers are substrings that mark an environment variable as secret
+var secretEnvMarkers = []string{"PASSWORD", "PASSWD", "SECRET", "TOKEN", "KEY", "CREDENTIAL"}
+
+// IsSecretEnvVar returns true if an environment variable name looks like it holds a secret
+func IsSecretEnvVar(name string) bool {
+	upper := strings.ToUpper(name)
+	for _, marker := range secretEnvMarkers {
+		if strings.Contains(upper, marker) {
+			return true
+		}
+	}
+	return false
+}
+
+// ValidateEnvVarKey checks that key can be passed to a container as an environment variable name
+func ValidateEnvVarKey(key string) error {
+	if key == "" {
+		return fmt.Errorf("environment variable name is required")
+	}
+	if strings.ContainsAny(key, "= \t\r\n") {
+		return fmt.Errorf("invalid environment variable name %q: must not contain '=' or whitespace", key)
+	}
+	return nil
+}
+
+// FormatTags formats tags as a comma-separated list for form input and display
+func FormatTags(tags []string) string {
+	return strings.Join(tags, ", ")
+}
+
+// ParseVolumeMounts parses a comma-separated list of mounts in Docker notation
+// Accepts "host:container", "host:container:ro" and "host:container:rw"
+func ParseVolumeMounts(input string) ([]VolumeMount, error) {
+	var mounts []VolumeMount
+	for _, part := range strings.Split(input, ",") {
+		part = strings.TrimSpace(part)
+		if part == "" {
+			continue
+		}
+
+		fields := strings.Split(part, ":")
+		if len(fields) < 2 || len(fields) > 3 {
+			return nil, fmt.Errorf("invalid volume %q: expected host:container[:ro]", part)
+		}
+
+		mount := VolumeMount{
+			HostPath:      strings.TrimSpace(fields[0]),
+			ContainerPath: strings.TrimSpace(fields[1]),
+		}
+		if len(fields) == 3 {
+			switch strings.TrimSpace(fields[2]) {
+			case "ro":
+				mount.ReadOnly = true
+			case "rw":
+			default:
+				return nil, fmt.Errorf("invalid volume mode %q: expected ro or rw", fields[2])
+			}
+		}
+		if err := mount.Validate(); err != nil {
+			return nil, err
+		}
+		mounts = append(mounts, mount)
+	}
+	return mounts, nil
+}
+
+// FormatVolumeMounts formats mounts as a comma-separated list for form input and display
+func FormatVolumeMounts(mounts []VolumeMount) string {
+	parts := make([]string, len(mounts))
+	for i, mount := range mounts {
+		parts[i] = mount.String()
+	}
+	return strings.Join(parts, ", ")
+}
+
+// ParseCPULimit parses a CPU limit in cores (e.g., "0.5" or "2"); a blank value means no limit
+func ParseCPULimit(input string) (float64, error) {
+	input = strings.TrimSpace(input)
+	if input == "" {
+		return 0, nil
+	}
+	cpus, err := strconv.ParseFloat(input, 64)
+	if err != nil || cpus < 0 {
+		return 0, fmt.Errorf("invalid CPU limit: %s", input)
+	}
+	return cpus, nil
+}
+
+// ParseMemoryLimit parses a memory limit in megabytes; a blank value means no limit
+func ParseMemoryLimit(input string) (int, error) {
+	input = strings.TrimSpace(input)
+	if input == "" {
+		return 0, nil
+	}
+	mb, err := strconv.Atoi(input)
+	if err != nil || mb < 0 {
+		return 0, fmt.Errorf("invalid memory limit: %s", input)
+	}
+	return mb, nil
+}
+
+// FormatCPULimit formats a CPU limit for form input, returning "" when unlimited
+func FormatCPULimit(cpus float64) string {
+	if cpus <= 0 {
+		return ""
+	}
+	return strconv.FormatFloat(cpus, 'f', -1, 64)
+}
+
+// FormatMemoryLimit formats a memory limit for form input, returning "" when unlimited
+func FormatMemoryLimit(mb int) string {
+	if mb <= 0 {
+		return ""
+	}
+	return strconv.Itoa(mb)
+}
+
+// DescribeResourceLimits returns a short summary of the container limits for display
+func (s *Site) DescribeResourceLimits() string {
+	cpu := "unlimited"
+	if s.CPULimit > 0 {
+		cpu = FormatCPULimit(s.CPULimit) + " cores"
+	}
+	memory := "unlimited"
+	if s.MemoryLimitMB > 0 {
+		memory = fmt.Sprintf("%d MB", s.MemoryLimitMB)
+	}
+	return fmt.Sprintf("CPU %s, memory %s", cpu, memory)
+}
+
+// DefaultHealthStatus is the status code a health path should return when a site doesn't set one
+const DefaultHealthStatus = 200
+
+// SiteHealth is the result of probing a site's health path, separate from its container status
+type SiteHealth string
+
+const (
+	SiteHealthUnknown   SiteHealth = "unknown" // Not probed yet, or the site has no health path
+	SiteHealthHealthy   SiteHealth = "healthy"
+	SiteHealthUnhealthy SiteHealth = "unhealthy"
+)
+
+// ParseHealthPath parses a health check path; a blank value means no health check
+func ParseHealthPath(input string) (string, error) {
+	input = strings.TrimSpace(input)
+	if input != "" && !strings.HasPrefix(input, "/") {
+		return "", fmt.Errorf("health path must start with /: %s", input)
+	}
+	return input, nil
+}
+
+// ParseHealthStatus parses the expected health check status code; a blank value means the default
+func ParseHealthStatus(input string) (int, error) {
+	input = strings.TrimSpace(input)
+	if input == "" {
+		return 0, nil
+	}
+	status, err := strconv.Atoi(input)
+	if err != nil || status < 100 || status > 599 {
+		return 0, fmt.Errorf("invalid health status code: %s", input)
+	}
+	return status, nil
+}
+
+// FormatHealthStatus formats an expected health status for form input, returning "" for the default
+func FormatHealthStatus(status int) string {
+	if status == 0 {
+		return ""
+	}
+	return strconv.Itoa(status)
+}
+
+// ExpectedHealthStatus returns the status code the site's health path should return
+func (s *Site) ExpectedHealthStatus() int {
+	if s.HealthStatus == 0 {
+		return DefaultHealthStatus
+	}
+	return s.HealthStatus
+}
+
+// HealthURL returns the URL probed for the site's health on its primary hostname, or "" if the
+// site has no health path
+func (s *Site) HealthURL(primary string) string {
+	if s.HealthPath == "" || primary == "" {
+		return ""
+	}
+	scheme := "http"
+	if s.SSLEnabled {
+		scheme = "https"
+	}
+	return scheme + "://" + primary + s.HealthPath
+}
+
 // ParsePortMapping parses port notation from a string
 // Accepts formats:
 //   - "3000" - single port (container and host use same port)
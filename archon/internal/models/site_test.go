@@ -0,0 +1,368 @@
+package models
+
+import (
+	"strconv"
+	"testing"
+
+	"github.com/google/uuid"
+)
+
+func TestParseResourceLimits(t *testing.T) {
+	cpuTests := []struct {
+		input   string
+		want    float64
+		wantErr bool
+	}{
+		{input: "", want: 0},
+		{input: " 0.5 ", want: 0.5},
+		{input: "2", want: 2},
+		{input: "-1", wantErr: true},
+		{input: "two", wantErr: true},
+	}
+	for _, tt := range cpuTests {
+		t.Run("cpu "+tt.input, func(t *testing.T) {
+			got, err := ParseCPULimit(tt.input)
+			if (err != nil) != tt.wantErr {
+				t.Fatalf("ParseCPULimit(%q) error = %v, wantErr %v", tt.input, err, tt.wantErr)
+			}
+			if got != tt.want {
+				t.Errorf("ParseCPULimit(%q) = %v, want %v", tt.input, got, tt.want)
+			}
+		})
+	}
+
+	memoryTests := []struct {
+		input   string
+		want    int
+		wantErr bool
+	}{
+		{input: "", want: 0},
+		{input: "512", want: 512},
+		{input: "-256", wantErr: true},
+		{input: "1.5", wantErr: true},
+	}
+	for _, tt := range memoryTests {
+		t.Run("memory "+tt.input, func(t *testing.T) {
+			got, err := ParseMemoryLimit(tt.input)
+			if (err != nil) != tt.wantErr {
+				t.Fatalf("ParseMemoryLimit(%q) error = %v, wantErr %v", tt.input, err, tt.wantErr)
+			}
+			if got != tt.want {
+				t.Errorf("ParseMemoryLimit(%q) = %v, want %v", tt.input, got, tt.want)
+			}
+		})
+	}
+}
+
+func TestDescribeResourceLimits(t *testing.T) {
+	tests := []struct {
+		name   string
+		cpu    float64
+		memory int
+		want   string
+	}{
+		{name: "unlimited", want: "CPU unlimited, memory unlimited"},
+		{name: "both limits", cpu: 0.5, memory: 512, want: "CPU 0.5 cores, memory 512 MB"},
+	}
+	for _, tt := range tests {
+		t.Run(tt.name, func(t *testing.T) {
+			site := &Site{CPULimit: tt.cpu, MemoryLimitMB: tt.memory}
+			if got := site.DescribeResourceLimits(); got != tt.want {
+				t.Errorf("DescribeResourceLimits() = %q, want %q", got, tt.want)
+			}
+		})
+	}
+}
+
+func TestParseVolumeMounts(t *testing.T) {
+	tests := []struct {
+		name    string
+		input   string
+		want    []VolumeMount
+		wantErr bool
+	}{
+		{name: "empty", input: ""},
+		{
+			name:  "host path and named volume",
+			input: "/srv/app/data:/data, pgdata:/var/lib/postgresql/data:ro",
+			want: []VolumeMount{
+				{HostPath: "/srv/app/data", ContainerPath: "/data"},
+				{HostPath: "pgdata", ContainerPath: "/var/lib/postgresql/data", ReadOnly: true},
+			},
+		},
+		{name: "explicit rw", input: "/srv/logs:/logs:rw", want: []VolumeMount{{HostPath: "/srv/logs", ContainerPath: "/logs"}}},
+		{name: "relative container path", input: "/srv/data:data", wantErr: true},
+		{name: "missing container path", input: "/srv/data", wantErr: true},
+		{name: "missing host path", input: ":/data", wantErr: true},
+		{name: "unknown mode", input: "/srv/data:/data:rx", wantErr: true},
+	}
+
+	for _, tt := range tests {
+		t.Run(tt.name, func(t *testing.T) {
+			got, err := ParseVolumeMounts(tt.input)
+			if (err != nil) != tt.wantErr {
+				t.Fatalf("ParseVolumeMounts(%q) error = %v, wantErr %v", tt.input, err, tt.wantErr)
+			}
+			if len(got) != len(tt.want) {
+				t.Fatalf("ParseVolumeMounts(%q) = %v, want %v", tt.input, got, tt.want)
+			}
+			for i := range got {
+				if got[i] != tt.want[i] {
+					t.Errorf("mount %d = %+v, want %+v", i, got[i], tt.want[i])
+				}
+			}
+		})
+	}
+}
+
+func TestFormatVolumeMountsRoundTrip(t *testing.T) {
+	input := "/srv/app/data:/data, pgdata:/var/lib/postgresql/data:ro"
+	mounts, err := ParseVolumeMounts(input)
+	if err != nil {
+		t.Fatalf("ParseVolumeMounts() error = %v", err)
+	}
+	if got := FormatVolumeMounts(mounts); got != input {
+		t.Errorf("FormatVolumeMounts() = %q, want %q", got, input)
+	}
+}
+
+func TestRegistryAddress(t *testing.T) {
+	tests := []struct {
+		name     string
+		image    string
+		registry string
+		want     string
+	}{
+		{name: "docker hub official image", image: "nginx:latest", want: ""},
+		{name: "docker hub user image", image: "myuser/app:v1", want: ""},
+		{name: "registry host in image", image: "ghcr.io/org/app:v1", want: "ghcr.io"},
+		{name: "registry with port", image: "registry.local:5000/app", want: "registry.local:5000"},
+		{name: "localhost registry", image: "localhost/app", want: "localhost"},
+		{name: "explicit registry wins", image: "org/app", registry: " registry.example.com ", want: "registry.example.com"},
+	}
+
+	for _, tt := range tests {
+		t.Run(tt.name, func(t *testing.T) {
+			site := &Site{DockerImage: tt.image, DockerRegistry: tt.registry}
+			if got := site.RegistryAddress(); got != tt.want {
+				t.Errorf("RegistryAddress() = %q, want %q", got, tt.want)
+			}
+		})
+	}
+}
+
+func TestSetNodeIDs(t *testing.T) {
+	nodeA, nodeB, nodeC := uuid.New(), uuid.New(), uuid.New()
+
+	site := &Site{NodeID: nodeA}
+	if got := site.GetNodeIDs(); len(got) != 1 || got[0] != nodeA {
+		t.Fatalf("legacy site GetNodeIDs() = %v, want [%s]", got, nodeA)
+	}
+
+	site.SetNodeIDs([]uuid.UUID{nodeB, nodeC})
+	if site.NodeID != nodeB || !site.HasReplicas() || !site.OnNode(nodeC) || site.OnNode(nodeA) {
+		t.Errorf("after SetNodeIDs(b, c): NodeID = %s, NodeIDs = %v", site.NodeID, site.NodeIDs)
+	}
+
+	site.SetReplicaStatus(nodeB, SiteStatusRunning, "")
+	site.SetReplicaStatus(nodeC, SiteStatusFailed, "pull failed")
+	site.SetReplicaStatus(nodeC, SiteStatusRunning, "")
+	if len(site.Replicas) != 2 || site.GetReplicaStatus(nodeC).Status != SiteStatusRunning {
+		t.Errorf("Replicas = %+v, want both nodes running", site.Replicas)
+	}
+
+	// Dropping a replica forgets its status
+	site.SetNodeIDs([]uuid.UUID{nodeB, nodeA})
+	if site.GetReplicaStatus(nodeC) != nil || site.GetReplicaStatus(nodeB) == nil {
+		t.Errorf("Replicas = %+v, want only node b's status kept", site.Replicas)
+	}
+
+	// A single node is stored the legacy way, without replicas
+	site.SetNodeIDs([]uuid.UUID{nodeA})
+	if site.NodeID != nodeA || site.NodeIDs != nil || site.Replicas != nil || site.HasReplicas() {
+		t.Errorf("after SetNodeIDs(a): NodeID = %s, NodeIDs = %v, Replicas = %v", site.NodeID, site.NodeIDs, site.Replicas)
+	}
+}
+
+func TestConfigFileValidate(t *testing.T) {
+	tests := []struct {
+		name    string
+		file    ConfigFile
+		wantErr bool
+	}{
+		{name: "valid", file: ConfigFile{Name: "nginx.conf", ContainerPath: "/etc/nginx/nginx.conf"}},
+		{name: "missing name", file: ConfigFile{ContainerPath: "/etc/app.conf"}, wantErr: true},
+		{name: "relative container path", file: ConfigFile{Name: "app.conf", ContainerPath: "etc/app.conf"}, wantErr: true},
+		{name: "missing container path", file: ConfigFile{Name: "app.conf"}, wantErr: true},
+	}
+
+	for _, tt := range tests {
+		t.Run(tt.name, func(t *testing.T) {
+			if err := tt.file.Validate(); (err != nil) != tt.wantErr {
+				t.Errorf("Validate() error = %v, wantErr %v", err, tt.wantErr)
+			}
+		})
+	}
+}
+
+func TestParseHealthCheck(t *testing.T) {
+	pathTests := []struct {
+		input   string
+		want    string
+		wantErr bool
+	}{
+		{input: "", want: ""},
+		{input: " /healthz ", want: "/healthz"},
+		{input: "healthz", wantErr: true},
+	}
+	for _, tt := range pathTests {
+		t.Run("path "+tt.input, func(t *testing.T) {
+			got, err := ParseHealthPath(tt.input)
+			if (err != nil) != tt.wantErr {
+				t.Fatalf("ParseHealthPath(%q) error = %v, wantErr %v", tt.input, err, tt.wantErr)
+			}
+			if got != tt.want {
+				t.Errorf("ParseHealthPath(%q) = %q, want %q", tt.input, got, tt.want)
+			}
+		})
+	}
+
+	statusTests := []struct {
+		input   string
+		want    int
+		wantErr bool
+	}{
+		{input: "", want: 0},
+		{input: "204", want: 204},
+		{input: "99", wantErr: true},
+		{input: "ok", wantErr: true},
+	}
+	for _, tt := range statusTests {
+		t.Run("status "+tt.input, func(t *testing.T) {
+			got, err := ParseHealthStatus(tt.input)
+			if (err != nil) != tt.wantErr {
+				t.Fatalf("ParseHealthStatus(%q) error = %v, wantErr %v", tt.input, err, tt.wantErr)
+			}
+			if got != tt.want {
+				t.Errorf("ParseHealthStatus(%q) = %v, want %v", tt.input, got, tt.want)
+			}
+		})
+	}
+}
+
+func TestHealthURL(t *testing.T) {
+	tests := []struct {
+		name string
+		site Site
+		want string
+	}{
+		{name: "no health path", site: Site{SSLEnabled: true}, want: ""},
+		{name: "https", site: Site{SSLEnabled: true, HealthPath: "/healthz"}, want: "https://app.example.com/healthz"},
+		{name: "http without ssl", site: Site{HealthPath: "/"}, want: "http://app.example.com/"},
+	}
+	for _, tt := range tests {
+		t.Run(tt.name, func(t *testing.T) {
+			if got := tt.site.HealthURL("app.example.com"); got != tt.want {
+				t.Errorf("HealthURL() = %q, want %q", got, tt.want)
+			}
+		})
+	}
+}
+
+func TestRecordDeployment(t *testing.T) {
+	site := &Site{}
+	for i := 0; i < MaxDeployments+5; i++ {
+		site.RecordDeployment(DeploymentRecord{Image: "app:v" + strconv.Itoa(i), Outcome: DeploymentSucceeded})
+	}
+
+	if len(site.Deployments) != MaxDeployments {
+		t.Fatalf("len(Deployments) = %d, want %d", len(site.Deployments), MaxDeployments)
+	}
+	// The oldest deploys are dropped
+	if got, want := site.Deployments[0].Image, "app:v5"; got != want {
+		t.Errorf("oldest kept deploy = %q, want %q", got, want)
+	}
+	if got, want := site.Deployments[MaxDeployments-1].Image, "app:v"+strconv.Itoa(MaxDeployments+4); got != want {
+		t.Errorf("newest deploy = %q, want %q", got, want)
+	}
+}
+
+func TestRollbackTarget(t *testing.T) {
+	tests := []struct {
+		name    string
+		site    Site
+		want    string
+		wantErr bool
+	}{
+		{
+			name: "last successful deploy of another image",
+			site: Site{DockerImage: "app:v3", Deployments: []DeploymentRecord{
+				{Image: "app:v1", Outcome: DeploymentSucceeded},
+				{Image: "app:v2", Outcome: DeploymentSucceeded},
+				{Image: "app:v3", Outcome: DeploymentFailed},
+				{Image: "app:v3", Outcome: DeploymentSucceeded},
+			}},
+			want: "app:v2",
+		},
+		{
+			name: "failed deploys are skipped",
+			site: Site{DockerImage: "app:v3", Deployments: []DeploymentRecord{
+				{Image: "app:v1", Outcome: DeploymentSucceeded},
+				{Image: "app:v2", Outcome: DeploymentFailed},
+			}},
+			want: "app:v1",
+		},
+		{
+			name: "builds are skipped",
+			site: Site{DockerImage: "app:v2", Deployments: []DeploymentRecord{
+				{Image: "build: /srv/app (Dockerfile)", Outcome: DeploymentSucceeded},
+			}},
+			wantErr: true,
+		},
+		{
+			name:    "no history",
+			site:    Site{DockerImage: "app:v1"},
+			wantErr: true,
+		},
+		{
+			name:    "site built from source",
+			site:    Site{Build: &BuildSpec{Context: "/srv/app"}, Deployments: []DeploymentRecord{{Image: "app:v1", Outcome: DeploymentSucceeded}}},
+			wantErr: true,
+		},
+	}
+
+	for _, tt := range tests {
+		t.Run(tt.name, func(t *testing.T) {
+			got, err := tt.site.RollbackTarget()
+			if (err != nil) != tt.wantErr {
+				t.Fatalf("RollbackTarget() error = %v, wantErr %v", err, tt.wantErr)
+			}
+			if got.Image != tt.want {
+				t.Errorf("RollbackTarget() image = %q, want %q", got.Image, tt.want)
+			}
+		})
+	}
+}
+
+func TestValidateDeploymentStrategy(t *testing.T) {
+	tests := []struct {
+		name    string
+		site    Site
+		proxy   ProxyType
+		wantErr bool
+	}{
+		{name: "recreate on any proxy", site: Site{DockerImage: "app:v1"}, proxy: ProxyTypeNginx},
+		{name: "blue/green on traefik", site: Site{DockerImage: "app:v1", Strategy: StrategyBlueGreen}, proxy: ProxyTypeTraefik},
+		{name: "blue/green on nginx", site: Site{DockerImage: "app:v1", Strategy: StrategyBlueGreen}, proxy: ProxyTypeNginx, wantErr: true},
+		{name: "blue/green compose site", site: Site{SiteType: SiteTypeCompose, Strategy: StrategyBlueGreen}, proxy: ProxyTypeTraefik, wantErr: true},
+	}
+
+	for _, tt := range tests {
+		t.Run(tt.name, func(t *testing.T) {
+			err := tt.site.ValidateDeploymentStrategy(tt.proxy)
+			if (err != nil) != tt.wantErr {
+				t.Errorf("ValidateDeploymentStrategy(%s) error = %v, wantErr %v", tt.proxy, err, tt.wantErr)
+			}
+		})
+	}
+}
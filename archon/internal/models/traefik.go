@@ -0,0 +1,111 @@
+package models
+
+import (
+	"fmt"
+	"strings"
+)
+
+type TraefikMiddlewareType string
+
+const (
+	TraefikMiddlewareRateLimit      TraefikMiddlewareType = "rate_limit"
+	TraefikMiddlewareCompress       TraefikMiddlewareType = "compress"
+	TraefikMiddlewareRedirectScheme TraefikMiddlewareType = "redirect_scheme"
+	TraefikMiddlewareBasicAuth      TraefikMiddlewareType = "basic_auth"
+	TraefikMiddlewareHeaders        TraefikMiddlewareType = "headers"
+)
+
+// TraefikMiddleware is a Traefik HTTP middleware attached to a site's router
+// Only the fields for the middleware's Type are used
+type TraefikMiddleware struct {
+	Type TraefikMiddlewareType `json:"type" toml:"type"`
+
+	// RateLimit
+	Average int `json:"average,omitempty" toml:"average,omitempty"` // Requests per second allowed on average
+	Burst   int `json:"burst,omitempty" toml:"burst,omitempty"`     // Requests allowed above the average in a short burst
+
+	// RedirectScheme
+	Scheme    string `json:"scheme,omitempty" toml:"scheme,omitempty"`       // Target scheme (defaults to "https")
+	Permanent bool   `json:"permanent,omitempty" toml:"permanent,omitempty"` // Use a 301/308 instead of a 302/307
+
+	// BasicAuth
+	Users []string `json:"users,omitempty" toml:"users,omitempty"` // htpasswd entries ("user:hash")
+
+	// Headers
+	RequestHeaders  map[string]string `json:"request_headers,omitempty" toml:"request_headers,omitempty"`   // Added to requests sent to the site
+	ResponseHeaders map[string]string `json:"response_headers,omitempty" toml:"response_headers,omitempty"` // Added to responses sent to clients
+}
+
+// labelName returns the Traefik option name used in labels for the middleware type
+func (t TraefikMiddlewareType) labelName() string {
+	switch t {
+	case TraefikMiddlewareRateLimit:
+		return "ratelimit"
+	case TraefikMiddlewareRedirectScheme:
+		return "redirectscheme"
+	case TraefikMiddlewareBasicAuth:
+		return "basicauth"
+	default:
+		return string(t)
+	}
+}
+
+// Validate checks that the middleware has the options its type requires
+func (m *TraefikMiddleware) Validate() error {
+	switch m.Type {
+	case TraefikMiddlewareRateLimit:
+		if m.Average <= 0 {
+			return fmt.Errorf("rate limit middleware requires a positive average")
+		}
+		if m.Burst < 0 {
+			return fmt.Errorf("rate limit burst cannot be negative")
+		}
+	case TraefikMiddlewareCompress, TraefikMiddlewareRedirectScheme:
+	case TraefikMiddlewareBasicAuth:
+		if len(m.Users) == 0 {
+			return fmt.Errorf("basic auth middleware requires at least one user")
+		}
+	case TraefikMiddlewareHeaders:
+		if len(m.RequestHeaders) == 0 && len(m.ResponseHeaders) == 0 {
+			return fmt.Errorf("headers middleware requires at least one header")
+		}
+	default:
+		return fmt.Errorf("unknown Traefik middleware type: %s", m.Type)
+	}
+	return nil
+}
+
+// Labels returns the traefik.http.middlewares.<name>.* labels defining the middleware
+func (m *TraefikMiddleware) Labels(name string) map[string]string {
+	prefix := fmt.Sprintf("traefik.http.middlewares.%s.%s", name, m.Type.labelName())
+	labels := make(map[string]string)
+
+	switch m.Type {
+	case TraefikMiddlewareRateLimit:
+		labels[prefix+".average"] = fmt.Sprintf("%d", m.Average)
+		if m.Burst > 0 {
+			labels[prefix+".burst"] = fmt.Sprintf("%d", m.Burst)
+		}
+	case TraefikMiddlewareCompress:
+		labels[prefix] = "true"
+	case TraefikMiddlewareRedirectScheme:
+		scheme := m.Scheme
+		if scheme == "" {
+			scheme = "https"
+		}
+		labels[prefix+".scheme"] = scheme
+		labels[prefix+".permanent"] = fmt.Sprintf("%t", m.Permanent)
+	case TraefikMiddlewareBasicAuth:
+		labels[prefix+".users"] = strings.Join(m.Users, ",")
+	case TraefikMiddlewareHeaders:
+		for key, value := range m.RequestHeaders {
+			labels[prefix+".customrequestheaders."+key] = value
+		}
+		for key, value := range m.ResponseHeaders {
+			labels[prefix+".customresponseheaders."+key] = value
+		}
+	}
+
+	return labels
+}
+
@@ -0,0 +1,214 @@
+package models
+
+import (
+	"strings"
+	"testing"
+
+	"github.com/google/uuid"
+)
+
+func TestGenerateTraefikLabelsMiddlewares(t *testing.T) {
+	tests := []struct {
+		name            string
+		middlewares     []TraefikMiddleware
+		wantMiddlewares string            // routers.<id>.middlewares value, empty means the label is absent
+		wantLabels      map[string]string // middleware labels keyed without the "traefik.http.middlewares.<id>-" prefix
+	}{
+		{
+			name: "no middlewares",
+		},
+		{
+			name:            "rate limit",
+			middlewares:     []TraefikMiddleware{{Type: TraefikMiddlewareRateLimit, Average: 100, Burst: 50}},
+			wantMiddlewares: "ratelimit-0",
+			wantLabels: map[string]string{
+				"ratelimit-0.ratelimit.average": "100",
+				"ratelimit-0.ratelimit.burst":   "50",
+			},
+		},
+		{
+			name: "middlewares keep their order",
+			middlewares: []TraefikMiddleware{
+				{Type: TraefikMiddlewareRedirectScheme, Permanent: true},
+				{Type: TraefikMiddlewareCompress},
+			},
+			wantMiddlewares: "redirectscheme-0,compress-1",
+			wantLabels: map[string]string{
+				"redirectscheme-0.redirectscheme.scheme":    "https",
+				"redirectscheme-0.redirectscheme.permanent": "true",
+				"compress-1.compress":                       "true",
+			},
+		},
+		{
+			name:            "basic auth",
+			middlewares:     []TraefikMiddleware{{Type: TraefikMiddlewareBasicAuth, Users: []string{"admin:$apr1$abc", "ops:$apr1$def"}}},
+			wantMiddlewares: "basicauth-0",
+			wantLabels: map[string]string{
+				"basicauth-0.basicauth.users": "admin:$apr1$abc,ops:$apr1$def",
+			},
+		},
+		{
+			name: "headers",
+			middlewares: []TraefikMiddleware{{
+				Type:            TraefikMiddlewareHeaders,
+				RequestHeaders:  map[string]string{"X-Forwarded-Proto": "https"},
+				ResponseHeaders: map[string]string{"X-Frame-Options": "DENY"},
+			}},
+			wantMiddlewares: "headers-0",
+			wantLabels: map[string]string{
+				"headers-0.headers.customrequestheaders.X-Forwarded-Proto": "https",
+				"headers-0.headers.customresponseheaders.X-Frame-Options":  "DENY",
+			},
+		},
+	}
+
+	for _, tt := range tests {
+		t.Run(tt.name, func(t *testing.T) {
+			site := NewSite("app", uuid.New(), uuid.New(), "nginx:latest", 8080)
+			site.Middlewares = tt.middlewares
+			router := site.ID.String()
+
+			labels := site.GenerateTraefikLabels("example.com")
+
+			if got := labels["traefik.http.routers."+router+".rule"]; got != "Host(`example.com`)" {
+				t.Errorf("router rule = %q", got)
+			}
+			if got := labels["traefik.http.services."+router+".loadbalancer.server.port"]; got != "8080" {
+				t.Errorf("service port = %q, want 8080", got)
+			}
+
+			// Middleware names are prefixed with the router name
+			wantMiddlewares := ""
+			if tt.wantMiddlewares != "" {
+				var names []string
+				for _, name := range strings.Split(tt.wantMiddlewares, ",") {
+					names = append(names, router+"-"+name)
+				}
+				wantMiddlewares = strings.Join(names, ",")
+			}
+			got, ok := labels["traefik.http.routers."+router+".middlewares"]
+			if got != wantMiddlewares || ok != (wantMiddlewares != "") {
+				t.Errorf("router middlewares = %q, want %q", got, wantMiddlewares)
+			}
+
+			for key, want := range tt.wantLabels {
+				full := "traefik.http.middlewares." + router + "-" + key
+				if labels[full] != want {
+					t.Errorf("%s = %q, want %q", full, labels[full], want)
+				}
+			}
+			middlewareLabels := 0
+			for key := range labels {
+				if strings.HasPrefix(key, "traefik.http.middlewares.") {
+					middlewareLabels++
+				}
+			}
+			if middlewareLabels != len(tt.wantLabels) {
+				t.Errorf("got %d middleware labels, want %d", middlewareLabels, len(tt.wantLabels))
+			}
+		})
+	}
+}
+
+func TestGenerateTraefikLabelsCertResolver(t *testing.T) {
+	tests := []struct {
+		name       string
+		ssl        bool
+		resolver   string
+		wantLabel  string
+		wantExists bool
+	}{
+		{name: "default resolver", ssl: true, resolver: "", wantLabel: DefaultCertResolver, wantExists: true},
+		{name: "custom resolver", ssl: true, resolver: "le-dns", wantLabel: "le-dns", wantExists: true},
+		{name: "no resolver without SSL", ssl: false, resolver: "le-dns", wantExists: false},
+	}
+
+	for _, tt := range tests {
+		t.Run(tt.name, func(t *testing.T) {
+			site := NewSite("app", uuid.New(), uuid.New(), "nginx:latest", 8080)
+			site.SSLEnabled = tt.ssl
+			site.CertResolver = tt.resolver
+
+			got, ok := site.GenerateTraefikLabels("example.com")["traefik.http.routers."+site.ID.String()+".tls.certresolver"]
+			if ok != tt.wantExists || got != tt.wantLabel {
+				t.Errorf("certresolver label = %q (present %v), want %q (present %v)", got, ok, tt.wantLabel, tt.wantExists)
+			}
+		})
+	}
+}
+
+func TestGenerateTraefikLabelsExtraHosts(t *testing.T) {
+	tests := []struct {
+		name       string
+		extraHosts []string
+		wantRule   string
+		wantSANs   string
+	}{
+		{name: "primary only", wantRule: "Host(`example.com`)"},
+		{
+			name:       "extra hosts",
+			extraHosts: []string{"www.example.com", "app.example.org"},
+			wantRule:   "Host(`example.com`) || Host(`www.example.com`) || Host(`app.example.org`)",
+			wantSANs:   "www.example.com,app.example.org",
+		},
+		{
+			name:       "duplicate of primary is ignored",
+			extraHosts: []string{"example.com", "www.example.com"},
+			wantRule:   "Host(`example.com`) || Host(`www.example.com`)",
+			wantSANs:   "www.example.com",
+		},
+	}
+
+	for _, tt := range tests {
+		t.Run(tt.name, func(t *testing.T) {
+			site := NewSite("app", uuid.New(), uuid.New(), "nginx:latest", 8080)
+			site.SSLEnabled = true
+			site.ExtraHosts = tt.extraHosts
+
+			labels := site.GenerateTraefikLabels("example.com")
+			router := "traefik.http.routers." + site.ID.String()
+
+			if got := labels[router+".rule"]; got != tt.wantRule {
+				t.Errorf("rule = %q, want %q", got, tt.wantRule)
+			}
+			if got := labels[router+".tls.domains[0].main"]; got != "example.com" {
+				t.Errorf("tls main domain = %q, want %q", got, "example.com")
+			}
+			if got := labels[router+".tls.domains[0].sans"]; got != tt.wantSANs {
+				t.Errorf("tls sans = %q, want %q", got, tt.wantSANs)
+			}
+		})
+	}
+}
+
+func TestGenerateTraefikLabelsHealthCheck(t *testing.T) {
+	tests := []struct {
+		name       string
+		path       string
+		status     int
+		wantPath   string
+		wantStatus string
+	}{
+		{name: "no health check"},
+		{name: "default status", path: "/healthz", wantPath: "/healthz"},
+		{name: "expected status", path: "/ping", status: 204, wantPath: "/ping", wantStatus: "204"},
+	}
+
+	for _, tt := range tests {
+		t.Run(tt.name, func(t *testing.T) {
+			site := NewSite("app", uuid.New(), uuid.New(), "nginx:latest", 8080)
+			site.HealthPath = tt.path
+			site.HealthStatus = tt.status
+
+			labels := site.GenerateTraefikLabels("example.com")
+			healthcheck := "traefik.http.services." + site.ID.String() + ".loadbalancer.healthcheck"
+
+			if got := labels[healthcheck+".path"]; got != tt.wantPath {
+				t.Errorf("healthcheck path = %q, want %q", got, tt.wantPath)
+			}
+			if got := labels[healthcheck+".status"]; got != tt.wantStatus {
+				t.Errorf("healthcheck status = %q, want %q", got, tt.wantStatus)
+			}
+		})
+	}
+}
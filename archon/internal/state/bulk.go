@@ -0,0 +1,91 @@
+package state
+
+import (
+	"fmt"
+
+	"github.com/google/uuid"
+
+	"github.com/BlueBeard63/archon/internal/models"
+)
+
+// BulkOperation stops or deploys every site on a node, a few at a time
+type BulkOperation struct {
+	NodeID    uuid.UUID
+	NodeName  string
+	OpType    string             // OpStopSite or OpDeploySite
+	Queue     []uuid.UUID        // Sites not started yet, in order
+	Running   map[uuid.UUID]bool // Tracked operation IDs still in flight
+	Total     int
+	Succeeded int
+	Failed    int
+}
+
+// NewBulkOperation queues opType for each of the sites
+func NewBulkOperation(node *models.Node, opType string, sites []models.Site) *BulkOperation {
+	queue := make([]uuid.UUID, len(sites))
+	for i, site := range sites {
+		queue[i] = site.ID
+	}
+	return &BulkOperation{
+		NodeID:   node.ID,
+		NodeName: node.Name,
+		OpType:   opType,
+		Queue:    queue,
+		Running:  make(map[uuid.UUID]bool),
+		Total:    len(sites),
+	}
+}
+
+// Next removes and returns the next queued site, or false if none are left
+func (b *BulkOperation) Next() (uuid.UUID, bool) {
+	if len(b.Queue) == 0 {
+		return uuid.Nil, false
+	}
+	id := b.Queue[0]
+	b.Queue = b.Queue[1:]
+	return id, true
+}
+
+// Finish records the result of one of the bulk operation's tracked operations
+// It returns false if the operation isn't part of this bulk operation
+func (b *BulkOperation) Finish(opID uuid.UUID, failed bool) bool {
+	if !b.Running[opID] {
+		return false
+	}
+	delete(b.Running, opID)
+	if failed {
+		b.Failed++
+	} else {
+		b.Succeeded++
+	}
+	return true
+}
+
+// Done reports whether every site has been handled
+func (b *BulkOperation) Done() bool {
+	return len(b.Queue) == 0 && len(b.Running) == 0
+}
+
+// Progress describes how far the bulk operation has got, e.g. "Stopping all sites on prod: 2/5 done"
+func (b *BulkOperation) Progress() string {
+	verb := "Stopping"
+	if b.OpType == OpDeploySite {
+		verb = "Deploying"
+	}
+	progress := fmt.Sprintf("%s all sites on %s: %d/%d done", verb, b.NodeName, b.Succeeded+b.Failed, b.Total)
+	if b.Failed > 0 {
+		progress += fmt.Sprintf(", %d failed", b.Failed)
+	}
+	return progress
+}
+
+// SitesOnNode returns the sites deployed to a node, including those with a replica on it, in config order
+func (s *AppState) SitesOnNode(nodeID uuid.UUID) []models.Site {
+	var sites []models.Site
+	for _, site := range s.Sites {
+		if site.OnNode(nodeID) {
+			sites = append(sites, site)
+		}
+	}
+	return sites
+}
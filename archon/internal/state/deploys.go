@@ -0,0 +1,105 @@
+package state
+
+import (
+	"encoding/json"
+	"os"
+	"path/filepath"
+	"time"
+
+	"github.com/google/uuid"
+
+	"github.com/BlueBeard63/archon/internal/models"
+)
+
+// DeployingPath returns the file listing the sites being deployed, next to the config. It is
+// kept up to date as deploys start and finish, so deploys cut short by Archon exiting can be
+// checked on their node at the next start.
+func DeployingPath(configPath string) string {
+	return filepath.Join(filepath.Dir(configPath), "deploying.json")
+}
+
+// DeployingSite is a deploy in progress, as listed in the file at DeployingPath
+type DeployingSite struct {
+	SiteID    uuid.UUID `json:"site_id"`
+	StartedAt time.Time `json:"started_at"`
+}
+
+// LoadDeploying reads the deploys saved by SaveDeploying. A missing file lists no deploys.
+func LoadDeploying(path string) ([]DeployingSite, error) {
+	data, err := os.ReadFile(path)
+	if os.IsNotExist(err) {
+		return nil, nil
+	}
+	if err != nil {
+		return nil, err
+	}
+
+	var deploys []DeployingSite
+	if err := json.Unmarshal(data, &deploys); err != nil {
+		return nil, err
+	}
+	return deploys, nil
+}
+
+// SaveDeploying writes the deploys in progress to path, removing the file when there are none
+func SaveDeploying(path string, deploys []DeployingSite) error {
+	if len(deploys) == 0 {
+		if err := os.Remove(path); err != nil && !os.IsNotExist(err) {
+			return err
+		}
+		return nil
+	}
+	data, err := json.MarshalIndent(deploys, "", "  ")
+	if err != nil {
+		return err
+	}
+	return os.WriteFile(path, data, 0600)
+}
+
+// DeployingSiteIDs returns the IDs of the sites whose status is deploying
+func (s *AppState) DeployingSiteIDs() []uuid.UUID {
+	var ids []uuid.UUID
+	for _, site := range s.Sites {
+		if site.Status == models.SiteStatusDeploying {
+			ids = append(ids, site.ID)
+		}
+	}
+	return ids
+}
+
+// DeployingSites returns the deploys in progress with when each started. A site newly
+// deploying is taken to have started now, and sites no longer deploying are forgotten.
+func (s *AppState) DeployingSites() []DeployingSite {
+	starts := make(map[uuid.UUID]time.Time)
+	var deploys []DeployingSite
+	for _, id := range s.DeployingSiteIDs() {
+		started, ok := s.DeployStarts[id]
+		if !ok {
+			started = time.Now()
+		}
+		starts[id] = started
+		deploys = append(deploys, DeployingSite{SiteID: id, StartedAt: started})
+	}
+	s.DeployStarts = starts
+	return deploys
+}
+
+// DeployStarted returns when a site's deploy in progress started, or the zero time if unknown
+func (s *AppState) DeployStarted(siteID uuid.UUID) time.Time {
+	return s.DeployStarts[siteID]
+}
+
+// MarkDeploying sets the status of the listed sites back to deploying, for deploys that were
+// running when Archon exited but whose status wasn't saved to the config. Sites that have
+// since been deleted are skipped.
+func (s *AppState) MarkDeploying(deploys []DeployingSite) {
+	for _, deploy := range deploys {
+		if site := s.GetSiteByID(deploy.SiteID); site != nil {
+			site.Status = models.SiteStatusDeploying
+			if s.DeployStarts == nil {
+				s.DeployStarts = make(map[uuid.UUID]time.Time)
+			}
+			s.DeployStarts[deploy.SiteID] = deploy.StartedAt
+		}
+	}
+}
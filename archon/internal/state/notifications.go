@@ -0,0 +1,67 @@
+package state
+
+import (
+	"encoding/json"
+	"os"
+	"path/filepath"
+)
+
+// MaxNotifications is how many notifications are kept, in memory and across restarts
+const MaxNotifications = 50
+
+// NotificationsPath returns the file notifications are kept in between sessions, next to the config
+func NotificationsPath(configPath string) string {
+	return filepath.Join(filepath.Dir(configPath), "notifications.json")
+}
+
+// LoadNotifications reads the notifications saved by SaveNotifications, oldest first,
+// keeping the most recent MaxNotifications. A missing file has no notifications.
+func LoadNotifications(path string) ([]Notification, error) {
+	data, err := os.ReadFile(path)
+	if os.IsNotExist(err) {
+		return nil, nil
+	}
+	if err != nil {
+		return nil, err
+	}
+
+	var notifications []Notification
+	if err := json.Unmarshal(data, &notifications); err != nil {
+		return nil, err
+	}
+	if len(notifications) > MaxNotifications {
+		notifications = notifications[len(notifications)-MaxNotifications:]
+	}
+	return notifications, nil
+}
+
+// SaveNotifications writes notifications to path. The file is private to the user, since
+// messages name sites, nodes and the errors they ran into.
+func SaveNotifications(path string, notifications []Notification) error {
+	data, err := json.MarshalIndent(notifications, "", "  ")
+	if err != nil {
+		return err
+	}
+	return os.WriteFile(path, data, 0600)
+}
+
+// RestoreNotifications puts notifications from the last session ahead of this session's.
+// Restored notifications show in the history but not in the status bar.
+func (s *AppState) RestoreNotifications(notifications []Notification) {
+	restored := append(append([]Notification(nil), notifications...), s.Notifications...)
+	s.restoredNotifications = len(notifications)
+	if len(restored) > MaxNotifications {
+		drop := len(restored) - MaxNotifications
+		restored = restored[drop:]
+		s.restoredNotifications = max(s.restoredNotifications-drop, 0)
+	}
+	s.Notifications = restored
+}
+
+// LatestNotification returns the most recent notification of this session, for the status bar
+func (s *AppState) LatestNotification() (Notification, bool) {
+	if len(s.Notifications) <= s.restoredNotifications {
+		return Notification{}, false
+	}
+	return s.Notifications[len(s.Notifications)-1], true
+}
@@ -0,0 +1,100 @@
+package state
+
+import (
+	"sort"
+	"strings"
+	"unicode"
+
+	"github.com/google/uuid"
+)
+
+// MaxPaletteResults limits how many matches the command palette shows
+const MaxPaletteResults = 10
+
+// Palette is the open command palette (Ctrl+P)
+type Palette struct {
+	Query string // Text typed so far
+	Index int    // Selected row in the current matches
+}
+
+// PaletteEntry is a site, domain or node the palette can jump to
+type PaletteEntry struct {
+	Kind   string // "site", "domain" or "node"
+	ID     uuid.UUID
+	Name   string
+	Detail string // Extra context shown next to the name
+	score  int
+}
+
+// PaletteMatches returns the sites, domains and nodes whose names fuzzy-match the
+// palette query, best match first. An empty query lists everything in tab order.
+func (s *AppState) PaletteMatches() []PaletteEntry {
+	query := ""
+	if s.Palette != nil {
+		query = s.Palette.Query
+	}
+
+	var entries []PaletteEntry
+	add := func(kind string, id uuid.UUID, name, detail string) {
+		if score, ok := FuzzyScore(query, name); ok {
+			entries = append(entries, PaletteEntry{Kind: kind, ID: id, Name: name, Detail: detail, score: score})
+		}
+	}
+
+	for _, site := range s.Sites {
+		add("site", site.ID, site.Name, string(site.Status))
+	}
+	for _, domain := range s.Domains {
+		add("domain", domain.ID, domain.Name, domain.ProviderName())
+	}
+	for _, node := range s.Nodes {
+		add("node", node.ID, node.Name, string(node.Status))
+	}
+
+	// Stable so equal scores keep sites, domains, nodes order
+	sort.SliceStable(entries, func(i, j int) bool {
+		return entries[i].score > entries[j].score
+	})
+	if len(entries) > MaxPaletteResults {
+		entries = entries[:MaxPaletteResults]
+	}
+	return entries
+}
+
+// FuzzyScore reports whether every character of query appears in text in order
+// (case-insensitive) and scores the match. Consecutive characters, matches at the
+// start of a word and shorter texts score higher.
+func FuzzyScore(query, text string) (int, bool) {
+	q := []rune(strings.ToLower(query))
+	t := []rune(strings.ToLower(text))
+	if len(q) == 0 {
+		return 0, true
+	}
+
+	score := 0
+	qi := 0
+	prev := -2
+	for ti, r := range t {
+		if qi == len(q) {
+			break
+		}
+		if r != q[qi] {
+			continue
+		}
+
+		score++
+		if ti == prev+1 {
+			score += 5 // Consecutive run
+		}
+		if ti == 0 || !unicode.IsLetter(t[ti-1]) && !unicode.IsDigit(t[ti-1]) {
+			score += 3 // Start of a word
+		}
+		prev = ti
+		qi++
+	}
+
+	if qi < len(q) {
+		return 0, false
+	}
+	return score*10 - len(t), true
+}
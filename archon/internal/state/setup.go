@@ -0,0 +1,39 @@
+package state
+
+// SetupStep is where the first-run setup wizard is up to
+type SetupStep int
+
+const (
+	SetupStepNode   SetupStep = iota // No node yet
+	SetupStepDomain                  // No domain yet
+	SetupStepSite                    // Optional: no site yet
+	SetupStepDone                    // A node, domain and site exist
+)
+
+// NeedsSetup reports whether nothing has been configured yet, as on a fresh install
+func (s *AppState) NeedsSetup() bool {
+	return len(s.Nodes) == 0 && len(s.Domains) == 0 && len(s.Sites) == 0
+}
+
+// SetupStep returns the first step of the setup wizard that hasn't been done. Steps are
+// done by what exists rather than by what was added in the wizard, so leaving it partway
+// and coming back picks up where it left off.
+func (s *AppState) SetupStep() SetupStep {
+	switch {
+	case len(s.Nodes) == 0:
+		return SetupStepNode
+	case len(s.Domains) == 0:
+		return SetupStepDomain
+	case len(s.Sites) == 0:
+		return SetupStepSite
+	default:
+		return SetupStepDone
+	}
+}
+
+// FinishSetup leaves the setup wizard for the dashboard, with nothing to go back to
+func (s *AppState) FinishSetup() {
+	s.CurrentScreen = ScreenDashboard
+	s.PreviousScreens = []Screen{}
+	s.NextScreens = []Screen{}
+}
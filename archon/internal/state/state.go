@@ -1,10 +1,21 @@
 package state
 
 import (
+	"context"
+	"fmt"
+	"sort"
+	"strings"
+	"time"
+
 	"github.com/google/uuid"
 	tea "github.com/charmbracelet/bubbletea"
 	"github.com/charmbracelet/bubbles/table"
 	"github.com/charmbracelet/bubbles/viewport"
+	"github.com/charmbracelet/lipgloss"
+	"github.com/BlueBeard63/archon/internal/api"
+	"github.com/BlueBeard63/archon/internal/config"
+	"github.com/BlueBeard63/archon/internal/desktop"
+	"github.com/BlueBeard63/archon/internal/dns"
 	"github.com/BlueBeard63/archon/internal/models"
 )
 
@@ -17,6 +28,7 @@ type TableComponent interface {
 	Update(tea.Msg) tea.Cmd
 	SetWidth(int)
 	GetSelectedRow() table.Row
+	SetSelectedColor(lipgloss.Color)
 }
 
 // Screen represents different screens in the TUI
@@ -28,10 +40,18 @@ const (
 	ScreenSiteCreate        Screen = "site_create"
 	ScreenSiteEdit          Screen = "site_edit"
 	ScreenSiteEnvVars       Screen = "site_env_vars"
+	ScreenSiteConfigFiles   Screen = "site_config_files"
+	ScreenSiteDetail        Screen = "site_detail"
+	ScreenSitesDeleted      Screen = "sites_deleted"
 	ScreenDomainsList       Screen = "domains_list"
 	ScreenDomainCreate      Screen = "domain_create"
 	ScreenDomainEdit        Screen = "domain_edit"
 	ScreenDomainDnsRecords  Screen = "domain_dns_records"
+	ScreenDnsRecordForm     Screen = "dns_record_form"
+	ScreenDnsSyncDiff       Screen = "dns_sync_diff"
+	ScreenDeployPreview     Screen = "deploy_preview"
+	ScreenOperations        Screen = "operations"
+	ScreenNotifications     Screen = "notifications"
 	ScreenNodesList         Screen = "nodes_list"
 	ScreenNodeCreate        Screen = "node_create"
 	ScreenNodeEdit          Screen = "node_edit"
@@ -39,6 +59,69 @@ const (
 	ScreenNodeConfigSave    Screen = "node_config_save"
 	ScreenSettings          Screen = "settings"
 	ScreenHelp              Screen = "help"
+	ScreenSetup             Screen = "setup"
+)
+
+// Site detail screen tabs
+const (
+	SiteDetailTabOverview = iota
+	SiteDetailTabLogs
+	SiteDetailTabMetrics
+	SiteDetailTabDeployments
+)
+
+// SiteDetailTabNames are the tab labels in display order
+var SiteDetailTabNames = []string{"Overview", "Logs", "Metrics", "Deployments"}
+
+// SiteSortColumn is a column the sites list can be sorted by
+type SiteSortColumn string
+
+const (
+	SiteSortNone   SiteSortColumn = ""
+	SiteSortName   SiteSortColumn = "name"
+	SiteSortStatus SiteSortColumn = "status"
+	SiteSortNode   SiteSortColumn = "node"
+	SiteSortPort   SiteSortColumn = "port"
+)
+
+// siteSortColumns is the order the sort key cycles through, back to insertion order
+var siteSortColumns = []SiteSortColumn{SiteSortNone, SiteSortName, SiteSortStatus, SiteSortNode, SiteSortPort}
+
+// MaxRecentlyDeletedSites is how many deleted sites are kept for restoring
+const MaxRecentlyDeletedSites = 10
+
+// MaxFailedOperations is how many failed operations are kept for retrying
+const MaxFailedOperations = 10
+
+// MaxMetricsSamples is how many metrics snapshots are kept per site for the trend graphs
+const MaxMetricsSamples = 60
+
+// MetricsSample is a metrics snapshot and when it was fetched
+type MetricsSample struct {
+	At      time.Time
+	Metrics api.ContainerMetrics
+}
+
+// DefaultLogBufferLines is how many log lines are kept per site when no limit is configured
+const DefaultLogBufferLines = 1000
+
+// Host ports suggested for new sites when no range is configured
+const (
+	DefaultPortRangeStart = 8000
+	DefaultPortRangeEnd   = 9000
+)
+
+// Offline nodes are health checked again after ReconnectBaseDelay, doubling after each
+// failed attempt up to ReconnectMaxDelay, until they answer
+const (
+	ReconnectBaseDelay = 5 * time.Second
+	ReconnectMaxDelay  = 2 * time.Minute
+)
+
+// Node latencies shown as a warning and as critical when no thresholds are configured
+const (
+	DefaultLatencyWarnMs     = 150
+	DefaultLatencyCriticalMs = 500
 )
 
 // AppState holds all application state for the TUI
@@ -51,6 +134,7 @@ type AppState struct {
 	// UI State
 	CurrentScreen   Screen   `json:"current_screen"`
 	PreviousScreens []Screen `json:"previous_screens"` // Navigation stack for back button
+	NextScreens     []Screen `json:"next_screens"`     // Screens backed out of, for the forward key
 
 	// Selection state (for table lists)
 	SitesListIndex   int       `json:"sites_list_index"`
@@ -60,13 +144,79 @@ type AppState struct {
 	SelectedDomainID uuid.UUID `json:"selected_domain_id"` // For editing domain
 	SelectedNodeID   uuid.UUID `json:"selected_node_id"`   // For viewing/editing node config
 
+	// Sites list filtering
+	SiteTagFilter    string `json:"site_tag_filter"` // Only show sites with this tag (empty = show all)
+	SiteSearch       string `json:"site_search"`     // Only show sites whose name, domain or status contains this (case-insensitive)
+	SiteSearchActive bool   `json:"-"`               // The search is being typed on the sites list
+
+	// Nodes list filtering
+	NodeTagFilter string `json:"node_tag_filter"` // Only show nodes with this tag on the nodes list and dashboard (empty = show all)
+
+	// DNS records editor
+	DnsRecordsIndex    int `json:"dns_records_index"` // Selected record of the selected domain
+	DnsRecordFormIndex int `json:"-"`                 // Record edited on the record form (-1 = adding a record)
+
+	// Dashboard failed sites panel
+	DashboardAlertIndex int `json:"dashboard_alert_index"` // Selected failed site
+
+	// Sites list ordering (empty column = insertion order)
+	SiteSortColumn SiteSortColumn `json:"site_sort_column"`
+	SiteSortDesc   bool           `json:"site_sort_desc"`
+
+	// Undo buffer of deleted sites for this session, oldest first
+	RecentlyDeletedSites []models.Site `json:"-"`
+	DeletedSitesIndex    int           `json:"deleted_sites_index"` // Selection on the recently deleted screen
+
+	// Destructive action awaiting confirmation (nil when no dialog is shown)
+	PendingConfirmation *Confirmation `json:"pending_confirmation"`
+
+	// Command palette overlay (nil when closed)
+	Palette *Palette `json:"-"`
+
+	// Fetched provider records awaiting approval on the DNS sync diff screen (nil when none)
+	PendingDnsSync *DnsSyncPreview `json:"-"`
+
+	// Last propagation check of each domain's DNS records (use SetPropagation/GetPropagation)
+	DnsPropagation map[uuid.UUID][]dns.PropagationResult `json:"-"`
+
+	// Reconnect attempts made per offline node; a node has an entry while it is being retried
+	// (use StartReconnect/NextReconnectDelay/StopReconnect)
+	NodeReconnects map[uuid.UUID]int `json:"-"`
+
+	// Request shown on the deploy preview screen (nil when none)
+	DeployPreview *DeployPreview `json:"-"`
+
+	// Site detail screen
+	SiteDetailTab int                                 `json:"site_detail_tab"` // Active tab (see SiteDetailTab* constants)
+	SiteLogs      map[uuid.UUID][]string              `json:"-"`               // Container log buffer per site (use AppendLogs/GetLogs)
+	SiteMetrics   map[uuid.UUID]*api.ContainerMetrics `json:"-"`               // Last fetched resource metrics per site (use SetMetrics/GetMetrics)
+	FollowLogs    uuid.UUID                           `json:"-"`               // Site whose logs are polled every second (uuid.Nil = not following)
+	FollowLogsGen int                                 `json:"-"`               // Bumped each time following starts, so polls from an earlier follow stop
+	LogCursors    map[uuid.UUID]string                `json:"-"`               // Node cursor after the last fetched log line per site (use AppendNewLogs/LogCursor)
+
+	// Recent metrics per site, oldest first (use SetMetrics/GetMetricsHistory, cleared when the site detail screen closes)
+	SiteMetricsHistory map[uuid.UUID][]MetricsSample `json:"-"`
+
+	// Each site's last status from its node, for the restart count and uptime (use SetContainerStatus/GetContainerStatus)
+	SiteContainers map[uuid.UUID]*api.SiteStatusResponse `json:"-"`
+
+	// When each site's deploy in progress started, kept in the deploying journal (use DeployingSites/DeployStarted)
+	DeployStarts map[uuid.UUID]time.Time `json:"-"`
+
+	// Automatic restarts of each failed site since it was last deployed or restarted by hand (use CountAutoRestart/ResetAutoRestarts)
+	AutoRestarts map[uuid.UUID]int `json:"-"`
+
+	// Result of each site's last health probe, separate from its status (use SetSiteHealth/GetSiteHealth)
+	SiteHealth map[uuid.UUID]models.SiteHealth `json:"-"`
+
 	// Table component instances (runtime only, not serialized)
 	SitesTable   TableComponent `json:"-"`
 	DomainsTable TableComponent `json:"-"`
 	NodesTable   TableComponent `json:"-"`
 
 	// Viewport for scrollable content (runtime only, not serialized)
-	NodeConfigViewport viewport.Model `json:"-"`
+	NodeConfigViewport    viewport.Model `json:"-"`
+	DeployPreviewViewport viewport.Model `json:"-"`
 
 	// Form state (for create/edit screens)
 	FormFields        []string    `json:"form_fields"`        // Current values of form fields
@@ -74,9 +224,17 @@ type AppState struct {
 	CursorPosition    int         `json:"cursor_position"`     // Cursor position within current field
 	DropdownOpen      bool        `json:"dropdown_open"`       // Is a dropdown currently expanded
 	DropdownIndex     int         `json:"dropdown_index"`      // Currently highlighted option in dropdown
+	RevealSecrets     bool        `json:"-"`                   // Show tokens and keys unmasked until leaving the screen
 	EnvVarPairs       []EnvVarPair `json:"env_var_pairs"`       // Environment variable key-value pairs
 	EnvVarFocusedPair int         `json:"env_var_focused_pair"` // Which ENV pair is currently focused
 	EnvVarFocusedField int        `json:"env_var_focused_field"` // 0=key, 1=value
+	EnvImportActive   bool        `json:"-"`                   // A .env file path is being typed on the ENV vars screen
+	EnvImportPath     string      `json:"-"`                   // Path of the .env file to import
+
+	// Config files of the site form, edited on the config files screen and saved with the site
+	ConfigFileDrafts []models.ConfigFile `json:"-"`
+	ConfigFilesIndex int                 `json:"-"` // Selected file on the config files screen
+	ConfigFileForm   *ConfigFileForm     `json:"-"` // File being added or edited (nil = showing the list)
 
 	// Domain mappings for multi-domain sites
 	DomainMappingPairs       []DomainMappingPair `json:"domain_mapping_pairs"`       // Domain mapping entries
@@ -88,25 +246,73 @@ type AppState struct {
 
 	// Compose deployment state (for create/edit screens)
 	SiteTypeSelection  string `json:"site_type_selection"`  // "container" or "compose"
+	ImageSource        string `json:"image_source"`         // "image" (prebuilt) or "build" (container sites only)
+	SiteSSLEnabled     bool   `json:"site_ssl_enabled"`     // SSL toggle on the site create/edit forms
+	SiteAutoRestart    bool   `json:"site_auto_restart"`    // Auto-restart toggle on the site create/edit forms
+	SiteBlueGreen      bool   `json:"site_blue_green"`      // Blue/green deploy toggle on the site create/edit forms
 	ComposeInputMethod string `json:"compose_input_method"` // "file" or "paste"
 	ComposeFilePath    string `json:"compose_file_path"`    // Path to compose file (when input method is "file")
 	ComposeContent     string `json:"compose_content"`      // Pasted compose YAML content (when input method is "paste")
 
 	// Async operations tracking
 	PendingOperations []AsyncOperation `json:"pending_operations"`
-	Notifications     []Notification   `json:"notifications"`
+	SpinnerFrame      int              `json:"-"` // Advanced by SpinnerTickMsg while operations are pending
+	SpinnerRunning    bool             `json:"-"` // A SpinnerTickMsg is scheduled
+	FailedOperations  []AsyncOperation `json:"-"` // Most recent failed operations, oldest first
+	OperationsIndex   int              `json:"-"` // Selection on the operations screen
+	Bulk              *BulkOperation   `json:"-"` // Stop or deploy of every site on a node, if running
+	QuitWaiting       bool             `json:"-"` // Quit was requested while operations were pending; quits once they finish
+	QuitSave          bool             `json:"-"` // Save the config before that quit
+
+	// Notifications, oldest first, including the last session's (saved on quit)
+	Notifications       []Notification   `json:"notifications"`
+	NotificationsScroll int              `json:"-"` // Top row of the notification history, newest first
+	Desktop             desktop.Notifier `json:"-"` // Shows errors on the desktop (nil when there is no desktop)
+	TerminalFocused     bool             `json:"-"` // The terminal reported it has focus, so desktop notifications are skipped
+
+	restoredNotifications int // Leading notifications loaded from the last session
+
+	// Help screen
+	HelpScroll int `json:"-"` // First line of the key bindings shown
 
 	// Window dimensions (updated on resize)
 	WindowWidth  int `json:"window_width"`
 	WindowHeight int `json:"window_height"`
 
 	// Configuration
-	ConfigPath         string `json:"config_path"`
-	AutoSave           bool   `json:"auto_save"`
-	ShouldQuit         bool   `json:"should_quit"`
-	CloudflareAPIToken string `json:"cloudflare_api_token"` // Global default, can be overridden per-domain
-	Route53AccessKey   string `json:"route53_access_key"`   // Global default, can be overridden per-domain
-	Route53SecretKey   string `json:"route53_secret_key"`   // Global default, can be overridden per-domain
+	ConfigPath              string   `json:"config_path"`
+	AutoSave                bool     `json:"auto_save"`
+	Dirty                   bool     `json:"dirty"`                     // Edited since the last save (only tracked when auto-save is off)
+	HealthCheckIntervalSecs int      `json:"health_check_interval_secs"` // Seconds between background node health checks
+	ShouldQuit              bool     `json:"should_quit"`
+	CloudflareAPIToken      string   `json:"cloudflare_api_token"`      // Global default, can be overridden per-domain
+	Route53AccessKey        string   `json:"route53_access_key"`        // Global default, can be overridden per-domain
+	Route53SecretKey        string   `json:"route53_secret_key"`        // Global default, can be overridden per-domain
+	DebugHTTP               bool     `json:"debug_http"`                // Log node API traffic to archon.log
+	LogBufferLines          int      `json:"log_buffer_lines"`          // Max log lines kept per site (0 = DefaultLogBufferLines)
+	RetryMaxAttempts        int      `json:"retry_max_attempts"`        // Node request attempts (0 = default)
+	RetryBaseDelayMs        int      `json:"retry_base_delay_ms"`       // Initial retry delay in milliseconds (0 = default)
+	RetryJitter             *float64 `json:"retry_jitter"`              // Retry delay jitter fraction (nil = default)
+	DNSRateLimitRetries     int      `json:"dns_rate_limit_retries"`    // DNS provider retries after HTTP 429 (0 = default, -1 = none)
+	MaxConcurrentOperations int      `json:"max_concurrent_operations"` // Tracked operations run at once (0 = default)
+	PortRangeStart          int      `json:"port_range_start"`          // First host port suggested for new sites (0 = DefaultPortRangeStart)
+	PortRangeEnd            int      `json:"port_range_end"`            // Last host port suggested for new sites (0 = DefaultPortRangeEnd)
+	LatencyWarnMs           int      `json:"latency_warn_ms"`           // Node latency shown as a warning (0 = DefaultLatencyWarnMs)
+	LatencyCriticalMs       int      `json:"latency_critical_ms"`       // Node latency shown as critical (0 = DefaultLatencyCriticalMs)
+	ConfigBackups           int      `json:"config_backups"`            // Previous config versions kept on save (0 = default, -1 = none)
+	SecretStorage           string   `json:"secret_storage"`            // "keyring" to keep credentials in the OS keyring
+	AuditLog                string   `json:"audit_log"`                 // Audit log path ("" = default, "off" = none)
+	AuditLogMaxMB           int      `json:"audit_log_max_mb"`          // Audit log rotation size (0 = default)
+	DesktopNotifications    bool     `json:"desktop_notifications"`     // Also show error notifications on the desktop
+	WebhookURL              string   `json:"webhook_url"`               // Key events are posted here ("" = none)
+
+	// Key bindings from [settings.keybindings] and the keymap built from them
+	Keybindings map[string]string `json:"keybindings"`
+	Keymap      *config.Keymap    `json:"-"`
+
+	// Color theme settings (the loaded theme is kept on the app model)
+	ThemeName string `json:"theme_name"`
+	ThemeFile string `json:"theme_file"`
 }
 
 // EnvVarPair represents a single environment variable key-value pair
@@ -125,16 +331,77 @@ type DomainMappingPair struct {
 
 // AsyncOperation tracks background operations like deployments
 type AsyncOperation struct {
-	ID     uuid.UUID `json:"id"`
-	OpType string    `json:"op_type"` // "deploy_site", "sync_dns", "health_check", etc.
-	Status string    `json:"status"`  // "pending", "completed", "failed"
-	Target string    `json:"target"`  // Description of what's being operated on
+	ID     uuid.UUID          `json:"id"`
+	OpType string             `json:"op_type"`         // "deploy_site", "sync_dns", "health_check", etc.
+	Status string             `json:"status"`          // "pending", "completed", "failed"
+	Target string             `json:"target"`          // Description of what's being operated on
+	Error  string             `json:"error,omitempty"` // Why a failed operation failed (e.g. "cancelled")
+	Retry  tea.Msg            `json:"-"`               // Starts the operation again
+	Cancel context.CancelFunc `json:"-"`               // Aborts the running operation
+}
+
+// Operation types shown while an operation is in flight
+const (
+	OpDeploySite  = "deploy_site"
+	OpStopSite    = "stop_site"
+	OpRestartSite = "restart_site"
+	OpSyncDns     = "sync_dns"
+	OpPushDns     = "push_dns"
+)
+
+// Label describes the operation for the status bar, e.g. "Deploying site blog"
+func (op AsyncOperation) Label() string {
+	var verb string
+	switch op.OpType {
+	case OpDeploySite:
+		verb = "Deploying site"
+	case OpStopSite:
+		verb = "Stopping site"
+	case OpRestartSite:
+		verb = "Restarting site"
+	case OpSyncDns:
+		verb = "Syncing DNS for"
+	case OpPushDns:
+		verb = "Pushing DNS for"
+	default:
+		verb = strings.ReplaceAll(op.OpType, "_", " ")
+	}
+	if op.Target == "" {
+		return verb
+	}
+	return verb + " " + op.Target
+}
+
+// Confirmation is a destructive action waiting for the user to confirm it
+type Confirmation struct {
+	Kind     string    `json:"kind"` // "site", "domain" or "node"
+	TargetID uuid.UUID `json:"target_id"`
+	Name     string    `json:"name"`
+
+	// Replace the delete wording for confirmations that aren't deletes
+	Title   string `json:"title,omitempty"`
+	Message string `json:"message,omitempty"`
+	Keys    string `json:"keys,omitempty"` // Replaces the "Press y to confirm" hint
+}
+
+// DnsSyncPreview holds a domain's fetched provider records and how they differ from the local ones
+type DnsSyncPreview struct {
+	DomainID uuid.UUID
+	Remote   []models.DnsRecord
+	Diff     dns.RecordDiff
+}
+
+// DeployPreview is the request a deploy would send for a site, built without contacting the node
+type DeployPreview struct {
+	SiteID  uuid.UUID
+	Request string // Indented JSON with secrets redacted
 }
 
 // Notification represents a message to display to the user
 type Notification struct {
-	Message string `json:"message"`
-	Level   string `json:"level"` // "success", "error", "warning", "info"
+	Message string    `json:"message"`
+	Level   string    `json:"level"` // "success", "error", "warning", "info"
+	Time    time.Time `json:"time"`
 }
 
 // NewAppState creates a new AppState with default values
@@ -145,11 +412,14 @@ func NewAppState() *AppState {
 		Nodes:             []models.Node{},
 		CurrentScreen:     ScreenDashboard,
 		PreviousScreens:   []Screen{},
+		NextScreens:       []Screen{},
 		SitesListIndex:    0,
 		DomainsListIndex:  0,
 		NodesListIndex:    0,
 		FormFields:        []string{},
 		CurrentFieldIndex: 0,
+		SiteLogs:          make(map[uuid.UUID][]string),
+		SiteMetrics:       make(map[uuid.UUID]*api.ContainerMetrics),
 		PendingOperations: []AsyncOperation{},
 		Notifications:     []Notification{},
 		AutoSave:          true,
@@ -161,17 +431,19 @@ func NewAppState() *AppState {
 func (s *AppState) NavigateTo(screen Screen) {
 	// Push current screen to history
 	s.PreviousScreens = append(s.PreviousScreens, s.CurrentScreen)
+	s.NextScreens = s.NextScreens[:0] // Going somewhere new drops the forward history
 
-	// Reset edit form flag when leaving edit screen for non-ENV screen
-	if s.CurrentScreen == ScreenSiteEdit && screen != ScreenSiteEnvVars {
+	// Reset edit form flag when leaving edit screen for a screen outside the site form
+	if s.CurrentScreen == ScreenSiteEdit && !isSiteFormSubscreen(screen) {
 		s.EditFormInitialized = false
 	}
 
 	// Switch to new screen
 	s.CurrentScreen = screen
+	s.RevealSecrets = false
 
-	// Reset form state when navigating (except when going to/from ENV screen)
-	if screen != ScreenSiteEnvVars {
+	// Reset form state when navigating (except to the ENV vars and config files screens)
+	if !isSiteFormSubscreen(screen) {
 		s.FormFields = []string{}
 		s.CurrentFieldIndex = 0
 		s.CursorPosition = 0
@@ -184,9 +456,99 @@ func (s *AppState) NavigateTo(screen Screen) {
 		s.DomainMappingFocusedPair = 0
 		s.DomainMappingFocusedField = 0
 		s.SiteTypeSelection = "container" // Default to container
+		s.ImageSource = "image"           // Default to prebuilt image
+		s.SiteSSLEnabled = true           // Default to SSL enabled
 		s.ComposeInputMethod = "file"     // Default to file input
 		s.ComposeFilePath = ""
 		s.ComposeContent = ""
+		s.SiteAutoRestart = false
+		s.SiteBlueGreen = false
+		s.ConfigFileDrafts = nil
+		s.ConfigFilesIndex = 0
+		s.ConfigFileForm = nil
+	}
+}
+
+// isSiteFormSubscreen returns true for screens opened from the site form that edit part of it,
+// which keep the form's state
+func isSiteFormSubscreen(screen Screen) bool {
+	return screen == ScreenSiteEnvVars || screen == ScreenSiteConfigFiles
+}
+
+// IsBuildSource returns true if the site form is set to build the image from source
+func (s *AppState) IsBuildSource() bool {
+	return s.SiteTypeSelection != "compose" && s.ImageSource == "build"
+}
+
+// EnvironmentVars builds a site's environment from the form's ENV pairs. Blank rows are
+// skipped; a value without a key, an invalid key or a key used twice is an error.
+func (s *AppState) EnvironmentVars() (map[string]string, error) {
+	vars := make(map[string]string)
+	for i, pair := range s.EnvVarPairs {
+		key := strings.TrimSpace(pair.Key)
+		value := strings.TrimSpace(pair.Value)
+		if key == "" && value == "" {
+			continue
+		}
+		if err := models.ValidateEnvVarKey(key); err != nil {
+			return nil, fmt.Errorf("ENV [%d]: %w", i+1, err)
+		}
+		if _, ok := vars[key]; ok {
+			return nil, fmt.Errorf("ENV [%d]: %s is already set", i+1, key)
+		}
+		vars[key] = value
+	}
+	return vars, nil
+}
+
+// MergeEnvVars adds imported variables to the form's ENV pairs. A variable whose key is
+// already set replaces that pair's value; others are appended and blank rows are dropped.
+// Returns how many pairs were added and how many were updated.
+func (s *AppState) MergeEnvVars(vars []models.EnvVar) (added, updated int) {
+	pairs := make([]EnvVarPair, 0, len(s.EnvVarPairs)+len(vars))
+	for _, pair := range s.EnvVarPairs {
+		if strings.TrimSpace(pair.Key) != "" || strings.TrimSpace(pair.Value) != "" {
+			pairs = append(pairs, pair)
+		}
+	}
+
+	for _, v := range vars {
+		found := false
+		for i := range pairs {
+			if strings.TrimSpace(pairs[i].Key) == v.Key {
+				pairs[i].Value = v.Value
+				found = true
+			}
+		}
+		if found {
+			updated++
+			continue
+		}
+		pairs = append(pairs, EnvVarPair{Key: v.Key, Value: v.Value})
+		added++
+	}
+
+	if len(pairs) == 0 {
+		pairs = []EnvVarPair{{}}
+	}
+	s.EnvVarPairs = pairs
+	if s.EnvVarFocusedPair >= len(pairs) {
+		s.EnvVarFocusedPair = len(pairs) - 1
+	}
+	return added, updated
+}
+
+// ToggleImageSource switches the site form between a prebuilt image and building from source
+// Fields 3 and 4 change meaning between the two modes, so they are cleared
+func (s *AppState) ToggleImageSource() {
+	if s.ImageSource == "build" {
+		s.ImageSource = "image"
+	} else {
+		s.ImageSource = "build"
+	}
+	if len(s.FormFields) > 4 {
+		s.FormFields[3] = ""
+		s.FormFields[4] = ""
 	}
 }
 
@@ -198,31 +560,109 @@ func (s *AppState) NavigateBack() {
 		targetScreen := s.PreviousScreens[lastIndex]
 		s.PreviousScreens = s.PreviousScreens[:lastIndex]
 
-		// Reset edit form flag when leaving edit screen for non-ENV screen
-		if s.CurrentScreen == ScreenSiteEdit && targetScreen != ScreenSiteEnvVars {
+		// Reset edit form flag when leaving edit screen for a screen outside the site form
+		if s.CurrentScreen == ScreenSiteEdit && !isSiteFormSubscreen(targetScreen) {
 			s.EditFormInitialized = false
 		}
 
+		// Remember the screen left so the forward key can return to it
+		if isForwardable(s.CurrentScreen) {
+			s.NextScreens = append(s.NextScreens, s.CurrentScreen)
+		}
+
 		s.CurrentScreen = targetScreen
+		s.RevealSecrets = false
 	}
 }
 
+// NavigateForward re-enters the screen most recently left with NavigateBack, reporting
+// whether there was one
+func (s *AppState) NavigateForward() bool {
+	if len(s.NextScreens) == 0 {
+		return false
+	}
+	lastIndex := len(s.NextScreens) - 1
+	targetScreen := s.NextScreens[lastIndex]
+	s.NextScreens = s.NextScreens[:lastIndex]
+
+	s.PreviousScreens = append(s.PreviousScreens, s.CurrentScreen)
+	s.CurrentScreen = targetScreen
+	s.RevealSecrets = false
+	return true
+}
+
+// BreadcrumbTrail returns the screens leading to the current one, starting from the most
+// recent top-level screen (one with its own tab, or reached with a global key)
+func (s *AppState) BreadcrumbTrail() []Screen {
+	trail := append(append([]Screen{}, s.PreviousScreens...), s.CurrentScreen)
+	for i := len(trail) - 1; i >= 0; i-- {
+		if isTopLevel(trail[i]) {
+			return trail[i:]
+		}
+	}
+	return trail
+}
+
+// isTopLevel reports whether a screen starts a breadcrumb trail
+func isTopLevel(screen Screen) bool {
+	switch screen {
+	case ScreenDashboard, ScreenSitesList, ScreenDomainsList, ScreenNodesList,
+		ScreenSettings, ScreenHelp, ScreenOperations, ScreenNotifications, ScreenSetup:
+		return true
+	}
+	return false
+}
+
+// isForwardable reports whether a screen can be returned to with the forward key. Forms and
+// the setup wizard can't: leaving a form saves or discards its input, so there's nothing to
+// return to, and the wizard is only shown until it's finished or skipped.
+func isForwardable(screen Screen) bool {
+	switch screen {
+	case ScreenSiteCreate, ScreenSiteEdit, ScreenSiteEnvVars, ScreenSiteConfigFiles,
+		ScreenDomainCreate, ScreenDomainEdit, ScreenDnsRecordForm,
+		ScreenNodeCreate, ScreenNodeEdit, ScreenNodeConfigSave,
+		ScreenSettings, ScreenDeployPreview, ScreenDnsSyncDiff, ScreenSetup:
+		return false
+	}
+	return true
+}
+
 // AddNotification adds a new notification to the queue
 func (s *AppState) AddNotification(message string, level string) {
 	s.Notifications = append(s.Notifications, Notification{
 		Message: message,
 		Level:   level,
+		Time:    time.Now(),
 	})
 
-	// Keep only last 50 notifications
-	if len(s.Notifications) > 50 {
+	// Errors also go to the desktop, in case Archon is in a background window
+	if level == "error" {
+		s.NotifyDesktop(message)
+	}
+
+	// Keep only the most recent notifications
+	if len(s.Notifications) > MaxNotifications {
 		s.Notifications = s.Notifications[1:]
+		if s.restoredNotifications > 0 {
+			s.restoredNotifications--
+		}
+	}
+}
+
+// NotifyDesktop shows a desktop notification if they are turned on and Archon's terminal
+// doesn't have focus. Systems without a desktop are skipped silently.
+func (s *AppState) NotifyDesktop(message string) {
+	if !s.DesktopNotifications || s.Desktop == nil || s.TerminalFocused {
+		return
 	}
+	notifier := s.Desktop
+	go func() { _ = notifier.Notify("Archon", message) }()
 }
 
 // ClearNotifications removes all notifications
 func (s *AppState) ClearNotifications() {
 	s.Notifications = []Notification{}
+	s.restoredNotifications = 0
 }
 
 // GetSiteByID finds a site by its UUID
@@ -235,6 +675,416 @@ func (s *AppState) GetSiteByID(id uuid.UUID) *models.Site {
 	return nil
 }
 
+// AllTags returns every tag used by any site, sorted and without duplicates
+func (s *AppState) AllTags() []string {
+	seen := make(map[string]bool)
+	var tags []string
+	for _, site := range s.Sites {
+		for _, tag := range site.Tags {
+			if !seen[tag] {
+				seen[tag] = true
+				tags = append(tags, tag)
+			}
+		}
+	}
+	sort.Strings(tags)
+	return tags
+}
+
+// SitesWithTag returns all sites labelled with the given tag
+func (s *AppState) SitesWithTag(tag string) []models.Site {
+	var sites []models.Site
+	for _, site := range s.Sites {
+		if site.HasTag(tag) {
+			sites = append(sites, site)
+		}
+	}
+	return sites
+}
+
+// AllNodeTags returns every tag used by any node, sorted and without duplicates
+func (s *AppState) AllNodeTags() []string {
+	seen := make(map[string]bool)
+	var tags []string
+	for _, node := range s.Nodes {
+		for _, tag := range node.Tags {
+			if !seen[tag] {
+				seen[tag] = true
+				tags = append(tags, tag)
+			}
+		}
+	}
+	sort.Strings(tags)
+	return tags
+}
+
+// NodesWithTag returns all nodes labelled with the given tag
+func (s *AppState) NodesWithTag(tag string) []models.Node {
+	var nodes []models.Node
+	for _, node := range s.Nodes {
+		if node.HasTag(tag) {
+			nodes = append(nodes, node)
+		}
+	}
+	return nodes
+}
+
+// VisibleNodes returns the nodes shown in the nodes list and dashboard, honouring the active tag filter
+func (s *AppState) VisibleNodes() []models.Node {
+	if s.NodeTagFilter == "" {
+		return s.Nodes
+	}
+	return s.NodesWithTag(s.NodeTagFilter)
+}
+
+// SelectedVisibleNode returns the node under the nodes list cursor, or nil if the list is empty
+func (s *AppState) SelectedVisibleNode() *models.Node {
+	nodes := s.VisibleNodes()
+	if s.NodesListIndex < 0 || s.NodesListIndex >= len(nodes) {
+		return nil
+	}
+	return &nodes[s.NodesListIndex]
+}
+
+// SitesForDomain returns all sites that reference the domain, either as their
+// primary domain or through any of their domain mappings
+func (s *AppState) SitesForDomain(domainID uuid.UUID) []models.Site {
+	var sites []models.Site
+	for _, site := range s.Sites {
+		if site.DomainID == domainID {
+			sites = append(sites, site)
+			continue
+		}
+		for _, mapping := range site.DomainMappings {
+			if mapping.DomainID == domainID {
+				sites = append(sites, site)
+				break
+			}
+		}
+	}
+	return sites
+}
+
+// SitesForNode returns all sites bound to the node, including those with a replica on it
+func (s *AppState) SitesForNode(nodeID uuid.UUID) []models.Site {
+	var sites []models.Site
+	for _, site := range s.Sites {
+		if site.OnNode(nodeID) {
+			sites = append(sites, site)
+		}
+	}
+	return sites
+}
+
+// SiteNameTaken returns true if a site other than excludeSiteID already uses the name (case-insensitive)
+func (s *AppState) SiteNameTaken(name string, excludeSiteID uuid.UUID) bool {
+	name = strings.TrimSpace(name)
+	for _, site := range s.Sites {
+		if site.ID != excludeSiteID && strings.EqualFold(strings.TrimSpace(site.Name), name) {
+			return true
+		}
+	}
+	return false
+}
+
+// DomainNameTaken returns true if a domain other than excludeDomainID already has the name
+func (s *AppState) DomainNameTaken(name string, excludeDomainID uuid.UUID) bool {
+	for _, domain := range s.Domains {
+		if domain.ID != excludeDomainID && models.SameDomainName(domain.Name, name) {
+			return true
+		}
+	}
+	return false
+}
+
+// FleetTotals sums the Docker and Traefik counts reported by every node
+type FleetTotals struct {
+	ContainersRunning int
+	Images            int
+	Routers           int
+	Services          int
+}
+
+// FleetTotals adds up the last reported Docker and Traefik info across the visible nodes,
+// counting nodes that haven't reported as zero
+func (s *AppState) FleetTotals() FleetTotals {
+	var totals FleetTotals
+	for _, node := range s.VisibleNodes() {
+		if node.DockerInfo != nil {
+			totals.ContainersRunning += node.DockerInfo.ContainersRunning
+			totals.Images += node.DockerInfo.ImagesCount
+		}
+		if node.TraefikInfo != nil {
+			totals.Routers += node.TraefikInfo.RoutersCount
+			totals.Services += node.TraefikInfo.ServicesCount
+		}
+	}
+	return totals
+}
+
+// FailedSites returns the sites whose last deploy failed, in configured order
+func (s *AppState) FailedSites() []models.Site {
+	var failed []models.Site
+	for _, site := range s.Sites {
+		if site.Status == models.SiteStatusFailed {
+			failed = append(failed, site)
+		}
+	}
+	return failed
+}
+
+// DomainHosts returns the hostnames served under a domain by its sites, in site order,
+// or just the domain's own name when no site uses it
+func (s *AppState) DomainHosts(domainID uuid.UUID) []string {
+	domain := s.GetDomainByID(domainID)
+	if domain == nil {
+		return nil
+	}
+
+	var hosts []string
+	seen := make(map[string]bool)
+	for _, site := range s.Sites {
+		for _, mapping := range site.GetDomainMappings() {
+			if mapping.DomainID != domainID {
+				continue
+			}
+			host := strings.ToLower(models.GetFullDomain(domain.Name, mapping.Subdomain))
+			if !seen[host] {
+				seen[host] = true
+				hosts = append(hosts, host)
+			}
+		}
+	}
+	if len(hosts) == 0 {
+		hosts = []string{strings.ToLower(domain.Name)}
+	}
+	return hosts
+}
+
+// PortInUse returns the site on the node that already publishes the host port, or nil if it is free.
+// The site with excludeSiteID is ignored so a site being edited doesn't conflict with itself.
+func (s *AppState) PortInUse(nodeID uuid.UUID, port int, excludeSiteID uuid.UUID) *models.Site {
+	for i := range s.Sites {
+		site := &s.Sites[i]
+		if !site.OnNode(nodeID) || site.ID == excludeSiteID {
+			continue
+		}
+		for _, mapping := range site.GetDomainMappings() {
+			if mapping.GetEffectiveHostPort() == port {
+				return site
+			}
+		}
+	}
+	return nil
+}
+
+// PortRange returns the host ports suggested for new sites, falling back to the defaults
+// when the configured range is unset or invalid
+func (s *AppState) PortRange() (int, int) {
+	start, end := s.PortRangeStart, s.PortRangeEnd
+	if start <= 0 {
+		start = DefaultPortRangeStart
+	}
+	if end <= 0 {
+		end = DefaultPortRangeEnd
+	}
+	if start > end || end > 65535 {
+		return DefaultPortRangeStart, DefaultPortRangeEnd
+	}
+	return start, end
+}
+
+// LatencyThresholds returns the node latencies, in milliseconds, from which the latency is
+// shown as a warning and as critical, falling back to the defaults when unset or invalid
+func (s *AppState) LatencyThresholds() (int64, int64) {
+	warn, critical := s.LatencyWarnMs, s.LatencyCriticalMs
+	if warn <= 0 {
+		warn = DefaultLatencyWarnMs
+	}
+	if critical <= 0 {
+		critical = DefaultLatencyCriticalMs
+	}
+	if warn > critical {
+		return DefaultLatencyWarnMs, DefaultLatencyCriticalMs
+	}
+	return int64(warn), int64(critical)
+}
+
+// NextFreePort returns the lowest host port in PortRange that no site on the node publishes,
+// or 0 if every port in the range is taken
+func (s *AppState) NextFreePort(nodeID uuid.UUID) int {
+	used := make(map[int]bool)
+	for _, site := range s.SitesForNode(nodeID) {
+		for _, mapping := range site.GetDomainMappings() {
+			used[mapping.GetEffectiveHostPort()] = true
+		}
+	}
+
+	start, end := s.PortRange()
+	for port := start; port <= end; port++ {
+		if !used[port] {
+			return port
+		}
+	}
+	return 0
+}
+
+// VisibleSites returns the sites shown in the sites list, honouring the active tag filter,
+// search and sort order
+func (s *AppState) VisibleSites() []models.Site {
+	sites := s.Sites
+	if s.SiteTagFilter != "" {
+		sites = s.SitesWithTag(s.SiteTagFilter)
+	}
+
+	query := strings.ToLower(strings.TrimSpace(s.SiteSearch))
+	if query != "" {
+		var matched []models.Site
+		for i := range sites {
+			if s.siteMatchesSearch(&sites[i], query) {
+				matched = append(matched, sites[i])
+			}
+		}
+		sites = matched
+	}
+
+	if s.SiteSortColumn == SiteSortNone {
+		return sites
+	}
+
+	// Sort a copy so the configured order of s.Sites is kept
+	sorted := append([]models.Site(nil), sites...)
+	sort.SliceStable(sorted, func(i, j int) bool {
+		if s.SiteSortDesc {
+			return s.siteLess(&sorted[j], &sorted[i])
+		}
+		return s.siteLess(&sorted[i], &sorted[j])
+	})
+	return sorted
+}
+
+// SelectedVisibleSite returns the site under the sites list cursor, or nil if the list is empty
+func (s *AppState) SelectedVisibleSite() *models.Site {
+	sites := s.VisibleSites()
+	if s.SitesListIndex < 0 || s.SitesListIndex >= len(sites) {
+		return nil
+	}
+	return &sites[s.SitesListIndex]
+}
+
+// SelectVisibleSite moves the sites list cursor to the site with the given ID, if it is shown
+func (s *AppState) SelectVisibleSite(id uuid.UUID) {
+	for i, site := range s.VisibleSites() {
+		if site.ID == id {
+			s.SitesListIndex = i
+			if s.SitesTable != nil {
+				s.SitesTable.SetCursor(i)
+			}
+			return
+		}
+	}
+}
+
+// CycleSiteSort moves the sites list to the next sort column, keeping the selected site selected
+func (s *AppState) CycleSiteSort() {
+	next := SiteSortNone
+	for i, column := range siteSortColumns {
+		if column == s.SiteSortColumn && i+1 < len(siteSortColumns) {
+			next = siteSortColumns[i+1]
+			break
+		}
+	}
+	s.resortSites(func() {
+		s.SiteSortColumn = next
+		s.SiteSortDesc = false
+	})
+}
+
+// ReverseSiteSort toggles between ascending and descending order, keeping the selected site selected
+func (s *AppState) ReverseSiteSort() {
+	s.resortSites(func() {
+		s.SiteSortDesc = !s.SiteSortDesc
+	})
+}
+
+// resortSites applies a sort change and moves the cursor to wherever the selected site ended up
+func (s *AppState) resortSites(change func()) {
+	var selected uuid.UUID
+	if site := s.SelectedVisibleSite(); site != nil {
+		selected = site.ID
+	}
+	change()
+	s.SelectVisibleSite(selected)
+}
+
+// siteLess compares two sites by the active sort column; ties keep insertion order
+func (s *AppState) siteLess(a, b *models.Site) bool {
+	switch s.SiteSortColumn {
+	case SiteSortName:
+		return strings.ToLower(a.Name) < strings.ToLower(b.Name)
+	case SiteSortStatus:
+		return siteStatusLabel(a) < siteStatusLabel(b)
+	case SiteSortNode:
+		return strings.ToLower(s.siteNodeName(a)) < strings.ToLower(s.siteNodeName(b))
+	case SiteSortPort:
+		return sitePort(a) < sitePort(b)
+	}
+	return false
+}
+
+// siteStatusLabel returns the status shown in the sites list (an unset status is inactive)
+func siteStatusLabel(site *models.Site) string {
+	if site.Status == "" {
+		return string(models.SiteStatusInactive)
+	}
+	return string(site.Status)
+}
+
+// siteNodeName returns the name of the site's node, or empty if the node is unknown
+func (s *AppState) siteNodeName(site *models.Site) string {
+	if node := s.GetNodeByID(site.NodeID); node != nil {
+		return node.Name
+	}
+	return ""
+}
+
+// SiteNodeNames returns the names of the nodes the site runs on, the primary node first.
+// A node that no longer exists is shown by the start of its ID.
+func (s *AppState) SiteNodeNames(site *models.Site) []string {
+	var names []string
+	for _, nodeID := range site.GetNodeIDs() {
+		if node := s.GetNodeByID(nodeID); node != nil {
+			names = append(names, node.Name)
+		} else {
+			names = append(names, nodeID.String()[:8]+"...")
+		}
+	}
+	return names
+}
+
+// sitePort returns the port shown in the sites list (the first mapping's), or 0 without mappings
+func sitePort(site *models.Site) int {
+	if mappings := site.GetDomainMappings(); len(mappings) > 0 {
+		return mappings[0].Port
+	}
+	return 0
+}
+
+// siteMatchesSearch returns true if the site's name, status or any of its domains contains query (lowercase)
+func (s *AppState) siteMatchesSearch(site *models.Site, query string) bool {
+	if strings.Contains(strings.ToLower(site.Name), query) || strings.Contains(siteStatusLabel(site), query) {
+		return true
+	}
+	for _, mapping := range site.GetDomainMappings() {
+		if domain := s.GetDomainByID(mapping.DomainID); domain != nil {
+			if strings.Contains(strings.ToLower(models.GetFullDomain(domain.Name, mapping.Subdomain)), query) {
+				return true
+			}
+		}
+	}
+	return false
+}
+
 // GetDomainByID finds a domain by its UUID
 func (s *AppState) GetDomainByID(id uuid.UUID) *models.Domain {
 	for i := range s.Domains {
@@ -255,26 +1105,398 @@ func (s *AppState) GetNodeByID(id uuid.UUID) *models.Node {
 	return nil
 }
 
+// NodeIDsByName resolves a comma-separated list of node names, as typed in the site form's
+// Node field, to node IDs in the order given. The first node is the site's primary node.
+func (s *AppState) NodeIDsByName(input string) ([]uuid.UUID, error) {
+	var nodeIDs []uuid.UUID
+	for _, name := range models.ParseTags(input) {
+		var node *models.Node
+		for i := range s.Nodes {
+			if s.Nodes[i].Name == name {
+				node = &s.Nodes[i]
+				break
+			}
+		}
+		if node == nil {
+			return nil, fmt.Errorf("node not found: %s", name)
+		}
+		nodeIDs = append(nodeIDs, node.ID)
+	}
+	return nodeIDs, nil
+}
+
+// ToggleFormNode adds the node to the site form's Node field, or removes it if it is already listed
+func (s *AppState) ToggleFormNode(name string) {
+	names := models.ParseTags(s.FormFields[1])
+	for i, listed := range names {
+		if listed == name {
+			s.FormFields[1] = models.FormatTags(append(names[:i], names[i+1:]...))
+			return
+		}
+	}
+	s.FormFields[1] = models.FormatTags(append(names, name))
+}
+
 // AddAsyncOperation adds a new async operation to track
-func (s *AppState) AddAsyncOperation(opType, target string) uuid.UUID {
-	// TODO: Implement async operation tracking
-	// Create new AsyncOperation with UUID, add to slice, return UUID
-	// Example:
-	// id := uuid.New()
-	// op := AsyncOperation{
-	//     ID:     id,
-	//     OpType: opType,
-	//     Status: "pending",
-	//     Target: target,
-	// }
-	// s.PendingOperations = append(s.PendingOperations, op)
-	// return id
-	return uuid.Nil
-}
-
-// CompleteAsyncOperation marks an operation as completed
-func (s *AppState) CompleteAsyncOperation(id uuid.UUID, success bool) {
-	// TODO: Implement operation completion
-	// Find operation by ID and update status to "completed" or "failed"
-	// Optionally remove from slice after completion
+// retry is the message that starts the operation again; cancel, if set, aborts it
+func (s *AppState) AddAsyncOperation(opType, target string, retry tea.Msg, cancel context.CancelFunc) uuid.UUID {
+	id := uuid.New()
+	s.PendingOperations = append(s.PendingOperations, AsyncOperation{
+		ID:     id,
+		OpType: opType,
+		Status: "pending",
+		Target: target,
+		Retry:  retry,
+		Cancel: cancel,
+	})
+	return id
+}
+
+// CompleteAsyncOperation stops tracking a finished operation and returns it
+// It returns false if the operation wasn't being tracked
+func (s *AppState) CompleteAsyncOperation(id uuid.UUID) (AsyncOperation, bool) {
+	for i, op := range s.PendingOperations {
+		if op.ID == id {
+			s.PendingOperations = append(s.PendingOperations[:i], s.PendingOperations[i+1:]...)
+			if op.Cancel != nil {
+				op.Cancel() // Release the operation's context
+			}
+			return op, true
+		}
+	}
+	return AsyncOperation{}, false
+}
+
+// RecordFailedOperation keeps a finished operation that failed so it can be retried
+// Only the MaxFailedOperations most recent failures are kept
+func (s *AppState) RecordFailedOperation(op AsyncOperation, reason string) {
+	op.Status = "failed"
+	op.Error = reason
+	op.Cancel = nil
+	s.FailedOperations = append(s.FailedOperations, op)
+	if len(s.FailedOperations) > MaxFailedOperations {
+		s.FailedOperations = s.FailedOperations[len(s.FailedOperations)-MaxFailedOperations:]
+	}
+}
+
+// TakeFailedOperation removes a failed operation from the list and returns it for retrying
+func (s *AppState) TakeFailedOperation(id uuid.UUID) (AsyncOperation, bool) {
+	for i, op := range s.FailedOperations {
+		if op.ID == id {
+			s.FailedOperations = append(s.FailedOperations[:i], s.FailedOperations[i+1:]...)
+			return op, true
+		}
+	}
+	return AsyncOperation{}, false
+}
+
+// Operations lists the operations screen's rows: pending operations oldest first,
+// then failed ones newest first
+func (s *AppState) Operations() []AsyncOperation {
+	ops := append([]AsyncOperation(nil), s.PendingOperations...)
+	for i := len(s.FailedOperations) - 1; i >= 0; i-- {
+		ops = append(ops, s.FailedOperations[i])
+	}
+	return ops
+}
+
+// ClampOperationsIndex keeps the operations screen selection on a row after rows are removed
+func (s *AppState) ClampOperationsIndex() {
+	if last := len(s.PendingOperations) + len(s.FailedOperations) - 1; s.OperationsIndex > last {
+		s.OperationsIndex = max(last, 0)
+	}
+}
+
+// GetAsyncOperation finds a tracked operation by its UUID
+func (s *AppState) GetAsyncOperation(id uuid.UUID) *AsyncOperation {
+	for i := range s.PendingOperations {
+		if s.PendingOperations[i].ID == id {
+			return &s.PendingOperations[i]
+		}
+	}
+	return nil
+}
+
+// CancelAsyncOperation aborts a pending operation and marks it failed as cancelled
+// It stays listed until its command returns. Returns false if it isn't pending.
+func (s *AppState) CancelAsyncOperation(id uuid.UUID) bool {
+	for i := range s.PendingOperations {
+		op := &s.PendingOperations[i]
+		if op.ID != id || op.Status != "pending" {
+			continue
+		}
+		if op.Cancel != nil {
+			op.Cancel()
+		}
+		op.Status = "failed"
+		op.Error = "cancelled"
+		return true
+	}
+	return false
+}
+
+// AppendLogs merges freshly fetched log lines into a site's buffer
+// Each fetch returns the latest tail of the container log, which usually overlaps the
+// end of the buffer, so only lines after the overlap are appended. The buffer is then
+// capped at LogBufferLines so repeated fetches don't grow it unbounded
+func (s *AppState) AppendLogs(siteID uuid.UUID, lines []string) {
+	s.setLogs(siteID, mergeLogs(s.SiteLogs[siteID], lines))
+}
+
+// AppendNewLogs appends log lines fetched after the site's log cursor, which never overlap
+// the buffer, and stores the cursor to fetch from next time
+func (s *AppState) AppendNewLogs(siteID uuid.UUID, lines []string, cursor string) {
+	s.setLogs(siteID, append(append([]string{}, s.SiteLogs[siteID]...), lines...))
+	s.SetLogCursor(siteID, cursor)
+}
+
+// SetLogCursor stores the cursor after a site's last fetched log line ("" to fetch the tail again)
+func (s *AppState) SetLogCursor(siteID uuid.UUID, cursor string) {
+	if s.LogCursors == nil {
+		s.LogCursors = make(map[uuid.UUID]string)
+	}
+	s.LogCursors[siteID] = cursor
+}
+
+// LogCursor returns the cursor after a site's last fetched log line, or "" if there is none
+func (s *AppState) LogCursor(siteID uuid.UUID) string {
+	return s.LogCursors[siteID]
+}
+
+// setLogs stores a site's log buffer, keeping only the last LogBufferLines lines
+func (s *AppState) setLogs(siteID uuid.UUID, lines []string) {
+	if s.SiteLogs == nil {
+		s.SiteLogs = make(map[uuid.UUID][]string)
+	}
+
+	limit := s.LogBufferLines
+	if limit <= 0 {
+		limit = DefaultLogBufferLines
+	}
+	if len(lines) > limit {
+		lines = append([]string(nil), lines[len(lines)-limit:]...)
+	}
+
+	s.SiteLogs[siteID] = lines
+}
+
+// GetLogs returns the buffered log lines for a site and whether logs have been fetched
+func (s *AppState) GetLogs(siteID uuid.UUID) ([]string, bool) {
+	logs, ok := s.SiteLogs[siteID]
+	return logs, ok
+}
+
+// SetContainerStatus stores the status last fetched from a site's node
+func (s *AppState) SetContainerStatus(siteID uuid.UUID, status *api.SiteStatusResponse) {
+	if s.SiteContainers == nil {
+		s.SiteContainers = make(map[uuid.UUID]*api.SiteStatusResponse)
+	}
+	s.SiteContainers[siteID] = status
+}
+
+// GetContainerStatus returns the status last fetched from a site's node, or nil if it hasn't been
+func (s *AppState) GetContainerStatus(siteID uuid.UUID) *api.SiteStatusResponse {
+	return s.SiteContainers[siteID]
+}
+
+// MaxAutoRestarts is how many times a failed site is restarted automatically before Archon gives up,
+// so a site that fails straight after starting isn't restarted forever
+const MaxAutoRestarts = 3
+
+// CountAutoRestart counts an automatic restart of a site and returns how many there have been,
+// including this one. Attempts past MaxAutoRestarts should not be made.
+func (s *AppState) CountAutoRestart(siteID uuid.UUID) int {
+	if s.AutoRestarts == nil {
+		s.AutoRestarts = make(map[uuid.UUID]int)
+	}
+	s.AutoRestarts[siteID]++
+	return s.AutoRestarts[siteID]
+}
+
+// ResetAutoRestarts allows a site its automatic restarts again, after it is deployed or restarted by hand
+func (s *AppState) ResetAutoRestarts(siteID uuid.UUID) {
+	delete(s.AutoRestarts, siteID)
+}
+
+// SetSiteHealth stores the result of a site's health probe
+func (s *AppState) SetSiteHealth(siteID uuid.UUID, health models.SiteHealth) {
+	if s.SiteHealth == nil {
+		s.SiteHealth = make(map[uuid.UUID]models.SiteHealth)
+	}
+	s.SiteHealth[siteID] = health
+}
+
+// GetSiteHealth returns the result of a site's last health probe, or unknown if the site has no
+// health path or hasn't been probed
+func (s *AppState) GetSiteHealth(siteID uuid.UUID) models.SiteHealth {
+	site := s.GetSiteByID(siteID)
+	health, ok := s.SiteHealth[siteID]
+	if site == nil || site.HealthPath == "" || !ok {
+		return models.SiteHealthUnknown
+	}
+	return health
+}
+
+// SiteHealthURL returns the URL probed for a site's health on its first domain mapping, or ""
+// if the site has no health path or domain
+func (s *AppState) SiteHealthURL(siteID uuid.UUID) string {
+	site := s.GetSiteByID(siteID)
+	if site == nil {
+		return ""
+	}
+	mappings := site.GetDomainMappings()
+	if len(mappings) == 0 {
+		return ""
+	}
+	domain := s.GetDomainByID(mappings[0].DomainID)
+	if domain == nil {
+		return ""
+	}
+	return site.HealthURL(models.GetFullDomain(domain.Name, mappings[0].Subdomain))
+}
+
+// SetMetrics stores the latest metrics for a site (nil when they could not be fetched)
+// and adds them to the site's history, keeping the last MaxMetricsSamples snapshots
+func (s *AppState) SetMetrics(siteID uuid.UUID, metrics *api.ContainerMetrics) {
+	if s.SiteMetrics == nil {
+		s.SiteMetrics = make(map[uuid.UUID]*api.ContainerMetrics)
+	}
+	s.SiteMetrics[siteID] = metrics
+
+	if metrics == nil {
+		return
+	}
+	if s.SiteMetricsHistory == nil {
+		s.SiteMetricsHistory = make(map[uuid.UUID][]MetricsSample)
+	}
+	history := append(s.SiteMetricsHistory[siteID], MetricsSample{At: time.Now(), Metrics: *metrics})
+	if len(history) > MaxMetricsSamples {
+		history = append([]MetricsSample(nil), history[len(history)-MaxMetricsSamples:]...)
+	}
+	s.SiteMetricsHistory[siteID] = history
+}
+
+// GetMetricsHistory returns a site's recent metrics snapshots, oldest first
+func (s *AppState) GetMetricsHistory(siteID uuid.UUID) []MetricsSample {
+	return s.SiteMetricsHistory[siteID]
+}
+
+// ClearMetricsHistory drops every site's metrics history
+func (s *AppState) ClearMetricsHistory() {
+	s.SiteMetricsHistory = nil
+}
+
+// GetMetrics returns the latest metrics for a site and whether metrics have been fetched
+func (s *AppState) GetMetrics(siteID uuid.UUID) (*api.ContainerMetrics, bool) {
+	metrics, ok := s.SiteMetrics[siteID]
+	return metrics, ok
+}
+
+// SetPropagation stores the results of checking a domain's records against public resolvers
+func (s *AppState) SetPropagation(domainID uuid.UUID, results []dns.PropagationResult) {
+	if s.DnsPropagation == nil {
+		s.DnsPropagation = make(map[uuid.UUID][]dns.PropagationResult)
+	}
+	s.DnsPropagation[domainID] = results
+}
+
+// GetPropagation returns the last propagation check of a record. Results are matched on the
+// record's type, name and value, so a record edited since the check has no result.
+func (s *AppState) GetPropagation(domainID uuid.UUID, record models.DnsRecord) (dns.PropagationResult, bool) {
+	for _, result := range s.DnsPropagation[domainID] {
+		checked := result.Record
+		if checked.RecordType == record.RecordType && checked.Name == record.Name && checked.Value == record.Value {
+			return result, true
+		}
+	}
+	return dns.PropagationResult{}, false
+}
+
+// StartReconnect starts retrying an offline node, returning the delay before the first
+// attempt, or false if the node is already being retried
+func (s *AppState) StartReconnect(nodeID uuid.UUID) (time.Duration, bool) {
+	if _, ok := s.NodeReconnects[nodeID]; ok {
+		return 0, false
+	}
+	if s.NodeReconnects == nil {
+		s.NodeReconnects = make(map[uuid.UUID]int)
+	}
+	s.NodeReconnects[nodeID] = 0
+	return ReconnectBaseDelay, true
+}
+
+// NextReconnectDelay records a failed attempt to reach a node and returns the delay before
+// the next one
+func (s *AppState) NextReconnectDelay(nodeID uuid.UUID) time.Duration {
+	if s.NodeReconnects == nil {
+		s.NodeReconnects = make(map[uuid.UUID]int)
+	}
+	s.NodeReconnects[nodeID]++
+
+	delay := ReconnectBaseDelay
+	for i := 0; i < s.NodeReconnects[nodeID] && delay < ReconnectMaxDelay; i++ {
+		delay *= 2
+	}
+	if delay > ReconnectMaxDelay {
+		delay = ReconnectMaxDelay
+	}
+	return delay
+}
+
+// StopReconnect stops retrying a node, returning how many attempts failed, or false if
+// the node wasn't being retried
+func (s *AppState) StopReconnect(nodeID uuid.UUID) (int, bool) {
+	attempts, ok := s.NodeReconnects[nodeID]
+	delete(s.NodeReconnects, nodeID)
+	return attempts, ok
+}
+
+// mergeLogs appends the lines of fetched that come after its overlap with the end of existing
+func mergeLogs(existing, fetched []string) []string {
+	merged := append([]string{}, existing...)
+
+	// Find the longest suffix of existing that is a prefix of fetched
+	maxOverlap := len(existing)
+	if len(fetched) < maxOverlap {
+		maxOverlap = len(fetched)
+	}
+	for overlap := maxOverlap; overlap > 0; overlap-- {
+		if equalLines(existing[len(existing)-overlap:], fetched[:overlap]) {
+			return append(merged, fetched[overlap:]...)
+		}
+	}
+
+	return append(merged, fetched...)
+}
+
+// equalLines returns true if both slices hold the same lines
+func equalLines(a, b []string) bool {
+	if len(a) != len(b) {
+		return false
+	}
+	for i := range a {
+		if a[i] != b[i] {
+			return false
+		}
+	}
+	return true
+}
+
+// RememberDeletedSite adds a deleted site to the undo buffer, dropping the oldest once it is full
+func (s *AppState) RememberDeletedSite(site models.Site) {
+	s.RecentlyDeletedSites = append(s.RecentlyDeletedSites, site)
+	if len(s.RecentlyDeletedSites) > MaxRecentlyDeletedSites {
+		s.RecentlyDeletedSites = s.RecentlyDeletedSites[len(s.RecentlyDeletedSites)-MaxRecentlyDeletedSites:]
+	}
+}
+
+// TakeDeletedSite removes a site from the undo buffer and returns it
+func (s *AppState) TakeDeletedSite(siteID uuid.UUID) (models.Site, bool) {
+	for i, site := range s.RecentlyDeletedSites {
+		if site.ID == siteID {
+			s.RecentlyDeletedSites = append(s.RecentlyDeletedSites[:i], s.RecentlyDeletedSites[i+1:]...)
+			return site, true
+		}
+	}
+	return models.Site{}, false
 }
@@ -0,0 +1,1028 @@
+package state
+
+import (
+	"fmt"
+	"path/filepath"
+	"reflect"
+	"strings"
+	"testing"
+	"time"
+
+	"github.com/google/uuid"
+
+	"github.com/BlueBeard63/archon/internal/api"
+	"github.com/BlueBeard63/archon/internal/models"
+)
+
+func TestAppendLogs(t *testing.T) {
+	tests := []struct {
+		name     string
+		limit    int
+		existing []string
+		fetches  [][]string
+		want     []string
+	}{
+		{
+			name:    "first fetch is stored as-is",
+			fetches: [][]string{{"a", "b", "c"}},
+			want:    []string{"a", "b", "c"},
+		},
+		{
+			name:    "overlapping tail only appends new lines",
+			fetches: [][]string{{"a", "b", "c"}, {"b", "c", "d", "e"}},
+			want:    []string{"a", "b", "c", "d", "e"},
+		},
+		{
+			name:    "identical fetch adds nothing",
+			fetches: [][]string{{"a", "b", "c"}, {"a", "b", "c"}},
+			want:    []string{"a", "b", "c"},
+		},
+		{
+			name:    "fetch without overlap is appended",
+			fetches: [][]string{{"a", "b"}, {"x", "y"}},
+			want:    []string{"a", "b", "x", "y"},
+		},
+		{
+			name:    "repeated lines use the longest overlap",
+			fetches: [][]string{{"ping", "ping"}, {"ping", "ping", "pong"}},
+			want:    []string{"ping", "ping", "pong"},
+		},
+		{
+			name:    "buffer is capped at the configured limit",
+			limit:   3,
+			fetches: [][]string{{"a", "b", "c"}, {"c", "d", "e"}},
+			want:    []string{"c", "d", "e"},
+		},
+		{
+			name:    "empty fetch marks logs as fetched",
+			fetches: [][]string{nil},
+			want:    []string{},
+		},
+	}
+
+	for _, tt := range tests {
+		t.Run(tt.name, func(t *testing.T) {
+			s := NewAppState()
+			s.LogBufferLines = tt.limit
+			siteID := uuid.New()
+
+			for _, fetch := range tt.fetches {
+				s.AppendLogs(siteID, fetch)
+			}
+
+			got, ok := s.GetLogs(siteID)
+			if !ok {
+				t.Fatal("GetLogs reported no logs after a fetch")
+			}
+			if !reflect.DeepEqual(got, tt.want) {
+				t.Errorf("logs = %q, want %q", got, tt.want)
+			}
+		})
+	}
+}
+
+func TestAppendLogsDefaultLimit(t *testing.T) {
+	s := NewAppState()
+	siteID := uuid.New()
+
+	// Fetch more than the default limit in distinct batches
+	for batch := 0; batch < 3; batch++ {
+		lines := make([]string, DefaultLogBufferLines/2+1)
+		for i := range lines {
+			lines[i] = fmt.Sprintf("batch %d line %d", batch, i)
+		}
+		s.AppendLogs(siteID, lines)
+	}
+
+	got, _ := s.GetLogs(siteID)
+	if len(got) != DefaultLogBufferLines {
+		t.Fatalf("len(logs) = %d, want %d", len(got), DefaultLogBufferLines)
+	}
+	if last := got[len(got)-1]; last != fmt.Sprintf("batch 2 line %d", DefaultLogBufferLines/2) {
+		t.Errorf("last line = %q, want the newest line", last)
+	}
+}
+
+func TestAppendNewLogs(t *testing.T) {
+	s := NewAppState()
+	siteID := uuid.New()
+
+	s.AppendLogs(siteID, []string{"GET / 200", "GET / 200"})
+	// Lines after a cursor are new even when they repeat the end of the buffer
+	s.AppendNewLogs(siteID, []string{"GET / 200"}, "c2")
+
+	got, _ := s.GetLogs(siteID)
+	if len(got) != 3 {
+		t.Errorf("logs = %q, want 3 lines", got)
+	}
+	if cursor := s.LogCursor(siteID); cursor != "c2" {
+		t.Errorf("LogCursor = %q, want %q", cursor, "c2")
+	}
+}
+
+func TestRecentlyDeletedSites(t *testing.T) {
+	s := NewAppState()
+
+	var ids []uuid.UUID
+	for i := 0; i < MaxRecentlyDeletedSites+2; i++ {
+		site := models.Site{ID: uuid.New(), Name: fmt.Sprintf("site-%d", i)}
+		ids = append(ids, site.ID)
+		s.RememberDeletedSite(site)
+	}
+
+	if len(s.RecentlyDeletedSites) != MaxRecentlyDeletedSites {
+		t.Fatalf("len(RecentlyDeletedSites) = %d, want %d", len(s.RecentlyDeletedSites), MaxRecentlyDeletedSites)
+	}
+
+	// The two oldest sites were dropped
+	if _, ok := s.TakeDeletedSite(ids[0]); ok {
+		t.Error("oldest site should have been dropped from the undo buffer")
+	}
+
+	newest := ids[len(ids)-1]
+	site, ok := s.TakeDeletedSite(newest)
+	if !ok || site.ID != newest {
+		t.Fatalf("TakeDeletedSite(newest) = %v, %v", site.ID, ok)
+	}
+	if _, ok := s.TakeDeletedSite(newest); ok {
+		t.Error("a taken site should no longer be restorable")
+	}
+	if len(s.RecentlyDeletedSites) != MaxRecentlyDeletedSites-1 {
+		t.Errorf("len(RecentlyDeletedSites) = %d, want %d", len(s.RecentlyDeletedSites), MaxRecentlyDeletedSites-1)
+	}
+}
+
+func TestPortInUse(t *testing.T) {
+	nodeA, nodeB, nodeC := uuid.New(), uuid.New(), uuid.New()
+	web := models.Site{ID: uuid.New(), Name: "web", NodeID: nodeA, DomainMappings: []models.DomainMapping{
+		{DomainID: uuid.New(), Port: 8080},
+		{DomainID: uuid.New(), Port: 3000, HostPort: 3001},
+	}}
+	legacy := models.Site{ID: uuid.New(), Name: "legacy", NodeID: nodeA, DomainID: uuid.New(), Port: 9000}
+	replicated := models.Site{ID: uuid.New(), Name: "replicated", NodeID: nodeB, NodeIDs: []uuid.UUID{nodeB, nodeC}, DomainMappings: []models.DomainMapping{
+		{DomainID: uuid.New(), Port: 5000},
+	}}
+
+	s := NewAppState()
+	s.Sites = []models.Site{web, legacy, replicated}
+
+	tests := []struct {
+		name    string
+		nodeID  uuid.UUID
+		port    int
+		exclude uuid.UUID
+		want    string // Name of the conflicting site, empty when free
+	}{
+		{name: "port used on the same node", nodeID: nodeA, port: 8080, want: "web"},
+		{name: "host port override is checked", nodeID: nodeA, port: 3001, want: "web"},
+		{name: "container port behind a host port is free", nodeID: nodeA, port: 3000},
+		{name: "legacy single port is checked", nodeID: nodeA, port: 9000, want: "legacy"},
+		{name: "same port on another node is free", nodeID: nodeB, port: 8080},
+		{name: "port used by a replica", nodeID: nodeC, port: 5000, want: "replicated"},
+		{name: "excluded site does not conflict with itself", nodeID: nodeA, port: 8080, exclude: web.ID},
+	}
+
+	for _, tt := range tests {
+		t.Run(tt.name, func(t *testing.T) {
+			got := ""
+			if site := s.PortInUse(tt.nodeID, tt.port, tt.exclude); site != nil {
+				got = site.Name
+			}
+			if got != tt.want {
+				t.Errorf("PortInUse() = %q, want %q", got, tt.want)
+			}
+		})
+	}
+}
+
+func TestNextFreePort(t *testing.T) {
+	nodeA, nodeB := uuid.New(), uuid.New()
+	s := NewAppState()
+	s.Sites = []models.Site{
+		{ID: uuid.New(), NodeID: nodeA, DomainMappings: []models.DomainMapping{
+			{DomainID: uuid.New(), Port: 8000},
+			{DomainID: uuid.New(), Port: 3000, HostPort: 8001},
+		}},
+		{ID: uuid.New(), NodeID: nodeA, DomainID: uuid.New(), Port: 8003},
+	}
+
+	tests := []struct {
+		name       string
+		nodeID     uuid.UUID
+		start, end int
+		want       int
+	}{
+		{name: "lowest gap on the node", nodeID: nodeA, want: 8002},
+		{name: "other nodes don't count", nodeID: nodeB, want: 8000},
+		{name: "configured range", nodeID: nodeA, start: 8003, end: 8010, want: 8004},
+		{name: "range exhausted", nodeID: nodeA, start: 8000, end: 8001, want: 0},
+		{name: "invalid range uses the default", nodeID: nodeA, start: 9000, end: 8000, want: 8002},
+	}
+
+	for _, tt := range tests {
+		t.Run(tt.name, func(t *testing.T) {
+			s.PortRangeStart, s.PortRangeEnd = tt.start, tt.end
+			if got := s.NextFreePort(tt.nodeID); got != tt.want {
+				t.Errorf("NextFreePort() = %d, want %d", got, tt.want)
+			}
+		})
+	}
+}
+
+func TestLatencyThresholds(t *testing.T) {
+	tests := []struct {
+		name               string
+		warn, critical     int
+		wantWarn, wantCrit int64
+	}{
+		{name: "defaults", wantWarn: DefaultLatencyWarnMs, wantCrit: DefaultLatencyCriticalMs},
+		{name: "configured", warn: 50, critical: 200, wantWarn: 50, wantCrit: 200},
+		{name: "only critical configured", critical: 1000, wantWarn: DefaultLatencyWarnMs, wantCrit: 1000},
+		{name: "warn above critical uses the defaults", warn: 800, critical: 200, wantWarn: DefaultLatencyWarnMs, wantCrit: DefaultLatencyCriticalMs},
+	}
+
+	for _, tt := range tests {
+		t.Run(tt.name, func(t *testing.T) {
+			s := NewAppState()
+			s.LatencyWarnMs, s.LatencyCriticalMs = tt.warn, tt.critical
+			warn, critical := s.LatencyThresholds()
+			if warn != tt.wantWarn || critical != tt.wantCrit {
+				t.Errorf("LatencyThresholds() = %d, %d, want %d, %d", warn, critical, tt.wantWarn, tt.wantCrit)
+			}
+		})
+	}
+}
+
+func TestNodeReconnect(t *testing.T) {
+	s := NewAppState()
+	nodeID := uuid.New()
+
+	if delay, ok := s.StartReconnect(nodeID); !ok || delay != ReconnectBaseDelay {
+		t.Fatalf("StartReconnect() = %s, %v, want %s, true", delay, ok, ReconnectBaseDelay)
+	}
+	if _, ok := s.StartReconnect(nodeID); ok {
+		t.Error("StartReconnect() started a second loop for the same node")
+	}
+
+	// The delay doubles after each failed attempt until it reaches the cap
+	want := []time.Duration{10 * time.Second, 20 * time.Second, 40 * time.Second, 80 * time.Second, ReconnectMaxDelay, ReconnectMaxDelay}
+	for i, w := range want {
+		if got := s.NextReconnectDelay(nodeID); got != w {
+			t.Errorf("NextReconnectDelay() after %d failures = %s, want %s", i+1, got, w)
+		}
+	}
+
+	if attempts, ok := s.StopReconnect(nodeID); !ok || attempts != len(want) {
+		t.Errorf("StopReconnect() = %d, %v, want %d, true", attempts, ok, len(want))
+	}
+	if _, ok := s.StopReconnect(nodeID); ok {
+		t.Error("StopReconnect() = true for a node that isn't being retried")
+	}
+}
+
+func TestNameTaken(t *testing.T) {
+	site := models.Site{ID: uuid.New(), Name: "Blog"}
+	domain := models.Domain{ID: uuid.New(), Name: "example.com"}
+
+	s := NewAppState()
+	s.Sites = []models.Site{site}
+	s.Domains = []models.Domain{domain}
+
+	siteTests := []struct {
+		name    string
+		input   string
+		exclude uuid.UUID
+		want    bool
+	}{
+		{name: "exact site name", input: "Blog", want: true},
+		{name: "site name differing in case", input: "blog", want: true},
+		{name: "different site name", input: "shop", want: false},
+		{name: "site keeps its own name", input: "Blog", exclude: site.ID, want: false},
+	}
+	for _, tt := range siteTests {
+		t.Run(tt.name, func(t *testing.T) {
+			if got := s.SiteNameTaken(tt.input, tt.exclude); got != tt.want {
+				t.Errorf("SiteNameTaken(%q) = %v, want %v", tt.input, got, tt.want)
+			}
+		})
+	}
+
+	domainTests := []struct {
+		name    string
+		input   string
+		exclude uuid.UUID
+		want    bool
+	}{
+		{name: "exact domain name", input: "example.com", want: true},
+		{name: "domain name differing in case", input: "Example.COM", want: true},
+		{name: "domain name with trailing dot", input: "example.com.", want: true},
+		{name: "subdomain is a different domain", input: "www.example.com", want: false},
+		{name: "domain keeps its own name", input: "EXAMPLE.com.", exclude: domain.ID, want: false},
+	}
+	for _, tt := range domainTests {
+		t.Run(tt.name, func(t *testing.T) {
+			if got := s.DomainNameTaken(tt.input, tt.exclude); got != tt.want {
+				t.Errorf("DomainNameTaken(%q) = %v, want %v", tt.input, got, tt.want)
+			}
+		})
+	}
+}
+
+func TestFuzzyScore(t *testing.T) {
+	tests := []struct {
+		name  string
+		query string
+		text  string
+		match bool
+	}{
+		{name: "empty query matches", query: "", text: "blog", match: true},
+		{name: "subsequence matches", query: "bg", text: "blog", match: true},
+		{name: "case-insensitive", query: "BLOG", text: "my-blog", match: true},
+		{name: "out of order does not match", query: "gb", text: "blog", match: false},
+		{name: "missing character does not match", query: "blox", text: "blog", match: false},
+	}
+
+	for _, tt := range tests {
+		t.Run(tt.name, func(t *testing.T) {
+			if _, ok := FuzzyScore(tt.query, tt.text); ok != tt.match {
+				t.Errorf("FuzzyScore(%q, %q) matched = %v, want %v", tt.query, tt.text, ok, tt.match)
+			}
+		})
+	}
+}
+
+func TestPaletteMatchesRanking(t *testing.T) {
+	s := NewAppState()
+	s.Sites = []models.Site{
+		{ID: uuid.New(), Name: "shop-api"},
+		{ID: uuid.New(), Name: "api"},
+		{ID: uuid.New(), Name: "a-p-i-legacy"},
+	}
+	s.Nodes = []models.Node{{ID: uuid.New(), Name: "web-1"}}
+	s.Palette = &Palette{Query: "api"}
+
+	var got []string
+	for _, entry := range s.PaletteMatches() {
+		got = append(got, entry.Name)
+	}
+	// Exact names beat longer names, and consecutive runs beat scattered matches
+	want := []string{"api", "shop-api", "a-p-i-legacy"}
+	if !reflect.DeepEqual(got, want) {
+		t.Errorf("PaletteMatches() = %v, want %v", got, want)
+	}
+}
+
+func TestVisibleSitesSearch(t *testing.T) {
+	domain := models.Domain{ID: uuid.New(), Name: "example.com"}
+	blog := models.Site{ID: uuid.New(), Name: "Blog", Status: models.SiteStatusRunning, Tags: []string{"prod"},
+		DomainMappings: []models.DomainMapping{{DomainID: domain.ID, Subdomain: "www", Port: 80}}}
+	shop := models.Site{ID: uuid.New(), Name: "Shop", Status: models.SiteStatusFailed, Tags: []string{"staging"}}
+	api := models.Site{ID: uuid.New(), Name: "API", Tags: []string{"prod"}}
+
+	s := NewAppState()
+	s.Domains = []models.Domain{domain}
+	s.Sites = []models.Site{blog, shop, api}
+
+	tests := []struct {
+		name   string
+		tag    string
+		search string
+		want   []string
+	}{
+		{name: "no search lists everything", want: []string{"Blog", "Shop", "API"}},
+		{name: "name substring ignores case", search: "BL", want: []string{"Blog"}},
+		{name: "full domain", search: "www.example", want: []string{"Blog"}},
+		{name: "status", search: "failed", want: []string{"Shop"}},
+		{name: "unset status counts as inactive", search: "inactive", want: []string{"API"}},
+		{name: "combined with tag filter", tag: "prod", search: "api", want: []string{"API"}},
+		{name: "no matches", search: "nothing", want: nil},
+	}
+
+	for _, tt := range tests {
+		t.Run(tt.name, func(t *testing.T) {
+			s.SiteTagFilter = tt.tag
+			s.SiteSearch = tt.search
+
+			var got []string
+			for _, site := range s.VisibleSites() {
+				got = append(got, site.Name)
+			}
+			if !reflect.DeepEqual(got, tt.want) {
+				t.Errorf("VisibleSites() = %v, want %v", got, tt.want)
+			}
+		})
+	}
+}
+
+func TestVisibleSitesSort(t *testing.T) {
+	alpha, beta := models.Node{ID: uuid.New(), Name: "alpha"}, models.Node{ID: uuid.New(), Name: "beta"}
+	web := models.Site{ID: uuid.New(), Name: "web", Status: models.SiteStatusRunning, NodeID: beta.ID,
+		DomainMappings: []models.DomainMapping{{DomainID: uuid.New(), Port: 8080}}}
+	api := models.Site{ID: uuid.New(), Name: "API", Status: models.SiteStatusFailed, NodeID: alpha.ID,
+		DomainMappings: []models.DomainMapping{{DomainID: uuid.New(), Port: 3000}}}
+	docs := models.Site{ID: uuid.New(), Name: "docs", NodeID: beta.ID, DomainID: uuid.New(), Port: 9000}
+
+	s := NewAppState()
+	s.Nodes = []models.Node{alpha, beta}
+	s.Sites = []models.Site{web, api, docs}
+
+	tests := []struct {
+		name   string
+		column SiteSortColumn
+		desc   bool
+		want   []string
+	}{
+		{name: "insertion order", want: []string{"web", "API", "docs"}},
+		{name: "name ignores case", column: SiteSortName, want: []string{"API", "docs", "web"}},
+		{name: "name descending", column: SiteSortName, desc: true, want: []string{"web", "docs", "API"}},
+		{name: "status", column: SiteSortStatus, want: []string{"API", "docs", "web"}},
+		{name: "node keeps insertion order for ties", column: SiteSortNode, want: []string{"API", "web", "docs"}},
+		{name: "node descending keeps insertion order for ties", column: SiteSortNode, desc: true, want: []string{"web", "docs", "API"}},
+		{name: "port", column: SiteSortPort, want: []string{"API", "web", "docs"}},
+	}
+
+	for _, tt := range tests {
+		t.Run(tt.name, func(t *testing.T) {
+			s.SiteSortColumn = tt.column
+			s.SiteSortDesc = tt.desc
+
+			var got []string
+			for _, site := range s.VisibleSites() {
+				got = append(got, site.Name)
+			}
+			if !reflect.DeepEqual(got, tt.want) {
+				t.Errorf("VisibleSites() = %v, want %v", got, tt.want)
+			}
+			if s.Sites[0].ID != web.ID {
+				t.Errorf("sorting reordered the configured sites")
+			}
+		})
+	}
+
+	t.Run("selection follows the site across a re-sort", func(t *testing.T) {
+		s.SiteSortColumn = SiteSortNone
+		s.SiteSortDesc = false
+		s.SitesListIndex = 2 // docs
+
+		s.CycleSiteSort() // name: API, docs, web
+		if s.SiteSortColumn != SiteSortName || s.SitesListIndex != 1 {
+			t.Errorf("after sorting by name: column %q, index %d; want name, 1", s.SiteSortColumn, s.SitesListIndex)
+		}
+		s.ReverseSiteSort() // web, docs, API
+		if site := s.SelectedVisibleSite(); site == nil || site.ID != docs.ID {
+			t.Errorf("selected site after reversing = %v, want docs", site)
+		}
+	})
+}
+
+func TestSetMetricsHistory(t *testing.T) {
+	siteID := uuid.New()
+	s := NewAppState()
+
+	for i := 0; i < MaxMetricsSamples+5; i++ {
+		s.SetMetrics(siteID, &api.ContainerMetrics{CPUPercent: float64(i)})
+	}
+	s.SetMetrics(siteID, nil) // A failed fetch is not a sample
+
+	history := s.GetMetricsHistory(siteID)
+	if len(history) != MaxMetricsSamples {
+		t.Fatalf("history length = %d, want %d", len(history), MaxMetricsSamples)
+	}
+	if first, last := history[0].Metrics.CPUPercent, history[len(history)-1].Metrics.CPUPercent; first != 5 || last != MaxMetricsSamples+4 {
+		t.Errorf("history spans CPU %v..%v, want the newest %d samples", first, last, MaxMetricsSamples)
+	}
+	if metrics, _ := s.GetMetrics(siteID); metrics != nil {
+		t.Errorf("latest metrics = %v, want nil after a failed fetch", metrics)
+	}
+
+	s.ClearMetricsHistory()
+	if history := s.GetMetricsHistory(siteID); len(history) != 0 {
+		t.Errorf("history after clear has %d samples, want 0", len(history))
+	}
+}
+
+func TestDomainHosts(t *testing.T) {
+	used := models.Domain{ID: uuid.New(), Name: "Example.com"}
+	unused := models.Domain{ID: uuid.New(), Name: "spare.org"}
+
+	s := NewAppState()
+	s.Domains = []models.Domain{used, unused}
+	s.Sites = []models.Site{
+		{ID: uuid.New(), Name: "web", DomainMappings: []models.DomainMapping{
+			{DomainID: used.ID, Subdomain: "www", Port: 80},
+			{DomainID: used.ID, Port: 80},
+		}},
+		{ID: uuid.New(), Name: "mirror", DomainMappings: []models.DomainMapping{{DomainID: used.ID, Subdomain: "WWW", Port: 8080}}},
+		{ID: uuid.New(), Name: "api", DomainMappings: []models.DomainMapping{{DomainID: used.ID, Subdomain: "api", Port: 3000}}},
+	}
+
+	tests := []struct {
+		name     string
+		domainID uuid.UUID
+		want     []string
+	}{
+		{name: "hosts of every site, without duplicates", domainID: used.ID, want: []string{"www.example.com", "example.com", "api.example.com"}},
+		{name: "unused domain falls back to its own name", domainID: unused.ID, want: []string{"spare.org"}},
+		{name: "unknown domain", domainID: uuid.New(), want: nil},
+	}
+
+	for _, tt := range tests {
+		t.Run(tt.name, func(t *testing.T) {
+			if got := s.DomainHosts(tt.domainID); !reflect.DeepEqual(got, tt.want) {
+				t.Errorf("DomainHosts() = %v, want %v", got, tt.want)
+			}
+		})
+	}
+}
+
+func TestFailedSites(t *testing.T) {
+	s := NewAppState()
+	s.Sites = []models.Site{
+		{ID: uuid.New(), Name: "web", Status: models.SiteStatusRunning},
+		{ID: uuid.New(), Name: "api", Status: models.SiteStatusFailed, LastError: "image not found"},
+		{ID: uuid.New(), Name: "docs"},
+		{ID: uuid.New(), Name: "shop", Status: models.SiteStatusFailed},
+	}
+
+	var got []string
+	for _, site := range s.FailedSites() {
+		got = append(got, site.Name)
+	}
+	if want := []string{"api", "shop"}; !reflect.DeepEqual(got, want) {
+		t.Errorf("FailedSites() = %v, want %v", got, want)
+	}
+}
+
+func TestFleetTotals(t *testing.T) {
+	s := NewAppState()
+	s.Nodes = []models.Node{
+		{ID: uuid.New(), Name: "both",
+			DockerInfo:  &models.DockerInfo{ContainersRunning: 3, ImagesCount: 5},
+			TraefikInfo: &models.TraefikInfo{RoutersCount: 4, ServicesCount: 2}},
+		{ID: uuid.New(), Name: "docker only", DockerInfo: &models.DockerInfo{ContainersRunning: 1, ImagesCount: 2}},
+		{ID: uuid.New(), Name: "never checked"},
+	}
+
+	want := FleetTotals{ContainersRunning: 4, Images: 7, Routers: 4, Services: 2}
+	if got := s.FleetTotals(); got != want {
+		t.Errorf("FleetTotals() = %+v, want %+v", got, want)
+	}
+}
+
+func TestVisibleNodes(t *testing.T) {
+	s := NewAppState()
+	s.Nodes = []models.Node{
+		{ID: uuid.New(), Name: "eu-1", Tags: []string{"eu", "prod"}, DockerInfo: &models.DockerInfo{ContainersRunning: 2}},
+		{ID: uuid.New(), Name: "us-1", Tags: []string{"us", "prod"}, DockerInfo: &models.DockerInfo{ContainersRunning: 5}},
+		{ID: uuid.New(), Name: "lab"},
+	}
+
+	if got, want := s.AllNodeTags(), []string{"eu", "prod", "us"}; !reflect.DeepEqual(got, want) {
+		t.Errorf("AllNodeTags() = %v, want %v", got, want)
+	}
+
+	tests := []struct {
+		tag        string
+		want       []string
+		containers int
+	}{
+		{"", []string{"eu-1", "us-1", "lab"}, 7},
+		{"prod", []string{"eu-1", "us-1"}, 7},
+		{"eu", []string{"eu-1"}, 2},
+		{"missing", nil, 0},
+	}
+
+	for _, tt := range tests {
+		t.Run(tt.tag, func(t *testing.T) {
+			s.NodeTagFilter = tt.tag
+			var got []string
+			for _, node := range s.VisibleNodes() {
+				got = append(got, node.Name)
+			}
+			if !reflect.DeepEqual(got, tt.want) {
+				t.Errorf("VisibleNodes() = %v, want %v", got, tt.want)
+			}
+			if got := s.FleetTotals().ContainersRunning; got != tt.containers {
+				t.Errorf("FleetTotals().ContainersRunning = %d, want %d", got, tt.containers)
+			}
+		})
+	}
+}
+
+func TestAsyncOperations(t *testing.T) {
+	s := NewAppState()
+	cancelled := false
+	deploy := s.AddAsyncOperation(OpDeploySite, "blog", nil, func() { cancelled = true })
+	sync := s.AddAsyncOperation(OpSyncDns, "example.com", nil, nil)
+
+	if got := s.PendingOperations[0].Label(); got != "Deploying site blog" {
+		t.Errorf("Label() = %q, want %q", got, "Deploying site blog")
+	}
+	if got := s.PendingOperations[1].Label(); got != "Syncing DNS for example.com" {
+		t.Errorf("Label() = %q, want %q", got, "Syncing DNS for example.com")
+	}
+
+	if !s.CancelAsyncOperation(deploy) || !cancelled {
+		t.Fatal("CancelAsyncOperation(deploy) should cancel the operation")
+	}
+	if s.CancelAsyncOperation(deploy) {
+		t.Error("cancelling an operation twice should return false")
+	}
+
+	op, ok := s.CompleteAsyncOperation(deploy)
+	if !ok || op.Status != "failed" || op.Error != "cancelled" {
+		t.Fatalf("CompleteAsyncOperation(deploy) = %+v, %v; want a cancelled failure", op, ok)
+	}
+	if _, ok := s.CompleteAsyncOperation(deploy); ok {
+		t.Error("completing an operation twice should return false")
+	}
+	if len(s.PendingOperations) != 1 || s.PendingOperations[0].ID != sync {
+		t.Errorf("PendingOperations = %+v, want only the DNS sync", s.PendingOperations)
+	}
+}
+
+func TestFailedOperations(t *testing.T) {
+	s := NewAppState()
+	for i := 0; i < MaxFailedOperations+2; i++ {
+		id := s.AddAsyncOperation(OpDeploySite, fmt.Sprintf("site-%d", i), i, nil)
+		op, _ := s.CompleteAsyncOperation(id)
+		s.RecordFailedOperation(op, "node unreachable")
+	}
+	pending := s.AddAsyncOperation(OpStopSite, "blog", nil, nil)
+
+	if len(s.FailedOperations) != MaxFailedOperations {
+		t.Fatalf("kept %d failed operations, want %d", len(s.FailedOperations), MaxFailedOperations)
+	}
+	if got := s.FailedOperations[0].Target; got != "site-2" {
+		t.Errorf("oldest failed operation = %q, want site-2", got)
+	}
+
+	// Pending first, then failed newest first
+	ops := s.Operations()
+	if ops[0].ID != pending || ops[1].Target != "site-11" || ops[len(ops)-1].Target != "site-2" {
+		t.Errorf("Operations() order = %s, %s ... %s", ops[0].Target, ops[1].Target, ops[len(ops)-1].Target)
+	}
+
+	s.OperationsIndex = len(ops) - 1
+	op, ok := s.TakeFailedOperation(ops[1].ID)
+	if !ok || op.Retry != 11 || op.Status != "failed" || op.Error != "node unreachable" {
+		t.Fatalf("TakeFailedOperation() = %+v, %v", op, ok)
+	}
+	if _, ok := s.TakeFailedOperation(op.ID); ok {
+		t.Error("taking a failed operation twice should return false")
+	}
+	s.ClampOperationsIndex()
+	if want := len(ops) - 2; s.OperationsIndex != want {
+		t.Errorf("OperationsIndex = %d, want %d", s.OperationsIndex, want)
+	}
+}
+
+func TestBulkOperation(t *testing.T) {
+	s := NewAppState()
+	node := models.Node{ID: uuid.New(), Name: "prod"}
+	s.Sites = []models.Site{
+		{ID: uuid.New(), Name: "web", NodeID: node.ID},
+		{ID: uuid.New(), Name: "other", NodeID: uuid.New()},
+		{ID: uuid.New(), Name: "api", NodeID: node.ID},
+	}
+
+	sites := s.SitesOnNode(node.ID)
+	if len(sites) != 2 || sites[0].Name != "web" || sites[1].Name != "api" {
+		t.Fatalf("SitesOnNode() = %+v, want web and api", sites)
+	}
+
+	bulk := NewBulkOperation(&node, OpStopSite, sites)
+	first, _ := bulk.Next()
+	second, _ := bulk.Next()
+	if first != sites[0].ID || second != sites[1].ID {
+		t.Error("Next() should return the sites in order")
+	}
+	if _, ok := bulk.Next(); ok {
+		t.Error("Next() should report an empty queue")
+	}
+
+	webOp, apiOp := uuid.New(), uuid.New()
+	bulk.Running[webOp] = true
+	bulk.Running[apiOp] = true
+	if bulk.Finish(uuid.New(), false) {
+		t.Error("Finish() should ignore operations outside the bulk operation")
+	}
+	bulk.Finish(webOp, false)
+	if got, want := bulk.Progress(), "Stopping all sites on prod: 1/2 done"; got != want {
+		t.Errorf("Progress() = %q, want %q", got, want)
+	}
+	if bulk.Done() {
+		t.Error("Done() should wait for the running operation")
+	}
+	bulk.Finish(apiOp, true)
+	if got, want := bulk.Progress(), "Stopping all sites on prod: 2/2 done, 1 failed"; got != want {
+		t.Errorf("Progress() = %q, want %q", got, want)
+	}
+	if !bulk.Done() {
+		t.Error("Done() should be true once every site has finished")
+	}
+}
+
+func TestRestoreNotifications(t *testing.T) {
+	path := filepath.Join(t.TempDir(), "notifications.json")
+
+	previous := NewAppState()
+	for i := 0; i < MaxNotifications+5; i++ {
+		previous.AddNotification(fmt.Sprintf("old %d", i), "info")
+	}
+	if err := SaveNotifications(path, previous.Notifications); err != nil {
+		t.Fatalf("SaveNotifications() error = %v", err)
+	}
+
+	saved, err := LoadNotifications(path)
+	if err != nil {
+		t.Fatalf("LoadNotifications() error = %v", err)
+	}
+	if len(saved) != MaxNotifications || saved[0].Message != "old 5" || saved[0].Time.IsZero() {
+		t.Fatalf("loaded %d notifications starting with %+v, want the last %d with times", len(saved), saved[0], MaxNotifications)
+	}
+
+	s := NewAppState()
+	s.AddNotification("startup warning", "warning")
+	s.RestoreNotifications(saved)
+	if len(s.Notifications) != MaxNotifications {
+		t.Fatalf("got %d notifications, want the %d cap", len(s.Notifications), MaxNotifications)
+	}
+	if latest, ok := s.LatestNotification(); !ok || latest.Message != "startup warning" {
+		t.Errorf("LatestNotification() = %+v, %v, want this session's warning", latest, ok)
+	}
+
+	// Restored notifications alone don't show in the status bar
+	s.ClearNotifications()
+	s.RestoreNotifications(saved)
+	if latest, ok := s.LatestNotification(); ok {
+		t.Errorf("LatestNotification() = %+v, want none from the last session", latest)
+	}
+	s.AddNotification("deployed", "success")
+	if latest, ok := s.LatestNotification(); !ok || latest.Message != "deployed" {
+		t.Errorf("LatestNotification() = %+v, %v, want the new notification", latest, ok)
+	}
+
+	if missing, err := LoadNotifications(filepath.Join(t.TempDir(), "missing.json")); err != nil || missing != nil {
+		t.Errorf("LoadNotifications(missing) = %v, %v, want no notifications", missing, err)
+	}
+}
+
+func TestEnvironmentVars(t *testing.T) {
+	tests := []struct {
+		name    string
+		pairs   []EnvVarPair
+		want    map[string]string
+		wantErr string
+	}{
+		{
+			name:  "blank rows are skipped",
+			pairs: []EnvVarPair{{Key: " PORT ", Value: " 8080 "}, {}, {Key: "EMPTY"}},
+			want:  map[string]string{"PORT": "8080", "EMPTY": ""},
+		},
+		{name: "value without a key", pairs: []EnvVarPair{{Value: "orphan"}}, wantErr: "name is required"},
+		{name: "key with whitespace", pairs: []EnvVarPair{{Key: "MY VAR", Value: "1"}}, wantErr: "must not contain"},
+		{name: "key with equals sign", pairs: []EnvVarPair{{Key: "A=B", Value: "1"}}, wantErr: "must not contain"},
+		{
+			name:    "duplicate key",
+			pairs:   []EnvVarPair{{Key: "API_TOKEN", Value: "a"}, {Key: "API_TOKEN", Value: "b"}},
+			wantErr: "ENV [2]: API_TOKEN is already set",
+		},
+	}
+
+	for _, tt := range tests {
+		t.Run(tt.name, func(t *testing.T) {
+			s := NewAppState()
+			s.EnvVarPairs = tt.pairs
+
+			got, err := s.EnvironmentVars()
+			if tt.wantErr != "" {
+				if err == nil || !strings.Contains(err.Error(), tt.wantErr) {
+					t.Fatalf("EnvironmentVars() error = %v, want error containing %q", err, tt.wantErr)
+				}
+				return
+			}
+			if err != nil {
+				t.Fatalf("EnvironmentVars() error = %v", err)
+			}
+			if !reflect.DeepEqual(got, tt.want) {
+				t.Errorf("EnvironmentVars() = %v, want %v", got, tt.want)
+			}
+		})
+	}
+}
+
+func TestMergeEnvVars(t *testing.T) {
+	s := NewAppState()
+	s.EnvVarPairs = []EnvVarPair{{Key: "PORT", Value: "80"}, {}, {Key: "DEBUG", Value: "false"}}
+	s.EnvVarFocusedPair = 2
+
+	added, updated := s.MergeEnvVars([]models.EnvVar{
+		{Key: "DEBUG", Value: "true"},
+		{Key: "API_TOKEN", Value: "secret"},
+	})
+
+	if added != 1 || updated != 1 {
+		t.Errorf("MergeEnvVars() = (%d, %d), want (1, 1)", added, updated)
+	}
+	want := []EnvVarPair{{Key: "PORT", Value: "80"}, {Key: "DEBUG", Value: "true"}, {Key: "API_TOKEN", Value: "secret"}}
+	if !reflect.DeepEqual(s.EnvVarPairs, want) {
+		t.Errorf("EnvVarPairs = %+v, want %+v", s.EnvVarPairs, want)
+	}
+}
+
+func TestSetupStep(t *testing.T) {
+	s := NewAppState()
+	if !s.NeedsSetup() || s.SetupStep() != SetupStepNode {
+		t.Fatalf("empty state: NeedsSetup() = %v, SetupStep() = %v, want true, SetupStepNode", s.NeedsSetup(), s.SetupStep())
+	}
+
+	s.Nodes = append(s.Nodes, models.Node{ID: uuid.New(), Name: "edge"})
+	if s.NeedsSetup() || s.SetupStep() != SetupStepDomain {
+		t.Errorf("with a node: NeedsSetup() = %v, SetupStep() = %v, want false, SetupStepDomain", s.NeedsSetup(), s.SetupStep())
+	}
+
+	s.Domains = append(s.Domains, models.Domain{ID: uuid.New(), Name: "example.com"})
+	if s.SetupStep() != SetupStepSite {
+		t.Errorf("with a domain: SetupStep() = %v, want SetupStepSite", s.SetupStep())
+	}
+
+	s.Sites = append(s.Sites, models.Site{ID: uuid.New(), Name: "blog"})
+	if s.SetupStep() != SetupStepDone {
+		t.Errorf("with a site: SetupStep() = %v, want SetupStepDone", s.SetupStep())
+	}
+
+	s.CurrentScreen = ScreenSetup
+	s.PreviousScreens = []Screen{ScreenDashboard}
+	s.FinishSetup()
+	if s.CurrentScreen != ScreenDashboard || len(s.PreviousScreens) != 0 {
+		t.Errorf("FinishSetup() left screen %s with history %v", s.CurrentScreen, s.PreviousScreens)
+	}
+}
+
+func TestNavigateForward(t *testing.T) {
+	s := NewAppState()
+	s.NavigateTo(ScreenSitesList)
+	s.NavigateTo(ScreenSiteDetail)
+
+	if s.NavigateForward() {
+		t.Fatal("NavigateForward() with no forward history = true, want false")
+	}
+
+	s.NavigateBack()
+	s.NavigateBack()
+	if s.CurrentScreen != ScreenDashboard {
+		t.Fatalf("after going back twice, screen = %s, want %s", s.CurrentScreen, ScreenDashboard)
+	}
+
+	for _, want := range []Screen{ScreenSitesList, ScreenSiteDetail} {
+		if !s.NavigateForward() || s.CurrentScreen != want {
+			t.Fatalf("NavigateForward() went to %s, want %s", s.CurrentScreen, want)
+		}
+	}
+	if s.NavigateForward() {
+		t.Errorf("NavigateForward() past the last screen = true, want false")
+	}
+
+	// Back then somewhere new drops the forward history
+	s.NavigateBack()
+	s.NavigateTo(ScreenNodesList)
+	if s.NavigateForward() {
+		t.Errorf("NavigateForward() after NavigateTo() = true, want false")
+	}
+
+	// Forms aren't returned to once left
+	s.NavigateTo(ScreenNodeCreate)
+	s.NavigateBack()
+	if s.NavigateForward() {
+		t.Errorf("NavigateForward() to a form = true, want false")
+	}
+}
+
+func TestBreadcrumbTrail(t *testing.T) {
+	tests := []struct {
+		name     string
+		previous []Screen
+		current  Screen
+		want     []Screen
+	}{
+		{name: "top-level screen", previous: nil, current: ScreenDashboard, want: []Screen{ScreenDashboard}},
+		{name: "drill down", previous: []Screen{ScreenDashboard, ScreenSitesList}, current: ScreenSiteDetail, want: []Screen{ScreenSitesList, ScreenSiteDetail}},
+		{name: "nested form", previous: []Screen{ScreenDashboard, ScreenSitesList, ScreenSiteDetail}, current: ScreenSiteEdit, want: []Screen{ScreenSitesList, ScreenSiteDetail, ScreenSiteEdit}},
+		{name: "no top-level screen", previous: nil, current: ScreenNodeConfig, want: []Screen{ScreenNodeConfig}},
+	}
+
+	for _, tt := range tests {
+		t.Run(tt.name, func(t *testing.T) {
+			s := NewAppState()
+			s.PreviousScreens = tt.previous
+			s.CurrentScreen = tt.current
+			if got := s.BreadcrumbTrail(); !reflect.DeepEqual(got, tt.want) {
+				t.Errorf("BreadcrumbTrail() = %v, want %v", got, tt.want)
+			}
+		})
+	}
+}
+
+func TestDeployingJournal(t *testing.T) {
+	path := filepath.Join(t.TempDir(), "deploying.json")
+
+	s := NewAppState()
+	s.Sites = []models.Site{
+		{ID: uuid.New(), Name: "blog", Status: models.SiteStatusDeploying},
+		{ID: uuid.New(), Name: "shop", Status: models.SiteStatusRunning},
+	}
+	deploys := s.DeployingSites()
+	if len(deploys) != 1 || deploys[0].StartedAt.IsZero() {
+		t.Fatalf("DeployingSites() = %v, want blog with a start time", deploys)
+	}
+	// The start time is kept while the deploy runs
+	if again := s.DeployingSites(); !reflect.DeepEqual(again, deploys) {
+		t.Errorf("DeployingSites() again = %v, want %v", again, deploys)
+	}
+	if err := SaveDeploying(path, deploys); err != nil {
+		t.Fatalf("SaveDeploying() error = %v", err)
+	}
+
+	// The next start loads a config saved before the deploy began
+	next := NewAppState()
+	next.Sites = []models.Site{
+		{ID: s.Sites[0].ID, Name: "blog", Status: models.SiteStatusStopped},
+		{ID: s.Sites[1].ID, Name: "shop", Status: models.SiteStatusRunning},
+	}
+	loaded, err := LoadDeploying(path)
+	if err != nil {
+		t.Fatalf("LoadDeploying() error = %v", err)
+	}
+	next.MarkDeploying(append(loaded, DeployingSite{SiteID: uuid.New()})) // Plus a site deleted since
+	if got := next.DeployingSiteIDs(); !reflect.DeepEqual(got, []uuid.UUID{s.Sites[0].ID}) {
+		t.Errorf("DeployingSiteIDs() after MarkDeploying() = %v, want only blog", got)
+	}
+	if got := next.DeployStarted(s.Sites[0].ID); !got.Equal(deploys[0].StartedAt) {
+		t.Errorf("DeployStarted() = %v, want %v", got, deploys[0].StartedAt)
+	}
+
+	// With nothing deploying the file is removed
+	if err := SaveDeploying(path, nil); err != nil {
+		t.Fatalf("SaveDeploying(nil) error = %v", err)
+	}
+	if deploys, err := LoadDeploying(path); err != nil || deploys != nil {
+		t.Errorf("LoadDeploying() after clearing = %v, %v, want no deploys", deploys, err)
+	}
+}
+
+func TestSiteHealth(t *testing.T) {
+	domain := models.Domain{ID: uuid.New(), Name: "example.com"}
+	probed := models.Site{
+		ID:             uuid.New(),
+		SSLEnabled:     true,
+		HealthPath:     "/healthz",
+		DomainMappings: []models.DomainMapping{{DomainID: domain.ID, Subdomain: "app", Port: 8080}},
+	}
+	unprobed := models.Site{ID: uuid.New(), DomainMappings: probed.DomainMappings}
+
+	s := NewAppState()
+	s.Domains = []models.Domain{domain}
+	s.Sites = []models.Site{probed, unprobed}
+
+	if got := s.SiteHealthURL(probed.ID); got != "https://app.example.com/healthz" {
+		t.Errorf("SiteHealthURL() = %q, want %q", got, "https://app.example.com/healthz")
+	}
+	if got := s.SiteHealthURL(unprobed.ID); got != "" {
+		t.Errorf("SiteHealthURL() without a health path = %q, want none", got)
+	}
+
+	if got := s.GetSiteHealth(probed.ID); got != models.SiteHealthUnknown {
+		t.Errorf("GetSiteHealth() before probing = %s, want %s", got, models.SiteHealthUnknown)
+	}
+	s.SetSiteHealth(probed.ID, models.SiteHealthUnhealthy)
+	if got := s.GetSiteHealth(probed.ID); got != models.SiteHealthUnhealthy {
+		t.Errorf("GetSiteHealth() = %s, want %s", got, models.SiteHealthUnhealthy)
+	}
+
+	// Removing the health path forgets the last result
+	s.Sites[0].HealthPath = ""
+	if got := s.GetSiteHealth(probed.ID); got != models.SiteHealthUnknown {
+		t.Errorf("GetSiteHealth() without a health path = %s, want %s", got, models.SiteHealthUnknown)
+	}
+}
+
+func TestCountAutoRestart(t *testing.T) {
+	s := NewAppState()
+	siteID := uuid.New()
+
+	for want := 1; want <= MaxAutoRestarts; want++ {
+		if got := s.CountAutoRestart(siteID); got != want {
+			t.Fatalf("CountAutoRestart() = %d, want %d", got, want)
+		}
+	}
+	if got := s.CountAutoRestart(uuid.New()); got != 1 {
+		t.Errorf("CountAutoRestart() for another site = %d, want 1", got)
+	}
+
+	// Deploying or restarting by hand allows the attempts again
+	s.ResetAutoRestarts(siteID)
+	if got := s.CountAutoRestart(siteID); got != 1 {
+		t.Errorf("CountAutoRestart() after ResetAutoRestarts() = %d, want 1", got)
+	}
+}
@@ -0,0 +1,195 @@
+package state
+
+import (
+	"strings"
+	"unicode/utf8"
+
+	"github.com/BlueBeard63/archon/internal/models"
+)
+
+// TextEditor is a multi-line text buffer with a cursor, edited one key at a time
+type TextEditor struct {
+	Lines  []string // Content split into lines (never empty)
+	Row    int      // Line the cursor is on
+	Col    int      // Byte offset of the cursor in its line
+	Scroll int      // First line shown (see ScrollTo)
+}
+
+// NewTextEditor returns an editor holding content with the cursor at the start
+func NewTextEditor(content string) *TextEditor {
+	return &TextEditor{Lines: strings.Split(strings.ReplaceAll(content, "\r\n", "\n"), "\n")}
+}
+
+// Value returns the editor's content
+func (e *TextEditor) Value() string {
+	return strings.Join(e.Lines, "\n")
+}
+
+// Insert types text at the cursor; newlines in text split the line
+func (e *TextEditor) Insert(text string) {
+	for i, part := range strings.Split(text, "\n") {
+		if i > 0 {
+			e.Newline()
+		}
+		line := e.Lines[e.Row]
+		e.Lines[e.Row] = line[:e.Col] + part + line[e.Col:]
+		e.Col += len(part)
+	}
+}
+
+// Newline splits the line at the cursor, moving the cursor to the start of the new line
+func (e *TextEditor) Newline() {
+	line := e.Lines[e.Row]
+	rest := line[e.Col:]
+	e.Lines[e.Row] = line[:e.Col]
+	e.Lines = append(e.Lines[:e.Row+1], append([]string{rest}, e.Lines[e.Row+1:]...)...)
+	e.Row++
+	e.Col = 0
+}
+
+// Backspace deletes the character before the cursor, joining with the previous line at its start
+func (e *TextEditor) Backspace() {
+	if e.Col > 0 {
+		line := e.Lines[e.Row]
+		_, size := utf8.DecodeLastRuneInString(line[:e.Col])
+		e.Lines[e.Row] = line[:e.Col-size] + line[e.Col:]
+		e.Col -= size
+		return
+	}
+	if e.Row == 0 {
+		return
+	}
+	prev := e.Lines[e.Row-1]
+	e.Lines[e.Row-1] = prev + e.Lines[e.Row]
+	e.Lines = append(e.Lines[:e.Row], e.Lines[e.Row+1:]...)
+	e.Row--
+	e.Col = len(prev)
+}
+
+// Delete deletes the character under the cursor, joining with the next line at its end
+func (e *TextEditor) Delete() {
+	line := e.Lines[e.Row]
+	if e.Col < len(line) {
+		_, size := utf8.DecodeRuneInString(line[e.Col:])
+		e.Lines[e.Row] = line[:e.Col] + line[e.Col+size:]
+		return
+	}
+	if e.Row == len(e.Lines)-1 {
+		return
+	}
+	e.Lines[e.Row] = line + e.Lines[e.Row+1]
+	e.Lines = append(e.Lines[:e.Row+1], e.Lines[e.Row+2:]...)
+}
+
+// MoveLeft moves the cursor back a character, wrapping to the end of the previous line
+func (e *TextEditor) MoveLeft() {
+	if e.Col > 0 {
+		_, size := utf8.DecodeLastRuneInString(e.Lines[e.Row][:e.Col])
+		e.Col -= size
+	} else if e.Row > 0 {
+		e.Row--
+		e.Col = len(e.Lines[e.Row])
+	}
+}
+
+// MoveRight moves the cursor forward a character, wrapping to the start of the next line
+func (e *TextEditor) MoveRight() {
+	if line := e.Lines[e.Row]; e.Col < len(line) {
+		_, size := utf8.DecodeRuneInString(line[e.Col:])
+		e.Col += size
+	} else if e.Row < len(e.Lines)-1 {
+		e.Row++
+		e.Col = 0
+	}
+}
+
+// MoveUp moves the cursor to the line above, keeping its column where the line is long enough
+func (e *TextEditor) MoveUp() {
+	if e.Row > 0 {
+		e.Row--
+		e.clampCol()
+	}
+}
+
+// MoveDown moves the cursor to the line below, keeping its column where the line is long enough
+func (e *TextEditor) MoveDown() {
+	if e.Row < len(e.Lines)-1 {
+		e.Row++
+		e.clampCol()
+	}
+}
+
+// Home moves the cursor to the start of its line
+func (e *TextEditor) Home() {
+	e.Col = 0
+}
+
+// End moves the cursor to the end of its line
+func (e *TextEditor) End() {
+	e.Col = len(e.Lines[e.Row])
+}
+
+// ScrollTo adjusts Scroll so the cursor's line is among the height lines shown
+func (e *TextEditor) ScrollTo(height int) {
+	if height < 1 {
+		height = 1
+	}
+	if e.Row < e.Scroll {
+		e.Scroll = e.Row
+	}
+	if e.Row >= e.Scroll+height {
+		e.Scroll = e.Row - height + 1
+	}
+}
+
+// clampCol keeps the cursor inside its line and on a character boundary
+func (e *TextEditor) clampCol() {
+	line := e.Lines[e.Row]
+	if e.Col > len(line) {
+		e.Col = len(line)
+	}
+	for e.Col > 0 && e.Col < len(line) && !utf8.RuneStart(line[e.Col]) {
+		e.Col--
+	}
+}
+
+// ConfigFileForm is a config file being added or edited on the site config files screen
+type ConfigFileForm struct {
+	Index         int         // File being edited (-1 = adding a file)
+	Name          *TextEditor // File name (single line)
+	ContainerPath *TextEditor // Where the file is mounted in the container (single line)
+	Content       *TextEditor // File content
+	Field         int         // Focused field: 0 = name, 1 = container path, 2 = content
+}
+
+// NewConfigFileForm returns a form for file, which is the file at index (-1 = a new file)
+func NewConfigFileForm(index int, file models.ConfigFile) *ConfigFileForm {
+	form := &ConfigFileForm{
+		Index:         index,
+		Name:          NewTextEditor(file.Name),
+		ContainerPath: NewTextEditor(file.ContainerPath),
+		Content:       NewTextEditor(file.Content),
+	}
+	form.Name.End()
+	return form
+}
+
+// Focused returns the editor of the focused field
+func (f *ConfigFileForm) Focused() *TextEditor {
+	switch f.Field {
+	case 0:
+		return f.Name
+	case 1:
+		return f.ContainerPath
+	}
+	return f.Content
+}
+
+// File returns the config file described by the form
+func (f *ConfigFileForm) File() models.ConfigFile {
+	return models.ConfigFile{
+		Name:          strings.TrimSpace(f.Name.Value()),
+		ContainerPath: strings.TrimSpace(f.ContainerPath.Value()),
+		Content:       f.Content.Value(),
+	}
+}
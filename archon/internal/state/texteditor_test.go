@@ -0,0 +1,75 @@
+package state
+
+import (
+	"reflect"
+	"testing"
+)
+
+func TestTextEditor(t *testing.T) {
+	e := NewTextEditor("server {\n}")
+
+	// Type a line inside the block
+	e.MoveDown()
+	e.Insert("  listen 80;")
+	e.Newline()
+	if got := e.Value(); got != "server {\n  listen 80;\n}" {
+		t.Fatalf("after typing, Value() = %q", got)
+	}
+	if e.Row != 2 || e.Col != 0 {
+		t.Errorf("cursor = (%d, %d), want (2, 0)", e.Row, e.Col)
+	}
+
+	// Backspace at the start of a line joins it with the one above
+	e.Backspace()
+	if want := []string{"server {", "  listen 80;}"}; !reflect.DeepEqual(e.Lines, want) {
+		t.Fatalf("after backspace, Lines = %q, want %q", e.Lines, want)
+	}
+	if e.Row != 1 || e.Col != len("  listen 80;") {
+		t.Errorf("cursor = (%d, %d), want (1, %d)", e.Row, e.Col, len("  listen 80;"))
+	}
+
+	// Delete at the end of a line joins the next one
+	e.Newline()
+	e.MoveLeft()
+	e.Delete()
+	if got := e.Value(); got != "server {\n  listen 80;}" {
+		t.Fatalf("after delete, Value() = %q", got)
+	}
+
+	// Moving up keeps the column inside the shorter line
+	e.End()
+	e.MoveUp()
+	if e.Row != 0 || e.Col != len("server {") {
+		t.Errorf("after moving up, cursor = (%d, %d), want (0, %d)", e.Row, e.Col, len("server {"))
+	}
+
+	// Multi-byte characters are moved over and deleted whole
+	e = NewTextEditor("")
+	e.Insert("é")
+	e.MoveLeft()
+	if e.Col != 0 {
+		t.Errorf("after moving left over é, Col = %d, want 0", e.Col)
+	}
+	e.Delete()
+	if got := e.Value(); got != "" {
+		t.Errorf("after deleting é, Value() = %q, want empty", got)
+	}
+}
+
+func TestTextEditorScrollTo(t *testing.T) {
+	e := NewTextEditor("1\n2\n3\n4\n5\n6")
+	for i := 0; i < 4; i++ {
+		e.MoveDown()
+	}
+	e.ScrollTo(3)
+	if e.Scroll != 2 {
+		t.Errorf("Scroll = %d, want 2 with the cursor on line 4", e.Scroll)
+	}
+	e.MoveUp()
+	e.MoveUp()
+	e.MoveUp()
+	e.ScrollTo(3)
+	if e.Scroll != 1 {
+		t.Errorf("Scroll = %d, want 1 with the cursor on line 1", e.Scroll)
+	}
+}
@@ -0,0 +1,73 @@
+package components
+
+import (
+	"strings"
+
+	"github.com/charmbracelet/lipgloss"
+
+	"github.com/BlueBeard63/archon/internal/state"
+	"github.com/BlueBeard63/archon/internal/ui/theme"
+)
+
+// breadcrumbSeparator goes between the crumbs of the breadcrumb line
+const breadcrumbSeparator = " › "
+
+// RenderBreadcrumb renders the path to the current screen, e.g. "Sites › blog › Logs", naming
+// the site, domain or node of screens that show one. Leading crumbs are dropped when the
+// line is wider than the window.
+func RenderBreadcrumb(s *state.AppState, windowWidth int, t theme.Theme) string {
+	var crumbs []string
+	trail := s.BreadcrumbTrail()
+	for i, screen := range trail {
+		for _, crumb := range screenCrumbs(s, screen, i == len(trail)-1) {
+			// A site's detail and edit screens both start with its name
+			if len(crumbs) > 0 && crumbs[len(crumbs)-1] == crumb {
+				continue
+			}
+			crumbs = append(crumbs, crumb)
+		}
+	}
+
+	style := lipgloss.NewStyle().Foreground(t.Muted).Padding(0, 1)
+	maxWidth := windowWidth - 2
+	line := strings.Join(crumbs, breadcrumbSeparator)
+	for len(crumbs) > 1 && maxWidth > 0 && lipgloss.Width(line) > maxWidth {
+		crumbs = crumbs[1:]
+		line = "…" + breadcrumbSeparator + strings.Join(crumbs, breadcrumbSeparator)
+	}
+	return style.Render(line)
+}
+
+// screenCrumbs returns the crumbs for a screen: its name, or the name of the site, domain or
+// node it shows followed by what it shows of it. The site detail screen adds its tab when it
+// is the current screen.
+func screenCrumbs(s *state.AppState, screen state.Screen, last bool) []string {
+	switch screen {
+	case state.ScreenSiteDetail:
+		if site := s.GetSiteByID(s.SelectedSiteID); site != nil {
+			if last && s.SiteDetailTab != state.SiteDetailTabOverview && s.SiteDetailTab < len(state.SiteDetailTabNames) {
+				return []string{site.Name, state.SiteDetailTabNames[s.SiteDetailTab]}
+			}
+			return []string{site.Name}
+		}
+	case state.ScreenSiteEdit:
+		if site := s.GetSiteByID(s.SelectedSiteID); site != nil {
+			return []string{site.Name, "Edit"}
+		}
+	case state.ScreenDomainEdit, state.ScreenDomainDnsRecords:
+		if domain := s.GetDomainByID(s.SelectedDomainID); domain != nil {
+			if screen == state.ScreenDomainEdit {
+				return []string{domain.Name, "Edit"}
+			}
+			return []string{domain.Name, "DNS Records"}
+		}
+	case state.ScreenNodeEdit, state.ScreenNodeConfig:
+		if node := s.GetNodeByID(s.SelectedNodeID); node != nil {
+			if screen == state.ScreenNodeEdit {
+				return []string{node.Name, "Edit"}
+			}
+			return []string{node.Name, "Config"}
+		}
+	}
+	return []string{getScreenName(screen)}
+}
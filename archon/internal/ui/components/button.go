@@ -3,35 +3,22 @@ package components
 import (
 	"github.com/charmbracelet/lipgloss"
 	zone "github.com/lrstanley/bubblezone"
+
+	"github.com/BlueBeard63/archon/internal/ui/theme"
 )
 
 var (
-	// Button styles
+	// Button styles, bordered and colored by style
 	buttonStyleNoBorder = lipgloss.NewStyle().
 				Bold(true).
 				Padding(0, 2).
 				Margin(0, 1)
 
-	buttonStyle = buttonStyleNoBorder.
-			Border(lipgloss.RoundedBorder()).
-			BorderForeground(lipgloss.Color("36"))
-
-	buttonPrimaryStyle = buttonStyle.Copy().
-				Background(lipgloss.Color("36")).
-				Foreground(lipgloss.Color("15"))
-
-	buttonSecondaryStyle = buttonStyle.Copy().
-				BorderForeground(lipgloss.Color("240"))
-
 	// Compact button style for icon-only buttons
 	buttonCompactStyleNoBorder = lipgloss.NewStyle().
 					Bold(true).
 					Padding(0, 1).
 					Margin(0)
-
-	buttonCompactStyle = buttonCompactStyleNoBorder.
-				Border(lipgloss.RoundedBorder()).
-				BorderForeground(lipgloss.Color("240"))
 )
 
 // Button represents a clickable button
@@ -43,48 +30,40 @@ type Button struct {
 	Icon    bool
 }
 
-// Render renders the button without zones
-func (b *Button) Render() string {
-	var style lipgloss.Style
-
+// style returns the button's style colored with t
+func (b *Button) style(t theme.Theme) lipgloss.Style {
 	// Use compact style for icon-only buttons
 	if b.Icon {
 		if b.Border {
-			style = buttonCompactStyle
-		} else {
-			style = buttonCompactStyleNoBorder
+			return buttonCompactStyleNoBorder.
+				Border(lipgloss.RoundedBorder()).
+				BorderForeground(t.Muted)
 		}
-	} else if b.Primary {
-		style = buttonPrimaryStyle
-	} else {
-		style = buttonSecondaryStyle
+		return buttonCompactStyleNoBorder
+	}
+
+	style := buttonStyleNoBorder.Border(lipgloss.RoundedBorder())
+	if b.Primary {
+		return style.
+			BorderForeground(t.Accent).
+			Background(t.Accent).
+			Foreground(t.Text)
 	}
+	return style.BorderForeground(t.Muted)
+}
 
-	return style.Render(b.Label)
+// Render renders the button without zones
+func (b *Button) Render(t theme.Theme) string {
+	return b.style(t).Render(b.Label)
 }
 
 // RenderWithZone renders the button with a clickable zone
-func (b *Button) RenderWithZone(zm *zone.Manager) string {
+func (b *Button) RenderWithZone(zm *zone.Manager, t theme.Theme) string {
 	if zm == nil {
-		return b.Render()
-	}
-
-	var style lipgloss.Style
-
-	// Use compact style for icon-only buttons
-	if b.Icon {
-		if b.Border {
-			style = buttonCompactStyle
-		} else {
-			style = buttonCompactStyleNoBorder
-		}
-	} else if b.Primary {
-		style = buttonPrimaryStyle
-	} else {
-		style = buttonSecondaryStyle
+		return b.Render(t)
 	}
 
-	rendered := style.Render(b.Label)
+	rendered := b.style(t).Render(b.Label)
 	return zm.Mark("button:"+b.ID, rendered)
 }
 
@@ -94,23 +73,23 @@ type ButtonGroup struct {
 }
 
 // Render renders the button group without zones
-func (bg *ButtonGroup) Render() string {
+func (bg *ButtonGroup) Render(t theme.Theme) string {
 	var buttons []string
 	for _, btn := range bg.Buttons {
-		buttons = append(buttons, btn.Render())
+		buttons = append(buttons, btn.Render(t))
 	}
 	return lipgloss.JoinHorizontal(lipgloss.Left, buttons...)
 }
 
 // RenderWithZones renders the button group with clickable zones
-func (bg *ButtonGroup) RenderWithZones(zm *zone.Manager) string {
+func (bg *ButtonGroup) RenderWithZones(zm *zone.Manager, t theme.Theme) string {
 	if zm == nil {
-		return bg.Render()
+		return bg.Render(t)
 	}
 
 	var buttons []string
 	for _, btn := range bg.Buttons {
-		buttons = append(buttons, btn.RenderWithZone(zm))
+		buttons = append(buttons, btn.RenderWithZone(zm, t))
 	}
 	return lipgloss.JoinHorizontal(lipgloss.Left, buttons...)
 }
@@ -0,0 +1,37 @@
+package components
+
+import (
+	"github.com/charmbracelet/lipgloss"
+
+	"github.com/BlueBeard63/archon/internal/state"
+	"github.com/BlueBeard63/archon/internal/ui/theme"
+)
+
+// RenderConfirmDialog renders the confirmation overlay for a pending destructive action,
+// centered in an area of the given size
+func RenderConfirmDialog(c *state.Confirmation, width, height int, t theme.Theme) string {
+	dialogStyle := lipgloss.NewStyle().
+		Border(lipgloss.RoundedBorder()).
+		BorderForeground(t.Error).
+		Padding(1, 3)
+	titleStyle := lipgloss.NewStyle().Bold(true).Foreground(t.Error)
+	nameStyle := lipgloss.NewStyle().Bold(true)
+
+	body := titleStyle.Render("Delete "+c.Kind+"?") + "\n\n" +
+		"This will delete " + c.Kind + " " + nameStyle.Render(c.Name) + ".\n" +
+		"This cannot be undone.\n\n" +
+		"Press y to confirm • Esc to cancel"
+	if c.Title != "" {
+		keys := c.Keys
+		if keys == "" {
+			keys = "Press y to confirm • Esc to cancel"
+		}
+		body = titleStyle.Render(c.Title) + "\n\n" + c.Message + "\n\n" + keys
+	}
+
+	dialog := dialogStyle.Render(body)
+	if width <= 0 || height <= 0 {
+		return dialog
+	}
+	return lipgloss.Place(width, height, lipgloss.Center, lipgloss.Center, dialog)
+}
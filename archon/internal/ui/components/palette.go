@@ -0,0 +1,52 @@
+package components
+
+import (
+	"fmt"
+	"strings"
+
+	"github.com/charmbracelet/lipgloss"
+
+	"github.com/BlueBeard63/archon/internal/state"
+	"github.com/BlueBeard63/archon/internal/ui/theme"
+)
+
+// RenderPalette renders the command palette overlay with the current query and its matches,
+// centered in an area of the given size
+func RenderPalette(s *state.AppState, width, height int, t theme.Theme) string {
+	dialogStyle := lipgloss.NewStyle().
+		Border(lipgloss.RoundedBorder()).
+		BorderForeground(t.Primary).
+		Padding(1, 2).
+		Width(60)
+	titleStyle := lipgloss.NewStyle().Bold(true).Foreground(t.Primary)
+	selectedStyle := lipgloss.NewStyle().Bold(true).Foreground(t.Primary)
+	faintStyle := lipgloss.NewStyle().Faint(true)
+
+	var b strings.Builder
+	b.WriteString(titleStyle.Render("Go to") + "\n\n")
+	b.WriteString("> " + s.Palette.Query + "_\n\n")
+
+	matches := s.PaletteMatches()
+	if len(matches) == 0 {
+		b.WriteString(faintStyle.Render("No matching sites, domains or nodes") + "\n")
+	}
+	for i, entry := range matches {
+		line := fmt.Sprintf("%-6s %s", entry.Kind, entry.Name)
+		if entry.Detail != "" {
+			line += faintStyle.Render("  " + entry.Detail)
+		}
+		if i == s.Palette.Index {
+			b.WriteString(selectedStyle.Render("▶ "+line) + "\n")
+		} else {
+			b.WriteString("  " + line + "\n")
+		}
+	}
+
+	b.WriteString("\n" + faintStyle.Render("Type to search • ↑/↓ to select • Enter to open • Esc to close"))
+
+	dialog := dialogStyle.Render(b.String())
+	if width <= 0 || height <= 0 {
+		return dialog
+	}
+	return lipgloss.Place(width, height, lipgloss.Center, lipgloss.Center, dialog)
+}
@@ -0,0 +1,37 @@
+package components
+
+import (
+	"fmt"
+
+	"github.com/charmbracelet/lipgloss"
+
+	"github.com/BlueBeard63/archon/internal/state"
+	"github.com/BlueBeard63/archon/internal/ui/theme"
+)
+
+// spinnerFrames are shown in turn, one per spinner tick
+var spinnerFrames = []string{"⠋", "⠙", "⠹", "⠸", "⠼", "⠴", "⠦", "⠧", "⠇", "⠏"}
+
+// RenderSpinner renders an animated spinner with the oldest pending operation,
+// e.g. "⠹ Deploying site blog… (+1 more)", or the progress of a running bulk operation.
+// It is empty when nothing is pending.
+func RenderSpinner(s *state.AppState, t theme.Theme) string {
+	if len(s.PendingOperations) == 0 {
+		return ""
+	}
+
+	frame := lipgloss.NewStyle().Foreground(t.Accent).Render(spinnerFrames[s.SpinnerFrame%len(spinnerFrames)])
+	if s.Bulk != nil {
+		return frame + " " + s.Bulk.Progress()
+	}
+
+	op := s.PendingOperations[0]
+	text := frame + " " + op.Label() + "…"
+	if op.Status == "failed" {
+		text += " (" + op.Error + ")"
+	}
+	if more := len(s.PendingOperations) - 1; more > 0 {
+		text += fmt.Sprintf(" (+%d more)", more)
+	}
+	return text
+}
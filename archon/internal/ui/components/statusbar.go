@@ -5,26 +5,55 @@ import (
 
 	"github.com/charmbracelet/lipgloss"
 	"github.com/BlueBeard63/archon/internal/state"
+	"github.com/BlueBeard63/archon/internal/ui/theme"
 )
 
+// notificationIcons prefix notifications so the level shows without color too
+var notificationIcons = map[string]string{
+	"success": "✓ ",
+	"error":   "✗ ",
+	"warning": "⚠ ",
+	"info":    "ℹ ",
+}
+
+// notificationStyle returns the style for notifications of a level, in the theme's color for it
+func notificationStyle(level string, t theme.Theme) (lipgloss.Style, bool) {
+	switch level {
+	case "success":
+		return lipgloss.NewStyle().Foreground(t.Success), true
+	case "error":
+		return lipgloss.NewStyle().Foreground(t.Error).Bold(true), true
+	case "warning":
+		return lipgloss.NewStyle().Foreground(t.Warning), true
+	case "info":
+		return lipgloss.NewStyle().Foreground(t.Accent), true
+	}
+	return lipgloss.Style{}, false
+}
+
 // RenderStatusBar renders the bottom status bar with notifications
-func RenderStatusBar(s *state.AppState, windowWidth int) string {
+func RenderStatusBar(s *state.AppState, windowWidth int, t theme.Theme) string {
 	// Define styles inline to avoid circular import
 	statusBarStyle := lipgloss.NewStyle().Padding(0, 1)
 
-	// Left side: current screen name
+	// Left side: current screen name, then any in-flight operation
 	screenName := getScreenName(s.CurrentScreen)
 	left := statusBarStyle.Render(screenName)
+	if spinner := RenderSpinner(s, t); spinner != "" {
+		left += statusBarStyle.Render(spinner)
+	}
 
 	// Center: latest notification (if any)
 	center := ""
-	if len(s.Notifications) > 0 {
-		latest := s.Notifications[len(s.Notifications)-1]
-		center = renderNotification(latest.Message, latest.Level)
+	if latest, ok := s.LatestNotification(); ok {
+		center = renderNotification(latest.Message, latest.Level, t)
 	}
 
-	// Right side: Help hint
+	// Right side: unsaved changes marker and help hint
 	right := "Press ? for help"
+	if s.Dirty && !s.AutoSave {
+		right = "● unsaved  " + right
+	}
 
 	// Calculate spacing
 	leftWidth := lipgloss.Width(left)
@@ -61,7 +90,7 @@ func RenderStatusBar(s *state.AppState, windowWidth int) string {
 }
 
 // RenderNotificationList renders a list of recent notifications
-func RenderNotificationList(s *state.AppState) string {
+func RenderNotificationList(s *state.AppState, t theme.Theme) string {
 	titleStyle := lipgloss.NewStyle().Bold(true).MarginBottom(1)
 
 	if len(s.Notifications) == 0 {
@@ -79,28 +108,20 @@ func RenderNotificationList(s *state.AppState) string {
 
 	for i := start; i < len(s.Notifications); i++ {
 		notif := s.Notifications[i]
-		line := renderNotification(notif.Message, notif.Level)
+		line := renderNotification(notif.Message, notif.Level, t)
 		b.WriteString(line + "\n")
 	}
 
 	return b.String()
 }
 
-// renderNotification renders a notification with the appropriate style
-func renderNotification(message, level string) string {
-	// Simple prefix based on level, no colors
-	prefix := ""
-	switch level {
-	case "success":
-		prefix = "[OK] "
-	case "error":
-		prefix = "[ERROR] "
-	case "warning":
-		prefix = "[WARN] "
-	case "info":
-		prefix = "[INFO] "
+// renderNotification renders a notification in its level's color, with the level's icon
+func renderNotification(message, level string, t theme.Theme) string {
+	style, ok := notificationStyle(level, t)
+	if !ok {
+		return message
 	}
-	return prefix + message
+	return style.Render(notificationIcons[level] + message)
 }
 
 // getScreenName returns a human-readable name for a screen
@@ -112,16 +133,50 @@ func getScreenName(screen state.Screen) string {
 		return "Sites"
 	case state.ScreenSiteCreate:
 		return "Create Site"
+	case state.ScreenSiteEdit:
+		return "Edit Site"
+	case state.ScreenSiteEnvVars:
+		return "Environment Variables"
+	case state.ScreenSiteConfigFiles:
+		return "Config Files"
+	case state.ScreenSiteDetail:
+		return "Site Details"
+	case state.ScreenSitesDeleted:
+		return "Deleted Sites"
 	case state.ScreenDomainsList:
 		return "Domains"
 	case state.ScreenDomainCreate:
 		return "Create Domain"
+	case state.ScreenDomainEdit:
+		return "Edit Domain"
+	case state.ScreenDomainDnsRecords:
+		return "DNS Records"
+	case state.ScreenDnsRecordForm:
+		return "DNS Record"
+	case state.ScreenDnsSyncDiff:
+		return "DNS Sync"
+	case state.ScreenDeployPreview:
+		return "Deploy Preview"
 	case state.ScreenNodesList:
 		return "Nodes"
 	case state.ScreenNodeCreate:
 		return "Create Node"
+	case state.ScreenNodeEdit:
+		return "Edit Node"
+	case state.ScreenNodeConfig:
+		return "Node Config"
+	case state.ScreenNodeConfigSave:
+		return "Save Node Config"
+	case state.ScreenSettings:
+		return "Settings"
 	case state.ScreenHelp:
 		return "Help"
+	case state.ScreenSetup:
+		return "Setup"
+	case state.ScreenOperations:
+		return "Operations"
+	case state.ScreenNotifications:
+		return "Notifications"
 	default:
 		return string(screen)
 	}
@@ -1,30 +1,44 @@
 package components
 
 import (
+	"github.com/charmbracelet/bubbles/key"
 	"github.com/charmbracelet/bubbles/table"
 	tea "github.com/charmbracelet/bubbletea"
 	"github.com/charmbracelet/lipgloss"
 )
 
-// TableComponent wraps bubbles.table with common styling and mouse support
+// TableComponent wraps bubbles.table with common styling and mouse support. It scrolls the
+// rows itself, handing the bubbles table only the rows in view, so a clicked line can be
+// mapped back to its row.
 type TableComponent struct {
-	table        table.Model
-	headerHeight int
-	menuHeight   int
-	tableStartY  int
-	rowCount     int
+	table    table.Model
+	rows     []table.Row
+	cursor   int            // Selected row, counted over all rows
+	offset   int            // First row in view
+	selected lipgloss.Color // Color of the selected row (see SetSelectedColor)
 }
 
+// tableHeaderLines is the height of the column titles and the border below them
+const tableHeaderLines = 2
+
 // NewTableComponent creates a new table component
 func NewTableComponent(columns []table.Column, rows []table.Row) *TableComponent {
 	t := table.New(
 		table.WithColumns(columns),
 		table.WithRows(rows),
 		table.WithFocused(true),
-		table.WithHeight(10), // Default height, will be adjusted
 	)
 
-	// Apply default styles inline to avoid circular import
+	t.SetStyles(tableStyles(""))
+	t.SetHeight(10) // Default height, will be adjusted; set after the styles, as the header's height counts
+
+	c := &TableComponent{table: t}
+	c.SetRows(rows)
+	return c
+}
+
+// tableStyles returns the table styles, with the selected row in the given color
+func tableStyles(selected lipgloss.Color) table.Styles {
 	s := table.DefaultStyles()
 	s.Header = lipgloss.NewStyle().
 		Bold(true).
@@ -33,18 +47,20 @@ func NewTableComponent(columns []table.Column, rows []table.Row) *TableComponent
 		Align(lipgloss.Left)
 	s.Selected = lipgloss.NewStyle().
 		Bold(true).
-		Underline(true)
+		Underline(true).
+		Foreground(selected)
 	s.Cell = lipgloss.NewStyle().
 		Align(lipgloss.Left)
-	t.SetStyles(s)
-
-	return &TableComponent{
-		table:        t,
-		headerHeight: 1, // App header
-		menuHeight:   1, // Navigation menu
-		tableStartY:  4, // header + menu + table title + table header
-		rowCount:     len(rows),
+	return s
+}
+
+// SetSelectedColor colors the selected row, e.g. with the theme's Selected color
+func (c *TableComponent) SetSelectedColor(color lipgloss.Color) {
+	if color == c.selected {
+		return
 	}
+	c.selected = color
+	c.table.SetStyles(tableStyles(color))
 }
 
 // View returns the rendered table
@@ -54,78 +70,107 @@ func (c *TableComponent) View() string {
 
 // SetCursor sets the selected row index
 func (c *TableComponent) SetCursor(index int) {
-	if index >= 0 && index < c.rowCount {
-		c.table.SetCursor(index)
+	if index >= 0 && index < len(c.rows) {
+		c.moveTo(index)
 	}
 }
 
 // GetCursor returns the current selected row index
 func (c *TableComponent) GetCursor() int {
-	return c.table.Cursor()
+	return c.cursor
 }
 
-// HandleMouseClick calculates which row was clicked based on Y coordinate
-// Returns the row index, or -1 if click was outside the table
+// HandleMouseClick returns the index of the row at clickY, counted from the top line of
+// the table's view, or -1 if the click was on the header or below the last row in view
 func (c *TableComponent) HandleMouseClick(clickY int) int {
-	// TODO: Implement click-to-row mapping
-	// Steps:
-	// 1. Check if clickY is within table bounds
-	// 2. Calculate row index: rowIndex = clickY - c.tableStartY
-	// 3. Validate row index is within 0 to c.rowCount-1
-	// 4. Return row index or -1
-
-	// Example:
-	// if clickY < c.tableStartY {
-	//     return -1 // Clicked above table
-	// }
-	//
-	// rowIndex := clickY - c.tableStartY
-	// if rowIndex >= 0 && rowIndex < c.rowCount {
-	//     return rowIndex
-	// }
-	//
-	// return -1
-
+	line := clickY - tableHeaderLines
+	if line >= 0 && line < len(c.table.Rows()) {
+		return c.offset + line
+	}
 	return -1
 }
 
 // SetRows updates the table with new rows
 func (c *TableComponent) SetRows(rows []table.Row) {
-	c.table.SetRows(rows)
-	c.rowCount = len(rows)
+	c.rows = rows
+	c.moveTo(c.cursor)
+}
+
+// moveTo selects the row at index, clamped to the rows there are, and scrolls as little as
+// possible to keep it in view
+func (c *TableComponent) moveTo(index int) {
+	c.cursor = max(min(index, len(c.rows)-1), 0)
+
+	height := max(c.table.Height(), 1)
+	if c.cursor < c.offset {
+		c.offset = c.cursor
+	} else if c.cursor >= c.offset+height {
+		c.offset = c.cursor - height + 1
+	}
+	// Don't leave blank lines below the last row while rows above it are hidden
+	c.offset = max(min(c.offset, len(c.rows)-height), 0)
+
+	c.table.SetRows(c.rows[c.offset:min(c.offset+height, len(c.rows))])
+	c.table.SetCursor(c.cursor - c.offset)
 }
 
 // SetHeight adjusts the table height based on available space
 func (c *TableComponent) SetHeight(windowHeight int) {
-	// Account for chrome (header, menu, status bar, margins)
-	const chrome = 6
+	// Account for chrome (header, menu, breadcrumb, status bar, margins)
+	const chrome = 7
 	height := windowHeight - chrome
 	if height < 5 {
 		height = 5
 	}
 	c.table.SetHeight(height)
+	c.moveTo(c.cursor)
 }
 
 // MoveUp moves the selection up by one row
 func (c *TableComponent) MoveUp() {
-	c.table.MoveUp(1)
+	c.moveTo(c.cursor - 1)
 }
 
 // MoveDown moves the selection down by one row
 func (c *TableComponent) MoveDown() {
-	c.table.MoveDown(1)
+	c.moveTo(c.cursor + 1)
 }
 
-// Update forwards TEA messages to the underlying table
+// Update moves the selection for the table's navigation keys
 func (c *TableComponent) Update(msg tea.Msg) tea.Cmd {
-	var cmd tea.Cmd
-	c.table, cmd = c.table.Update(msg)
-	return cmd
+	keyMsg, ok := msg.(tea.KeyMsg)
+	if !ok {
+		return nil
+	}
+
+	keys, page := c.table.KeyMap, c.table.Height()
+	switch {
+	case key.Matches(keyMsg, keys.LineUp):
+		c.moveTo(c.cursor - 1)
+	case key.Matches(keyMsg, keys.LineDown):
+		c.moveTo(c.cursor + 1)
+	case key.Matches(keyMsg, keys.PageUp):
+		c.moveTo(c.cursor - page)
+	case key.Matches(keyMsg, keys.PageDown):
+		c.moveTo(c.cursor + page)
+	case key.Matches(keyMsg, keys.HalfPageUp):
+		c.moveTo(c.cursor - page/2)
+	case key.Matches(keyMsg, keys.HalfPageDown):
+		c.moveTo(c.cursor + page/2)
+	case key.Matches(keyMsg, keys.GotoTop):
+		c.moveTo(0)
+	case key.Matches(keyMsg, keys.GotoBottom):
+		c.moveTo(len(c.rows) - 1)
+	}
+	return nil
 }
 
 // GetSelectedRow returns the currently selected row data
 func (c *TableComponent) GetSelectedRow() table.Row {
-	return c.table.SelectedRow()
+	if c.cursor >= len(c.rows) {
+		return nil
+	}
+	return c.rows[c.cursor]
 }
 
 // SetWidth adjusts the table width and proportionally scales columns
@@ -0,0 +1,67 @@
+package components
+
+import (
+	"fmt"
+	"testing"
+
+	"github.com/charmbracelet/bubbles/table"
+	tea "github.com/charmbracelet/bubbletea"
+)
+
+func newTestTable(rowCount int) *TableComponent {
+	rows := make([]table.Row, rowCount)
+	for i := range rows {
+		rows[i] = table.Row{fmt.Sprintf("row-%d", i)}
+	}
+	return NewTableComponent([]table.Column{{Title: "Name", Width: 10}}, rows)
+}
+
+func TestHandleMouseClick(t *testing.T) {
+	// The default height shows 8 rows below the 2 header lines
+	tests := []struct {
+		name   string
+		rows   int
+		cursor int
+		downs  int // Down key presses after setting the cursor
+		clickY int
+		want   int
+	}{
+		{name: "column titles", rows: 20, clickY: 0, want: -1},
+		{name: "header border", rows: 20, clickY: 1, want: -1},
+		{name: "first row", rows: 20, clickY: 2, want: 0},
+		{name: "last row in view", rows: 20, clickY: 9, want: 7},
+		{name: "below the rows in view", rows: 20, clickY: 10, want: -1},
+		{name: "below the last row", rows: 3, clickY: 5, want: -1},
+		{name: "scrolled by selecting a row", rows: 20, cursor: 15, clickY: 2, want: 8},
+		{name: "scrolled row at the bottom", rows: 20, cursor: 15, clickY: 9, want: 15},
+		{name: "scrolled with the keyboard", rows: 20, downs: 10, clickY: 2, want: 3},
+		{name: "scrolled to the end", rows: 20, cursor: 19, clickY: 9, want: 19},
+	}
+
+	for _, tt := range tests {
+		t.Run(tt.name, func(t *testing.T) {
+			c := newTestTable(tt.rows)
+			c.SetCursor(tt.cursor)
+			for i := 0; i < tt.downs; i++ {
+				c.Update(tea.KeyMsg{Type: tea.KeyDown})
+			}
+			if got := c.HandleMouseClick(tt.clickY); got != tt.want {
+				t.Errorf("HandleMouseClick(%d) = %d, want %d", tt.clickY, got, tt.want)
+			}
+		})
+	}
+}
+
+func TestTableKeepsScrollWhenScrollingBack(t *testing.T) {
+	c := newTestTable(20)
+	c.SetCursor(15)
+	c.SetCursor(10)
+
+	// Row 10 was already in view, so the table doesn't scroll
+	if got := c.HandleMouseClick(2); got != 8 {
+		t.Errorf("HandleMouseClick(2) = %d, want 8", got)
+	}
+	if row := c.GetSelectedRow(); len(row) == 0 || row[0] != "row-10" {
+		t.Errorf("GetSelectedRow() = %v, want [row-10]", row)
+	}
+}
@@ -5,6 +5,7 @@ import (
 	zone "github.com/lrstanley/bubblezone"
 
 	"github.com/BlueBeard63/archon/internal/state"
+	"github.com/BlueBeard63/archon/internal/ui/theme"
 )
 
 var (
@@ -31,26 +32,29 @@ var (
 		BottomLeft:  "╰",
 		BottomRight: "╯",
 	}
-
-	// Active tab style
-	activeTabStyle = lipgloss.NewStyle().
-			Border(activeTabBorder, true).
-			BorderForeground(lipgloss.Color("36")).
-			Padding(0, 1).
-			Bold(true)
-
-	// Inactive tab style
-	inactiveTabStyle = lipgloss.NewStyle().
-				Border(tabBorder, true).
-				BorderForeground(lipgloss.Color("240")).
-				Padding(0, 1)
-
-	// Tab gap style (fills space between tabs and edge)
-	tabGapStyle = lipgloss.NewStyle().
-			Border(lipgloss.Border{Bottom: "─"}, false, false, true, false).
-			BorderForeground(lipgloss.Color("240"))
 )
 
+// tabStyles returns the active tab, inactive tab and gap styles colored with t
+func tabStyles(t theme.Theme) (lipgloss.Style, lipgloss.Style, lipgloss.Style) {
+	active := lipgloss.NewStyle().
+		Border(activeTabBorder, true).
+		BorderForeground(t.Accent).
+		Padding(0, 1).
+		Bold(true)
+
+	inactive := lipgloss.NewStyle().
+		Border(tabBorder, true).
+		BorderForeground(t.Muted).
+		Padding(0, 1)
+
+	// Fills the space between the tabs and the edge
+	gap := lipgloss.NewStyle().
+		Border(lipgloss.Border{Bottom: "─"}, false, false, true, false).
+		BorderForeground(t.Muted)
+
+	return active, inactive, gap
+}
+
 // Tab represents a single tab item
 type Tab struct {
 	ID     string
@@ -82,8 +86,9 @@ func NewTabBar() *TabBar {
 }
 
 // Render renders the tab bar (without zones)
-func (t *TabBar) Render(currentScreen state.Screen) string {
+func (t *TabBar) Render(currentScreen state.Screen, th theme.Theme) string {
 	t.Active = currentScreen
+	activeTabStyle, inactiveTabStyle, tabGapStyle := tabStyles(th)
 
 	var renderedTabs []string
 	for _, tab := range t.Tabs {
@@ -109,12 +114,13 @@ func (t *TabBar) Render(currentScreen state.Screen) string {
 }
 
 // RenderWithZones renders the tab bar with clickable zones
-func (t *TabBar) RenderWithZones(currentScreen state.Screen, zm *zone.Manager) string {
+func (t *TabBar) RenderWithZones(currentScreen state.Screen, zm *zone.Manager, th theme.Theme) string {
 	if zm == nil {
-		return t.Render(currentScreen)
+		return t.Render(currentScreen, th)
 	}
 
 	t.Active = currentScreen
+	activeTabStyle, inactiveTabStyle, tabGapStyle := tabStyles(th)
 
 	var renderedTabs []string
 	for _, tab := range t.Tabs {
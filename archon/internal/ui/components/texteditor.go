@@ -0,0 +1,46 @@
+package components
+
+import (
+	"fmt"
+	"strings"
+
+	"github.com/charmbracelet/lipgloss"
+
+	"github.com/BlueBeard63/archon/internal/state"
+	"github.com/BlueBeard63/archon/internal/ui/theme"
+)
+
+// RenderTextEditor renders height lines of the editor with line numbers inside a border,
+// scrolled to keep the cursor in view. The cursor is only drawn when focused.
+func RenderTextEditor(e *state.TextEditor, width, height int, focused bool, t theme.Theme) string {
+	borderColor := t.Muted
+	if focused {
+		borderColor = t.Primary
+	}
+	boxStyle := lipgloss.NewStyle().
+		Border(lipgloss.RoundedBorder()).
+		BorderForeground(borderColor).
+		Width(width)
+	numberStyle := lipgloss.NewStyle().Faint(true)
+
+	e.ScrollTo(height)
+	numberWidth := len(fmt.Sprint(len(e.Lines)))
+
+	var b strings.Builder
+	for row := e.Scroll; row < e.Scroll+height; row++ {
+		if row > e.Scroll {
+			b.WriteString("\n")
+		}
+		if row >= len(e.Lines) {
+			b.WriteString(numberStyle.Render(fmt.Sprintf("%*s", numberWidth, "~")))
+			continue
+		}
+		line := e.Lines[row]
+		if focused && row == e.Row {
+			line = line[:e.Col] + "_" + line[e.Col:]
+		}
+		b.WriteString(numberStyle.Render(fmt.Sprintf("%*d", numberWidth, row+1)) + " " + line)
+	}
+
+	return boxStyle.Render(b.String())
+}
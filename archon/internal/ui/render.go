@@ -6,13 +6,14 @@ import (
 	"github.com/BlueBeard63/archon/internal/state"
 	"github.com/BlueBeard63/archon/internal/ui/components"
 	"github.com/BlueBeard63/archon/internal/ui/screens"
+	"github.com/BlueBeard63/archon/internal/ui/theme"
 	"github.com/charmbracelet/lipgloss"
 )
 
 var tabBar = components.NewTabBar()
 
 // Render is the main rendering function that routes to appropriate screen (without zones)
-func Render(s *state.AppState) string {
+func Render(s *state.AppState, t theme.Theme) string {
 	// Update tab bar width based on window width
 	tabBar.Width = s.WindowWidth
 	if tabBar.Width == 0 {
@@ -20,29 +21,38 @@ func Render(s *state.AppState) string {
 	}
 
 	// Render header
-	header := RenderHeader()
+	header := RenderHeader(t)
 
 	// Render tab navigation bar
-	tabs := tabBar.Render(s.CurrentScreen)
+	tabs := tabBar.Render(s.CurrentScreen, t)
+
+	// Render the path to the current screen under the tabs
+	breadcrumb := components.RenderBreadcrumb(s, s.WindowWidth, t)
 
 	// Render main content based on current screen
-	content := RenderScreen(s, nil)
+	content := RenderScreen(s, nil, t)
+	if s.PendingConfirmation != nil {
+		content = renderConfirmOverlay(s, content, t)
+	} else if s.Palette != nil {
+		content = components.RenderPalette(s, lipgloss.Width(content), lipgloss.Height(content), t)
+	}
 
 	// Render status bar with notifications
-	statusBar := components.RenderStatusBar(s, s.WindowWidth)
+	statusBar := components.RenderStatusBar(s, s.WindowWidth, t)
 
 	// Join all sections vertically
 	return lipgloss.JoinVertical(
 		lipgloss.Left,
 		header,
 		tabs,
+		breadcrumb,
 		content,
 		statusBar,
 	)
 }
 
 // RenderWithZones is the main rendering function with bubblezone support
-func RenderWithZones(s *state.AppState, zm *zone.Manager) string {
+func RenderWithZones(s *state.AppState, zm *zone.Manager, t theme.Theme) string {
 	// Update tab bar width based on window width
 	tabBar.Width = s.WindowWidth
 	if tabBar.Width == 0 {
@@ -50,66 +60,104 @@ func RenderWithZones(s *state.AppState, zm *zone.Manager) string {
 	}
 
 	// Render header
-	header := RenderHeader()
+	header := RenderHeader(t)
 
 	// Render tab navigation bar with zones
-	tabs := tabBar.RenderWithZones(s.CurrentScreen, zm)
+	tabs := tabBar.RenderWithZones(s.CurrentScreen, zm, t)
+
+	// Render the path to the current screen under the tabs
+	breadcrumb := components.RenderBreadcrumb(s, s.WindowWidth, t)
 
 	// Render main content based on current screen with zones
-	content := RenderScreen(s, zm)
+	content := RenderScreen(s, zm, t)
+	if s.PendingConfirmation != nil {
+		content = renderConfirmOverlay(s, content, t)
+	} else if s.Palette != nil {
+		content = components.RenderPalette(s, lipgloss.Width(content), lipgloss.Height(content), t)
+	}
 
 	// Render status bar with notifications
-	statusBar := components.RenderStatusBar(s, s.WindowWidth)
+	statusBar := components.RenderStatusBar(s, s.WindowWidth, t)
 
 	// Join all sections vertically
 	return lipgloss.JoinVertical(
 		lipgloss.Left,
 		header,
 		tabs,
+		breadcrumb,
 		content,
 		statusBar,
 	)
 }
 
+// renderConfirmOverlay replaces the screen content with the confirmation dialog, keeping its size
+func renderConfirmOverlay(s *state.AppState, content string, t theme.Theme) string {
+	return components.RenderConfirmDialog(s.PendingConfirmation, lipgloss.Width(content), lipgloss.Height(content), t)
+}
+
 // RenderHeader renders the top header bar
-func RenderHeader() string {
-	style := lipgloss.NewStyle().Bold(true).Padding(0, 1)
+func RenderHeader(t theme.Theme) string {
+	style := lipgloss.NewStyle().
+		Foreground(t.Primary).
+		Background(t.Background).
+		Bold(true).
+		Padding(0, 1)
 	return style.Render("⚡ ARCHON TUI - Docker Site Manager")
 }
 
 // RenderScreen routes to the appropriate screen renderer
-func RenderScreen(s *state.AppState, zm *zone.Manager) string {
+func RenderScreen(s *state.AppState, zm *zone.Manager, t theme.Theme) string {
 	switch s.CurrentScreen {
 	case state.ScreenDashboard:
-		return screens.RenderDashboard(s)
+		return screens.RenderDashboardWithZones(s, zm, t)
 	case state.ScreenSitesList:
-		return screens.RenderSitesListWithZones(s, zm)
+		return screens.RenderSitesListWithZones(s, zm, t)
 	case state.ScreenSiteCreate:
-		return screens.RenderSiteCreateWithZones(s, zm)
+		return screens.RenderSiteCreateWithZones(s, zm, t)
 	case state.ScreenSiteEdit:
-		return screens.RenderSiteEditWithZones(s, zm)
+		return screens.RenderSiteEditWithZones(s, zm, t)
 	case state.ScreenSiteEnvVars:
-		return screens.RenderSiteEnvVarsWithZones(s, zm)
+		return screens.RenderSiteEnvVarsWithZones(s, zm, t)
+	case state.ScreenSiteConfigFiles:
+		return screens.RenderSiteConfigFiles(s, t)
+	case state.ScreenSiteDetail:
+		return screens.RenderSiteDetail(s, t)
+	case state.ScreenSitesDeleted:
+		return screens.RenderSitesDeleted(s)
 	case state.ScreenDomainsList:
-		return screens.RenderDomainsListWithZones(s, zm)
+		return screens.RenderDomainsListWithZones(s, zm, t)
 	case state.ScreenDomainCreate:
-		return screens.RenderDomainCreateWithZones(s, zm)
+		return screens.RenderDomainCreateWithZones(s, zm, t)
 	case state.ScreenDomainEdit:
-		return screens.RenderDomainEditWithZones(s, zm)
+		return screens.RenderDomainEditWithZones(s, zm, t)
+	case state.ScreenDomainDnsRecords:
+		return screens.RenderDomainDnsRecords(s, s.SelectedDomainID.String(), t)
+	case state.ScreenDnsRecordForm:
+		return screens.RenderDnsRecordForm(s, t)
+	case state.ScreenDnsSyncDiff:
+		return screens.RenderDnsSyncDiff(s, t)
+	case state.ScreenDeployPreview:
+		return screens.RenderDeployPreview(s)
+	case state.ScreenOperations:
+		return screens.RenderOperations(s, t)
+	case state.ScreenNotifications:
+		return screens.RenderNotifications(s, t)
 	case state.ScreenNodesList:
-		return screens.RenderNodesListWithZones(s, zm)
+		return screens.RenderNodesListWithZones(s, zm, t)
 	case state.ScreenNodeCreate:
-		return screens.RenderNodeCreateWithZones(s, zm)
+		return screens.RenderNodeCreateWithZones(s, zm, t)
 	case state.ScreenNodeEdit:
-		return screens.RenderNodeEditWithZones(s, zm)
+		return screens.RenderNodeEditWithZones(s, zm, t)
 	case state.ScreenNodeConfig:
 		return screens.RenderNodeConfig(s)
 	case state.ScreenNodeConfigSave:
-		return screens.RenderNodeConfigSaveWithZones(s, zm)
+		return screens.RenderNodeConfigSaveWithZones(s, zm, t)
 	case state.ScreenSettings:
 		return screens.RenderSettingsWithZones(s, zm)
 	case state.ScreenHelp:
-		return screens.RenderHelp()
+		return screens.RenderHelp(s)
+	case state.ScreenSetup:
+		return screens.RenderSetup(s, t)
 	default:
 		return TitleStyle.Render("Unknown Screen")
 	}
@@ -0,0 +1,86 @@
+package screens
+
+import (
+	"fmt"
+	"strings"
+
+	"github.com/charmbracelet/lipgloss"
+
+	"github.com/BlueBeard63/archon/internal/state"
+	"github.com/BlueBeard63/archon/internal/ui/components"
+	"github.com/BlueBeard63/archon/internal/ui/theme"
+)
+
+// RenderSiteConfigFiles renders the config files of the site form, or the file being edited
+func RenderSiteConfigFiles(s *state.AppState, t theme.Theme) string {
+	// The site form's name field, which may not be saved yet
+	siteName := "New Site"
+	if len(s.FormFields) > 0 && s.FormFields[0] != "" {
+		siteName = s.FormFields[0]
+	}
+	title := titleStyle.Render("📄 Config Files: " + siteName)
+
+	if s.ConfigFileForm != nil {
+		return title + "\n\n" + renderConfigFileForm(s, t)
+	}
+
+	var content string
+	if len(s.ConfigFileDrafts) == 0 {
+		content = helpStyle.Render("No config files yet. Press 'a' to add one.") + "\n"
+	}
+	for i, file := range s.ConfigFileDrafts {
+		lines := strings.Count(file.Content, "\n") + 1
+		line := fmt.Sprintf("%s → %s", file.Name, file.ContainerPath)
+		detail := lipgloss.NewStyle().Faint(true).Render(fmt.Sprintf("  (%d lines)", lines))
+		if i == s.ConfigFilesIndex {
+			content += formLabelFocusedStyle(t).Render("▶ "+line) + detail + "\n"
+		} else {
+			content += "  " + line + detail + "\n"
+		}
+	}
+
+	help := helpStyle.Render("a: add • e/Enter: edit • d: remove • ↑/↓: select • Esc: back")
+	note := helpStyle.Render("Files are mounted into the container and saved with the site")
+
+	return title + "\n\n" + content + "\n" + help + "\n" + note
+}
+
+// renderConfigFileForm renders the name, container path and content of the file being edited
+func renderConfigFileForm(s *state.AppState, t theme.Theme) string {
+	form := s.ConfigFileForm
+
+	heading := "Add Config File"
+	if form.Index >= 0 {
+		heading = "Edit Config File"
+	}
+
+	singleLine := func(e *state.TextEditor, focused bool) string {
+		value := e.Value()
+		if focused {
+			value = value[:e.Col] + "_" + value[e.Col:]
+		}
+		return value
+	}
+
+	fields := renderFieldLabel("Name:", form.Field == 0, t) + " " + singleLine(form.Name, form.Field == 0) + "\n"
+	fields += "  " + lipgloss.NewStyle().Faint(true).Render("File name, e.g. nginx.conf") + "\n\n"
+	fields += renderFieldLabel("Container Path:", form.Field == 1, t) + " " + singleLine(form.ContainerPath, form.Field == 1) + "\n"
+	fields += "  " + lipgloss.NewStyle().Faint(true).Render("Absolute path inside the container, e.g. /etc/nginx/nginx.conf") + "\n\n"
+	fields += renderFieldLabel("Content:", form.Field == 2, t) + " " +
+		lipgloss.NewStyle().Faint(true).Render("${name}, ${domain}, ${port} and ${env.NAME} are filled in on deploy ($${...} for a literal)") + "\n"
+
+	// Leave room for the title, fields and help around the editor
+	height := s.WindowHeight - 19
+	if height < 5 {
+		height = 5
+	}
+	width := s.WindowWidth - 6
+	if width < 40 {
+		width = 40
+	}
+	editor := components.RenderTextEditor(form.Content, width, height, form.Field == 2, t)
+
+	help := helpStyle.Render("Tab/Shift+Tab: switch field • Enter: new line (content) • Ctrl+S: save file • Esc: cancel")
+
+	return heading + "\n\n" + fields + editor + "\n" + help
+}
@@ -2,9 +2,12 @@ package screens
 
 import (
 	"fmt"
+	"strings"
 
 	"github.com/charmbracelet/lipgloss"
+	zone "github.com/lrstanley/bubblezone"
 	"github.com/BlueBeard63/archon/internal/state"
+	"github.com/BlueBeard63/archon/internal/ui/theme"
 )
 
 // Inline styles to avoid circular import
@@ -21,9 +24,19 @@ var (
 )
 
 // RenderDashboard renders the main dashboard with 3-column layout
-func RenderDashboard(s *state.AppState) string {
+func RenderDashboard(s *state.AppState, t theme.Theme) string {
+	return RenderDashboardWithZones(s, nil, t)
+}
+
+// RenderDashboardWithZones renders the dashboard, making failed sites clickable when zm is set
+func RenderDashboardWithZones(s *state.AppState, zm *zone.Manager, t theme.Theme) string {
 	title := titleStyle.Render("📊 Dashboard")
 
+	// Failed sites go above the summaries so they are seen first
+	if alerts := renderFailedSites(s, zm, t); alerts != "" {
+		title += "\n\n" + alerts
+	}
+
 	// Render summaries
 	leftColumn := renderSitesSummary(s)
 	middleColumn := renderNodesSummary(s)
@@ -39,11 +52,47 @@ func RenderDashboard(s *state.AppState) string {
 		rightColumn,
 	)
 
-	help := helpStyle.Render("\nPress 1 or s for Sites • 2 or d for Domains • 3 or n for Nodes • 4 or c for Settings • ? for Help • q to Quit")
+	help := helpStyle.Render("\nPress 1 or s for Sites • 2 or d for Domains • 3 or n for Nodes • 4 or c for Settings • r to refresh • ? for Help • q to Quit")
 
 	return title + "\n\n" + columns + "\n" + help
 }
 
+// renderFailedSites renders a panel listing every failed site with its node and last error,
+// or nothing when no site has failed
+func renderFailedSites(s *state.AppState, zm *zone.Manager, t theme.Theme) string {
+	failed := s.FailedSites()
+	if len(failed) == 0 {
+		return ""
+	}
+
+	var lines []string
+	for i, site := range failed {
+		nodeName := "unknown node"
+		if node := s.GetNodeByID(site.NodeID); node != nil {
+			nodeName = node.Name
+		}
+		reason := site.LastError
+		if reason == "" {
+			reason = "no error recorded"
+		}
+
+		line := fmt.Sprintf("✗ %s on %s: %s", site.Name, nodeName, truncate(reason, 70))
+		if i == s.DashboardAlertIndex || (i == len(failed)-1 && s.DashboardAlertIndex >= len(failed)) {
+			line = alertStyle(t).Bold(true).Render("▶ " + line)
+		} else {
+			line = alertStyle(t).Render("  " + line)
+		}
+		if zm != nil {
+			line = zm.Mark("alert:site-"+site.ID.String(), line)
+		}
+		lines = append(lines, line)
+	}
+
+	header := titleStyle.Render(fmt.Sprintf("🚨 Failed Sites (%d)", len(failed)))
+	help := helpStyle.Render("j/k to select • Enter or click to view details")
+	return alertBoxStyle(t).Render(header + "\n" + strings.Join(lines, "\n") + "\n\n" + help)
+}
+
 // renderBox renders content in a box with title
 func renderBox(title, content string) string {
 	titleText := titleStyle.Render(title)
@@ -80,13 +129,15 @@ func renderSitesSummary(s *state.AppState) string {
 
 // renderNodesSummary renders the nodes summary box
 func renderNodesSummary(s *state.AppState) string {
-	total := len(s.Nodes)
+	// Only summarize nodes matching the active tag filter
+	nodes := s.VisibleNodes()
+	total := len(nodes)
 
 	// Show first few node names for debugging
 	nodeNames := ""
 	if total > 0 {
 		nodeNames = "\n\nNodes:\n"
-		for i, node := range s.Nodes {
+		for i, node := range nodes {
 			if i < 3 {
 				nodeNames += fmt.Sprintf("• %s\n", node.Name)
 			}
@@ -96,14 +147,26 @@ func renderNodesSummary(s *state.AppState) string {
 		}
 	}
 
+	totals := s.FleetTotals()
 	content := fmt.Sprintf(
 		"Total Nodes: %d%s\n\n"+
-			"Press 'n' or '3' to manage nodes",
+			"Containers running: %d\n"+
+			"Images: %d\n"+
+			"Traefik: %d routers, %d services\n\n"+
+			"Press 'n' or '3' to manage nodes, 't' to filter by tag",
 		total,
 		nodeNames,
+		totals.ContainersRunning,
+		totals.Images,
+		totals.Routers,
+		totals.Services,
 	)
 
-	return renderBox("🖥️  Nodes", content)
+	title := "🖥️  Nodes"
+	if s.NodeTagFilter != "" {
+		title += " (tag: " + s.NodeTagFilter + ")"
+	}
+	return renderBox(title, content)
 }
 
 // renderDomainsSummary renders the domains summary box
@@ -3,6 +3,7 @@ package screens
 import (
 	"fmt"
 	"strings"
+	"time"
 
 	"github.com/charmbracelet/bubbles/table"
 	"github.com/charmbracelet/lipgloss"
@@ -11,19 +12,18 @@ import (
 	"github.com/BlueBeard63/archon/internal/models"
 	"github.com/BlueBeard63/archon/internal/state"
 	"github.com/BlueBeard63/archon/internal/ui/components"
+	"github.com/BlueBeard63/archon/internal/ui/theme"
 )
 
-var (
-	notificationWarningStyle = lipgloss.NewStyle().Bold(true)
-)
+var notificationWarningStyle = lipgloss.NewStyle().Bold(true)
 
 // RenderDomainsList renders the domains list screen with table
-func RenderDomainsList(s *state.AppState) string {
-	return RenderDomainsListWithZones(s, nil)
+func RenderDomainsList(s *state.AppState, t theme.Theme) string {
+	return RenderDomainsListWithZones(s, nil, t)
 }
 
 // RenderDomainsListWithZones renders domains list with table and button zones
-func RenderDomainsListWithZones(s *state.AppState, zm *zone.Manager) string {
+func RenderDomainsListWithZones(s *state.AppState, zm *zone.Manager, t theme.Theme) string {
 	title := titleStyle.Render("🌍 Domains")
 
 	// Create button group
@@ -35,9 +35,9 @@ func RenderDomainsListWithZones(s *state.AppState, zm *zone.Manager) string {
 
 	var buttons string
 	if zm != nil {
-		buttons = buttonGroup.RenderWithZones(zm)
+		buttons = buttonGroup.RenderWithZones(zm, t)
 	} else {
-		buttons = buttonGroup.Render()
+		buttons = buttonGroup.Render(t)
 	}
 
 	var content string
@@ -57,11 +57,24 @@ func RenderDomainsListWithZones(s *state.AppState, zm *zone.Manager) string {
 				traefikStatus = "Yes"
 			}
 
+			certStatus := "-"
+			if days, ok := domain.CertDaysLeft(time.Now()); ok {
+				switch {
+				case days < 0:
+					certStatus = "✗ expired"
+				case days < models.CertExpiryWarningDays:
+					certStatus = fmt.Sprintf("⚠ %dd", days)
+				default:
+					certStatus = fmt.Sprintf("%dd", days)
+				}
+			}
+
 			rows = append(rows, table.Row{
 				truncate(domain.Name, 30),
 				truncate(providerName, 15),
 				fmt.Sprintf("%d", len(domain.DnsRecords)),
 				traefikStatus,
+				certStatus,
 			})
 		}
 
@@ -72,6 +85,7 @@ func RenderDomainsListWithZones(s *state.AppState, zm *zone.Manager) string {
 				{Title: "Provider", Width: 15},
 				{Title: "Records", Width: 8},
 				{Title: "Traefik", Width: 8},
+				{Title: "Cert", Width: 10},
 			}
 			s.DomainsTable = components.NewTableComponent(columns, rows)
 			s.DomainsTable.SetCursor(s.DomainsListIndex)
@@ -81,7 +95,12 @@ func RenderDomainsListWithZones(s *state.AppState, zm *zone.Manager) string {
 		}
 
 		// 3. Render table view
+		s.DomainsTable.SetSelectedColor(t.Selected)
 		tableView := s.DomainsTable.View()
+		if zm != nil {
+			// Rows are selected by clicking, see TableComponent.HandleMouseClick
+			tableView = zm.Mark("table:domains", tableView)
+		}
 
 		// 4. Build action buttons column (aligned with rows)
 		var actionsColumn strings.Builder
@@ -105,9 +124,9 @@ func RenderDomainsListWithZones(s *state.AppState, zm *zone.Manager) string {
 
 			var actionLine string
 			if zm != nil {
-				actionLine = editBtn.RenderWithZone(zm) + " " + deleteBtn.RenderWithZone(zm)
+				actionLine = editBtn.RenderWithZone(zm, t) + " " + deleteBtn.RenderWithZone(zm, t)
 			} else {
-				actionLine = editBtn.Render() + " " + deleteBtn.Render()
+				actionLine = editBtn.Render(t) + " " + deleteBtn.Render(t)
 			}
 
 			actionsColumn.WriteString(actionLine + "\n")
@@ -124,7 +143,7 @@ func RenderDomainsListWithZones(s *state.AppState, zm *zone.Manager) string {
 		var sidebar string
 		if len(s.Domains) > 0 && s.DomainsListIndex >= 0 && s.DomainsListIndex < len(s.Domains) {
 			domain := &s.Domains[s.DomainsListIndex]
-			sidebar = renderDomainSidebar(s, domain)
+			sidebar = renderDomainSidebar(s, domain, t)
 		}
 
 		// 7. Join main content + sidebar
@@ -140,7 +159,7 @@ func RenderDomainsListWithZones(s *state.AppState, zm *zone.Manager) string {
 		}
 	}
 
-	help := helpStyle.Render("\n\nPress j/k or arrows to navigate • e to edit • d to delete • n to create • Esc to go back")
+	help := helpStyle.Render("\n\nPress j/k or arrows to navigate • e to edit • d to delete • s to sync DNS • p to push DNS • t to check certificates • y to copy name • r to refresh all • n to create • Esc to go back")
 
 	return lipgloss.JoinVertical(
 		lipgloss.Left,
@@ -154,12 +173,12 @@ func RenderDomainsListWithZones(s *state.AppState, zm *zone.Manager) string {
 }
 
 // RenderDomainCreate renders the domain creation form
-func RenderDomainCreate(s *state.AppState) string {
-	return RenderDomainCreateWithZones(s, nil)
+func RenderDomainCreate(s *state.AppState, t theme.Theme) string {
+	return RenderDomainCreateWithZones(s, nil, t)
 }
 
 // RenderDomainCreateWithZones renders the domain creation form with clickable field
-func RenderDomainCreateWithZones(s *state.AppState, zm *zone.Manager) string {
+func RenderDomainCreateWithZones(s *state.AppState, zm *zone.Manager, t theme.Theme) string {
 	// Initialize form if needed
 	// Fields: 0=domain name, 1=provider, 2=zone/hosted zone ID, 3=access key (route53 only), 4=secret key (route53 only)
 	if len(s.FormFields) != 5 {
@@ -178,7 +197,7 @@ func RenderDomainCreateWithZones(s *state.AppState, zm *zone.Manager) string {
 		labels = []string{
 			"Domain Name:",
 			"DNS Provider:",
-			"Cloudflare Zone ID:",
+			"Cloudflare Zone ID (optional):",
 		}
 	case "route53":
 		labels = []string{
@@ -203,7 +222,7 @@ func RenderDomainCreateWithZones(s *state.AppState, zm *zone.Manager) string {
 		helpTexts = []string{
 			"e.g., example.com",
 			"Select DNS provider",
-			"Found in Cloudflare domain overview (32 characters)",
+			"Leave blank to look it up with the API token set in Settings, or copy it from the Cloudflare domain overview",
 		}
 	case "route53":
 		helpTexts = []string{
@@ -227,19 +246,18 @@ func RenderDomainCreateWithZones(s *state.AppState, zm *zone.Manager) string {
 		displayValue := value
 		isFocused := i == s.CurrentFieldIndex
 
-		// Mask sensitive fields (Route53 keys only) when not focused
-		isSensitive := (providerType == "route53" && (i == 3 || i == 4))
-		if isSensitive && value != "" && !isFocused {
-			displayValue = "••••••••••••••••"
+		// Mask sensitive fields (Route53 keys only), also while typing, unless revealed
+		if providerType == "route53" && (i == 3 || i == 4) {
+			displayValue = maskSecret(value, s.RevealSecrets)
 		}
 
 		// Show cursor if focused
 		if isFocused {
-			displayValue = value + "_"
+			displayValue += "_"
 		}
 
 		// Render label with focus styling
-		styledLabel := renderFieldLabel(label, isFocused)
+		styledLabel := renderFieldLabel(label, isFocused, t)
 
 		// Wrap the field line in a clickable zone
 		fieldLine := styledLabel + " " + displayValue + "\n"
@@ -307,12 +325,12 @@ func renderProviderDropdown(providers []string, selectedIndex int) string {
 }
 
 // RenderDomainEdit renders the domain edit form
-func RenderDomainEdit(s *state.AppState) string {
-	return RenderDomainEditWithZones(s, nil)
+func RenderDomainEdit(s *state.AppState, t theme.Theme) string {
+	return RenderDomainEditWithZones(s, nil, t)
 }
 
 // RenderDomainEditWithZones renders the domain edit form with clickable field
-func RenderDomainEditWithZones(s *state.AppState, zm *zone.Manager) string {
+func RenderDomainEditWithZones(s *state.AppState, zm *zone.Manager, t theme.Theme) string {
 	// Find the domain
 	var domain *models.Domain
 	for i := range s.Domains {
@@ -407,19 +425,18 @@ func RenderDomainEditWithZones(s *state.AppState, zm *zone.Manager) string {
 		displayValue := value
 		isFocused := i == s.CurrentFieldIndex
 
-		// Mask sensitive fields (Route53 keys only) when not focused
-		isSensitive := (providerType == "route53" && (i == 3 || i == 4))
-		if isSensitive && value != "" && !isFocused {
-			displayValue = "••••••••••••••••"
+		// Mask sensitive fields (Route53 keys only), also while typing, unless revealed
+		if providerType == "route53" && (i == 3 || i == 4) {
+			displayValue = maskSecret(value, s.RevealSecrets)
 		}
 
 		// Show cursor if focused
 		if isFocused {
-			displayValue = value + "_"
+			displayValue += "_"
 		}
 
 		// Render label with focus styling
-		styledLabel := renderFieldLabel(label, isFocused)
+		styledLabel := renderFieldLabel(label, isFocused, t)
 
 		// Wrap the field line in a clickable zone
 		fieldLine := styledLabel + " " + displayValue + "\n"
@@ -459,7 +476,7 @@ func RenderDomainEditWithZones(s *state.AppState, zm *zone.Manager) string {
 }
 
 // RenderDomainDnsRecords renders DNS records for a domain
-func RenderDomainDnsRecords(s *state.AppState, domainID string) string {
+func RenderDomainDnsRecords(s *state.AppState, domainID string, t theme.Theme) string {
 	title := titleStyle.Render("DNS Records")
 
 	// Find the domain
@@ -489,10 +506,15 @@ func RenderDomainDnsRecords(s *state.AppState, domainID string) string {
 		content += "No DNS records configured\n"
 	} else {
 		content += "DNS Records:\n\n"
-		content += fmt.Sprintf("%-8s %-25s %-30s %-8s %-8s\n", "Type", "Name", "Value", "TTL", "Proxied")
-		content += fmt.Sprintf("%s\n", "--------------------------------------------------------------------------------")
+		content += fmt.Sprintf("  %-8s %-25s %-30s %-8s %-8s %-10s %-10s\n", "Type", "Name", "Value", "TTL", "Proxied", "Sync", "Resolves")
+		content += fmt.Sprintf("  %s\n", "------------------------------------------------------------------------------------------------------")
+
+		for i, record := range domain.DnsRecords {
+			prefix := "  "
+			if i == s.DnsRecordsIndex {
+				prefix = "▶ "
+			}
 
-		for _, record := range domain.DnsRecords {
 			proxied := "No"
 			if record.Proxied {
 				proxied = "Yes"
@@ -508,29 +530,181 @@ func RenderDomainDnsRecords(s *state.AppState, domainID string) string {
 				value = value[:27] + "..."
 			}
 
-			content += fmt.Sprintf("%-8s %-25s %-30s %-8d %-8s\n",
+			syncState := string(record.SyncState)
+			if syncState == "" {
+				syncState = string(models.DnsSyncStateSynced)
+			}
+
+			propagation, checked := s.GetPropagation(domain.ID, record)
+			resolves := "-"
+			if checked {
+				resolves = fmt.Sprintf("%d/%d", propagation.Matched(), len(propagation.Resolvers))
+			}
+
+			row := fmt.Sprintf("%s%-8s %-25s %-30s %-8d %-8s %-10s %-10s",
+				prefix,
 				record.RecordType,
 				name,
 				value,
 				record.TTL,
 				proxied,
+				syncState,
+				resolves,
 			)
+			if i == s.DnsRecordsIndex {
+				row = formLabelFocusedStyle(t).Render(row)
+			}
+			content += row + "\n"
+
+			// Show the provider's value for conflicting records
+			if record.SyncState == models.DnsSyncStateConflict {
+				content += notificationWarningStyle.Render(fmt.Sprintf("           ⚠ Provider has: %s", record.ConflictValue)) + "\n"
+			}
+
+			// Show what the resolvers that don't have the record yet returned
+			if checked {
+				for _, res := range propagation.Resolvers {
+					if res.Match {
+						continue
+					}
+					answer := strings.Join(res.Values, ", ")
+					if res.Error != nil {
+						answer = res.Error.Error()
+					} else if answer == "" {
+						answer = "no answer"
+					}
+					content += notificationWarningStyle.Render(fmt.Sprintf("           ⚠ %s returned: %s", res.Server, truncate(answer, 60))) + "\n"
+				}
+			}
 		}
 	}
 
-	help := helpStyle.Render("\nPress n to create record • Esc to go back")
+	help := helpStyle.Render("\na to add • e to edit • d to delete • s to sync from provider • p/P to push • v to check propagation • Esc to go back")
 	if domain.IsManualDNS() {
-		help = helpStyle.Render("\nPress n to add record (manual config required) • Esc to go back")
+		help = helpStyle.Render("\na to add (manual config required) • e to edit • d to delete • v to check propagation • Esc to go back")
 	}
 
 	return title + "\n\n" + content + "\n" + help
 }
 
+// RenderDnsRecordForm renders the form for adding or editing one of the selected domain's DNS records
+func RenderDnsRecordForm(s *state.AppState, t theme.Theme) string {
+	title := titleStyle.Render("Add DNS Record")
+	if s.DnsRecordFormIndex >= 0 {
+		title = titleStyle.Render("Edit DNS Record")
+	}
+
+	domainName := ""
+	for i := range s.Domains {
+		if s.Domains[i].ID == s.SelectedDomainID {
+			domainName = s.Domains[i].Name
+			break
+		}
+	}
+
+	labels := []string{"Type:", "Name:", "Value:", "TTL:", "Proxied:"}
+
+	types := make([]string, len(models.DnsRecordTypes))
+	for i, recordType := range models.DnsRecordTypes {
+		types[i] = string(recordType)
+	}
+	helpTexts := []string{
+		strings.Join(types, ", "),
+		"@ for " + domainName + ", or a subdomain such as www",
+		"IP address, hostname or text, depending on the type (MX: 10 mail.example.com, CAA: 0 issue \"letsencrypt.org\")",
+		fmt.Sprintf("Seconds (%d-%d), or 1 to let the provider decide", models.MinDnsTTL, models.MaxDnsTTL),
+		"yes to route A, AAAA and CNAME traffic through Cloudflare",
+	}
+
+	var fields string
+	for i, label := range labels {
+		if i >= len(s.FormFields) {
+			break
+		}
+		value := s.FormFields[i]
+		focused := i == s.CurrentFieldIndex
+		if focused {
+			cursor := min(max(s.CursorPosition, 0), len(value))
+			value = value[:cursor] + "_" + value[cursor:]
+		}
+		fields += renderFieldLabel(label, focused, t) + " " + value + "\n"
+		fields += "  " + lipgloss.NewStyle().Faint(true).Render(helpTexts[i]) + "\n\n"
+	}
+
+	help := helpStyle.Render("Tab/Shift+Tab to navigate, Enter to save, Esc to cancel")
+	note := helpStyle.Render("Changes are local until DNS is pushed to the provider")
+
+	return title + "\n\n" + "Domain: " + domainName + "\n\n" + fields + help + "\n" + note
+}
+
+// RenderDnsSyncDiff renders the changes fetched from a domain's DNS provider for approval
+func RenderDnsSyncDiff(s *state.AppState, t theme.Theme) string {
+	title := titleStyle.Render("DNS Sync Preview")
+
+	pending := s.PendingDnsSync
+	if pending == nil {
+		return title + "\n\n" + "No pending DNS sync\n\n" + helpStyle.Render("Press Esc to go back")
+	}
+
+	domainName := pending.DomainID.String()
+	if domain := s.GetDomainByID(pending.DomainID); domain != nil {
+		domainName = domain.Name
+	}
+
+	diff := pending.Diff
+	var content strings.Builder
+	content.WriteString(fmt.Sprintf("Domain: %s\n", domainName))
+	content.WriteString(fmt.Sprintf("%d added, %d modified, %d only local\n\n", len(diff.Added), len(diff.Modified), len(diff.Removed)))
+
+	if len(diff.Added) > 0 {
+		content.WriteString("New at provider (will be added):\n")
+		for _, rec := range diff.Added {
+			content.WriteString(diffAddedStyle(t).Render(fmt.Sprintf("  + %-6s %s %s", rec.RecordType, truncate(rec.Name, 30), rec.Value)) + "\n")
+		}
+		content.WriteString("\n")
+	}
+
+	if len(diff.Modified) > 0 {
+		content.WriteString("Changed at provider (will be updated, local edits are kept):\n")
+		for _, change := range diff.Modified {
+			content.WriteString(diffModifiedStyle(t).Render(fmt.Sprintf("  ~ %-6s %s %s", change.Local.RecordType, truncate(change.Local.Name, 30), describeRecordChange(change.Local, change.Remote))) + "\n")
+		}
+		content.WriteString("\n")
+	}
+
+	if len(diff.Removed) > 0 {
+		content.WriteString("Missing at provider (kept locally, marked local-only):\n")
+		for _, rec := range diff.Removed {
+			content.WriteString(diffRemovedStyle(t).Render(fmt.Sprintf("  - %-6s %s %s", rec.RecordType, truncate(rec.Name, 30), rec.Value)) + "\n")
+		}
+		content.WriteString("\n")
+	}
+
+	help := helpStyle.Render("Enter/y to apply • n to discard • Esc to go back without applying")
+
+	return title + "\n\n" + content.String() + help
+}
+
+// describeRecordChange summarises which fields differ between a local record and the provider's
+func describeRecordChange(local, remote models.DnsRecord) string {
+	var parts []string
+	if local.Value != remote.Value {
+		parts = append(parts, fmt.Sprintf("%s → %s", local.Value, remote.Value))
+	}
+	if local.TTL != remote.TTL {
+		parts = append(parts, fmt.Sprintf("TTL %d → %d", local.TTL, remote.TTL))
+	}
+	if local.Proxied != remote.Proxied {
+		parts = append(parts, fmt.Sprintf("proxied %t → %t", local.Proxied, remote.Proxied))
+	}
+	return strings.Join(parts, ", ")
+}
+
 // renderDomainSidebar renders a sidebar showing sites related to the selected domain
-func renderDomainSidebar(s *state.AppState, domain *models.Domain) string {
+func renderDomainSidebar(s *state.AppState, domain *models.Domain, t theme.Theme) string {
 	sidebarStyle := lipgloss.NewStyle().
 		Border(lipgloss.RoundedBorder()).
-		BorderForeground(lipgloss.Color("240")).
+		BorderForeground(t.Muted).
 		Padding(1, 2).
 		Width(35)
 
@@ -538,11 +712,9 @@ func renderDomainSidebar(s *state.AppState, domain *models.Domain) string {
 
 	// Find sites using this domain
 	var relatedSites []string
-	for _, site := range s.Sites {
-		if site.DomainID == domain.ID {
-			relatedSites = append(relatedSites,
-				fmt.Sprintf("• %s (Port %d)", site.Name, site.Port))
-		}
+	for _, site := range s.SitesForDomain(domain.ID) {
+		relatedSites = append(relatedSites,
+			fmt.Sprintf("• %s (Port %d)", site.Name, site.Port))
 	}
 
 	var content string
@@ -553,5 +725,24 @@ func renderDomainSidebar(s *state.AppState, domain *models.Domain) string {
 		content = strings.Join(relatedSites, "\n")
 	}
 
-	return sidebarStyle.Render(title + "\n\n" + content)
+	certTitle := lipgloss.NewStyle().Bold(true).Render("🔒 Certificate")
+	return sidebarStyle.Render(title + "\n\n" + content + "\n\n" + certTitle + "\n\n" + renderCertExpiry(domain, t))
+}
+
+// renderCertExpiry describes when the domain's certificate expires, coloured by how soon
+func renderCertExpiry(domain *models.Domain, t theme.Theme) string {
+	days, ok := domain.CertDaysLeft(time.Now())
+	if !ok {
+		return lipgloss.NewStyle().Faint(true).Render("Not checked yet (press t)")
+	}
+
+	date := domain.CertExpiresAt.Format("2006-01-02")
+	switch {
+	case days < 0:
+		return diffRemovedStyle(t).Render("Expired on " + date)
+	case days < models.CertExpiryWarningDays:
+		return diffModifiedStyle(t).Render(fmt.Sprintf("Expires %s (%d days)", date, days))
+	default:
+		return diffAddedStyle(t).Render(fmt.Sprintf("Valid until %s (%d days)", date, days))
+	}
 }
@@ -2,20 +2,30 @@ package screens
 
 import (
 	"github.com/charmbracelet/lipgloss"
+
+	"github.com/BlueBeard63/archon/internal/state"
 )
 
 // No color constants needed - using default white on black
 
-// RenderHelp renders the help screen with all key bindings in 2 columns
-func RenderHelp() string {
+// RenderHelp renders the help screen with all key bindings in 2 columns, scrolled to
+// HelpScroll when they don't fit in the window
+func RenderHelp(s *state.AppState) string {
 	title := titleStyle.Render("Help - Keyboard Shortcuts")
 
 	// Left Column Sections
 	globalSection := titleStyle.Render("Global Keys") + "\n" +
 		formatKeyBinding("?", "Show this help screen") + "\n" +
 		formatKeyBinding("Esc", "Go back / Cancel") + "\n" +
+		formatKeyBinding("], Alt+→", "Go forward to the screen left with Esc") + "\n" +
 		formatKeyBinding("Ctrl+C, q", "Quit application") + "\n" +
-		formatKeyBinding("Ctrl+S", "Save configuration")
+		formatKeyBinding("Ctrl+S", "Save configuration") + "\n" +
+		formatKeyBinding("F5", "Refresh live data") + "\n" +
+		formatKeyBinding("Ctrl+L", "Reload config from disk") + "\n" +
+		formatKeyBinding("Ctrl+P", "Go to a site, domain or node") + "\n" +
+		formatKeyBinding("Ctrl+O", "Operations (c to cancel, r to retry a failure)") + "\n" +
+		formatKeyBinding("Ctrl+N", "Notification history") + "\n" +
+		formatKeyBinding("Ctrl+R", "Show or hide secrets in forms")
 
 	navigationSection := titleStyle.Render("Navigation") + "\n" +
 		formatKeyBinding("Click Tabs", "Navigate with mouse") + "\n" +
@@ -30,29 +40,57 @@ func RenderHelp() string {
 		formatKeyBinding("n, c", "Create new item") + "\n" +
 		formatKeyBinding("d", "Delete selected item") + "\n" +
 		formatKeyBinding("Enter", "View/Deploy selected item") + "\n" +
+		formatKeyBinding("y", "Copy endpoint / site ID / domain name") + "\n" +
 		formatKeyBinding("Click", "Select item (mouse)")
 
 	formsSection := titleStyle.Render("Forms (Create/Edit)") + "\n" +
 		formatKeyBinding("Tab", "Next field") + "\n" +
 		formatKeyBinding("Shift+Tab", "Previous field") + "\n" +
 		formatKeyBinding("Enter", "Submit form") + "\n" +
+		formatKeyBinding("Ctrl+T", "Toggle SSL (site forms) / Test connection (node forms)") + "\n" +
+		formatKeyBinding("Ctrl+G", "Toggle auto-restart when failed (site forms)") + "\n" +
+		formatKeyBinding("Ctrl+X", "Toggle blue/green deploys (site forms)") + "\n" +
+		formatKeyBinding("Ctrl+N/Ctrl+D", "Add / remove environment variable") + "\n" +
+		formatKeyBinding("Ctrl+F", "Edit config files (site forms) / Import a .env file (ENV vars)") + "\n" +
+		formatKeyBinding("Ctrl+S", "Save the config file being edited") + "\n" +
 		formatKeyBinding("Esc", "Cancel") + "\n" +
 		formatKeyBinding("Click", "Focus field (mouse)")
 
 	// Right Column Sections
 	sitesSection := titleStyle.Render("Sites Specific") + "\n" +
 		formatKeyBinding("Enter", "Deploy site to node") + "\n" +
+		formatKeyBinding("p", "Preview deploy request") + "\n" +
 		formatKeyBinding("s", "Stop site") + "\n" +
 		formatKeyBinding("r", "Restart site") + "\n" +
-		formatKeyBinding("l", "View logs")
+		formatKeyBinding("t", "Filter by tag") + "\n" +
+		formatKeyBinding("/", "Search by name, domain or status") + "\n" +
+		formatKeyBinding("o / O", "Cycle sort column / reverse order") + "\n" +
+		formatKeyBinding("i", "Site details (Tab to switch tabs)") + "\n" +
+		formatKeyBinding("f", "Follow logs on the site details screen") + "\n" +
+		formatKeyBinding("x", "Export fetched logs to a file") + "\n" +
+		formatKeyBinding("l", "View logs") + "\n" +
+		formatKeyBinding("u", "Restore a deleted site") + "\n" +
+		formatKeyBinding("b", "Roll back to the last known-good image (site details)")
 
 	domainsSection := titleStyle.Render("Domains Specific") + "\n" +
-		formatKeyBinding("s", "Sync DNS records") + "\n" +
-		formatKeyBinding("e", "Edit DNS records") + "\n" +
-		formatKeyBinding("Enter", "View DNS records")
+		formatKeyBinding("s", "Sync DNS records (preview changes before applying)") + "\n" +
+		formatKeyBinding("p", "Push local DNS records to the provider") + "\n" +
+		formatKeyBinding("P", "Push DNS records and delete provider-only records") + "\n" +
+		formatKeyBinding("t", "Check TLS certificate expiry") + "\n" +
+		formatKeyBinding("e", "Edit domain") + "\n" +
+		formatKeyBinding("Enter", "View and edit DNS records")
+
+	dnsRecordsSection := titleStyle.Render("DNS Records") + "\n" +
+		formatKeyBinding("a", "Add a record") + "\n" +
+		formatKeyBinding("e / d", "Edit / delete the selected record") + "\n" +
+		formatKeyBinding("s", "Sync from the provider") + "\n" +
+		formatKeyBinding("p / P", "Push to the provider / and delete provider-only records") + "\n" +
+		formatKeyBinding("v", "Check propagation on public resolvers")
 
 	nodesSection := titleStyle.Render("Nodes Specific") + "\n" +
 		formatKeyBinding("v", "View node config") + "\n" +
+		formatKeyBinding("S / D", "Stop / deploy every site on the node") + "\n" +
+		formatKeyBinding("t", "Filter by tag (also on the dashboard)") + "\n" +
 		formatKeyBinding("h", "Health check") + "\n" +
 		formatKeyBinding("Enter", "View node details") + "\n" +
 		formatKeyBinding("m", "View metrics")
@@ -83,6 +121,8 @@ func RenderHelp() string {
 		"",
 		domainsSection,
 		"",
+		dnsRecordsSection,
+		"",
 		nodesSection,
 		"",
 		mouseSection,
@@ -103,6 +143,18 @@ func RenderHelp() string {
 		rightColumnStyled,
 	)
 
+	// Show as many lines as fit between the title and footer
+	rows := s.WindowHeight - 13
+	if rows < 5 {
+		rows = 5
+	}
+	columns, position := scrollLines(columns, &s.HelpScroll, rows)
+
+	footer := "Press Esc or ? to close this help screen"
+	if position != "" {
+		footer = position + " • ↑/↓ to scroll • PgUp/PgDn for a page • g/G for top/bottom • Esc or ? to close"
+	}
+
 	// Combine title, columns, and footer
 	content := lipgloss.JoinVertical(
 		lipgloss.Left,
@@ -110,7 +162,7 @@ func RenderHelp() string {
 		"",
 		columns,
 		"",
-		helpStyle.Render("Press Esc or ? to close this help screen"),
+		helpStyle.Render(footer),
 	)
 
 	return content
@@ -17,15 +17,16 @@ import (
 	"github.com/BlueBeard63/archon/internal/models"
 	"github.com/BlueBeard63/archon/internal/state"
 	"github.com/BlueBeard63/archon/internal/ui/components"
+	"github.com/BlueBeard63/archon/internal/ui/theme"
 )
 
 // RenderNodesList renders the nodes list screen
-func RenderNodesList(s *state.AppState) string {
-	return RenderNodesListWithZones(s, nil)
+func RenderNodesList(s *state.AppState, t theme.Theme) string {
+	return RenderNodesListWithZones(s, nil, t)
 }
 
 // RenderNodesListWithZones renders nodes list with button zones
-func RenderNodesListWithZones(s *state.AppState, zm *zone.Manager) string {
+func RenderNodesListWithZones(s *state.AppState, zm *zone.Manager, t theme.Theme) string {
 	title := titleStyle.Render("🖥️  Nodes")
 
 	// Create button group
@@ -37,23 +38,35 @@ func RenderNodesListWithZones(s *state.AppState, zm *zone.Manager) string {
 
 	var buttons string
 	if zm != nil {
-		buttons = buttonGroup.RenderWithZones(zm)
+		buttons = buttonGroup.RenderWithZones(zm, t)
 	} else {
-		buttons = buttonGroup.Render()
+		buttons = buttonGroup.Render(t)
 	}
 
+	// Show active tag filter in the title
+	if s.NodeTagFilter != "" {
+		title += helpStyle.Render("  (tag: " + s.NodeTagFilter + ")")
+	}
+
+	// Only list nodes matching the active tag filter
+	nodes := s.VisibleNodes()
+
 	var content string
 	if len(s.Nodes) == 0 {
 		content = helpStyle.Render("No nodes yet. Click 'Create Node' or press 'n'.")
+	} else if len(nodes) == 0 {
+		content = helpStyle.Render("No nodes tagged '" + s.NodeTagFilter + "'. Press 't' to change the filter.")
 	} else {
 		// 1. Build table rows (data only, NO buttons)
 		var rows []table.Row
-		for _, node := range s.Nodes {
+		for _, node := range nodes {
 			rows = append(rows, table.Row{
 				truncateNode(node.Name, 20),
 				truncateNode(node.IPAddress.String(), 20),
 				truncateNode(node.APIEndpoint, 28),
 				string(node.Status),
+				truncateNode(latencyText(node.LastLatencyMs), 10),
+				truncateNode(strings.Join(node.Tags, ","), 16),
 			})
 		}
 
@@ -64,6 +77,8 @@ func RenderNodesListWithZones(s *state.AppState, zm *zone.Manager) string {
 				{Title: "IP Address", Width: 20},
 				{Title: "API Endpoint", Width: 28},
 				{Title: "Status", Width: 10},
+				{Title: "Latency", Width: 10},
+				{Title: "Tags", Width: 16},
 			}
 			s.NodesTable = components.NewTableComponent(columns, rows)
 			s.NodesTable.SetCursor(s.NodesListIndex)
@@ -73,13 +88,22 @@ func RenderNodesListWithZones(s *state.AppState, zm *zone.Manager) string {
 		}
 
 		// 3. Render table view
+		s.NodesTable.SetSelectedColor(t.Selected)
 		tableView := s.NodesTable.View()
+		if zm != nil {
+			// Rows are selected by clicking, see TableComponent.HandleMouseClick
+			tableView = zm.Mark("table:nodes", tableView)
+		}
 
-		// 4. Build action buttons column (aligned with rows)
-		var actionsColumn strings.Builder
+		// 4. Build latency marker and action buttons columns (aligned with rows)
+		// The table truncates cells counting escape codes as text, so colors can't go in it
+		var markersColumn, actionsColumn strings.Builder
+		markersColumn.WriteString("\n\n") // Header padding
 		actionsColumn.WriteString("\n\n") // Header padding
 
-		for _, node := range s.Nodes {
+		for _, node := range nodes {
+			markersColumn.WriteString(latencyStyle(s, node.LastLatencyMs, t).Render("●") + " \n")
+
 			viewBtn := components.Button{
 				ID:      "view-node-" + node.ID.String(),
 				Label:   "👁️",
@@ -104,9 +128,9 @@ func RenderNodesListWithZones(s *state.AppState, zm *zone.Manager) string {
 
 			var actionLine string
 			if zm != nil {
-				actionLine = viewBtn.RenderWithZone(zm) + " " + editBtn.RenderWithZone(zm) + " " + deleteBtn.RenderWithZone(zm)
+				actionLine = viewBtn.RenderWithZone(zm, t) + " " + editBtn.RenderWithZone(zm, t) + " " + deleteBtn.RenderWithZone(zm, t)
 			} else {
-				actionLine = viewBtn.Render() + " " + editBtn.Render() + " " + deleteBtn.Render()
+				actionLine = viewBtn.Render(t) + " " + editBtn.Render(t) + " " + deleteBtn.Render(t)
 			}
 
 			actionsColumn.WriteString(actionLine + "\n")
@@ -116,14 +140,14 @@ func RenderNodesListWithZones(s *state.AppState, zm *zone.Manager) string {
 		mainContent := lipgloss.JoinHorizontal(
 			lipgloss.Top,
 			tableView,
+			markersColumn.String(),
 			actionsColumn.String(),
 		)
 
 		// 6. Build sidebar for selected node
 		var sidebar string
-		if len(s.Nodes) > 0 && s.NodesListIndex >= 0 && s.NodesListIndex < len(s.Nodes) {
-			node := &s.Nodes[s.NodesListIndex]
-			sidebar = renderNodeSidebar(s, node)
+		if node := s.SelectedVisibleNode(); node != nil {
+			sidebar = renderNodeSidebar(s, node, t)
 		}
 
 		// 7. Join main content + sidebar
@@ -139,7 +163,7 @@ func RenderNodesListWithZones(s *state.AppState, zm *zone.Manager) string {
 		}
 	}
 
-	help := helpStyle.Render("\n\nPress j/k or arrows to navigate • e to edit • d to delete • enter to view • n to create • Esc to go back")
+	help := helpStyle.Render("\n\nPress j/k or arrows to navigate • e to edit • d to delete • enter to view • n to create • y to copy endpoint • r to refresh health • t to filter by tag • S/D to stop/deploy all its sites • Esc to go back")
 
 	return lipgloss.JoinVertical(
 		lipgloss.Left,
@@ -171,6 +195,31 @@ func truncateNode(s string, maxLen int) string {
 	return s
 }
 
+// latencyText formats a node's last health check latency, or "-" if it hasn't been checked
+func latencyText(latencyMs *int64) string {
+	if latencyMs == nil {
+		return "-"
+	}
+	return fmt.Sprintf("%dms", *latencyMs)
+}
+
+// latencyStyle colors a latency green/yellow/red by the configured thresholds
+func latencyStyle(s *state.AppState, latencyMs *int64, t theme.Theme) lipgloss.Style {
+	if latencyMs == nil {
+		return lipgloss.NewStyle().Faint(true)
+	}
+
+	warnMs, criticalMs := s.LatencyThresholds()
+	color := t.Success
+	switch {
+	case *latencyMs >= criticalMs:
+		color = t.Error
+	case *latencyMs >= warnMs:
+		color = t.Warning
+	}
+	return lipgloss.NewStyle().Foreground(color)
+}
+
 // generateAPIKey generates a random 32-character API key
 func generateAPIKey() string {
 	bytes := make([]byte, 24) // 24 bytes = 32 base64 characters
@@ -182,16 +231,16 @@ func generateAPIKey() string {
 }
 
 // RenderNodeCreate renders the node creation form
-func RenderNodeCreate(s *state.AppState) string {
-	// Initialize form if needed (3 editable fields + 1 generated field: Name, Endpoint, Proxy, APIKey)
-	if len(s.FormFields) != 4 {
-		s.FormFields = []string{"", "", "nginx", generateAPIKey()}
+func RenderNodeCreate(s *state.AppState, t theme.Theme) string {
+	// Initialize form if needed (5 editable fields + 1 generated field: Name, Endpoint, Proxy, PublicIP, Tags, APIKey)
+	if len(s.FormFields) != 6 {
+		s.FormFields = []string{"", "", "nginx", "", "", generateAPIKey()}
 		s.CurrentFieldIndex = 0
 	}
 
 	title := titleStyle.Render("Create New Node")
 
-	labels := []string{"Name:", "API Endpoint:", "Reverse Proxy:", "API Key (auto-generated):"}
+	labels := []string{"Name:", "API Endpoint:", "Reverse Proxy:", "Public IP (optional):", "Tags (comma-separated):", "API Key (auto-generated):"}
 
 	// Render each field
 	var fields string
@@ -201,16 +250,16 @@ func RenderNodeCreate(s *state.AppState) string {
 		isFocused := i == s.CurrentFieldIndex
 
 		// Show cursor if focused (but not for proxy or API key field)
-		if isFocused && i < 2 {
+		if isFocused && i != 2 && i < 5 {
 			displayValue = value + "_"
 		}
 
 		// Render label with focus styling
-		styledLabel := renderFieldLabel(label, isFocused)
+		styledLabel := renderFieldLabel(label, isFocused, t)
 
-		// Show API key as read-only
-		if i == 3 {
-			displayValue = lipgloss.NewStyle().Faint(true).Render(value)
+		// Show API key as read-only, masked until revealed
+		if i == 5 {
+			displayValue = lipgloss.NewStyle().Faint(true).Render(maskSecret(value, s.RevealSecrets))
 		}
 
 		fields += styledLabel + " " + displayValue + "\n"
@@ -223,14 +272,19 @@ func RenderNodeCreate(s *state.AppState) string {
 		}
 	}
 
-	helpText := "\nTab to navigate, Enter to create, Esc to cancel"
-	if s.CurrentFieldIndex == 2 {
+	helpText := "\nTab to navigate, Enter to create, Ctrl+T to test connection, Ctrl+R to show the API key, Esc to cancel"
+	switch s.CurrentFieldIndex {
+	case 2:
 		// On proxy field
 		if s.DropdownOpen {
 			helpText = "\nUp/Down to select, Enter/Tab to confirm, Esc to cancel"
 		} else {
 			helpText = "\nPress Enter or Down to open proxy dropdown"
 		}
+	case 3:
+		helpText = "\nPublic IP for DNS records if different from the endpoint IP (e.g., behind a load balancer)"
+	case 4:
+		helpText = "\nLabels for grouping nodes (e.g., eu-west, staging). Press 't' on the nodes list to filter by tag"
 	}
 
 	help := helpStyle.Render(helpText)
@@ -268,21 +322,21 @@ func renderProxyDropdown(proxies []string, selectedIndex int) string {
 }
 
 // RenderNodeCreateWithZones renders the node creation form with clickable fields
-func RenderNodeCreateWithZones(s *state.AppState, zm *zone.Manager) string {
+func RenderNodeCreateWithZones(s *state.AppState, zm *zone.Manager, t theme.Theme) string {
 	// Fall back to regular rendering if no zone manager
 	if zm == nil {
-		return RenderNodeCreate(s)
+		return RenderNodeCreate(s, t)
 	}
 
-	// Initialize form if needed (3 editable fields + 1 generated field: Name, Endpoint, Proxy, APIKey)
-	if len(s.FormFields) != 4 {
-		s.FormFields = []string{"", "", "nginx", generateAPIKey()}
+	// Initialize form if needed (5 editable fields + 1 generated field: Name, Endpoint, Proxy, PublicIP, Tags, APIKey)
+	if len(s.FormFields) != 6 {
+		s.FormFields = []string{"", "", "nginx", "", "", generateAPIKey()}
 		s.CurrentFieldIndex = 0
 	}
 
 	title := titleStyle.Render("Create New Node")
 
-	labels := []string{"Name:", "API Endpoint:", "Reverse Proxy:", "API Key (auto-generated):"}
+	labels := []string{"Name:", "API Endpoint:", "Reverse Proxy:", "Public IP (optional):", "Tags (comma-separated):", "API Key (auto-generated):"}
 
 	// Render each field with zones
 	var fields string
@@ -292,21 +346,21 @@ func RenderNodeCreateWithZones(s *state.AppState, zm *zone.Manager) string {
 		isFocused := i == s.CurrentFieldIndex
 
 		// Show cursor if focused (but not for proxy or API key field)
-		if isFocused && i < 2 {
+		if isFocused && i != 2 && i < 5 {
 			displayValue = value + "_"
 		}
 
 		// Render label with focus styling
-		styledLabel := renderFieldLabel(label, isFocused)
+		styledLabel := renderFieldLabel(label, isFocused, t)
 
-		// Show API key as read-only
-		if i == 3 {
-			displayValue = lipgloss.NewStyle().Faint(true).Render(value)
+		// Show API key as read-only, masked until revealed
+		if i == 5 {
+			displayValue = lipgloss.NewStyle().Faint(true).Render(maskSecret(value, s.RevealSecrets))
 		}
 
 		// Wrap the entire field line in a clickable zone (only for editable fields)
 		fieldLine := styledLabel + " " + displayValue + "\n"
-		if i < 3 {
+		if i < 5 {
 			fields += zm.Mark(fmt.Sprintf("field:%d", i), fieldLine)
 		} else {
 			fields += fieldLine
@@ -320,14 +374,19 @@ func RenderNodeCreateWithZones(s *state.AppState, zm *zone.Manager) string {
 		}
 	}
 
-	helpText := "\nTab to navigate, Enter to create, Esc to cancel"
-	if s.CurrentFieldIndex == 2 {
+	helpText := "\nTab to navigate, Enter to create, Ctrl+T to test connection, Ctrl+R to show the API key, Esc to cancel"
+	switch s.CurrentFieldIndex {
+	case 2:
 		// On proxy field
 		if s.DropdownOpen {
 			helpText = "\nUp/Down to select, Enter/Tab to confirm, Esc to cancel"
 		} else {
 			helpText = "\nPress Enter or Down to open proxy dropdown"
 		}
+	case 3:
+		helpText = "\nPublic IP for DNS records if different from the endpoint IP (e.g., behind a load balancer)"
+	case 4:
+		helpText = "\nLabels for grouping nodes (e.g., eu-west, staging). Press 't' on the nodes list to filter by tag"
 	}
 
 	help := helpStyle.Render(helpText)
@@ -337,12 +396,12 @@ func RenderNodeCreateWithZones(s *state.AppState, zm *zone.Manager) string {
 }
 
 // RenderNodeEdit renders the node edit form
-func RenderNodeEdit(s *state.AppState) string {
-	return RenderNodeEditWithZones(s, nil)
+func RenderNodeEdit(s *state.AppState, t theme.Theme) string {
+	return RenderNodeEditWithZones(s, nil, t)
 }
 
 // RenderNodeEditWithZones renders the node edit form with clickable fields
-func RenderNodeEditWithZones(s *state.AppState, zm *zone.Manager) string {
+func RenderNodeEditWithZones(s *state.AppState, zm *zone.Manager, t theme.Theme) string {
 	// Find the node
 	var node *models.Node
 	for i := range s.Nodes {
@@ -356,15 +415,19 @@ func RenderNodeEditWithZones(s *state.AppState, zm *zone.Manager) string {
 		return titleStyle.Render("Edit Node") + "\n\n" + "Node not found\n\n" + helpStyle.Render("Press Esc to go back")
 	}
 
-	// Initialize form if needed (3 editable fields: Name, Endpoint, Proxy)
-	if len(s.FormFields) != 3 {
-		s.FormFields = []string{node.Name, node.APIEndpoint, string(node.ProxyType)}
+	// Initialize form if needed (5 editable fields: Name, Endpoint, Proxy, PublicIP, Tags)
+	if len(s.FormFields) != 5 {
+		publicIP := ""
+		if len(node.PublicIP) > 0 {
+			publicIP = node.PublicIP.String()
+		}
+		s.FormFields = []string{node.Name, node.APIEndpoint, string(node.ProxyType), publicIP, models.FormatTags(node.Tags)}
 		s.CurrentFieldIndex = 0
 	}
 
 	title := titleStyle.Render("Edit Node: " + node.Name)
 
-	labels := []string{"Name:", "API Endpoint:", "Reverse Proxy:"}
+	labels := []string{"Name:", "API Endpoint:", "Reverse Proxy:", "Public IP (optional):", "Tags (comma-separated):"}
 
 	// Render each field
 	var fields string
@@ -374,12 +437,12 @@ func RenderNodeEditWithZones(s *state.AppState, zm *zone.Manager) string {
 		isFocused := i == s.CurrentFieldIndex
 
 		// Show cursor if focused (but not for proxy field)
-		if isFocused && i < 2 {
+		if isFocused && i != 2 {
 			displayValue = value + "_"
 		}
 
 		// Render label with focus styling
-		styledLabel := renderFieldLabel(label, isFocused)
+		styledLabel := renderFieldLabel(label, isFocused, t)
 
 		// Wrap the field line in a clickable zone
 		fieldLine := styledLabel + " " + displayValue + "\n"
@@ -397,14 +460,19 @@ func RenderNodeEditWithZones(s *state.AppState, zm *zone.Manager) string {
 		}
 	}
 
-	helpText := "\nTab/Shift+Tab to navigate, Enter to save, Esc to cancel"
-	if s.CurrentFieldIndex == 2 {
+	helpText := "\nTab/Shift+Tab to navigate, Enter to save, Ctrl+T to test connection, Esc to cancel"
+	switch s.CurrentFieldIndex {
+	case 2:
 		// On proxy field
 		if s.DropdownOpen {
 			helpText = "\nUp/Down to select, Enter/Tab to confirm, Esc to cancel"
 		} else {
 			helpText = "\nPress Enter or Down to open proxy dropdown"
 		}
+	case 3:
+		helpText = "\nPublic IP for DNS records if different from the endpoint IP (leave blank to use endpoint IP)"
+	case 4:
+		helpText = "\nLabels for grouping nodes (e.g., eu-west, staging). Press 't' on the nodes list to filter by tag"
 	}
 
 	help := helpStyle.Render(helpText)
@@ -414,7 +482,7 @@ func RenderNodeEditWithZones(s *state.AppState, zm *zone.Manager) string {
 }
 
 // RenderNodeDetails renders detailed information about a node
-func RenderNodeDetails(s *state.AppState, nodeID string) string {
+func RenderNodeDetails(s *state.AppState, nodeID string, t theme.Theme) string {
 	title := titleStyle.Render("Node Details")
 
 	// Find the node
@@ -434,7 +502,11 @@ func RenderNodeDetails(s *state.AppState, nodeID string) string {
 	content := fmt.Sprintf("Name: %s\n", node.Name)
 	content += fmt.Sprintf("Endpoint: %s\n", node.APIEndpoint)
 	content += fmt.Sprintf("IP Address: %s\n", node.IPAddress.String())
-	content += fmt.Sprintf("Status: %s\n\n", node.Status)
+	if len(node.PublicIP) > 0 {
+		content += fmt.Sprintf("Public IP: %s\n", node.PublicIP.String())
+	}
+	content += fmt.Sprintf("Status: %s\n", node.Status)
+	content += fmt.Sprintf("Latency: %s\n\n", latencyStyle(s, node.LastLatencyMs, t).Render(latencyText(node.LastLatencyMs)))
 
 	// Docker info section
 	content += "Docker Information:\n"
@@ -460,8 +532,8 @@ func RenderNodeDetails(s *state.AppState, nodeID string) string {
 	content += "Deployed Sites:\n"
 	sitesFound := false
 	for _, site := range s.Sites {
-		if site.NodeID == node.ID {
-			content += fmt.Sprintf("  - %s (%s)\n", site.Name, site.DockerImage)
+		if site.OnNode(node.ID) {
+			content += fmt.Sprintf("  - %s (%s)\n", site.Name, site.ImageSource())
 			sitesFound = true
 		}
 	}
@@ -480,12 +552,12 @@ func RenderNodeDetails(s *state.AppState, nodeID string) string {
 }
 
 // RenderNodeConfigSave renders the file save dialog for node config
-func RenderNodeConfigSave(s *state.AppState) string {
-	return RenderNodeConfigSaveWithZones(s, nil)
+func RenderNodeConfigSave(s *state.AppState, t theme.Theme) string {
+	return RenderNodeConfigSaveWithZones(s, nil, t)
 }
 
 // RenderNodeConfigSaveWithZones renders the file save dialog with clickable fields
-func RenderNodeConfigSaveWithZones(s *state.AppState, zm *zone.Manager) string {
+func RenderNodeConfigSaveWithZones(s *state.AppState, zm *zone.Manager, t theme.Theme) string {
 	// Find the node
 	var node *models.Node
 	for i := range s.Nodes {
@@ -516,7 +588,7 @@ func RenderNodeConfigSaveWithZones(s *state.AppState, zm *zone.Manager) string {
 
 	// Render the file path field with focus styling
 	isFocused := s.CurrentFieldIndex == 0
-	styledLabel := renderFieldLabel("File Path:", isFocused)
+	styledLabel := renderFieldLabel("File Path:", isFocused, t)
 
 	value := s.FormFields[0]
 	displayValue := value
@@ -579,7 +651,7 @@ func RenderNodeConfig(s *state.AppState) string {
 	if s.NodeConfigViewport.Width == 0 {
 		// Set viewport size based on window dimensions
 		// Leave room for title (3 lines), help (2 lines), and some padding
-		viewportHeight := s.WindowHeight - 7
+		viewportHeight := s.WindowHeight - 8
 		if viewportHeight < 10 {
 			viewportHeight = 10
 		}
@@ -603,10 +675,10 @@ func RenderNodeConfig(s *state.AppState) string {
 }
 
 // renderNodeSidebar renders a sidebar showing sites deployed on the selected node
-func renderNodeSidebar(s *state.AppState, node *models.Node) string {
+func renderNodeSidebar(s *state.AppState, node *models.Node, t theme.Theme) string {
 	sidebarStyle := lipgloss.NewStyle().
 		Border(lipgloss.RoundedBorder()).
-		BorderForeground(lipgloss.Color("240")).
+		BorderForeground(t.Muted).
 		Padding(1, 2).
 		Width(35)
 
@@ -614,11 +686,9 @@ func renderNodeSidebar(s *state.AppState, node *models.Node) string {
 
 	// Find sites on this node
 	var deployedSites []string
-	for _, site := range s.Sites {
-		if site.NodeID == node.ID {
-			deployedSites = append(deployedSites,
-				fmt.Sprintf("• %s (%s)", site.Name, site.Status))
-		}
+	for _, site := range s.SitesForNode(node.ID) {
+		deployedSites = append(deployedSites,
+			fmt.Sprintf("• %s (%s)", site.Name, site.Status))
 	}
 
 	var content string
@@ -629,5 +699,11 @@ func renderNodeSidebar(s *state.AppState, node *models.Node) string {
 		content = strings.Join(deployedSites, "\n")
 	}
 
-	return sidebarStyle.Render(title + "\n\n" + content)
+	// Tags
+	tagInfo := "🏷️  Tags: None"
+	if len(node.Tags) > 0 {
+		tagInfo = "🏷️  Tags: " + models.FormatTags(node.Tags)
+	}
+
+	return sidebarStyle.Render(title + "\n\n" + content + "\n\n" + tagInfo)
 }
@@ -0,0 +1,49 @@
+package screens
+
+import (
+	"strings"
+	"testing"
+
+	"github.com/charmbracelet/lipgloss"
+	"github.com/google/uuid"
+	"github.com/muesli/termenv"
+
+	"github.com/BlueBeard63/archon/internal/models"
+	"github.com/BlueBeard63/archon/internal/state"
+	"github.com/BlueBeard63/archon/internal/ui/theme"
+)
+
+func TestNodesListShowsPlainLatency(t *testing.T) {
+	// Render colors as a terminal would, so escape codes in table cells would be cut
+	profile := lipgloss.ColorProfile()
+	lipgloss.SetColorProfile(termenv.TrueColor)
+	t.Cleanup(func() { lipgloss.SetColorProfile(profile) })
+
+	fast, slow := int64(45), int64(900)
+	s := state.NewAppState()
+	s.Nodes = []models.Node{
+		{ID: uuid.New(), Name: "fast-node", Status: models.NodeStatusOnline, LastLatencyMs: &fast, Tags: []string{"eu-west"}},
+		{ID: uuid.New(), Name: "slow-node", Status: models.NodeStatusOnline, LastLatencyMs: &slow, Tags: []string{"us-east"}},
+	}
+
+	view := RenderNodesList(s, theme.Dark())
+
+	for _, want := range []struct{ name, latency, tag string }{
+		{"fast-node", "45ms", "eu-west"},
+		{"slow-node", "900ms", "us-east"},
+	} {
+		var row string
+		for _, line := range strings.Split(view, "\n") {
+			if strings.Contains(line, want.name) {
+				row = line
+				break
+			}
+		}
+		if row == "" {
+			t.Fatalf("no row for %s in:\n%s", want.name, view)
+		}
+		if !strings.Contains(row, want.latency) || !strings.Contains(row, want.tag) {
+			t.Errorf("row for %s = %q, want latency %q and tag %q", want.name, row, want.latency, want.tag)
+		}
+	}
+}
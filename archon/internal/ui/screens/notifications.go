@@ -0,0 +1,64 @@
+package screens
+
+import (
+	"fmt"
+	"strings"
+
+	"github.com/charmbracelet/lipgloss"
+
+	"github.com/BlueBeard63/archon/internal/state"
+	"github.com/BlueBeard63/archon/internal/ui/theme"
+)
+
+// RenderNotifications renders the notification history, newest first, including the
+// notifications kept from the last session
+func RenderNotifications(s *state.AppState, t theme.Theme) string {
+	title := titleStyle.Render("Notifications")
+
+	total := len(s.Notifications)
+	if total == 0 {
+		return title + "\n\n" + "No notifications yet\n\n" + helpStyle.Render("Press Esc to go back")
+	}
+
+	// Leave room for the header, tabs, help and status bar
+	rows := s.WindowHeight - 13
+	if rows < 5 {
+		rows = 5
+	}
+	start := min(s.NotificationsScroll, total-1)
+	end := min(start+rows, total)
+
+	maxWidth := s.WindowWidth - 30
+	if maxWidth < 40 {
+		maxWidth = 40
+	}
+
+	var content strings.Builder
+	for i := start; i < end; i++ {
+		notif := s.Notifications[total-1-i]
+		when := "-"
+		if !notif.Time.IsZero() {
+			when = notif.Time.Format("Jan 02 15:04:05")
+		}
+		level := notificationLevelStyle(notif.Level, t).Render(fmt.Sprintf("%-7s", notif.Level))
+		content.WriteString(fmt.Sprintf("%-15s  %s  %s\n", when, level, truncate(notif.Message, maxWidth)))
+	}
+
+	help := helpStyle.Render(fmt.Sprintf("\n%d-%d of %d • j/k to scroll • g/G for newest/oldest • Esc to go back\nThe last %d notifications are kept when Archon exits",
+		start+1, end, total, state.MaxNotifications))
+
+	return title + "\n\n" + content.String() + help
+}
+
+// notificationLevelStyle colors a notification level in the history
+func notificationLevelStyle(level string, t theme.Theme) lipgloss.Style {
+	switch level {
+	case "success":
+		return diffAddedStyle(t)
+	case "error":
+		return diffRemovedStyle(t)
+	case "warning":
+		return diffModifiedStyle(t)
+	}
+	return lipgloss.NewStyle()
+}
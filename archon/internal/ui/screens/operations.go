@@ -0,0 +1,51 @@
+package screens
+
+import (
+	"fmt"
+	"strings"
+
+	"github.com/BlueBeard63/archon/internal/state"
+	"github.com/BlueBeard63/archon/internal/ui/theme"
+)
+
+// RenderOperations renders the async operations still in flight, oldest first, followed by
+// recently failed ones that can be retried, newest first
+func RenderOperations(s *state.AppState, t theme.Theme) string {
+	title := titleStyle.Render("Operations")
+
+	ops := s.Operations()
+	if len(ops) == 0 {
+		return title + "\n\n" + "No operations are running or have failed\n\n" + helpStyle.Render("Press Esc to go back")
+	}
+
+	var content strings.Builder
+	pending := len(s.PendingOperations)
+	for i, op := range ops {
+		if i == pending {
+			if pending > 0 {
+				content.WriteString("\n")
+			}
+			content.WriteString(diffRemovedStyle(t).Render("Failed") + "\n")
+		}
+
+		prefix := "  "
+		if i == s.OperationsIndex {
+			prefix = "▶ "
+		}
+
+		var status string
+		switch {
+		case i >= pending:
+			status = op.Error
+		case op.Status == "failed":
+			status = op.Error + ", waiting for it to stop"
+		default:
+			status = "running"
+		}
+		content.WriteString(fmt.Sprintf("%s%-40s %s\n", prefix, truncate(op.Label(), 40), status))
+	}
+
+	help := helpStyle.Render(fmt.Sprintf("\nj/k to select • c to cancel • r to retry a failed operation • Esc to go back\nUp to %d failed operations are kept until Archon exits", state.MaxFailedOperations))
+
+	return title + "\n\n" + content.String() + help
+}
@@ -0,0 +1,34 @@
+package screens
+
+import (
+	"fmt"
+	"strings"
+)
+
+// scrollLines returns the rows lines of content starting at *scroll, clamping *scroll so the
+// last page is full, and an indicator of where the window is ("" when everything fits)
+func scrollLines(content string, scroll *int, rows int) (string, string) {
+	lines := strings.Split(content, "\n")
+	total := len(lines)
+	if rows < 1 {
+		rows = 1
+	}
+	if total <= rows {
+		*scroll = 0
+		return content, ""
+	}
+
+	*scroll = min(max(*scroll, 0), total-rows)
+	end := *scroll + rows
+
+	indicator := fmt.Sprintf("Lines %d-%d of %d", *scroll+1, end, total)
+	switch {
+	case *scroll == 0:
+		indicator += " ▼"
+	case end == total:
+		indicator += " ▲"
+	default:
+		indicator += " ▲▼"
+	}
+	return strings.Join(lines[*scroll:end], "\n"), indicator
+}
@@ -44,12 +44,9 @@ func RenderSettingsWithZones(s *state.AppState, zm *zone.Manager) string {
 	var fields string
 	for i, label := range labels {
 		value := s.FormFields[i]
-		displayValue := value
 
-		// Mask the value if it's not empty and not focused
-		if value != "" && i != s.CurrentFieldIndex {
-			displayValue = "••••••••••••••••"
-		}
+		// Mask the credential, also while typing, unless revealed
+		displayValue := maskSecret(value, s.RevealSecrets)
 
 		// Show cursor if focused
 		if i == s.CurrentFieldIndex {
@@ -61,7 +58,7 @@ func RenderSettingsWithZones(s *state.AppState, zm *zone.Manager) string {
 			if cursor > len(value) {
 				cursor = len(value)
 			}
-			displayValue = value[:cursor] + "_" + value[cursor:]
+			displayValue = maskSecret(value[:cursor], s.RevealSecrets) + "_" + maskSecret(value[cursor:], s.RevealSecrets)
 			label = "> " + label // Show arrow for focused field
 		} else {
 			label = "  " + label
@@ -77,7 +74,7 @@ func RenderSettingsWithZones(s *state.AppState, zm *zone.Manager) string {
 		}
 	}
 
-	help := helpStyle.Render("\nTab/Shift+Tab to navigate, Enter to save, Esc to cancel")
+	help := helpStyle.Render("\nTab/Shift+Tab to navigate, Enter to save, Ctrl+R to show or hide credentials, Esc to cancel")
 	note := helpStyle.Render("Note: Global credentials are stored in config.toml and used as defaults.\nZone IDs are configured per-domain when creating or editing domains.")
 
 	return title + "\n\n" + fields + help + "\n" + note
@@ -0,0 +1,59 @@
+package screens
+
+import (
+	"fmt"
+
+	"github.com/charmbracelet/lipgloss"
+
+	"github.com/BlueBeard63/archon/internal/state"
+	"github.com/BlueBeard63/archon/internal/ui/theme"
+)
+
+// setupSteps are the setup wizard's steps, in order, with what each one adds
+var setupSteps = []struct {
+	step  state.SetupStep
+	label string
+	about string
+}{
+	{state.SetupStepNode, "Add a node", "A server running the Archon node server, which runs your sites"},
+	{state.SetupStepDomain, "Add a domain", "The domain sites are served from, with its DNS provider"},
+	{state.SetupStepSite, "Add a site (optional)", "A container or compose project to deploy to the node"},
+}
+
+// RenderSetup renders the first-run setup wizard: the steps with the current one highlighted
+func RenderSetup(s *state.AppState, t theme.Theme) string {
+	title := titleStyle.Render("👋 Welcome to Archon")
+	intro := "Let's set up your first node, domain and site. Each step opens the usual form.\n\n"
+
+	current := s.SetupStep()
+	faint := lipgloss.NewStyle().Faint(true)
+
+	var steps string
+	for i, step := range setupSteps {
+		line := fmt.Sprintf("%d. %s", i+1, step.label)
+		switch {
+		case step.step < current:
+			steps += diffAddedStyle(t).Render("✓ "+line) + "\n"
+		case step.step == current:
+			steps += formLabelFocusedStyle(t).Render("▶ "+line) + "\n"
+		default:
+			steps += "  " + line + "\n"
+		}
+		steps += "    " + faint.Render(step.about) + "\n"
+	}
+
+	var help string
+	switch current {
+	case state.SetupStepNode:
+		help = "Enter: add a node • Esc: skip to the dashboard"
+	case state.SetupStepDomain:
+		help = "Enter: add a domain • c: settings • Esc: skip to the dashboard\n" +
+			"Cloudflare domains use the API token from settings, so add it there first"
+	case state.SetupStepSite:
+		help = "Enter: add a site • Esc: finish without one"
+	default:
+		help = "All set! Enter or Esc: go to the dashboard"
+	}
+
+	return title + "\n\n" + intro + steps + "\n" + helpStyle.Render(help)
+}
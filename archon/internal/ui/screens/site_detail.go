@@ -0,0 +1,361 @@
+package screens
+
+import (
+	"fmt"
+	"sort"
+	"strings"
+	"time"
+
+	"github.com/BlueBeard63/archon/internal/api"
+	"github.com/BlueBeard63/archon/internal/models"
+	"github.com/BlueBeard63/archon/internal/state"
+	"github.com/BlueBeard63/archon/internal/ui/theme"
+)
+
+// RenderSiteDetail renders the site detail screen with overview, logs, metrics and deployments tabs
+func RenderSiteDetail(s *state.AppState, t theme.Theme) string {
+	site := s.GetSiteByID(s.SelectedSiteID)
+	if site == nil {
+		return titleStyle.Render("Site Details") + "\n\n" + "Site not found\n\n" + helpStyle.Render("Press Esc to go back")
+	}
+
+	title := titleStyle.Render("Site: " + site.Name)
+
+	// Tab header
+	var tabs []string
+	for i, name := range state.SiteDetailTabNames {
+		if i == s.SiteDetailTab {
+			tabs = append(tabs, siteDetailActiveTabStyle(t).Render(name))
+		} else {
+			tabs = append(tabs, siteDetailTabStyle(t).Render(name))
+		}
+	}
+	tabLine := strings.Join(tabs, "  │  ")
+
+	var content string
+	switch s.SiteDetailTab {
+	case state.SiteDetailTabLogs:
+		content = renderSiteLogsTab(s, site, t)
+	case state.SiteDetailTabMetrics:
+		content = renderSiteMetricsTab(s, site, t)
+	case state.SiteDetailTabDeployments:
+		content = renderSiteDeploymentsTab(site, t)
+	default:
+		content = renderSiteOverviewTab(s, site, t)
+	}
+
+	help := helpStyle.Render("\nTab/Shift+Tab to switch tabs • r to refresh • f to follow logs • x to export logs • e to edit • p to preview deploy • b to roll back • y to copy ID • Esc to go back")
+
+	return title + "\n" + tabLine + "\n\n" + content + "\n" + help
+}
+
+// highRestartCount is the container restart count flagged as a possible crash loop
+const highRestartCount = 3
+
+// renderSiteOverviewTab renders status, placement and environment variables for a site
+func renderSiteOverviewTab(s *state.AppState, site *models.Site, t theme.Theme) string {
+	content := fmt.Sprintf("Status: %s\n", site.Status)
+	if container := s.GetContainerStatus(site.ID); container != nil && site.Status != models.SiteStatusInactive {
+		content += "Container: " + renderContainerStatus(container, time.Now(), t) + "\n"
+	}
+	content += fmt.Sprintf("Type: %s\n", site.GetSiteType())
+	if site.GetSiteType() != models.SiteTypeCompose {
+		content += fmt.Sprintf("Image: %s\n", site.ImageSource())
+		if registry := site.RegistryAddress(); registry != "" && !site.IsBuild() {
+			content += fmt.Sprintf("Registry: %s\n", registry)
+		}
+	}
+
+	if site.HasReplicas() {
+		// Each replica with the outcome of its last deploy
+		content += "Nodes:\n"
+		for i, nodeID := range site.GetNodeIDs() {
+			nodeName := "Not found"
+			if node := s.GetNodeByID(nodeID); node != nil {
+				nodeName = node.Name
+			}
+			if i == 0 {
+				nodeName += " (primary)"
+			}
+			status := "not deployed"
+			if replica := site.GetReplicaStatus(nodeID); replica != nil {
+				status = string(replica.Status)
+				if replica.LastError != "" {
+					status += ": " + replica.LastError
+				}
+			}
+			content += fmt.Sprintf("  %s - %s\n", nodeName, status)
+		}
+	} else {
+		nodeName := "Not found"
+		if node := s.GetNodeByID(site.NodeID); node != nil {
+			nodeName = node.Name
+		}
+		content += fmt.Sprintf("Node: %s\n", nodeName)
+	}
+
+	ssl := "Disabled"
+	if site.SSLEnabled {
+		ssl = "Enabled"
+	}
+	content += fmt.Sprintf("SSL: %s\n", ssl)
+	if site.GetSiteType() != models.SiteTypeCompose {
+		content += fmt.Sprintf("Resources: %s\n", site.DescribeResourceLimits())
+	}
+	if len(site.Volumes) > 0 {
+		content += "\nVolumes:\n"
+		for _, volume := range site.Volumes {
+			content += fmt.Sprintf("  - %s\n", volume)
+		}
+	}
+
+	// Domain mappings
+	content += "\nDomains:\n"
+	mappings := site.GetDomainMappings()
+	if len(mappings) == 0 {
+		content += "  None\n"
+	}
+	for _, mapping := range mappings {
+		domainName := mapping.DomainID.String()
+		if domain := s.GetDomainByID(mapping.DomainID); domain != nil {
+			domainName = domain.Name
+			if mapping.Subdomain != "" {
+				domainName = mapping.Subdomain + "." + domainName
+			}
+		}
+		content += fmt.Sprintf("  - %s → :%d\n", domainName, mapping.Port)
+	}
+	// Extra hostnames are served on the primary mapping's port
+	for _, host := range site.ExtraHosts {
+		port := 0
+		if len(mappings) > 0 {
+			port = mappings[0].Port
+		}
+		content += fmt.Sprintf("  - %s → :%d (extra hostname)\n", host, port)
+	}
+
+	// Environment variables, sorted for a stable display
+	content += "\nEnvironment Variables:\n"
+	if len(site.EnvironmentVars) == 0 {
+		content += "  None\n"
+	}
+	keys := make([]string, 0, len(site.EnvironmentVars))
+	for key := range site.EnvironmentVars {
+		keys = append(keys, key)
+	}
+	sort.Strings(keys)
+	for _, key := range keys {
+		value := truncate(site.EnvironmentVars[key], 60)
+		if models.IsSecretEnvVar(key) {
+			value = maskSecret(value, s.RevealSecrets)
+		}
+		content += fmt.Sprintf("  %s=%s\n", key, value)
+	}
+
+	return content
+}
+
+// renderContainerStatus renders a container's restart count and uptime, e.g. "restarts: 5, up 2h",
+// flagging a restart count that suggests the container is crash-looping
+func renderContainerStatus(container *api.SiteStatusResponse, now time.Time, t theme.Theme) string {
+	restarts := fmt.Sprintf("restarts: %d", container.RestartCount)
+	if container.RestartCount >= highRestartCount {
+		restarts = diffModifiedStyle(t).Render(restarts + " ⚠ crash-looping?")
+	}
+	if container.StartedAt == nil {
+		return restarts
+	}
+	return restarts + ", up " + formatUptime(now.Sub(*container.StartedAt))
+}
+
+// formatUptime renders how long a container has been up in its largest units, e.g. "2h 5m"
+func formatUptime(d time.Duration) string {
+	switch {
+	case d < time.Minute:
+		return fmt.Sprintf("%ds", max(int(d.Seconds()), 0))
+	case d < time.Hour:
+		return fmt.Sprintf("%dm", int(d.Minutes()))
+	case d < 24*time.Hour:
+		return fmt.Sprintf("%dh %dm", int(d.Hours()), int(d.Minutes())%60)
+	default:
+		return fmt.Sprintf("%dd %dh", int(d.Hours())/24, int(d.Hours())%24)
+	}
+}
+
+// renderSiteLogsTab renders the tail of the site's container logs that fits on screen
+// The newest line is always at the bottom, so following logs scrolls automatically
+func renderSiteLogsTab(s *state.AppState, site *models.Site, t theme.Theme) string {
+	following := ""
+	if s.FollowLogs == site.ID {
+		following = siteDetailActiveTabStyle(t).Render("● Following (f to stop)") + "\n"
+	}
+
+	logs, fetched := s.GetLogs(site.ID)
+	if !fetched {
+		return following + "Fetching logs...\n"
+	}
+	if len(logs) == 0 {
+		return following + "No log output\n"
+	}
+
+	// Leave room for the header, tabs, help and status bar
+	maxLines := s.WindowHeight - 15
+	if following != "" {
+		maxLines--
+	}
+	if maxLines < 5 {
+		maxLines = 5
+	}
+	if len(logs) > maxLines {
+		logs = logs[len(logs)-maxLines:]
+	}
+
+	maxWidth := s.WindowWidth - 4
+	if maxWidth < 40 {
+		maxWidth = 40
+	}
+
+	var content strings.Builder
+	content.WriteString(following)
+	for _, line := range logs {
+		line = strings.TrimRight(line, "\r\n")
+		if len(line) > maxWidth {
+			line = line[:maxWidth-3] + "..."
+		}
+		content.WriteString(line + "\n")
+	}
+	return content.String()
+}
+
+// renderSiteMetricsTab renders CPU and memory usage gauges for the site's container
+func renderSiteMetricsTab(s *state.AppState, site *models.Site, t theme.Theme) string {
+	metrics, fetched := s.GetMetrics(site.ID)
+	if !fetched {
+		return "Fetching metrics...\n"
+	}
+	if metrics == nil {
+		return "No metrics available (is the site running?)\n"
+	}
+
+	content := renderGauge("CPU", metrics.CPUPercent, t) + "\n\n"
+
+	content += renderGauge("Memory", memoryPercent(metrics), t) + "\n"
+	content += fmt.Sprintf("        %s / %s\n\n", formatBytes(metrics.MemoryUsage), formatBytes(metrics.MemoryLimit))
+
+	content += fmt.Sprintf("Network: ↓ %s  ↑ %s\n", formatBytes(metrics.NetworkRxBytes), formatBytes(metrics.NetworkTxBytes))
+
+	// Trends from the snapshots taken while this screen has been open
+	if history := s.GetMetricsHistory(site.ID); len(history) > 1 {
+		cpu := make([]float64, len(history))
+		memory := make([]float64, len(history))
+		for i := range history {
+			cpu[i] = history[i].Metrics.CPUPercent
+			memory[i] = memoryPercent(&history[i].Metrics)
+		}
+		content += fmt.Sprintf("\nLast %s:\n", time.Since(history[0].At).Round(time.Second))
+		content += fmt.Sprintf("%-7s %s\n", "CPU:", renderSparkline(cpu, t))
+		content += fmt.Sprintf("%-7s %s\n", "Memory:", renderSparkline(memory, t))
+	}
+	return content
+}
+
+// sparkBlocks are the bar heights used by renderSparkline, lowest first
+var sparkBlocks = []rune("▁▂▃▄▅▆▇█")
+
+// renderSiteDeploymentsTab lists the site's past deploys, newest first
+func renderSiteDeploymentsTab(site *models.Site, t theme.Theme) string {
+	if len(site.Deployments) == 0 {
+		return helpStyle.Render("No deploys recorded yet. Press Esc and deploy the site to start its history.") + "\n"
+	}
+
+	var content strings.Builder
+	content.WriteString(fmt.Sprintf("Last %d deploys (newest first):\n\n", len(site.Deployments)))
+	for i := len(site.Deployments) - 1; i >= 0; i-- {
+		record := site.Deployments[i]
+		outcome := diffAddedStyle(t).Render("✓ " + string(record.Outcome))
+		if record.Outcome == models.DeploymentFailed {
+			outcome = diffRemovedStyle(t).Render("✗ " + string(record.Outcome))
+		}
+		containerID := record.ContainerID
+		if len(containerID) > 12 {
+			containerID = containerID[:12]
+		}
+		content.WriteString(fmt.Sprintf("  %s  %s  %s", record.Timestamp.Local().Format("2006-01-02 15:04"), outcome, record.Image))
+		if containerID != "" {
+			content.WriteString("  (container " + containerID + ")")
+		}
+		content.WriteString("\n")
+		if record.Error != "" {
+			content.WriteString("      " + helpStyle.Render(record.Error) + "\n")
+		}
+	}
+	return content.String()
+}
+
+// renderSparkline renders percentages as one bar per value, scaled to 100% (or the peak if higher,
+// since CPU usage can exceed 100% on multi-core hosts)
+func renderSparkline(values []float64, t theme.Theme) string {
+	peak := 100.0
+	for _, v := range values {
+		if v > peak {
+			peak = v
+		}
+	}
+
+	bars := make([]rune, len(values))
+	for i, v := range values {
+		level := int(v / peak * float64(len(sparkBlocks)-1))
+		if level < 0 {
+			level = 0
+		}
+		bars[i] = sparkBlocks[level]
+	}
+	return gaugeFilledStyle(t).Render(string(bars))
+}
+
+// memoryPercent returns memory usage as a percentage of the container limit
+func memoryPercent(metrics *api.ContainerMetrics) float64 {
+	if metrics.MemoryLimit <= 0 {
+		return 0
+	}
+	return float64(metrics.MemoryUsage) / float64(metrics.MemoryLimit) * 100
+}
+
+// renderGauge renders a horizontal bar for a percentage, colored by how full it is
+func renderGauge(label string, percent float64, t theme.Theme) string {
+	const width = 40
+
+	clamped := percent
+	if clamped < 0 {
+		clamped = 0
+	}
+	if clamped > 100 {
+		clamped = 100
+	}
+	filled := int(clamped / 100 * width)
+
+	style := gaugeFilledStyle(t)
+	switch {
+	case clamped >= 90:
+		style = gaugeHighStyle(t)
+	case clamped >= 70:
+		style = gaugeWarnStyle(t)
+	}
+
+	bar := style.Render(strings.Repeat("█", filled)) + gaugeEmptyStyle(t).Render(strings.Repeat("░", width-filled))
+	return fmt.Sprintf("%-7s %s %5.1f%%", label+":", bar, percent)
+}
+
+// formatBytes formats a byte count using binary units
+func formatBytes(bytes int64) string {
+	const unit = 1024
+	if bytes < unit {
+		return fmt.Sprintf("%d B", bytes)
+	}
+	div, exp := int64(unit), 0
+	for n := bytes / unit; n >= unit; n /= unit {
+		div *= unit
+		exp++
+	}
+	return fmt.Sprintf("%.1f %ciB", float64(bytes)/float64(div), "KMGTPE"[exp])
+}
@@ -3,41 +3,42 @@ package screens
 import (
 	"fmt"
 	"strings"
+	"unicode/utf8"
 
 	"github.com/charmbracelet/bubbles/table"
+	"github.com/charmbracelet/bubbles/viewport"
 	"github.com/charmbracelet/lipgloss"
 	zone "github.com/lrstanley/bubblezone"
 
 	"github.com/BlueBeard63/archon/internal/models"
 	"github.com/BlueBeard63/archon/internal/state"
 	"github.com/BlueBeard63/archon/internal/ui/components"
-)
-
-// Colors for focused field styling (local to avoid circular import with ui package)
-var (
-	colorPrimary = lipgloss.Color("#7C3AED") // Purple
-
-	// formLabelFocusedStyle is the style for focused form field labels
-	formLabelFocusedStyle = lipgloss.NewStyle().
-				Foreground(colorPrimary).
-				Bold(true)
+	"github.com/BlueBeard63/archon/internal/ui/theme"
 )
 
 // renderFieldLabel renders a field label with focus indicator and styling
-func renderFieldLabel(label string, focused bool) string {
+func renderFieldLabel(label string, focused bool, t theme.Theme) string {
 	if focused {
-		return formLabelFocusedStyle.Render("> " + label)
+		return formLabelFocusedStyle(t).Render("> " + label)
 	}
 	return "  " + label
 }
 
+// maskSecret hides a token or key as one bullet per character, unless secrets are revealed
+func maskSecret(value string, reveal bool) string {
+	if reveal {
+		return value
+	}
+	return strings.Repeat("•", utf8.RuneCountInString(value))
+}
+
 // RenderSitesList renders the sites list screen with buttons
-func RenderSitesList(s *state.AppState) string {
-	return RenderSitesListWithZones(s, nil)
+func RenderSitesList(s *state.AppState, t theme.Theme) string {
+	return RenderSitesListWithZones(s, nil, t)
 }
 
 // RenderSitesListWithZones renders sites list with optional button zones
-func RenderSitesListWithZones(s *state.AppState, zm *zone.Manager) string {
+func RenderSitesListWithZones(s *state.AppState, zm *zone.Manager, t theme.Theme) string {
 	title := titleStyle.Render("🌐 Sites")
 
 	// Create button group
@@ -49,26 +50,52 @@ func RenderSitesListWithZones(s *state.AppState, zm *zone.Manager) string {
 
 	var buttons string
 	if zm != nil {
-		buttons = buttonGroup.RenderWithZones(zm)
+		buttons = buttonGroup.RenderWithZones(zm, t)
 	} else {
-		buttons = buttonGroup.Render()
+		buttons = buttonGroup.Render(t)
+	}
+
+	// Show active tag filter in the title
+	if s.SiteTagFilter != "" {
+		title += helpStyle.Render("  (tag: " + s.SiteTagFilter + ")")
 	}
 
+	// Show the sort column and direction
+	if s.SiteSortColumn != state.SiteSortNone {
+		arrow := "↑"
+		if s.SiteSortDesc {
+			arrow = "↓"
+		}
+		title += helpStyle.Render("  (sort: " + string(s.SiteSortColumn) + " " + arrow + ")")
+	}
+
+	// Show the search being typed, or the applied search
+	if s.SiteSearchActive {
+		buttons += "\n\n" + renderFieldLabel("Search:", true, t) + " " + s.SiteSearch + "_"
+	} else if s.SiteSearch != "" {
+		title += helpStyle.Render("  (search: " + s.SiteSearch + ")")
+	}
+
+	// Only list sites matching the active tag filter and search
+	sites := s.VisibleSites()
+
 	var content string
 	if len(s.Sites) == 0 {
 		content = helpStyle.Render("No sites yet. Click 'Create Site' or press 'n'.")
+	} else if len(sites) == 0 && s.SiteSearch != "" {
+		content = helpStyle.Render("No sites match '" + s.SiteSearch + "'. Press '/' then Esc to clear the search.")
+	} else if len(sites) == 0 {
+		content = helpStyle.Render("No sites tagged '" + s.SiteTagFilter + "'. Press 't' to change the filter.")
 	} else {
 		// 1. Build table rows (data only, NO buttons)
 		var rows []table.Row
-		for _, site := range s.Sites {
-			// Get domain and node names
-			nodeName := site.NodeID.String()[:8] + "..."
-
-			// Find node name
-			for _, n := range s.Nodes {
-				if n.ID == site.NodeID {
-					nodeName = n.Name
-					break
+		for _, site := range sites {
+			// Get domain and node names, counting any replicas after the primary node
+			nodeName := "(none)"
+			if nodeNames := s.SiteNodeNames(&site); len(nodeNames) > 0 {
+				nodeName = nodeNames[0]
+				if len(nodeNames) > 1 {
+					nodeName += fmt.Sprintf(" +%d", len(nodeNames)-1)
 				}
 			}
 
@@ -102,6 +129,15 @@ func RenderSitesListWithZones(s *state.AppState, zm *zone.Manager) string {
 				statusDisplay = "inactive"
 			}
 
+			// Health from the last probe of the site's health path, if it has one
+			healthDisplay := "-"
+			switch s.GetSiteHealth(site.ID) {
+			case models.SiteHealthHealthy:
+				healthDisplay = "healthy"
+			case models.SiteHealthUnhealthy:
+				healthDisplay = "unhealthy"
+			}
+
 			// Get site type display
 			typeDisplay := "Container"
 			if site.GetSiteType() == models.SiteTypeCompose {
@@ -115,6 +151,8 @@ func RenderSitesListWithZones(s *state.AppState, zm *zone.Manager) string {
 				truncate(nodeName, 18),
 				truncate(portDisplay, 8),
 				truncate(statusDisplay, 10),
+				truncate(healthDisplay, 10),
+				truncate(strings.Join(site.Tags, ","), 16),
 			})
 		}
 
@@ -127,6 +165,8 @@ func RenderSitesListWithZones(s *state.AppState, zm *zone.Manager) string {
 				{Title: "Node", Width: 18},
 				{Title: "Port", Width: 8},
 				{Title: "Status", Width: 10},
+				{Title: "Health", Width: 10},
+				{Title: "Tags", Width: 16},
 			}
 			s.SitesTable = components.NewTableComponent(columns, rows)
 			s.SitesTable.SetCursor(s.SitesListIndex)
@@ -136,13 +176,18 @@ func RenderSitesListWithZones(s *state.AppState, zm *zone.Manager) string {
 		}
 
 		// 3. Render table view
+		s.SitesTable.SetSelectedColor(t.Selected)
 		tableView := s.SitesTable.View()
+		if zm != nil {
+			// Rows are selected by clicking, see TableComponent.HandleMouseClick
+			tableView = zm.Mark("table:sites", tableView)
+		}
 
 		// 4. Build action buttons column (aligned with rows)
 		var actionsColumn strings.Builder
 		actionsColumn.WriteString("\n\n") // Header padding
 
-		for _, site := range s.Sites {
+		for _, site := range sites {
 			var buttons []string
 
 			// Only show deploy and DNS buttons for inactive/failed sites
@@ -156,9 +201,9 @@ func RenderSitesListWithZones(s *state.AppState, zm *zone.Manager) string {
 					Icon:    true,
 				}
 				if zm != nil {
-					buttons = append(buttons, dnsBtn.RenderWithZone(zm))
+					buttons = append(buttons, dnsBtn.RenderWithZone(zm, t))
 				} else {
-					buttons = append(buttons, dnsBtn.Render())
+					buttons = append(buttons, dnsBtn.Render(t))
 				}
 
 				// Deploy button
@@ -170,9 +215,9 @@ func RenderSitesListWithZones(s *state.AppState, zm *zone.Manager) string {
 					Icon:    true,
 				}
 				if zm != nil {
-					buttons = append(buttons, deployBtn.RenderWithZone(zm))
+					buttons = append(buttons, deployBtn.RenderWithZone(zm, t))
 				} else {
-					buttons = append(buttons, deployBtn.Render())
+					buttons = append(buttons, deployBtn.Render(t))
 				}
 			}
 
@@ -200,9 +245,9 @@ func RenderSitesListWithZones(s *state.AppState, zm *zone.Manager) string {
 			// Add control button if status is not inactive
 			if (site.Status != models.SiteStatusInactive && site.Status != models.SiteStatusFailed) && site.Status != "" {
 				if zm != nil {
-					buttons = append(buttons, controlBtn.RenderWithZone(zm))
+					buttons = append(buttons, controlBtn.RenderWithZone(zm, t))
 				} else {
-					buttons = append(buttons, controlBtn.Render())
+					buttons = append(buttons, controlBtn.Render(t))
 				}
 			}
 
@@ -222,11 +267,11 @@ func RenderSitesListWithZones(s *state.AppState, zm *zone.Manager) string {
 			}
 
 			if zm != nil {
-				buttons = append(buttons, editBtn.RenderWithZone(zm))
-				buttons = append(buttons, deleteBtn.RenderWithZone(zm))
+				buttons = append(buttons, editBtn.RenderWithZone(zm, t))
+				buttons = append(buttons, deleteBtn.RenderWithZone(zm, t))
 			} else {
-				buttons = append(buttons, editBtn.Render())
-				buttons = append(buttons, deleteBtn.Render())
+				buttons = append(buttons, editBtn.Render(t))
+				buttons = append(buttons, deleteBtn.Render(t))
 			}
 
 			actionLine := strings.Join(buttons, " ")
@@ -242,9 +287,9 @@ func RenderSitesListWithZones(s *state.AppState, zm *zone.Manager) string {
 
 		// 6. Build sidebar for selected site
 		var sidebar string
-		if len(s.Sites) > 0 && s.SitesListIndex >= 0 && s.SitesListIndex < len(s.Sites) {
-			site := &s.Sites[s.SitesListIndex]
-			sidebar = renderSiteSidebar(s, site)
+		if s.SitesListIndex >= 0 && s.SitesListIndex < len(sites) {
+			site := &sites[s.SitesListIndex]
+			sidebar = renderSiteSidebar(s, site, t)
 		}
 
 		// 7. Join main content + sidebar
@@ -260,7 +305,7 @@ func RenderSitesListWithZones(s *state.AppState, zm *zone.Manager) string {
 		}
 	}
 
-	help := helpStyle.Render("\n\nPress j/k or arrows to navigate • Space/Enter to deploy • s to start/stop • e to edit • d to delete • n to create • Esc to go back")
+	help := helpStyle.Render("\n\nPress j/k or arrows to navigate • Space/Enter to deploy • p to preview deploy • s to start/stop • e to edit • i for details • l for logs • y to copy ID • d to delete • u to restore deleted • n to create • t to filter by tag • / to search • o/O to sort • F5 to refresh status • Esc to go back")
 
 	return lipgloss.JoinVertical(
 		lipgloss.Left,
@@ -293,15 +338,15 @@ func truncate(s string, maxLen int) string {
 }
 
 // RenderSiteCreate renders the site creation form
-func RenderSiteCreate(s *state.AppState) string {
-	return RenderSiteCreateWithZones(s, nil)
+func RenderSiteCreate(s *state.AppState, t theme.Theme) string {
+	return RenderSiteCreateWithZones(s, nil, t)
 }
 
 // RenderSiteCreateWithZones renders the site creation form with clickable fields
-func RenderSiteCreateWithZones(s *state.AppState, zm *zone.Manager) string {
-	// Always ensure form is properly initialized (7 fields: name, node, docker image/compose path, docker username, docker token, ssl email, config file)
-	if len(s.FormFields) != 7 {
-		s.FormFields = []string{"", "", "", "", "", "", ""}
+func RenderSiteCreateWithZones(s *state.AppState, zm *zone.Manager, t theme.Theme) string {
+	// Always ensure form is properly initialized (16 fields: name, node, docker image/compose path, docker username, docker token, ssl email, config file, tags, cert resolver, extra hostnames, cpu limit, memory limit, volumes, docker registry, health path, health status)
+	if len(s.FormFields) != 16 {
+		s.FormFields = []string{"", "", "", "", "", "", "", "", models.DefaultCertResolver, "", "", "", "", "", "", ""}
 	}
 
 	// Only reset field index if it's out of bounds (-1 is valid for site type selector)
@@ -337,7 +382,7 @@ func RenderSiteCreateWithZones(s *state.AppState, zm *zone.Manager) string {
 	var fields string
 
 	// Site Type selector (special field index -1, rendered first)
-	siteTypeLabel := renderFieldLabel("Deployment Type:", s.CurrentFieldIndex == -1)
+	siteTypeLabel := renderFieldLabel("Deployment Type:", s.CurrentFieldIndex == -1, t)
 	siteTypeValue := "Container"
 	if isCompose {
 		siteTypeValue = "Compose"
@@ -362,6 +407,17 @@ func RenderSiteCreateWithZones(s *state.AppState, zm *zone.Manager) string {
 		fields += dropdownStr
 	}
 
+	// Image source toggle and config files (container sites only)
+	if !isCompose {
+		fields += renderImageSourceLine(s)
+	}
+	fields += renderSSLLine(s)
+	fields += renderAutoRestartLine(s)
+	if !isCompose {
+		fields += renderStrategyLine(s)
+		fields += renderConfigFilesLine(s)
+	}
+
 	// Define labels based on site type
 	var labels []string
 	if isCompose {
@@ -373,6 +429,34 @@ func RenderSiteCreateWithZones(s *state.AppState, zm *zone.Manager) string {
 			"", // Hidden (docker token)
 			"SSL Email (for Let's Encrypt):",
 			"", // Hidden (config file - not applicable for compose)
+			"Tags (comma-separated):",
+			"Cert Resolver:",
+			"Extra Hostnames (comma-separated):",
+			"", // Hidden (cpu limit - set limits in the compose file)
+			"", // Hidden (memory limit - set limits in the compose file)
+			"", // Hidden (volumes - declare volumes in the compose file)
+			"", // Hidden (docker registry)
+			"Health Check Path (optional):",
+			"Expected Health Status (optional):",
+		}
+	} else if s.IsBuildSource() {
+		labels = []string{
+			"Name:",
+			"Node:",
+			"Build Context (path or git URL):",
+			"Dockerfile (optional):",
+			"", // Hidden (docker token - not used for builds)
+			"SSL Email (for Let's Encrypt):",
+			"Config File Path (optional):",
+			"Tags (comma-separated):",
+			"Cert Resolver:",
+			"Extra Hostnames (comma-separated):",
+			"CPU Limit (cores, optional):",
+			"Memory Limit (MB, optional):",
+			"Volumes (host:container[:ro], comma-separated):",
+			"", // Hidden (docker registry - not used for builds)
+			"Health Check Path (optional):",
+			"Expected Health Status (optional):",
 		}
 	} else {
 		labels = []string{
@@ -383,6 +467,15 @@ func RenderSiteCreateWithZones(s *state.AppState, zm *zone.Manager) string {
 			"Docker Token:",
 			"SSL Email (for Let's Encrypt):",
 			"Config File Path (optional):",
+			"Tags (comma-separated):",
+			"Cert Resolver:",
+			"Extra Hostnames (comma-separated):",
+			"CPU Limit (cores, optional):",
+			"Memory Limit (MB, optional):",
+			"Volumes (host:container[:ro], comma-separated):",
+			"Docker Registry (optional):",
+			"Health Check Path (optional):",
+			"Expected Health Status (optional):",
 		}
 	}
 
@@ -394,6 +487,10 @@ func RenderSiteCreateWithZones(s *state.AppState, zm *zone.Manager) string {
 		}
 
 		value := s.FormFields[i]
+		if i == 4 {
+			// Mask the registry token
+			value = maskSecret(value, s.RevealSecrets)
+		}
 		displayValue := value
 		isFocused := i == s.CurrentFieldIndex
 
@@ -403,7 +500,7 @@ func RenderSiteCreateWithZones(s *state.AppState, zm *zone.Manager) string {
 		}
 
 		// Render label with focus styling
-		styledLabel := renderFieldLabel(label, isFocused)
+		styledLabel := renderFieldLabel(label, isFocused, t)
 
 		// Wrap the entire field line in a clickable zone
 		fieldLine := styledLabel + " " + displayValue + "\n"
@@ -413,14 +510,14 @@ func RenderSiteCreateWithZones(s *state.AppState, zm *zone.Manager) string {
 		if isFocused && i == 1 && s.DropdownOpen {
 			// Node dropdown
 			dropdownOptions := renderDropdownOptions(s, s.Nodes, s.DropdownIndex, func(n models.Node) string {
-				return n.Name
+				return nodeOptionLabel(s, n)
 			})
 			fields += dropdownOptions + "\n"
 		}
 	}
 
 	// Render domain mappings section
-	domainMappingsSection := renderDomainMappingsSection(s, zm)
+	domainMappingsSection := renderDomainMappingsSection(s, zm, t)
 	fields += "\n" + domainMappingsSection
 
 	helpText := "\nTab/Shift+Tab to navigate, Enter to create, Esc to cancel"
@@ -435,24 +532,52 @@ func RenderSiteCreateWithZones(s *state.AppState, zm *zone.Manager) string {
 	case 1:
 		// On Node dropdown field
 		if s.DropdownOpen {
-			helpText = "\nUp/Down to select, Enter/Tab to confirm, Esc to cancel"
+			helpText = "\nUp/Down to select, Enter/Tab to confirm, Space to add or remove a replica node, Esc to close"
 		} else {
-			helpText = "\nPress Enter or Down to open dropdown, Tab to skip"
+			helpText = "\nPress Enter or Down to open dropdown, Tab to skip • Several comma-separated nodes run the site as replicas"
 		}
 	case 2:
 		if isCompose {
 			helpText = "\nEnter path to docker-compose.yml file (port will be auto-detected)"
+		} else if s.IsBuildSource() {
+			helpText = "\nDirectory on the node or git URL to build from • Ctrl+B to use a prebuilt image"
 		} else {
-			helpText = "\nDocker image to deploy (e.g., nginx:latest, myrepo/myimage:v1)"
+			helpText = "\nDocker image to deploy (e.g., nginx:latest, myrepo/myimage:v1) • Ctrl+B to build from source"
 		}
 	case 3:
-		helpText = "\nLeave blank to skip Docker Auth (if image is public)"
+		if s.IsBuildSource() {
+			helpText = "\nDockerfile path relative to the build context (defaults to Dockerfile)"
+		} else {
+			helpText = "\nLeave blank to skip Docker Auth (if image is public)"
+		}
 	case 4:
 		helpText = "\nLeave blank to skip Docker Auth (if image is public)"
 	case 5:
-		helpText = "\nEmail for Let's Encrypt SSL certificate notifications (e.g., admin@example.com)"
+		if s.SiteSSLEnabled {
+			helpText = "\nEmail for Let's Encrypt SSL certificate notifications (e.g., admin@example.com)"
+		} else {
+			helpText = "\nSSL is disabled for this site • Ctrl+T to enable"
+		}
 	case 6:
-		helpText = "\nEnter full path to config file (will be loaded when site is created)"
+		helpText = "\nEnter full path to config file (will be loaded when site is created) • Ctrl+F to edit config files"
+	case 7:
+		helpText = "\nOptional tags for grouping sites (e.g., customer-a, internal)"
+	case 8:
+		helpText = "\nTraefik certificate resolver used for SSL (must match your Traefik config, e.g., letsencrypt)"
+	case 9:
+		helpText = "\nOther hostnames served on the first mapping's port (e.g., www.example.com), included in the SSL certificate"
+	case 10:
+		helpText = "\nMaximum CPU cores the container may use (e.g., 0.5 or 2) • Leave blank for no limit"
+	case 11:
+		helpText = "\nMaximum memory for the container in megabytes (e.g., 512) • Leave blank for no limit"
+	case 12:
+		helpText = "\nPersistent mounts, e.g. /srv/app/data:/data, pgdata:/var/lib/postgresql/data:ro • Container paths must be absolute"
+	case 13:
+		helpText = "\nRegistry to log in to with the Docker credentials (e.g., ghcr.io) • Leave blank to infer it from the image"
+	case 14:
+		helpText = "\nPath probed on the site's first domain to check it is healthy (e.g., /healthz) • Leave blank for no health check"
+	case 15:
+		helpText = "\nStatus code the health check path should return • Leave blank for 200"
 	case 200:
 		// Special index for domain mappings
 		if isCompose {
@@ -475,12 +600,12 @@ func RenderSiteCreateWithZones(s *state.AppState, zm *zone.Manager) string {
 }
 
 // RenderSiteEdit renders the site editing form
-func RenderSiteEdit(s *state.AppState) string {
-	return RenderSiteEditWithZones(s, nil)
+func RenderSiteEdit(s *state.AppState, t theme.Theme) string {
+	return RenderSiteEditWithZones(s, nil, t)
 }
 
 // RenderSiteEditWithZones renders the site editing form with clickable fields
-func RenderSiteEditWithZones(s *state.AppState, zm *zone.Manager) string {
+func RenderSiteEditWithZones(s *state.AppState, zm *zone.Manager, t theme.Theme) string {
 	// Find the site being edited
 	site := s.GetSiteByID(s.SelectedSiteID)
 	if site == nil {
@@ -494,31 +619,43 @@ func RenderSiteEditWithZones(s *state.AppState, zm *zone.Manager) string {
 	// Only initialize form data on first entry to edit screen
 	// This prevents typed input from being overwritten on every render
 	if !s.EditFormInitialized {
-		s.FormFields = make([]string, 7)
+		s.FormFields = make([]string, 16)
 		s.FormFields[0] = site.Name
+		s.ImageSource = "image"
 		if isCompose {
 			s.FormFields[2] = "(Compose content loaded)" // Placeholder for compose sites
+			s.FormFields[3] = site.DockerUsername
+			s.FormFields[4] = site.DockerToken
+		} else if site.IsBuild() {
+			s.ImageSource = "build"
+			s.FormFields[2] = site.Build.Context
+			s.FormFields[3] = site.Build.Dockerfile
 		} else {
 			s.FormFields[2] = site.DockerImage
+			s.FormFields[3] = site.DockerUsername
+			s.FormFields[4] = site.DockerToken
 		}
-		s.FormFields[3] = site.DockerUsername
-		s.FormFields[4] = site.DockerToken
 		s.FormFields[5] = site.SSLEmail
-
-		// Find node name
-		for _, n := range s.Nodes {
-			if n.ID == site.NodeID {
-				s.FormFields[1] = n.Name
-				break
-			}
-		}
-
-		// Config file path (leave blank or show first config file name)
-		if len(site.ConfigFiles) > 0 {
-			s.FormFields[6] = site.ConfigFiles[0].Name
-		} else {
-			s.FormFields[6] = ""
-		}
+		s.SiteSSLEnabled = site.SSLEnabled
+		s.SiteAutoRestart = site.AutoRestart
+		s.SiteBlueGreen = site.GetDeploymentStrategy() == models.StrategyBlueGreen
+		s.FormFields[7] = models.FormatTags(site.Tags)
+		s.FormFields[8] = site.GetCertResolver()
+		s.FormFields[9] = models.FormatTags(site.ExtraHosts)
+		s.FormFields[10] = models.FormatCPULimit(site.CPULimit)
+		s.FormFields[11] = models.FormatMemoryLimit(site.MemoryLimitMB)
+		s.FormFields[12] = models.FormatVolumeMounts(site.Volumes)
+		s.FormFields[13] = site.DockerRegistry
+		s.FormFields[14] = site.HealthPath
+		s.FormFields[15] = models.FormatHealthStatus(site.HealthStatus)
+
+		// Node names, the primary node first
+		s.FormFields[1] = models.FormatTags(s.SiteNodeNames(site))
+
+		// Config files are edited on the config files screen; the path field only loads another one
+		s.FormFields[6] = ""
+		s.ConfigFileDrafts = append([]models.ConfigFile{}, site.ConfigFiles...)
+		s.ConfigFilesIndex = 0
 
 		// Initialize domain mapping pairs from current site data
 		s.DomainMappingPairs = []state.DomainMappingPair{}
@@ -576,6 +713,17 @@ func RenderSiteEditWithZones(s *state.AppState, zm *zone.Manager) string {
 	siteTypeLine := "  Deployment Type: " + siteTypeValue + " (read-only)\n"
 	fields += siteTypeLine
 
+	// Image source toggle and config files (container sites only)
+	if !isCompose {
+		fields += renderImageSourceLine(s)
+	}
+	fields += renderSSLLine(s)
+	fields += renderAutoRestartLine(s)
+	if !isCompose {
+		fields += renderStrategyLine(s)
+		fields += renderConfigFilesLine(s)
+	}
+
 	// Define labels based on site type
 	var labels []string
 	if isCompose {
@@ -587,6 +735,34 @@ func RenderSiteEditWithZones(s *state.AppState, zm *zone.Manager) string {
 			"", // Hidden (docker token)
 			"SSL Email (for Let's Encrypt):",
 			"", // Hidden (config file)
+			"Tags (comma-separated):",
+			"Cert Resolver:",
+			"Extra Hostnames (comma-separated):",
+			"", // Hidden (cpu limit - set limits in the compose file)
+			"", // Hidden (memory limit - set limits in the compose file)
+			"", // Hidden (volumes - declare volumes in the compose file)
+			"", // Hidden (docker registry)
+			"Health Check Path (optional):",
+			"Expected Health Status (optional):",
+		}
+	} else if s.IsBuildSource() {
+		labels = []string{
+			"Name:",
+			"Node:",
+			"Build Context (path or git URL):",
+			"Dockerfile (optional):",
+			"", // Hidden (docker token - not used for builds)
+			"SSL Email (for Let's Encrypt):",
+			"Config File Path (optional):",
+			"Tags (comma-separated):",
+			"Cert Resolver:",
+			"Extra Hostnames (comma-separated):",
+			"CPU Limit (cores, optional):",
+			"Memory Limit (MB, optional):",
+			"Volumes (host:container[:ro], comma-separated):",
+			"", // Hidden (docker registry - not used for builds)
+			"Health Check Path (optional):",
+			"Expected Health Status (optional):",
 		}
 	} else {
 		labels = []string{
@@ -597,6 +773,15 @@ func RenderSiteEditWithZones(s *state.AppState, zm *zone.Manager) string {
 			"Docker Token:",
 			"SSL Email (for Let's Encrypt):",
 			"Config File Path (optional):",
+			"Tags (comma-separated):",
+			"Cert Resolver:",
+			"Extra Hostnames (comma-separated):",
+			"CPU Limit (cores, optional):",
+			"Memory Limit (MB, optional):",
+			"Volumes (host:container[:ro], comma-separated):",
+			"Docker Registry (optional):",
+			"Health Check Path (optional):",
+			"Expected Health Status (optional):",
 		}
 	}
 
@@ -608,6 +793,10 @@ func RenderSiteEditWithZones(s *state.AppState, zm *zone.Manager) string {
 		}
 
 		value := s.FormFields[i]
+		if i == 4 {
+			// Mask the registry token
+			value = maskSecret(value, s.RevealSecrets)
+		}
 		displayValue := value
 		isFocused := i == s.CurrentFieldIndex
 
@@ -617,7 +806,7 @@ func RenderSiteEditWithZones(s *state.AppState, zm *zone.Manager) string {
 		}
 
 		// Render label with focus styling
-		styledLabel := renderFieldLabel(label, isFocused)
+		styledLabel := renderFieldLabel(label, isFocused, t)
 
 		// Wrap the entire field line in a clickable zone
 		fieldLine := styledLabel + " " + displayValue + "\n"
@@ -627,14 +816,14 @@ func RenderSiteEditWithZones(s *state.AppState, zm *zone.Manager) string {
 		if isFocused && i == 1 && s.DropdownOpen {
 			// Node dropdown
 			dropdownOptions := renderDropdownOptions(s, s.Nodes, s.DropdownIndex, func(n models.Node) string {
-				return n.Name
+				return nodeOptionLabel(s, n)
 			})
 			fields += dropdownOptions + "\n"
 		}
 	}
 
 	// Render domain mappings section
-	domainMappingsSection := renderDomainMappingsSection(s, zm)
+	domainMappingsSection := renderDomainMappingsSection(s, zm, t)
 	fields += "\n" + domainMappingsSection
 
 	// Add ENV vars hint (only for container deployments)
@@ -647,24 +836,52 @@ func RenderSiteEditWithZones(s *state.AppState, zm *zone.Manager) string {
 	case 1:
 		// On Node dropdown field
 		if s.DropdownOpen {
-			helpText = "\nUp/Down to select, Enter/Tab to confirm, Esc to cancel"
+			helpText = "\nUp/Down to select, Enter/Tab to confirm, Space to add or remove a replica node, Esc to close"
 		} else {
-			helpText = "\nPress Enter or Down to open dropdown, Tab to skip"
+			helpText = "\nPress Enter or Down to open dropdown, Tab to skip • Several comma-separated nodes run the site as replicas"
 		}
 	case 2:
 		if isCompose {
 			helpText = "\nCompose content is read-only (re-deploy to change)"
+		} else if s.IsBuildSource() {
+			helpText = "\nDirectory on the node or git URL to build from • Ctrl+B to use a prebuilt image"
 		} else {
-			helpText = "\nDocker image to deploy (e.g., nginx:latest, myrepo/myimage:v1)"
+			helpText = "\nDocker image to deploy (e.g., nginx:latest, myrepo/myimage:v1) • Ctrl+B to build from source"
 		}
 	case 3:
-		helpText = "\nLeave blank to skip Docker Auth (if image is public)"
+		if s.IsBuildSource() {
+			helpText = "\nDockerfile path relative to the build context (defaults to Dockerfile)"
+		} else {
+			helpText = "\nLeave blank to skip Docker Auth (if image is public)"
+		}
 	case 4:
 		helpText = "\nLeave blank to skip Docker Auth (if image is public)"
 	case 5:
-		helpText = "\nEmail for Let's Encrypt SSL certificate notifications (e.g., admin@example.com)"
+		if s.SiteSSLEnabled {
+			helpText = "\nEmail for Let's Encrypt SSL certificate notifications (e.g., admin@example.com)"
+		} else {
+			helpText = "\nSSL is disabled for this site • Ctrl+T to enable"
+		}
 	case 6:
-		helpText = "\nEnter full path to config file (will be loaded when site is saved)"
+		helpText = "\nEnter full path to another config file (will be loaded when site is saved) • Ctrl+F to edit config files"
+	case 7:
+		helpText = "\nOptional tags for grouping sites (e.g., customer-a, internal)"
+	case 8:
+		helpText = "\nTraefik certificate resolver used for SSL (must match your Traefik config, e.g., letsencrypt)"
+	case 9:
+		helpText = "\nOther hostnames served on the first mapping's port (e.g., www.example.com), included in the SSL certificate"
+	case 10:
+		helpText = "\nMaximum CPU cores the container may use (e.g., 0.5 or 2) • Leave blank for no limit"
+	case 11:
+		helpText = "\nMaximum memory for the container in megabytes (e.g., 512) • Leave blank for no limit"
+	case 12:
+		helpText = "\nPersistent mounts, e.g. /srv/app/data:/data, pgdata:/var/lib/postgresql/data:ro • Container paths must be absolute"
+	case 13:
+		helpText = "\nRegistry to log in to with the Docker credentials (e.g., ghcr.io) • Leave blank to infer it from the image"
+	case 14:
+		helpText = "\nPath probed on the site's first domain to check it is healthy (e.g., /healthz) • Leave blank for no health check"
+	case 15:
+		helpText = "\nStatus code the health check path should return • Leave blank for 200"
 	case 200:
 		// Special index for domain mappings
 		helpText = "\nSelect subdomain/domain/port, Tab to switch fields, +/- buttons to add/remove mappings"
@@ -682,15 +899,64 @@ func RenderSiteEditWithZones(s *state.AppState, zm *zone.Manager) string {
 	return title + "\n\n" + fields + "\n" + help + "\n" + note
 }
 
+// renderImageSourceLine renders the prebuilt image / build from source toggle for container sites
+func renderImageSourceLine(s *state.AppState) string {
+	source := "Prebuilt image"
+	if s.IsBuildSource() {
+		source = "Build from source"
+	}
+	return "  Image Source: " + source + " (Ctrl+B to switch)\n"
+}
+
+// renderSSLLine renders the SSL toggle for the site forms
+func renderSSLLine(s *state.AppState) string {
+	ssl := "Disabled"
+	if s.SiteSSLEnabled {
+		ssl = "Enabled"
+	}
+	return "  SSL: " + ssl + " (Ctrl+T to toggle)\n"
+}
+
+// renderAutoRestartLine renders the auto-restart toggle for the site forms
+func renderAutoRestartLine(s *state.AppState) string {
+	restart := "Off"
+	if s.SiteAutoRestart {
+		restart = fmt.Sprintf("On (up to %d attempts)", state.MaxAutoRestarts)
+	}
+	return "  Auto-restart when failed: " + restart + " (Ctrl+G to toggle)\n"
+}
+
+// renderStrategyLine renders the deployment strategy toggle for container site forms
+func renderStrategyLine(s *state.AppState) string {
+	strategy := "Recreate"
+	if s.SiteBlueGreen {
+		strategy = "Blue/green (Traefik nodes only)"
+	}
+	return "  Deploy Strategy: " + strategy + " (Ctrl+X to toggle)\n"
+}
+
+// renderConfigFilesLine renders how many config files the site form has
+func renderConfigFilesLine(s *state.AppState) string {
+	return fmt.Sprintf("  Config Files: %d (Ctrl+F to edit)\n", len(s.ConfigFileDrafts))
+}
+
 // renderEnvVarsSection renders the environment variables section with +/- buttons
-func renderEnvVarsSection(s *state.AppState, zm *zone.Manager) string {
+func renderEnvVarsSection(s *state.AppState, zm *zone.Manager, t theme.Theme) string {
 	var section strings.Builder
 
 	section.WriteString("Environment Variables:\n")
 
+	// Count keys so rows reusing one can be flagged before saving
+	keyCounts := make(map[string]int)
+	for _, pair := range s.EnvVarPairs {
+		keyCounts[strings.TrimSpace(pair.Key)]++
+	}
+
 	for i, pair := range s.EnvVarPairs {
 		// Determine if this pair is focused
 		isFocused := s.CurrentFieldIndex == 100 && s.EnvVarFocusedPair == i
+		key := strings.TrimSpace(pair.Key)
+		secret := models.IsSecretEnvVar(key)
 
 		// Render key field
 		keyValue := pair.Key
@@ -705,25 +971,44 @@ func renderEnvVarsSection(s *state.AppState, zm *zone.Manager) string {
 			keyValue = keyValue[:cursor] + "_" + keyValue[cursor:]
 		}
 
-		// Render value field
-		valueDisplay := pair.Value
+		// Render value field, masking values of keys that look like secrets
+		mask := func(value string) string {
+			if secret {
+				return maskSecret(value, s.RevealSecrets)
+			}
+			return value
+		}
+		valueDisplay := mask(pair.Value)
 		if isFocused && s.EnvVarFocusedField == 1 {
 			cursor := s.CursorPosition
 			if cursor < 0 {
 				cursor = 0
 			}
-			if cursor > len(valueDisplay) {
-				cursor = len(valueDisplay)
+			if cursor > len(pair.Value) {
+				cursor = len(pair.Value)
 			}
-			valueDisplay = valueDisplay[:cursor] + "_" + valueDisplay[cursor:]
+			valueDisplay = mask(pair.Value[:cursor]) + "_" + mask(pair.Value[cursor:])
+		}
+
+		// Flag secrets and keys that won't save
+		var note string
+		switch {
+		case key == "" && strings.TrimSpace(pair.Value) != "":
+			note = " ⚠ key required"
+		case key != "" && keyCounts[key] > 1:
+			note = " ⚠ duplicate key"
+		case key != "" && models.ValidateEnvVarKey(key) != nil:
+			note = " ⚠ invalid key"
+		case secret:
+			note = " 🔒"
 		}
 
 		// Build the row with separate focus styling for Key and Value
 		keyFocused := isFocused && s.EnvVarFocusedField == 0
 		valueFocused := isFocused && s.EnvVarFocusedField == 1
 
-		styledKeyLabel := renderFieldLabel(fmt.Sprintf("[%d] Key:", i+1), keyFocused)
-		styledValueLabel := renderFieldLabel("Value:", valueFocused)
+		styledKeyLabel := renderFieldLabel(fmt.Sprintf("[%d] Key:", i+1), keyFocused, t)
+		styledValueLabel := renderFieldLabel("Value:", valueFocused, t)
 
 		line := fmt.Sprintf("%s %-20s %s %-30s", styledKeyLabel, keyValue, styledValueLabel, valueDisplay)
 
@@ -748,9 +1033,9 @@ func renderEnvVarsSection(s *state.AppState, zm *zone.Manager) string {
 			if len(s.EnvVarPairs) > 1 || i > 0 {
 				section.WriteString(zm.Mark(removeZoneID, removeBtn))
 			}
-			section.WriteString("\n")
+			section.WriteString(note + "\n")
 		} else {
-			section.WriteString(line + " " + addBtn + removeBtn + "\n")
+			section.WriteString(line + " " + addBtn + removeBtn + note + "\n")
 		}
 	}
 
@@ -758,7 +1043,7 @@ func renderEnvVarsSection(s *state.AppState, zm *zone.Manager) string {
 }
 
 // renderDomainMappingsSection renders the domain mappings section with +/- buttons
-func renderDomainMappingsSection(s *state.AppState, zm *zone.Manager) string {
+func renderDomainMappingsSection(s *state.AppState, zm *zone.Manager, t theme.Theme) string {
 	var section strings.Builder
 
 	section.WriteString("Domain Mappings:\n")
@@ -804,7 +1089,7 @@ func renderDomainMappingsSection(s *state.AppState, zm *zone.Manager) string {
 
 		// Build the row with focus styling
 		rowLabel := fmt.Sprintf("[%d] Subdomain:", i+1)
-		styledPrefix := renderFieldLabel(rowLabel, isFocused)
+		styledPrefix := renderFieldLabel(rowLabel, isFocused, t)
 
 		line := fmt.Sprintf("%s %-15s Domain: %-25s Port (container:host): %-6s",
 			styledPrefix, subdomainValue, domainDisplay, portValue)
@@ -847,6 +1132,16 @@ func renderDomainMappingsSection(s *state.AppState, zm *zone.Manager) string {
 	return section.String()
 }
 
+// nodeOptionLabel names a node in the Node dropdown, ticking the nodes already in the field
+func nodeOptionLabel(s *state.AppState, n models.Node) string {
+	for _, name := range models.ParseTags(s.FormFields[1]) {
+		if name == n.Name {
+			return "✓ " + n.Name
+		}
+	}
+	return n.Name
+}
+
 // renderDropdownOptions renders a dropdown list of options
 func renderDropdownOptions[T any](_ *state.AppState, items []T, selectedIndex int, getName func(T) string) string {
 	if len(items) == 0 {
@@ -897,10 +1192,10 @@ func renderDropdownOptions[T any](_ *state.AppState, items []T, selectedIndex in
 }
 
 // renderSiteSidebar renders a sidebar showing relationships for the selected site
-func renderSiteSidebar(s *state.AppState, site *models.Site) string {
+func renderSiteSidebar(s *state.AppState, site *models.Site, t theme.Theme) string {
 	sidebarStyle := lipgloss.NewStyle().
 		Border(lipgloss.RoundedBorder()).
-		BorderForeground(lipgloss.Color("240")).
+		BorderForeground(t.Muted).
 		Padding(1, 2).
 		Width(35)
 
@@ -954,18 +1249,56 @@ func renderSiteSidebar(s *state.AppState, site *models.Site) string {
 	if nodeInfo == "" {
 		nodeInfo = "🖥️  Node: Not found"
 	}
+	if nodeNames := s.SiteNodeNames(site); len(nodeNames) > 1 {
+		nodeInfo += "\n   Replicas: " + strings.Join(nodeNames[1:], ", ")
+	}
 
-	content := domainInfo + "\n\n" + nodeInfo
+	// Tags
+	tagInfo := "🏷️  Tags: None"
+	if len(site.Tags) > 0 {
+		tagInfo = "🏷️  Tags: " + models.FormatTags(site.Tags)
+	}
+
+	content := domainInfo + "\n\n" + nodeInfo + "\n\n" + tagInfo
 	return sidebarStyle.Render(title + "\n\n" + content)
 }
 
+// RenderSitesDeleted renders the sites deleted this session that can be restored, newest first
+func RenderSitesDeleted(s *state.AppState) string {
+	title := titleStyle.Render("Recently Deleted Sites")
+
+	deleted := s.RecentlyDeletedSites
+	if len(deleted) == 0 {
+		return title + "\n\n" + "No deleted sites to restore\n\n" + helpStyle.Render("Press Esc to go back")
+	}
+
+	var content strings.Builder
+	for i := len(deleted) - 1; i >= 0; i-- {
+		site := deleted[i]
+		prefix := "  "
+		if i == s.DeletedSitesIndex {
+			prefix = "▶ "
+		}
+
+		nodeName := "node deleted"
+		if node := s.GetNodeByID(site.NodeID); node != nil {
+			nodeName = node.Name
+		}
+		content.WriteString(fmt.Sprintf("%s%-24s %-30s %s\n", prefix, truncate(site.Name, 24), truncate(site.ImageSource(), 30), nodeName))
+	}
+
+	help := helpStyle.Render(fmt.Sprintf("\nj/k to select • Enter to restore and redeploy • Esc to go back\nUp to %d sites are kept until Archon exits", state.MaxRecentlyDeletedSites))
+
+	return title + "\n\n" + content.String() + help
+}
+
 // RenderSiteEnvVars renders the dedicated environment variables screen
-func RenderSiteEnvVars(s *state.AppState) string {
-	return RenderSiteEnvVarsWithZones(s, nil)
+func RenderSiteEnvVars(s *state.AppState, t theme.Theme) string {
+	return RenderSiteEnvVarsWithZones(s, nil, t)
 }
 
 // RenderSiteEnvVarsWithZones renders the ENV vars screen with clickable zones
-func RenderSiteEnvVarsWithZones(s *state.AppState, zm *zone.Manager) string {
+func RenderSiteEnvVarsWithZones(s *state.AppState, zm *zone.Manager, t theme.Theme) string {
 	// Get site being edited
 	site := s.GetSiteByID(s.SelectedSiteID)
 	if site == nil {
@@ -989,9 +1322,55 @@ func RenderSiteEnvVarsWithZones(s *state.AppState, zm *zone.Manager) string {
 	}
 
 	// Render ENV table (reuse existing renderEnvVarsSection)
-	envSection := renderEnvVarsSection(s, zm)
+	envSection := renderEnvVarsSection(s, zm, t)
+
+	// Show the .env path being typed in place of the usual keys
+	if s.EnvImportActive {
+		prompt := renderFieldLabel("Import .env file:", true, t) + " " + s.EnvImportPath + "_"
+		help := helpStyle.Render("\nEnter: import (existing keys are overwritten) • Esc: cancel")
+		return title + "\n\n" + envSection + "\n" + prompt + "\n" + help
+	}
 
-	help := helpStyle.Render("\nTab: switch field • Up/Down: navigate pairs • +/-: add/remove • Enter: save • Esc: back")
+	help := helpStyle.Render("\nTab: switch field • Up/Down: navigate pairs • Ctrl+N/Ctrl+D or +/-: add/remove • Ctrl+F: import .env file • Ctrl+R: reveal 🔒 values • Enter: save • Esc: back")
 
 	return title + "\n\n" + envSection + "\n" + help
 }
+
+// RenderDeployPreview renders the request a deploy would send, in a scrollable viewport
+func RenderDeployPreview(s *state.AppState) string {
+	if s.DeployPreview == nil {
+		return titleStyle.Render("Deploy Preview") + "\n\n" + "Nothing to preview\n\n" + helpStyle.Render("Press Esc to go back")
+	}
+
+	site := s.GetSiteByID(s.DeployPreview.SiteID)
+	if site == nil {
+		return titleStyle.Render("Deploy Preview") + "\n\n" + "Site not found\n\n" + helpStyle.Render("Press Esc to go back")
+	}
+
+	title := titleStyle.Render("🔍 Deploy Preview: " + site.Name)
+
+	note := helpStyle.Render("Request that would be sent to the node (secrets redacted). Nothing has been sent.")
+	fullContent := note + "\n\n" + s.DeployPreview.Request
+
+	// Initialize viewport if needed
+	if s.DeployPreviewViewport.Width == 0 {
+		// Leave room for title (3 lines), help (2 lines), and some padding
+		viewportHeight := s.WindowHeight - 8
+		if viewportHeight < 10 {
+			viewportHeight = 10
+		}
+
+		s.DeployPreviewViewport = viewport.New(s.WindowWidth-4, viewportHeight)
+	}
+	s.DeployPreviewViewport.SetContent(fullContent)
+
+	help := helpStyle.Render("\n↑/↓ to scroll • PgUp/PgDn for page • Home/End to jump • Enter to deploy • Esc to go back")
+
+	return lipgloss.JoinVertical(
+		lipgloss.Left,
+		title,
+		"",
+		s.DeployPreviewViewport.View(),
+		help,
+	)
+}
@@ -0,0 +1,67 @@
+package screens
+
+import (
+	"github.com/charmbracelet/lipgloss"
+
+	"github.com/BlueBeard63/archon/internal/ui/theme"
+)
+
+// formLabelFocusedStyle is the style for focused form field labels
+func formLabelFocusedStyle(t theme.Theme) lipgloss.Style {
+	return lipgloss.NewStyle().
+		Foreground(t.Primary).
+		Bold(true)
+}
+
+// DNS sync diff, also used for other good, bad and changed states
+
+func diffAddedStyle(t theme.Theme) lipgloss.Style {
+	return lipgloss.NewStyle().Foreground(t.Success)
+}
+
+func diffRemovedStyle(t theme.Theme) lipgloss.Style {
+	return lipgloss.NewStyle().Foreground(t.Error)
+}
+
+func diffModifiedStyle(t theme.Theme) lipgloss.Style {
+	return lipgloss.NewStyle().Foreground(t.Warning)
+}
+
+// Dashboard failed sites panel
+
+func alertStyle(t theme.Theme) lipgloss.Style {
+	return lipgloss.NewStyle().Foreground(t.Error)
+}
+
+func alertBoxStyle(t theme.Theme) lipgloss.Style {
+	return boxStyle.BorderForeground(t.Error)
+}
+
+// Site detail tabs and metrics gauges
+
+func siteDetailActiveTabStyle(t theme.Theme) lipgloss.Style {
+	return lipgloss.NewStyle().
+		Foreground(t.Primary).
+		Bold(true).
+		Underline(true)
+}
+
+func siteDetailTabStyle(t theme.Theme) lipgloss.Style {
+	return lipgloss.NewStyle().Foreground(t.Muted)
+}
+
+func gaugeFilledStyle(t theme.Theme) lipgloss.Style {
+	return lipgloss.NewStyle().Foreground(t.Accent)
+}
+
+func gaugeWarnStyle(t theme.Theme) lipgloss.Style {
+	return lipgloss.NewStyle().Foreground(t.Warning)
+}
+
+func gaugeHighStyle(t theme.Theme) lipgloss.Style {
+	return lipgloss.NewStyle().Foreground(t.Error)
+}
+
+func gaugeEmptyStyle(t theme.Theme) lipgloss.Style {
+	return lipgloss.NewStyle().Foreground(t.Muted)
+}
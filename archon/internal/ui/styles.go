@@ -196,8 +196,8 @@ func MaxWidth(windowWidth int) int {
 
 // MaxHeight returns the maximum height for content
 func MaxHeight(windowHeight int) int {
-	// Account for header (1) + menu (1) + status bar (1)
-	const chrome = 3
+	// Account for header (1) + menu (1) + breadcrumb (1) + status bar (1)
+	const chrome = 4
 	maxH := windowHeight - chrome
 	if maxH < 10 {
 		return 10
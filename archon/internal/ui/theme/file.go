@@ -0,0 +1,132 @@
+package theme
+
+import (
+	"bytes"
+	"errors"
+	"fmt"
+	"os"
+	"strconv"
+	"strings"
+
+	"github.com/charmbracelet/lipgloss"
+	"github.com/pelletier/go-toml/v2"
+)
+
+// colorNames maps the color names accepted in theme files to ANSI color numbers
+var colorNames = map[string]string{
+	"black":          "0",
+	"red":            "1",
+	"green":          "2",
+	"yellow":         "3",
+	"blue":           "4",
+	"magenta":        "5",
+	"cyan":           "6",
+	"white":          "7",
+	"gray":           "8",
+	"grey":           "8",
+	"bright_red":     "9",
+	"bright_green":   "10",
+	"bright_yellow":  "11",
+	"bright_blue":    "12",
+	"bright_magenta": "13",
+	"bright_cyan":    "14",
+	"bright_white":   "15",
+}
+
+// themeFile is the layout of a theme file; unset colors keep the base theme's color
+type themeFile struct {
+	Primary    string `toml:"primary"`
+	Accent     string `toml:"accent"`
+	Success    string `toml:"success"`
+	Warning    string `toml:"warning"`
+	Error      string `toml:"error"`
+	Muted      string `toml:"muted"`
+	Text       string `toml:"text"`
+	Background string `toml:"background"`
+	Selected   string `toml:"selected"`
+}
+
+// LoadFile reads a TOML theme file and applies its colors on top of base
+func LoadFile(path string, base Theme) (Theme, error) {
+	data, err := os.ReadFile(path)
+	if err != nil {
+		return base, fmt.Errorf("failed to read theme file: %w", err)
+	}
+	t, err := Parse(data, base)
+	if err != nil {
+		return base, fmt.Errorf("invalid theme file %s: %w", path, err)
+	}
+	return t, nil
+}
+
+// Parse reads theme file TOML and applies its colors on top of base
+// Each color is a name (e.g., "cyan"), an ANSI color number (0-255) or a hex string ("#RGB" or "#RRGGBB").
+func Parse(data []byte, base Theme) (Theme, error) {
+	var file themeFile
+	decoder := toml.NewDecoder(bytes.NewReader(data))
+	decoder.DisallowUnknownFields()
+	if err := decoder.Decode(&file); err != nil {
+		var strict *toml.StrictMissingError
+		if errors.As(err, &strict) && len(strict.Errors) > 0 {
+			return base, fmt.Errorf("unknown key %q", strings.Join(strict.Errors[0].Key(), "."))
+		}
+		return base, err
+	}
+
+	t := base
+	fields := []struct {
+		key   string
+		value string
+		color *lipgloss.Color
+	}{
+		{"primary", file.Primary, &t.Primary},
+		{"accent", file.Accent, &t.Accent},
+		{"success", file.Success, &t.Success},
+		{"warning", file.Warning, &t.Warning},
+		{"error", file.Error, &t.Error},
+		{"muted", file.Muted, &t.Muted},
+		{"text", file.Text, &t.Text},
+		{"background", file.Background, &t.Background},
+		{"selected", file.Selected, &t.Selected},
+	}
+	for _, f := range fields {
+		if f.value == "" {
+			continue
+		}
+		color, err := ParseColor(f.value)
+		if err != nil {
+			return base, fmt.Errorf("%s: %w", f.key, err)
+		}
+		*f.color = color
+	}
+	return t, nil
+}
+
+// ParseColor converts a color name, ANSI color number or hex string into a lipgloss color
+func ParseColor(value string) (lipgloss.Color, error) {
+	s := strings.ToLower(strings.TrimSpace(value))
+
+	if ansi, ok := colorNames[s]; ok {
+		return lipgloss.Color(ansi), nil
+	}
+
+	if strings.HasPrefix(s, "#") {
+		hex := s[1:]
+		if len(hex) != 3 && len(hex) != 6 {
+			return "", fmt.Errorf("invalid hex color %q: expected #RGB or #RRGGBB", value)
+		}
+		if _, err := strconv.ParseUint(hex, 16, 32); err != nil {
+			return "", fmt.Errorf("invalid hex color %q", value)
+		}
+		return lipgloss.Color(s), nil
+	}
+
+	if n, err := strconv.Atoi(s); err == nil {
+		if n < 0 || n > 255 {
+			return "", fmt.Errorf("invalid ANSI color %q: expected 0-255", value)
+		}
+		return lipgloss.Color(s), nil
+	}
+
+	return "", fmt.Errorf("unknown color %q: use a color name, an ANSI number (0-255) or a hex string", value)
+}
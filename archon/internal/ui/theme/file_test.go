@@ -0,0 +1,92 @@
+package theme
+
+import (
+	"strings"
+	"testing"
+
+	"github.com/charmbracelet/lipgloss"
+)
+
+func TestParseColor(t *testing.T) {
+	tests := []struct {
+		value   string
+		want    lipgloss.Color
+		wantErr bool
+	}{
+		{value: "cyan", want: "6"},
+		{value: " Bright_Red ", want: "9"},
+		{value: "208", want: "208"},
+		{value: "#FFAA00", want: "#ffaa00"},
+		{value: "#fa0", want: "#fa0"},
+		{value: "256", wantErr: true},
+		{value: "-1", wantErr: true},
+		{value: "#ff00", wantErr: true},
+		{value: "#gggggg", wantErr: true},
+		{value: "chartreuse", wantErr: true},
+	}
+
+	for _, tt := range tests {
+		t.Run(tt.value, func(t *testing.T) {
+			got, err := ParseColor(tt.value)
+			if (err != nil) != tt.wantErr {
+				t.Fatalf("ParseColor(%q) error = %v, wantErr %v", tt.value, err, tt.wantErr)
+			}
+			if got != tt.want {
+				t.Errorf("ParseColor(%q) = %q, want %q", tt.value, got, tt.want)
+			}
+		})
+	}
+}
+
+func TestParse(t *testing.T) {
+	tests := []struct {
+		name    string
+		data    string
+		want    func(Theme) Theme
+		wantErr string
+	}{
+		{
+			name: "overrides set colors",
+			data: "primary = \"magenta\"\nbackground = \"#002b36\"\nselected = \"33\"\n",
+			want: func(t Theme) Theme {
+				t.Primary = "5"
+				t.Background = "#002b36"
+				t.Selected = "33"
+				return t
+			},
+		},
+		{
+			name: "empty file keeps base",
+			data: "",
+			want: func(t Theme) Theme { return t },
+		},
+		{
+			name:    "invalid color names the key",
+			data:    "error = \"#12345\"\n",
+			wantErr: "error: invalid hex color",
+		},
+		{
+			name:    "unknown key",
+			data:    "highlight = \"red\"\n",
+			wantErr: "highlight",
+		},
+	}
+
+	for _, tt := range tests {
+		t.Run(tt.name, func(t *testing.T) {
+			got, err := Parse([]byte(tt.data), Dark())
+			if tt.wantErr != "" {
+				if err == nil || !strings.Contains(err.Error(), tt.wantErr) {
+					t.Fatalf("Parse() error = %v, want it to contain %q", err, tt.wantErr)
+				}
+				return
+			}
+			if err != nil {
+				t.Fatalf("Parse() error = %v", err)
+			}
+			if want := tt.want(Dark()); got != want {
+				t.Errorf("Parse() = %+v, want %+v", got, want)
+			}
+		})
+	}
+}
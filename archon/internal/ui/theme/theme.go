@@ -0,0 +1,83 @@
+package theme
+
+import (
+	"strings"
+
+	"github.com/charmbracelet/lipgloss"
+)
+
+// Theme is the set of colors the TUI is drawn with
+// An empty color leaves the terminal's own color in place
+type Theme struct {
+	Primary    lipgloss.Color // Titles, focused fields and highlights
+	Accent     lipgloss.Color // Buttons, active tabs and gauges
+	Success    lipgloss.Color
+	Warning    lipgloss.Color
+	Error      lipgloss.Color
+	Muted      lipgloss.Color // Borders and inactive tabs
+	Text       lipgloss.Color // Text drawn on an accent background (e.g., primary buttons)
+	Background lipgloss.Color // Header bar background
+	Selected   lipgloss.Color // Selected table rows
+}
+
+// Dark is the default theme, suited to dark terminal backgrounds
+func Dark() Theme {
+	return Theme{
+		Primary: lipgloss.Color("#7C3AED"), // Purple
+		Accent:  lipgloss.Color("36"),      // Teal
+		Success: lipgloss.Color("#10B981"), // Green
+		Warning: lipgloss.Color("#F59E0B"), // Yellow
+		Error:   lipgloss.Color("#EF4444"), // Red
+		Muted:   lipgloss.Color("240"),     // Gray
+		Text:    lipgloss.Color("15"),      // White
+	}
+}
+
+// Light uses darker colors that stay readable on light terminal backgrounds
+func Light() Theme {
+	return Theme{
+		Primary:    lipgloss.Color("#6D28D9"),
+		Accent:     lipgloss.Color("#0E7490"),
+		Success:    lipgloss.Color("#047857"),
+		Warning:    lipgloss.Color("#B45309"),
+		Error:      lipgloss.Color("#B91C1C"),
+		Muted:      lipgloss.Color("#9CA3AF"),
+		Text:       lipgloss.Color("#FFFFFF"),
+		Background: lipgloss.Color("#E5E7EB"),
+		Selected:   lipgloss.Color("#1D4ED8"),
+	}
+}
+
+// Solarized uses the Solarized palette
+func Solarized() Theme {
+	return Theme{
+		Primary:    lipgloss.Color("#6C71C4"), // Violet
+		Accent:     lipgloss.Color("#2AA198"), // Cyan
+		Success:    lipgloss.Color("#859900"), // Green
+		Warning:    lipgloss.Color("#B58900"), // Yellow
+		Error:      lipgloss.Color("#DC322F"), // Red
+		Muted:      lipgloss.Color("#586E75"), // Base01
+		Text:       lipgloss.Color("#FDF6E3"), // Base3
+		Background: lipgloss.Color("#073642"), // Base02
+		Selected:   lipgloss.Color("#268BD2"), // Blue
+	}
+}
+
+// Default returns the theme used when none is configured
+func Default() Theme {
+	return Dark()
+}
+
+// ByName returns the preset theme with the given name (case-insensitive)
+// "default" and an empty name are the dark theme. Unknown names return the default and false.
+func ByName(name string) (Theme, bool) {
+	switch strings.ToLower(strings.TrimSpace(name)) {
+	case "", "default", "dark":
+		return Dark(), true
+	case "light":
+		return Light(), true
+	case "solarized":
+		return Solarized(), true
+	}
+	return Default(), false
+}
@@ -0,0 +1,27 @@
+package theme
+
+import "testing"
+
+func TestByName(t *testing.T) {
+	tests := []struct {
+		name   string
+		want   Theme
+		wantOK bool
+	}{
+		{name: "", want: Dark(), wantOK: true},
+		{name: "default", want: Dark(), wantOK: true},
+		{name: "dark", want: Dark(), wantOK: true},
+		{name: " Light ", want: Light(), wantOK: true},
+		{name: "SOLARIZED", want: Solarized(), wantOK: true},
+		{name: "neon", want: Default(), wantOK: false},
+	}
+
+	for _, tt := range tests {
+		t.Run(tt.name, func(t *testing.T) {
+			got, ok := ByName(tt.name)
+			if got != tt.want || ok != tt.wantOK {
+				t.Errorf("ByName(%q) = %+v, %v; want %+v, %v", tt.name, got, ok, tt.want, tt.wantOK)
+			}
+		})
+	}
+}
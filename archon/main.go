@@ -1,34 +1,95 @@
 package main
 
 import (
+	"errors"
+	"flag"
 	"fmt"
 	"os"
 
 	"github.com/BlueBeard63/archon/internal/app"
 	"github.com/BlueBeard63/archon/internal/config"
 	tea "github.com/charmbracelet/bubbletea"
+	"github.com/charmbracelet/x/term"
 )
 
+// passphraseEnv lets scripts supply the config passphrase without a prompt
+const passphraseEnv = "ARCHON_PASSPHRASE"
+
+// maxPassphraseAttempts is how many times a wrong passphrase may be entered before exiting
+const maxPassphraseAttempts = 3
+
 func main() {
-	// Get config path
-	configPath, err := config.DefaultConfigPath()
-	if err != nil {
-		fmt.Fprintf(os.Stderr, "Error finding config path: %v\n", err)
+	configFlag := flag.String("config", "", "path to the config file")
+	demoFlag := flag.Bool("demo", false, "explore Archon with sample nodes, domains and sites that are simulated in memory")
+	flag.Usage = func() { fmt.Fprintln(os.Stderr, app.CommandUsage) }
+	flag.Parse()
+	args := flag.Args()
+
+	if len(args) > 0 && args[0] == "help" {
+		fmt.Println(app.CommandUsage)
+		return
+	}
+
+	// Demo mode uses its own sample config, leaving the real one alone
+	if *demoFlag {
+		if len(args) > 0 {
+			fmt.Fprintln(os.Stderr, "--demo only starts the terminal UI")
+			os.Exit(2)
+		}
+		model, err := app.NewDemoModel()
+		if err != nil {
+			fmt.Fprintf(os.Stderr, "Error initializing demo: %v\n", err)
+			os.Exit(1)
+		}
+		runProgram(model)
+		return
+	}
+
+	// Get config path, preferring --config over the platform default
+	configPath := *configFlag
+	if configPath == "" {
+		var err error
+		configPath, err = config.DefaultConfigPath()
+		if err != nil {
+			fmt.Fprintf(os.Stderr, "Error finding config path: %v\n", err)
+			os.Exit(1)
+		}
+	}
+
+	// Fail now rather than losing changes when they are saved
+	if err := config.CheckWritable(configPath); err != nil {
+		fmt.Fprintf(os.Stderr, "Error: %v\n", err)
 		os.Exit(1)
 	}
 
-	// Create app model
-	model, err := app.NewModel(configPath)
+	// Config backups are managed without loading the config, which may be the broken part
+	if len(args) > 0 && args[0] == "config" {
+		os.Exit(exitCode(app.RunConfigCommand(configPath, args[1:], os.Stdout)))
+	}
+
+	// Create app model, asking for the passphrase if secrets are encrypted
+	model, err := newModel(configPath)
 	if err != nil {
 		fmt.Fprintf(os.Stderr, "Error initializing app: %v\n", err)
 		os.Exit(1)
 	}
 
+	// Run a headless subcommand instead of the TUI when one is given
+	if len(args) > 0 {
+		os.Exit(runCommand(model, args))
+	}
+
+	runProgram(model)
+}
+
+// runProgram runs the TUI until the user quits
+func runProgram(model *app.Model) {
 	// Run Bubbletea program with mouse support
 	p := tea.NewProgram(
 		model,
 		tea.WithAltScreen(),       // Use alternate screen buffer
 		tea.WithMouseCellMotion(), // Enable mouse support
+		tea.WithReportFocus(),     // Skip desktop notifications while Archon's terminal has focus
 	)
 
 	if _, err := p.Run(); err != nil {
@@ -36,3 +97,98 @@ func main() {
 		os.Exit(1)
 	}
 }
+
+// runCommand runs a headless subcommand and returns the process exit code
+func runCommand(model *app.Model, args []string) int {
+	return exitCode(model.RunCommand(args, os.Stdout))
+}
+
+// exitCode reports a subcommand's error and returns the process exit code
+// (0 on success, 1 if the operation failed, 2 for a malformed command line)
+func exitCode(err error) int {
+	switch {
+	case err == nil:
+		return 0
+	case errors.Is(err, app.ErrUsage):
+		fmt.Fprintf(os.Stderr, "Error: %v\n\n%s\n", err, app.CommandUsage)
+		return 2
+	default:
+		fmt.Fprintf(os.Stderr, "Error: %v\n", err)
+		return 1
+	}
+}
+
+// newModel creates the app model, resolving the passphrase used to encrypt secrets in the config
+func newModel(configPath string) (*app.Model, error) {
+	if passphrase, ok := os.LookupEnv(passphraseEnv); ok {
+		return app.NewModel(configPath, passphrase)
+	}
+
+	needed, firstRun, err := config.NeedsPassphrase(configPath)
+	if err != nil {
+		return nil, err
+	}
+
+	// First run: offer to encrypt secrets (an empty passphrase keeps them in plaintext)
+	if firstRun {
+		if !term.IsTerminal(os.Stdin.Fd()) {
+			return app.NewModel(configPath, "")
+		}
+		passphrase, err := promptNewPassphrase()
+		if err != nil {
+			return nil, err
+		}
+		return app.NewModel(configPath, passphrase)
+	}
+
+	if !needed {
+		return app.NewModel(configPath, "")
+	}
+
+	for attempt := 1; ; attempt++ {
+		passphrase, err := promptPassphrase("Config passphrase: ")
+		if err != nil {
+			return nil, err
+		}
+		model, err := app.NewModel(configPath, passphrase)
+		if errors.Is(err, config.ErrWrongPassphrase) && attempt < maxPassphraseAttempts {
+			fmt.Fprintln(os.Stderr, "Incorrect passphrase, try again.")
+			continue
+		}
+		return model, err
+	}
+}
+
+// promptNewPassphrase asks for an optional passphrase on first run and confirms it
+func promptNewPassphrase() (string, error) {
+	fmt.Fprintln(os.Stderr, "Archon can encrypt API keys and tokens stored in its config.")
+	for {
+		passphrase, err := promptPassphrase("New passphrase (leave empty to store secrets in plaintext): ")
+		if err != nil || passphrase == "" {
+			return passphrase, err
+		}
+		confirm, err := promptPassphrase("Confirm passphrase: ")
+		if err != nil {
+			return "", err
+		}
+		if passphrase == confirm {
+			return passphrase, nil
+		}
+		fmt.Fprintln(os.Stderr, "Passphrases do not match, try again.")
+	}
+}
+
+// promptPassphrase reads a passphrase from the terminal without echoing it
+func promptPassphrase(prompt string) (string, error) {
+	if !term.IsTerminal(os.Stdin.Fd()) {
+		return "", fmt.Errorf("config is encrypted; set %s or run in a terminal", passphraseEnv)
+	}
+	// Prompts go to stderr so they never mix with --json output on stdout
+	fmt.Fprint(os.Stderr, prompt)
+	passphrase, err := term.ReadPassword(os.Stdin.Fd())
+	fmt.Fprintln(os.Stderr)
+	if err != nil {
+		return "", err
+	}
+	return string(passphrase), nil
+}
@@ -4,6 +4,7 @@ import (
 	"encoding/json"
 	"log"
 	"net/http"
+	"time"
 
 	"github.com/go-chi/chi/v5"
 	"github.com/google/uuid"
@@ -259,7 +260,7 @@ func (h *Handlers) HandleDeleteSite(w http.ResponseWriter, r *http.Request) {
 	respondJSON(w, http.StatusOK, map[string]string{"message": "Site deleted successfully"})
 }
 
-// HandleGetLogs retrieves container logs
+// HandleGetLogs retrieves container logs, or build logs when ?source=build is set
 func (h *Handlers) HandleGetLogs(w http.ResponseWriter, r *http.Request) {
 	ctx := r.Context()
 
@@ -271,16 +272,57 @@ func (h *Handlers) HandleGetLogs(w http.ResponseWriter, r *http.Request) {
 		return
 	}
 
-	// Get logs
-	logs, err := h.dockerClient.GetContainerLogs(ctx, siteID, 100)
+	// Only return container lines logged after the cursor from an earlier request
+	var since time.Time
+	if s := r.URL.Query().Get("since"); s != "" {
+		since, err = time.Parse(time.RFC3339Nano, s)
+		if err != nil {
+			respondError(w, http.StatusBadRequest, "Invalid since cursor")
+			return
+		}
+	}
+
+	// Get logs, either from the running container or the last image build
+	var logs []string
+	var cursor time.Time
+	if r.URL.Query().Get("source") == "build" {
+		logs, err = docker.ReadBuildLogs(h.dataDir, siteID, 100)
+	} else {
+		logs, cursor, err = h.dockerClient.GetContainerLogs(ctx, siteID, 100, since)
+	}
 	if err != nil {
 		respondError(w, http.StatusInternalServerError, "Failed to get logs: "+err.Error())
 		return
 	}
 
-	respondJSON(w, http.StatusOK, map[string]interface{}{
+	response := map[string]interface{}{
 		"logs": logs,
-	})
+	}
+	if !cursor.IsZero() {
+		response["cursor"] = cursor.Format(time.RFC3339Nano)
+	}
+	respondJSON(w, http.StatusOK, response)
+}
+
+// HandleGetMetrics returns the current resource usage of a site's container
+func (h *Handlers) HandleGetMetrics(w http.ResponseWriter, r *http.Request) {
+	ctx := r.Context()
+
+	// Get site ID from URL
+	siteIDStr := chi.URLParam(r, "siteID")
+	siteID, err := uuid.Parse(siteIDStr)
+	if err != nil {
+		respondError(w, http.StatusBadRequest, "Invalid site ID")
+		return
+	}
+
+	metrics, err := h.dockerClient.GetContainerMetrics(ctx, siteID)
+	if err != nil {
+		respondError(w, http.StatusInternalServerError, "Failed to get metrics: "+err.Error())
+		return
+	}
+
+	respondJSON(w, http.StatusOK, metrics)
 }
 
 // getDomainMappingsForHandler extracts domain-port mappings from a DeployRequest
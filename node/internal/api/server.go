@@ -73,6 +73,7 @@ func NewServer(cfg *config.Config) (*Server, error) {
 		r.Post("/api/v1/sites/{siteID}/restart", handlers.HandleRestartSite)
 		r.Delete("/api/v1/sites/{siteID}", handlers.HandleDeleteSite)
 		r.Get("/api/v1/sites/{siteID}/logs", handlers.HandleGetLogs)
+		r.Get("/api/v1/sites/{siteID}/metrics", handlers.HandleGetMetrics)
 	})
 
 	// Create HTTP server
@@ -13,6 +13,7 @@ import (
 	"github.com/gorilla/websocket"
 
 	"github.com/BlueBeard63/archon-node/internal/models"
+	"github.com/BlueBeard63/archon-node/internal/pipeline/stages"
 	"github.com/BlueBeard63/archon-node/internal/ssl"
 )
 
@@ -86,8 +87,17 @@ func (h *Handlers) HandleDeploySiteWebSocket(w http.ResponseWriter, r *http.Requ
 	log.Printf("========================================")
 
 	// Validate request
-	if req.Name == "" || len(req.DomainMappings) == 0 || req.Docker.Image == "" {
-		sendError(conn, "Missing required fields")
+	if err := req.Validate(); err != nil {
+		sendError(conn, err.Error())
+		return
+	}
+	// This handler only runs single-container deploys
+	if req.IsCompose() {
+		sendError(conn, "compose deployments are not supported over WebSocket")
+		return
+	}
+	if err := stages.ValidateStrategy(&req, h.proxyManager); err != nil {
+		sendError(conn, err.Error())
 		return
 	}
 
@@ -169,8 +179,15 @@ func (h *Handlers) HandleDeploySiteWebSocket(w http.ResponseWriter, r *http.Requ
 	}
 
 	// Deploy container
-	sendProgress(conn, "Deploying Docker container: "+req.Docker.Image, "docker")
-	deployResp, err := h.dockerClient.DeploySite(ctx, &req, h.dataDir)
+	sendProgress(conn, "Deploying Docker container: "+req.Docker.Source(), "docker")
+	var deployResp *models.DeployResponse
+	if req.IsBlueGreen() {
+		deployResp, err = h.dockerClient.DeploySiteBlueGreen(ctx, &req, h.dataDir, func(message string) {
+			sendProgress(conn, message, "blue_green")
+		})
+	} else {
+		deployResp, err = h.dockerClient.DeploySite(ctx, &req, h.dataDir)
+	}
 	if err != nil {
 		sendError(conn, "Failed to deploy site: "+err.Error())
 		return
@@ -0,0 +1,262 @@
+package docker
+
+import (
+	"context"
+	"fmt"
+	"net/http"
+	"strings"
+	"time"
+
+	"github.com/docker/docker/api/types"
+
+	"github.com/BlueBeard63/archon-node/internal/models"
+)
+
+// Blue/green deploys keep the site's running container serving until the new one is healthy.
+// Labels can't change on a running container, so moving traffic takes two new containers:
+//
+//  1. A candidate, routed only for an internal preview hostname, is started from the new image
+//     and probed until it is healthy. If it never is, it is removed and the old container stays.
+//  2. The new live container is started with the site's Host() rules under routers of its own,
+//     then the old container and the candidate are removed.
+//
+// Live containers alternate between the blue and green slots, named archon-<name>-<slot>.
+// None of them publish host ports, so blue/green deploys need Traefik as the node's proxy.
+
+const (
+	blueGreenHealthTimeout = 2 * time.Minute // How long a new container has to become healthy
+	blueGreenSettleTime    = 5 * time.Second // How long a container without a health path must stay up
+	blueGreenPollInterval  = 2 * time.Second
+)
+
+// healthClient probes new containers; a redirect is the answer, as it is for Traefik's health checks
+var healthClient = &http.Client{
+	CheckRedirect: func(*http.Request, []*http.Request) error { return http.ErrUseLastResponse },
+}
+
+// DeploySiteBlueGreen deploys a site beside its running container and moves traffic to the new
+// one once it is healthy. progress is called as each phase starts.
+func (c *Client) DeploySiteBlueGreen(ctx context.Context, req *models.DeployRequest, dataDir string, progress func(message string)) (*models.DeployResponse, error) {
+	if err := c.EnsureNetwork(ctx); err != nil {
+		return nil, err
+	}
+
+	imageRef, err := c.prepareImage(ctx, req, dataDir)
+	if err != nil {
+		return failedDeploy(req, err), nil
+	}
+
+	existing, err := c.siteContainers(ctx, req.ID)
+	if err != nil {
+		return failedDeploy(req, err), nil
+	}
+	slot := nextSlot(req.Name, existing)
+	liveName := fmt.Sprintf("archon-%s-%s", req.Name, slot)
+	candidateName := liveName + "-candidate"
+
+	// Leftovers of an interrupted deploy into this slot would clash with the new containers
+	var previous []types.Container
+	for _, cont := range existing {
+		if name := containerName(cont); name == liveName || name == candidateName {
+			c.removeContainer(ctx, cont.ID)
+			continue
+		}
+		previous = append(previous, cont)
+	}
+
+	// Phase 1: start the candidate where only the preview hostname reaches it
+	previewHost := fmt.Sprintf("%s-%s.archon.internal", req.Name, slot)
+	progress(fmt.Sprintf("Starting the %s container beside the running one (preview at %s)", slot, previewHost))
+	candidateSpec, err := c.newContainerSpec(req, imageRef, dataDir, slotLabels(req.TraefikLabels, slot+"-candidate", previewHost), false)
+	if err != nil {
+		return failedDeploy(req, err), nil
+	}
+	candidateID, err := c.startContainer(ctx, candidateSpec, candidateName)
+	if err != nil {
+		c.removeContainer(ctx, candidateID)
+		return failedDeploy(req, err), nil
+	}
+
+	// Phase 2: wait for it to be healthy
+	progress(fmt.Sprintf("Waiting for the %s container to become healthy", slot))
+	if err := c.waitHealthy(ctx, candidateID, req); err != nil {
+		c.removeContainer(ctx, candidateID)
+		return failedDeploy(req, fmt.Errorf("the %s container never became healthy, so the running one was kept: %v", slot, err)), nil
+	}
+
+	// Phase 3: start the live container with the site's Host() rules
+	progress(fmt.Sprintf("Switching traffic to the %s container", slot))
+	liveSpec, err := c.newContainerSpec(req, imageRef, dataDir, slotLabels(req.TraefikLabels, slot, ""), false)
+	if err != nil {
+		c.removeContainer(ctx, candidateID)
+		return failedDeploy(req, err), nil
+	}
+	liveID, err := c.startContainer(ctx, liveSpec, liveName)
+	if err == nil {
+		err = c.waitHealthy(ctx, liveID, req)
+	}
+	if err != nil {
+		c.removeContainer(ctx, liveID)
+		c.removeContainer(ctx, candidateID)
+		return failedDeploy(req, fmt.Errorf("failed to switch to the %s container, so the running one was kept: %v", slot, err)), nil
+	}
+
+	// Phase 4: tear down the old container and the candidate
+	progress("Removing the previous container")
+	for _, cont := range previous {
+		c.removeContainer(ctx, cont.ID)
+	}
+	c.removeContainer(ctx, candidateID)
+
+	return &models.DeployResponse{
+		SiteID:      req.ID,
+		Status:      models.SiteStatusRunning,
+		ContainerID: liveID,
+		Message:     fmt.Sprintf("Site deployed to the %s slot", slot),
+	}, nil
+}
+
+// nextSlot returns the slot the next live container goes in: green if blue is taken, else blue
+func nextSlot(siteName string, containers []types.Container) string {
+	for _, cont := range containers {
+		if containerName(cont) == fmt.Sprintf("archon-%s-blue", siteName) {
+			return "green"
+		}
+	}
+	return "blue"
+}
+
+// slotLabels renames the routers, services and middlewares defined in labels with suffix, so a
+// container's routes don't merge with those of the site's other containers. With a preview host,
+// routers match only that hostname and TLS is left off, as no certificate can be issued for it.
+func slotLabels(labels map[string]string, suffix, previewHost string) map[string]string {
+	// Names defined by the labels, by kind ("routers", "services" or "middlewares")
+	defined := make(map[string]map[string]bool)
+	for key := range labels {
+		if kind, name, _, ok := splitTraefikKey(key); ok {
+			if defined[kind] == nil {
+				defined[kind] = make(map[string]bool)
+			}
+			defined[kind][name] = true
+		}
+	}
+	rename := func(kind, name string) string {
+		if defined[kind][name] {
+			return name + "-" + suffix
+		}
+		return name
+	}
+
+	renamed := make(map[string]string, len(labels))
+	for key, value := range labels {
+		kind, name, option, ok := splitTraefikKey(key)
+		if !ok {
+			renamed[key] = value
+			continue
+		}
+		if kind == "routers" {
+			switch {
+			case previewHost != "" && strings.HasPrefix(option, "tls"):
+				continue
+			case previewHost != "" && option == "rule":
+				value = fmt.Sprintf("Host(`%s`)", previewHost)
+			case option == "service":
+				value = rename("services", value)
+			case option == "middlewares":
+				middlewares := strings.Split(value, ",")
+				for i, middleware := range middlewares {
+					middlewares[i] = rename("middlewares", strings.TrimSpace(middleware))
+				}
+				value = strings.Join(middlewares, ",")
+			}
+		}
+		renamed[fmt.Sprintf("traefik.http.%s.%s.%s", kind, rename(kind, name), option)] = value
+	}
+	return renamed
+}
+
+// splitTraefikKey splits a label like traefik.http.routers.<name>.<option> into its parts
+func splitTraefikKey(key string) (kind, name, option string, ok bool) {
+	parts := strings.SplitN(strings.TrimPrefix(key, "traefik.http."), ".", 3)
+	if !strings.HasPrefix(key, "traefik.http.") || len(parts) < 3 {
+		return "", "", "", false
+	}
+	switch parts[0] {
+	case "routers", "services", "middlewares":
+		return parts[0], parts[1], parts[2], true
+	}
+	return "", "", "", false
+}
+
+// waitHealthy waits for a new container to pass checkHealthy, giving up after blueGreenHealthTimeout
+func (c *Client) waitHealthy(ctx context.Context, containerID string, req *models.DeployRequest) error {
+	deadline := time.Now().Add(blueGreenHealthTimeout)
+	for {
+		err := c.checkHealthy(ctx, containerID, req)
+		if err == nil {
+			return nil
+		}
+		if time.Now().After(deadline) {
+			return err
+		}
+
+		select {
+		case <-ctx.Done():
+			return ctx.Err()
+		case <-time.After(blueGreenPollInterval):
+		}
+	}
+}
+
+// checkHealthy checks a container is running and, when the site has a health path, that it answers
+// it on the Docker network with the expected status. Without a health path, the container must
+// have stayed up for blueGreenSettleTime.
+func (c *Client) checkHealthy(ctx context.Context, containerID string, req *models.DeployRequest) error {
+	info, err := c.cli.ContainerInspect(ctx, containerID)
+	if err != nil {
+		return fmt.Errorf("failed to inspect container: %w", err)
+	}
+	if info.State == nil || !info.State.Running {
+		return fmt.Errorf("container is not running")
+	}
+
+	if req.HealthPath == "" {
+		startedAt, err := time.Parse(time.RFC3339Nano, info.State.StartedAt)
+		if err != nil || time.Since(startedAt) < blueGreenSettleTime {
+			return fmt.Errorf("container has not been up for %s yet", blueGreenSettleTime)
+		}
+		return nil
+	}
+
+	var address string
+	if info.NetworkSettings != nil {
+		if endpoint, ok := info.NetworkSettings.Networks[c.networkName]; ok && endpoint != nil {
+			address = endpoint.IPAddress
+		}
+	}
+	if address == "" {
+		return fmt.Errorf("container has no address on network %s", c.networkName)
+	}
+	url := fmt.Sprintf("http://%s:%d%s", address, req.DomainMappings[0].Port, req.HealthPath)
+
+	probeCtx, cancel := context.WithTimeout(ctx, 5*time.Second)
+	defer cancel()
+	httpReq, err := http.NewRequestWithContext(probeCtx, http.MethodGet, url, nil)
+	if err != nil {
+		return err
+	}
+	resp, err := healthClient.Do(httpReq)
+	if err != nil {
+		return fmt.Errorf("health check failed: %w", err)
+	}
+	resp.Body.Close()
+
+	expected := req.HealthStatus
+	if expected == 0 {
+		expected = http.StatusOK
+	}
+	if resp.StatusCode != expected {
+		return fmt.Errorf("health check returned %d, want %d", resp.StatusCode, expected)
+	}
+	return nil
+}
@@ -0,0 +1,63 @@
+package docker
+
+import "testing"
+
+func TestSlotLabels(t *testing.T) {
+	labels := map[string]string{
+		"traefik.enable":                                             "true",
+		"traefik.http.routers.site.rule":                             "Host(`example.com`)",
+		"traefik.http.routers.site.service":                          "site",
+		"traefik.http.routers.site.middlewares":                      "site-headers-0,auth@file",
+		"traefik.http.routers.site.tls.certresolver":                 "letsencrypt",
+		"traefik.http.services.site.loadbalancer.server.port":        "8080",
+		"traefik.http.middlewares.site-headers-0.headers.stsSeconds": "300",
+	}
+
+	tests := []struct {
+		name        string
+		suffix      string
+		previewHost string
+		want        map[string]string
+	}{
+		{
+			name:   "live container",
+			suffix: "green",
+			want: map[string]string{
+				"traefik.enable":                                                   "true",
+				"traefik.http.routers.site-green.rule":                             "Host(`example.com`)",
+				"traefik.http.routers.site-green.service":                          "site-green",
+				"traefik.http.routers.site-green.middlewares":                      "site-headers-0-green,auth@file",
+				"traefik.http.routers.site-green.tls.certresolver":                 "letsencrypt",
+				"traefik.http.services.site-green.loadbalancer.server.port":        "8080",
+				"traefik.http.middlewares.site-headers-0-green.headers.stsSeconds": "300",
+			},
+		},
+		{
+			name:        "candidate container",
+			suffix:      "blue-candidate",
+			previewHost: "site-blue.archon.internal",
+			want: map[string]string{
+				"traefik.enable":                                                            "true",
+				"traefik.http.routers.site-blue-candidate.rule":                             "Host(`site-blue.archon.internal`)",
+				"traefik.http.routers.site-blue-candidate.service":                          "site-blue-candidate",
+				"traefik.http.routers.site-blue-candidate.middlewares":                      "site-headers-0-blue-candidate,auth@file",
+				"traefik.http.services.site-blue-candidate.loadbalancer.server.port":        "8080",
+				"traefik.http.middlewares.site-headers-0-blue-candidate.headers.stsSeconds": "300",
+			},
+		},
+	}
+
+	for _, tt := range tests {
+		t.Run(tt.name, func(t *testing.T) {
+			got := slotLabels(labels, tt.suffix, tt.previewHost)
+			if len(got) != len(tt.want) {
+				t.Errorf("slotLabels() returned %d labels, want %d: %v", len(got), len(tt.want), got)
+			}
+			for key, want := range tt.want {
+				if got[key] != want {
+					t.Errorf("label %s = %q, want %q", key, got[key], want)
+				}
+			}
+		})
+	}
+}
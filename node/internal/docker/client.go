@@ -1,22 +1,25 @@
 package docker
 
 import (
+	"archive/tar"
+	"bytes"
 	"context"
-	"encoding/base64"
 	"encoding/json"
 	"fmt"
 	"io"
-	"log"
 	"os"
 	"path/filepath"
 	"strings"
+	"time"
 
 	"github.com/docker/docker/api/types"
 	"github.com/docker/docker/api/types/container"
 	"github.com/docker/docker/api/types/filters"
 	"github.com/docker/docker/api/types/image"
 	"github.com/docker/docker/api/types/network"
+	"github.com/docker/docker/api/types/registry"
 	"github.com/docker/docker/client"
+	"github.com/docker/docker/pkg/stdcopy"
 	"github.com/docker/go-connections/nat"
 	"github.com/google/uuid"
 
@@ -81,33 +84,95 @@ func (c *Client) DeploySite(ctx context.Context, req *models.DeployRequest, data
 		return nil, err
 	}
 
-	encodedJSON, err := json.Marshal(req.Docker.Credentials)
+	imageRef, err := c.prepareImage(ctx, req, dataDir)
 	if err != nil {
-		log.Fatal(err)
+		return failedDeploy(req, err), nil
 	}
 
-	// Get authentication for pulling image
-	authStr := base64.StdEncoding.EncodeToString(encodedJSON)
+	// Prepare container name
+	containerName := fmt.Sprintf("archon-%s", req.Name)
+
+	spec, err := c.newContainerSpec(req, imageRef, dataDir, req.TraefikLabels, true)
+	if err != nil {
+		return failedDeploy(req, err), nil
+	}
+
+	// Stop and remove the existing container, and any left by earlier blue/green deploys
+	containers, err := c.siteContainers(ctx, req.ID)
+	if err != nil {
+		return failedDeploy(req, err), nil
+	}
+	for _, cont := range containers {
+		c.removeContainer(ctx, cont.ID)
+	}
+	if err := c.removeContainerNamed(ctx, containerName); err != nil {
+		return failedDeploy(req, err), nil
+	}
+
+	containerID, err := c.startContainer(ctx, spec, containerName)
+	if err != nil {
+		return failedDeploy(req, err), nil
+	}
+
+	return &models.DeployResponse{
+		SiteID:      req.ID,
+		Status:      models.SiteStatusRunning,
+		ContainerID: containerID,
+		Message:     "Site deployed successfully",
+	}, nil
+}
+
+// failedDeploy returns the response for a deploy that failed with err
+func failedDeploy(req *models.DeployRequest, err error) *models.DeployResponse {
+	return &models.DeployResponse{
+		SiteID:  req.ID,
+		Status:  models.SiteStatusFailed,
+		Message: err.Error(),
+	}
+}
+
+// prepareImage builds or pulls the image for req and returns the reference to run
+func (c *Client) prepareImage(ctx context.Context, req *models.DeployRequest, dataDir string) (string, error) {
+	if req.Docker.Build != nil && req.Docker.Build.Context != "" {
+		// Build image from source
+		builtImage, err := c.BuildImage(ctx, req, dataDir)
+		if err != nil {
+			return "", fmt.Errorf("Failed to build image: %v", err)
+		}
+		return builtImage, nil
+	}
+
+	// Get authentication for pulling image (logs in to the site's registry when credentials are set)
+	authStr, err := registry.EncodeAuthConfig(registryAuth(req.Docker.Credentials))
+	if err != nil {
+		return "", fmt.Errorf("Failed to encode registry credentials: %v", err)
+	}
 
 	// Pull image
 	reader, err := c.cli.ImagePull(ctx, req.Docker.Image, image.PullOptions{
 		RegistryAuth: authStr,
 	})
 	if err != nil {
-		return &models.DeployResponse{
-			SiteID:  req.ID,
-			Status:  models.SiteStatusFailed,
-			Message: fmt.Sprintf("Failed to pull image: %v", err),
-		}, nil
+		return "", fmt.Errorf("Failed to pull image: %v", err)
 	}
 	defer reader.Close()
 
 	// Consume pull output
 	io.Copy(io.Discard, reader)
+	return req.Docker.Image, nil
+}
 
-	// Prepare container name
-	containerName := fmt.Sprintf("archon-%s", req.Name)
+// containerSpec is everything needed to create a site's container
+type containerSpec struct {
+	config     *container.Config
+	host       *container.HostConfig
+	networking *network.NetworkingConfig
+}
 
+// newContainerSpec prepares the container for req running imageRef, with traefikLabels as its
+// routes. Host ports are only published when publishPorts is set; Traefik reaches containers over
+// the Docker network either way. The site's config files are written to the node here.
+func (c *Client) newContainerSpec(req *models.DeployRequest, imageRef, dataDir string, traefikLabels map[string]string, publishPorts bool) (*containerSpec, error) {
 	// Prepare environment variables
 	var envVars []string
 	for k, v := range req.EnvironmentVars {
@@ -123,6 +188,9 @@ func (c *Client) DeploySite(ctx context.Context, req *models.DeployRequest, data
 	for _, mapping := range req.DomainMappings {
 		containerPort := nat.Port(fmt.Sprintf("%d/tcp", mapping.Port))
 		exposedPorts[containerPort] = struct{}{}
+		if !publishPorts {
+			continue
+		}
 
 		// Determine host port (use HostPort if specified, otherwise use Port)
 		hostPort := mapping.Port
@@ -146,13 +214,13 @@ func (c *Client) DeploySite(ctx context.Context, req *models.DeployRequest, data
 	}
 
 	// Add Traefik labels if provided
-	for k, v := range req.TraefikLabels {
+	for k, v := range traefikLabels {
 		labels[k] = v
 	}
 
 	// Create container config
 	containerConfig := &container.Config{
-		Image:        req.Docker.Image,
+		Image:        imageRef,
 		Env:          envVars,
 		ExposedPorts: exposedPorts,
 		Labels:       labels,
@@ -163,6 +231,7 @@ func (c *Client) DeploySite(ctx context.Context, req *models.DeployRequest, data
 		RestartPolicy: container.RestartPolicy{
 			Name: "unless-stopped",
 		},
+		Resources: containerResources(req),
 	}
 
 	networkingConfig := &network.NetworkingConfig{
@@ -175,76 +244,294 @@ func (c *Client) DeploySite(ctx context.Context, req *models.DeployRequest, data
 	if len(req.ConfigFiles) > 0 {
 		siteDataDir := filepath.Join(dataDir, "sites", req.ID.String())
 		if err := os.MkdirAll(siteDataDir, 0755); err != nil {
-			return &models.DeployResponse{
-				SiteID:  req.ID,
-				Status:  models.SiteStatusFailed,
-				Message: fmt.Sprintf("Failed to create site data directory: %v", err),
-			}, nil
+			return nil, fmt.Errorf("Failed to create site data directory: %v", err)
 		}
 
 		var binds []string
 		for _, cf := range req.ConfigFiles {
 			hostPath := filepath.Join(siteDataDir, cf.Name)
 			if err := os.WriteFile(hostPath, []byte(cf.Content), 0644); err != nil {
-				return &models.DeployResponse{
-					SiteID:  req.ID,
-					Status:  models.SiteStatusFailed,
-					Message: fmt.Sprintf("Failed to write config file %s: %v", cf.Name, err),
-				}, nil
+				return nil, fmt.Errorf("Failed to write config file %s: %v", cf.Name, err)
 			}
 			binds = append(binds, fmt.Sprintf("%s:%s:ro", hostPath, cf.ContainerPath))
 		}
 		hostConfig.Binds = binds
 	}
 
-	// Stop and remove existing container if it exists
+	// Mount persistent volumes
+	for _, v := range req.Volumes {
+		hostConfig.Binds = append(hostConfig.Binds, v.Bind())
+	}
+
+	return &containerSpec{config: containerConfig, host: hostConfig, networking: networkingConfig}, nil
+}
+
+// startContainer creates a container named name from spec and starts it, returning its ID
+func (c *Client) startContainer(ctx context.Context, spec *containerSpec, name string) (string, error) {
+	resp, err := c.cli.ContainerCreate(ctx, spec.config, spec.host, spec.networking, nil, name)
+	if err != nil {
+		return "", fmt.Errorf("Failed to create container: %v", err)
+	}
+
+	if err := c.cli.ContainerStart(ctx, resp.ID, container.StartOptions{}); err != nil {
+		return resp.ID, fmt.Errorf("Failed to start container: %v", err)
+	}
+	return resp.ID, nil
+}
+
+// siteContainers lists every container deployed for a site, running or not
+func (c *Client) siteContainers(ctx context.Context, siteID uuid.UUID) ([]types.Container, error) {
+	containers, err := c.cli.ContainerList(ctx, container.ListOptions{
+		All:     true,
+		Filters: filters.NewArgs(filters.Arg("label", "archon.site.id="+siteID.String())),
+	})
+	if err != nil {
+		return nil, fmt.Errorf("Failed to list containers: %v", err)
+	}
+	return containers, nil
+}
+
+// removeContainerNamed stops and removes the container with name, if there is one
+func (c *Client) removeContainerNamed(ctx context.Context, name string) error {
 	containers, err := c.cli.ContainerList(ctx, container.ListOptions{All: true})
 	if err != nil {
-		return &models.DeployResponse{
-			SiteID:  req.ID,
-			Status:  models.SiteStatusFailed,
-			Message: fmt.Sprintf("Failed to list containers: %v", err),
-		}, nil
+		return fmt.Errorf("Failed to list containers: %v", err)
 	}
 
 	for _, cont := range containers {
-		for _, name := range cont.Names {
-			if strings.TrimPrefix(name, "/") == containerName {
-				// Stop container
-				timeout := 10
-				c.cli.ContainerStop(ctx, cont.ID, container.StopOptions{Timeout: &timeout})
-				// Remove container
-				c.cli.ContainerRemove(ctx, cont.ID, container.RemoveOptions{Force: true})
-				break
+		if containerName(cont) == name {
+			c.removeContainer(ctx, cont.ID)
+			break
+		}
+	}
+	return nil
+}
+
+// removeContainer stops and removes a container, ignoring failures as the container may already be gone
+func (c *Client) removeContainer(ctx context.Context, containerID string) {
+	timeout := 10
+	c.cli.ContainerStop(ctx, containerID, container.StopOptions{Timeout: &timeout})
+	c.cli.ContainerRemove(ctx, containerID, container.RemoveOptions{Force: true})
+}
+
+// containerName returns a listed container's name without Docker's leading slash
+func containerName(cont types.Container) string {
+	if len(cont.Names) == 0 {
+		return ""
+	}
+	return strings.TrimPrefix(cont.Names[0], "/")
+}
+
+// registryAuth converts deploy credentials to the auth config sent with an image pull
+func registryAuth(creds models.DockerCredentials) registry.AuthConfig {
+	return registry.AuthConfig{
+		Username:      creds.Username,
+		Password:      creds.Password,
+		ServerAddress: creds.Registry,
+	}
+}
+
+// containerResources converts the request's CPU and memory limits to Docker resource constraints
+// Zero values leave the container unconstrained
+func containerResources(req *models.DeployRequest) container.Resources {
+	var resources container.Resources
+	if req.CPULimit > 0 {
+		resources.NanoCPUs = int64(req.CPULimit * 1e9)
+	}
+	if req.MemoryLimitMB > 0 {
+		resources.Memory = req.MemoryLimitMB * 1024 * 1024
+	}
+	return resources
+}
+
+// buildMessage is a single line of the JSON stream returned by the image build API
+type buildMessage struct {
+	Stream      string `json:"stream"`
+	Error       string `json:"error"`
+	ErrorDetail struct {
+		Message string `json:"message"`
+	} `json:"errorDetail"`
+}
+
+// BuildLogPath returns where the output of a site's last image build is stored
+func BuildLogPath(dataDir string, siteID uuid.UUID) string {
+	return filepath.Join(dataDir, "sites", siteID.String(), "build.log")
+}
+
+// imageTag returns the tag for a site's built image, archon-<name>:latest. The name is lowercased
+// and each run of characters a Docker repository name can't hold becomes a dash; a name with no
+// letters or digits left is replaced by the site ID.
+func imageTag(req *models.DeployRequest) string {
+	var slug strings.Builder
+	separator := "" // Characters since the last letter or digit
+	for _, r := range strings.ToLower(req.Name) {
+		if (r < 'a' || r > 'z') && (r < '0' || r > '9') {
+			separator += string(r)
+			continue
+		}
+		if separator != "" && slug.Len() > 0 {
+			// A single dot or underscore is allowed between letters and digits
+			if separator != "." && separator != "_" {
+				separator = "-"
 			}
+			slug.WriteString(separator)
 		}
+		separator = ""
+		slug.WriteRune(r)
+	}
+
+	name := slug.String()
+	if name == "" {
+		name = req.ID.String()
 	}
+	return fmt.Sprintf("archon-%s:latest", name)
+}
+
+// BuildImage builds the image described by req.Docker.Build and returns its tag
+// Build output is written to the site's build log so it can be viewed later
+func (c *Client) BuildImage(ctx context.Context, req *models.DeployRequest, dataDir string) (string, error) {
+	spec := req.Docker.Build
+	tag := imageTag(req)
 
-	// Create container
-	resp, err := c.cli.ContainerCreate(ctx, containerConfig, hostConfig, networkingConfig, nil, containerName)
+	dockerfile := spec.Dockerfile
+	if dockerfile == "" {
+		dockerfile = "Dockerfile"
+	}
+
+	options := types.ImageBuildOptions{
+		Tags:        []string{tag},
+		Dockerfile:  dockerfile,
+		Remove:      true,
+		ForceRemove: true,
+	}
+
+	// Remote contexts are fetched by the daemon, local ones are sent as a tarball
+	var buildContext io.Reader
+	if spec.IsRemote() {
+		options.RemoteContext = spec.Context
+	} else {
+		tarball, err := tarDirectory(spec.Context)
+		if err != nil {
+			return "", fmt.Errorf("failed to read build context: %w", err)
+		}
+		buildContext = tarball
+	}
+
+	// Open the build log, replacing output from any previous build
+	logPath := BuildLogPath(dataDir, req.ID)
+	if err := os.MkdirAll(filepath.Dir(logPath), 0755); err != nil {
+		return "", fmt.Errorf("failed to create site data directory: %w", err)
+	}
+	logFile, err := os.Create(logPath)
 	if err != nil {
-		return &models.DeployResponse{
-			SiteID:  req.ID,
-			Status:  models.SiteStatusFailed,
-			Message: fmt.Sprintf("Failed to create container: %v", err),
-		}, nil
+		return "", fmt.Errorf("failed to create build log: %w", err)
 	}
+	defer logFile.Close()
 
-	// Start container
-	if err := c.cli.ContainerStart(ctx, resp.ID, container.StartOptions{}); err != nil {
-		return &models.DeployResponse{
-			SiteID:  req.ID,
-			Status:  models.SiteStatusFailed,
-			Message: fmt.Sprintf("Failed to start container: %v", err),
-		}, nil
+	resp, err := c.cli.ImageBuild(ctx, buildContext, options)
+	if err != nil {
+		fmt.Fprintf(logFile, "build failed: %v\n", err)
+		return "", err
+	}
+	defer resp.Body.Close()
+
+	// Stream build output into the log, stopping at the first error
+	decoder := json.NewDecoder(resp.Body)
+	for {
+		var msg buildMessage
+		if err := decoder.Decode(&msg); err == io.EOF {
+			break
+		} else if err != nil {
+			return "", fmt.Errorf("failed to read build output: %w", err)
+		}
+
+		if msg.Stream != "" {
+			logFile.WriteString(msg.Stream)
+		}
+		if msg.Error != "" {
+			fmt.Fprintf(logFile, "ERROR: %s\n", msg.Error)
+			return "", fmt.Errorf("%s", msg.Error)
+		}
 	}
 
-	return &models.DeployResponse{
-		SiteID:      req.ID,
-		Status:      models.SiteStatusRunning,
-		ContainerID: resp.ID,
-		Message:     "Site deployed successfully",
-	}, nil
+	return tag, nil
+}
+
+// tarDirectory packs a local directory into an in-memory tar archive for use as a build context
+func tarDirectory(dir string) (io.Reader, error) {
+	info, err := os.Stat(dir)
+	if err != nil {
+		return nil, err
+	}
+	if !info.IsDir() {
+		return nil, fmt.Errorf("%s is not a directory", dir)
+	}
+
+	var buf bytes.Buffer
+	tw := tar.NewWriter(&buf)
+
+	err = filepath.Walk(dir, func(path string, fi os.FileInfo, err error) error {
+		if err != nil {
+			return err
+		}
+
+		rel, err := filepath.Rel(dir, path)
+		if err != nil || rel == "." {
+			return err
+		}
+
+		// Only regular files and directories are meaningful in a build context
+		if !fi.Mode().IsRegular() && !fi.IsDir() {
+			return nil
+		}
+
+		header, err := tar.FileInfoHeader(fi, "")
+		if err != nil {
+			return err
+		}
+		header.Name = filepath.ToSlash(rel)
+		if err := tw.WriteHeader(header); err != nil {
+			return err
+		}
+
+		if fi.IsDir() {
+			return nil
+		}
+
+		f, err := os.Open(path)
+		if err != nil {
+			return err
+		}
+		defer f.Close()
+
+		_, err = io.Copy(tw, f)
+		return err
+	})
+	if err != nil {
+		return nil, err
+	}
+
+	if err := tw.Close(); err != nil {
+		return nil, err
+	}
+	return &buf, nil
+}
+
+// ReadBuildLogs returns the last lines of a site's build log
+func ReadBuildLogs(dataDir string, siteID uuid.UUID, lines int) ([]string, error) {
+	data, err := os.ReadFile(BuildLogPath(dataDir, siteID))
+	if err != nil {
+		if os.IsNotExist(err) {
+			return nil, fmt.Errorf("no build log found")
+		}
+		return nil, fmt.Errorf("failed to read build log: %w", err)
+	}
+
+	logLines := strings.Split(strings.TrimRight(string(data), "\n"), "\n")
+	if len(logLines) > lines {
+		logLines = logLines[len(logLines)-lines:]
+	}
+	return logLines, nil
 }
 
 // GetSiteStatus returns the status of a deployed site
@@ -266,12 +553,25 @@ func (c *Client) GetSiteStatus(ctx context.Context, siteID uuid.UUID) (*models.S
 				status = models.SiteStatusRunning
 			}
 
-			return &models.SiteStatusResponse{
+			response := &models.SiteStatusResponse{
 				SiteID:      siteID,
 				Status:      status,
 				ContainerID: cont.ID,
 				IsRunning:   isRunning,
-			}, nil
+			}
+
+			// Restarts and uptime show whether the container is crash-looping; the status
+			// stands without them if the inspect fails
+			if info, err := c.cli.ContainerInspect(ctx, cont.ID); err == nil && info.ContainerJSONBase != nil {
+				response.RestartCount = info.RestartCount
+				if isRunning && info.State != nil {
+					if startedAt, err := time.Parse(time.RFC3339Nano, info.State.StartedAt); err == nil {
+						response.StartedAt = &startedAt
+					}
+				}
+			}
+
+			return response, nil
 		}
 	}
 
@@ -346,38 +646,149 @@ func (c *Client) DeleteSite(ctx context.Context, siteID uuid.UUID) error {
 	return nil
 }
 
-// GetContainerLogs retrieves logs from a container
-func (c *Client) GetContainerLogs(ctx context.Context, siteID uuid.UUID, lines int) ([]string, error) {
+// GetContainerLogs retrieves logs from a container. When since is set only lines logged after it
+// are returned. The returned cursor is the time of the last line, to pass as since on the next call.
+func (c *Client) GetContainerLogs(ctx context.Context, siteID uuid.UUID, lines int, since time.Time) ([]string, time.Time, error) {
 	status, err := c.GetSiteStatus(ctx, siteID)
 	if err != nil {
-		return nil, err
+		return nil, since, err
 	}
 
 	if status.ContainerID == "" {
-		return nil, fmt.Errorf("container not found")
+		return nil, since, fmt.Errorf("container not found")
 	}
 
 	tail := fmt.Sprintf("%d", lines)
 	options := container.LogsOptions{
 		ShowStdout: true,
 		ShowStderr: true,
+		Timestamps: true,
 		Tail:       tail,
 	}
+	if !since.IsZero() {
+		// Docker includes lines logged at exactly since, which were returned last time
+		next := since.Add(time.Nanosecond)
+		options.Since = fmt.Sprintf("%d.%09d", next.Unix(), next.Nanosecond())
+	}
 
 	reader, err := c.cli.ContainerLogs(ctx, status.ContainerID, options)
 	if err != nil {
-		return nil, fmt.Errorf("failed to get logs: %w", err)
+		return nil, since, fmt.Errorf("failed to get logs: %w", err)
 	}
 	defer reader.Close()
 
-	logs, err := io.ReadAll(reader)
+	// Containers run without a TTY, so stdout and stderr are multiplexed
+	var logs bytes.Buffer
+	if _, err := stdcopy.StdCopy(&logs, &logs, reader); err != nil {
+		return nil, since, fmt.Errorf("failed to read logs: %w", err)
+	}
+
+	logLines, cursor := splitTimestamps(strings.Split(strings.TrimRight(logs.String(), "\n"), "\n"))
+	if cursor.IsZero() {
+		cursor = since
+	}
+	return logLines, cursor, nil
+}
+
+// splitTimestamps strips the timestamp Docker adds to each log line and returns the lines with
+// the time of the last one
+func splitTimestamps(lines []string) ([]string, time.Time) {
+	var last time.Time
+	stripped := make([]string, 0, len(lines))
+	for _, line := range lines {
+		if line == "" {
+			continue
+		}
+		stamp, rest, _ := strings.Cut(line, " ")
+		if t, err := time.Parse(time.RFC3339Nano, stamp); err == nil {
+			last = t
+			line = rest
+		}
+		stripped = append(stripped, line)
+	}
+	return stripped, last
+}
+
+// containerStats holds the parts of a Docker stats response used for ContainerMetrics
+type containerStats struct {
+	CPUStats    cpuStats `json:"cpu_stats"`
+	PreCPUStats cpuStats `json:"precpu_stats"`
+	MemoryStats struct {
+		Usage int64            `json:"usage"`
+		Limit int64            `json:"limit"`
+		Stats map[string]int64 `json:"stats"`
+	} `json:"memory_stats"`
+	Networks map[string]struct {
+		RxBytes int64 `json:"rx_bytes"`
+		TxBytes int64 `json:"tx_bytes"`
+	} `json:"networks"`
+}
+
+type cpuStats struct {
+	CPUUsage struct {
+		TotalUsage  uint64   `json:"total_usage"`
+		PercpuUsage []uint64 `json:"percpu_usage"`
+	} `json:"cpu_usage"`
+	SystemUsage uint64 `json:"system_cpu_usage"`
+	OnlineCPUs  uint32 `json:"online_cpus"`
+}
+
+// GetContainerMetrics returns the current resource usage of a site's container
+func (c *Client) GetContainerMetrics(ctx context.Context, siteID uuid.UUID) (*models.ContainerMetrics, error) {
+	status, err := c.GetSiteStatus(ctx, siteID)
 	if err != nil {
-		return nil, fmt.Errorf("failed to read logs: %w", err)
+		return nil, err
 	}
 
-	// Split into lines
-	logLines := strings.Split(string(logs), "\n")
-	return logLines, nil
+	if status.ContainerID == "" {
+		return nil, fmt.Errorf("container not found")
+	}
+
+	// Without streaming Docker takes two samples, so the CPU delta covers about a second
+	stats, err := c.cli.ContainerStats(ctx, status.ContainerID, false)
+	if err != nil {
+		return nil, fmt.Errorf("failed to get stats: %w", err)
+	}
+	defer stats.Body.Close()
+
+	var raw containerStats
+	if err := json.NewDecoder(stats.Body).Decode(&raw); err != nil {
+		return nil, fmt.Errorf("failed to decode stats: %w", err)
+	}
+
+	return metricsFromStats(&raw), nil
+}
+
+// metricsFromStats computes usage the same way `docker stats` does
+func metricsFromStats(raw *containerStats) *models.ContainerMetrics {
+	metrics := &models.ContainerMetrics{
+		MemoryUsage: raw.MemoryStats.Usage,
+		MemoryLimit: raw.MemoryStats.Limit,
+	}
+
+	// Page cache can be reclaimed, so it isn't counted as used (cgroup v1 and v2 name it differently)
+	if cache, ok := raw.MemoryStats.Stats["total_inactive_file"]; ok && cache < metrics.MemoryUsage {
+		metrics.MemoryUsage -= cache
+	} else if cache, ok := raw.MemoryStats.Stats["inactive_file"]; ok && cache < metrics.MemoryUsage {
+		metrics.MemoryUsage -= cache
+	}
+
+	cpuDelta := float64(raw.CPUStats.CPUUsage.TotalUsage) - float64(raw.PreCPUStats.CPUUsage.TotalUsage)
+	systemDelta := float64(raw.CPUStats.SystemUsage) - float64(raw.PreCPUStats.SystemUsage)
+	cpus := float64(raw.CPUStats.OnlineCPUs)
+	if cpus == 0 {
+		cpus = float64(len(raw.CPUStats.CPUUsage.PercpuUsage))
+	}
+	if cpuDelta > 0 && systemDelta > 0 {
+		metrics.CPUPercent = cpuDelta / systemDelta * cpus * 100
+	}
+
+	for _, nw := range raw.Networks {
+		metrics.NetworkRxBytes += nw.RxBytes
+		metrics.NetworkTxBytes += nw.TxBytes
+	}
+
+	return metrics
 }
 
 // GetDockerInfo returns information about Docker
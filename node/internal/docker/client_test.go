@@ -0,0 +1,94 @@
+package docker
+
+import (
+	"testing"
+	"time"
+
+	"github.com/google/uuid"
+
+	"github.com/BlueBeard63/archon-node/internal/models"
+)
+
+func TestImageTag(t *testing.T) {
+	id := uuid.MustParse("0b6f1c2e-6a1d-4d8e-9c3b-2f4e5a6b7c8d")
+
+	tests := []struct {
+		name string
+		want string
+	}{
+		{name: "blog", want: "archon-blog:latest"},
+		{name: "My Blog", want: "archon-my-blog:latest"},
+		{name: "team/app:v2", want: "archon-team-app-v2:latest"},
+		{name: "api.v1", want: "archon-api.v1:latest"},
+		{name: "my_site", want: "archon-my_site:latest"},
+		{name: "  --Shop!! (EU)-- ", want: "archon-shop-eu:latest"},
+		{name: "a..b", want: "archon-a-b:latest"},
+		{name: "!!!", want: "archon-0b6f1c2e-6a1d-4d8e-9c3b-2f4e5a6b7c8d:latest"},
+	}
+
+	for _, tt := range tests {
+		t.Run(tt.name, func(t *testing.T) {
+			if got := imageTag(&models.DeployRequest{ID: id, Name: tt.name}); got != tt.want {
+				t.Errorf("imageTag(%q) = %q, want %q", tt.name, got, tt.want)
+			}
+		})
+	}
+}
+
+func TestMetricsFromStats(t *testing.T) {
+	var raw containerStats
+	raw.CPUStats.CPUUsage.TotalUsage = 300
+	raw.CPUStats.SystemUsage = 2000
+	raw.CPUStats.OnlineCPUs = 2
+	raw.PreCPUStats.CPUUsage.TotalUsage = 100
+	raw.PreCPUStats.SystemUsage = 1000
+	raw.MemoryStats.Usage = 1000
+	raw.MemoryStats.Limit = 4000
+	raw.MemoryStats.Stats = map[string]int64{"inactive_file": 200}
+	raw.Networks = map[string]struct {
+		RxBytes int64 `json:"rx_bytes"`
+		TxBytes int64 `json:"tx_bytes"`
+	}{
+		"eth0": {RxBytes: 10, TxBytes: 20},
+		"eth1": {RxBytes: 1, TxBytes: 2},
+	}
+
+	got := metricsFromStats(&raw)
+	want := models.ContainerMetrics{
+		CPUPercent:     40,
+		MemoryUsage:    800,
+		MemoryLimit:    4000,
+		NetworkRxBytes: 11,
+		NetworkTxBytes: 22,
+	}
+	if *got != want {
+		t.Errorf("metricsFromStats() = %+v, want %+v", *got, want)
+	}
+
+	// No earlier sample, as on the first read of a new container
+	raw.PreCPUStats = cpuStats{}
+	raw.CPUStats.SystemUsage = 0
+	if got := metricsFromStats(&raw); got.CPUPercent != 0 {
+		t.Errorf("CPUPercent without a previous sample = %v, want 0", got.CPUPercent)
+	}
+}
+
+func TestSplitTimestamps(t *testing.T) {
+	lines, last := splitTimestamps([]string{
+		"2024-05-01T10:00:00.000000001Z GET / 200",
+		"2024-05-01T10:00:01.500000000Z GET /about 200",
+		"",
+	})
+
+	if len(lines) != 2 || lines[0] != "GET / 200" || lines[1] != "GET /about 200" {
+		t.Errorf("lines = %q, want timestamps stripped", lines)
+	}
+	want := time.Date(2024, 5, 1, 10, 0, 1, 500000000, time.UTC)
+	if !last.Equal(want) {
+		t.Errorf("last = %v, want %v", last, want)
+	}
+
+	if _, last := splitTimestamps(nil); !last.IsZero() {
+		t.Errorf("last with no lines = %v, want zero", last)
+	}
+}
@@ -2,6 +2,8 @@ package models
 
 import (
 	"fmt"
+	"path"
+	"strings"
 	"time"
 
 	"github.com/google/uuid"
@@ -39,6 +41,25 @@ type DeployRequest struct {
 	SSLKey          string            `json:"ssl_key,omitempty"`   // Base64 encoded key
 	ConfigFiles     []ConfigFile      `json:"config_files"`
 	TraefikLabels   map[string]string `json:"traefik_labels,omitempty"`
+	CPULimit        float64           `json:"cpu_limit,omitempty"`       // Max CPU cores for the container (0 = unlimited)
+	MemoryLimitMB   int64             `json:"memory_limit_mb,omitempty"` // Max container memory in MB (0 = unlimited)
+	Volumes         []VolumeMount     `json:"volumes,omitempty"`         // Host paths or named volumes mounted into the container
+	Strategy        DeployStrategy    `json:"strategy,omitempty"`        // How the running container is replaced (defaults to recreate)
+	HealthPath      string            `json:"health_path,omitempty"`     // Probed on a blue/green deploy's new container before traffic moves to it
+	HealthStatus    int               `json:"health_status,omitempty"`   // Status the health path should return (0 = 200)
+}
+
+// DeployStrategy is how a deploy replaces the container already running for a site
+type DeployStrategy string
+
+const (
+	DeployStrategyRecreate  DeployStrategy = "recreate"   // Stop the old container, then start the new one
+	DeployStrategyBlueGreen DeployStrategy = "blue_green" // Start the new container beside the old one and move traffic once it is healthy
+)
+
+// IsBlueGreen returns true if the old container should keep serving until the new one is healthy
+func (r *DeployRequest) IsBlueGreen() bool {
+	return r.Strategy == DeployStrategyBlueGreen && !r.IsCompose()
 }
 
 // IsCompose returns true if this is a compose deployment
@@ -54,14 +75,25 @@ func (r *DeployRequest) Validate() error {
 	if len(r.DomainMappings) == 0 {
 		return fmt.Errorf("at least one domain mapping is required")
 	}
+	if r.CPULimit < 0 {
+		return fmt.Errorf("cpu limit cannot be negative")
+	}
+	if r.MemoryLimitMB < 0 {
+		return fmt.Errorf("memory limit cannot be negative")
+	}
+	for _, v := range r.Volumes {
+		if err := v.Validate(); err != nil {
+			return err
+		}
+	}
 
 	if r.IsCompose() {
 		if r.ComposeContent == "" {
 			return fmt.Errorf("compose content is required for compose deployments")
 		}
 	} else {
-		if r.Docker.Image == "" {
-			return fmt.Errorf("docker image is required for container deployments")
+		if err := r.Docker.ValidateSource(); err != nil {
+			return err
 		}
 	}
 	return nil
@@ -77,11 +109,51 @@ type DomainMapping struct {
 type Docker struct {
 	Credentials DockerCredentials `json:"credentials"`
 	Image       string            `json:"image"`
+	Build       *BuildSpec        `json:"build,omitempty"` // Build the image on the node instead of pulling Image
+}
+
+// BuildSpec describes how to build a site image from source
+type BuildSpec struct {
+	Context    string `json:"context"`              // Path on the node or a git repository URL
+	Dockerfile string `json:"dockerfile,omitempty"` // Relative to the context (defaults to "Dockerfile")
+}
+
+// IsRemote returns true if the build context is a git repository URL
+func (b *BuildSpec) IsRemote() bool {
+	for _, prefix := range []string{"git://", "git@", "github.com/", "http://", "https://"} {
+		if strings.HasPrefix(b.Context, prefix) {
+			return true
+		}
+	}
+	return false
+}
+
+// ValidateSource checks that exactly one of image or build is set
+func (d *Docker) ValidateSource() error {
+	hasImage := d.Image != ""
+	hasBuild := d.Build != nil && d.Build.Context != ""
+
+	if hasImage && hasBuild {
+		return fmt.Errorf("docker image and build cannot both be set")
+	}
+	if !hasImage && !hasBuild {
+		return fmt.Errorf("docker image or build context is required for container deployments")
+	}
+	return nil
+}
+
+// Source returns a human-readable description of where the image comes from
+func (d *Docker) Source() string {
+	if d.Build != nil && d.Build.Context != "" {
+		return "build from " + d.Build.Context
+	}
+	return d.Image
 }
 
 type DockerCredentials struct {
 	Username string `json:"username"`
 	Password string `json:"password"`
+	Registry string `json:"registry,omitempty"` // Registry server address (e.g., "ghcr.io"); empty means Docker Hub
 }
 
 type ConfigFile struct {
@@ -90,6 +162,33 @@ type ConfigFile struct {
 	ContainerPath string `json:"container_path"`
 }
 
+// VolumeMount binds a host path or named volume into a site's container
+type VolumeMount struct {
+	HostPath      string `json:"host_path"`           // Absolute host path or Docker volume name
+	ContainerPath string `json:"container_path"`      // Absolute path inside the container
+	ReadOnly      bool   `json:"read_only,omitempty"` // Mount read-only
+}
+
+// Validate checks that the mount has a source and an absolute container path
+func (v *VolumeMount) Validate() error {
+	if v.HostPath == "" {
+		return fmt.Errorf("volume host path is required")
+	}
+	if !path.IsAbs(v.ContainerPath) {
+		return fmt.Errorf("volume container path must be absolute: %q", v.ContainerPath)
+	}
+	return nil
+}
+
+// Bind returns the mount in Docker bind notation ("host:container[:ro]")
+func (v *VolumeMount) Bind() string {
+	bind := v.HostPath + ":" + v.ContainerPath
+	if v.ReadOnly {
+		bind += ":ro"
+	}
+	return bind
+}
+
 // DeployResponse is the response from deploying a site
 type DeployResponse struct {
 	SiteID      uuid.UUID  `json:"site_id"`
@@ -100,11 +199,13 @@ type DeployResponse struct {
 
 // SiteStatusResponse returns the current status of a site
 type SiteStatusResponse struct {
-	SiteID      uuid.UUID  `json:"site_id"`
-	Status      SiteStatus `json:"status"`
-	ContainerID string     `json:"container_id,omitempty"`
-	IsRunning   bool       `json:"is_running"`
-	Message     string     `json:"message,omitempty"`
+	SiteID       uuid.UUID  `json:"site_id"`
+	Status       SiteStatus `json:"status"`
+	ContainerID  string     `json:"container_id,omitempty"`
+	IsRunning    bool       `json:"is_running"`
+	Message      string     `json:"message,omitempty"`
+	RestartCount int        `json:"restart_count"`        // Times Docker has restarted the container
+	StartedAt    *time.Time `json:"started_at,omitempty"` // When the container last started, while it is running
 }
 
 // HealthResponse returns the health status of the node
@@ -46,9 +46,17 @@ func (s *DeploymentStage) Execute(ctx context.Context, state *pipeline.Deploymen
 func (s *DeploymentStage) deployContainer(ctx context.Context, state *pipeline.DeploymentState) error {
 	req := state.Request
 
-	log.Printf("[DEPLOY] Deploying container: image=%s", req.Docker.Image)
-
-	resp, err := s.dockerClient.DeploySite(ctx, req, state.DataDir)
+	log.Printf("[DEPLOY] Deploying container: source=%s", req.Docker.Source())
+
+	var resp *models.DeployResponse
+	var err error
+	if req.IsBlueGreen() {
+		resp, err = s.dockerClient.DeploySiteBlueGreen(ctx, req, state.DataDir, func(message string) {
+			log.Printf("[DEPLOY] %s", message)
+		})
+	} else {
+		resp, err = s.dockerClient.DeploySite(ctx, req, state.DataDir)
+	}
 	if err != nil {
 		return fmt.Errorf("failed to deploy container: %w", err)
 	}
@@ -88,7 +96,7 @@ func (s *DeploymentStage) deployCompose(ctx context.Context, state *pipeline.Dep
 	return nil
 }
 
-// Rollback removes the deployed container or compose stack
+// Rollback removes the deployed container or compose stack, except after a blue/green deploy
 func (s *DeploymentStage) Rollback(ctx context.Context, state *pipeline.DeploymentState) error {
 	req := state.Request
 
@@ -100,6 +108,13 @@ func (s *DeploymentStage) Rollback(ctx context.Context, state *pipeline.Deployme
 		return nil
 	}
 
+	// A blue/green deploy has already removed the old container, so removing the new one
+	// would leave the site with none
+	if req.IsBlueGreen() {
+		log.Printf("[ROLLBACK] Keeping blue/green container: %s", state.ContainerID)
+		return nil
+	}
+
 	if state.ContainerID != "" {
 		log.Printf("[ROLLBACK] Removing container: %s", state.ContainerID)
 		if err := s.dockerClient.DeleteSite(ctx, req.ID); err != nil {
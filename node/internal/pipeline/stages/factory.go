@@ -19,7 +19,7 @@ type Dependencies struct {
 // NewDeploymentPipeline creates the standard deployment pipeline
 func NewDeploymentPipeline(deps *Dependencies) *pipeline.Pipeline {
 	return pipeline.NewPipeline(
-		NewValidationStage(),
+		NewValidationStage(deps.ProxyManager),
 		NewPortCheckStage(deps.DockerClient),
 		NewSSLStage(deps.ProxyManager, deps.SSLManager),
 		NewDeploymentStage(deps.DockerClient, deps.ComposeExecutor),
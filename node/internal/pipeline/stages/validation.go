@@ -4,18 +4,22 @@ import (
 	"context"
 	"fmt"
 
+	"github.com/BlueBeard63/archon-node/internal/models"
 	"github.com/BlueBeard63/archon-node/internal/pipeline"
+	"github.com/BlueBeard63/archon-node/internal/proxy"
 )
 
 // ValidationStage validates the deployment request
 type ValidationStage struct {
 	pipeline.BaseStage
+	proxyManager proxy.ProxyManager
 }
 
 // NewValidationStage creates a new validation stage
-func NewValidationStage() *ValidationStage {
+func NewValidationStage(proxyManager proxy.ProxyManager) *ValidationStage {
 	return &ValidationStage{
-		BaseStage: pipeline.NewBaseStage("validation"),
+		BaseStage:    pipeline.NewBaseStage("validation"),
+		proxyManager: proxyManager,
 	}
 }
 
@@ -23,25 +27,20 @@ func NewValidationStage() *ValidationStage {
 func (s *ValidationStage) Execute(ctx context.Context, state *pipeline.DeploymentState) error {
 	req := state.Request
 
-	// Basic validation
-	if req.Name == "" {
-		return fmt.Errorf("site name is required")
+	if err := req.Validate(); err != nil {
+		return err
 	}
 
-	if len(req.DomainMappings) == 0 {
-		return fmt.Errorf("at least one domain mapping is required")
-	}
+	return ValidateStrategy(req, s.proxyManager)
+}
 
-	// Type-specific validation
-	if req.IsCompose() {
-		if req.ComposeContent == "" {
-			return fmt.Errorf("compose content is required for compose deployments")
-		}
-	} else {
-		if req.Docker.Image == "" {
-			return fmt.Errorf("docker image is required for container deployments")
+// ValidateStrategy checks the node's proxy can route to the request's deployment strategy
+func ValidateStrategy(req *models.DeployRequest, proxyManager proxy.ProxyManager) error {
+	// Blue/green containers publish no host ports, so only Traefik can route to them
+	if req.IsBlueGreen() {
+		if _, ok := proxyManager.(*proxy.TraefikManager); !ok {
+			return fmt.Errorf("blue/green deploys need Traefik as the node's proxy")
 		}
 	}
-
 	return nil
 }
@@ -0,0 +1,40 @@
+package stages
+
+import (
+	"context"
+	"testing"
+
+	"github.com/BlueBeard63/archon-node/internal/models"
+	"github.com/BlueBeard63/archon-node/internal/pipeline"
+)
+
+func TestValidationStage(t *testing.T) {
+	tests := []struct {
+		name    string
+		modify  func(r *models.DeployRequest)
+		wantErr bool
+	}{
+		{name: "valid request", modify: func(r *models.DeployRequest) {}},
+		{name: "relative container path", modify: func(r *models.DeployRequest) { r.Volumes[0].ContainerPath = "data" }, wantErr: true},
+		{name: "empty host path", modify: func(r *models.DeployRequest) { r.Volumes[0].HostPath = "" }, wantErr: true},
+		{name: "negative cpu limit", modify: func(r *models.DeployRequest) { r.CPULimit = -1 }, wantErr: true},
+		{name: "negative memory limit", modify: func(r *models.DeployRequest) { r.MemoryLimitMB = -256 }, wantErr: true},
+	}
+
+	for _, tt := range tests {
+		t.Run(tt.name, func(t *testing.T) {
+			req := &models.DeployRequest{
+				Name:           "web",
+				Docker:         models.Docker{Image: "nginx:latest"},
+				DomainMappings: []models.DomainMapping{{Domain: "example.com", Port: 80}},
+				Volumes:        []models.VolumeMount{{HostPath: "/srv/web", ContainerPath: "/data"}},
+			}
+			tt.modify(req)
+
+			err := NewValidationStage(nil).Execute(context.Background(), &pipeline.DeploymentState{Request: req})
+			if (err != nil) != tt.wantErr {
+				t.Errorf("Execute() error = %v, wantErr %v", err, tt.wantErr)
+			}
+		})
+	}
+}